@@ -0,0 +1,129 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AbstractState, AnalysisResult},
+    PointwiseState,
+};
+use prusti_rustc_interface::{data_structures::fx::FxHashMap, middle::mir, span::def_id::DefId};
+use std::{collections::BTreeSet, iter::FromIterator};
+
+/// Trait to be used to define a backward abstract-interpretation-based static
+/// analysis of a MIR body, such as a liveness analysis. The abstract states are
+/// propagated against the control flow, from the successors of a block to its
+/// terminator and from the end of a block to its beginning.
+///
+/// The resulting `PointwiseState` has the same shape as the one of a forward
+/// analysis: it contains the state before every statement and terminator, and
+/// the state on every outgoing CFG edge of a block, which is the state at the
+/// beginning of the successor block.
+pub trait BackwardFixpointEngine<'mir, 'tcx: 'mir> {
+    type State: AbstractState;
+
+    /// Return the DefId of the MIR body to be analyzed.
+    fn def_id(&self) -> DefId;
+
+    /// Return the MIR body to be analyzed.
+    fn body(&self) -> &'mir mir::Body<'tcx>;
+
+    /// Creates a new abstract state which corresponds to the bottom element in the lattice
+    fn new_bottom(&self) -> Self::State;
+
+    /// Determines if the number of times a block was traversed by the analyzer given in `counter`
+    /// is large enough to widen the state
+    fn need_to_widen(counter: u32) -> bool;
+
+    /// Modify the state after the statement at `location` such that it
+    /// becomes the state before the statement.
+    ///
+    /// The statement can be extracted using
+    /// `self.mir[location.block].statements[location.statement_index]`.
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::State,
+        location: mir::Location,
+    ) -> AnalysisResult<()>;
+
+    /// Compute the state before the terminator at `location` from the states
+    /// at the beginning of its successor blocks. The map is empty if the
+    /// terminator has no successors, e.g. for `return`.
+    ///
+    /// The terminator can be extracted using `self.mir[location.block].terminator()`.
+    fn apply_terminator_effect(
+        &self,
+        successor_states: &FxHashMap<mir::BasicBlock, Self::State>,
+        location: mir::Location,
+    ) -> AnalysisResult<Self::State>;
+
+    /// Produces an abstract state for every program point in `mir` by iterating over all statements
+    /// in reverse program order until a fixed point is reached (i.e. by abstract interpretation).
+    fn run_bwd_analysis(&self) -> AnalysisResult<PointwiseState<'mir, 'tcx, Self::State>> {
+        let mir = self.body();
+        let mut p_state = PointwiseState::new(mir);
+        let mut work_set: BTreeSet<mir::BasicBlock> =
+            BTreeSet::from_iter(mir.basic_blocks.indices());
+
+        let mut counters: FxHashMap<mir::BasicBlock, u32> =
+            FxHashMap::with_capacity_and_hasher(mir.basic_blocks.len(), Default::default());
+
+        // extract the bb with the maximal index, since the blocks near the
+        // exit of a body tend to have larger indices
+        while let Some(&bb) = work_set.iter().next_back() {
+            work_set.remove(&bb);
+            let block_start = mir::Location {
+                block: bb,
+                statement_index: 0,
+            };
+            let prev_state = p_state.lookup_before(block_start).cloned();
+
+            let mut successor_states: FxHashMap<mir::BasicBlock, Self::State> =
+                FxHashMap::default();
+            for succ_bb in mir[bb].terminator().successors() {
+                let location = mir::Location {
+                    block: succ_bb,
+                    statement_index: 0,
+                };
+                // if no state is present: assume bottom
+                let state = p_state
+                    .lookup_before(location)
+                    .cloned()
+                    .unwrap_or_else(|| self.new_bottom());
+                successor_states.insert(succ_bb, state);
+            }
+
+            let location = mir.terminator_loc(bb);
+            let mut current_state = self.apply_terminator_effect(&successor_states, location)?;
+            p_state.set_before(location, current_state.clone());
+            *p_state.lookup_mut_after_block(bb) = successor_states;
+
+            for statement_index in (0..mir[bb].statements.len()).rev() {
+                let location = mir::Location {
+                    block: bb,
+                    statement_index,
+                };
+                self.apply_statement_effect(&mut current_state, location)?;
+                p_state.set_before(location, current_state.clone());
+            }
+
+            // widen if needed
+            let counter = counters.entry(bb).or_insert(0);
+            *counter += 1;
+            if Self::need_to_widen(*counter) {
+                if let Some(prev_state) = &prev_state {
+                    current_state.widen(prev_state);
+                    p_state.set_before(block_start, current_state.clone());
+                }
+            }
+
+            // the state at the beginning of the block has changed => add the
+            // predecessors to the worklist
+            if prev_state.as_ref() != Some(&current_state) {
+                work_set.extend(mir.basic_blocks.predecessors()[bb].iter().copied());
+            }
+        }
+        AnalysisResult::Ok(p_state)
+    }
+}
@@ -0,0 +1,224 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AbstractState, AnalysisResult},
+    AnalysisError,
+};
+use prusti_rustc_interface::{
+    data_structures::fx::{FxHashMap, FxHashSet},
+    middle::{mir, ty},
+    span::def_id::DefId,
+};
+use std::collections::VecDeque;
+
+/// The call graph of a set of MIR bodies, e.g. of all the functions of a crate.
+///
+/// Only calls of statically known functions are recorded. Calls of trait
+/// methods are recorded with the implementation that they resolve to, and are
+/// not recorded if the implementation depends on the type parameters of the
+/// caller or is not a function item, e.g. for calls through `dyn` traits or
+/// function pointers.
+pub struct CallGraph<'mir, 'tcx: 'mir> {
+    /// The functions in the order in which they were given.
+    functions: Vec<DefId>,
+    bodies: FxHashMap<DefId, &'mir mir::Body<'tcx>>,
+    /// The call sites of every function, with the called item.
+    call_sites: FxHashMap<DefId, Vec<(mir::Location, DefId)>>,
+    /// The functions of the graph that call a given item.
+    callers: FxHashMap<DefId, Vec<DefId>>,
+}
+
+impl<'mir, 'tcx: 'mir> CallGraph<'mir, 'tcx> {
+    pub fn new(
+        tcx: ty::TyCtxt<'tcx>,
+        bodies: impl IntoIterator<Item = (DefId, &'mir mir::Body<'tcx>)>,
+    ) -> Self {
+        let mut call_graph = CallGraph {
+            functions: Vec::new(),
+            bodies: FxHashMap::default(),
+            call_sites: FxHashMap::default(),
+            callers: FxHashMap::default(),
+        };
+        for (def_id, body) in bodies {
+            let mut call_sites = Vec::new();
+            for (bb, bb_data) in body.basic_blocks.iter_enumerated() {
+                if let Some(callee) = called_function(tcx, def_id, bb_data.terminator()) {
+                    call_sites.push((body.terminator_loc(bb), callee));
+                    let callers = call_graph.callers.entry(callee).or_default();
+                    if !callers.contains(&def_id) {
+                        callers.push(def_id);
+                    }
+                }
+            }
+            call_graph.functions.push(def_id);
+            call_graph.bodies.insert(def_id, body);
+            call_graph.call_sites.insert(def_id, call_sites);
+        }
+        call_graph
+    }
+
+    /// The functions of the graph, i.e. the ones with a body.
+    pub fn functions(&self) -> &[DefId] {
+        &self.functions
+    }
+
+    pub fn body(&self, def_id: DefId) -> Option<&'mir mir::Body<'tcx>> {
+        self.bodies.get(&def_id).copied()
+    }
+
+    /// The call sites in the body of `def_id`, together with the called items.
+    pub fn call_sites(&self, def_id: DefId) -> &[(mir::Location, DefId)] {
+        self.call_sites.get(&def_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The function called at `location` in the body of `def_id`, if it is
+    /// statically known.
+    pub fn callee(&self, def_id: DefId, location: mir::Location) -> Option<DefId> {
+        self.call_sites(def_id)
+            .iter()
+            .find(|(call_location, _)| *call_location == location)
+            .map(|&(_, callee)| callee)
+    }
+
+    /// The functions of the graph that call `def_id`.
+    pub fn callers(&self, def_id: DefId) -> &[DefId] {
+        self.callers.get(&def_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The functions of the graph in post order, i.e. callees before their
+    /// callers, except in the case of recursion.
+    fn post_order(&self) -> Vec<DefId> {
+        let mut visited: FxHashSet<DefId> = FxHashSet::default();
+        let mut post_order = Vec::with_capacity(self.functions.len());
+        for &root in &self.functions {
+            if !visited.insert(root) {
+                continue;
+            }
+            // Each entry holds a function and the index of its next call site.
+            let mut stack = vec![(root, 0)];
+            while let Some((def_id, next)) = stack.pop() {
+                match self.call_sites(def_id).get(next) {
+                    Some(&(_, callee)) => {
+                        stack.push((def_id, next + 1));
+                        if self.bodies.contains_key(&callee) && visited.insert(callee) {
+                            stack.push((callee, 0));
+                        }
+                    }
+                    None => post_order.push(def_id),
+                }
+            }
+        }
+        post_order
+    }
+}
+
+/// Returns the function that is statically called by `terminator` in the body
+/// of `caller`, if any. Calls of trait methods are resolved to the called
+/// implementation, and are considered unknown if it cannot be determined.
+pub fn called_function<'tcx>(
+    tcx: ty::TyCtxt<'tcx>,
+    caller: DefId,
+    terminator: &mir::Terminator<'tcx>,
+) -> Option<DefId> {
+    let mir::TerminatorKind::Call { ref func, .. } = terminator.kind else {
+        return None;
+    };
+    let (def_id, substs) = func.const_fn_def()?;
+    if tcx.trait_of_item(def_id).is_none() {
+        return Some(def_id);
+    }
+    // trait resolution does not depend on lifetimes
+    let substs = tcx.erase_regions(substs);
+    let param_env = tcx.param_env(caller);
+    match tcx.resolve_instance(param_env.and((def_id, substs))) {
+        Ok(Some(instance)) if matches!(instance.def, ty::InstanceDef::Item(_)) => {
+            Some(instance.def_id())
+        }
+        _ => None,
+    }
+}
+
+/// Trait to be used to define a context-insensitive interprocedural analysis,
+/// which computes a summary for every function of a call graph.
+///
+/// The summary of a function is computed from its body, typically by an
+/// intraprocedural `FixpointEngine` that applies the summaries of the callees
+/// at the call sites, binding the arguments of the call to the parameters of
+/// the callee and the result of the callee to the destination of the call.
+/// Called functions without a body in the call graph have no summary and must
+/// be handled conservatively.
+pub trait InterproceduralAnalysis<'mir, 'tcx: 'mir> {
+    type Summary: AbstractState;
+
+    /// Return the call graph of the functions to be analyzed.
+    fn call_graph(&self) -> &CallGraph<'mir, 'tcx>;
+
+    /// Creates the summary of `def_id` before its body is analyzed, which
+    /// corresponds to the bottom element of the lattice.
+    fn new_bottom_summary(&self, def_id: DefId) -> Self::Summary;
+
+    /// Determines if the number of times the summary of a function was computed, given in
+    /// `counter`, is large enough to widen the summary.
+    fn need_to_widen(counter: u32) -> bool;
+
+    /// Computes the summary of `def_id` from its body, assuming the current
+    /// `summaries` of the functions of the call graph.
+    fn compute_summary(
+        &self,
+        def_id: DefId,
+        summaries: &FxHashMap<DefId, Self::Summary>,
+    ) -> AnalysisResult<Self::Summary>;
+
+    /// Produces a summary for every function of the call graph by recomputing
+    /// the summaries of the callers of a function whose summary changed until a
+    /// fixed point is reached.
+    ///
+    /// In case of an error, the function whose body could not be analyzed is
+    /// returned together with the error.
+    fn run_interprocedural_analysis(
+        &self,
+    ) -> Result<FxHashMap<DefId, Self::Summary>, (DefId, AnalysisError)> {
+        let call_graph = self.call_graph();
+        let mut summaries: FxHashMap<DefId, Self::Summary> = call_graph
+            .functions()
+            .iter()
+            .map(|&def_id| (def_id, self.new_bottom_summary(def_id)))
+            .collect();
+        let mut counters: FxHashMap<DefId, u32> = FxHashMap::default();
+
+        // analyze callees first to reduce the number of iterations
+        let mut work_list: VecDeque<DefId> = call_graph.post_order().into();
+        let mut in_work_list: FxHashSet<DefId> = work_list.iter().copied().collect();
+
+        while let Some(def_id) = work_list.pop_front() {
+            in_work_list.remove(&def_id);
+
+            let mut summary = self
+                .compute_summary(def_id, &summaries)
+                .map_err(|error| (def_id, error))?;
+            let previous = &summaries[&def_id];
+            // summaries only grow, so that the iteration terminates
+            summary.join(previous);
+
+            let counter = counters.entry(def_id).or_insert(0);
+            *counter += 1;
+            if Self::need_to_widen(*counter) {
+                summary.widen(previous);
+            }
+
+            if &summary != previous {
+                summaries.insert(def_id, summary);
+                for &caller in call_graph.callers(def_id) {
+                    if in_work_list.insert(caller) {
+                        work_list.push_back(caller);
+                    }
+                }
+            }
+        }
+        Ok(summaries)
+    }
+}
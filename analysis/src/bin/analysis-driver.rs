@@ -5,11 +5,15 @@
 // https://github.com/rust-lang/rust/blob/master/src/test/run-make-fulldeps/obtain-borrowck/driver.rs
 
 use analysis::{
-    abstract_interpretation::FixpointEngine,
+    abstract_interpretation::{
+        BackwardFixpointEngine, CallGraph, FixpointEngine, InterproceduralAnalysis,
+    },
     domains::{
-        DefinitelyAccessibleAnalysis, DefinitelyInitializedAnalysis, FramingAnalysis,
-        MaybeBorrowedAnalysis, ReachingDefsAnalysis,
+        compute_may_alias, compute_reaching_defs, DefinitelyAccessibleAnalysis,
+        DefinitelyInitializedAnalysis, FramingAnalysis, MaybeBorrowedAnalysis, MaybeLiveAnalysis,
+        MaybeReturnedArgsAnalysis,
     },
+    AnalysisError, PointwiseState,
 };
 use prusti_rustc_interface::{
     ast::ast,
@@ -19,18 +23,46 @@ use prusti_rustc_interface::{
     hir::def_id::{DefId, LocalDefId},
     interface::{interface, Config, Queries},
     middle::{
-        ty,
+        mir, ty,
         ty::query::{query_values::mir_borrowck, ExternProviders, Providers},
     },
     polonius_engine::{Algorithm, Output},
     session::{Attribute, Session},
 };
+use serde::Serialize;
 use std::{cell::RefCell, rc::Rc};
 
 struct OurCompilerCalls {
     args: Vec<String>,
 }
 
+impl OurCompilerCalls {
+    /// Get the value of the argument `<name>=<value>`, if present.
+    fn get_arg(&self, name: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .filter_map(|arg| arg.strip_prefix(name)?.strip_prefix('='))
+            .next()
+    }
+}
+
+/// Print the result of an analysis, or record it in `json_dump` to print all
+/// the results as a single JSON object at the end.
+fn report_result<S: Serialize>(
+    function_name: String,
+    result: Result<PointwiseState<S>, AnalysisError>,
+    body: &mir::Body,
+    json_dump: Option<&mut serde_json::Map<String, serde_json::Value>>,
+) {
+    match (result, json_dump) {
+        (Ok(state), Some(json_dump)) => {
+            json_dump.insert(function_name, state.to_program_points_json());
+        }
+        (Ok(state), None) => println!("{}", serde_json::to_string_pretty(&state).unwrap()),
+        (Err(e), _) => eprintln!("{}", e.to_pretty_str(body)),
+    }
+}
+
 fn get_attributes(
     tcx: ty::TyCtxt<'_>,
     def_id: DefId,
@@ -151,40 +183,98 @@ impl prusti_rustc_interface::driver::Callbacks for OurCompilerCalls {
         let session = compiler.session();
         session.abort_if_errors();
 
-        let abstract_domain: &str = self
-            .args
-            .iter()
-            .filter(|a| a.starts_with("--analysis"))
-            .flat_map(|a| a.rsplit('='))
-            .next()
+        let abstract_domain = self
+            .get_arg("--analysis")
             .expect("Please add --analysis=<DOMAIN>");
+        let function_name = self.get_arg("--analysis-function");
+        let mut json_dump = match self.get_arg("--dump") {
+            None => None,
+            Some("json") => Some(serde_json::Map::new()),
+            Some(format) => panic!("Unknown dump format: {format}"),
+        };
 
-        println!(
-            "Analyzing file {} using {}...",
-            compiler.session().io.input.source_name().prefer_local(),
-            abstract_domain
-        );
+        if json_dump.is_none() {
+            println!(
+                "Analyzing file {} using {}...",
+                compiler.session().io.input.source_name().prefer_local(),
+                abstract_domain
+            );
+        }
 
         queries.global_ctxt().unwrap().enter(|tcx| {
-            // collect all functions with attribute #[analyzer::run]
+            // collect the function with the given name or, by default, all
+            // functions with attribute #[analyzer::run]
             let mut local_def_ids: Vec<_> = tcx
                 .mir_keys(())
                 .iter()
-                .filter(|id| get_attribute(tcx, id.to_def_id(), "analyzer", "run").is_some())
+                .filter(|id| match function_name {
+                    Some(name) => tcx
+                        .opt_item_name(id.to_def_id())
+                        .map_or(false, |item_name| item_name.as_str() == name),
+                    None => get_attribute(tcx, id.to_def_id(), "analyzer", "run").is_some(),
+                })
                 .collect();
+            if let Some(name) = function_name {
+                assert!(
+                    !local_def_ids.is_empty(),
+                    "There is no function named {name}"
+                );
+            }
 
-            // sort according to argument span to ensure deterministic output
+            // sort according to span to ensure deterministic output
             local_def_ids.sort_unstable_by_key(|id| {
                 get_attribute(tcx, id.to_def_id(), "analyzer", "run")
-                    .unwrap()
-                    .span
+                    .map_or_else(|| tcx.def_span(id.to_def_id()), |attr| attr.span)
             });
 
-            for &local_def_id in local_def_ids {
-                println!(
-                    "Result for function {}():",
-                    tcx.item_name(local_def_id.to_def_id())
+            if abstract_domain == "MaybeReturnedArgsAnalysis" {
+                // The summaries depend on the bodies of all the called
+                // functions, so all of them are analyzed.
+                let bodies_with_facts: Vec<_> = tcx
+                    .mir_keys(())
+                    .iter()
+                    .filter(|id| tcx.def_kind(id.to_def_id()).is_fn_like())
+                    .map(|&id| {
+                        // SAFETY: This is safe because we are feeding in the same `tcx`
+                        // that was used to store the data.
+                        let body_with_facts =
+                            unsafe { self::mir_storage::retrieve_mir_body(tcx, id) };
+                        (id.to_def_id(), body_with_facts)
+                    })
+                    .collect();
+                let call_graph = CallGraph::new(
+                    tcx,
+                    bodies_with_facts
+                        .iter()
+                        .map(|(def_id, body_with_facts)| (*def_id, &body_with_facts.body)),
                 );
+                let summaries = match MaybeReturnedArgsAnalysis::new(&call_graph)
+                    .run_interprocedural_analysis()
+                {
+                    Ok(summaries) => summaries,
+                    Err((def_id, error)) => {
+                        eprintln!("{}", error.to_pretty_str(call_graph.body(def_id).unwrap()));
+                        return;
+                    }
+                };
+                for &local_def_id in local_def_ids {
+                    let item_name = tcx.item_name(local_def_id.to_def_id()).to_string();
+                    let summary = &summaries[&local_def_id.to_def_id()];
+                    if let Some(json_dump) = json_dump.as_mut() {
+                        json_dump.insert(item_name, serde_json::to_value(summary).unwrap());
+                    } else {
+                        println!("Result for function {item_name}():");
+                        println!("{}", serde_json::to_string_pretty(summary).unwrap());
+                    }
+                }
+                return;
+            }
+
+            for &local_def_id in local_def_ids {
+                let item_name = tcx.item_name(local_def_id.to_def_id()).to_string();
+                if json_dump.is_none() {
+                    println!("Result for function {item_name}():");
+                }
 
                 // SAFETY: This is safe because we are feeding in the same `tcx`
                 // that was used to store the data.
@@ -197,80 +287,72 @@ impl prusti_rustc_interface::driver::Callbacks for OurCompilerCalls {
                 ));
                 assert!(!body_with_facts.input_facts.cfg_edge.is_empty());
                 let body = &body_with_facts.body;
+                let def_id = local_def_id.to_def_id();
+                let json_dump = json_dump.as_mut();
 
                 match abstract_domain {
-                    "ReachingDefsAnalysis" => {
-                        let result = ReachingDefsAnalysis::new(tcx, local_def_id.to_def_id(), body)
-                            .run_fwd_analysis();
-                        match result {
-                            Ok(state) => {
-                                println!("{}", serde_json::to_string_pretty(&state).unwrap())
-                            }
-                            Err(e) => eprintln!("{}", e.to_pretty_str(body)),
-                        }
-                    }
-                    "DefinitelyInitializedAnalysis" => {
-                        let result =
-                            DefinitelyInitializedAnalysis::new(tcx, local_def_id.to_def_id(), body)
-                                .run_fwd_analysis();
-                        match result {
-                            Ok(state) => {
-                                println!("{}", serde_json::to_string_pretty(&state).unwrap())
-                            }
-                            Err(e) => eprintln!("{}", e.to_pretty_str(body)),
-                        }
-                    }
-                    "RelaxedDefinitelyInitializedAnalysis" => {
-                        let result = DefinitelyInitializedAnalysis::new_relaxed(
-                            tcx,
-                            local_def_id.to_def_id(),
-                            body,
-                        )
-                        .run_fwd_analysis();
-                        match result {
-                            Ok(state) => {
-                                println!("{}", serde_json::to_string_pretty(&state).unwrap())
-                            }
-                            Err(e) => eprintln!("{}", e.to_pretty_str(body)),
-                        }
-                    }
-                    "MaybeBorrowedAnalysis" => {
-                        let analyzer = MaybeBorrowedAnalysis::new(tcx, &body_with_facts);
-                        match analyzer.run_analysis() {
-                            Ok(state) => {
-                                println!("{}", serde_json::to_string_pretty(&state).unwrap())
-                            }
-                            Err(e) => eprintln!("{}", e.to_pretty_str(body)),
-                        }
-                    }
-                    "DefinitelyAccessibleAnalysis" => {
-                        let analyzer = DefinitelyAccessibleAnalysis::new(
-                            tcx,
-                            local_def_id.to_def_id(),
-                            &body_with_facts,
-                        );
-                        match analyzer.run_analysis() {
-                            Ok(state) => {
-                                println!("{}", serde_json::to_string_pretty(&state).unwrap());
-                            }
-                            Err(e) => eprintln!("{}", e.to_pretty_str(body)),
-                        }
-                    }
-                    "FramingAnalysis" => {
-                        let analyzer =
-                            FramingAnalysis::new(tcx, local_def_id.to_def_id(), &body_with_facts);
-                        match analyzer.run_analysis() {
-                            Ok(state) => {
-                                println!("{}", serde_json::to_string_pretty(&state).unwrap());
-                            }
-                            Err(e) => eprintln!("{}", e.to_pretty_str(body)),
-                        }
-                    }
+                    "ReachingDefsAnalysis" | "reaching_definitions" => report_result(
+                        item_name,
+                        compute_reaching_defs(tcx, def_id, body),
+                        body,
+                        json_dump,
+                    ),
+                    "DefinitelyInitializedAnalysis" => report_result(
+                        item_name,
+                        DefinitelyInitializedAnalysis::new(tcx, def_id, body).run_fwd_analysis(),
+                        body,
+                        json_dump,
+                    ),
+                    "RelaxedDefinitelyInitializedAnalysis" => report_result(
+                        item_name,
+                        DefinitelyInitializedAnalysis::new_relaxed(tcx, def_id, body)
+                            .run_fwd_analysis(),
+                        body,
+                        json_dump,
+                    ),
+                    "MayAliasAnalysis" => report_result(
+                        item_name,
+                        compute_may_alias(tcx, def_id, body),
+                        body,
+                        json_dump,
+                    ),
+                    "MaybeBorrowedAnalysis" => report_result(
+                        item_name,
+                        MaybeBorrowedAnalysis::new(tcx, &body_with_facts).run_analysis(),
+                        body,
+                        json_dump,
+                    ),
+                    "MaybeLiveAnalysis" | "liveness" => report_result(
+                        item_name,
+                        MaybeLiveAnalysis::new(def_id, body).run_bwd_analysis(),
+                        body,
+                        json_dump,
+                    ),
+                    "DefinitelyAccessibleAnalysis" => report_result(
+                        item_name,
+                        DefinitelyAccessibleAnalysis::new(tcx, def_id, &body_with_facts)
+                            .run_analysis(),
+                        body,
+                        json_dump,
+                    ),
+                    "FramingAnalysis" => report_result(
+                        item_name,
+                        FramingAnalysis::new(tcx, def_id, &body_with_facts).run_analysis(),
+                        body,
+                        json_dump,
+                    ),
                     _ => panic!("Unknown domain argument: {abstract_domain}"),
                 }
             }
         });
 
+        if let Some(json_dump) = json_dump {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::Value::Object(json_dump)).unwrap()
+            );
+        }
+
         Compilation::Stop
     }
 }
@@ -280,13 +362,20 @@ impl prusti_rustc_interface::driver::Callbacks for OurCompilerCalls {
 /// Give arguments to the analyzer by prefixing them with '--analysis'
 /// A abstract domain has to be provided by using '--analysis=' (without spaces), e.g.:
 /// --analysis=ReachingDefsState or --analysis=DefinitelyInitializedAnalysis
+///
+/// By default, the functions with attribute `#[analyzer::run]` are analyzed;
+/// '--analysis-function=<NAME>' analyzes the function with the given name instead.
+/// With '--dump=json', the results of all analyzed functions are printed as a
+/// single JSON object, mapping each function name to the state of the analysis
+/// before every statement and terminator, e.g.:
+/// --analysis=reaching_definitions --analysis-function=main --dump=json
 fn main() {
     env_logger::init();
     prusti_rustc_interface::driver::init_rustc_env_logger();
     let mut compiler_args = Vec::new();
     let mut callback_args = Vec::new();
     for arg in std::env::args() {
-        if arg.starts_with("--analysis") {
+        if arg.starts_with("--analysis") || arg.starts_with("--dump") {
             callback_args.push(arg);
         } else {
             compiler_args.push(arg);
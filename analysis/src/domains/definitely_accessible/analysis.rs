@@ -5,17 +5,17 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::{
-    abstract_interpretation::{AnalysisResult, FixpointEngine},
+    abstract_interpretation::{AnalysisResult, BackwardFixpointEngine, FixpointEngine},
     domains::{
         DefinitelyAccessibleState, DefinitelyInitializedAnalysis, DefinitelyInitializedState,
-        MaybeBorrowedAnalysis, MaybeBorrowedState,
+        MaybeBorrowedAnalysis, MaybeBorrowedState, MaybeLiveAnalysis,
     },
     mir_utils::remove_place_from_set,
     PointwiseState,
 };
 use prusti_rustc_interface::{
     borrowck::BodyWithBorrowckFacts,
-    data_structures::fx::{FxHashMap, FxHashSet},
+    data_structures::fx::FxHashSet,
     middle::{mir, ty::TyCtxt},
     span::def_id::DefId,
 };
@@ -46,16 +46,10 @@ impl<'mir, 'tcx: 'mir> DefinitelyAccessibleAnalysis<'mir, 'tcx> {
         let def_init_analysis =
             DefinitelyInitializedAnalysis::new_relaxed(self.tcx, self.def_id, body);
         let borrowed_analysis = MaybeBorrowedAnalysis::new(self.tcx, self.body_with_facts);
+        let liveness_analysis = MaybeLiveAnalysis::new(self.def_id, body);
         let def_init = def_init_analysis.run_fwd_analysis()?;
         let borrowed = borrowed_analysis.run_analysis()?;
-        let location_table = &self.body_with_facts.location_table;
-        let borrowck_out_facts = self.body_with_facts.output_facts.as_ref();
-        let var_live_on_entry: FxHashMap<_, _> = borrowck_out_facts
-            .var_live_on_entry
-            .iter()
-            .map(|(&point, live_vars)| (point, FxHashSet::from_iter(live_vars.iter().cloned())))
-            .collect();
-        let empty_locals_set: FxHashSet<mir::Local> = FxHashSet::default();
+        let liveness = liveness_analysis.run_bwd_analysis()?;
         let mut analysis_state = PointwiseState::default(body);
 
         for (block, block_data) in body.basic_blocks.iter_enumerated() {
@@ -71,9 +65,10 @@ impl<'mir, 'tcx: 'mir> DefinitelyAccessibleAnalysis<'mir, 'tcx> {
                 let borrowed_before = borrowed
                     .lookup_before(location)
                     .unwrap_or_else(|| panic!("No 'borrowed' state before location {location:?}"));
-                let liveness_before = var_live_on_entry
-                    .get(&location_table.start_index(location))
-                    .unwrap_or(&empty_locals_set);
+                let liveness_before = liveness
+                    .lookup_before(location)
+                    .unwrap_or_else(|| panic!("No 'liveness' state before location {location:?}"))
+                    .get_maybe_live_locals();
                 let state = self.compute_accessible_state(
                     def_init_before,
                     borrowed_before,
@@ -98,9 +93,10 @@ impl<'mir, 'tcx: 'mir> DefinitelyAccessibleAnalysis<'mir, 'tcx> {
                 let borrowed_after = borrowed_after_block.get(&successor).unwrap_or_else(|| {
                     panic!("No 'borrowed' state from {block:?} to {successor:?}")
                 });
-                let liveness_after = var_live_on_entry
-                    .get(&location_table.start_index(successor.start_location()))
-                    .unwrap_or(&empty_locals_set);
+                let liveness_after = liveness
+                    .lookup_before(successor.start_location())
+                    .unwrap_or_else(|| panic!("No 'liveness' state before block {successor:?}"))
+                    .get_maybe_live_locals();
                 let state =
                     self.compute_accessible_state(def_init_after, borrowed_after, liveness_after);
                 state.check_invariant(successor);
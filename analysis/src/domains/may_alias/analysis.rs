@@ -0,0 +1,94 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AnalysisResult, FixpointEngine},
+    domains::{MayAliasState, Pointee},
+    AnalysisError, PointwiseState,
+};
+use prusti_rustc_interface::{
+    data_structures::fx::{FxHashMap, FxHashSet},
+    middle::{mir, ty::TyCtxt},
+    span::def_id::DefId,
+};
+
+pub struct MayAliasAnalysis<'mir, 'tcx: 'mir> {
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    mir: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> MayAliasAnalysis<'mir, 'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, def_id: DefId, mir: &'mir mir::Body<'tcx>) -> Self {
+        MayAliasAnalysis { tcx, def_id, mir }
+    }
+}
+
+/// Compute the places that the locals of `mir` might point to at every program
+/// point, from which it can be decided whether two places might alias.
+pub fn compute_may_alias<'mir, 'tcx: 'mir>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    mir: &'mir mir::Body<'tcx>,
+) -> Result<PointwiseState<'mir, 'tcx, MayAliasState<'mir, 'tcx>>, AnalysisError> {
+    MayAliasAnalysis::new(tcx, def_id, mir).run_fwd_analysis()
+}
+
+impl<'mir, 'tcx: 'mir> FixpointEngine<'mir, 'tcx> for MayAliasAnalysis<'mir, 'tcx> {
+    type State = MayAliasState<'mir, 'tcx>;
+
+    fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
+    fn body(&self) -> &'mir mir::Body<'tcx> {
+        self.mir
+    }
+
+    /// The bottom element of the lattice, in which no local points anywhere.
+    fn new_bottom(&self) -> Self::State {
+        MayAliasState {
+            points_to: FxHashMap::default(),
+            exposed: FxHashSet::default(),
+            mir: self.mir,
+            tcx: self.tcx,
+        }
+    }
+
+    /// The parameters might point to any memory that is not owned by the body.
+    fn new_initial(&self) -> Self::State {
+        let mut state = self.new_bottom();
+        for local in self.mir.args_iter() {
+            state
+                .points_to
+                .entry(local)
+                .or_default()
+                .insert(Pointee::Unknown);
+        }
+        state
+    }
+
+    fn need_to_widen(_counter: u32) -> bool {
+        // the pointees are places that occur in the body => no lattice of infinite height
+        false
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::State,
+        location: mir::Location,
+    ) -> AnalysisResult<()> {
+        state.apply_statement_effect(location)
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &Self::State,
+        location: mir::Location,
+    ) -> AnalysisResult<Vec<(mir::BasicBlock, Self::State)>> {
+        state.apply_terminator_effect(location)
+    }
+}
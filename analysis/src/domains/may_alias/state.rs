@@ -0,0 +1,356 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{abstract_interpretation::AbstractState, AnalysisError};
+use prusti_rustc_interface::{
+    data_structures::fx::{FxHashMap, FxHashSet},
+    middle::{mir, ty::TyCtxt},
+};
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// A memory location that a pointer might point to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pointee<'tcx> {
+    /// A place of the body that does not contain any dereference.
+    Place(mir::Place<'tcx>),
+    /// Memory that is not owned by the body, e.g. reachable from the arguments
+    /// or returned by a call. It might contain the exposed locals.
+    Unknown,
+}
+
+impl<'tcx> fmt::Display for Pointee<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pointee::Place(place) => write!(f, "{place:?}"),
+            Pointee::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// The pointees of the references and raw pointers stored in each local.
+///
+/// The analysis is field-insensitive on the side of the pointers: the pointees
+/// of a local are the ones of all the pointers stored in any of its fields.
+#[derive(Clone)]
+pub struct MayAliasState<'mir, 'tcx: 'mir> {
+    pub(super) points_to: FxHashMap<mir::Local, FxHashSet<Pointee<'tcx>>>,
+    /// The locals whose address might be stored in unknown memory, e.g. by a
+    /// call, such that they might be modified or read through it.
+    pub(super) exposed: FxHashSet<mir::Local>,
+    pub(super) mir: &'mir mir::Body<'tcx>,
+    pub(super) tcx: TyCtxt<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> fmt::Debug for MayAliasState<'mir, 'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // ignore mir
+        f.debug_struct("MayAliasState")
+            .field("points_to", &self.points_to)
+            .field("exposed", &self.exposed)
+            .finish()
+    }
+}
+
+impl<'mir, 'tcx: 'mir> PartialEq for MayAliasState<'mir, 'tcx> {
+    fn eq(&self, other: &Self) -> bool {
+        self.points_to == other.points_to && self.exposed == other.exposed
+    }
+}
+
+impl<'mir, 'tcx: 'mir> Eq for MayAliasState<'mir, 'tcx> {}
+
+impl<'mir, 'tcx: 'mir> Serialize for MayAliasState<'mir, 'tcx> {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        let exposed = !self.exposed.is_empty();
+        let mut map = serializer.serialize_map(Some(self.points_to.len() + exposed as usize))?;
+        let ordered_points_to: BTreeMap<_, _> = self.points_to.iter().collect();
+        for (local, pointees) in ordered_points_to {
+            let ordered_pointees: BTreeSet<_> =
+                pointees.iter().map(|pointee| pointee.to_string()).collect();
+            map.serialize_entry(&format!("{local:?}"), &ordered_pointees)?;
+        }
+        if exposed {
+            let ordered_exposed: BTreeSet<_> = self.exposed.iter().collect();
+            let exposed: Vec<_> = ordered_exposed
+                .into_iter()
+                .map(|local| format!("{local:?}"))
+                .collect();
+            map.serialize_entry("exposed", &exposed)?;
+        }
+        map.end()
+    }
+}
+
+impl<'mir, 'tcx: 'mir> MayAliasState<'mir, 'tcx> {
+    pub fn get_pointees(&self, local: mir::Local) -> Option<&FxHashSet<Pointee<'tcx>>> {
+        self.points_to.get(&local)
+    }
+
+    /// The memory locations that `place` might denote, obtained by replacing
+    /// each dereference of a pointer by the pointees of the pointer.
+    pub fn resolve_place(&self, place: mir::Place<'tcx>) -> FxHashSet<Pointee<'tcx>> {
+        let deref_index = match place
+            .projection
+            .iter()
+            .position(|elem| elem == mir::ProjectionElem::Deref)
+        {
+            Some(deref_index) => deref_index,
+            None => return [Pointee::Place(place)].into_iter().collect(),
+        };
+        let suffix = &place.projection[deref_index + 1..];
+        let mut resolved = FxHashSet::default();
+        for pointee in self.stored_pointees(place.local) {
+            match pointee {
+                Pointee::Place(base) => {
+                    resolved.extend(self.resolve_place(base.project_deeper(suffix, self.tcx)))
+                }
+                Pointee::Unknown => {
+                    resolved.insert(Pointee::Unknown);
+                }
+            }
+        }
+        resolved
+    }
+
+    /// Returns `false` only if the memory locations denoted by `place_a` and by
+    /// `place_b` are guaranteed to be disjoint.
+    pub fn may_alias(&self, place_a: mir::Place<'tcx>, place_b: mir::Place<'tcx>) -> bool {
+        self.may_overlap(&self.resolve_place(place_a), &self.resolve_place(place_b))
+    }
+
+    /// Returns `false` only if the memory locations of `pointees_a` are
+    /// guaranteed to be disjoint from the ones of `pointees_b`.
+    pub fn may_overlap(
+        &self,
+        pointees_a: &FxHashSet<Pointee<'tcx>>,
+        pointees_b: &FxHashSet<Pointee<'tcx>>,
+    ) -> bool {
+        pointees_a.iter().any(|a| {
+            pointees_b.iter().any(|b| match (a, b) {
+                (Pointee::Place(a), Pointee::Place(b)) => self.places_may_overlap(*a, *b),
+                (Pointee::Unknown, Pointee::Unknown) => true,
+                (Pointee::Place(place), Pointee::Unknown)
+                | (Pointee::Unknown, Pointee::Place(place)) => self.exposed.contains(&place.local),
+            })
+        })
+    }
+
+    /// Decides whether two places without dereferences might overlap. Distinct
+    /// fields of a struct are disjoint, while the elements of an array and the
+    /// variants of an enum are conservatively considered to overlap.
+    fn places_may_overlap(&self, place_a: mir::Place<'tcx>, place_b: mir::Place<'tcx>) -> bool {
+        if place_a.local != place_b.local {
+            return false;
+        }
+        for ((base, elem_a), elem_b) in place_a.iter_projections().zip(place_b.projection) {
+            match (elem_a, elem_b) {
+                (
+                    mir::ProjectionElem::Field(field_a, _),
+                    mir::ProjectionElem::Field(field_b, _),
+                ) if field_a != field_b => {
+                    return base.ty(self.mir, self.tcx).ty.is_union();
+                }
+                (
+                    mir::ProjectionElem::Downcast(_, variant_a),
+                    mir::ProjectionElem::Downcast(_, variant_b),
+                ) if variant_a != variant_b => {
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        true
+    }
+
+    /// The pointees of the pointers that might be stored in `local`.
+    fn stored_pointees(&self, local: mir::Local) -> FxHashSet<Pointee<'tcx>> {
+        let mut pointees = self.points_to.get(&local).cloned().unwrap_or_default();
+        if self.exposed.contains(&local) {
+            // a pointer to unknown memory might have been stored through the exposed address
+            pointees.insert(Pointee::Unknown);
+        }
+        pointees
+    }
+
+    /// The pointees of the pointers that might be stored in `place`.
+    fn place_pointees(&self, place: mir::Place<'tcx>) -> FxHashSet<Pointee<'tcx>> {
+        if !place
+            .projection
+            .iter()
+            .any(|elem| elem == mir::ProjectionElem::Deref)
+        {
+            return self.stored_pointees(place.local);
+        }
+        let mut pointees = FxHashSet::default();
+        for location in self.resolve_place(place) {
+            match location {
+                Pointee::Place(location) => pointees.extend(self.stored_pointees(location.local)),
+                Pointee::Unknown => {
+                    pointees.insert(Pointee::Unknown);
+                }
+            }
+        }
+        pointees
+    }
+
+    fn operand_pointees(&self, operand: &mir::Operand<'tcx>) -> FxHashSet<Pointee<'tcx>> {
+        match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => self.place_pointees(*place),
+            mir::Operand::Constant(_) => FxHashSet::default(),
+        }
+    }
+
+    /// The pointees of the pointers that might be contained in the value of
+    /// `rvalue`.
+    fn rvalue_pointees(&self, rvalue: &mir::Rvalue<'tcx>) -> FxHashSet<Pointee<'tcx>> {
+        match rvalue {
+            mir::Rvalue::Ref(_, _, place) | mir::Rvalue::AddressOf(_, place) => {
+                self.resolve_place(*place)
+            }
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Repeat(operand, _)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand)
+            | mir::Rvalue::ShallowInitBox(operand, _) => self.operand_pointees(operand),
+            mir::Rvalue::BinaryOp(_, operands) | mir::Rvalue::CheckedBinaryOp(_, operands) => {
+                let (left, right) = &**operands;
+                let mut pointees = self.operand_pointees(left);
+                pointees.extend(self.operand_pointees(right));
+                pointees
+            }
+            mir::Rvalue::Aggregate(_, operands) => operands
+                .iter()
+                .flat_map(|operand| self.operand_pointees(operand))
+                .collect(),
+            mir::Rvalue::CopyForDeref(place) => self.place_pointees(*place),
+            mir::Rvalue::ThreadLocalRef(_) => [Pointee::Unknown].into_iter().collect(),
+            mir::Rvalue::Len(_) | mir::Rvalue::Discriminant(_) | mir::Rvalue::NullaryOp(..) => {
+                FxHashSet::default()
+            }
+        }
+    }
+
+    /// Records that the pointers stored in `target` might point to `pointees`.
+    ///
+    /// Only the assignment of a whole local overwrites its previous pointees.
+    /// Storing pointers in unknown memory exposes the locals they point to.
+    fn assign(&mut self, target: mir::Place<'tcx>, pointees: FxHashSet<Pointee<'tcx>>) {
+        if let Some(local) = target.as_local() {
+            if pointees.is_empty() {
+                self.points_to.remove(&local);
+            } else {
+                self.points_to.insert(local, pointees);
+            }
+            return;
+        }
+        for location in self.resolve_place(target) {
+            match location {
+                Pointee::Place(location) => {
+                    if !pointees.is_empty() {
+                        self.points_to
+                            .entry(location.local)
+                            .or_default()
+                            .extend(pointees.iter().copied());
+                    }
+                }
+                Pointee::Unknown => self.expose(&pointees),
+            }
+        }
+    }
+
+    fn expose(&mut self, pointees: &FxHashSet<Pointee<'tcx>>) {
+        for pointee in pointees {
+            if let Pointee::Place(place) = pointee {
+                self.exposed.insert(place.local);
+            }
+        }
+    }
+
+    pub(super) fn apply_statement_effect(
+        &mut self,
+        location: mir::Location,
+    ) -> Result<(), AnalysisError> {
+        let stmt = &self.mir[location.block].statements[location.statement_index];
+        if let mir::StatementKind::Assign(box (target, ref rvalue)) = stmt.kind {
+            let pointees = self.rvalue_pointees(rvalue);
+            self.assign(target, pointees);
+        }
+        Ok(())
+    }
+
+    /// A called function might store the pointers contained in its arguments
+    /// in unknown memory, and return a value pointing to any of them or to
+    /// unknown memory.
+    pub(super) fn apply_terminator_effect(
+        &self,
+        location: mir::Location,
+    ) -> Result<Vec<(mir::BasicBlock, Self)>, AnalysisError> {
+        let mut res_vec = Vec::new();
+        let terminator = self.mir[location.block].terminator();
+        match terminator.kind {
+            mir::TerminatorKind::Call {
+                ref func,
+                ref args,
+                destination,
+                target,
+                cleanup,
+                ..
+            } => {
+                let mut arg_pointees = self.operand_pointees(func);
+                for arg in args {
+                    arg_pointees.extend(self.operand_pointees(arg));
+                }
+                let mut state_after_call = self.clone();
+                state_after_call.expose(&arg_pointees);
+
+                if let Some(bb) = cleanup {
+                    res_vec.push((bb, state_after_call.clone()));
+                }
+                if let Some(bb) = target {
+                    arg_pointees.insert(Pointee::Unknown);
+                    state_after_call.assign(destination, arg_pointees);
+                    res_vec.push((bb, state_after_call));
+                }
+            }
+            mir::TerminatorKind::InlineAsm { .. } | mir::TerminatorKind::Yield { .. } => {
+                return Err(AnalysisError::UnsupportedStatement(location));
+            }
+            _ => {
+                for bb in terminator.successors() {
+                    // no assignment -> no change of state
+                    res_vec.push((bb, self.clone()));
+                }
+            }
+        }
+
+        Ok(res_vec)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> AbstractState for MayAliasState<'mir, 'tcx> {
+    fn is_bottom(&self) -> bool {
+        self.points_to.values().all(|pointees| pointees.is_empty()) && self.exposed.is_empty()
+    }
+
+    fn join(&mut self, other: &Self) {
+        for (local, other_pointees) in other.points_to.iter() {
+            self.points_to
+                .entry(*local)
+                .or_default()
+                .extend(other_pointees.iter().copied());
+        }
+        self.exposed.extend(other.exposed.iter().copied());
+    }
+
+    fn widen(&mut self, _previous: &Self) {
+        // the pointees are places that occur in the body => widening should not be needed
+        unimplemented!()
+    }
+}
@@ -4,7 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::mir_utils::Place;
+use crate::mir_utils::{is_prefix, Place};
 use prusti_rustc_interface::data_structures::fx::FxHashSet;
 use serde::{ser::SerializeMap, Serialize, Serializer};
 
@@ -22,6 +22,39 @@ impl<'tcx> MaybeBorrowedState<'tcx> {
     pub fn get_maybe_mut_borrowed(&self) -> &FxHashSet<Place<'tcx>> {
         &self.maybe_mut_borrowed
     }
+
+    /// Records that `place` might be blocked by a shared loan.
+    pub fn insert_maybe_shared_borrowed(&mut self, place: Place<'tcx>) {
+        self.maybe_shared_borrowed.insert(place);
+    }
+
+    /// Records that `place` might be blocked by a mutable loan.
+    pub fn insert_maybe_mut_borrowed(&mut self, place: Place<'tcx>) {
+        self.maybe_mut_borrowed.insert(place);
+    }
+
+    /// Returns whether no part of `place` might be borrowed, i.e. whether no
+    /// place that might be borrowed is a prefix or an extension of `place`.
+    /// The permission of such a place can be folded and unfolded without
+    /// considering loans.
+    pub fn is_definitely_unborrowed(&self, place: Place<'tcx>) -> bool {
+        self.maybe_shared_borrowed
+            .iter()
+            .chain(self.maybe_mut_borrowed.iter())
+            .all(|&borrowed| !conflicts(borrowed, place))
+    }
+
+    /// Returns whether no part of `place` might be mutably borrowed.
+    pub fn is_definitely_not_mut_borrowed(&self, place: Place<'tcx>) -> bool {
+        self.maybe_mut_borrowed
+            .iter()
+            .all(|&borrowed| !conflicts(borrowed, place))
+    }
+}
+
+/// Two places conflict if one of them is a prefix of the other.
+fn conflicts<'tcx>(left: Place<'tcx>, right: Place<'tcx>) -> bool {
+    is_prefix(left, right) || is_prefix(right, left)
 }
 
 impl<'tcx> Serialize for MaybeBorrowedState<'tcx> {
@@ -0,0 +1,60 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AnalysisResult, BackwardFixpointEngine},
+    domains::MaybeLiveState,
+};
+use prusti_rustc_interface::{data_structures::fx::FxHashMap, middle::mir, span::def_id::DefId};
+
+/// A backward analysis computing the locals that are (use-)live before each
+/// statement.
+pub struct MaybeLiveAnalysis<'mir, 'tcx: 'mir> {
+    def_id: DefId,
+    mir: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> MaybeLiveAnalysis<'mir, 'tcx> {
+    pub fn new(def_id: DefId, mir: &'mir mir::Body<'tcx>) -> Self {
+        MaybeLiveAnalysis { def_id, mir }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> BackwardFixpointEngine<'mir, 'tcx> for MaybeLiveAnalysis<'mir, 'tcx> {
+    type State = MaybeLiveState<'mir, 'tcx>;
+
+    fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
+    fn body(&self) -> &'mir mir::Body<'tcx> {
+        self.mir
+    }
+
+    fn new_bottom(&self) -> Self::State {
+        MaybeLiveState::new_bottom(self.mir)
+    }
+
+    fn need_to_widen(_counter: u32) -> bool {
+        false
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::State,
+        location: mir::Location,
+    ) -> AnalysisResult<()> {
+        state.apply_statement_effect(location)
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        successor_states: &FxHashMap<mir::BasicBlock, Self::State>,
+        location: mir::Location,
+    ) -> AnalysisResult<Self::State> {
+        MaybeLiveState::apply_terminator_effect(self.mir, successor_states, location)
+    }
+}
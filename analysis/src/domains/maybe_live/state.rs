@@ -0,0 +1,182 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{abstract_interpretation::AbstractState, AnalysisError};
+use prusti_rustc_interface::{
+    data_structures::fx::{FxHashMap, FxHashSet},
+    middle::mir::{
+        self,
+        visit::{PlaceContext, Visitor},
+    },
+};
+use serde::{ser::SerializeSeq, Serialize, Serializer};
+use std::{collections::BTreeSet, fmt};
+
+/// A set of MIR locals whose current value might be used later, i.e. a local
+/// is not in the set if it is definitely assigned again, deallocated or only
+/// dropped before any use.
+#[derive(Clone)]
+pub struct MaybeLiveState<'mir, 'tcx: 'mir> {
+    pub(super) maybe_live_locals: FxHashSet<mir::Local>,
+    pub(super) mir: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> fmt::Debug for MaybeLiveState<'mir, 'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // ignore mir
+        f.debug_struct("MaybeLiveState")
+            .field("maybe_live_locals", &self.maybe_live_locals)
+            .finish()
+    }
+}
+
+impl<'mir, 'tcx: 'mir> PartialEq for MaybeLiveState<'mir, 'tcx> {
+    fn eq(&self, other: &Self) -> bool {
+        self.maybe_live_locals == other.maybe_live_locals
+    }
+}
+
+impl<'mir, 'tcx: 'mir> Eq for MaybeLiveState<'mir, 'tcx> {}
+
+impl<'mir, 'tcx: 'mir> Serialize for MaybeLiveState<'mir, 'tcx> {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.maybe_live_locals.len()))?;
+
+        let ordered_set: BTreeSet<_> = self.maybe_live_locals.iter().collect();
+        for local in ordered_set {
+            seq.serialize_element(&format!("{local:?}"))?;
+        }
+        seq.end()
+    }
+}
+
+/// Collects all the locals used by the visited MIR fragment.
+struct UsedLocals<'a>(&'a mut FxHashSet<mir::Local>);
+
+impl<'a, 'tcx> Visitor<'tcx> for UsedLocals<'a> {
+    fn visit_local(&mut self, local: mir::Local, _context: PlaceContext, _location: mir::Location) {
+        self.0.insert(local);
+    }
+}
+
+impl<'mir, 'tcx: 'mir> MaybeLiveState<'mir, 'tcx> {
+    pub fn get_maybe_live_locals(&self) -> &FxHashSet<mir::Local> {
+        &self.maybe_live_locals
+    }
+
+    /// The bottom element of the lattice contains no locals
+    pub fn new_bottom(mir: &'mir mir::Body<'tcx>) -> Self {
+        Self {
+            maybe_live_locals: FxHashSet::default(),
+            mir,
+        }
+    }
+
+    /// Removes `place` from the live locals, if it is a whole local that is
+    /// assigned, and otherwise marks its locals as used.
+    fn define_place(&mut self, place: mir::Place<'tcx>, location: mir::Location) {
+        if let Some(local) = place.as_local() {
+            self.maybe_live_locals.remove(&local);
+        } else {
+            self.use_place(place, location);
+        }
+    }
+
+    fn use_place(&mut self, place: mir::Place<'tcx>, location: mir::Location) {
+        UsedLocals(&mut self.maybe_live_locals).visit_place(
+            &place,
+            PlaceContext::NonMutatingUse(mir::visit::NonMutatingUseContext::Inspect),
+            location,
+        );
+    }
+
+    pub(super) fn apply_statement_effect(
+        &mut self,
+        location: mir::Location,
+    ) -> Result<(), AnalysisError> {
+        let statement = &self.mir[location.block].statements[location.statement_index];
+        match &statement.kind {
+            mir::StatementKind::Assign(box (place, rvalue)) => {
+                self.define_place(*place, location);
+                UsedLocals(&mut self.maybe_live_locals).visit_rvalue(rvalue, location);
+            }
+            mir::StatementKind::StorageLive(local) | mir::StatementKind::StorageDead(local) => {
+                self.maybe_live_locals.remove(local);
+            }
+            mir::StatementKind::Deinit(box place) => {
+                self.define_place(*place, location);
+            }
+            _ => {
+                UsedLocals(&mut self.maybe_live_locals).visit_statement(statement, location);
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the state before the terminator at `location` from the states
+    /// at the beginning of its successors.
+    pub(super) fn apply_terminator_effect(
+        mir: &'mir mir::Body<'tcx>,
+        successor_states: &FxHashMap<mir::BasicBlock, Self>,
+        location: mir::Location,
+    ) -> Result<Self, AnalysisError> {
+        let terminator = mir[location.block].terminator();
+        let mut state = Self::new_bottom(mir);
+        match &terminator.kind {
+            mir::TerminatorKind::Call {
+                func,
+                args,
+                destination,
+                target,
+                ..
+            } => {
+                // The destination is only assigned if the call returns normally.
+                for (&successor, successor_state) in successor_states {
+                    let mut successor_state = successor_state.clone();
+                    if Some(successor) == *target {
+                        successor_state.define_place(*destination, location);
+                    }
+                    state.join(&successor_state);
+                }
+                let mut used_locals = UsedLocals(&mut state.maybe_live_locals);
+                used_locals.visit_operand(func, location);
+                for arg in args {
+                    used_locals.visit_operand(arg, location);
+                }
+            }
+            mir::TerminatorKind::Drop { .. } => {
+                // Dropping a value is not a use of it; the drop-liveness of
+                // a local is not tracked.
+                for successor_state in successor_states.values() {
+                    state.join(successor_state);
+                }
+            }
+            _ => {
+                for successor_state in successor_states.values() {
+                    state.join(successor_state);
+                }
+                UsedLocals(&mut state.maybe_live_locals).visit_terminator(terminator, location);
+            }
+        }
+        Ok(state)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> AbstractState for MaybeLiveState<'mir, 'tcx> {
+    fn is_bottom(&self) -> bool {
+        self.maybe_live_locals.is_empty()
+    }
+
+    /// The lattice join unites the two sets of locals
+    fn join(&mut self, other: &Self) {
+        self.maybe_live_locals
+            .extend(other.maybe_live_locals.iter().copied());
+    }
+
+    fn widen(&mut self, _previous: &Self) {
+        unimplemented!()
+    }
+}
@@ -0,0 +1,166 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AnalysisResult, CallGraph, FixpointEngine, InterproceduralAnalysis},
+    domains::{ArgFlowState, MaybeReturnedArgs},
+};
+use prusti_rustc_interface::{
+    data_structures::fx::{FxHashMap, FxHashSet},
+    middle::mir,
+    span::def_id::DefId,
+};
+
+/// An intraprocedural analysis computing the parameters whose value might flow
+/// into each local, given the summaries of the called functions.
+pub struct ArgFlowAnalysis<'a, 'mir, 'tcx: 'mir> {
+    def_id: DefId,
+    mir: &'mir mir::Body<'tcx>,
+    call_graph: &'a CallGraph<'mir, 'tcx>,
+    /// The locals which are borrowed anywhere in the body, and may thus be
+    /// modified through a reference.
+    borrowed_locals: FxHashSet<mir::Local>,
+    summaries: &'a FxHashMap<DefId, MaybeReturnedArgs>,
+}
+
+impl<'a, 'mir, 'tcx: 'mir> ArgFlowAnalysis<'a, 'mir, 'tcx> {
+    /// `def_id` has to be a function of `call_graph`.
+    pub fn new(
+        def_id: DefId,
+        call_graph: &'a CallGraph<'mir, 'tcx>,
+        summaries: &'a FxHashMap<DefId, MaybeReturnedArgs>,
+    ) -> Self {
+        let mir = call_graph.body(def_id).unwrap();
+        let mut borrowed_locals = FxHashSet::default();
+        for bb_data in mir.basic_blocks.iter() {
+            for stmt in &bb_data.statements {
+                if let mir::StatementKind::Assign(box (
+                    _,
+                    mir::Rvalue::Ref(_, _, ref place) | mir::Rvalue::AddressOf(_, ref place),
+                )) = stmt.kind
+                {
+                    borrowed_locals.insert(place.local);
+                }
+            }
+        }
+        ArgFlowAnalysis {
+            def_id,
+            mir,
+            call_graph,
+            borrowed_locals,
+            summaries,
+        }
+    }
+}
+
+impl<'a, 'mir, 'tcx: 'mir> FixpointEngine<'mir, 'tcx> for ArgFlowAnalysis<'a, 'mir, 'tcx> {
+    type State = ArgFlowState<'mir, 'tcx>;
+
+    fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
+    fn body(&self) -> &'mir mir::Body<'tcx> {
+        self.mir
+    }
+
+    fn new_bottom(&self) -> Self::State {
+        ArgFlowState {
+            flows: FxHashMap::default(),
+            mir: self.mir,
+        }
+    }
+
+    fn new_initial(&self) -> Self::State {
+        let mut flows: FxHashMap<mir::Local, FxHashSet<usize>> = FxHashMap::default();
+        for (idx, local) in self.mir.args_iter().enumerate() {
+            flows.entry(local).or_default().insert(idx);
+        }
+        ArgFlowState {
+            flows,
+            mir: self.mir,
+        }
+    }
+
+    fn need_to_widen(_counter: u32) -> bool {
+        // the number of parameters is finite => no lattice of infinite height
+        false
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::State,
+        location: mir::Location,
+    ) -> AnalysisResult<()> {
+        state.apply_statement_effect(location, &self.borrowed_locals)
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &Self::State,
+        location: mir::Location,
+    ) -> AnalysisResult<Vec<(mir::BasicBlock, Self::State)>> {
+        let callee_summary = self
+            .call_graph
+            .callee(self.def_id, location)
+            .and_then(|callee| self.summaries.get(&callee));
+        state.apply_terminator_effect(location, &self.borrowed_locals, callee_summary)
+    }
+}
+
+/// A context-insensitive interprocedural analysis computing, for every function
+/// of a call graph, the parameters that might be contained in its result.
+///
+/// Values stored in global or interior-mutable memory are not tracked.
+pub struct MaybeReturnedArgsAnalysis<'a, 'mir, 'tcx: 'mir> {
+    call_graph: &'a CallGraph<'mir, 'tcx>,
+}
+
+impl<'a, 'mir, 'tcx: 'mir> MaybeReturnedArgsAnalysis<'a, 'mir, 'tcx> {
+    pub fn new(call_graph: &'a CallGraph<'mir, 'tcx>) -> Self {
+        MaybeReturnedArgsAnalysis { call_graph }
+    }
+}
+
+impl<'a, 'mir, 'tcx: 'mir> InterproceduralAnalysis<'mir, 'tcx>
+    for MaybeReturnedArgsAnalysis<'a, 'mir, 'tcx>
+{
+    type Summary = MaybeReturnedArgs;
+
+    fn call_graph(&self) -> &CallGraph<'mir, 'tcx> {
+        self.call_graph
+    }
+
+    fn new_bottom_summary(&self, _def_id: DefId) -> Self::Summary {
+        MaybeReturnedArgs::default()
+    }
+
+    fn need_to_widen(_counter: u32) -> bool {
+        false
+    }
+
+    fn compute_summary(
+        &self,
+        def_id: DefId,
+        summaries: &FxHashMap<DefId, Self::Summary>,
+    ) -> AnalysisResult<Self::Summary> {
+        let mir = self.call_graph.body(def_id).unwrap();
+        let p_state =
+            ArgFlowAnalysis::new(def_id, self.call_graph, summaries).run_fwd_analysis()?;
+        let mut summary = MaybeReturnedArgs::default();
+        for (bb, bb_data) in mir.basic_blocks.iter_enumerated() {
+            if let mir::TerminatorKind::Return = bb_data.terminator().kind {
+                // unreachable returns have no state
+                if let Some(state) = p_state.lookup_before(mir.terminator_loc(bb)) {
+                    if let Some(args) = state.get_flows(mir::RETURN_PLACE) {
+                        summary.args.extend(args.iter().copied());
+                    }
+                }
+            }
+        }
+        Ok(summary)
+    }
+}
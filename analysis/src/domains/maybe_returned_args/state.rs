@@ -0,0 +1,267 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{abstract_interpretation::AbstractState, AnalysisError};
+use prusti_rustc_interface::{
+    data_structures::fx::{FxHashMap, FxHashSet},
+    middle::mir::{
+        self,
+        visit::{PlaceContext, Visitor},
+    },
+};
+use serde::{
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// The summary of a function: the indices of the parameters whose value, or a
+/// part or a reference of it, might be contained in the result.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaybeReturnedArgs {
+    pub(super) args: FxHashSet<usize>,
+}
+
+impl MaybeReturnedArgs {
+    pub fn contains(&self, arg_index: usize) -> bool {
+        self.args.contains(&arg_index)
+    }
+}
+
+impl Serialize for MaybeReturnedArgs {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.args.len()))?;
+        let ordered_set: BTreeSet<_> = self.args.iter().collect();
+        for idx in ordered_set {
+            seq.serialize_element(&format!("arg{idx}"))?;
+        }
+        seq.end()
+    }
+}
+
+impl AbstractState for MaybeReturnedArgs {
+    fn is_bottom(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    fn join(&mut self, other: &Self) {
+        self.args.extend(other.args.iter().copied());
+    }
+
+    fn widen(&mut self, _previous: &Self) {
+        // the number of parameters is finite => widening should not be needed
+        unimplemented!()
+    }
+}
+
+/// The indices of the parameters whose value might flow into each local.
+#[derive(Clone)]
+pub struct ArgFlowState<'mir, 'tcx: 'mir> {
+    pub(super) flows: FxHashMap<mir::Local, FxHashSet<usize>>,
+    pub(super) mir: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> fmt::Debug for ArgFlowState<'mir, 'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // ignore mir
+        f.debug_struct("ArgFlowState")
+            .field("flows", &self.flows)
+            .finish()
+    }
+}
+
+impl<'mir, 'tcx: 'mir> PartialEq for ArgFlowState<'mir, 'tcx> {
+    fn eq(&self, other: &Self) -> bool {
+        self.flows == other.flows
+    }
+}
+
+impl<'mir, 'tcx: 'mir> Eq for ArgFlowState<'mir, 'tcx> {}
+
+impl<'mir, 'tcx: 'mir> Serialize for ArgFlowState<'mir, 'tcx> {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        let mut map = serializer.serialize_map(Some(self.flows.len()))?;
+        let ordered_flows: BTreeMap<_, _> = self.flows.iter().collect();
+        for (local, args) in ordered_flows {
+            let ordered_args: BTreeSet<_> = args.iter().collect();
+            let args: Vec<_> = ordered_args
+                .into_iter()
+                .map(|idx| format!("arg{idx}"))
+                .collect();
+            map.serialize_entry(&format!("{local:?}"), &args)?;
+        }
+        map.end()
+    }
+}
+
+/// Collects all the locals used by the visited MIR fragment.
+struct UsedLocals<'a>(&'a mut FxHashSet<mir::Local>);
+
+impl<'a, 'tcx> Visitor<'tcx> for UsedLocals<'a> {
+    fn visit_local(&mut self, local: mir::Local, _context: PlaceContext, _location: mir::Location) {
+        self.0.insert(local);
+    }
+}
+
+impl<'mir, 'tcx: 'mir> ArgFlowState<'mir, 'tcx> {
+    pub fn get_flows(&self, local: mir::Local) -> Option<&FxHashSet<usize>> {
+        self.flows.get(&local)
+    }
+
+    /// The parameters that might flow into the value of `operand`.
+    fn operand_flows(
+        &self,
+        operand: &mir::Operand<'tcx>,
+        location: mir::Location,
+    ) -> FxHashSet<usize> {
+        let mut locals = FxHashSet::default();
+        UsedLocals(&mut locals).visit_operand(operand, location);
+        self.locals_flows(&locals)
+    }
+
+    fn locals_flows(&self, locals: &FxHashSet<mir::Local>) -> FxHashSet<usize> {
+        locals
+            .iter()
+            .filter_map(|local| self.flows.get(local))
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Records that the parameters in `args` might flow into `place`.
+    ///
+    /// Only the assignment of a whole local overwrites its previous value. An
+    /// assignment through a reference might modify any of the `borrowed_locals`.
+    fn assign(
+        &mut self,
+        place: mir::Place<'tcx>,
+        args: FxHashSet<usize>,
+        borrowed_locals: &FxHashSet<mir::Local>,
+    ) {
+        if place.is_indirect() {
+            for &local in borrowed_locals {
+                self.flows.entry(local).or_default().extend(args.iter());
+            }
+        }
+        if let Some(local) = place.as_local() {
+            self.flows.insert(local, args);
+        } else {
+            self.flows.entry(place.local).or_default().extend(args);
+        }
+    }
+
+    pub(super) fn apply_statement_effect(
+        &mut self,
+        location: mir::Location,
+        borrowed_locals: &FxHashSet<mir::Local>,
+    ) -> Result<(), AnalysisError> {
+        let stmt = &self.mir[location.block].statements[location.statement_index];
+        if let mir::StatementKind::Assign(box (ref target, ref rvalue)) = stmt.kind {
+            let mut locals = FxHashSet::default();
+            UsedLocals(&mut locals).visit_rvalue(rvalue, location);
+            let args = self.locals_flows(&locals);
+            self.assign(*target, args, borrowed_locals);
+        }
+        Ok(())
+    }
+
+    /// The value returned by a call of a function with a summary contains the
+    /// arguments for the parameters in the summary. Without a summary, e.g. if
+    /// the called function is not statically known, it might contain any
+    /// argument. In both cases, the callee might store any argument in the
+    /// memory reachable from a mutable reference.
+    pub(super) fn apply_terminator_effect(
+        &self,
+        location: mir::Location,
+        borrowed_locals: &FxHashSet<mir::Local>,
+        callee_summary: Option<&MaybeReturnedArgs>,
+    ) -> Result<Vec<(mir::BasicBlock, Self)>, AnalysisError> {
+        let mut res_vec = Vec::new();
+        let terminator = self.mir[location.block].terminator();
+        match terminator.kind {
+            mir::TerminatorKind::Call {
+                ref func,
+                ref args,
+                destination,
+                target,
+                cleanup,
+                ..
+            } => {
+                let arg_flows: Vec<_> = args
+                    .iter()
+                    .map(|arg| self.operand_flows(arg, location))
+                    .collect();
+                let returned_flows: FxHashSet<usize> = match callee_summary {
+                    Some(summary) => arg_flows
+                        .iter()
+                        .enumerate()
+                        .filter(|(idx, _)| summary.contains(*idx))
+                        .flat_map(|(_, flows)| flows.iter().copied())
+                        .chain(self.operand_flows(func, location))
+                        .collect(),
+                    None => arg_flows
+                        .iter()
+                        .flatten()
+                        .copied()
+                        .chain(self.operand_flows(func, location))
+                        .collect(),
+                };
+
+                let mut state_after_call = self.clone();
+                let all_arg_flows: FxHashSet<usize> = arg_flows.into_iter().flatten().collect();
+                for &local in borrowed_locals {
+                    state_after_call
+                        .flows
+                        .entry(local)
+                        .or_default()
+                        .extend(all_arg_flows.iter());
+                }
+
+                if let Some(bb) = cleanup {
+                    res_vec.push((bb, state_after_call.clone()));
+                }
+                if let Some(bb) = target {
+                    state_after_call.assign(destination, returned_flows, borrowed_locals);
+                    res_vec.push((bb, state_after_call));
+                }
+            }
+            mir::TerminatorKind::InlineAsm { .. } => {
+                return Err(AnalysisError::UnsupportedStatement(location));
+            }
+            _ => {
+                for bb in terminator.successors() {
+                    // no assignment -> no change of state
+                    res_vec.push((bb, self.clone()));
+                }
+            }
+        }
+
+        Ok(res_vec)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> AbstractState for ArgFlowState<'mir, 'tcx> {
+    fn is_bottom(&self) -> bool {
+        self.flows.values().all(|args| args.is_empty())
+    }
+
+    fn join(&mut self, other: &Self) {
+        for (local, other_args) in other.flows.iter() {
+            self.flows
+                .entry(*local)
+                .or_default()
+                .extend(other_args.iter());
+        }
+    }
+
+    fn widen(&mut self, _previous: &Self) {
+        // the number of parameters is finite => widening should not be needed
+        unimplemented!()
+    }
+}
@@ -8,12 +8,18 @@ mod definitely_accessible;
 mod definitely_allocated;
 mod definitely_initialized;
 mod framing;
+mod may_alias;
 mod maybe_borrowed;
+mod maybe_live;
+mod maybe_returned_args;
 mod reaching_definitions;
 
 pub use definitely_accessible::*;
 pub use definitely_allocated::*;
 pub use definitely_initialized::*;
 pub use framing::*;
+pub use may_alias::*;
 pub use maybe_borrowed::*;
+pub use maybe_live::*;
+pub use maybe_returned_args::*;
 pub use reaching_definitions::*;
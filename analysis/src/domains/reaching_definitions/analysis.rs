@@ -7,6 +7,7 @@
 use crate::{
     abstract_interpretation::{AnalysisResult, FixpointEngine},
     domains::{DefLocation, ReachingDefsState},
+    AnalysisError, PointwiseState,
 };
 use prusti_rustc_interface::{
     data_structures::fx::{FxHashMap, FxHashSet},
@@ -26,6 +27,16 @@ impl<'mir, 'tcx: 'mir> ReachingDefsAnalysis<'mir, 'tcx> {
     }
 }
 
+/// Compute the reaching definitions of the locals of `mir` at every program
+/// point. This is the stable entry point for consumers of the analysis.
+pub fn compute_reaching_defs<'mir, 'tcx: 'mir>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    mir: &'mir mir::Body<'tcx>,
+) -> Result<PointwiseState<'mir, 'tcx, ReachingDefsState<'mir, 'tcx>>, AnalysisError> {
+    ReachingDefsAnalysis::new(tcx, def_id, mir).run_fwd_analysis()
+}
+
 impl<'mir, 'tcx: 'mir> FixpointEngine<'mir, 'tcx> for ReachingDefsAnalysis<'mir, 'tcx> {
     type State = ReachingDefsState<'mir, 'tcx>;
 
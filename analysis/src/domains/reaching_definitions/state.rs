@@ -99,6 +99,11 @@ impl<'mir, 'tcx: 'mir> Serialize for ReachingDefsState<'mir, 'tcx> {
 }
 
 impl<'mir, 'tcx: 'mir> ReachingDefsState<'mir, 'tcx> {
+    /// The definitions that might have assigned the current value of `local`.
+    pub fn get_reaching_defs(&self, local: mir::Local) -> Option<&FxHashSet<DefLocation>> {
+        self.reaching_defs.get(&local)
+    }
+
     pub(super) fn apply_statement_effect(
         &mut self,
         location: mir::Location,
@@ -4,6 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use crate::mir_utils::location_to_stmt_str;
 use prusti_rustc_interface::{data_structures::fx::FxHashMap, middle::mir};
 use serde::{ser::SerializeMap, Serialize, Serializer};
 use std::{collections::BTreeMap, fmt};
@@ -128,6 +129,34 @@ impl<'mir, 'tcx: 'mir, S: Serialize> PointwiseState<'mir, 'tcx, S> {
             .or_insert_with(FxHashMap::default)
     }
 
+    /// Translate the state before every statement and terminator to a flat
+    /// JSON array of program points, ordered by location. Unlike the
+    /// serialization of `PointwiseState`, this format is meant to be consumed
+    /// by external tools.
+    pub fn to_program_points_json(&self) -> serde_json::Value {
+        let mut program_points = Vec::new();
+        for (block, bb_data) in self.mir.basic_blocks.iter_enumerated() {
+            for statement_index in 0..=bb_data.statements.len() {
+                let location = mir::Location {
+                    block,
+                    statement_index,
+                };
+                let kind = if statement_index < bb_data.statements.len() {
+                    "statement"
+                } else {
+                    "terminator"
+                };
+                program_points.push(serde_json::json!({
+                    "location": format!("{location:?}"),
+                    "kind": kind,
+                    "code": location_to_stmt_str(location, self.mir),
+                    "state": self.lookup_before(location),
+                }));
+            }
+        }
+        serde_json::Value::Array(program_points)
+    }
+
     /// Update the state before the `location`.
     /// The `location` can point to a statement or terminator.
     pub(crate) fn set_before(&mut self, location: mir::Location, state: S) {
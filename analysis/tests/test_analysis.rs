@@ -41,6 +41,30 @@ fn test_runner(_tests: &[&()]) {
         "tests/test_cases/reaching_definitions",
         vec!["--analysis=ReachingDefsAnalysis".into()],
     );
+    run_tests(
+        "ui",
+        "tests/test_cases/reaching_definitions_json",
+        vec![
+            "--analysis=reaching_definitions".into(),
+            "--analysis-function=main".into(),
+            "--dump=json".into(),
+        ],
+    );
+    run_tests(
+        "ui",
+        "tests/test_cases/may_alias",
+        vec!["--analysis=MayAliasAnalysis".into()],
+    );
+    run_tests(
+        "ui",
+        "tests/test_cases/maybe_live",
+        vec!["--analysis=MaybeLiveAnalysis".into()],
+    );
+    run_tests(
+        "ui",
+        "tests/test_cases/maybe_returned_args",
+        vec!["--analysis=MaybeReturnedArgsAnalysis".into()],
+    );
     run_tests(
         "ui",
         "tests/test_cases/definitely_initialized",
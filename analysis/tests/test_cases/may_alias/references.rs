@@ -0,0 +1,9 @@
+#[analyzer::run]
+fn main() {
+    let mut a = 1;
+    let b = 2;
+    let x = &mut a;
+    let y = &b;
+    let z = y;
+    let w = &*z;
+}
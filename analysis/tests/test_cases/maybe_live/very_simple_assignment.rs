@@ -0,0 +1,6 @@
+#[analyzer::run]
+fn main() {
+    let x = 123;
+    let y: u32;
+    y = x;
+}
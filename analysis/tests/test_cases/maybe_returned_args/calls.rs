@@ -0,0 +1,62 @@
+fn main() {
+    let a = second(1, 2);
+    let b = constant(&a);
+    let c = recursive(3, b);
+    let d = unknown(vec![a, b, c]);
+    let e = pick_first(a, b);
+    let f = pick_generic(&First, e, c);
+    println!("{} {}", d, f);
+}
+
+#[analyzer::run]
+fn identity<T>(x: T) -> T {
+    x
+}
+
+#[analyzer::run]
+fn second(_a: u32, b: u32) -> u32 {
+    identity(b)
+}
+
+#[analyzer::run]
+fn constant(_a: &u32) -> u32 {
+    42
+}
+
+#[analyzer::run]
+fn recursive(n: u32, acc: u32) -> u32 {
+    if n == 0 {
+        acc
+    } else {
+        recursive(n - 1, acc)
+    }
+}
+
+#[analyzer::run]
+fn unknown(v: Vec<u32>) -> usize {
+    v.len()
+}
+
+trait Pick {
+    fn pick(&self, a: u32, b: u32) -> u32;
+}
+
+struct First;
+
+impl Pick for First {
+    fn pick(&self, a: u32, _b: u32) -> u32 {
+        a
+    }
+}
+
+// The call is resolved to `First::pick`
+#[analyzer::run]
+fn pick_first(a: u32, b: u32) -> u32 {
+    First.pick(a, b)
+}
+
+// The called implementation is not known
+#[analyzer::run]
+fn pick_generic<P: Pick>(p: &P, a: u32, b: u32) -> u32 {
+    p.pick(a, b)
+}
@@ -0,0 +1,5 @@
+fn main() {
+    let x = 123;
+    let y: u32;
+    y = x;
+}
@@ -569,6 +569,8 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for expression::ContainerOp {
             }
             expression::ContainerOpKind::SeqIndex => ast.seq_index(arg(0), arg(1)),
             expression::ContainerOpKind::SeqConcat => ast.seq_append(arg(0), arg(1)),
+            expression::ContainerOpKind::SeqTake => ast.seq_take(arg(0), arg(1)),
+            expression::ContainerOpKind::SeqDrop => ast.seq_drop(arg(0), arg(1)),
             expression::ContainerOpKind::SeqLen => ast.seq_length(arg(0)),
             expression::ContainerOpKind::MapEmpty => {
                 let (key_ty, val_ty) = key_value_types();
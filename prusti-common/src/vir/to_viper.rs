@@ -110,6 +110,9 @@ impl<'v> ToViper<'v, viper::Type<'v>> for Type {
                 key_type.to_viper(_context, ast),
                 val_type.to_viper(_context, ast),
             ),
+            Type::Float(_) if config::float_encoding() == config::FloatEncoding::Real => {
+                ast.perm_type()
+            }
             Type::Float(Float::F32) => ast.backend_f32_type(),
             Type::Float(Float::F64) => ast.backend_f64_type(),
             Type::BitVector(bv_size) => match bv_size {
@@ -414,6 +417,14 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for Expr {
                     pos.to_viper(context, ast),
                 ),
             Expr::UnaryOp(op, ref expr, ref pos) => match expr.get_type() {
+                Type::Float(_) if config::float_encoding() == config::FloatEncoding::Real => {
+                    match op {
+                        UnaryOpKind::Minus => ast.perm_minus(expr.to_viper(context, ast)),
+                        // A real number is never NaN.
+                        UnaryOpKind::IsNaN => ast.false_lit_with_pos(pos.to_viper(context, ast)),
+                        _ => unreachable!("illegal unary operation for floats: {}", op),
+                    }
+                }
                 Type::Float(float_ty) => {
                     let size = match float_ty {
                         Float::F32 => viper::FloatSizeViper::F32,
@@ -446,6 +457,38 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for Expr {
                 },
             },
             Expr::BinOp(op, ref left, ref right, ref pos) => match left.get_maybe_type() {
+                Some(Type::Float(_)) if config::float_encoding() == config::FloatEncoding::Real => {
+                    let viper_left = left.to_viper(context, ast);
+                    let viper_right = right.to_viper(context, ast);
+                    match op {
+                        BinaryOpKind::Add => ast.perm_add(viper_left, viper_right),
+                        BinaryOpKind::Sub => ast.perm_sub(viper_left, viper_right),
+                        BinaryOpKind::Mul => ast.perm_mul(viper_left, viper_right),
+                        BinaryOpKind::EqCmp => {
+                            ast.eq_cmp_with_pos(viper_left, viper_right, pos.to_viper(context, ast))
+                        }
+                        BinaryOpKind::NeCmp => {
+                            ast.ne_cmp_with_pos(viper_left, viper_right, pos.to_viper(context, ast))
+                        }
+                        BinaryOpKind::GtCmp => ast.perm_gt_cmp(viper_left, viper_right),
+                        BinaryOpKind::GeCmp => ast.perm_ge_cmp(viper_left, viper_right),
+                        BinaryOpKind::LtCmp => ast.perm_lt_cmp(viper_left, viper_right),
+                        BinaryOpKind::LeCmp => ast.perm_le_cmp(viper_left, viper_right),
+                        BinaryOpKind::Min => ast.cond_exp(
+                            ast.perm_le_cmp(viper_left, viper_right),
+                            left.to_viper(context, ast),
+                            right.to_viper(context, ast),
+                        ),
+                        BinaryOpKind::Max => ast.cond_exp(
+                            ast.perm_ge_cmp(viper_left, viper_right),
+                            left.to_viper(context, ast),
+                            right.to_viper(context, ast),
+                        ),
+                        _ => {
+                            unreachable!("illegal binary operation for real-encoded floats: {}", op)
+                        }
+                    }
+                }
                 Some(Type::Float(float_ty)) => {
                     let size = match float_ty {
                         Float::F32 => viper::FloatSizeViper::F32,
@@ -778,6 +821,19 @@ impl<'v, 'a, 'b> ToViper<'v, viper::Expr<'v>> for (&'a Const, &'b Position) {
             Const::Bool(false) => ast.false_lit_with_pos(self.1.to_viper(context, ast)),
             Const::Int(x) => ast.int_lit_with_pos(*x, self.1.to_viper(context, ast)),
             Const::BigInt(ref x) => ast.int_lit_from_ref_with_pos(x, self.1.to_viper(context, ast)),
+            Const::Float(float_const)
+                if config::float_encoding() == config::FloatEncoding::Real =>
+            {
+                let value = match float_const {
+                    FloatConst::F32(bits) => f32::from_bits(*bits) as f64,
+                    FloatConst::F64(bits) => f64::from_bits(*bits),
+                };
+                let (numerator, denominator) = float_to_fraction(value);
+                ast.fractional_perm(
+                    ast.int_lit_from_ref(&numerator),
+                    ast.int_lit_from_ref(&denominator),
+                )
+            }
             Const::Float(FloatConst::F32(val)) => ast.backend_f32_lit(*val),
             Const::Float(FloatConst::F64(val)) => ast.backend_f64_lit(*val),
             Const::BitVector(bv_const) => match bv_const.typ {
@@ -1251,6 +1307,74 @@ fn block_to_viper<'a>(
     ast.seqn(&stmts, &[])
 }
 
+/// Returns the numerator and the denominator of the fraction that is exactly
+/// equal to the given finite floating-point number.
+fn float_to_fraction(value: f64) -> (String, String) {
+    assert!(
+        value.is_finite(),
+        "non-finite float {value} has no fraction"
+    );
+    let bits = value.to_bits();
+    let negative = bits >> 63 == 1;
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    // The value is `mantissa * 2^exponent`.
+    let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    if mantissa == 0 {
+        return ("0".to_string(), "1".to_string());
+    }
+    while mantissa % 2 == 0 && exponent < 0 {
+        mantissa /= 2;
+        exponent += 1;
+    }
+    let (numerator, denominator) = if exponent >= 0 {
+        (
+            mul_pow2_to_decimal(mantissa, exponent as u32),
+            "1".to_string(),
+        )
+    } else {
+        (
+            mantissa.to_string(),
+            mul_pow2_to_decimal(1, (-exponent) as u32),
+        )
+    };
+    if negative {
+        (format!("-{numerator}"), denominator)
+    } else {
+        (numerator, denominator)
+    }
+}
+
+/// Returns the decimal representation of `value * 2^exponent`.
+fn mul_pow2_to_decimal(value: u64, exponent: u32) -> String {
+    const BASE: u64 = 1_000_000_000;
+    // Little-endian digits in base `BASE`.
+    let mut digits = vec![value % BASE, (value / BASE) % BASE, value / BASE / BASE];
+    for _ in 0..exponent {
+        let mut carry = 0;
+        for digit in digits.iter_mut() {
+            let doubled = *digit * 2 + carry;
+            *digit = doubled % BASE;
+            carry = doubled / BASE;
+        }
+        if carry > 0 {
+            digits.push(carry);
+        }
+    }
+    while digits.len() > 1 && digits.last() == Some(&0) {
+        digits.pop();
+    }
+    let mut decimal = digits.last().unwrap().to_string();
+    for digit in digits.iter().rev().skip(1) {
+        decimal.push_str(&format!("{digit:09}"));
+    }
+    decimal
+}
+
 fn lower_bitvector_size(size: BitVectorSize) -> viper::BvSize {
     match size {
         BitVectorSize::BV8 => viper::BvSize::BV8,
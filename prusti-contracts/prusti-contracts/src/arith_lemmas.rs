@@ -0,0 +1,79 @@
+//! Lemmas about integer division and remainder. The backend solvers handle
+//! these nonlinear operations poorly, so facts such as `(a * b) / b == a`
+//! often fail to verify unless they are stated explicitly.
+//!
+//! The lemmas are stated for `u128` and apply to all unsigned integer types
+//! through casts, which the `lemma_*!` macros of this crate insert. Calling a
+//! lemma adds its postcondition at the call point only, so no quantified
+//! axioms need to be instantiated by the solver; the encoder does not insert
+//! them by itself. The lemmas are trusted and there are none for signed
+//! integers.
+
+#![allow(unused_variables)]
+
+use crate::*;
+
+/// Lemma: `a` is decomposed into the quotient and the remainder of its
+/// division by `b`, and both are bounded.
+#[trusted]
+#[requires(b > 0)]
+#[ensures(a == (a / b) * b + a % b)]
+#[ensures(a % b < b)]
+#[ensures(a / b <= a)]
+#[ensures(a % b <= a)]
+pub fn div_mod(a: u128, b: u128) {}
+
+/// Lemma: multiplying by `b` and dividing by `b` cancel out.
+#[trusted]
+#[requires(b > 0)]
+#[ensures((a * b) / b == a)]
+#[ensures((a * b) % b == 0)]
+pub fn mul_div_cancel(a: u128, b: u128) {}
+
+/// Lemma: adding a multiple of `b` does not change the remainder modulo `b`
+/// and increases the quotient by the multiple.
+#[trusted]
+#[requires(b > 0)]
+#[ensures((a + k * b) % b == a % b)]
+#[ensures((a + k * b) / b == a / b + k)]
+pub fn div_mod_add_multiple(a: u128, k: u128, b: u128) {}
+
+/// Lemma: division by `b` is monotonic.
+#[trusted]
+#[requires(b > 0 && a <= c)]
+#[ensures(a / b <= c / b)]
+pub fn div_monotonic(a: u128, c: u128, b: u128) {}
+
+/// Lemma: dividing by a larger divisor results in a smaller quotient.
+#[trusted]
+#[requires(0 < b && b <= c)]
+#[ensures(a / c <= a / b)]
+pub fn div_antitonic(a: u128, b: u128, c: u128) {}
+
+/// Inserts the facts of [`div_mod`] about the division of `$a`
+/// by `$b` at the call point, for expressions of any unsigned integer type.
+#[macro_export]
+macro_rules! lemma_div_mod {
+    ($a:expr, $b:expr) => {
+        $crate::arith_lemmas::div_mod($a as u128, $b as u128)
+    };
+}
+
+/// Inserts the facts of [`mul_div_cancel`] about `($a * $b) / $b`
+/// at the call point, for expressions of any unsigned integer type.
+#[macro_export]
+macro_rules! lemma_mul_div {
+    ($a:expr, $b:expr) => {
+        $crate::arith_lemmas::mul_div_cancel($a as u128, $b as u128)
+    };
+}
+
+/// Inserts the facts of [`div_mod_add_multiple`] about
+/// `$a + $k * $b` at the call point, for expressions of any unsigned integer
+/// type.
+#[macro_export]
+macro_rules! lemma_div_mod_add_multiple {
+    ($a:expr, $k:expr, $b:expr) => {
+        $crate::arith_lemmas::div_mod_add_multiple($a as u128, $k as u128, $b as u128)
+    };
+}
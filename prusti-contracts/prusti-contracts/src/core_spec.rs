@@ -15,4 +15,326 @@ impl<T, E> ::core::result::Result<T, E> {
 impl<T, E: ::core::fmt::Debug> ::core::result::Result<T, E> {
     #[requires(matches!(self, Ok(_)))]
     fn unwrap(self) -> T;
+
+    #[requires(matches!(self, Ok(_)))]
+    fn expect(self, msg: &str) -> T;
+}
+
+// The defaults of the types of fields for which `#[derive(Default)]` yields a
+// pure implementation.
+macro_rules! __default_spec__ {
+    ($($ty:ident = $value:literal),*) => {$(
+        #[extern_spec]
+        impl Default for $ty {
+            #[pure]
+            #[ensures(result == $value)]
+            fn default() -> Self;
+        }
+    )*}
+}
+
+__default_spec__!(
+    bool = false,
+    char = '\0',
+    i8 = 0,
+    i16 = 0,
+    i32 = 0,
+    i64 = 0,
+    i128 = 0,
+    isize = 0,
+    u8 = 0,
+    u16 = 0,
+    u32 = 0,
+    u64 = 0,
+    u128 = 0,
+    usize = 0
+);
+
+// A value is its own borrowed form, so looking up a key of a map by the key
+// itself finds the same entry as looking it up by any other borrowed form.
+#[extern_spec]
+impl<T: ?Sized> ::core::borrow::Borrow<T> for T {
+    #[pure]
+    #[ensures(result === self)]
+    fn borrow(&self) -> &T;
+}
+
+// Characters are compared by their code points, so the ASCII classes are
+// ranges of characters.
+#[extern_spec]
+impl char {
+    #[pure]
+    #[ensures(result == (*self <= '\x7f'))]
+    fn is_ascii(&self) -> bool;
+
+    #[pure]
+    #[ensures(result == ('0' <= *self && *self <= '9'))]
+    fn is_ascii_digit(&self) -> bool;
+
+    #[pure]
+    #[ensures(result == ('a' <= *self && *self <= 'z'))]
+    fn is_ascii_lowercase(&self) -> bool;
+
+    #[pure]
+    #[ensures(result == ('A' <= *self && *self <= 'Z'))]
+    fn is_ascii_uppercase(&self) -> bool;
+
+    #[pure]
+    #[ensures(result == ('a' <= *self && *self <= 'z' || 'A' <= *self && *self <= 'Z'))]
+    fn is_ascii_alphabetic(&self) -> bool;
+
+    #[pure]
+    #[ensures(result == (
+        'a' <= *self && *self <= 'z'
+            || 'A' <= *self && *self <= 'Z'
+            || '0' <= *self && *self <= '9'
+    ))]
+    fn is_ascii_alphanumeric(&self) -> bool;
+
+    #[pure]
+    #[ensures(if 'A' <= *self && *self <= 'Z' {
+        result as u32 == *self as u32 + 32
+    } else {
+        result == *self
+    })]
+    fn to_ascii_lowercase(&self) -> char;
+
+    #[pure]
+    #[ensures(if 'a' <= *self && *self <= 'z' {
+        result as u32 == *self as u32 - 32
+    } else {
+        result == *self
+    })]
+    fn to_ascii_uppercase(&self) -> char;
+
+    #[pure]
+    #[ensures(result == if (self as u32) < 0x80 {
+        1
+    } else if (self as u32) < 0x800 {
+        2
+    } else if (self as u32) < 0x10000 {
+        3
+    } else {
+        4
+    })]
+    fn len_utf8(self) -> usize;
+
+    // Only the digits of radixes up to 10 are specified; the letters used as
+    // digits by larger radixes are not.
+    #[pure]
+    #[requires(radix <= 36)]
+    #[ensures(radix <= 10 ==> result == if '0' <= self && (self as u32) < '0' as u32 + radix {
+        Some(self as u32 - '0' as u32)
+    } else {
+        None
+    })]
+    fn to_digit(self, radix: u32) -> Option<u32>;
+
+    #[pure]
+    #[ensures(if i < 0xD800 || 0xDFFF < i && i <= 0x10FFFF {
+        matches!(result, Some(c) if c as u32 == i)
+    } else {
+        matches!(result, None)
+    })]
+    fn from_u32(i: u32) -> Option<char>;
+}
+
+#[extern_spec]
+trait Iterator {
+    // If the new step overflows, at most one value is yielded and the step
+    // is left unspecified.
+    #[requires(step > 0)]
+    #[refine_spec(where Self: RangeIter, [
+        ensures(result.range_first() == self.range_first()),
+        ensures(result.range_len() == if self.range_len() == 0 {
+            0
+        } else {
+            (self.range_len() - 1) / step + 1
+        }),
+        ensures(self.range_step() <= usize::MAX / step ==>
+            result.range_step() == self.range_step() * step),
+        ensures(result.range_rev() == self.range_rev()),
+    ])]
+    fn step_by(self, step: usize) -> ::core::iter::StepBy<Self>
+    where
+        Self: Sized;
+
+    #[refine_spec(where Self: RangeIter, [
+        ensures(self.range_len() > 0 ==> result.range_first() == if self.range_rev() {
+            self.range_first() - (self.range_len() - 1) * self.range_step()
+        } else {
+            self.range_first() + (self.range_len() - 1) * self.range_step()
+        }),
+        ensures(result.range_len() == self.range_len()),
+        ensures(result.range_step() == self.range_step()),
+        ensures(result.range_rev() == !self.range_rev()),
+    ])]
+    fn rev(self) -> ::core::iter::Rev<Self>
+    where
+        Self: Sized + DoubleEndedIterator;
+
+    #[refine_spec(where Self: RangeIter, [
+        ensures(n < self.range_len() ==> result.range_first() == if self.range_rev() {
+            self.range_first() - n * self.range_step()
+        } else {
+            self.range_first() + n * self.range_step()
+        }),
+        ensures(result.range_len() == if n < self.range_len() { self.range_len() - n } else { 0 }),
+        ensures(result.range_step() == self.range_step()),
+        ensures(result.range_rev() == self.range_rev()),
+    ])]
+    fn skip(self, n: usize) -> ::core::iter::Skip<Self>
+    where
+        Self: Sized;
+
+    #[refine_spec(where Self: RangeIter, [
+        ensures(result.range_first() == self.range_first()),
+        ensures(result.range_len() == if n < self.range_len() { n } else { self.range_len() }),
+        ensures(result.range_step() == self.range_step()),
+        ensures(result.range_rev() == self.range_rev()),
+    ])]
+    #[refine_spec(where Self: SeqIter, Self::Item: Copy, [
+        ensures(result.remaining() === if Int::new_usize(n) < self.remaining().len() {
+            self.remaining().take(Int::new_usize(n))
+        } else {
+            self.remaining()
+        }),
+    ])]
+    fn take(self, n: usize) -> ::core::iter::Take<Self>
+    where
+        Self: Sized;
+
+    #[refine_spec(where Self: SeqIter, Self::Item: Copy, [
+        ensures(result.remaining().len() == self.remaining().len()),
+        ensures(forall(|i: usize| Int::new_usize(i) < self.remaining().len() ==>
+            result.remaining()[i].0 == i && result.remaining()[i].1 === self.remaining()[i])),
+    ])]
+    fn enumerate(self) -> ::core::iter::Enumerate<Self>
+    where
+        Self: Sized;
+
+    #[refine_spec(where Self: SeqIter, Self::Item: Copy, B: Copy, [
+        ensures(result.remaining().len() == self.remaining().len()),
+        ensures(forall(|i: usize| Int::new_usize(i) < self.remaining().len() ==>
+            closure_ensures(&f, self.remaining()[i], result.remaining()[i]))),
+    ])]
+    fn map<B, F>(self, f: F) -> ::core::iter::Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> B;
+
+    #[refine_spec(where Self: SeqIter, Self::Item: Copy, [
+        ensures(result.remaining().len() <= self.remaining().len()),
+        ensures(forall(|i: usize| Int::new_usize(i) < result.remaining().len() ==>
+            closure_ensures(&predicate, &result.remaining()[i], true))),
+    ])]
+    fn filter<P>(self, predicate: P) -> ::core::iter::Filter<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool;
+}
+
+#[extern_spec]
+impl<A: ::core::iter::Step> Iterator for ::core::ops::Range<A> {
+    #[refine_spec(where ::core::ops::Range<A>: RangeIter, [
+        ensures(range_iter::range_iter_advanced(
+            old(self.range_first()),
+            old(self.range_len()),
+            old(self.range_step()),
+            old(self.range_rev()),
+            &*self,
+            result,
+        )),
+    ])]
+    #[refine_spec(where ::core::ops::Range<A>: SeqIter, A: Copy, [
+        ensures(seq_iter::seq_iter_advanced(old(self.remaining()), &*self, result)),
+    ])]
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+#[extern_spec]
+impl<I: Iterator> Iterator for ::core::iter::StepBy<I> {
+    #[refine_spec(where I: RangeIter, [
+        ensures(range_iter::range_iter_advanced(
+            old(self.range_first()),
+            old(self.range_len()),
+            old(self.range_step()),
+            old(self.range_rev()),
+            &*self,
+            result,
+        )),
+    ])]
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+#[extern_spec]
+impl<I: DoubleEndedIterator> Iterator for ::core::iter::Rev<I> {
+    #[refine_spec(where I: RangeIter, [
+        ensures(range_iter::range_iter_advanced(
+            old(self.range_first()),
+            old(self.range_len()),
+            old(self.range_step()),
+            old(self.range_rev()),
+            &*self,
+            result,
+        )),
+    ])]
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+#[extern_spec]
+impl<I: Iterator> Iterator for ::core::iter::Skip<I> {
+    #[refine_spec(where I: RangeIter, [
+        ensures(range_iter::range_iter_advanced(
+            old(self.range_first()),
+            old(self.range_len()),
+            old(self.range_step()),
+            old(self.range_rev()),
+            &*self,
+            result,
+        )),
+    ])]
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+#[extern_spec]
+impl<I: Iterator> Iterator for ::core::iter::Take<I> {
+    #[refine_spec(where I: RangeIter, [
+        ensures(range_iter::range_iter_advanced(
+            old(self.range_first()),
+            old(self.range_len()),
+            old(self.range_step()),
+            old(self.range_rev()),
+            &*self,
+            result,
+        )),
+    ])]
+    #[refine_spec(where I: SeqIter, I::Item: Copy, [
+        ensures(seq_iter::seq_iter_advanced(old(self.remaining()), &*self, result)),
+    ])]
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+#[extern_spec]
+impl<I: Iterator> Iterator for ::core::iter::Enumerate<I> {
+    #[refine_spec(where I: SeqIter, I::Item: Copy, [
+        ensures(seq_iter::seq_iter_advanced(old(self.remaining()), &*self, result)),
+    ])]
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+#[extern_spec]
+impl<B, I: Iterator, F: FnMut(I::Item) -> B> Iterator for ::core::iter::Map<I, F> {
+    #[refine_spec(where I: SeqIter, B: Copy, [
+        ensures(seq_iter::seq_iter_advanced(old(self.remaining()), &*self, result)),
+    ])]
+    fn next(&mut self) -> Option<B>;
+}
+
+#[extern_spec]
+impl<I: Iterator, P: FnMut(&I::Item) -> bool> Iterator for ::core::iter::Filter<I, P> {
+    #[refine_spec(where I: SeqIter, I::Item: Copy, [
+        ensures(seq_iter::seq_iter_advanced(old(self.remaining()), &*self, result)),
+    ])]
+    fn next(&mut self) -> Option<I::Item>;
 }
@@ -0,0 +1,39 @@
+//! Frame conditions for functions which modify a single element of a slice or
+//! a single entry of a map, such as `#[ensures(unchanged_except!(a, i))]`.
+
+use crate::*;
+
+// The slices `a` and `b` have the same length and the same elements, except
+// possibly at the index `i`.
+predicate! {
+    pub fn equal_except<T>(a: &[T], b: &[T], i: usize) -> bool {
+        a.len() == b.len()
+            && forall(|k: usize| (k < a.len() && k != i) ==> a[k] === b[k])
+    }
+}
+
+/// Frame condition: the mutable slice `$a` has the same length and the same
+/// elements as at the beginning of the function, except possibly at the index
+/// `$i`.
+///
+/// With a key type, `unchanged_except!($map, $key, $K)` is the frame condition
+/// of a map with keys of type `$K`: the result of the pure methods
+/// `contains_key(&k)` and `get(&k)` of `$map` is unchanged for every key `k`
+/// other than `$key`.
+#[macro_export]
+macro_rules! unchanged_except {
+    ($a:expr, $i:expr) => {
+        $crate::frame::equal_except(&$a[..], &$crate::old(&*$a)[..], $i)
+    };
+    ($map:expr, $key:expr, $K:ty) => {
+        $crate::prusti_expr!(
+            forall(
+                |k: $K| !(k === $key) ==> (
+                    $map.contains_key(&k) == $crate::old($map.contains_key(&k))
+                        && $map.get(&k) === $crate::old($map.get(&k))
+                ),
+                triggers = [($map.contains_key(&k),), ($map.get(&k),)]
+            )
+        )
+    };
+}
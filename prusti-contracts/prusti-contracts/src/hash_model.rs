@@ -0,0 +1,27 @@
+//! A model of the hash function used by hash-based collections, such as
+//! `HashMap`, for specifying their bucket behavior and iteration order.
+//!
+//! The meaning of `hash` is selected with the `hash_model` configuration flag.
+//! By default (`uninterpreted`), the hash is an unknown, but deterministic,
+//! function of the value, so proofs hold for every hasher. With
+//! `-Phash_model=identity`, the hash of an unsigned integer of at most 64 bits
+//! is the integer itself, which is what identity hashers assume.
+
+#![allow(unused_variables)]
+
+use crate::*;
+use core::hash::Hash;
+
+/// The hash of `value`.
+#[pure]
+#[trusted]
+pub fn hash<T: Hash>(value: &T) -> u64 {
+    unimplemented!()
+}
+
+/// The bucket of `value` in a table with `buckets` buckets.
+#[pure]
+#[requires(buckets > 0)]
+pub fn bucket<T: Hash>(value: &T, buckets: usize) -> usize {
+    (hash(value) % buckets as u64) as usize
+}
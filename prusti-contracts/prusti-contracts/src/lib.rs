@@ -1,4 +1,5 @@
 #![no_std]
+#![cfg_attr(feature = "prusti", feature(step_trait))]
 
 /// A macro for writing a precondition on a function.
 pub use prusti_contracts_proc_macros::requires;
@@ -6,6 +7,10 @@ pub use prusti_contracts_proc_macros::requires;
 /// A macro for writing a postcondition on a function.
 pub use prusti_contracts_proc_macros::ensures;
 
+/// A macro for writing a postcondition on the output of the future returned
+/// by a function.
+pub use prusti_contracts_proc_macros::ensures_on_completion;
+
 /// A macro for writing a pledge on a function.
 pub use prusti_contracts_proc_macros::after_expiry;
 
@@ -15,18 +20,38 @@ pub use prusti_contracts_proc_macros::assert_on_expiry;
 /// A macro for marking a function as pure.
 pub use prusti_contracts_proc_macros::pure;
 
-/// A macro for marking a function as trusted.
+/// A macro for marking a function or a type as trusted, optionally with the
+/// reason why it is trusted, as in `#[trusted("reason")]`.
 pub use prusti_contracts_proc_macros::trusted;
 
 /// A macro for marking a function as opted into verification.
 pub use prusti_contracts_proc_macros::verified;
 
+/// A macro for declaring a law of a trait, which every implementation must
+/// verify.
+pub use prusti_contracts_proc_macros::law;
+
+/// A macro for opting a function into the experimental verification of raw
+/// pointer dereferences.
+pub use prusti_contracts_proc_macros::trusted_unsafe_memory;
+
+/// A macro for overriding the verification timeout of a function, in
+/// milliseconds.
+pub use prusti_contracts_proc_macros::timeout;
+
 /// A macro for type invariants.
 pub use prusti_contracts_proc_macros::invariant;
 
 /// A macro for writing a loop body invariant.
 pub use prusti_contracts_proc_macros::body_invariant;
 
+/// A macro for writing a loop invariant immediately before the loop.
+pub use prusti_contracts_proc_macros::loop_invariant;
+
+/// A macro for marking the enclosing loop to be verified by unrolling it up to
+/// a bound instead of with a loop invariant.
+pub use prusti_contracts_proc_macros::no_invariant_needed;
+
 /// A macro for writing assertions using the full prusti specifications
 pub use prusti_contracts_proc_macros::prusti_assert;
 
@@ -36,9 +61,17 @@ pub use prusti_contracts_proc_macros::prusti_assume;
 /// A macro for writing refutations using prusti syntax
 pub use prusti_contracts_proc_macros::prusti_refute;
 
+/// A macro for translating an expression in prusti syntax to Rust. It can be
+/// used to define custom specification macros, which expand to prusti syntax.
+pub use prusti_contracts_proc_macros::prusti_expr;
+
 /// A macro for impl blocks that refine trait specifications.
 pub use prusti_contracts_proc_macros::refine_trait_spec;
 
+/// A macro for trait implementations which only exist during verification,
+/// e.g. to give a meaning to foreign traits in specifications.
+pub use prusti_contracts_proc_macros::ghost_impl;
+
 /// A macro for specifying external functions.
 pub use prusti_contracts_proc_macros::extern_spec;
 
@@ -66,6 +99,10 @@ pub use prusti_contracts_proc_macros::terminates;
 /// A macro to annotate body variant of a loop to prove termination
 pub use prusti_contracts_proc_macros::body_variant;
 
+/// A macro that evaluates to `true` if the crate is being verified by Prusti
+/// and to `false` otherwise, e.g. to enable proof hints only during verification
+pub use prusti_contracts_proc_macros::is_verified_call;
+
 #[cfg(not(feature = "prusti"))]
 mod private {
     use core::marker::PhantomData;
@@ -124,6 +161,34 @@ mod private {
 #[cfg(feature = "prusti")]
 pub mod core_spec;
 
+pub mod arith_lemmas;
+
+pub mod frame;
+
+pub mod hash_model;
+
+pub mod math;
+
+pub mod range_iter;
+
+pub mod seq_iter;
+
+pub mod seq_lemmas;
+
+pub mod slices;
+
+#[cfg(feature = "prusti")]
+pub use slices::{permutation_of, permuted_by, swapped};
+
+pub use range_iter::RangeIter;
+
+pub use seq_iter::SeqIter;
+
+// Allows the specifications in this crate to refer to the `::prusti_contracts`
+// paths generated by the specification macros.
+#[cfg(feature = "prusti")]
+extern crate self as prusti_contracts;
+
 #[cfg(feature = "prusti")]
 mod private {
     use core::{marker::PhantomData, ops::*};
@@ -217,6 +282,14 @@ mod private {
         pub fn concat(self, _: Self) -> Self {
             panic!()
         }
+        /// The prefix of the sequence of length `n`.
+        pub fn take(self, _n: Int) -> Self {
+            panic!()
+        }
+        /// The sequence without its prefix of length `n`.
+        pub fn drop(self, _n: Int) -> Self {
+            panic!()
+        }
         pub fn lookup(self, _index: usize) -> T {
             panic!()
         }
@@ -339,6 +412,28 @@ pub fn old<T>(arg: T) -> T {
     arg
 }
 
+/// This function is used in loop invariants to evaluate an expression in the
+/// state in which the loop invariant held in the previous iteration. On loop
+/// entry, that is the current state.
+pub fn prev_iter<T>(arg: T) -> T {
+    arg
+}
+
+/// The result of the last call of the given function made by the specified
+/// function. The call is not executed again.
+///
+/// This is a Prusti-internal representation of the `call_summary!` syntax.
+pub fn call_summary<T, F: FnOnce() -> T>(_call: F) -> T {
+    unimplemented!()
+}
+
+/// Holds if the postcondition of the function or closure `f` holds for a call
+/// with the single argument `arg` that returned `result`. Nothing is known
+/// about the call if the type of `f` is not a function or a closure.
+pub fn closure_ensures<F, A, R>(_f: &F, _arg: A, _result: R) -> bool {
+    true
+}
+
 /// Universal quantifier.
 ///
 /// This is a Prusti-internal representation of the `forall` syntax.
@@ -368,4 +463,12 @@ pub fn snapshot_equality<T>(_l: T, _r: T) -> bool {
     true
 }
 
+/// Implemented by a type `T` only for `T` itself. This is a Prusti-internal
+/// representation of type equality constraints, such as `T = u32` in
+/// `#[refine_spec(where T = u32, [...])]`.
+#[doc(hidden)]
+pub trait SameType<T> {}
+
+impl<T> SameType<T> for T {}
+
 pub use private::*;
@@ -0,0 +1,81 @@
+//! Mathematical functions for specifications, such as bounds on the number of
+//! steps of divide-and-conquer algorithms, and lemmas about them.
+//!
+//! The functions are defined recursively, which makes them unfold one step at
+//! a time during verification. Facts that would need induction are provided
+//! as trusted lemmas.
+
+#![allow(unused_variables)]
+
+use crate::*;
+
+/// `base` raised to the power `exp`. The power has to fit in a `u128`, as
+/// ensured by `pow_in_range`.
+#[pure]
+#[requires(pow_in_range(base, exp))]
+pub fn pow(base: u128, exp: u128) -> u128 {
+    if exp == 0 {
+        1
+    } else {
+        base * pow(base, exp - 1)
+    }
+}
+
+/// Whether `pow(base, exp)` is known to fit in a `u128`: either the base is
+/// at most `2` and the exponent at most `127`, or `exp * (log2(base) + 1)`,
+/// the number of bits of the power, is at most `128`.
+#[pure]
+pub fn pow_in_range(base: u128, exp: u128) -> bool {
+    base <= 2 && exp < 128 || exp * (log2(base) + 1) <= 128
+}
+
+/// The base-2 logarithm of `n`, rounded down. By convention, `log2(0)` is `0`.
+#[pure]
+pub fn log2(n: u128) -> u128 {
+    if n <= 1 {
+        0
+    } else {
+        1 + log2(n / 2)
+    }
+}
+
+/// Lemma: the product of two powers of the same base is a power of the base.
+#[trusted]
+#[requires(pow_in_range(base, m + n))]
+#[ensures(pow(base, m + n) == pow(base, m) * pow(base, n))]
+pub fn pow_add(base: u128, m: u128, n: u128) {}
+
+/// Lemma: powers of a positive base are monotonic in the exponent.
+#[trusted]
+#[requires(base >= 1 && m <= n && pow_in_range(base, n))]
+#[ensures(pow(base, m) <= pow(base, n))]
+pub fn pow_monotonic(base: u128, m: u128, n: u128) {}
+
+/// Lemma: `log2` is the inverse of the powers of two.
+#[trusted]
+#[requires(k < 128)]
+#[ensures(log2(pow(2, k)) == k)]
+pub fn log2_pow2(k: u128) {}
+
+/// Lemma: a positive `n` lies between the power of two of its logarithm and
+/// the next power of two.
+#[trusted]
+#[requires(n >= 1)]
+#[ensures(log2(n) < 128)]
+#[ensures(pow(2, log2(n)) <= n)]
+#[ensures(n < 2 * pow(2, log2(n)))]
+pub fn log2_bounds(n: u128) {}
+
+/// Lemma: `log2` is monotonic.
+#[trusted]
+#[requires(m <= n)]
+#[ensures(log2(m) <= log2(n))]
+pub fn log2_monotonic(m: u128, n: u128) {}
+
+/// Lemma: the `n * log2(n)` bound of a divide-and-conquer algorithm that
+/// solves two halves of a problem of size `n` and combines them in `n` steps,
+/// as in `cost(n) == 2 * cost(n / 2) + n`.
+#[trusted]
+#[requires(n >= 2 && n <= 18_446_744_073_709_551_615)]
+#[ensures(2 * ((n / 2) * log2(n / 2)) + n <= n * log2(n))]
+pub fn n_log2_n_split(n: u128) {}
@@ -0,0 +1,120 @@
+//! Models of iterators over ranges of `usize` values, possibly adapted with
+//! `step_by`, `rev`, `skip` and `take`, such as `(0..n).step_by(2).rev()`.
+//!
+//! The values which such an iterator has not yet yielded are described by an
+//! arithmetic progression: `range_len()` values starting at `range_first()`,
+//! which are `range_step()` apart and decrease if `range_rev()` holds. The
+//! specifications of the iterator methods in terms of these models are in
+//! `core_spec`.
+
+use crate::*;
+use core::{
+    iter::{Rev, Skip, StepBy, Take},
+    ops::Range,
+};
+
+/// An iterator over a range of `usize` values.
+pub trait RangeIter: Iterator<Item = usize> {
+    /// The value yielded next, if `range_len() > 0`.
+    #[pure]
+    fn range_first(&self) -> usize;
+
+    /// The number of values which are still to be yielded.
+    #[pure]
+    fn range_len(&self) -> usize;
+
+    /// The distance between consecutive yielded values.
+    #[pure]
+    fn range_step(&self) -> usize;
+
+    /// Whether the yielded values decrease.
+    #[pure]
+    fn range_rev(&self) -> bool;
+}
+
+impl RangeIter for Range<usize> {
+    #[pure]
+    fn range_first(&self) -> usize {
+        self.start
+    }
+
+    // `saturating_sub` has no specification that pure functions could use
+    #[allow(clippy::implicit_saturating_sub)]
+    #[pure]
+    fn range_len(&self) -> usize {
+        if self.start < self.end {
+            self.end - self.start
+        } else {
+            0
+        }
+    }
+
+    #[pure]
+    fn range_step(&self) -> usize {
+        1
+    }
+
+    #[pure]
+    fn range_rev(&self) -> bool {
+        false
+    }
+}
+
+// The state of the adapters is private, so their models are abstract and only
+// constrained by the specifications of the methods creating and advancing them.
+macro_rules! __abstract_range_iter_impl__ {
+    ($($adapter:ident $(+ $bound:path)?),*) => {$(
+        impl<I: RangeIter $(+ $bound)?> RangeIter for $adapter<I> {
+            #[pure]
+            #[trusted]
+            fn range_first(&self) -> usize {
+                unimplemented!()
+            }
+
+            #[pure]
+            #[trusted]
+            fn range_len(&self) -> usize {
+                unimplemented!()
+            }
+
+            #[pure]
+            #[trusted]
+            fn range_step(&self) -> usize {
+                unimplemented!()
+            }
+
+            #[pure]
+            #[trusted]
+            fn range_rev(&self) -> bool {
+                unimplemented!()
+            }
+        }
+    )*}
+}
+
+__abstract_range_iter_impl__!(StepBy, Rev + DoubleEndedIterator, Skip, Take);
+
+/// Holds if a range iterator, whose model was `first`, `len`, `step` and
+/// `rev`, yielded `result` and then had the state `iter`.
+#[pure]
+pub fn range_iter_advanced<I: RangeIter>(
+    first: usize,
+    len: usize,
+    step: usize,
+    rev: bool,
+    iter: &I,
+    result: Option<usize>,
+) -> bool {
+    iter.range_step() == step
+        && iter.range_rev() == rev
+        && match result {
+            Some(value) => {
+                len > 0
+                    && value == first
+                    && iter.range_len() == len - 1
+                    && (iter.range_len() == 0
+                        || iter.range_first() == if rev { first - step } else { first + step })
+            }
+            None => len == 0 && iter.range_len() == 0,
+        }
+}
@@ -0,0 +1,80 @@
+//! Models of iterator pipelines, such as `(0..n).map(f).enumerate().take(k)`,
+//! by the sequence of values which the iterator has not yet yielded.
+//!
+//! Advancing an iterator with `next()` yields the first value of its model
+//! and drops it from the model. The adapters `map`, `filter`, `enumerate` and
+//! `take` relate the model of the adapted iterator to the one of the
+//! underlying iterator. The specifications of these methods are in
+//! `core_spec`. The values yielded by `map` and `filter` are related to the
+//! closure by its postcondition: every value yielded by `map(f)` satisfies the
+//! postcondition of `f` for the corresponding value of the underlying
+//! iterator, and `predicate` returns `true` for every value yielded by
+//! `filter(predicate)`.
+
+#![allow(unused_variables)]
+
+use crate::*;
+use core::{
+    iter::{Enumerate, Filter, Map, Take},
+    ops::Range,
+};
+
+/// An iterator whose not yet yielded values are described by a sequence.
+pub trait SeqIter: Iterator {
+    /// The values which are still to be yielded, in order.
+    #[pure]
+    fn remaining(&self) -> Seq<Self::Item>
+    where
+        Self::Item: Copy;
+}
+
+impl SeqIter for Range<usize> {
+    #[pure]
+    #[trusted]
+    #[ensures(result.len() == Int::new_usize(self.range_len()))]
+    #[ensures(forall(|i: usize| i < self.range_len() ==> result[i] == self.start + i))]
+    fn remaining(&self) -> Seq<usize> {
+        unimplemented!()
+    }
+}
+
+// The state of the adapters is private, so their models are abstract and only
+// constrained by the specifications of the methods creating and advancing them.
+macro_rules! __abstract_seq_iter_impl__ {
+    ($([$($generics:tt)*] $adapter:ty),*) => {$(
+        impl<$($generics)*> SeqIter for $adapter {
+            #[pure]
+            #[trusted]
+            fn remaining(&self) -> Seq<Self::Item>
+            where
+                Self::Item: Copy,
+            {
+                unimplemented!()
+            }
+        }
+    )*}
+}
+
+__abstract_seq_iter_impl__!(
+    [I: SeqIter] Enumerate<I>,
+    [I: SeqIter] Take<I>,
+    [B, I: SeqIter, F: FnMut(I::Item) -> B] Map<I, F>,
+    [I: SeqIter, P: FnMut(&I::Item) -> bool] Filter<I, P>
+);
+
+predicate! {
+    /// Holds if an iterator, whose model was `remaining`, yielded `result` and
+    /// then had the state `iter`.
+    pub fn seq_iter_advanced<T: Copy, I: SeqIter<Item = T>>(
+        remaining: Seq<T>,
+        iter: &I,
+        result: Option<T>,
+    ) -> bool {
+        match result {
+            Some(value) => remaining.len() > Int::new(0)
+                && value === remaining[0usize]
+                && iter.remaining() === remaining.drop(Int::new(1)),
+            None => remaining.len() == Int::new(0) && iter.remaining().len() == Int::new(0),
+        }
+    }
+}
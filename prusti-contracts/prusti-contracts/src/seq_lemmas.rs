@@ -0,0 +1,71 @@
+//! Lemmas about the ghost sequence type `Seq`, to be called from ghost code
+//! whenever the verifier needs one of these facts about concrete sequences.
+//!
+//! The lemmas follow from the axioms of the sequence theory of the
+//! verification backend. They are marked as trusted so that their proofs are
+//! not repeated in every crate using them; their statements are verified
+//! without `#[trusted]` by the test `core_proof/seq_lemmas_statements.rs` of
+//! `prusti-tests`.
+
+#![allow(unused_variables)]
+
+use crate::*;
+
+/// Lemma: concatenation of sequences is associative.
+#[trusted]
+#[ensures(a.concat(b).concat(c) === a.concat(b.concat(c)))]
+pub fn concat_assoc<T: Copy>(a: Seq<T>, b: Seq<T>, c: Seq<T>) {}
+
+/// Lemma: the empty sequence is the neutral element of concatenation.
+#[trusted]
+#[ensures(a.concat(Seq::empty()) === a)]
+#[ensures(Seq::empty().concat(a) === a)]
+pub fn concat_empty<T: Copy>(a: Seq<T>) {}
+
+/// Lemma: the length of a concatenation is the sum of the lengths.
+#[trusted]
+#[ensures(a.concat(b).len() == a.len() + b.len())]
+pub fn concat_len<T: Copy>(a: Seq<T>, b: Seq<T>) {}
+
+/// Lemma: indexing into a concatenation indexes into the left sequence for
+/// indices below its length, and into the right sequence otherwise.
+#[trusted]
+#[requires(Int::new(0) <= i && i < a.len() + b.len())]
+#[ensures(i < a.len() ==> a.concat(b)[i] === a[i])]
+#[ensures(i >= a.len() ==> a.concat(b)[i] === b[i - a.len()])]
+pub fn lookup_concat<T: Copy>(a: Seq<T>, b: Seq<T>, i: Int) {}
+
+/// Lemma: the lengths of the prefix and of the suffix of a sequence split at
+/// `n`.
+#[trusted]
+#[requires(Int::new(0) <= n && n <= a.len())]
+#[ensures(a.take(n).len() == n)]
+#[ensures(a.drop(n).len() == a.len() - n)]
+pub fn take_drop_len<T: Copy>(a: Seq<T>, n: Int) {}
+
+/// Lemma: splitting a sequence at `n` and concatenating the parts results in
+/// the original sequence.
+#[trusted]
+#[requires(Int::new(0) <= n && n <= a.len())]
+#[ensures(a.take(n).concat(a.drop(n)) === a)]
+pub fn take_concat_drop<T: Copy>(a: Seq<T>, n: Int) {}
+
+/// Lemma: the elements of a prefix are the elements of the sequence.
+#[trusted]
+#[requires(Int::new(0) <= i && i < n && n <= a.len())]
+#[ensures(a.take(n)[i] === a[i])]
+pub fn lookup_take<T: Copy>(a: Seq<T>, n: Int, i: Int) {}
+
+/// Lemma: the elements of a suffix are the elements of the sequence, shifted
+/// by the length of the dropped prefix.
+#[trusted]
+#[requires(Int::new(0) <= n && Int::new(0) <= i && n + i < a.len())]
+#[ensures(a.drop(n)[i] === a[n + i])]
+pub fn lookup_drop<T: Copy>(a: Seq<T>, n: Int, i: Int) {}
+
+/// Lemma: splitting a concatenation at the length of the left sequence
+/// results in the two concatenated sequences.
+#[trusted]
+#[ensures(a.concat(b).take(a.len()) === a)]
+#[ensures(a.concat(b).drop(a.len()) === b)]
+pub fn split_concat<T: Copy>(a: Seq<T>, b: Seq<T>) {}
@@ -0,0 +1,125 @@
+//! Predicates and lemmas for specifying algorithms which rearrange the
+//! elements of slices, such as sorts and shuffles.
+
+#![allow(unused_variables)]
+
+use crate::*;
+
+// The slice `a` is equal to the slice `b` with the elements at the indices `i`
+// and `j` exchanged. In a postcondition, the effect of swapping in a mutable
+// slice `a` is stated as `swapped(a, old(&*a), i, j)`.
+predicate! {
+    pub fn swapped<T>(a: &[T], b: &[T], i: usize, j: usize) -> bool {
+        a.len() == b.len() && i < a.len() && j < a.len()
+            && a[i] === b[j] && a[j] === b[i]
+            && forall(|k: usize| (k < a.len() && k != i && k != j) ==> a[k] === b[k])
+    }
+}
+
+// The slice `a` is the slice `b` rearranged by the permutation `p`, i.e. the
+// element at the index `k` of `a` is the one at the index `p[k]` of `b`. The
+// slice `q` is the inverse of `p`, which ensures that no index of `b` is used
+// twice. Unlike `permutation_of`, this predicate has a body, so algorithms that
+// maintain `p` and `q` along with `a` verify it without lemmas.
+predicate! {
+    pub fn permuted_by<T>(a: &[T], b: &[T], p: &[usize], q: &[usize]) -> bool {
+        a.len() == b.len() && p.len() == a.len() && q.len() == a.len()
+            && forall(|k: usize| k < a.len() ==> (p[k] < a.len() && a[k] === b[p[k]] && q[p[k]] == k))
+    }
+}
+
+/// The slices `a` and `b` contain the same elements with the same
+/// multiplicities, possibly in a different order. This predicate is left
+/// abstract; facts about it are established with the lemmas of this module.
+#[cfg(feature = "prusti")]
+#[pure]
+#[trusted]
+pub fn permutation_of<T>(_a: &[T], _b: &[T]) -> bool {
+    unimplemented!()
+}
+
+/// Lemma: every slice is a permutation of itself.
+#[trusted]
+#[ensures(permutation_of(a, a))]
+pub fn permutation_refl<T>(a: &[T]) {}
+
+/// Lemma: `permutation_of` is symmetric.
+#[trusted]
+#[requires(permutation_of(a, b))]
+#[ensures(permutation_of(b, a))]
+pub fn permutation_symm<T>(a: &[T], b: &[T]) {}
+
+/// Lemma: `permutation_of` is transitive.
+#[trusted]
+#[requires(permutation_of(a, b) && permutation_of(b, c))]
+#[ensures(permutation_of(a, c))]
+pub fn permutation_trans<T>(a: &[T], b: &[T], c: &[T]) {}
+
+/// Lemma: exchanging two elements of a slice results in a permutation of it.
+#[trusted]
+#[requires(swapped(a, b, i, j))]
+#[ensures(permutation_of(a, b))]
+pub fn swapped_permutation<T>(a: &[T], b: &[T], i: usize, j: usize) {}
+
+/// Lemma: a slice rearranged by a permutation is a permutation of it.
+#[trusted]
+#[requires(permuted_by(a, b, p, q))]
+#[ensures(permutation_of(a, b))]
+pub fn permuted_by_permutation<T>(a: &[T], b: &[T], p: &[usize], q: &[usize]) {}
+
+/// Invariant template: the elements of the slice `$a` in the index range
+/// `$lo..$hi` are sorted in ascending order. Without a range, the whole slice
+/// is sorted.
+#[macro_export]
+macro_rules! sorted_range {
+    ($a:expr) => {
+        $crate::prusti_expr!(
+            forall(|i: usize, j: usize| (i <= j && j < $a.len()) ==> $a[i] <= $a[j])
+        )
+    };
+    ($a:expr, $lo:expr, $hi:expr) => {
+        $crate::prusti_expr!(
+            forall(|i: usize, j: usize| ($lo <= i && i <= j && j < $hi) ==> $a[i] <= $a[j])
+        )
+    };
+}
+
+/// Invariant template: `$m` is the maximum of the elements of the slice `$a`
+/// in the non-empty index range `$lo..$hi`, such as the running maximum of a
+/// loop over `$a`.
+#[macro_export]
+macro_rules! is_max_of {
+    ($m:expr, $a:expr, $lo:expr, $hi:expr) => {
+        $crate::prusti_expr!(
+            $lo < $hi
+                && forall(|k: usize| ($lo <= k && k < $hi) ==> $a[k] <= $m)
+                && exists(|k: usize| $lo <= k && k < $hi && $a[k] == $m)
+        )
+    };
+}
+
+/// Invariant template: `$m` is the minimum of the elements of the slice `$a`
+/// in the non-empty index range `$lo..$hi`.
+#[macro_export]
+macro_rules! is_min_of {
+    ($m:expr, $a:expr, $lo:expr, $hi:expr) => {
+        $crate::prusti_expr!(
+            $lo < $hi
+                && forall(|k: usize| ($lo <= k && k < $hi) ==> $m <= $a[k])
+                && exists(|k: usize| $lo <= k && k < $hi && $a[k] == $m)
+        )
+    };
+}
+
+/// Invariant template: the slices `$a` and `$b` contain the same elements
+/// with the same multiplicities. With a single argument, the mutable slice
+/// `$a` contains the same elements as at the beginning of the function.
+#[macro_export]
+macro_rules! multiset_eq {
+    ($a:expr) => {
+        $crate::multiset_eq!($a, $crate::old(&*$a))
+    };
+    ($a:expr, $b:expr) => {
+        $crate::slices::permutation_of(&$a[..], &$b[..])
+    };
+}
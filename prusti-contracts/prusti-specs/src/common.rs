@@ -2,10 +2,10 @@
 
 use proc_macro2::Ident;
 pub(crate) use receiver_rewriter::*;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 pub(crate) use self_type_rewriter::*;
 use std::borrow::BorrowMut;
-use syn::{parse_quote, punctuated::Punctuated, spanned::Spanned, GenericParam, TypeParam};
+use syn::{parse_quote, spanned::Spanned, GenericParam, TypeParam};
 pub(crate) use syn_extensions::*;
 use uuid::Uuid;
 
@@ -164,13 +164,22 @@ mod syn_extensions {
 
 /// See [SelfTypeRewriter]
 mod self_type_rewriter {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
     use syn::{
-        parse_quote_spanned, spanned::Spanned, visit_mut::VisitMut, ImplItemMethod, ItemFn, Type,
-        TypePath, WhereClause,
+        parse::{Parse, ParseStream},
+        parse_quote_spanned,
+        punctuated::Punctuated,
+        spanned::Spanned,
+        visit_mut::VisitMut,
+        Expr, ExprPath, ExprStruct, ImplItemMethod, ItemFn, Macro, Pat, PatPath, PatStruct,
+        PatTupleStruct, Path, PathArguments, Type, TypePath, WhereClause,
     };
 
     /// Given a replacement for the `Self` type and the trait it should fulfill,
     /// this type rewrites `Self` and associated type paths.
+    /// `Self` constructors (`Self { .. }`, `Self(..)`) and associated items
+    /// (`Self::new(..)`) in expressions are rewritten as well.
     ///
     /// # Example
     /// Given a `Self` replacement `T_Self` and a self trait constraint `Foo<X>`,
@@ -186,6 +195,13 @@ mod self_type_rewriter {
     ///     self.bar()
     /// }
     /// ```
+    ///
+    /// If `Self` is replaced by a path type such as `Foo<T>`, an expression
+    /// `Self { x: 1 }` is rewritten to `Foo::<T> { x: 1 }`, and an associated
+    /// item `Self::new()` to `<Foo<T> as Bar>::new()` (or `<Foo<T>>::new()`
+    /// without a self trait constraint). In patterns, `Self { x }` and
+    /// `Self::Variant(x)` are rewritten to `Foo::<T> { x }` and
+    /// `Foo::<T>::Variant(x)`.
     pub(crate) trait SelfTypeRewriter {
         fn rewrite_self_type(&mut self, self_type: &Type, self_type_trait: Option<&TypePath>);
     }
@@ -237,6 +253,81 @@ mod self_type_rewriter {
         pub fn rewrite_where_clause(&mut self, where_clause: &mut WhereClause) {
             syn::visit_mut::visit_where_clause_mut(self, where_clause);
         }
+
+        /// The `Self` replacement as a path usable in expression position,
+        /// i.e. with turbofish generic arguments. Returns `None` if the
+        /// replacement is not a path type.
+        fn self_type_expr_path(&self) -> Option<Path> {
+            let Type::Path(TypePath { qself: None, path }) = self.self_type else {
+                return None;
+            };
+            let mut path = path.clone();
+            for segment in path.segments.iter_mut() {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    args.colon2_token = Some(<syn::Token![::]>::default());
+                }
+            }
+            Some(path)
+        }
+
+        /// Rewrites a path starting with `Self` in a pattern, such as
+        /// `Self::Variant`, by replacing `Self` with the path of the `Self`
+        /// replacement. Patterns cannot contain qualified paths to variants.
+        fn rewrite_pat_path(&self, path: &mut Path) {
+            if !is_self_path(path) {
+                return;
+            }
+            if let Some(mut new_path) = self.self_type_expr_path() {
+                new_path
+                    .segments
+                    .extend(path.segments.iter().skip(1).cloned());
+                *path = new_path;
+            }
+        }
+    }
+
+    /// The arguments of `matches!(expr, pattern if guard)`.
+    struct MatchesArgs {
+        expr: Expr,
+        comma: syn::Token![,],
+        pats: Punctuated<Pat, syn::Token![|]>,
+        guard: Option<(syn::Token![if], Expr)>,
+        trailing_comma: Option<syn::Token![,]>,
+    }
+
+    impl Parse for MatchesArgs {
+        fn parse(input: ParseStream) -> syn::Result<Self> {
+            Ok(MatchesArgs {
+                expr: input.parse()?,
+                comma: input.parse()?,
+                pats: Punctuated::parse_separated_nonempty(input)?,
+                guard: if input.peek(syn::Token![if]) {
+                    Some((input.parse()?, input.parse()?))
+                } else {
+                    None
+                },
+                trailing_comma: input.parse()?,
+            })
+        }
+    }
+
+    impl ToTokens for MatchesArgs {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.expr.to_tokens(tokens);
+            self.comma.to_tokens(tokens);
+            self.pats.to_tokens(tokens);
+            if let Some((if_token, guard)) = &self.guard {
+                if_token.to_tokens(tokens);
+                guard.to_tokens(tokens);
+            }
+            self.trailing_comma.to_tokens(tokens);
+        }
+    }
+
+    fn is_self_path(path: &Path) -> bool {
+        path.leading_colon.is_none()
+            && !path.segments.is_empty()
+            && path.segments[0].ident == "Self"
     }
 
     impl<'a> VisitMut for Rewriter<'a> {
@@ -273,6 +364,82 @@ mod self_type_rewriter {
             }
             syn::visit_mut::visit_type_mut(self, ty);
         }
+
+        fn visit_expr_struct_mut(&mut self, expr: &mut ExprStruct) {
+            if is_self_path(&expr.path) && expr.path.segments.len() == 1 {
+                // replace `Self { .. }` constructors
+                if let Some(path) = self.self_type_expr_path() {
+                    expr.path = path;
+                }
+            }
+            syn::visit_mut::visit_expr_struct_mut(self, expr);
+        }
+
+        fn visit_expr_path_mut(&mut self, expr: &mut ExprPath) {
+            if expr.qself.is_none() && is_self_path(&expr.path) {
+                if expr.path.segments.len() == 1 {
+                    // replace `Self` in tuple and unit struct constructors
+                    if let Some(path) = self.self_type_expr_path() {
+                        expr.path = path;
+                    }
+                } else {
+                    // replace associated items, e.g. `Self::new`
+                    let path_rest = expr
+                        .path
+                        .segments
+                        .clone()
+                        .into_pairs()
+                        .skip(1)
+                        .collect::<syn::punctuated::Punctuated<syn::PathSegment, _>>();
+                    let self_type = &self.self_type;
+                    let new_expr_path: ExprPath = match self.self_type_trait {
+                        Some(self_type_trait) => parse_quote_spanned! {expr.span()=>
+                            < #self_type as #self_type_trait > :: #path_rest
+                        },
+                        None => parse_quote_spanned! {expr.span()=>
+                            < #self_type > :: #path_rest
+                        },
+                    };
+                    expr.qself = new_expr_path.qself;
+                    expr.path = new_expr_path.path;
+                }
+            }
+            syn::visit_mut::visit_expr_path_mut(self, expr);
+        }
+
+        fn visit_macro_mut(&mut self, mac: &mut Macro) {
+            // Patterns in specifications are usually written in `matches!`
+            if mac.path.is_ident("matches") {
+                if let Ok(mut args) = mac.parse_body_with(MatchesArgs::parse) {
+                    self.visit_expr_mut(&mut args.expr);
+                    for pat in args.pats.iter_mut() {
+                        self.visit_pat_mut(pat);
+                    }
+                    if let Some((_, guard)) = &mut args.guard {
+                        self.visit_expr_mut(guard);
+                    }
+                    mac.tokens = args.into_token_stream();
+                }
+            }
+            syn::visit_mut::visit_macro_mut(self, mac);
+        }
+
+        fn visit_pat_struct_mut(&mut self, pat: &mut PatStruct) {
+            self.rewrite_pat_path(&mut pat.path);
+            syn::visit_mut::visit_pat_struct_mut(self, pat);
+        }
+
+        fn visit_pat_tuple_struct_mut(&mut self, pat: &mut PatTupleStruct) {
+            self.rewrite_pat_path(&mut pat.path);
+            syn::visit_mut::visit_pat_tuple_struct_mut(self, pat);
+        }
+
+        fn visit_pat_path_mut(&mut self, pat: &mut PatPath) {
+            if pat.qself.is_none() {
+                self.rewrite_pat_path(&mut pat.path);
+            }
+            syn::visit_mut::visit_pat_path_mut(self, pat);
+        }
     }
 }
 
@@ -389,8 +556,9 @@ mod receiver_rewriter {
 }
 
 /// Copies the [syn::Generics] of `source` to the generics of `target`
-/// **Important**: Lifetimes and const params are currently ignored.
-/// If `source` has generic params which do not appear in `target`, they are added first.
+/// **Important**: Lifetimes are currently ignored.
+/// If `source` has generic params which do not appear in `target`, they are added first
+/// (after the lifetimes of `target`).
 ///
 /// # Example
 /// ```ignore
@@ -406,38 +574,62 @@ pub(crate) fn merge_generics<T: HasGenerics>(target: &mut T, source: &T) {
     let generics_target = target.generics_mut();
     let generics_source = source.generics();
 
-    // Merge all type params
+    // Merge all type and const params
     let mut existing_target_type_params: FxHashMap<Ident, &mut TypeParam> = FxHashMap::default();
+    let mut existing_target_const_params: FxHashSet<Ident> = FxHashSet::default();
     let mut new_generic_params: Vec<GenericParam> = Vec::new();
     for param_target in generics_target.params.iter_mut() {
-        if let GenericParam::Type(type_param_target) = param_target {
-            existing_target_type_params.insert(type_param_target.ident.clone(), type_param_target);
+        match param_target {
+            GenericParam::Type(type_param_target) => {
+                existing_target_type_params
+                    .insert(type_param_target.ident.clone(), type_param_target);
+            }
+            GenericParam::Const(const_param_target) => {
+                existing_target_const_params.insert(const_param_target.ident.clone());
+            }
+            GenericParam::Lifetime(_) => {}
         }
     }
 
     for param_source in generics_source.params.iter() {
-        // Lifetimes and consts are currently not handled
-        if let GenericParam::Type(type_param_source) = param_source {
-            // We can remove the target type param here, because the source will not have the
-            // same type param with the same identifiers
-            let maybe_type_param_source =
-                existing_target_type_params.remove(&type_param_source.ident);
-            if let Some(type_param_target) = maybe_type_param_source {
-                type_param_target
-                    .bounds
-                    .extend(type_param_source.bounds.clone());
-            } else {
-                new_generic_params.push(GenericParam::Type(type_param_source.clone()));
+        match param_source {
+            GenericParam::Type(type_param_source) => {
+                // We can remove the target type param here, because the source will not have the
+                // same type param with the same identifiers
+                let maybe_type_param_source =
+                    existing_target_type_params.remove(&type_param_source.ident);
+                if let Some(type_param_target) = maybe_type_param_source {
+                    type_param_target
+                        .bounds
+                        .extend(type_param_source.bounds.clone());
+                } else {
+                    new_generic_params.push(GenericParam::Type(type_param_source.clone()));
+                }
+            }
+            GenericParam::Const(const_param_source) => {
+                if !existing_target_const_params.contains(&const_param_source.ident) {
+                    new_generic_params.push(GenericParam::Const(const_param_source.clone()));
+                }
             }
+            // Lifetimes are currently not handled
+            GenericParam::Lifetime(_) => {}
         }
     }
 
     // Merge the new parameters with the existing ones.
-    // New parameters are added as a prefix.
+    // New parameters are added as a prefix, but after the lifetimes, which
+    // have to come first.
     if !new_generic_params.is_empty() {
-        let existing_params = generics_target.params.clone();
-        new_generic_params.extend(existing_params);
-        generics_target.params = Punctuated::from_iter(new_generic_params);
+        let (lifetimes, others): (Vec<_>, Vec<_>) = generics_target
+            .params
+            .clone()
+            .into_iter()
+            .partition(|param| matches!(param, GenericParam::Lifetime(_)));
+        generics_target.params = lifetimes
+            .into_iter()
+            .chain(new_generic_params)
+            .chain(others)
+            .collect();
     }
 
     // Merge the where clause
@@ -572,6 +764,25 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_const_params() {
+            test_merge! {
+                [impl<const N: usize> Foo for Bar {}] into
+                [impl<T: A> Foo for Bar {}] gives
+                [impl<const N: usize, T: A> Foo for Bar {}]
+            }
+            test_merge! {
+                [impl<const N: usize> Foo for Bar {}] into
+                [impl<const N: usize> Foo for Bar {}] gives
+                [impl<const N: usize> Foo for Bar {}]
+            }
+            test_merge! {
+                [impl<T: A, const N: usize> Foo for Bar {}] into
+                [impl<'a, U> Foo for Bar {}] gives
+                [impl<'a, T: A, const N: usize, U> Foo for Bar {}]
+            }
+        }
+
         #[test]
         fn test_where_clause() {
             test_merge! {
@@ -708,4 +919,115 @@ mod tests {
             }
         }
     }
+
+    mod self_type_rewriter {
+        use super::*;
+        use quote::ToTokens;
+        use syn::parse_quote;
+
+        fn rewrite(item_fn: &mut syn::ItemFn) {
+            let self_type: syn::Type = parse_quote!(Foo<T>);
+            let self_type_trait: syn::TypePath = parse_quote!(Bar);
+            item_fn.rewrite_self_type(&self_type, Some(&self_type_trait));
+        }
+
+        #[test]
+        fn rewrite_struct_constructor() {
+            let mut item_fn: syn::ItemFn = parse_quote!(
+                fn spec(x: i32) -> bool {
+                    Self {
+                        x,
+                        y: Self::Assoc::default(),
+                    } == Self {
+                        x: 1,
+                        ..Self::new()
+                    }
+                }
+            );
+            rewrite(&mut item_fn);
+            let expected: syn::ItemFn = parse_quote!(
+                fn spec(x: i32) -> bool {
+                    Foo::<T> {
+                        x,
+                        y: <Foo<T> as Bar>::Assoc::default(),
+                    } == Foo::<T> {
+                        x: 1,
+                        ..<Foo<T> as Bar>::new()
+                    }
+                }
+            );
+            assert_eq!(
+                expected.to_token_stream().to_string(),
+                item_fn.to_token_stream().to_string()
+            );
+        }
+
+        #[test]
+        fn rewrite_associated_item_without_trait() {
+            let mut item_fn: syn::ItemFn = parse_quote!(
+                fn spec(result: Self) -> bool {
+                    result == Self::new()
+                }
+            );
+            let self_type: syn::Type = parse_quote!(Foo<T>);
+            item_fn.rewrite_self_type(&self_type, None);
+            let expected: syn::ItemFn = parse_quote!(
+                fn spec(result: Foo<T>) -> bool {
+                    result == <Foo<T>>::new()
+                }
+            );
+            assert_eq!(
+                expected.to_token_stream().to_string(),
+                item_fn.to_token_stream().to_string()
+            );
+        }
+
+        #[test]
+        fn rewrite_patterns() {
+            let mut item_fn: syn::ItemFn = parse_quote!(
+                fn spec(result: Self) -> bool {
+                    matches!(result, Self { x: 1, .. })
+                        && match result {
+                            Self::A(x) => x > 0,
+                            Self::B { y } => y > 0,
+                            Self::C => true,
+                        }
+                }
+            );
+            rewrite(&mut item_fn);
+            let expected: syn::ItemFn = parse_quote!(
+                fn spec(result: Foo<T>) -> bool {
+                    matches!(result, Foo::<T> { x: 1, .. })
+                        && match result {
+                            Foo::<T>::A(x) => x > 0,
+                            Foo::<T>::B { y } => y > 0,
+                            Foo::<T>::C => true,
+                        }
+                }
+            );
+            assert_eq!(
+                expected.to_token_stream().to_string(),
+                item_fn.to_token_stream().to_string()
+            );
+        }
+
+        #[test]
+        fn rewrite_tuple_constructor() {
+            let mut item_fn: syn::ItemFn = parse_quote!(
+                fn spec(result: Self) -> bool {
+                    result == Self(1)
+                }
+            );
+            rewrite(&mut item_fn);
+            let expected: syn::ItemFn = parse_quote!(
+                fn spec(result: Foo<T>) -> bool {
+                    result == Foo::<T>(1)
+                }
+            );
+            assert_eq!(
+                expected.to_token_stream().to_string(),
+                item_fn.to_token_stream().to_string()
+            );
+        }
+    }
 }
@@ -0,0 +1,126 @@
+//! Errors in specifications with secondary spans and help messages.
+//!
+//! The parsing code reports errors as [syn::Error]s. An error can carry
+//! additional notes and help messages, which are stored as further errors
+//! combined into it and marked by a prefix of their message. When the error is
+//! reported, they are turned back into a single compiler diagnostic, in which
+//! they show up as `note:` and `help:` entries below the primary message.
+
+use proc_macro2::{Span, TokenStream};
+
+const NOTE_PREFIX: &str = "\u{1f}note:";
+const HELP_PREFIX: &str = "\u{1f}help:";
+const SPAN_HELP_PREFIX: &str = "\u{1f}span_help:";
+
+/// Attaches notes and help messages to an error.
+pub(crate) trait SpecErrorExt {
+    /// Adds a note pointing to a secondary span, e.g. the other operand of
+    /// an operator.
+    fn note_at(self, span: Span, message: &str) -> Self;
+    /// Adds a help message without a span.
+    fn help(self, message: &str) -> Self;
+    /// Adds a help message pointing to a span, typically with a suggestion
+    /// of what to write instead of the code at the span.
+    fn help_at(self, span: Span, message: &str) -> Self;
+}
+
+impl SpecErrorExt for syn::Error {
+    fn note_at(mut self, span: Span, message: &str) -> Self {
+        self.combine(syn::Error::new(span, format!("{NOTE_PREFIX}{message}")));
+        self
+    }
+
+    fn help(mut self, message: &str) -> Self {
+        let span = self.span();
+        self.combine(syn::Error::new(span, format!("{HELP_PREFIX}{message}")));
+        self
+    }
+
+    fn help_at(mut self, span: Span, message: &str) -> Self {
+        self.combine(syn::Error::new(
+            span,
+            format!("{SPAN_HELP_PREFIX}{message}"),
+        ));
+        self
+    }
+}
+
+/// An error message together with its attached notes and help messages.
+struct SpecDiagnostic {
+    error: syn::Error,
+    children: Vec<(Child, Span, String)>,
+}
+
+enum Child {
+    Note,
+    Help,
+    SpanHelp,
+}
+
+/// Splits a (combined) error into the diagnostics to report.
+fn collect_diagnostics(error: syn::Error) -> Vec<SpecDiagnostic> {
+    let mut diagnostics: Vec<SpecDiagnostic> = Vec::new();
+    for error in error {
+        let message = error.to_string();
+        let child = [
+            (NOTE_PREFIX, Child::Note),
+            (HELP_PREFIX, Child::Help),
+            (SPAN_HELP_PREFIX, Child::SpanHelp),
+        ]
+        .into_iter()
+        .find_map(|(prefix, child)| {
+            message
+                .strip_prefix(prefix)
+                .map(|message| (child, message.to_string()))
+        });
+        match (child, diagnostics.last_mut()) {
+            (Some((child, message)), Some(diagnostic)) => {
+                diagnostic.children.push((child, error.span(), message))
+            }
+            // A note without an error to attach to should not happen, but
+            // report it anyway rather than losing it.
+            (Some((_, message)), None) => diagnostics.push(SpecDiagnostic {
+                error: syn::Error::new(error.span(), message),
+                children: vec![],
+            }),
+            (None, _) => diagnostics.push(SpecDiagnostic {
+                error,
+                children: vec![],
+            }),
+        }
+    }
+    diagnostics
+}
+
+/// Reports the error, including its notes and help messages. Errors without
+/// notes are emitted as `compile_error!` invocations, which are returned;
+/// errors with notes are emitted directly as compiler diagnostics.
+pub(crate) fn error_to_tokens(error: syn::Error) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    for diagnostic in collect_diagnostics(error) {
+        // Compiler diagnostics can only be emitted from within a proc macro,
+        // which is not the case when running the unit tests.
+        if diagnostic.children.is_empty() || !proc_macro::is_available() {
+            tokens.extend(diagnostic.error.to_compile_error());
+        } else {
+            emit(diagnostic);
+        }
+    }
+    tokens
+}
+
+fn emit(diagnostic: SpecDiagnostic) {
+    let mut emitted = proc_macro::Diagnostic::spanned(
+        diagnostic.error.span().unwrap(),
+        proc_macro::Level::Error,
+        diagnostic.error.to_string(),
+    );
+    for (child, span, message) in diagnostic.children {
+        emitted = match child {
+            Child::Note => emitted.span_note(span.unwrap(), message),
+            Child::Help => emitted.help(message),
+            Child::SpanHelp => emitted.span_help(span.unwrap(), message),
+        };
+    }
+    emitted.emit();
+}
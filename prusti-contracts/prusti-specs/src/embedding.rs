@@ -0,0 +1,155 @@
+//! Interface for procedural macros that generate functions which carry Prusti
+//! specifications.
+//!
+//! A macro can attach the specifications directly to the functions it emits,
+//! instead of emitting `#[prusti_contracts::requires(...)]` and similar
+//! attributes, which must then be expanded by `prusti-contracts` in a later
+//! expansion step:
+//!
+//! ```ignore
+//! let clauses = parse_clauses(quote! { requires(x > 0), ensures(result > x) })?;
+//! let tokens = embed_specs(clauses, quote! { fn next(x: u32) -> u32 { x + 1 } })?;
+//! ```
+//!
+//! The generated code only compiles when the crate is verified by Prusti, so
+//! the macro should only call this interface when it is itself compiled for
+//! verification, like `prusti-contracts-proc-macros` with its `prusti`
+//! feature.
+
+use crate::{
+    generate_spec_and_assertions, specifications::untyped, SpecAttributeKind, SPECS_VERSION,
+};
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use std::convert::TryFrom;
+use syn::{parse::Parse, punctuated::Punctuated, spanned::Spanned};
+
+/// A specification clause of a function, such as `requires(x > 0)` or
+/// `pure`, corresponding to the attribute `#[requires(x > 0)]` or `#[pure]`.
+#[derive(Debug, Clone)]
+pub struct Clause {
+    kind: SpecAttributeKind,
+    tokens: TokenStream,
+    span: Span,
+}
+
+impl Clause {
+    /// The clause `kind` with the arguments `tokens`, which are the tokens
+    /// between the parentheses of the corresponding attribute.
+    pub fn new(kind: SpecAttributeKind, tokens: TokenStream) -> Self {
+        Self {
+            kind,
+            tokens,
+            span: Span::call_site(),
+        }
+    }
+
+    pub fn kind(&self) -> SpecAttributeKind {
+        self.kind
+    }
+
+    pub fn tokens(&self) -> &TokenStream {
+        &self.tokens
+    }
+}
+
+impl Parse for Clause {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name: syn::Ident = input.parse()?;
+        let kind = SpecAttributeKind::try_from(name.to_string()).map_err(|_| {
+            syn::Error::new(
+                name.span(),
+                format!("`{name}` is not a Prusti specification clause"),
+            )
+        })?;
+        let tokens = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            content.parse()?
+        } else {
+            TokenStream::new()
+        };
+        Ok(Self {
+            kind,
+            tokens,
+            span: name.span(),
+        })
+    }
+}
+
+/// Parses a comma-separated list of clauses, e.g.
+/// `requires(x > 0), ensures(result > x), pure`.
+pub fn parse_clauses(tokens: TokenStream) -> syn::Result<Vec<Clause>> {
+    let clauses = syn::parse::Parser::parse2(
+        Punctuated::<Clause, syn::Token![,]>::parse_terminated,
+        tokens,
+    )?;
+    Ok(clauses.into_iter().collect())
+}
+
+/// The code generated for the specification of a function.
+#[derive(Debug)]
+pub struct GeneratedSpecs {
+    /// The items encoding the specification, which need to be emitted next to
+    /// the function, i.e. in the same module, `impl` block or trait.
+    pub spec_items: Vec<syn::Item>,
+    /// The attributes linking the function to its specification, which need
+    /// to be added to the function.
+    pub attributes: Vec<syn::Attribute>,
+}
+
+/// Generates the specification of the function `item` with the given
+/// `clauses`. The Prusti attributes of `item` itself are not processed; they
+/// are expanded by the compiler as usual.
+pub fn generate_specs(clauses: Vec<Clause>, item: TokenStream) -> syn::Result<GeneratedSpecs> {
+    let item: untyped::AnyFnItem = syn::parse2(item)?;
+    let mut prusti_attributes = Vec::with_capacity(clauses.len());
+    for clause in clauses {
+        check_function_clause(&clause)?;
+        prusti_attributes.push((clause.kind, clause.tokens));
+    }
+    let (spec_items, mut attributes) = generate_spec_and_assertions(prusti_attributes, &item)?;
+    attributes.push(syn::parse_quote_spanned! {item.span()=>
+        #[prusti::specs_version = #SPECS_VERSION]
+    });
+    Ok(GeneratedSpecs {
+        spec_items,
+        attributes,
+    })
+}
+
+/// Generates the specification of the function `item` with the given
+/// `clauses` and returns the function with its specification, in the same
+/// form as if the clauses were written as attributes of the function.
+pub fn embed_specs(clauses: Vec<Clause>, item: TokenStream) -> syn::Result<TokenStream> {
+    let GeneratedSpecs {
+        spec_items,
+        attributes,
+    } = generate_specs(clauses, item.clone())?;
+    Ok(quote_spanned! {item.span()=>
+        #(#spec_items)*
+        #(#attributes)*
+        #item
+    })
+}
+
+/// Reports the clauses that cannot be attached to a function with this
+/// interface.
+fn check_function_clause(clause: &Clause) -> syn::Result<()> {
+    match clause.kind {
+        SpecAttributeKind::Predicate => Err(syn::Error::new(
+            clause.span,
+            "predicates cannot be generated from clauses; emit `predicate! { fn ... }` instead",
+        )),
+        SpecAttributeKind::Invariant
+        | SpecAttributeKind::Model
+        | SpecAttributeKind::PrintCounterexample => Err(syn::Error::new(
+            clause.span,
+            format!(
+                "`{}` is a specification of types, not of functions",
+                clause.kind.name()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
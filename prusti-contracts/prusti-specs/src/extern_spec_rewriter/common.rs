@@ -1,7 +1,9 @@
 use crate::{
     common::{HasAttributes, HasSignature},
-    extract_prusti_attributes, generate_spec_and_assertions,
+    extract_cfg_prusti_attributes, extract_prusti_attributes, generate_cfg_spec_and_assertions,
+    generate_spec_and_assertions,
     span_overrider::SpanOverrider,
+    specifications::common::stable_path_hash,
     untyped::AnyFnItem,
     ExternSpecKind, RewritableReceiver, SelfTypeRewriter,
 };
@@ -151,8 +153,13 @@ pub(crate) fn generate_extern_spec_method_stub<T: HasSignature + HasAttributes +
     // Eagerly extract and process specifications
     let mut stub_method = AnyFnItem::ImplMethod(stub_method);
     let prusti_attributes = extract_prusti_attributes(&mut stub_method);
-    let (spec_items, generated_attributes) =
+    let cfg_attributes = extract_cfg_prusti_attributes(&mut stub_method)?;
+    let (mut spec_items, mut generated_attributes) =
         generate_spec_and_assertions(prusti_attributes, &stub_method)?;
+    let (cfg_spec_items, cfg_generated_attributes) =
+        generate_cfg_spec_and_assertions(cfg_attributes, &stub_method)?;
+    spec_items.extend(cfg_spec_items);
+    generated_attributes.extend(cfg_generated_attributes);
 
     // In the generated spec items and the stub method:
     // - Rewrite associated types
@@ -196,8 +203,14 @@ pub(crate) fn generate_extern_spec_function_stub<Input: HasSignature + HasAttrib
     let signature = function.sig();
     let mut signature = with_explicit_lifetimes(signature).unwrap_or_else(|| signature.clone());
     if mangle_name {
-        signature.ident = format_ident!("prusti_extern_spec_{}", signature.ident);
+        // Functions of different modules may share their name, so the path of
+        // the specified function is part of the name of its stub.
+        let path_hash = stable_path_hash(&fn_path.to_token_stream().to_string());
+        signature.ident = format_ident!("prusti_extern_spec_{}_{}", signature.ident, path_hash);
     }
+    // The stub is never evaluated at compile time, and a `const` stub could
+    // not call functions which are only unstably `const`.
+    signature.constness = None;
     // Make elided lifetimes explicit, if necessary.
     let attrs = function.attrs().clone();
     let generic_params = &signature.generic_params_as_call_args();
@@ -246,7 +259,11 @@ impl MethodParamsAsCallArguments for Punctuated<FnArg, Token![,]> {
                     ..
                 }) => parse_quote_spanned! {span=>#ident },
                 FnArg::Receiver(_) => parse_quote_spanned! {span=>self},
-                _ => unimplemented!(),
+                // The call cannot be generated without a name for the
+                // argument; report the problem where the stub is compiled
+                _ => parse_quote_spanned! {span=>
+                    ::core::compile_error!("patterns in the parameters of external specs are not supported; use an identifier instead")
+                },
             }
         }))
     }
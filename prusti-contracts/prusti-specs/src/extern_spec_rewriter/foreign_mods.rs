@@ -1,10 +1,10 @@
 //! Process external specifications in Rust foreign modules marked with
 //! the #[extern_spec] attribute.
 
-use super::functions::rewrite_stub;
+use super::common::generate_extern_spec_function_stub;
+use crate::ExternSpecKind;
 use proc_macro2::TokenStream;
-use quote::ToTokens;
-use syn::spanned::Spanned;
+use syn::{parse_quote_spanned, spanned::Spanned};
 
 pub fn rewrite_extern_spec(
     item_foreign_mod: &syn::ItemForeignMod,
@@ -14,8 +14,7 @@ pub fn rewrite_extern_spec(
     for item in item_foreign_mod.items.iter() {
         match item {
             syn::ForeignItem::Fn(item_fn) => {
-                let tokens = rewrite_stub(&item_fn.to_token_stream(), path, true);
-                res.extend(tokens);
+                res.extend(rewrite_foreign_fn(item_fn, path)?);
             }
             // eventually: handle specs for foreign variables (statics)
             _ => return Err(syn::Error::new(item.span(), "unexpected item")),
@@ -23,3 +22,26 @@ pub fn rewrite_extern_spec(
     }
     Ok(res)
 }
+
+/// Rewrite the declaration of a foreign function to a trusted stub which calls
+/// the function and carries the specification attributes of the declaration.
+fn rewrite_foreign_fn(
+    foreign_fn: &syn::ForeignItemFn,
+    path: &syn::Path,
+) -> syn::Result<TokenStream> {
+    if let Some(variadic) = &foreign_fn.sig.variadic {
+        return Err(syn::Error::new(
+            variadic.span(),
+            "variadic foreign functions are not supported",
+        ));
+    }
+    let ident = &foreign_fn.sig.ident;
+    let fn_path = parse_quote_spanned!(ident.span()=> #path :: #ident);
+    Ok(generate_extern_spec_function_stub(
+        foreign_fn,
+        &fn_path,
+        ExternSpecKind::Method,
+        true,
+        true,
+    ))
+}
@@ -7,6 +7,15 @@ pub mod functions;
 pub mod foreign_mods;
 mod common;
 
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    visit_mut::VisitMut,
+    Token,
+};
+
 #[derive(Debug, Clone, Copy)]
 pub enum ExternSpecKind {
     InherentImpl,
@@ -46,3 +55,74 @@ impl From<ExternSpecKind> for String {
         })
     }
 }
+
+/// Arguments of the `#[extern_spec]` attribute: an optional module path,
+/// optionally followed by `override`, e.g. `#[extern_spec(std::mem, override)]`.
+pub struct ExternSpecArgs {
+    pub mod_path: syn::Path,
+    pub is_override: bool,
+}
+
+impl Parse for ExternSpecArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mod_path = if input.is_empty() || input.peek(Token![override]) {
+            syn::Path {
+                leading_colon: None,
+                segments: syn::punctuated::Punctuated::new(),
+            }
+        } else {
+            input.parse()?
+        };
+        let is_override = if mod_path.segments.is_empty() {
+            input.parse::<Option<Token![override]>>()?.is_some()
+        } else if input.is_empty() {
+            false
+        } else {
+            input.parse::<Token![,]>()?;
+            input.parse::<Token![override]>()?;
+            true
+        };
+        if !input.is_empty() {
+            return Err(input.error("expected a module path and/or `override`"));
+        }
+        Ok(Self {
+            mod_path,
+            is_override,
+        })
+    }
+}
+
+/// Adds `#[prusti::extern_spec_override]` to every stub generated for an
+/// `#[extern_spec(override)]` item. The specifications of marked stubs take
+/// priority over specifications for the same function imported from other crates.
+pub fn mark_override(tokens: TokenStream) -> syn::Result<TokenStream> {
+    let mut file: syn::File = syn::parse2(tokens)?;
+    OverrideMarker.visit_file_mut(&mut file);
+    Ok(file.into_token_stream())
+}
+
+struct OverrideMarker;
+
+impl OverrideMarker {
+    fn mark(attrs: &mut Vec<syn::Attribute>) {
+        let is_stub = attrs.iter().any(|attr| {
+            let segments = &attr.path.segments;
+            segments.len() == 2
+                && segments[0].ident == "prusti"
+                && segments[1].ident == "extern_spec"
+        });
+        if is_stub {
+            attrs.push(parse_quote!(#[prusti::extern_spec_override]));
+        }
+    }
+}
+
+impl VisitMut for OverrideMarker {
+    fn visit_item_fn_mut(&mut self, item_fn: &mut syn::ItemFn) {
+        Self::mark(&mut item_fn.attrs);
+    }
+
+    fn visit_impl_item_method_mut(&mut self, method: &mut syn::ImplItemMethod) {
+        Self::mark(&mut method.attrs);
+    }
+}
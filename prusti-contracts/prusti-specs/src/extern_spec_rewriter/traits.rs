@@ -15,16 +15,19 @@ use syn::{parse_quote, spanned::Spanned, TypeParam};
 /// ```rust
 /// #[extern_spec]
 /// trait SomeTrait<T> {
+///     type ArgTy: Copy;
 ///     fn foo(&self, arg: Self::ArgTy) -> Self::RetTy;
 /// }
 /// ```
 /// it produces a struct
 /// ```rust
-/// struct Aux<T, TSelf> where TSelf: SomeTrait {
+/// struct Aux<T, TSelf> where TSelf: SomeTrait, <TSelf as SomeTrait>::ArgTy: Copy {
 ///     // phantom data for T, TSelf
 /// }
 /// ```
 /// and a corresponding impl block with methods of `SomeTrait`.
+/// Bounds on associated types are added to the where clause of the struct, so
+/// that specs can rely on them.
 ///
 pub fn rewrite_extern_spec(
     item_trait: &syn::ItemTrait,
@@ -92,6 +95,21 @@ fn generate_new_struct(
     };
     new_struct.generics.where_clause = Some(self_where_clause);
 
+    // Bounds on associated types are added to the where clause
+    for trait_item in item_trait.items.iter() {
+        if let syn::TraitItem::Type(assoc_type) = trait_item {
+            if let Some(predicate) =
+                generate_assoc_type_predicate(assoc_type, &self_type_ident, &self_type_trait)?
+            {
+                new_struct
+                    .generics
+                    .make_where_clause()
+                    .predicates
+                    .push(predicate);
+            }
+        }
+    }
+
     add_phantom_data_for_generic_params(&mut new_struct);
 
     Ok(GeneratedStruct {
@@ -102,6 +120,57 @@ fn generate_new_struct(
     })
 }
 
+/// Generates the where predicate `<TSelf as SomeTrait>::Assoc: Bounds` for an
+/// associated type declaration `type Assoc: Bounds;` in an external trait spec,
+/// or `for<'a> <TSelf as SomeTrait>::Assoc<'a>: Bounds` for a generic
+/// associated type `type Assoc<'a>: Bounds;`.
+fn generate_assoc_type_predicate(
+    assoc_type: &syn::TraitItemType,
+    self_type_ident: &syn::Ident,
+    self_type_trait: &syn::TypePath,
+) -> syn::Result<Option<syn::WherePredicate>> {
+    if let Some((_, default)) = &assoc_type.default {
+        return Err(syn::Error::new(
+            default.span(),
+            "Associated types in external trait specs should not have a default",
+        ));
+    }
+    if assoc_type.bounds.is_empty() {
+        return Ok(None);
+    }
+    // The bounds of a generic associated type have to hold for all of its
+    // parameters, which can only be expressed for lifetimes, e.g.
+    // `for<'a> <TSelf as SomeTrait>::Item<'a>: Bounds`
+    let generics = &assoc_type.generics;
+    if let Some(param) = generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new(
+            param.span(),
+            "Bounds on generic associated types with type or const parameters are not supported in external trait specs",
+        ));
+    }
+    let lifetimes: Vec<_> = generics.lifetimes().map(|def| &def.lifetime).collect();
+    let (binder, args) = if lifetimes.is_empty() {
+        (None, None)
+    } else {
+        (
+            Some(quote_spanned! {generics.span()=> for<#(#lifetimes),*> }),
+            Some(quote_spanned! {generics.span()=> <#(#lifetimes),*> }),
+        )
+    };
+    let ident = &assoc_type.ident;
+    let bounds = &assoc_type.bounds;
+    // Bounds may mention `Self`, e.g. `type Item: PartialEq<Self>;`
+    let mut where_clause: syn::WhereClause = parse_quote_spanned! {assoc_type.span()=>
+        where #binder < #self_type_ident as #self_type_trait > :: #ident #args : #bounds
+    };
+    where_clause.rewrite_self_type(&parse_quote! { #self_type_ident }, Some(self_type_trait));
+    Ok(where_clause.predicates.pop().map(|p| p.into_value()))
+}
+
 fn parse_trait_type_params(item_trait: &syn::ItemTrait) -> syn::Result<Vec<TypeParam>> {
     item_trait
         .generics
@@ -143,12 +212,9 @@ impl<'a> GeneratedStruct<'a> {
         // Add items to impl block
         for trait_item in self.item_trait.items.iter() {
             match trait_item {
-                syn::TraitItem::Type(_) => {
-                    return Err(syn::Error::new(
-                        trait_item.span(),
-                        "Associated types in external trait specs should not be declared",
-                    ));
-                }
+                // Associated types only contribute bounds to the where clause
+                // of the generated struct
+                syn::TraitItem::Type(_) => {}
                 syn::TraitItem::Method(trait_method) => {
                     if let Some(default) = &trait_method.default {
                         return Err(check_is_stub(default).expect_err("this cannot be a stub"));
@@ -166,7 +232,12 @@ impl<'a> GeneratedStruct<'a> {
                         "Can not declare abstract predicate in external spec",
                     ));
                 }
-                _ => unimplemented!("Unimplemented trait item for extern spec"),
+                _ => {
+                    return Err(syn::Error::new(
+                        trait_item.span(),
+                        "only methods and associated types are supported in external trait specs",
+                    ));
+                }
             };
         }
 
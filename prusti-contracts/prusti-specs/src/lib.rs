@@ -2,14 +2,19 @@
 #![feature(drain_filter)]
 #![feature(box_patterns)]
 #![feature(proc_macro_span)]
+#![feature(proc_macro_diagnostic)]
 #![feature(if_let_guard)]
 #![feature(assert_matches)]
 // This Clippy chcek seems to be always wrong.
 #![allow(clippy::iter_with_drain)]
 #![warn(clippy::disallowed_types)]
 
+extern crate proc_macro;
+
 #[macro_use]
 mod common;
+mod diagnostics;
+pub mod embedding;
 mod extern_spec_rewriter;
 mod type_cond_specs;
 mod parse_closure_macro;
@@ -30,9 +35,13 @@ use std::convert::TryInto;
 use syn::{spanned::Spanned, visit::Visit};
 
 use crate::{
-    common::{merge_generics, RewritableReceiver, SelfTypeRewriter},
+    common::{merge_generics, HasSignature, RewritableReceiver, SelfTypeRewriter},
+    diagnostics::SpecErrorExt,
     predicate::{is_predicate_macro, ParsedPredicate},
-    specifications::preparser::{parse_prusti, parse_type_cond_spec, NestedSpec},
+    specifications::preparser::{
+        parse_field_spec_entailment, parse_prusti, parse_type_cond_spec, reject_call_summaries,
+        FieldSpecEntailment, NestedSpec,
+    },
 };
 pub use extern_spec_rewriter::ExternSpecKind;
 use parse_closure_macro::ClosureWithSpec;
@@ -41,11 +50,33 @@ use specifications::{common::SpecificationId, untyped};
 
 pub const SPECS_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The `cfg` option set by the Prusti driver when it verifies the crate being
+/// compiled, as opposed to only compiling it (e.g. a dependency with
+/// `no_verify_deps`). It is queried by `is_verified_call!`.
+pub const VERIFIED_CFG: &str = "prusti_verified";
+
+/// The `cfg` option set by the Prusti driver with `strict_spec_attributes`.
+/// Prusti attributes in positions where they have no effect are then reported
+/// as errors instead of being silently ignored.
+pub const STRICT_ATTRIBUTES_CFG: &str = "prusti_strict_attributes";
+
 macro_rules! handle_result {
     ($parse_result: expr) => {
         match $parse_result {
             Ok(data) => data,
-            Err(err) => return err.to_compile_error(),
+            Err(err) => return crate::diagnostics::error_to_tokens(err),
+        }
+    };
+    // Emit the item together with the error, so that the item does not vanish
+    // and cause further errors in the code that refers to it.
+    ($parse_result: expr, $item: expr) => {
+        match $parse_result {
+            Ok(data) => data,
+            Err(err) => {
+                let error = crate::diagnostics::error_to_tokens(err);
+                let item = $item;
+                return quote! { #error #item };
+            }
         }
     };
 }
@@ -60,47 +91,23 @@ macro_rules! result_to_tokens {
 fn extract_prusti_attributes(
     item: &mut untyped::AnyFnItem,
 ) -> Vec<(SpecAttributeKind, TokenStream)> {
+    extract_prusti_attributes_with_spans(item)
+        .into_iter()
+        .map(|(attr_kind, tokens, _)| (attr_kind, tokens))
+        .collect()
+}
+
+/// Like [extract_prusti_attributes], but also returns the span of each
+/// attribute, for reporting errors about it.
+fn extract_prusti_attributes_with_spans(
+    item: &mut untyped::AnyFnItem,
+) -> Vec<(SpecAttributeKind, TokenStream, Span)> {
     let mut prusti_attributes = Vec::new();
     let mut regular_attributes = Vec::new();
     for attr in item.attrs_mut().drain(0..) {
-        if attr.path.segments.len() == 1
-            || (attr.path.segments.len() == 2 && attr.path.segments[0].ident == "prusti_contracts")
-        {
-            let idx = attr.path.segments.len() - 1;
-            if let Ok(attr_kind) = attr.path.segments[idx].ident.to_string().try_into() {
-                let tokens = match attr_kind {
-                    SpecAttributeKind::Requires
-                    | SpecAttributeKind::Ensures
-                    | SpecAttributeKind::AfterExpiry
-                    | SpecAttributeKind::AssertOnExpiry
-                    | SpecAttributeKind::RefineSpec => {
-                        // We need to drop the surrounding parenthesis to make the
-                        // tokens identical to the ones passed by the native procedural
-                        // macro call.
-                        let mut iter = attr.tokens.into_iter();
-                        let TokenTree::Group(group) = iter.next().unwrap() else { unreachable!() };
-                        assert!(iter.next().is_none(), "Unexpected shape of an attribute.");
-                        group.stream()
-                    }
-                    // Nothing to do for attributes without arguments.
-                    SpecAttributeKind::Pure
-                    | SpecAttributeKind::Terminates
-                    | SpecAttributeKind::Trusted
-                    | SpecAttributeKind::Predicate
-                    | SpecAttributeKind::Verified => {
-                        assert!(attr.tokens.is_empty(), "Unexpected shape of an attribute.");
-                        attr.tokens
-                    }
-                    SpecAttributeKind::Invariant => unreachable!("type invariant on function"),
-                    SpecAttributeKind::Model => unreachable!("model on function"),
-                    SpecAttributeKind::PrintCounterexample => {
-                        unreachable!("print_counterexample on function")
-                    }
-                };
-                prusti_attributes.push((attr_kind, tokens));
-            } else {
-                regular_attributes.push(attr);
-            }
+        if let Some(attr_kind) = prusti_attribute_kind(&attr) {
+            let span = attr.span();
+            prusti_attributes.push((attr_kind, prusti_attribute_tokens(attr_kind, attr), span));
         } else {
             regular_attributes.push(attr);
         }
@@ -109,6 +116,175 @@ fn extract_prusti_attributes(
     prusti_attributes
 }
 
+/// The arguments of a Prusti attribute on a function, as they are passed to
+/// the procedural macro implementing the attribute.
+fn prusti_attribute_tokens(attr_kind: SpecAttributeKind, attr: syn::Attribute) -> TokenStream {
+    match attr_kind {
+        SpecAttributeKind::Requires
+        | SpecAttributeKind::Ensures
+        | SpecAttributeKind::AfterExpiry
+        | SpecAttributeKind::AssertOnExpiry
+        | SpecAttributeKind::RefineSpec
+        | SpecAttributeKind::Timeout
+        | SpecAttributeKind::EnsuresOnCompletion => {
+            // We need to drop the surrounding parenthesis to make the
+            // tokens identical to the ones passed by the native procedural
+            // macro call.
+            let mut iter = attr.tokens.into_iter();
+            let TokenTree::Group(group) = iter.next().unwrap() else { unreachable!() };
+            assert!(iter.next().is_none(), "Unexpected shape of an attribute.");
+            group.stream()
+        }
+        // Nothing to do for attributes without arguments.
+        SpecAttributeKind::Pure
+        | SpecAttributeKind::Terminates
+        | SpecAttributeKind::Trusted
+        | SpecAttributeKind::Predicate
+        | SpecAttributeKind::Verified
+        | SpecAttributeKind::Law
+        | SpecAttributeKind::TrustedUnsafeMemory => {
+            assert!(attr.tokens.is_empty(), "Unexpected shape of an attribute.");
+            attr.tokens
+        }
+        SpecAttributeKind::Invariant => unreachable!("type invariant on function"),
+        SpecAttributeKind::Model => unreachable!("model on function"),
+        SpecAttributeKind::PrintCounterexample => {
+            unreachable!("print_counterexample on function")
+        }
+    }
+}
+
+/// Prusti attributes which were nested in `#[cfg_attr(predicate, ...)]`,
+/// grouped by the configuration predicate under which they apply.
+type CfgPrustiAttributes = Vec<(TokenStream, Vec<(SpecAttributeKind, TokenStream)>)>;
+
+/// Removes the Prusti attributes nested in `#[cfg_attr(predicate, ...)]`
+/// attributes of `item`, keeping the other attributes nested in them.
+///
+/// The compiler expands `cfg_attr` only on the item a macro is applied to, so
+/// the macros which process the specifications of the items they contain,
+/// such as `#[extern_spec]` and `#[refine_trait_spec]`, see these attributes
+/// unexpanded. The specifications generated for them are made conditional on
+/// the predicate by [generate_cfg_spec_and_assertions].
+fn extract_cfg_prusti_attributes(
+    item: &mut untyped::AnyFnItem,
+) -> syn::Result<CfgPrustiAttributes> {
+    let mut cfg_attributes = CfgPrustiAttributes::new();
+    let mut regular_attributes = Vec::new();
+    for attr in item.attrs_mut().drain(0..) {
+        if !attr.path.is_ident("cfg_attr") {
+            regular_attributes.push(attr);
+            continue;
+        }
+        let mut nested_attributes = Vec::new();
+        split_cfg_attr(&attr, None, &mut nested_attributes)?;
+        if nested_attributes
+            .iter()
+            .all(|(_, nested)| prusti_attribute_kind(nested).is_none())
+        {
+            regular_attributes.push(attr);
+            continue;
+        }
+        for (predicate, nested) in nested_attributes {
+            if let Some(attr_kind) = prusti_attribute_kind(&nested) {
+                let tokens = prusti_attribute_tokens(attr_kind, nested);
+                let predicate_str = predicate.to_string();
+                match cfg_attributes
+                    .iter_mut()
+                    .find(|(other, _)| other.to_string() == predicate_str)
+                {
+                    Some((_, attributes)) => attributes.push((attr_kind, tokens)),
+                    None => cfg_attributes.push((predicate, vec![(attr_kind, tokens)])),
+                }
+            } else {
+                let path = &nested.path;
+                let tokens = &nested.tokens;
+                regular_attributes.push(parse_quote_spanned! {nested.span()=>
+                    #[cfg_attr(#predicate, #path #tokens)]
+                });
+            }
+        }
+    }
+    *item.attrs_mut() = regular_attributes;
+    Ok(cfg_attributes)
+}
+
+/// Collects the attributes nested in the `cfg_attr` attribute `attr`, each
+/// with the configuration predicate under which it applies. Nested `cfg_attr`
+/// attributes are flattened, combining their predicates with `all`.
+fn split_cfg_attr(
+    attr: &syn::Attribute,
+    outer_predicate: Option<&TokenStream>,
+    nested_attributes: &mut Vec<(TokenStream, syn::Attribute)>,
+) -> syn::Result<()> {
+    let (predicate, attributes) = attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let predicate: syn::Meta = input.parse()?;
+        let mut attributes = Vec::new();
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let path = input.call(syn::Path::parse_mod_style)?;
+            let mut tokens = TokenStream::new();
+            while !input.is_empty() && !input.peek(syn::Token![,]) {
+                tokens.extend([input.parse::<TokenTree>()?]);
+            }
+            attributes.push(syn::Attribute {
+                pound_token: attr.pound_token,
+                style: attr.style,
+                bracket_token: attr.bracket_token,
+                path,
+                tokens,
+            });
+        }
+        Ok((predicate, attributes))
+    })?;
+    let predicate = match outer_predicate {
+        Some(outer) => quote_spanned! {predicate.span()=> all(#outer, #predicate)},
+        None => predicate.into_token_stream(),
+    };
+    for nested in attributes {
+        if nested.path.is_ident("cfg_attr") {
+            split_cfg_attr(&nested, Some(&predicate), nested_attributes)?;
+        } else {
+            nested_attributes.push((predicate.clone(), nested));
+        }
+    }
+    Ok(())
+}
+
+/// The kind of the attribute, if it is a Prusti attribute.
+fn prusti_attribute_kind(attr: &syn::Attribute) -> Option<SpecAttributeKind> {
+    let segments = &attr.path.segments;
+    if segments.len() == 1 || (segments.len() == 2 && segments[0].ident == "prusti_contracts") {
+        segments.last()?.ident.to_string().try_into().ok()
+    } else {
+        None
+    }
+}
+
+/// Generates an error for each Prusti attribute among `attrs`, which are in a
+/// position where Prusti ignores them. The errors are only reported in strict
+/// mode (see [STRICT_ATTRIBUTES_CFG]); otherwise the attributes stay ignored.
+fn report_ignored_prusti_attributes(attrs: &[syn::Attribute], position: &str) -> TokenStream {
+    let cfg = syn::Ident::new(STRICT_ATTRIBUTES_CFG, Span::call_site());
+    attrs
+        .iter()
+        .filter_map(|attr| {
+            let attr_kind = prusti_attribute_kind(attr)?;
+            let message = format!(
+                "`#[{}]` has no effect on {position} and is ignored",
+                attr_kind.name()
+            );
+            Some(quote_spanned! {attr.span()=>
+                #[cfg(#cfg)]
+                compile_error!(#message);
+            })
+        })
+        .collect()
+}
+
 /// Rewrite an item as required by *all* its specification attributes.
 ///
 /// The first attribute (the outer one) needs to be passed via `attr_kind` and `attr` because
@@ -120,26 +296,34 @@ pub fn rewrite_prusti_attributes(
 ) -> TokenStream {
     let mut item: untyped::AnyFnItem = handle_result!(syn::parse2(item_tokens));
 
-    // Start with the outer attribute
-    let mut prusti_attributes = vec![(outer_attr_kind, outer_attr_tokens)];
-
     // Collect the remaining Prusti attributes, removing them from `item`.
-    prusti_attributes.extend(extract_prusti_attributes(&mut item));
+    let inner_attributes = extract_prusti_attributes_with_spans(&mut item);
+    let cfg_attributes = handle_result!(extract_cfg_prusti_attributes(&mut item), &item);
 
     // make sure to also update the check in the predicate! handling method
-    if prusti_attributes
-        .iter()
-        .any(|(ak, _)| ak == &SpecAttributeKind::Predicate)
-    {
-        return syn::Error::new(
-            item.span(),
-            "`predicate!` is incompatible with other Prusti attributes",
-        )
-        .to_compile_error();
-    }
+    handle_result!(
+        check_predicate_attributes(outer_attr_kind, &inner_attributes),
+        &item
+    );
 
-    let (generated_spec_items, generated_attributes) =
-        handle_result!(generate_spec_and_assertions(prusti_attributes, &item));
+    // Start with the outer attribute
+    let mut prusti_attributes = vec![(outer_attr_kind, outer_attr_tokens)];
+    prusti_attributes.extend(
+        inner_attributes
+            .into_iter()
+            .map(|(attr_kind, tokens, _)| (attr_kind, tokens)),
+    );
+
+    let (mut generated_spec_items, mut generated_attributes) = handle_result!(
+        generate_spec_and_assertions(prusti_attributes, &item),
+        &item
+    );
+    let (cfg_spec_items, cfg_attributes) = handle_result!(
+        generate_cfg_spec_and_assertions(cfg_attributes, &item),
+        &item
+    );
+    generated_spec_items.extend(cfg_spec_items);
+    generated_attributes.extend(cfg_attributes);
 
     quote_spanned! {item.span()=>
         #(#generated_spec_items)*
@@ -149,6 +333,69 @@ pub fn rewrite_prusti_attributes(
     }
 }
 
+/// Reports each Prusti attribute that is combined with a `predicate`
+/// attribute, which can only be used through the `predicate!` macro.
+fn check_predicate_attributes(
+    outer_attr_kind: SpecAttributeKind,
+    inner_attributes: &[(SpecAttributeKind, TokenStream, Span)],
+) -> syn::Result<()> {
+    let Some(&(_, _, predicate_span)) = inner_attributes
+        .iter()
+        .find(|(attr_kind, ..)| attr_kind == &SpecAttributeKind::Predicate)
+    else {
+        return Ok(());
+    };
+    // The span of the outer attribute is not available, so it is reported
+    // at the predicate attribute.
+    let mut error = syn::Error::new(
+        predicate_span,
+        format!(
+            "`predicate!` is incompatible with `#[{}]`",
+            outer_attr_kind.name()
+        ),
+    )
+    .help("declare the predicate with `predicate! { fn ... }` instead");
+    for &(attr_kind, _, span) in inner_attributes {
+        if attr_kind != SpecAttributeKind::Predicate {
+            error.combine(
+                syn::Error::new(
+                    span,
+                    format!(
+                        "`#[{}]` is incompatible with `predicate!`",
+                        attr_kind.name()
+                    ),
+                )
+                .note_at(predicate_span, "the function is declared a predicate here"),
+            );
+        }
+    }
+    Err(error)
+}
+
+/// Reports a `call_summary!(..)` in a postcondition of a pure function. Calls
+/// in pure functions are not executed, so there is no call to refer to.
+fn check_no_call_summaries_if_pure(
+    prusti_attributes: &[(SpecAttributeKind, TokenStream)],
+) -> syn::Result<()> {
+    if !prusti_attributes
+        .iter()
+        .any(|(attr_kind, _)| attr_kind == &SpecAttributeKind::Pure)
+    {
+        return Ok(());
+    }
+    for (attr_kind, attr_tokens) in prusti_attributes {
+        if attr_kind == &SpecAttributeKind::Ensures {
+            reject_call_summaries(attr_tokens).map_err(|error| {
+                syn::Error::new(
+                    error.span(),
+                    "`call_summary!(..)` cannot be used in the postconditions of pure functions",
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
 type GeneratedResult = syn::Result<(Vec<syn::Item>, Vec<syn::Attribute>)>;
 
 /// Generate spec items and attributes for `item` from the Prusti attributes
@@ -156,6 +403,7 @@ fn generate_spec_and_assertions(
     mut prusti_attributes: Vec<(SpecAttributeKind, TokenStream)>,
     item: &untyped::AnyFnItem,
 ) -> GeneratedResult {
+    check_no_call_summaries_if_pure(&prusti_attributes)?;
     let mut generated_items = vec![];
     let mut generated_attributes = vec![];
 
@@ -167,11 +415,19 @@ fn generate_spec_and_assertions(
             SpecAttributeKind::AssertOnExpiry => generate_for_assert_on_expiry(attr_tokens, item),
             SpecAttributeKind::Pure => generate_for_pure(attr_tokens, item),
             SpecAttributeKind::Verified => generate_for_verified(attr_tokens, item),
+            SpecAttributeKind::Law => generate_for_law(attr_tokens, item),
+            SpecAttributeKind::TrustedUnsafeMemory => {
+                generate_for_trusted_unsafe_memory(attr_tokens, item)
+            }
             SpecAttributeKind::Terminates => generate_for_terminates(attr_tokens, item),
+            SpecAttributeKind::Timeout => generate_for_timeout(attr_tokens, item),
+            SpecAttributeKind::EnsuresOnCompletion => {
+                generate_for_ensures_on_completion(attr_tokens, item)
+            }
             SpecAttributeKind::Trusted => generate_for_trusted(attr_tokens, item),
             // Predicates are handled separately below; the entry in the SpecAttributeKind enum
             // only exists so we successfully parse it and emit an error in
-            // `check_incompatible_attrs`; so we'll never reach here.
+            // `check_predicate_attributes`; so we'll never reach here.
             SpecAttributeKind::Predicate => unreachable!(),
             SpecAttributeKind::Invariant => unreachable!(),
             SpecAttributeKind::RefineSpec => type_cond_specs::generate(attr_tokens, item),
@@ -186,6 +442,36 @@ fn generate_spec_and_assertions(
     Ok((generated_items, generated_attributes))
 }
 
+/// Generate spec items and attributes for `item` from the Prusti attributes
+/// which apply under a configuration predicate. The spec items and attributes
+/// are only emitted if the predicate holds.
+fn generate_cfg_spec_and_assertions(
+    cfg_attributes: CfgPrustiAttributes,
+    item: &untyped::AnyFnItem,
+) -> GeneratedResult {
+    let mut generated_items = vec![];
+    let mut generated_attributes = vec![];
+
+    for (predicate, prusti_attributes) in cfg_attributes {
+        let (new_items, new_attributes) = generate_spec_and_assertions(prusti_attributes, item)?;
+        generated_items.extend(new_items.into_iter().map(|new_item| -> syn::Item {
+            parse_quote_spanned! {new_item.span()=>
+                #[cfg(#predicate)]
+                #new_item
+            }
+        }));
+        generated_attributes.extend(new_attributes.into_iter().map(|new_attribute| {
+            let path = &new_attribute.path;
+            let tokens = &new_attribute.tokens;
+            parse_quote_spanned! {new_attribute.span()=>
+                #[cfg_attr(#predicate, #path #tokens)]
+            }
+        }));
+    }
+
+    Ok((generated_items, generated_attributes))
+}
+
 /// Generate spec items and attributes to typecheck the and later retrieve "requires" annotations.
 fn generate_for_requires(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
     let mut rewriter = rewriter::AstRewriter::new();
@@ -216,6 +502,29 @@ fn generate_for_ensures(attr: TokenStream, item: &untyped::AnyFnItem) -> Generat
     ))
 }
 
+/// Generate spec items and attributes to typecheck the and later retrieve "ensures_on_completion"
+/// annotations.
+fn generate_for_ensures_on_completion(
+    attr: TokenStream,
+    item: &untyped::AnyFnItem,
+) -> GeneratedResult {
+    let mut rewriter = rewriter::AstRewriter::new();
+    let spec_id = rewriter.generate_spec_id();
+    let spec_id_str = spec_id.to_string();
+    let spec_item = rewriter.process_assertion(
+        rewriter::SpecItemType::CompletionPostcondition,
+        spec_id,
+        attr,
+        item,
+    )?;
+    Ok((
+        vec![spec_item],
+        vec![parse_quote_spanned! {item.span()=>
+            #[prusti::post_on_completion_spec_id_ref = #spec_id_str]
+        }],
+    ))
+}
+
 /// Generate spec items and attributes to typecheck and later retrieve "after_expiry" annotations.
 fn generate_for_after_expiry(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
     let mut rewriter = rewriter::AstRewriter::new();
@@ -288,6 +597,12 @@ fn generate_for_pure(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedR
             "the `#[pure]` attribute does not take parameters",
         ));
     }
+    if let Some(asyncness) = item.sig().asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "async functions cannot be pure",
+        ));
+    }
 
     Ok((
         vec![],
@@ -314,6 +629,108 @@ fn generate_for_verified(attr: TokenStream, item: &untyped::AnyFnItem) -> Genera
     ))
 }
 
+/// Generate the attribute overriding the verification timeout of the item, in
+/// milliseconds.
+fn generate_for_timeout(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    let attr_span = attr.span();
+    let timeout: syn::LitInt = syn::parse2(attr).map_err(|_| {
+        syn::Error::new(
+            attr_span,
+            "expected the `#[timeout]` attribute to have the form `#[timeout(ms)]`",
+        )
+    })?;
+    timeout.base10_parse::<u64>()?;
+    let timeout_str = timeout.base10_digits();
+
+    Ok((
+        vec![],
+        vec![parse_quote_spanned! {item.span()=>
+            #[prusti::timeout = #timeout_str]
+        }],
+    ))
+}
+
+/// Generate spec items and attributes to typecheck and later retrieve
+/// "trusted_unsafe_memory" annotations.
+fn generate_for_trusted_unsafe_memory(
+    attr: TokenStream,
+    item: &untyped::AnyFnItem,
+) -> GeneratedResult {
+    if !attr.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "the `#[trusted_unsafe_memory]` attribute does not take parameters",
+        ));
+    }
+
+    Ok((
+        vec![],
+        vec![parse_quote_spanned! {item.span()=>
+            #[prusti::trusted_unsafe_memory]
+        }],
+    ))
+}
+
+/// Generate spec items and attributes to typecheck and later retrieve "law" annotations.
+///
+/// A law is a method of a trait without a result, whose postcondition every
+/// implementation of the trait has to establish. Generic code can call the
+/// law to assume its postcondition for some arguments.
+fn generate_for_law(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    if !attr.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "the `#[law]` attribute does not take parameters",
+        ));
+    }
+
+    match item {
+        untyped::AnyFnItem::TraitMethod(method) if method.default.is_some() => {
+            return Err(syn::Error::new(
+                method.span(),
+                "a law must not have a default implementation, it has to be verified for every implementation of the trait",
+            ));
+        }
+        untyped::AnyFnItem::TraitMethod(_) | untyped::AnyFnItem::ImplMethod(_) => {}
+        _ => {
+            return Err(syn::Error::new(
+                item.span(),
+                "the `#[law]` attribute can only be used on methods of traits and their implementations",
+            ));
+        }
+    }
+
+    let sig = item.sig();
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        return Err(syn::Error::new(ty.span(), "a law must not return a value"));
+    }
+    for input in sig.inputs.iter() {
+        let mutable = match input {
+            syn::FnArg::Receiver(receiver) => receiver.mutability.is_some(),
+            syn::FnArg::Typed(pat_type) => matches!(
+                &*pat_type.ty,
+                syn::Type::Reference(syn::TypeReference {
+                    mutability: Some(_),
+                    ..
+                })
+            ),
+        };
+        if mutable {
+            return Err(syn::Error::new(
+                input.span(),
+                "a law must not take mutable references",
+            ));
+        }
+    }
+
+    Ok((
+        vec![],
+        vec![parse_quote_spanned! {item.span()=>
+            #[prusti::law]
+        }],
+    ))
+}
+
 /// Generate spec items and attributes to typecheck and later retrieve "pure" annotations, but encoded as a referenced separate function that type-conditional spec refinements can apply trait bounds to.
 fn generate_for_pure_refinements(item: &untyped::AnyFnItem) -> GeneratedResult {
     let mut rewriter = rewriter::AstRewriter::new();
@@ -331,29 +748,39 @@ fn generate_for_pure_refinements(item: &untyped::AnyFnItem) -> GeneratedResult {
 
 /// Generate spec items and attributes to typecheck and later retrieve "trusted" annotations.
 fn generate_for_trusted(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
-    if !attr.is_empty() {
-        return Err(syn::Error::new(
-            attr.span(),
-            "the `#[trusted]` attribute does not take parameters",
-        ));
-    }
+    let reason = parse_trusted_reason(attr)?;
 
     Ok((
         vec![],
         vec![parse_quote_spanned! {item.span()=>
             #[prusti::trusted]
-        }],
+        }]
+        .into_iter()
+        .chain(reason)
+        .collect(),
     ))
 }
 
+/// Parse the optional reason of a `#[trusted("reason")]` annotation into the
+/// attribute recording it, which is read when auditing the trusted items.
+fn parse_trusted_reason(attr: TokenStream) -> syn::Result<Option<syn::Attribute>> {
+    if attr.is_empty() {
+        return Ok(None);
+    }
+    let reason: syn::LitStr = syn::parse2(attr.clone()).map_err(|_| {
+        syn::Error::new(
+            attr.span(),
+            "the `#[trusted]` attribute only takes a string literal with the reason why the item is trusted",
+        )
+    })?;
+    Ok(Some(parse_quote_spanned! {reason.span()=>
+        #[prusti::trusted_reason = #reason]
+    }))
+}
+
 /// Generate spec items and attributes to typecheck and later retrieve "trusted" annotations.
 fn generate_for_trusted_for_types(attr: TokenStream, item: &syn::DeriveInput) -> GeneratedResult {
-    if !attr.is_empty() {
-        return Err(syn::Error::new(
-            attr.span(),
-            "the `#[trusted]` attribute does not take parameters",
-        ));
-    }
+    let reason = parse_trusted_reason(attr)?;
     // TODO: reduce duplication with `invariant`
     let mut rewriter = rewriter::AstRewriter::new();
     let spec_id = rewriter.generate_spec_id();
@@ -370,6 +797,7 @@ fn generate_for_trusted_for_types(attr: TokenStream, item: &syn::DeriveInput) ->
         #[allow(unused_variables, dead_code, non_snake_case)]
         #[prusti::spec_only]
         #[prusti::trusted_type]
+        #reason
         #[prusti::spec_id = #spec_id_str]
         fn #item_name(self) {}
     };
@@ -422,6 +850,17 @@ pub fn body_invariant(tokens: TokenStream) -> TokenStream {
     generate_expression_closure(&AstRewriter::process_loop_invariant, tokens)
 }
 
+/// Marks the enclosing loop to be verified by unrolling it instead of with a
+/// loop invariant. The loop is only verified for executions in which it
+/// iterates at most `bound` times; the other executions are not checked.
+pub fn no_invariant_needed(tokens: TokenStream) -> TokenStream {
+    generate_expression_closure(&AstRewriter::process_loop_unrolling_bound, tokens)
+}
+
+pub fn loop_invariant(tokens: TokenStream) -> TokenStream {
+    generate_expression_closure(&AstRewriter::process_loop_invariant_before_loop, tokens)
+}
+
 pub fn prusti_assertion(tokens: TokenStream) -> TokenStream {
     generate_expression_closure(&AstRewriter::process_prusti_assertion, tokens)
 }
@@ -434,6 +873,33 @@ pub fn prusti_refutation(tokens: TokenStream) -> TokenStream {
     generate_expression_closure(&AstRewriter::process_prusti_refutation, tokens)
 }
 
+/// Translates an expression in Prusti syntax to a Rust expression, which can
+/// be used within other specifications. This allows other crates to define
+/// their own specification macros that expand to Prusti syntax.
+pub fn prusti_expr(tokens: TokenStream) -> TokenStream {
+    let expr = handle_result!(parse_prusti(tokens));
+    quote_spanned! {expr.span()=>
+        (#expr)
+    }
+}
+
+/// Expands to a boolean telling whether the crate is being verified. Since
+/// the `cfg` option is only known to the compiler session of the crate that
+/// contains the invocation, it is checked with `cfg!` there rather than here.
+pub fn is_verified_call(tokens: TokenStream) -> TokenStream {
+    if !tokens.is_empty() {
+        return syn::Error::new(
+            tokens.span(),
+            "`is_verified_call!` does not take any arguments",
+        )
+        .to_compile_error();
+    }
+    let cfg = syn::Ident::new(VERIFIED_CFG, Span::call_site());
+    quote! {
+        cfg!(#cfg)
+    }
+}
+
 /// Generates the TokenStream encoding an expression using prusti syntax
 /// Used for body invariants, assertions, and assumptions
 fn generate_expression_closure(
@@ -509,13 +975,19 @@ pub fn closure(tokens: TokenStream) -> TokenStream {
     let (spec_toks_pre, spec_toks_post) =
         handle_result!(rewriter.process_closure(inputs.clone(), output_type, preconds, postconds,));
 
+    let ignored_attrs = report_ignored_prusti_attributes(&attrs, "closures");
     let mut attrs_ts = TokenStream::new();
     for a in attrs {
-        attrs_ts.extend(a.into_token_stream());
+        // Prusti attributes other than the specifications have no meaning
+        // on closures and are dropped
+        if prusti_attribute_kind(&a).is_none() {
+            attrs_ts.extend(a.into_token_stream());
+        }
     }
 
     quote_spanned! {callsite_span=>
         {
+            #ignored_attrs
             #[allow(unused_variables, unused_braces, unused_parens)]
             #[prusti::closure]
             #[prusti::specs_version = #SPECS_VERSION]
@@ -556,28 +1028,32 @@ pub fn refine_trait_spec(_attr: TokenStream, tokens: TokenStream) -> TokenStream
 
     let mut new_items = Vec::new();
     let mut generated_spec_items = Vec::new();
+    // Errors in the specification of a method are reported without dropping
+    // the method, so that the remaining methods are still checked.
+    let mut errors = TokenStream::new();
     for item in impl_block.items {
         match item {
             syn::ImplItem::Method(method) => {
                 let mut method_item = untyped::AnyFnItem::ImplMethod(method);
                 let prusti_attributes: Vec<_> = extract_prusti_attributes(&mut method_item);
 
-                let illegal_attribute_span = prusti_attributes
-                    .iter()
-                    .filter(|(kind, _)| kind == &SpecAttributeKind::RefineSpec)
-                    .map(|(_, tokens)| tokens.span())
-                    .next();
-                if let Some(span) = illegal_attribute_span {
-                    let err = Err(syn::Error::new(
-                        span,
-                        "Type-conditional spec refinements in trait spec refinements not supported",
-                    ));
-                    handle_result!(err);
-                }
-
-                let (spec_items, generated_attributes) = handle_result!(
-                    generate_spec_and_assertions(prusti_attributes, &method_item)
-                );
+                let generated =
+                    extract_cfg_prusti_attributes(&mut method_item).and_then(|cfg_attributes| {
+                        let (mut spec_items, mut generated_attributes) =
+                            generate_spec_and_assertions(prusti_attributes, &method_item)?;
+                        let (cfg_spec_items, cfg_attributes) =
+                            generate_cfg_spec_and_assertions(cfg_attributes, &method_item)?;
+                        spec_items.extend(cfg_spec_items);
+                        generated_attributes.extend(cfg_attributes);
+                        Ok((spec_items, generated_attributes))
+                    });
+                let (spec_items, generated_attributes) = match generated {
+                    Ok(generated) => generated,
+                    Err(err) => {
+                        errors.extend(diagnostics::error_to_tokens(err));
+                        (vec![], vec![])
+                    }
+                };
 
                 spec_items
                     .into_iter()
@@ -619,24 +1095,50 @@ pub fn refine_trait_spec(_attr: TokenStream, tokens: TokenStream) -> TokenStream
 
     impl_block.items = new_items;
     quote_spanned! {impl_block.span()=>
+        #errors
         #(#generated_spec_items)*
         #[prusti::specs_version = #SPECS_VERSION]
         #impl_block
     }
 }
 
-pub fn trusted(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+/// Rewrites a trait implementation which only exists during verification. Its
+/// methods are marked, so that the verifier can reject their usage from
+/// executable code.
+pub fn ghost_impl(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     if !attr.is_empty() {
         return syn::Error::new(
             attr.span(),
-            "the `#[trusted]` attribute does not take parameters",
+            "the `#[ghost_impl]` attribute does not take parameters",
         )
         .to_compile_error();
     }
 
+    let mut impl_block: syn::ItemImpl = handle_result!(syn::parse2(tokens));
+    if impl_block.trait_.is_none() {
+        return syn::Error::new(
+            impl_block.span(),
+            "only trait implementations can be ghost implementations",
+        )
+        .to_compile_error();
+    }
+    for item in impl_block.items.iter_mut() {
+        if let syn::ImplItem::Method(method) = item {
+            method
+                .attrs
+                .push(parse_quote_spanned! {method.span()=> #[prusti::ghost_impl]});
+        }
+    }
+
+    refine_trait_spec(TokenStream::new(), impl_block.into_token_stream())
+}
+
+pub fn trusted(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     // `#[trusted]` can be applied to both types and to methods, figure out
     // which one by trying to parse a `DeriveInput`.
     if syn::parse2::<syn::DeriveInput>(tokens.clone()).is_ok() {
+        let reason = handle_result!(parse_trusted_reason(attr));
+
         // TODO: reduce duplication with `invariant`
         let mut rewriter = rewriter::AstRewriter::new();
         let spec_id = rewriter.generate_spec_id();
@@ -658,6 +1160,7 @@ pub fn trusted(attr: TokenStream, tokens: TokenStream) -> TokenStream {
             #[allow(unused_variables, dead_code, non_snake_case)]
             #[prusti::spec_only]
             #[prusti::trusted_type]
+            #reason
             #[prusti::spec_id = #spec_id_str]
             fn #item_name(self) {}
         };
@@ -727,17 +1230,26 @@ pub fn invariant(attr: TokenStream, tokens: TokenStream) -> TokenStream {
         item_span,
     );
 
-    let attr = handle_result!(parse_prusti(attr));
-
-    // TODO: move some of this to AstRewriter?
-    // see AstRewriter::generate_spec_item_fn for explanation of syntax below
-    let spec_item: syn::ItemFn = parse_quote_spanned! {item_span=>
-        #[allow(unused_must_use, unused_parens, unused_variables, dead_code, non_snake_case)]
-        #[prusti::spec_only]
-        #[prusti::type_invariant_spec]
-        #[prusti::spec_id = #spec_id_str]
-        fn #item_name(self) -> bool {
-            !!((#attr) : bool)
+    let impl_items = match handle_result!(parse_field_spec_entailment(attr.clone()), &item) {
+        Some(entailment) => handle_result!(
+            generate_for_field_spec_entailment(entailment, &item, spec_id),
+            &item
+        ),
+        None => {
+            let attr = handle_result!(parse_prusti(attr), &item);
+
+            // TODO: move some of this to AstRewriter?
+            // see AstRewriter::generate_spec_item_fn for explanation of syntax below
+            let spec_item: syn::ItemFn = parse_quote_spanned! {item_span=>
+                #[allow(unused_must_use, unused_parens, unused_variables, dead_code, non_snake_case)]
+                #[prusti::spec_only]
+                #[prusti::type_invariant_spec]
+                #[prusti::spec_id = #spec_id_str]
+                fn #item_name(self) -> bool {
+                    !!((#attr) : bool)
+                }
+            };
+            spec_item.into_token_stream()
         }
     };
 
@@ -756,7 +1268,7 @@ pub fn invariant(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     // TODO: similarly to extern_specs, don't generate an actual impl
     let item_impl: syn::ItemImpl = parse_quote_spanned! {item_span=>
         impl #generics #item_ident < #generics_idents > {
-            #spec_item
+            #impl_items
         }
     };
     quote_spanned! { item_span =>
@@ -766,18 +1278,135 @@ pub fn invariant(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     }
 }
 
+/// Generate a trusted function carrying the contract of the callable stored in
+/// a field, as declared by the type invariant `self.field |= |args| [specs]`.
+/// Calls of the callable through the field are verified against this contract.
+fn generate_for_field_spec_entailment(
+    entailment: FieldSpecEntailment,
+    item: &syn::DeriveInput,
+    spec_id: SpecificationId,
+) -> syn::Result<TokenStream> {
+    let field_span = entailment.field.span();
+    let field_ty = match &item.data {
+        syn::Data::Struct(data) => data
+            .fields
+            .iter()
+            .find(|field| field.ident.as_ref() == Some(&entailment.field))
+            .map(|field| &field.ty),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new(
+            field_span,
+            format!("`{}` is not a field of `{}`", entailment.field, item.ident),
+        )
+    })?;
+    let (input_tys, output) = callable_field_signature(field_ty).ok_or_else(|| {
+        syn::Error::new(
+            field_ty.span(),
+            "specification entailments are only supported for fields of type `Box<dyn Fn(..) -> ..>`",
+        )
+    })?;
+    if input_tys.len() != entailment.args.len() {
+        return Err(syn::Error::new(
+            field_span,
+            format!(
+                "the callable stored in `{}` takes {} arguments",
+                entailment.field,
+                input_tys.len()
+            ),
+        ));
+    }
+    let args = entailment
+        .args
+        .iter()
+        .zip(input_tys)
+        .map(|((name, typ), input_ty)| {
+            let typ = typ.as_ref().unwrap_or(input_ty);
+            quote_spanned! {name.span()=> #name: #typ }
+        });
+
+    let field_name = entailment.field.to_string();
+    let stub_ident = syn::Ident::new(
+        &format!(
+            "prusti_field_callable_{}_{}_{}",
+            item.ident, field_name, spec_id
+        ),
+        field_span,
+    );
+    let stub = untyped::AnyFnItem::ImplMethod(parse_quote_spanned! {field_span=>
+        #[allow(unused_variables, dead_code, non_snake_case)]
+        #[prusti::field_callable = #field_name]
+        fn #stub_ident(_callable: &#field_ty, #(#args),*) #output {
+            unreachable!()
+        }
+    });
+
+    let mut prusti_attributes = vec![(SpecAttributeKind::Trusted, TokenStream::new())];
+    for spec in entailment.specs {
+        prusti_attributes.push(match spec {
+            NestedSpec::Requires(tokens) => (SpecAttributeKind::Requires, tokens),
+            NestedSpec::Ensures(tokens) => (SpecAttributeKind::Ensures, tokens),
+            NestedSpec::Pure => {
+                return Err(syn::Error::new(
+                    field_span,
+                    "callables stored in fields cannot be declared pure",
+                ))
+            }
+        });
+    }
+    let (generated_spec_items, generated_attributes) =
+        generate_spec_and_assertions(prusti_attributes, &stub)?;
+
+    Ok(quote_spanned! {field_span=>
+        #(#generated_spec_items)*
+        #(#generated_attributes)*
+        #[prusti::specs_version = #SPECS_VERSION]
+        #stub
+    })
+}
+
+/// Returns the argument types and the return type of the callable stored in a
+/// field of type `Box<dyn Fn(..) -> ..>`.
+fn callable_field_signature(ty: &syn::Type) -> Option<(Vec<&syn::Type>, &syn::ReturnType)> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Box" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(generic_args) = &segment.arguments else {
+        return None;
+    };
+    let trait_object = match generic_args.args.first()? {
+        syn::GenericArgument::Type(syn::Type::TraitObject(trait_object)) => trait_object,
+        _ => return None,
+    };
+    trait_object.bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            return None;
+        };
+        let segment = trait_bound.path.segments.last()?;
+        match &segment.arguments {
+            syn::PathArguments::Parenthesized(args) if segment.ident == "Fn" => {
+                Some((args.inputs.iter().collect(), &args.output))
+            }
+            _ => None,
+        }
+    })
+}
+
 pub fn extern_spec(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     result_to_tokens!({
         let item: syn::Item = syn::parse2(tokens)?;
-        let mod_path: syn::Path = Some(attr)
-            .filter(|attr| !attr.is_empty())
-            .map(syn::parse2)
-            .transpose()?
-            .unwrap_or_else(|| syn::Path {
-                leading_colon: None,
-                segments: syn::punctuated::Punctuated::new(),
-            });
-        match item {
+        let extern_spec_rewriter::ExternSpecArgs {
+            mod_path,
+            is_override,
+        } = syn::parse2(attr)?;
+        let mut ignored_attrs = IgnoredExternSpecAttributes::default();
+        syn::visit::Visit::visit_item(&mut ignored_attrs, &item);
+        let rewritten = match item {
             syn::Item::Impl(item_impl) => {
                 if !mod_path.segments.is_empty() {
                     return Err(syn::Error::new(
@@ -807,10 +1436,72 @@ pub fn extern_spec(attr: TokenStream, tokens: TokenStream) -> TokenStream {
                 Span::call_site(), // this covers the entire macro invocation, unlike attr.span() which changes to only cover arguments if possible
                 "Extern specs cannot be attached to this item",
             )),
-        }
+        }?;
+        let rewritten = if is_override {
+            extern_spec_rewriter::mark_override(rewritten)?
+        } else {
+            rewritten
+        };
+        let ignored_attrs = ignored_attrs.reports;
+        Ok(quote! {
+            #rewritten
+            #ignored_attrs
+        })
     })
 }
 
+/// Collects the Prusti attributes of an `#[extern_spec]` item that Prusti
+/// ignores. Only the attributes of the specified functions and methods are
+/// used; those of the enclosing impl blocks, traits, modules and extern
+/// blocks and those of associated types and constants are dropped.
+#[derive(Default)]
+struct IgnoredExternSpecAttributes {
+    reports: TokenStream,
+}
+
+impl IgnoredExternSpecAttributes {
+    fn report(&mut self, attrs: &[syn::Attribute], position: &str) {
+        self.reports.extend(report_ignored_prusti_attributes(
+            attrs,
+            &format!("{position} in external specs"),
+        ));
+    }
+}
+
+impl<'ast> syn::visit::Visit<'ast> for IgnoredExternSpecAttributes {
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        self.report(&item.attrs, "impl blocks");
+        syn::visit::visit_item_impl(self, item);
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast syn::ItemTrait) {
+        self.report(&item.attrs, "traits");
+        syn::visit::visit_item_trait(self, item);
+    }
+
+    fn visit_item_mod(&mut self, item: &'ast syn::ItemMod) {
+        self.report(&item.attrs, "modules");
+        syn::visit::visit_item_mod(self, item);
+    }
+
+    fn visit_item_foreign_mod(&mut self, item: &'ast syn::ItemForeignMod) {
+        self.report(&item.attrs, "extern blocks");
+        syn::visit::visit_item_foreign_mod(self, item);
+    }
+
+    fn visit_trait_item_type(&mut self, item: &'ast syn::TraitItemType) {
+        self.report(&item.attrs, "associated types");
+    }
+
+    fn visit_trait_item_const(&mut self, item: &'ast syn::TraitItemConst) {
+        self.report(&item.attrs, "associated constants");
+    }
+
+    fn visit_block(&mut self, _block: &'ast syn::Block) {
+        // Bodies of external specifications are stubs
+    }
+}
+
 pub fn predicate(tokens: TokenStream) -> TokenStream {
     let parsed = handle_result!(predicate::parse_predicate(tokens));
     parsed.into_token_stream()
@@ -871,9 +1562,17 @@ fn extract_prusti_attributes_for_types(
                     SpecAttributeKind::RefineSpec => unreachable!("refine_spec on type"),
                     SpecAttributeKind::Pure => unreachable!("pure on type"),
                     SpecAttributeKind::Verified => unreachable!("verified on type"),
+                    SpecAttributeKind::Law => unreachable!("law on type"),
+                    SpecAttributeKind::TrustedUnsafeMemory => {
+                        unreachable!("trusted_unsafe_memory on type")
+                    }
                     SpecAttributeKind::Invariant => unreachable!("invariant on type"),
                     SpecAttributeKind::Predicate => unreachable!("predicate on type"),
                     SpecAttributeKind::Terminates => unreachable!("terminates on type"),
+                    SpecAttributeKind::Timeout => unreachable!("timeout on type"),
+                    SpecAttributeKind::EnsuresOnCompletion => {
+                        unreachable!("ensures_on_completion on type")
+                    }
                     SpecAttributeKind::Trusted | SpecAttributeKind::Model => {
                         assert!(attr.tokens.is_empty(), "Unexpected shape of an attribute.");
                         attr.tokens
@@ -915,10 +1614,14 @@ fn generate_spec_and_assertions_for_types(
             SpecAttributeKind::AssertOnExpiry => unreachable!(),
             SpecAttributeKind::Pure => unreachable!(),
             SpecAttributeKind::Verified => unreachable!(),
+            SpecAttributeKind::Law => unreachable!(),
+            SpecAttributeKind::TrustedUnsafeMemory => unreachable!(),
             SpecAttributeKind::Predicate => unreachable!(),
             SpecAttributeKind::Invariant => unreachable!(),
             SpecAttributeKind::RefineSpec => unreachable!(),
             SpecAttributeKind::Terminates => unreachable!(),
+            SpecAttributeKind::Timeout => unreachable!(),
+            SpecAttributeKind::EnsuresOnCompletion => unreachable!(),
             SpecAttributeKind::Trusted => generate_for_trusted_for_types(attr_tokens, item),
             SpecAttributeKind::Model => generate_for_model(attr_tokens, item),
             SpecAttributeKind::PrintCounterexample => {
@@ -1098,7 +1801,12 @@ pub fn ghost(tokens: TokenStream) -> TokenStream {
         for (loop_label, loop_span) in visitor.loops.iter() {
             let loop_span = loop_span.unwrap();
             let label_match = break_label == loop_label || break_label.is_none();
-            let break_inside = loop_span.join(break_span.unwrap()).unwrap().eq(&loop_span);
+            // Spans from different macro expansions cannot be joined, in which
+            // case the `break` is conservatively assumed to leave the loop
+            let break_inside = matches!(
+                loop_span.join(break_span.unwrap()),
+                Some(joined) if joined.eq(&loop_span)
+            );
             if label_match && break_inside {
                 continue 'breaks;
             }
@@ -1132,3 +1840,430 @@ pub fn ghost(tokens: TokenStream) -> TokenStream {
         syn_errors
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    /// The attributes of the function `name` among the rewritten `tokens`
+    /// which are not generated by the rewriting.
+    fn user_attributes(tokens: TokenStream, name: &str) -> Vec<syn::Attribute> {
+        let file: syn::File = syn::parse2(tokens).unwrap();
+        let function = file
+            .items
+            .into_iter()
+            .find_map(|item| match item {
+                syn::Item::Fn(function) if function.sig.ident == name => Some(function),
+                _ => None,
+            })
+            .unwrap();
+        function
+            .attrs
+            .into_iter()
+            .filter(|attr| attr.path.segments[0].ident != "prusti")
+            .collect()
+    }
+
+    #[test]
+    fn test_regular_attributes_are_preserved() {
+        let item = quote! {
+            #[inline(always)]
+            #[ensures(result > 0)]
+            #[must_use]
+            #[cfg_attr(test, allow(dead_code))]
+            #[pure]
+            #[doc = "Returns one."]
+            fn one() -> u32 { 1 }
+        };
+        let rewritten =
+            rewrite_prusti_attributes(SpecAttributeKind::Requires, quote! { true }, item);
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote! { #[inline(always)] },
+            parse_quote! { #[must_use] },
+            parse_quote! { #[cfg_attr(test, allow(dead_code))] },
+            parse_quote! { #[doc = "Returns one."] },
+        ];
+        assert_eq!(expected, user_attributes(rewritten, "one"));
+    }
+
+    #[test]
+    fn test_regular_attributes_on_predicates_are_preserved() {
+        let predicate = predicate::parse_predicate(quote! {
+            /// Holds for positive numbers.
+            #[inline]
+            #[trusted]
+            #[cfg_attr(test, must_use)]
+            fn positive(x: i32) -> bool { x > 0 }
+        })
+        .unwrap();
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote! { #[doc = r" Holds for positive numbers."] },
+            parse_quote! { #[inline] },
+            parse_quote! { #[cfg_attr(test, must_use)] },
+        ];
+        let attributes: Vec<_> = user_attributes(predicate.into_token_stream(), "positive")
+            .into_iter()
+            .filter(|attr| !attr.path.is_ident("allow"))
+            .collect();
+        assert_eq!(expected, attributes);
+    }
+
+    /// The items among the rewritten `tokens` which are only emitted under
+    /// the configuration predicate `predicate`.
+    fn cfg_items(tokens: TokenStream, predicate: TokenStream) -> Vec<syn::ItemFn> {
+        let file: syn::File = syn::parse2(tokens).unwrap();
+        let cfg: syn::Attribute = parse_quote! { #[cfg(#predicate)] };
+        file.items
+            .into_iter()
+            .filter_map(|item| match item {
+                syn::Item::Fn(function) if function.attrs.contains(&cfg) => Some(function),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cfg_attr_specs_are_conditional() {
+        let item = quote! {
+            #[cfg_attr(feature = "strict", ensures(result > 0), inline)]
+            fn one() -> u32 { 1 }
+        };
+        let rewritten =
+            rewrite_prusti_attributes(SpecAttributeKind::Requires, quote! { true }, item);
+        let spec_items = cfg_items(rewritten.clone(), quote! { feature = "strict" });
+        assert_eq!(1, spec_items.len());
+        let spec_id = spec_items[0]
+            .attrs
+            .iter()
+            .find_map(|attr| match attr.parse_meta() {
+                Ok(syn::Meta::NameValue(name_value))
+                    if name_value.path.segments.last().unwrap().ident == "spec_id" =>
+                {
+                    Some(name_value.lit)
+                }
+                _ => None,
+            })
+            .unwrap();
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote! { #[cfg_attr(feature = "strict", prusti::post_spec_id_ref = #spec_id)] },
+            parse_quote! { #[cfg_attr(feature = "strict", inline)] },
+        ];
+        let attributes: Vec<_> = syn::parse2::<syn::File>(rewritten)
+            .unwrap()
+            .items
+            .into_iter()
+            .find_map(|item| match item {
+                syn::Item::Fn(function) if function.sig.ident == "one" => Some(function.attrs),
+                _ => None,
+            })
+            .unwrap()
+            .into_iter()
+            .filter(|attr| attr.path.is_ident("cfg_attr"))
+            .collect();
+        assert_eq!(expected, attributes);
+    }
+
+    #[test]
+    fn test_nested_cfg_attr_specs_are_conditional() {
+        let item = quote! {
+            #[cfg_attr(unix, cfg_attr(feature = "strict", requires(x > 0)))]
+            fn id(x: u32) -> u32 { x }
+        };
+        let rewritten = rewrite_prusti_attributes(SpecAttributeKind::Pure, quote! {}, item);
+        assert_eq!(
+            1,
+            cfg_items(rewritten.clone(), quote! { all(unix, feature = "strict") }).len()
+        );
+        // Only the reference to the conditional spec item remains
+        let attributes = user_attributes(rewritten, "id");
+        assert_eq!(1, attributes.len());
+        let attribute = attributes[0].tokens.to_string();
+        let predicate = quote! { all(unix, feature = "strict") };
+        assert!(attribute.starts_with(&format!("({predicate} , prusti :: pre_spec_id_ref =")));
+    }
+
+    #[test]
+    fn test_cfg_attr_specs_in_trait_refinements_are_conditional() {
+        let impl_block = quote! {
+            impl Trait for Struct {
+                #[cfg_attr(feature = "strict", ensures(result > 0))]
+                #[cfg_attr(feature = "lenient", ensures(result >= 0))]
+                fn get(&self) -> u32 { 1 }
+            }
+        };
+        let rewritten = refine_trait_spec(quote! {}, impl_block);
+        for predicate in [
+            quote! { feature = "strict" },
+            quote! { feature = "lenient" },
+        ] {
+            assert_eq!(1, cfg_items(rewritten.clone(), predicate).len());
+        }
+        let file: syn::File = syn::parse2(rewritten).unwrap();
+        let Some(syn::Item::Impl(impl_block)) = file.items.last() else {
+            panic!("expected the impl block last");
+        };
+        let syn::ImplItem::Method(method) = &impl_block.items[0] else {
+            panic!("expected a method");
+        };
+        let paths: Vec<_> = method
+            .attrs
+            .iter()
+            .map(|attr| {
+                let syn::Meta::List(list) = attr.parse_meta().unwrap() else {
+                    panic!("expected a `cfg_attr`");
+                };
+                let Some(syn::NestedMeta::Meta(nested)) = list.nested.last() else {
+                    panic!("expected a nested attribute");
+                };
+                nested.path().to_token_stream().to_string()
+            })
+            .collect();
+        let expected = quote! { prusti::post_spec_id_ref }.to_string();
+        assert_eq!(vec![expected.clone(), expected], paths);
+    }
+
+    /// The names of the functions, at any depth, among the rewritten `tokens`.
+    fn function_names(tokens: TokenStream) -> Vec<String> {
+        struct FunctionNames(Vec<String>);
+        impl<'ast> syn::visit::Visit<'ast> for FunctionNames {
+            fn visit_item_fn(&mut self, function: &'ast syn::ItemFn) {
+                self.0.push(function.sig.ident.to_string());
+                syn::visit::visit_item_fn(self, function);
+            }
+            fn visit_impl_item_method(&mut self, function: &'ast syn::ImplItemMethod) {
+                self.0.push(function.sig.ident.to_string());
+                syn::visit::visit_impl_item_method(self, function);
+            }
+        }
+        let file: syn::File = syn::parse2(tokens).unwrap();
+        let mut names = FunctionNames(Vec::new());
+        syn::visit::Visit::visit_file(&mut names, &file);
+        names.0
+    }
+
+    #[test]
+    fn test_extern_spec_stubs_of_functions_with_the_same_name_are_distinct() {
+        let module = quote! {
+            mod std {
+                mod iter {
+                    #[ensures(true)]
+                    fn empty<T>() -> std::iter::Empty<T>;
+                }
+                mod io {
+                    #[ensures(true)]
+                    fn empty() -> std::io::Empty;
+                }
+            }
+        };
+        let stubs = |tokens: TokenStream| -> Vec<String> {
+            function_names(tokens)
+                .into_iter()
+                .filter(|name| name.starts_with("prusti_extern_spec_empty_"))
+                .collect()
+        };
+        let names = stubs(extern_spec(quote! {}, module.clone()));
+        assert_eq!(2, names.len());
+        assert_ne!(names[0], names[1]);
+        // The names do not change between compilations.
+        assert_eq!(names, stubs(extern_spec(quote! {}, module)));
+        let stub = quote! { fn empty<T>() -> std::iter::Empty<T>; };
+        assert_eq!(
+            names[..1],
+            stubs(extern_spec(quote! { std::iter }, stub))[..]
+        );
+    }
+
+    #[test]
+    fn test_invariants_of_types_with_the_same_name_are_distinct() {
+        // Like types of different modules, or generated by the same macro.
+        let invariant_items = || -> Vec<String> {
+            let item = quote! {
+                struct Counter {
+                    value: u32,
+                }
+            };
+            function_names(invariant(quote! { self.value < 10 }, item))
+                .into_iter()
+                .filter(|name| name.starts_with("prusti_invariant_item_Counter_"))
+                .collect()
+        };
+        let first = invariant_items();
+        let second = invariant_items();
+        assert_eq!(1, first.len());
+        assert_eq!(1, second.len());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_pure_refinements_of_methods_with_the_same_name_are_distinct() {
+        let ghost_items = |self_type: TokenStream| -> Vec<String> {
+            let impl_block = quote! {
+                impl Trait for #self_type {
+                    #[refine_spec(where T: Copy, [pure])]
+                    fn check<T>(&self, _t: T) -> bool { true }
+                }
+            };
+            function_names(refine_trait_spec(quote! {}, impl_block))
+                .into_iter()
+                .filter(|name| name.starts_with("prusti_pure_ghost_item_check"))
+                .collect()
+        };
+        let first = ghost_items(quote! { First });
+        let second = ghost_items(quote! { Second });
+        assert_eq!(1, first.len());
+        assert_eq!(1, second.len());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_trusted_reason_is_recorded() {
+        let item = quote! { fn one() -> u32 { 1 } };
+        let rewritten = rewrite_prusti_attributes(
+            SpecAttributeKind::Trusted,
+            quote! { "the body is too slow to verify" },
+            item.clone(),
+        );
+        let file: syn::File = syn::parse2(rewritten).unwrap();
+        let Some(syn::Item::Fn(function)) = file.items.last() else {
+            panic!("expected the function last");
+        };
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote! { #[prusti::trusted] },
+            parse_quote! { #[prusti::trusted_reason = "the body is too slow to verify"] },
+        ];
+        assert_eq!(expected, function.attrs[..2]);
+
+        let rewritten =
+            rewrite_prusti_attributes(SpecAttributeKind::Trusted, quote! { too slow }, item);
+        assert!(rewritten
+            .to_string()
+            .contains("only takes a string literal with the reason"));
+    }
+
+    #[test]
+    fn test_embedded_specs_match_attributes() {
+        let item = quote! { fn next(x: u32) -> u32 { x + 1 } };
+        let clauses =
+            embedding::parse_clauses(quote! { requires(x > 0), ensures(result > x), pure, })
+                .unwrap();
+        let kinds: Vec<_> = clauses.iter().map(|clause| clause.kind()).collect();
+        assert_eq!(
+            vec![
+                SpecAttributeKind::Requires,
+                SpecAttributeKind::Ensures,
+                SpecAttributeKind::Pure
+            ],
+            kinds
+        );
+
+        /// The generated attributes without the identifiers of the specs,
+        /// which are unique to each expansion.
+        fn attribute_names(function: &syn::ItemFn) -> Vec<String> {
+            function
+                .attrs
+                .iter()
+                .map(|attr| attr.path.to_token_stream().to_string())
+                .collect()
+        }
+        let embedded: syn::File =
+            syn::parse2(embedding::embed_specs(clauses, item.clone()).unwrap()).unwrap();
+        let rewritten: syn::File = syn::parse2(rewrite_prusti_attributes(
+            SpecAttributeKind::Requires,
+            quote! { x > 0 },
+            quote! { #[ensures(result > x)] #[pure] #item },
+        ))
+        .unwrap();
+        assert_eq!(rewritten.items.len(), embedded.items.len());
+        let (Some(syn::Item::Fn(embedded)), Some(syn::Item::Fn(rewritten))) =
+            (embedded.items.last(), rewritten.items.last())
+        else {
+            panic!("expected the function last");
+        };
+        assert_eq!(attribute_names(rewritten), attribute_names(embedded));
+    }
+
+    #[test]
+    fn test_embedded_specs_reject_type_clauses() {
+        let item = quote! { fn one() -> u32 { 1 } };
+        let clauses = embedding::parse_clauses(quote! { invariant(true) }).unwrap();
+        assert!(embedding::generate_specs(clauses, item.clone()).is_err());
+        assert!(embedding::parse_clauses(quote! { requires(true), unknown(true) }).is_err());
+        let clauses = vec![embedding::Clause::new(
+            SpecAttributeKind::Ensures,
+            quote! { result == 1 },
+        )];
+        let specs = embedding::generate_specs(clauses, item).unwrap();
+        assert_eq!(1, specs.spec_items.len());
+    }
+
+    #[test]
+    fn test_specs_of_foreign_functions_are_rejected() {
+        let rewritten = rewrite_prusti_attributes(
+            SpecAttributeKind::Ensures,
+            quote! { result >= 0 },
+            quote! { pub fn abs(x: i32) -> i32; },
+        );
+        assert!(rewritten
+            .to_string()
+            .contains("have to be given in an `#[extern_spec]` block"));
+    }
+
+    #[test]
+    fn test_extern_spec_of_foreign_functions() {
+        let rewritten = extern_spec(
+            quote! { crate },
+            quote! {
+                extern "C" {
+                    #[ensures(result >= 0)]
+                    pub fn abs(x: i32) -> i32;
+                }
+            },
+        );
+        let names = function_names(rewritten);
+        assert_eq!(1, names.len());
+        assert!(names[0].starts_with("prusti_extern_spec_abs_"));
+
+        let rewritten = extern_spec(
+            quote! { crate },
+            quote! { extern "C" { fn printf(format: *const u8, ...) -> i32; } },
+        );
+        assert!(rewritten
+            .to_string()
+            .contains("variadic foreign functions are not supported"));
+    }
+
+    #[test]
+    fn test_extern_spec_of_traits_with_generic_associated_types() {
+        let rewritten = extern_spec(
+            quote! { crate },
+            quote! {
+                trait Lending {
+                    type Item<'a>: Copy where Self: 'a;
+                    #[ensures(result.is_some())]
+                    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+                }
+            },
+        );
+        let file: syn::File = syn::parse2(rewritten).unwrap();
+        let Some(syn::Item::Struct(generated_struct)) = file.items.first() else {
+            panic!("expected the generated struct");
+        };
+        let where_clause = generated_struct.generics.where_clause.as_ref().unwrap();
+        let expected: syn::WherePredicate = parse_quote! {
+            for<'a> <Prusti_T_Self as crate::Lending::<>>::Item<'a>: Copy
+        };
+        assert_eq!(
+            expected.to_token_stream().to_string(),
+            where_clause.predicates[1].to_token_stream().to_string()
+        );
+
+        let rewritten = extern_spec(
+            quote! { crate },
+            quote! { trait Container { type Item<T>: Copy; } },
+        );
+        assert!(rewritten
+            .to_string()
+            .contains("with type or const parameters are not supported"));
+    }
+}
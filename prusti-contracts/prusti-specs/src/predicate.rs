@@ -2,6 +2,7 @@
 
 use crate::{
     common::{HasMacro, HasSignature},
+    diagnostics::SpecErrorExt,
     rewriter, SpecificationId, SPECS_VERSION,
 };
 use proc_macro2::{Span, TokenStream};
@@ -84,9 +85,10 @@ fn parse_predicate_internal(
     let input: PredicateFnInput = syn::parse2(tokens).map_err(|e| {
         syn::Error::new(
             e.span(),
-            "`predicate!` can only be used on function definitions; it supports no attributes",
+            "`predicate!` can only be used on function definitions",
         )
     })?;
+    let attributes = parse_predicate_attributes(&input)?;
     let return_type = match &input.fn_sig.output {
         syn::ReturnType::Default => {
             return Err(syn::Error::new(
@@ -103,7 +105,7 @@ fn parse_predicate_internal(
 
         if in_spec_refinement {
             let patched_function: syn::ImplItemMethod =
-                patch_predicate_macro_body(&input, span, spec_id);
+                patch_predicate_macro_body(&input, span, spec_id, &attributes);
             let spec_function = generate_spec_function(
                 input.body.unwrap(),
                 return_type,
@@ -116,7 +118,8 @@ fn parse_predicate_internal(
                 patched_function,
             }))
         } else {
-            let patched_function: syn::ItemFn = patch_predicate_macro_body(&input, span, spec_id);
+            let patched_function: syn::ItemFn =
+                patch_predicate_macro_body(&input, span, spec_id, &attributes);
             let spec_function = generate_spec_function(
                 input.body.unwrap(),
                 return_type,
@@ -130,10 +133,25 @@ fn parse_predicate_internal(
             }))
         }
     } else {
+        if let Some(unfold_depth) = attributes.unfold_depth {
+            return Err(syn::Error::new(
+                unfold_depth.span(),
+                "`#[unfold_depth(n)]` can only be used on predicates with a body",
+            ));
+        }
+        if let Some(trusted) = attributes.trusted {
+            return Err(syn::Error::new(
+                trusted.span(),
+                "`#[trusted]` can only be used on predicates with a body",
+            )
+            .help("abstract predicates have no body that could be hidden from the verifier"));
+        }
         let signature = input.fn_sig;
+        let regular_attrs = &attributes.regular;
         let patched_function = parse_quote_spanned!(span=>
             #[prusti::abstract_predicate]
             #[prusti::specs_version = #SPECS_VERSION]
+            #(#regular_attrs)*
             #signature;
         );
 
@@ -147,15 +165,28 @@ fn patch_predicate_macro_body<R: Parse>(
     predicate: &PredicateFnInput,
     input_span: Span,
     spec_id: SpecificationId,
+    attributes: &PredicateAttributes,
 ) -> R {
     let visibility = &predicate.visibility;
     let signature = &predicate.fn_sig;
     let spec_id_str = spec_id.to_string();
+    let unfold_depth_attr = attributes.unfold_depth.as_ref().map(|depth| {
+        let depth_str = depth.base10_digits();
+        quote_spanned!(depth.span()=> #[prusti::unfold_depth = #depth_str])
+    });
+    let trusted_attr = attributes
+        .trusted
+        .as_ref()
+        .map(|trusted| quote_spanned!(trusted.span()=> #[prusti::trusted]));
+    let regular_attrs = &attributes.regular;
 
     parse_quote_spanned!(input_span=>
         #[allow(unused_must_use, unused_variables, dead_code)]
         #[prusti::pred_spec_id_ref = #spec_id_str]
+        #unfold_depth_attr
+        #trusted_attr
         #[prusti::specs_version = #SPECS_VERSION]
+        #(#regular_attrs)*
         #visibility #signature {
             unimplemented!("predicate")
         }
@@ -179,13 +210,114 @@ fn generate_spec_function<T: HasSignature + Spanned>(
 
 #[derive(Debug)]
 struct PredicateFnInput {
+    attrs: Vec<syn::Attribute>,
     visibility: Option<syn::Visibility>,
     fn_sig: syn::Signature,
     body: Option<TokenStream>,
 }
 
+/// The attributes supported on a `predicate!`.
+#[derive(Default)]
+struct PredicateAttributes {
+    /// The depth given by `#[unfold_depth(n)]`, which determines how many
+    /// times the body of a recursive predicate is unfolded into its own
+    /// definition.
+    unfold_depth: Option<syn::LitInt>,
+    /// A `#[trusted]` attribute, which hides the body of the predicate from
+    /// the verifier.
+    trusted: Option<syn::Attribute>,
+    /// The attributes that are not Prusti attributes, such as doc comments,
+    /// in their original order. They are kept on the predicate function.
+    regular: Vec<syn::Attribute>,
+}
+
+/// Parses the attributes of a `predicate!`. Of the Prusti attributes, only a
+/// single `#[trusted]` and a single `#[unfold_depth(n)]` with a positive `n`
+/// are supported; every other one is reported with its own error. All other
+/// attributes are kept.
+fn parse_predicate_attributes(predicate: &PredicateFnInput) -> syn::Result<PredicateAttributes> {
+    let mut attributes = PredicateAttributes::default();
+    let mut errors: Option<syn::Error> = None;
+    let mut report = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+    for attr in &predicate.attrs {
+        let name = attr
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        match name.as_str() {
+            "unfold_depth" => {
+                if let Some(previous) = &attributes.unfold_depth {
+                    report(
+                        syn::Error::new(attr.span(), "duplicate `#[unfold_depth(n)]` attribute")
+                            .note_at(previous.span(), "the unfold depth is first given here"),
+                    );
+                    continue;
+                }
+                let depth: syn::LitInt = match attr.parse_args() {
+                    Ok(depth) => depth,
+                    Err(error) => {
+                        report(error);
+                        continue;
+                    }
+                };
+                match depth.base10_parse::<u32>() {
+                    Ok(0) => report(syn::Error::new(
+                        depth.span(),
+                        "the unfold depth of a predicate must be positive",
+                    )),
+                    Ok(_) => attributes.unfold_depth = Some(depth),
+                    Err(error) => report(error),
+                }
+            }
+            "trusted" | "prusti_contracts::trusted" => {
+                if let Some(previous) = &attributes.trusted {
+                    report(
+                        syn::Error::new(attr.span(), "duplicate `#[trusted]` attribute")
+                            .note_at(previous.span(), "first marked trusted here"),
+                    );
+                } else if !attr.tokens.is_empty() {
+                    report(syn::Error::new(
+                        attr.tokens.span(),
+                        "`#[trusted]` does not take any arguments",
+                    ));
+                } else {
+                    attributes.trusted = Some(attr.clone());
+                }
+            }
+            _ if crate::prusti_attribute_kind(attr).is_none() => {
+                attributes.regular.push(attr.clone())
+            }
+            _ => report(
+                syn::Error::new(
+                    attr.span(),
+                    format!("`#[{name}]` is incompatible with `predicate!`"),
+                )
+                .note_at(
+                    predicate.fn_sig.ident.span(),
+                    "the attribute is applied to this predicate",
+                )
+                .help(
+                    "predicates support no Prusti attributes other than `#[trusted]` and \
+                    `#[unfold_depth(n)]`",
+                ),
+            ),
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(attributes),
+    }
+}
+
 impl syn::parse::Parse for PredicateFnInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
         let visibility = input.parse().ok();
         let fn_sig = input.parse()?;
 
@@ -201,6 +333,7 @@ impl syn::parse::Parse for PredicateFnInput {
         };
 
         Ok(PredicateFnInput {
+            attrs,
             visibility,
             fn_sig,
             body,
@@ -2,13 +2,19 @@ use crate::{
     common::HasSignature,
     specifications::{
         common::{SpecificationId, SpecificationIdGenerator},
-        preparser::{parse_prusti, parse_prusti_assert_pledge, parse_prusti_pledge},
+        preparser::{
+            parse_prusti, parse_prusti_assert_pledge, parse_prusti_pledge, reject_call_summaries,
+            reject_prev_iter, translate_call_summaries,
+        },
         untyped,
     },
 };
-use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote, quote_spanned};
-use syn::{parse_quote_spanned, punctuated::Punctuated, spanned::Spanned, Pat, Token, Type};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse_quote_spanned, punctuated::Punctuated, spanned::Spanned, visit_mut::VisitMut, Pat, Token,
+    Type,
+};
 
 pub(crate) struct AstRewriter {
     spec_id_generator: SpecificationIdGenerator,
@@ -18,6 +24,7 @@ pub(crate) struct AstRewriter {
 pub enum SpecItemType {
     Precondition,
     Postcondition,
+    CompletionPostcondition,
     Pledge,
     Predicate(TokenStream),
     Termination,
@@ -28,6 +35,7 @@ impl std::fmt::Display for SpecItemType {
         match self {
             SpecItemType::Precondition => write!(f, "pre"),
             SpecItemType::Postcondition => write!(f, "post"),
+            SpecItemType::CompletionPostcondition => write!(f, "post_on_completion"),
             SpecItemType::Pledge => write!(f, "pledge"),
             SpecItemType::Predicate(_) => write!(f, "pred"),
             SpecItemType::Termination => write!(f, "term"),
@@ -65,21 +73,63 @@ impl AstRewriter {
         None
     }
 
-    fn generate_result_arg<T: HasSignature + Spanned>(&self, item: &T) -> syn::FnArg {
+    /// The result of an `async fn` is the output of its future, which is the
+    /// declared return type.
+    ///
+    /// The argument is declared with the span of the `result` used in the
+    /// specification (see `find_result_span`), so that it resolves even if the
+    /// specification and the function come from different macro expansions.
+    fn generate_result_arg<T: HasSignature + Spanned>(
+        &self,
+        item: &T,
+        expr: &TokenStream,
+    ) -> syn::FnArg {
         let item_span = item.span();
+        let result_span = find_result_span(expr).unwrap_or(item_span);
         let output_ty = match &item.sig().output {
             syn::ReturnType::Default => parse_quote_spanned!(item_span=> ()),
             syn::ReturnType::Type(_, ty) => ty.clone(),
         };
         let fn_arg = syn::FnArg::Typed(syn::PatType {
             attrs: Vec::new(),
-            pat: Box::new(parse_quote_spanned!(item_span=> result)),
+            pat: Box::new(parse_quote_spanned!(result_span=> result)),
             colon_token: syn::Token![:](item.sig().output.span()),
             ty: output_ty,
         });
         fn_arg
     }
 
+    /// The result of a postcondition on completion is the output of the
+    /// returned future: the declared return type of an `async fn`, or `T` for
+    /// a function returning e.g. `impl Future<Output = T>`.
+    fn generate_completion_result_arg<T: HasSignature + Spanned>(
+        &self,
+        item: &T,
+        expr: &TokenStream,
+    ) -> syn::Result<syn::FnArg> {
+        if item.sig().asyncness.is_some() {
+            return Ok(self.generate_result_arg(item, expr));
+        }
+        let output_ty = match &item.sig().output {
+            syn::ReturnType::Type(_, ty) => future_output_ty(ty),
+            syn::ReturnType::Default => None,
+        };
+        let Some(output_ty) = output_ty else {
+            return Err(syn::Error::new(
+                item.sig().output.span(),
+                "`ensures_on_completion` can only be used on async functions and on \
+                functions returning a future with a known `Output` type",
+            ));
+        };
+        let result_span = find_result_span(expr).unwrap_or_else(|| item.span());
+        Ok(syn::FnArg::Typed(syn::PatType {
+            attrs: Vec::new(),
+            pat: Box::new(parse_quote_spanned!(result_span=> result)),
+            colon_token: syn::Token![:](item.sig().output.span()),
+            ty: Box::new(output_ty),
+        }))
+    }
+
     /// Turn an expression into the appropriate function
     pub fn generate_spec_item_fn<T: HasSignature + Spanned>(
         &mut self,
@@ -94,6 +144,14 @@ impl AstRewriter {
                 "it is not allowed to use the keyword `result` as a function argument".to_string(),
             ));
         }
+        reject_prev_iter(&expr)?;
+        let expr = if matches!(spec_type, SpecItemType::Postcondition) {
+            translate_call_summaries(expr)?
+        } else {
+            reject_call_summaries(&expr)?;
+            expr
+        };
+        let expr = project_old_places(expr)?;
         let item_span = expr.span();
         let item_name = syn::Ident::new(
             &format!("prusti_{}_item_{}_{}", spec_type, item.sig().ident, spec_id),
@@ -134,9 +192,16 @@ impl AstRewriter {
 
         spec_item.sig.generics = item.sig().generics.clone();
         spec_item.sig.inputs = item.sig().inputs.clone();
+        // The specification of an unsafe function may use unsafe operations,
+        // such as dereferencing raw pointers.
+        spec_item.sig.unsafety = item.sig().unsafety;
         match spec_type {
             SpecItemType::Postcondition | SpecItemType::Pledge => {
-                let fn_arg = self.generate_result_arg(item);
+                let fn_arg = self.generate_result_arg(item, &expr);
+                spec_item.sig.inputs.push(fn_arg);
+            }
+            SpecItemType::CompletionPostcondition => {
+                let fn_arg = self.generate_completion_result_arg(item, &expr)?;
                 spec_item.sig.inputs.push(fn_arg);
             }
             _ => (),
@@ -177,7 +242,7 @@ impl AstRewriter {
     ) -> syn::Result<syn::Item> {
         let item_span = item.span();
         let item_name = syn::Ident::new(
-            &format!("prusti_pure_ghost_item_{}", item.sig().ident),
+            &format!("prusti_pure_ghost_item_{}_{}", item.sig().ident, spec_id),
             item_span,
         );
 
@@ -208,6 +273,28 @@ impl AstRewriter {
         Ok((lhs_item, rhs_item))
     }
 
+    /// Generate the marker of a loop that is verified by unrolling it at most
+    /// `bound` times
+    pub fn process_loop_unrolling_bound(
+        &mut self,
+        spec_id: SpecificationId,
+        tokens: TokenStream,
+    ) -> syn::Result<TokenStream> {
+        let bound = parse_unrolling_bound(tokens)?;
+        let spec_id_str = spec_id.to_string();
+        let bound_str = bound.base10_digits();
+        Ok(quote_spanned! {bound.span()=>
+            {
+                #[prusti::spec_only]
+                #[prusti::loop_unrolling_bound = #bound_str]
+                #[prusti::spec_id = #spec_id_str]
+                || -> bool {
+                    true
+                };
+            }
+        })
+    }
+
     /// Parse a loop invariant into a Rust expression
     pub fn process_loop_variant(
         &mut self,
@@ -215,6 +302,8 @@ impl AstRewriter {
         tokens: TokenStream,
     ) -> syn::Result<TokenStream> {
         let expr = parse_prusti(tokens)?;
+        reject_prev_iter(&expr)?;
+        reject_call_summaries(&expr)?;
         let spec_id_str = spec_id.to_string();
         Ok(quote_spanned! {expr.span()=>
             {
@@ -234,7 +323,25 @@ impl AstRewriter {
         spec_id: SpecificationId,
         tokens: TokenStream,
     ) -> syn::Result<TokenStream> {
-        self.process_prusti_expression(quote! {loop_body_invariant_spec}, spec_id, tokens)
+        let expr = parse_prusti(tokens)?;
+        self.process_prusti_expression(&[quote! {loop_body_invariant_spec}], spec_id, expr)
+    }
+
+    /// Parse a loop invariant placed before the loop into a Rust expression
+    pub fn process_loop_invariant_before_loop(
+        &mut self,
+        spec_id: SpecificationId,
+        tokens: TokenStream,
+    ) -> syn::Result<TokenStream> {
+        let expr = parse_prusti(tokens)?;
+        self.process_prusti_expression(
+            &[
+                quote! {loop_body_invariant_spec},
+                quote! {loop_invariant_before_loop},
+            ],
+            spec_id,
+            expr,
+        )
     }
 
     /// Parse a prusti assertion into a Rust expression
@@ -243,7 +350,9 @@ impl AstRewriter {
         spec_id: SpecificationId,
         tokens: TokenStream,
     ) -> syn::Result<TokenStream> {
-        self.process_prusti_expression(quote! {prusti_assertion}, spec_id, tokens)
+        let expr = parse_prusti(tokens)?;
+        reject_prev_iter(&expr)?;
+        self.process_prusti_expression(&[quote! {prusti_assertion}], spec_id, expr)
     }
 
     /// Parse a prusti assumption into a Rust expression
@@ -252,7 +361,9 @@ impl AstRewriter {
         spec_id: SpecificationId,
         tokens: TokenStream,
     ) -> syn::Result<TokenStream> {
-        self.process_prusti_expression(quote! {prusti_assumption}, spec_id, tokens)
+        let expr = parse_prusti(tokens)?;
+        reject_prev_iter(&expr)?;
+        self.process_prusti_expression(&[quote! {prusti_assumption}], spec_id, expr)
     }
 
     /// Parse a prusti refute into a Rust expression
@@ -261,21 +372,24 @@ impl AstRewriter {
         spec_id: SpecificationId,
         tokens: TokenStream,
     ) -> syn::Result<TokenStream> {
-        self.process_prusti_expression(quote! {prusti_refutation}, spec_id, tokens)
+        let expr = parse_prusti(tokens)?;
+        reject_prev_iter(&expr)?;
+        self.process_prusti_expression(&[quote! {prusti_refutation}], spec_id, expr)
     }
 
     fn process_prusti_expression(
         &mut self,
-        kind: TokenStream,
+        kinds: &[TokenStream],
         spec_id: SpecificationId,
-        tokens: TokenStream,
+        expr: TokenStream,
     ) -> syn::Result<TokenStream> {
-        let expr = parse_prusti(tokens)?;
+        reject_call_summaries(&expr)?;
+        let expr = snapshot_old_places(project_old_places(expr)?)?;
         let spec_id_str = spec_id.to_string();
         Ok(quote_spanned! {expr.span()=>
             {
                 #[prusti::spec_only]
-                #[prusti::#kind]
+                #(#[prusti::#kinds])*
                 #[prusti::spec_id = #spec_id_str]
                 || -> bool {
                     #expr
@@ -338,6 +452,8 @@ impl AstRewriter {
         tokens: TokenStream,
     ) -> syn::Result<syn::Expr> {
         let expr = parse_prusti(tokens)?;
+        reject_prev_iter(&expr)?;
+        reject_call_summaries(&expr)?;
         let spec_id_str = spec_id.to_string();
         let callsite_span = Span::call_site();
         Ok(parse_quote_spanned! {callsite_span=>
@@ -352,3 +468,241 @@ impl AstRewriter {
         })
     }
 }
+
+/// Parses the `bound = K` argument of `no_invariant_needed!`.
+fn parse_unrolling_bound(tokens: TokenStream) -> syn::Result<syn::LitInt> {
+    let tokens_span = tokens.span();
+    let error = || {
+        syn::Error::new(
+            tokens_span,
+            "expected `no_invariant_needed!` to have the form `no_invariant_needed!(bound = K)`",
+        )
+    };
+    let meta: syn::MetaNameValue = syn::parse2(tokens).map_err(|_| error())?;
+    if !meta.path.is_ident("bound") {
+        return Err(error());
+    }
+    let syn::Lit::Int(bound) = meta.lit else {
+        return Err(error());
+    };
+    bound.base10_parse::<u32>()?;
+    Ok(bound)
+}
+
+/// Field accesses on an old value are moved into the `old`: `old(x).f` becomes
+/// `old(x.f)`. The two are equivalent, but only the mentioned projection of
+/// `x` then needs to be snapshotted in the old state, instead of the whole
+/// value of `x`.
+fn project_old_places(expr: TokenStream) -> syn::Result<TokenStream> {
+    struct OldPlaceProjector;
+    impl VisitMut for OldPlaceProjector {
+        fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+            syn::visit_mut::visit_expr_mut(self, expr);
+            let syn::Expr::Field(field) = expr else {
+                return;
+            };
+            let syn::Expr::Call(call) = &*field.base else {
+                return;
+            };
+            let Some(arg) = old_call_place_argument(call) else {
+                return;
+            };
+            let member = &field.member;
+            let projected: syn::Expr = if let syn::Expr::Unary(_) = arg {
+                parse_quote_spanned! {arg.span()=> (#arg).#member }
+            } else {
+                parse_quote_spanned! {arg.span()=> #arg.#member }
+            };
+            let mut call = call.clone();
+            call.args[0] = projected;
+            *expr = syn::Expr::Call(call);
+        }
+    }
+    let mut expr: syn::Expr = syn::parse2(expr)?;
+    OldPlaceProjector.visit_expr_mut(&mut expr);
+    Ok(expr.into_token_stream())
+}
+
+/// Specifications in a function body, such as `prusti_assert!`, are closures
+/// that capture the variables they mention. Since `old` takes its argument by
+/// value, `old(x)` would move a non-`Copy` variable `x` into the closure, and
+/// `x` could no longer be used after the specification. The argument of `old`
+/// is therefore replaced by a snapshot if it is a place: `old(snap(&(x)))`.
+fn snapshot_old_places(expr: TokenStream) -> syn::Result<TokenStream> {
+    struct OldPlaceSnapshotter;
+    impl VisitMut for OldPlaceSnapshotter {
+        fn visit_expr_call_mut(&mut self, call: &mut syn::ExprCall) {
+            syn::visit_mut::visit_expr_call_mut(self, call);
+            if let Some(arg) = old_call_place_argument(call) {
+                call.args[0] = parse_quote_spanned! {arg.span()=>
+                    ::prusti_contracts::snap(&(#arg))
+                };
+            }
+        }
+    }
+    let mut expr: syn::Expr = syn::parse2(expr)?;
+    OldPlaceSnapshotter.visit_expr_mut(&mut expr);
+    Ok(expr.into_token_stream())
+}
+
+/// If `call` is `old(e)` and `e` is a place expression, returns `e`.
+fn old_call_place_argument(call: &syn::ExprCall) -> Option<&syn::Expr> {
+    let is_old = match &*call.func {
+        syn::Expr::Path(syn::ExprPath { path, .. }) => {
+            matches!(path.segments.last(), Some(segment) if segment.ident == "old")
+        }
+        _ => false,
+    };
+    if is_old && call.args.len() == 1 && is_place(&call.args[0]) {
+        Some(&call.args[0])
+    } else {
+        None
+    }
+}
+
+fn is_place(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Path(_) => true,
+        syn::Expr::Field(syn::ExprField { base, .. }) => is_place(base),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Deref(_),
+            expr,
+            ..
+        }) => is_place(expr),
+        syn::Expr::Paren(syn::ExprParen { expr, .. }) => is_place(expr),
+        _ => false,
+    }
+}
+
+/// The `Output` of a future type such as `impl Future<Output = T>` or
+/// `Pin<Box<dyn Future<Output = T>>>`.
+fn future_output_ty(ty: &syn::Type) -> Option<syn::Type> {
+    let bounds = match ty {
+        syn::Type::ImplTrait(syn::TypeImplTrait { bounds, .. })
+        | syn::Type::TraitObject(syn::TypeTraitObject { bounds, .. }) => bounds,
+        syn::Type::Paren(syn::TypeParen { elem, .. }) => return future_output_ty(elem),
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            let segment = path.segments.last()?;
+            if segment.ident != "Pin" && segment.ident != "Box" {
+                return None;
+            }
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            return match args.args.first()? {
+                syn::GenericArgument::Type(inner_ty) => future_output_ty(inner_ty),
+                _ => None,
+            };
+        }
+        _ => return None,
+    };
+    bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            return None;
+        };
+        let segment = trait_bound.path.segments.last()?;
+        if segment.ident != "Future" {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Binding(binding) if binding.ident == "Output" => {
+                Some(binding.ty.clone())
+            }
+            _ => None,
+        })
+    })
+}
+
+/// The span of the first use of the variable `result` in a specification.
+///
+/// If a function and its specification are produced by different macro
+/// expansions, e.g. when a `macro_rules!` macro receives the specification as
+/// an argument, the `result` written in the specification is not resolved by
+/// a `result` argument declared with the span of the function.
+fn find_result_span(expr: &TokenStream) -> Option<Span> {
+    let mut previous: Option<TokenTree> = None;
+    for token in expr.clone() {
+        match &token {
+            TokenTree::Ident(ident) if ident == "result" => {
+                let is_path_or_field = matches!(
+                    &previous,
+                    Some(TokenTree::Punct(p)) if p.as_char() == '.' || p.as_char() == ':'
+                );
+                if !is_path_or_field {
+                    return Some(ident.span());
+                }
+            }
+            TokenTree::Group(group) => {
+                if let Some(span) = find_result_span(&group.stream()) {
+                    return Some(span);
+                }
+            }
+            _ => {}
+        }
+        previous = Some(token);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_old_places() {
+        let project = |expr: &str| {
+            project_old_places(expr.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(project("old(x).f == 1"), "old (x . f) == 1");
+        assert_eq!(project("old(*self).a.0 > 0"), "old ((* self) . a . 0) > 0");
+        assert_eq!(project("old(x.len()).f"), "old (x . len ()) . f");
+        assert_eq!(project("old(x).len()"), "old (x) . len ()");
+    }
+
+    #[test]
+    fn test_future_output_ty() {
+        let output = |ty: &str| {
+            future_output_ty(&syn::parse_str(ty).unwrap())
+                .map(|ty| ty.to_token_stream().to_string())
+        };
+        assert_eq!(output("impl Future<Output = u32>").as_deref(), Some("u32"));
+        assert_eq!(
+            output("impl core::future::Future<Output = Vec<u8>> + Send").as_deref(),
+            Some("Vec < u8 >")
+        );
+        assert_eq!(
+            output("Pin<Box<dyn Future<Output = bool>>>").as_deref(),
+            Some("bool")
+        );
+        assert_eq!(output("impl Future"), None);
+        assert_eq!(output("u32"), None);
+    }
+
+    #[test]
+    fn test_snapshot_old_places() {
+        let snapshot = |expr: &str| {
+            snapshot_old_places(expr.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            snapshot("x == old(x) + 1"),
+            "x == old (:: prusti_contracts :: snap (& (x))) + 1"
+        );
+        assert_eq!(snapshot("old(x.len())"), "old (x . len ())");
+    }
+
+    #[test]
+    fn test_find_result_span() {
+        let has_result = |expr: &str| find_result_span(&expr.parse().unwrap()).is_some();
+        assert!(has_result("result > 0"));
+        assert!(has_result("forall(|i: usize| i < result.len())"));
+        assert!(!has_result("x.result > 0"));
+        assert!(!has_result("core::result::Result::is_ok(&x)"));
+    }
+}
@@ -18,6 +18,35 @@ pub enum SpecAttributeKind {
     Terminates = 10,
     PrintCounterexample = 11,
     Verified = 12,
+    Law = 13,
+    TrustedUnsafeMemory = 14,
+    Timeout = 15,
+    EnsuresOnCompletion = 16,
+}
+
+impl SpecAttributeKind {
+    /// The name of the attribute, as written in the source code.
+    pub fn name(self) -> &'static str {
+        match self {
+            SpecAttributeKind::Requires => "requires",
+            SpecAttributeKind::Ensures => "ensures",
+            SpecAttributeKind::AfterExpiry => "after_expiry",
+            SpecAttributeKind::AssertOnExpiry => "assert_on_expiry",
+            SpecAttributeKind::Pure => "pure",
+            SpecAttributeKind::Trusted => "trusted",
+            SpecAttributeKind::Predicate => "predicate",
+            SpecAttributeKind::Invariant => "invariant",
+            SpecAttributeKind::RefineSpec => "refine_spec",
+            SpecAttributeKind::Terminates => "terminates",
+            SpecAttributeKind::Model => "model",
+            SpecAttributeKind::PrintCounterexample => "print_counterexample",
+            SpecAttributeKind::Verified => "verified",
+            SpecAttributeKind::Law => "law",
+            SpecAttributeKind::TrustedUnsafeMemory => "trusted_unsafe_memory",
+            SpecAttributeKind::Timeout => "timeout",
+            SpecAttributeKind::EnsuresOnCompletion => "ensures_on_completion",
+        }
+    }
 }
 
 impl TryFrom<String> for SpecAttributeKind {
@@ -37,6 +66,10 @@ impl TryFrom<String> for SpecAttributeKind {
             "model" => Ok(SpecAttributeKind::Model),
             "print_counterexample" => Ok(SpecAttributeKind::PrintCounterexample),
             "verified" => Ok(SpecAttributeKind::Verified),
+            "law" => Ok(SpecAttributeKind::Law),
+            "trusted_unsafe_memory" => Ok(SpecAttributeKind::TrustedUnsafeMemory),
+            "timeout" => Ok(SpecAttributeKind::Timeout),
+            "ensures_on_completion" => Ok(SpecAttributeKind::EnsuresOnCompletion),
             _ => Err(name),
         }
     }
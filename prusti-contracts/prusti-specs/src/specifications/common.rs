@@ -56,6 +56,7 @@ pub struct SpecificationId(Uuid);
 pub enum SpecIdRef {
     Precondition(SpecificationId),
     Postcondition(SpecificationId),
+    CompletionPostcondition(SpecificationId),
     Purity(SpecificationId),
     Pledge {
         lhs: Option<SpecificationId>,
@@ -110,6 +111,23 @@ pub(crate) fn generate_struct_name_for_trait(item: &syn::ItemTrait) -> String {
     format!("PrustiTrait{}_{}", item.ident, uuid)
 }
 
+/// Returns a hash of `path` which, unlike the random identifiers of
+/// specification items, is the same in every compilation. It disambiguates
+/// generated items whose names are derived from an identifier that may be
+/// shared by several paths, such as the `swap` of `std::mem::swap` and
+/// `std::ptr::swap`.
+pub(crate) fn stable_path_hash(path: &str) -> String {
+    // 64-bit FNV-1a, whose result does not depend on the compiler version
+    // like the one of `std::hash::DefaultHasher` may.
+    let hash = path
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
 fn generate_name_for_type(ty: &syn::Type) -> Option<String> {
     match ty {
         syn::Type::Path(ty_path) => Some(String::from_iter(
@@ -8,6 +8,8 @@ use syn::{
     spanned::Spanned,
 };
 
+use crate::diagnostics::SpecErrorExt;
+
 /// The representation of an argument to a quantifier (for example `a: i32`)
 #[derive(Debug, Clone)]
 pub struct Arg {
@@ -23,9 +25,10 @@ pub fn parse_prusti(tokens: TokenStream) -> syn::Result<TokenStream> {
     Ok(parsed)
 }
 pub fn parse_prusti_pledge(tokens: TokenStream) -> syn::Result<TokenStream> {
-    // TODO: pledges with reference that is not "result" are not supported;
-    // for this reason we assert here that the reference (if there is any) is "result"
-    // then return the RHS only
+    // TODO: pledges with reference that is not "result" are not supported,
+    // because the encoder has a single magic wand per function, for the
+    // returned reference; for this reason we assert here that the reference
+    // (if there is any) is "result" then return the RHS only
     let (reference, rhs) = PrustiTokenStream::new(tokens).parse_pledge()?;
     if let Some(reference) = reference {
         if reference.to_string() != "result" {
@@ -40,9 +43,10 @@ pub fn parse_prusti_pledge(tokens: TokenStream) -> syn::Result<TokenStream> {
 }
 
 pub fn parse_prusti_assert_pledge(tokens: TokenStream) -> syn::Result<(TokenStream, TokenStream)> {
-    // TODO: pledges with reference that is not "result" are not supported;
-    // for this reason we assert here that the reference (if there is any) is "result"
-    // then return the RHS only
+    // TODO: pledges with reference that is not "result" are not supported,
+    // because the encoder has a single magic wand per function, for the
+    // returned reference; for this reason we assert here that the reference
+    // (if there is any) is "result" then return the RHS only
     let (reference, lhs, rhs) = PrustiTokenStream::new(tokens).parse_assert_pledge()?;
     if let Some(reference) = reference {
         if reference.to_string() != "result" {
@@ -57,10 +61,138 @@ pub fn parse_prusti_assert_pledge(tokens: TokenStream) -> syn::Result<(TokenStre
     Ok((lhs, rhs))
 }
 
+/// Reports an error if a specification that is not a loop invariant refers to
+/// the state of the previous loop iteration with `prev_iter(..)`.
+pub fn reject_prev_iter(tokens: &TokenStream) -> syn::Result<()> {
+    let mut previous: Option<&TokenTree> = None;
+    let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident) if ident == "prev_iter" => {
+                let is_method = matches!(previous, Some(TokenTree::Punct(p)) if p.as_char() == '.');
+                let is_call = matches!(
+                    tokens.get(index + 1),
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis
+                );
+                if is_call && !is_method {
+                    return err(
+                        ident.span(),
+                        "`prev_iter(..)` can only be used in loop invariants",
+                    );
+                }
+            }
+            TokenTree::Group(group) => reject_prev_iter(&group.stream())?,
+            _ => {}
+        }
+        previous = Some(token);
+    }
+    Ok(())
+}
+
+/// Translates the references `call_summary!(f(args))` of a postcondition to
+/// the result of the last call `f(args)` made by the specified function into
+/// calls of `prusti_contracts::call_summary`.
+pub fn translate_call_summaries(tokens: TokenStream) -> syn::Result<TokenStream> {
+    let mut translated = TokenStream::new();
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut index = 0;
+    while index < tokens.len() {
+        match &tokens[index] {
+            TokenTree::Ident(ident) if ident == "call_summary" && is_macro_call(&tokens, index) => {
+                let TokenTree::Group(group) = &tokens[index + 2] else {
+                    unreachable!()
+                };
+                let call = group.stream();
+                if reject_call_summaries(&call).is_err() {
+                    return err(ident.span(), "`call_summary!(..)` cannot be nested");
+                }
+                match syn::parse2::<syn::Expr>(call.clone())? {
+                    syn::Expr::Call(_) | syn::Expr::MethodCall(_) => {}
+                    other => {
+                        return err(
+                            other.span(),
+                            "expected a call in `call_summary!(..)`, e.g. `call_summary!(f(x))`",
+                        )
+                    }
+                }
+                translated.extend(quote_spanned! {ident.span()=>
+                    ::prusti_contracts::call_summary(
+                        #[prusti::spec_only]
+                        #[prusti::call_summary]
+                        || #call
+                    )
+                });
+                index += 3;
+                continue;
+            }
+            TokenTree::Group(group) => {
+                let mut new_group = proc_macro2::Group::new(
+                    group.delimiter(),
+                    translate_call_summaries(group.stream())?,
+                );
+                new_group.set_span(group.span());
+                translated.extend([TokenTree::Group(new_group)]);
+            }
+            token => translated.extend([token.clone()]),
+        }
+        index += 1;
+    }
+    Ok(translated)
+}
+
+/// Reports an error if a specification that is not a postcondition refers to
+/// the result of a call with `call_summary!(..)`.
+pub fn reject_call_summaries(tokens: &TokenStream) -> syn::Result<()> {
+    let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident) if ident == "call_summary" && is_macro_call(&tokens, index) => {
+                return err(
+                    ident.span(),
+                    "`call_summary!(..)` can only be used in postconditions",
+                );
+            }
+            TokenTree::Group(group) => reject_call_summaries(&group.stream())?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Whether the identifier at `index` is the name of a macro invoked with
+/// parentheses, as in `name!(..)`.
+fn is_macro_call(tokens: &[TokenTree], index: usize) -> bool {
+    matches!(tokens.get(index + 1), Some(TokenTree::Punct(p)) if p.as_char() == '!')
+        && matches!(
+            tokens.get(index + 2),
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis
+        )
+}
+
 pub fn parse_type_cond_spec(tokens: TokenStream) -> syn::Result<TypeCondSpecRefinement> {
     syn::parse2(tokens)
 }
 
+/// Parses a type invariant of the form `self.field |= |args| [specs]`, which
+/// declares the contract of the callable stored in `field`. Returns `None` if
+/// the invariant has a different form.
+pub fn parse_field_spec_entailment(
+    tokens: TokenStream,
+) -> syn::Result<Option<FieldSpecEntailment>> {
+    let has_field_spec_entailment_prefix = |input: ParseStream| -> syn::Result<()> {
+        input.parse::<syn::Token![self]>()?;
+        input.parse::<syn::Token![.]>()?;
+        input.parse::<syn::Ident>()?;
+        input.parse::<syn::Token![|=]>()?;
+        input.parse::<TokenStream>()?;
+        Ok(())
+    };
+    if syn::parse::Parser::parse2(has_field_spec_entailment_prefix, tokens.clone()).is_err() {
+        return Ok(None);
+    }
+    syn::parse2(tokens).map(Some)
+}
+
 /*
 Preparsing consists of two stages:
 
@@ -83,6 +215,10 @@ fn error(span: Span, msg: &str) -> syn::Error {
     syn::Error::new(span, msg)
 }
 
+/// The help message for malformed quantifiers.
+const QUANTIFIER_SYNTAX_HELP: &str =
+    "quantifiers are written as `forall(|x: T| body)` or `exists(|x: T| body)`";
+
 /// Same as `error`, conveniently packaged as `syn::Result::Err`.
 fn err<T>(span: Span, msg: &str) -> syn::Result<T> {
     Err(error(span, msg))
@@ -149,6 +285,11 @@ impl PrustiTokenStream {
                     PrustiToken::Quantifier(ident.span(), Quantifier::Forall),
                 (TokenTree::Ident(ident), _, _, _) if ident == "exists" =>
                     PrustiToken::Quantifier(ident.span(), Quantifier::Exists),
+                // `is` is not a keyword, so it is only recognised as the
+                // pattern operator if it is not a field or a path segment
+                (TokenTree::Ident(ident), _, _, _) if ident == "is"
+                    && !(pos >= 2 && matches!(&source[pos - 2], TokenTree::Punct(p) if p.as_char() == '.' || p.as_char() == ':')) =>
+                    PrustiToken::Is(ident.span()),
                 (TokenTree::Punct(punct), _, _, _)
                     if punct.as_char() == ',' && punct.spacing() == Alone =>
                     PrustiToken::BinOp(punct.span(), PrustiBinaryOp::Rust(RustOp::Comma)),
@@ -216,10 +357,17 @@ impl PrustiTokenStream {
             self.tokens
                 .into_iter()
                 .map(|token| match token {
-                    PrustiToken::Group(_, _, box stream) => stream.parse_rust_only(),
+                    PrustiToken::Group(span, delimiter, box stream) => {
+                        let mut group =
+                            proc_macro2::Group::new(delimiter, stream.parse_rust_only()?);
+                        group.set_span(span);
+                        Ok(TokenTree::Group(group).to_token_stream())
+                    }
                     PrustiToken::Token(tree) => Ok(tree.to_token_stream()),
                     PrustiToken::BinOp(span, PrustiBinaryOp::Rust(op)) => Ok(op.to_tokens(span)),
-                    _ => err(token.span(), "unexpected Prusti syntax"),
+                    _ => Err(error(token.span(), "unexpected Prusti syntax").help(
+                        "Prusti operators such as `==>` cannot be used in closure arguments or patterns",
+                    )),
                 })
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter(),
@@ -267,9 +415,7 @@ impl PrustiTokenStream {
     fn expr_bp(&mut self, min_bp: u8) -> syn::Result<TokenStream> {
         let mut lhs = match self.tokens.pop_front() {
             Some(PrustiToken::Group(span, delimiter, box stream)) => {
-                let mut group = proc_macro2::Group::new(delimiter, stream.parse()?);
-                group.set_span(span);
-                TokenTree::Group(group).to_token_stream()
+                translate_group(span, delimiter, stream)?
             }
             Some(PrustiToken::Outer(span)) => {
                 let _stream = self
@@ -280,51 +426,79 @@ impl PrustiTokenStream {
             Some(PrustiToken::Quantifier(span, kind)) => {
                 let mut stream = self.pop_group(Delimiter::Parenthesis).ok_or_else(|| {
                     error(span, "expected parenthesized expression after quantifier")
+                        .help(QUANTIFIER_SYNTAX_HELP)
+                })?;
+                let args = stream.pop_closure_args().ok_or_else(|| {
+                    error(span, "expected quantifier body").help(QUANTIFIER_SYNTAX_HELP)
                 })?;
-                let args = stream
-                    .pop_closure_args()
-                    .ok_or_else(|| error(span, "expected quantifier body"))?;
 
-                {
-                    // for quantifiers, argument types must be explicit
-                    // here we parse the closure with syn and check each
-                    // argument has a type annotation
-                    let cl_args = args.clone().parse_rust_only()?;
-                    let check_cl = quote! { | #cl_args | 0 };
-                    let parsed_cl = syn::parse2::<syn::ExprClosure>(check_cl)?;
-                    for pat in parsed_cl.inputs {
-                        match pat {
-                            syn::Pat::Type(_) => {}
-                            _ => {
-                                return err(
-                                    pat.span(),
-                                    "quantifier arguments must have explicit types",
-                                )
-                            }
+                // for quantifiers, argument types must be explicit
+                // here we parse the closure with syn and check each
+                // argument has a type annotation
+                let cl_args = args.clone().parse_rust_only()?;
+                let check_cl = quote! { | #cl_args | 0 };
+                let parsed_cl = syn::parse2::<syn::ExprClosure>(check_cl)?;
+                for pat in &parsed_cl.inputs {
+                    match pat {
+                        syn::Pat::Type(_) => {}
+                        _ => {
+                            return Err(error(
+                                pat.span(),
+                                "quantifier arguments must have explicit types",
+                            )
+                            .help_at(
+                                pat.span(),
+                                &format!("add a type annotation, e.g. `{}: usize`", quote!(#pat)),
+                            ))
                         }
                     }
-                };
+                }
 
                 let triggers = stream.extract_triggers()?;
                 if args.is_empty() {
-                    return err(span, "a quantifier must have at least one argument");
+                    return Err(error(span, "a quantifier must have at least one argument")
+                        .help(QUANTIFIER_SYNTAX_HELP));
+                }
+                if let (Quantifier::Forall, Some(containment)) =
+                    (&kind, stream.as_containment_quantifier(&parsed_cl.inputs))
+                {
+                    if !triggers.is_empty() {
+                        return err(
+                            span,
+                            "triggers of a quantifier over the elements of a collection are chosen automatically",
+                        );
+                    }
+                    containment.translate(span)?
+                } else {
+                    let args = args.parse()?;
+                    let body = stream.parse()?;
+                    kind.translate(span, triggers, args, body)
                 }
-                let args = args.parse()?;
-                let body = stream.parse()?;
-                kind.translate(span, triggers, args, body)
             }
 
             Some(PrustiToken::SpecEnt(span, _)) | Some(PrustiToken::CallDesc(span, _)) => {
                 return err(span, "unexpected operator")
             }
 
+            Some(PrustiToken::Is(span)) => return err(span, "expected expression before `is`"),
+
             // some Rust binary operators can appear on their own, e.g. `(..)`
             Some(PrustiToken::BinOp(span, PrustiBinaryOp::Rust(op))) => op.to_tokens(span),
 
-            Some(PrustiToken::BinOp(span, _)) => return err(span, "unexpected binary operator"),
+            Some(PrustiToken::BinOp(span, op)) => {
+                return Err(error(span, "unexpected binary operator").help(&format!(
+                    "`{}` needs an expression on both sides, as in `a {} b`",
+                    op.symbol(),
+                    op.symbol()
+                )))
+            }
             Some(PrustiToken::Token(token)) => token.to_token_stream(),
             None => return Ok(TokenStream::new()),
         };
+        // The scrutinee and the pattern if `lhs` is an `is` expression, so that
+        // the variables bound by the pattern can be used in the RHS of a
+        // following `==>` or `&&`.
+        let mut is_match = None;
         loop {
             let (span, op) = match self.tokens.front() {
                 // If we see a group or token, we simply add them to the
@@ -332,9 +506,7 @@ impl PrustiTokenStream {
                 // precedence operators (e.g. plus) are connected into atoms
                 // as far as our parser is concerned.
                 Some(PrustiToken::Group(span, delimiter, box stream)) => {
-                    let mut group = proc_macro2::Group::new(*delimiter, stream.clone().parse()?);
-                    group.set_span(*span);
-                    lhs.extend(TokenTree::Group(group).to_token_stream());
+                    lhs.extend(translate_group(*span, *delimiter, stream.clone())?);
                     self.tokens.pop_front();
                     continue;
                 }
@@ -367,6 +539,20 @@ impl PrustiTokenStream {
 
                 Some(PrustiToken::CallDesc(..)) => todo!("call desc"),
 
+                Some(PrustiToken::Is(span)) => {
+                    let span = *span;
+                    self.tokens.pop_front();
+                    let pattern = self.pop_pattern();
+                    if pattern.is_empty() {
+                        return err(span, "expected pattern after `is`");
+                    }
+                    let pattern = pattern.parse_rust_only()?;
+                    let scrutinee = lhs;
+                    lhs = quote_spanned! { span => match (#scrutinee) { #pattern => true, _ => false } };
+                    is_match = Some((scrutinee, pattern));
+                    continue;
+                }
+
                 Some(PrustiToken::BinOp(span, op)) => (*span, *op),
                 Some(PrustiToken::Outer(span)) => return err(*span, "unexpected outer"),
                 Some(PrustiToken::Quantifier(span, _)) => {
@@ -380,7 +566,14 @@ impl PrustiTokenStream {
                 break;
             }
             self.tokens.pop_front();
-            let rhs = self.expr_bp(r_bp)?;
+            let is_match = is_match.take();
+            // The RHS of `&&` following an `is` expression extends over the
+            // following conjuncts, which are all in the scope of the pattern.
+            let rhs = if is_match.is_some() && op == PrustiBinaryOp::And {
+                self.expr_bp(l_bp)?
+            } else {
+                self.expr_bp(r_bp)?
+            };
 
             // In [new], when identifying consecutive sequences of operators,
             // we delegate to `parse_op*` which identifies Rust operators. In
@@ -389,9 +582,27 @@ impl PrustiTokenStream {
             // Prusti defines, as the actual Rust operators will raise a parse
             // error after desugaring anyway.
             if !matches!(op, PrustiBinaryOp::Rust(_)) && rhs.is_empty() {
-                return err(span, "expected expression");
+                let mut error = error(span, "expected expression");
+                if let (Some(first), Some(last)) = (
+                    lhs.clone().into_iter().next(),
+                    lhs.clone().into_iter().last(),
+                ) {
+                    error = error.note_at(
+                        join_spans(first.span(), last.span()),
+                        &format!("this is the left-hand side of `{}`", op.symbol()),
+                    );
+                }
+                return Err(error.help(&format!("add an expression after `{}`", op.symbol())));
             }
-            lhs = op.translate(span, lhs, rhs);
+            lhs = match (is_match, op) {
+                (Some((scrutinee, pattern)), PrustiBinaryOp::Implies) => {
+                    translate_is_match(span, scrutinee, pattern, rhs, true)
+                }
+                (Some((scrutinee, pattern)), PrustiBinaryOp::And) => {
+                    translate_is_match(span, scrutinee, pattern, rhs, false)
+                }
+                _ => op.translate(span, lhs, rhs),
+            };
         }
         Ok(lhs)
     }
@@ -403,6 +614,29 @@ impl PrustiTokenStream {
         }
     }
 
+    /// Pops the pattern of an `is` expression, which extends up to the next
+    /// binary operator that cannot be part of a pattern.
+    fn pop_pattern(&mut self) -> Self {
+        let mut tokens = VecDeque::new();
+        while let Some(token) = self.tokens.front() {
+            match token {
+                PrustiToken::Token(_)
+                | PrustiToken::Group(..)
+                | PrustiToken::BinOp(
+                    _,
+                    PrustiBinaryOp::Rust(RustOp::Range | RustOp::RangeInclusive),
+                ) => {
+                    tokens.push_back(self.tokens.pop_front().unwrap());
+                }
+                _ => break,
+            }
+        }
+        Self {
+            tokens,
+            source_span: self.source_span,
+        }
+    }
+
     fn pop_closure_args(&mut self) -> Option<Self> {
         let mut tokens = VecDeque::new();
 
@@ -499,6 +733,78 @@ impl PrustiTokenStream {
         res
     }
 
+    /// Recognizes the body of a quantifier over the elements of a collection,
+    /// i.e. `forall(|x: &T| v.contains(x) ==> body)`. The quantified variable
+    /// must be a single reference which is only constrained by the
+    /// `contains` call on the left-hand side of the implication.
+    fn as_containment_quantifier(
+        &self,
+        args: &syn::punctuated::Punctuated<syn::Pat, syn::Token![,]>,
+    ) -> Option<ContainmentQuantifier> {
+        let [syn::Pat::Type(arg)] = &args.iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        let syn::Pat::Ident(elem) = &*arg.pat else {
+            return None;
+        };
+        if !matches!(*arg.ty, syn::Type::Reference(_)) {
+            return None;
+        }
+        let implies_pos = self
+            .tokens
+            .iter()
+            .position(|token| matches!(token, PrustiToken::BinOp(_, PrustiBinaryOp::Implies)))?;
+        // operators binding weaker than the implication would make the
+        // `contains` call not the premise of the whole body
+        if self.tokens.iter().skip(implies_pos + 1).any(|token| {
+            matches!(
+                token,
+                PrustiToken::BinOp(_, PrustiBinaryOp::Iff | PrustiBinaryOp::ImpliesReverse)
+            )
+        }) {
+            return None;
+        }
+        let premise = self.tokens.range(..implies_pos).collect::<Vec<_>>();
+        let [collection @ .., PrustiToken::Token(TokenTree::Punct(dot)), PrustiToken::Token(TokenTree::Ident(method)), PrustiToken::Group(_, Delimiter::Parenthesis, box contains_arg)] =
+            &premise[..]
+        else {
+            return None;
+        };
+        if dot.as_char() != '.' || method != "contains" || collection.is_empty() {
+            return None;
+        }
+        if !matches!(
+            &contains_arg.tokens.iter().collect::<Vec<_>>()[..],
+            [PrustiToken::Token(TokenTree::Ident(ident))] if *ident == elem.ident
+        ) {
+            return None;
+        }
+        // the collection must be a plain path or method chain such as
+        // `self.items` or `v.as_slice()`
+        let is_collection = collection.iter().all(|token| match token {
+            PrustiToken::Token(TokenTree::Ident(_)) | PrustiToken::Group(..) => true,
+            PrustiToken::Token(TokenTree::Punct(punct)) => {
+                punct.as_char() == '.' || punct.as_char() == ':'
+            }
+            _ => false,
+        });
+        if !is_collection || matches!(collection[0], PrustiToken::Token(TokenTree::Punct(_))) {
+            return None;
+        }
+        Some(ContainmentQuantifier {
+            elem: elem.ident.clone(),
+            elem_ty: (*arg.ty).clone(),
+            collection: Self {
+                tokens: collection.iter().map(|&token| token.clone()).collect(),
+                source_span: self.source_span,
+            },
+            body: Self {
+                tokens: self.tokens.range(implies_pos + 1..).cloned().collect(),
+                source_span: self.source_span,
+            },
+        })
+    }
+
     fn extract_triggers(&mut self) -> syn::Result<Vec<Vec<TokenStream>>> {
         let len = self.tokens.len();
         if len < 4 {
@@ -522,6 +828,7 @@ impl PrustiTokenStream {
                             .pop_group(Delimiter::Parenthesis)
                             .ok_or_else(|| {
                                 error(*triggers_span, "trigger sets must be tuples of expressions")
+                                    .help("a trigger set with a single trigger is written as a one-element tuple, e.g. `triggers=[(f(x),)]`")
                             })?
                             .split(PrustiBinaryOp::Rust(RustOp::Comma), true)
                             .into_iter()
@@ -537,9 +844,62 @@ impl PrustiTokenStream {
     }
 }
 
+/// Translates a delimited group back into Rust syntax.
+fn translate_group(
+    span: Span,
+    delimiter: Delimiter,
+    stream: PrustiTokenStream,
+) -> syn::Result<TokenStream> {
+    let mut content = stream.parse()?;
+    if delimiter == Delimiter::Brace {
+        content = snapshot_struct_update_base(content);
+    }
+    let mut group = proc_macro2::Group::new(delimiter, content);
+    group.set_span(span);
+    Ok(TokenTree::Group(group).to_token_stream())
+}
+
+/// In a struct update expression such as `S { x: 5, ..old(*self) }`, the
+/// fields that are not mentioned are moved out of the base expression. If the
+/// base is an `old(...)` of a place behind a reference, the move is rejected by
+/// the borrow checker, so we take a snapshot of the place instead:
+/// `S { x: 5, ..old(snap(&(*self))) }`. The snapshot has the same type and
+/// value, therefore the rewrite is harmless for `Copy` types.
+fn snapshot_struct_update_base(content: TokenStream) -> TokenStream {
+    let mut tokens = content.into_iter().collect::<Vec<_>>();
+    let len = tokens.len();
+    if len < 4 {
+        return TokenStream::from_iter(tokens);
+    }
+    if let [TokenTree::Punct(p1), TokenTree::Punct(p2), TokenTree::Ident(ident), TokenTree::Group(group)] =
+        &tokens[len - 4..]
+    {
+        if p1.as_char() == '.'
+            && p1.spacing() == Joint
+            && p2.as_char() == '.'
+            && p2.spacing() == Alone
+            && ident == "old"
+            && group.delimiter() == Delimiter::Parenthesis
+        {
+            let base = group.stream();
+            let mut new_group = proc_macro2::Group::new(
+                Delimiter::Parenthesis,
+                quote_spanned! { group.span() => ::prusti_contracts::snap(&(#base)) },
+            );
+            new_group.set_span(group.span());
+            tokens[len - 1] = TokenTree::Group(new_group);
+        }
+    }
+    TokenStream::from_iter(tokens)
+}
+
 #[derive(Debug)]
 pub struct TypeCondSpecRefinement {
+    /// The trait bounds of the refinement. An equality constraint `T = U`
+    /// is represented by the bound `T: ::prusti_contracts::SameType<U>`.
     pub trait_bounds: Vec<syn::PredicateType>,
+    /// Generic parameters fixed to a concrete type by an equality constraint
+    pub type_equalities: Vec<(syn::Ident, syn::Type)>,
     pub specs: Vec<NestedSpec<TokenStream>>,
 }
 
@@ -548,21 +908,88 @@ impl Parse for TypeCondSpecRefinement {
         input
             .parse::<syn::Token![where]>()
             .map_err(with_type_cond_spec_example)?;
+        let (trait_bounds, type_equalities) = parse_trait_bounds(input)?;
         Ok(TypeCondSpecRefinement {
-            trait_bounds: parse_trait_bounds(input)?,
+            trait_bounds,
+            type_equalities,
             specs: PrustiTokenStream::new(input.parse().unwrap())
                 .parse_rest(|pts| pts.pop_group_of_nested_specs(input.span()))?,
         })
     }
 }
 
-fn parse_trait_bounds(input: ParseStream) -> syn::Result<Vec<syn::PredicateType>> {
+/// The contract of the callable stored in a field, declared by a type invariant
+/// `self.field |= |args| [specs]`.
+#[derive(Debug)]
+pub struct FieldSpecEntailment {
+    pub field: syn::Ident,
+    /// The arguments of the callable, with their types if given
+    pub args: Vec<(syn::Ident, Option<syn::Type>)>,
+    pub specs: Vec<NestedSpec<TokenStream>>,
+}
+
+impl Parse for FieldSpecEntailment {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<syn::Token![self]>()?;
+        input.parse::<syn::Token![.]>()?;
+        let field = input.parse()?;
+        let spec_ent_token = input.parse::<syn::Token![|=]>()?;
+        if input.peek(syn::Token![!]) {
+            return err(
+                spec_ent_token.span(),
+                "single-call specification entailments are not supported for fields",
+            );
+        }
+        let mut args = vec![];
+        if input.parse::<Option<syn::Token![||]>>()?.is_none() {
+            input.parse::<syn::Token![|]>()?;
+            while !input.peek(syn::Token![|]) {
+                let name = input.parse()?;
+                let typ = if input.parse::<Option<syn::Token![:]>>()?.is_some() {
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                args.push((name, typ));
+                if input.parse::<Option<syn::Token![,]>>()?.is_none() {
+                    break;
+                }
+            }
+            input.parse::<syn::Token![|]>()?;
+        }
+        Ok(FieldSpecEntailment {
+            field,
+            args,
+            specs: PrustiTokenStream::new(input.parse().unwrap())
+                .parse_rest(|pts| pts.pop_group_of_nested_specs(spec_ent_token.span()))?,
+        })
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_trait_bounds(
+    input: ParseStream,
+) -> syn::Result<(Vec<syn::PredicateType>, Vec<(syn::Ident, syn::Type)>)> {
     let mut bounds: Vec<syn::PredicateType> = Vec::new();
+    let mut equalities: Vec<(syn::Ident, syn::Type)> = Vec::new();
     loop {
-        let predicate = input
-            .parse::<syn::WherePredicate>()
-            .map_err(with_type_cond_spec_example)?;
-        bounds.push(validate_predicate(predicate)?);
+        // `syn` does not parse equality constraints as where predicates
+        let fork = input.fork();
+        if fork.parse::<syn::Type>().is_ok() && fork.peek(syn::Token![=]) {
+            let eq_bound = syn::PredicateEq {
+                lhs_ty: input.parse()?,
+                eq_token: input.parse()?,
+                rhs_ty: input.parse().map_err(with_type_cond_spec_example)?,
+            };
+            let (param, ty, bound) = validate_equality(eq_bound)?;
+            equalities.push((param, ty));
+            bounds.push(bound);
+        } else {
+            let predicate = input
+                .parse::<syn::WherePredicate>()
+                .map_err(with_type_cond_spec_example)?;
+            bounds.push(validate_predicate(predicate)?);
+        }
         input
             .parse::<syn::token::Comma>()
             .map_err(with_type_cond_spec_example)?;
@@ -572,7 +999,33 @@ fn parse_trait_bounds(input: ParseStream) -> syn::Result<Vec<syn::PredicateType>
             break;
         }
     }
-    Ok(bounds)
+    Ok((bounds, equalities))
+}
+
+/// Checks that the equality constraint `T = U` fixes a generic parameter `T`,
+/// and translates it to a trait bound which only holds if `T` is `U`.
+fn validate_equality(
+    eq_bound: syn::PredicateEq,
+) -> syn::Result<(syn::Ident, syn::Type, syn::PredicateType)> {
+    let param = match &eq_bound.lhs_ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.get_ident().cloned(),
+        _ => None,
+    };
+    let Some(param) = param else {
+        return err(
+            eq_bound.lhs_ty.span(),
+            "only generic parameters can be constrained to be equal to a type",
+        );
+    };
+    let span = eq_bound.span();
+    let ty = eq_bound.rhs_ty;
+    let bound: syn::WherePredicate = syn::parse_quote_spanned! {span=>
+        #param: ::prusti_contracts::SameType<#ty>
+    };
+    let syn::WherePredicate::Type(bound) = bound else {
+        unreachable!()
+    };
+    Ok((param, ty, bound))
 }
 
 fn validate_predicate(predicate: syn::WherePredicate) -> syn::Result<syn::PredicateType> {
@@ -651,6 +1104,31 @@ enum PrustiToken {
     Quantifier(Span, Quantifier),
     SpecEnt(Span, bool),
     CallDesc(Span, bool),
+    /// The `is` in `expr is pattern`
+    Is(Span),
+}
+
+/// Translates `scrutinee is pattern ==> rhs` (if `implication` is true) or
+/// `scrutinee is pattern && rhs` into a match, in which `rhs` can use the
+/// variables bound by the pattern.
+fn translate_is_match(
+    span: Span,
+    scrutinee: TokenStream,
+    pattern: TokenStream,
+    rhs: TokenStream,
+    implication: bool,
+) -> TokenStream {
+    let otherwise = if implication {
+        quote_spanned! { span => true }
+    } else {
+        quote_spanned! { span => false }
+    };
+    quote_spanned! { span =>
+        match (#scrutinee) {
+            #pattern => (#rhs),
+            _ => #otherwise,
+        }
+    }
 }
 
 fn translate_spec_ent(
@@ -767,6 +1245,46 @@ impl Quantifier {
     }
 }
 
+/// A universal quantifier over the elements of a collection, such as
+/// `forall(|x: &T| v.contains(x) ==> body)`. It is translated to a quantifier
+/// over the indices of the collection, so that it is linked to the sequence
+/// model of slices and can be triggered on indexing:
+/// `forall(|i: usize| i < v.len() ==> { let x: &T = &v[i]; body },
+/// triggers = [(v[i],)])`.
+struct ContainmentQuantifier {
+    elem: syn::Ident,
+    elem_ty: syn::Type,
+    collection: PrustiTokenStream,
+    body: PrustiTokenStream,
+}
+
+impl ContainmentQuantifier {
+    fn translate(self, span: Span) -> syn::Result<TokenStream> {
+        let Self {
+            elem,
+            elem_ty,
+            collection,
+            body,
+        } = self;
+        let collection = collection.parse()?;
+        let body = body.parse()?;
+        let index = proc_macro2::Ident::new("__prusti_index", span);
+        let args = quote_spanned! { span => #index: usize };
+        let premise = quote_spanned! { collection.span() => #index < (#collection).len() };
+        let body = quote_spanned! { body.span() => {
+            let #elem: #elem_ty = &(#collection)[#index];
+            #body
+        } };
+        let trigger = quote_spanned! { collection.span() => (#collection)[#index] };
+        Ok(Quantifier::Forall.translate(
+            span,
+            vec![vec![trigger]],
+            args,
+            PrustiBinaryOp::Implies.translate(span, premise, body),
+        ))
+    }
+}
+
 // For Prusti-specific operators, in [operator2], [operator3], and [operator4]
 // we mainly care about the spacing of the last [Punct], as this lets us
 // know that the last character is not itself part of an actual Rust
@@ -804,7 +1322,8 @@ impl PrustiToken {
             | Self::Outer(span)
             | Self::Quantifier(span, _)
             | Self::SpecEnt(span, _)
-            | Self::CallDesc(span, _) => *span,
+            | Self::CallDesc(span, _)
+            | Self::Is(span) => *span,
             Self::Token(tree) => tree.span(),
         }
     }
@@ -938,6 +1457,21 @@ impl PrustiBinaryOp {
         }
     }
 
+    /// The Prusti operator as written in specifications, for use in error
+    /// messages.
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Rust(_) => unreachable!("not a Prusti operator"),
+            Self::Iff => "<==>",
+            Self::Implies => "==>",
+            Self::ImpliesReverse => "<==",
+            Self::Or => "||",
+            Self::And => "&&",
+            Self::SnapEq => "===",
+            Self::SnapNe => "!==",
+        }
+    }
+
     fn translate(&self, span: Span, raw_lhs: TokenStream, raw_rhs: TokenStream) -> TokenStream {
         // TODO: enforce types more strictly with type ascriptions
         let lhs = quote_spanned! { raw_lhs.span() => (#raw_lhs) };
@@ -1032,11 +1566,12 @@ fn join_spans(s1: Span, s2: Span) -> Span {
         // During tests we don't care so much about returning a default
         s1.join(s2).unwrap_or(s1)
     } else {
-        // This works even when compiled with stable, unlike `s1.join(s2)`
+        // This works even when compiled with stable, unlike `s1.join(s2)`.
+        // Spans from different macro expansions, e.g. of an operand passed to
+        // a `macro_rules!` macro, cannot be joined.
         s1.unwrap()
             .join(s2.unwrap())
-            .expect("Failed to join spans!")
-            .into()
+            .map_or_else(Span::call_site, Into::into)
     }
 }
 
@@ -1099,12 +1634,126 @@ mod tests {
             parse_prusti("forall(|x: i32| a ==> b, triggers = [(c,), (d, e)])".parse().unwrap()).unwrap().to_string(),
             ":: prusti_contracts :: forall (((# [prusti :: spec_only] | x : i32 | (c) ,) , (# [prusti :: spec_only] | x : i32 | (d) , # [prusti :: spec_only] | x : i32 | (e) ,) ,) , # [prusti :: spec_only] | x : i32 | -> bool { ((! (a) || (b)) : bool) })",
         );
+        assert_eq!(
+            parse_prusti("forall(|x: &i32| v.contains(x) ==> *x > 0)".parse().unwrap()).unwrap().to_string(),
+            ":: prusti_contracts :: forall (((# [prusti :: spec_only] | __prusti_index : usize | ((v) [__prusti_index]) ,) ,) , # [prusti :: spec_only] | __prusti_index : usize | -> bool { ((! (__prusti_index < (v) . len ()) || ({ let x : & i32 = & (v) [__prusti_index] ; * x > 0 })) : bool) })",
+        );
+        assert_eq!(
+            parse_prusti("forall(|x: &i32| v.contains(x) ==> a <==> b)".parse().unwrap()).unwrap().to_string(),
+            ":: prusti_contracts :: forall (() , # [prusti :: spec_only] | x : & i32 | -> bool { (((! (v . contains (x)) || (a)) == (b)) : bool) })",
+        );
         assert_eq!(
             parse_prusti("assert!(a === b ==> b)".parse().unwrap())
                 .unwrap()
                 .to_string(),
             "assert ! (! (snapshot_equality (& (a) , & (b))) || (b))",
         );
+        assert_eq!(
+            parse_prusti("*self == S { x: 5, ..old(*self) }".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "* self == S { x : 5 , .. old (:: prusti_contracts :: snap (& (* self))) }",
+        );
+        assert_eq!(
+            parse_prusti("a ==> s === S { ..old(*t) }".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "! (a) || (snapshot_equality (& (s) , & (S { .. old (:: prusti_contracts :: snap (& (* t))) })))",
+        );
+    }
+
+    #[test]
+    fn test_is_pattern() {
+        assert_eq!(
+            parse_prusti("result is Ok(v) ==> v > 0".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "match (result) { Ok (v) => (v > 0) , _ => true , }",
+        );
+        assert_eq!(
+            parse_prusti("x is Some(v) && v > 0 && v < 10 ==> b".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "! (match (x) { Some (v) => ((v > 0) && (v < 10)) , _ => false , }) || (b)",
+        );
+        assert_eq!(
+            parse_prusti("a && self.x is 1..=5 || self.is".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "((a) && (match (self . x) { 1 ..= 5 => true , _ => false })) || (self . is)",
+        );
+        assert_error!(
+            parse_prusti("x is ==> y".parse().unwrap()),
+            "expected pattern after `is`"
+        );
+        assert_error!(
+            parse_prusti("is Some(x)".parse().unwrap()),
+            "expected expression before `is`"
+        );
+    }
+
+    #[test]
+    fn test_error_notes() {
+        // The notes and help messages do not change the primary message.
+        assert_error!(
+            parse_prusti("==> a".parse().unwrap()),
+            "unexpected binary operator"
+        );
+        assert_error!(
+            parse_prusti("a && b ==>".parse().unwrap()),
+            "expected expression"
+        );
+        let error = parse_prusti("a && b ==>".parse().unwrap()).unwrap_err();
+        assert_eq!(error.into_iter().count(), 3);
+        let error = parse_prusti("forall(|x| x > 0)".parse().unwrap()).unwrap_err();
+        assert_eq!(error.into_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_reject_prev_iter() {
+        assert!(reject_prev_iter(&"x > old(x)".parse().unwrap()).is_ok());
+        assert!(reject_prev_iter(&"x.prev_iter() > 0".parse().unwrap()).is_ok());
+        assert_error!(
+            reject_prev_iter(&"x >= prev_iter(x)".parse().unwrap()),
+            "`prev_iter(..)` can only be used in loop invariants"
+        );
+        assert_error!(
+            reject_prev_iter(
+                &"forall(|i: usize| a[i] == prusti_contracts::prev_iter(a[i]))"
+                    .parse()
+                    .unwrap()
+            ),
+            "`prev_iter(..)` can only be used in loop invariants"
+        );
+    }
+
+    #[test]
+    fn test_call_summaries() {
+        assert_eq!(
+            translate_call_summaries("result == call_summary!(helper(x)) + 1".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "result == :: prusti_contracts :: call_summary (# [prusti :: spec_only] # [prusti :: call_summary] || helper (x)) + 1",
+        );
+        assert_eq!(
+            translate_call_summaries("call_summary != (x)".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "call_summary != (x)",
+        );
+        assert_error!(
+            translate_call_summaries("call_summary!(x + 1) > 0".parse().unwrap()),
+            "expected a call in `call_summary!(..)`, e.g. `call_summary!(f(x))`"
+        );
+        assert_error!(
+            translate_call_summaries("call_summary!(f(call_summary!(g(x)))) > 0".parse().unwrap()),
+            "`call_summary!(..)` cannot be nested"
+        );
+        assert!(reject_call_summaries(&"x > call_summary(x)".parse().unwrap()).is_ok());
+        assert_error!(
+            reject_call_summaries(&"(call_summary!(f(x)) > 0)".parse().unwrap()),
+            "`call_summary!(..)` can only be used in postconditions"
+        );
     }
 
     mod type_cond_specs {
@@ -1201,6 +1850,26 @@ mod tests {
             assert!(parse_type_cond_spec(quote! { where T: Fn<(i32, bool,)>, []}).is_ok());
         }
 
+        #[test]
+        fn type_equality() {
+            let constraint =
+                parse_type_cond_spec(quote! { where T = u32, U: A, [ensures(result > 0)]}).unwrap();
+            assert_bounds_eq(
+                &constraint.trait_bounds,
+                &[
+                    quote! { T : ::prusti_contracts::SameType<u32> },
+                    quote! { U : A },
+                ],
+            );
+            assert_eq!(constraint.type_equalities.len(), 1);
+            assert_eq!(constraint.type_equalities[0].0.to_string(), "T");
+            assert_eq!(constraint.type_equalities[0].1, syn::parse_quote! { u32 });
+            assert_error!(
+                parse_type_cond_spec(quote! { where Vec<T> = u32, [] }),
+                "only generic parameters can be constrained to be equal to a type"
+            );
+        }
+
         fn assert_bounds_eq(parsed: &[syn::PredicateType], quotes: &[TokenStream]) {
             assert_eq!(parsed.len(), quotes.len());
             for (parsed, quote) in parsed.iter().zip(quotes.iter()) {
@@ -1211,4 +1880,52 @@ mod tests {
             }
         }
     }
+
+    mod field_spec_entailments {
+        use super::*;
+
+        #[test]
+        fn callable_field() {
+            let entailment = parse_field_spec_entailment(
+                "self.f |= |x: i32, y| [requires(x > 0), ensures(result ==> y)]"
+                    .parse()
+                    .unwrap(),
+            )
+            .unwrap()
+            .unwrap();
+            assert_eq!(entailment.field.to_string(), "f");
+            assert_eq!(entailment.args.len(), 2);
+            assert_eq!(entailment.args[0].0.to_string(), "x");
+            assert_eq!(entailment.args[0].1, Some(syn::parse_quote! { i32 }));
+            assert_eq!(entailment.args[1].0.to_string(), "y");
+            assert!(entailment.args[1].1.is_none());
+            match &entailment.specs[1] {
+                NestedSpec::Ensures(ts) => assert_eq!(ts.to_string(), "! (result) || (y)"),
+                _ => panic!(),
+            }
+            assert_eq!(entailment.specs.len(), 2);
+        }
+
+        #[test]
+        fn other_invariants() {
+            assert!(parse_field_spec_entailment(quote! { self.x > 0 })
+                .unwrap()
+                .is_none());
+            assert!(parse_field_spec_entailment(quote! { f |= || [requires(true)] })
+                .unwrap()
+                .is_none());
+        }
+
+        #[test]
+        fn invalid_entailments() {
+            assert_error!(
+                parse_field_spec_entailment(quote! { self.f |=! || [requires(true)] }),
+                "single-call specification entailments are not supported for fields"
+            );
+            assert_error!(
+                parse_field_spec_entailment(quote! { self.f |= || [requires(true)] && true }),
+                "unexpected extra tokens"
+            );
+        }
+    }
 }
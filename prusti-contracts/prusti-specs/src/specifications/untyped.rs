@@ -1,4 +1,4 @@
-use crate::common::HasSignature;
+use crate::{common::HasSignature, diagnostics::SpecErrorExt};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Signature;
@@ -14,7 +14,6 @@ pub enum AnyFnItem {
     Fn(syn::ItemFn),
     TraitMethod(syn::TraitItemMethod),
     ImplMethod(syn::ImplItemMethod),
-    ForeignFn(syn::ForeignItemFn),
 }
 
 impl syn::parse::Parse for AnyFnItem {
@@ -30,6 +29,18 @@ impl syn::parse::Parse for AnyFnItem {
             }
             Err(_) => {
                 // It is not a valid ItemFn.
+                // A declaration without a body but with a visibility can only
+                // be a function of an `extern` block. Without a visibility,
+                // it cannot be told apart from a trait method.
+                if let Ok(foreign_fn) = input.fork().parse::<syn::ForeignItemFn>() {
+                    if !matches!(foreign_fn.vis, syn::Visibility::Inherited) {
+                        return Err(syn::Error::new(
+                            foreign_fn.sig.ident.span(),
+                            "specifications of functions declared in `extern` blocks have to be given in an `#[extern_spec]` block",
+                        )
+                        .help("declare the function again with its specification in `#[extern_spec(crate)] extern \"C\" { ... }`"));
+                    }
+                }
                 let item_method = input.parse()?;
                 Ok(AnyFnItem::TraitMethod(item_method))
             }
@@ -43,7 +54,6 @@ impl AnyFnItem {
             AnyFnItem::Fn(item) => &mut item.attrs,
             AnyFnItem::TraitMethod(item) => &mut item.attrs,
             AnyFnItem::ImplMethod(item) => &mut item.attrs,
-            AnyFnItem::ForeignFn(item) => &mut item.attrs,
         }
     }
 
@@ -52,7 +62,6 @@ impl AnyFnItem {
             AnyFnItem::Fn(item) => Some(&item.block),
             AnyFnItem::ImplMethod(item) => Some(&item.block),
             AnyFnItem::TraitMethod(item) => item.default.as_ref(),
-            AnyFnItem::ForeignFn(_) => None,
         }
     }
 
@@ -61,7 +70,6 @@ impl AnyFnItem {
             AnyFnItem::Fn(item) => Some(&item.vis),
             AnyFnItem::ImplMethod(item) => Some(&item.vis),
             AnyFnItem::TraitMethod(_) => None,
-            AnyFnItem::ForeignFn(item) => Some(&item.vis),
         }
     }
 
@@ -71,13 +79,6 @@ impl AnyFnItem {
             _ => unreachable!(),
         }
     }
-
-    pub fn expect_foreign_item_fn(self) -> syn::ForeignItemFn {
-        match self {
-            AnyFnItem::ForeignFn(f) => f,
-            _ => unreachable!(),
-        }
-    }
 }
 
 impl HasSignature for AnyFnItem {
@@ -86,7 +87,6 @@ impl HasSignature for AnyFnItem {
             Self::Fn(item) => item.sig(),
             Self::ImplMethod(item) => item.sig(),
             Self::TraitMethod(item) => item.sig(),
-            Self::ForeignFn(item) => item.sig(),
         }
     }
 
@@ -95,7 +95,6 @@ impl HasSignature for AnyFnItem {
             Self::Fn(item) => item.sig_mut(),
             Self::ImplMethod(item) => item.sig_mut(),
             Self::TraitMethod(item) => item.sig_mut(),
-            Self::ForeignFn(item) => item.sig_mut(),
         }
     }
 }
@@ -106,7 +105,6 @@ impl ToTokens for AnyFnItem {
             AnyFnItem::Fn(item) => item.to_tokens(tokens),
             AnyFnItem::TraitMethod(item) => item.to_tokens(tokens),
             AnyFnItem::ImplMethod(item) => item.to_tokens(tokens),
-            AnyFnItem::ForeignFn(item) => item.to_tokens(tokens),
         }
     }
 }
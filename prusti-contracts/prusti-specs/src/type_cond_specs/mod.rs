@@ -3,7 +3,7 @@ use crate::{
     parse_type_cond_spec, untyped, GeneratedResult, NestedSpec,
 };
 use proc_macro2::TokenStream;
-use syn::{parse_quote_spanned, spanned::Spanned};
+use syn::{parse_quote_spanned, spanned::Spanned, visit_mut::VisitMut};
 
 pub fn generate(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
     let tokens_span = attr.span();
@@ -33,6 +33,15 @@ pub fn generate(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult
                 item_fn.sig.generics.where_clause.as_ref(),
             ));
 
+            // Arguments whose type is fixed by an equality constraint get the
+            // concrete type, so that the spec can use its operations
+            let mut substitutor = TypeSubstitutor {
+                equalities: &type_cond_spec.type_equalities,
+            };
+            for input in item_fn.sig.inputs.iter_mut() {
+                substitutor.visit_fn_arg_mut(input);
+            }
+
             // Add attribute to mark this as a "specification with constraint" (used for processing the contract in `SpecCollector`)
             item_fn.attrs.push(parse_quote_spanned! {tokens_span=>
                 #[prusti::type_cond_spec_trait_bounds_in_where_clause]
@@ -63,3 +72,24 @@ fn generate_where_clause_for_spec(
         }));
     where_clause
 }
+
+/// Replaces generic parameters by the types they are constrained to be equal to.
+struct TypeSubstitutor<'a> {
+    equalities: &'a [(syn::Ident, syn::Type)],
+}
+
+impl VisitMut for TypeSubstitutor<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+            if let Some((_, concrete_ty)) = self
+                .equalities
+                .iter()
+                .find(|(param, _)| path.is_ident(param))
+            {
+                *ty = concrete_ty.clone();
+                return;
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+}
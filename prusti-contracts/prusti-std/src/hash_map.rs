@@ -0,0 +1,90 @@
+use prusti_contracts::*;
+
+// A `HashMap` is modelled by its number of entries and by which keys it
+// contains; the values stored for the keys are not yet modelled.
+//
+// Keys are looked up through their borrowed forms, e.g. a `&str` for `String`
+// keys. By the contract of `Borrow`, the borrowed forms of two keys are equal
+// exactly when the keys are equal, so looking up `k` finds the contained key
+// whose borrowed form is `k`, if any.
+
+/// Whether `map` contains the key `key`. The lookups of keys through their
+/// borrowed forms are specified in terms of this function.
+#[pure]
+#[trusted]
+#[allow(unused_variables)]
+// Without the `prusti` feature, the specifications using it are removed.
+#[cfg_attr(not(feature = "prusti"), allow(dead_code))]
+pub fn contains<K, V, S>(map: &::std::collections::hash_map::HashMap<K, V, S>, key: &K) -> bool {
+    unimplemented!()
+}
+
+#[extern_spec]
+impl<K, V> ::std::collections::hash_map::HashMap<K, V, ::std::collections::hash_map::RandomState> {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::collections::hash_map::HashMap<K, V>;
+
+    #[ensures(result.len() == 0)]
+    pub fn with_capacity(capacity: usize) -> ::std::collections::hash_map::HashMap<K, V>;
+}
+
+#[extern_spec]
+impl<K, V, S> ::std::collections::hash_map::HashMap<K, V, S> {
+    #[pure]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[ensures(self.len() == 0)]
+    pub fn clear(&mut self);
+}
+
+#[extern_spec]
+impl<K, V, S> ::std::collections::hash_map::HashMap<K, V, S>
+where
+    K: Eq + ::core::hash::Hash,
+    S: ::std::hash::BuildHasher,
+{
+    #[pure]
+    #[ensures(self.len() == 0 ==> !result)]
+    #[ensures(forall(
+        |key: K| <K as ::core::borrow::Borrow<Q>>::borrow(&key) === k ==> result == contains(self, &key),
+        triggers=[(contains(self, &key),)]
+    ))]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        K: ::core::borrow::Borrow<Q>,
+        Q: ?Sized + ::core::hash::Hash + Eq;
+
+    #[ensures(matches!(result, Some(_)) == self.contains_key(k))]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        K: ::core::borrow::Borrow<Q>,
+        Q: ?Sized + ::core::hash::Hash + Eq;
+
+    #[ensures(contains(self, &k))]
+    #[ensures(matches!(result, Some(_)) == old(contains(self, &k)))]
+    #[ensures(self.len() == if old(contains(self, &k)) { old(self.len()) } else { old(self.len()) + 1 })]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V>;
+
+    #[ensures(!self.contains_key(k))]
+    #[ensures(matches!(result, Some(_)) == old(self.contains_key(k)))]
+    #[ensures(self.len() == if old(self.contains_key(k)) { old(self.len()) - 1 } else { old(self.len()) })]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: ::core::borrow::Borrow<Q>,
+        Q: ?Sized + ::core::hash::Hash + Eq;
+}
+
+#[extern_spec]
+impl<K, Q: ?Sized, V, S> ::core::ops::Index<&Q> for ::std::collections::hash_map::HashMap<K, V, S>
+where
+    K: Eq + ::core::hash::Hash + ::core::borrow::Borrow<Q>,
+    Q: Eq + ::core::hash::Hash,
+    S: ::std::hash::BuildHasher,
+{
+    #[requires(self.contains_key(key))]
+    fn index(&self, key: &Q) -> &V;
+}
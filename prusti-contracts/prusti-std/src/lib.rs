@@ -1,14 +1,11 @@
-use prusti_contracts::*;
+//! Specifications of the most commonly used items of `std`, shipped with
+//! Prusti. The crate is loaded automatically by Prusti when it is available
+//! (see the `LOAD_STD_SPECS` flag), so that its specifications can be used
+//! without `extern crate prusti_std;`.
 
-#[extern_spec]
-impl<K, V, S> ::std::collections::hash_map::HashMap<K, V, S>
-where
-    K: Eq + ::core::hash::Hash,
-    S: ::std::hash::BuildHasher,
-{
-    #[pure]
-    pub fn contains_key<Q>(&self, k: &Q) -> bool
-    where
-        K: ::core::borrow::Borrow<Q>,
-        Q: ::core::hash::Hash + Eq;
-}
+#![cfg_attr(feature = "prusti", feature(allocator_api))]
+
+mod hash_map;
+mod string;
+mod vec;
+mod vec_deque;
@@ -0,0 +1,58 @@
+use prusti_contracts::*;
+
+// A string is modelled as the UTF-8 bytes of its string slice, so `s.len()`
+// is the length of `s.as_str()` and `s.as_str().as_bytes()[i]` its bytes.
+#[extern_spec]
+impl ::std::string::String {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::string::String;
+
+    #[pure]
+    pub fn as_str(&self) -> &str;
+
+    #[pure]
+    #[ensures(result == self.as_str().len())]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[ensures(self.len() == old(self.len()) + string.len())]
+    #[ensures(forall(|i: usize| i < old(self.len()) ==>
+        self.as_str().as_bytes()[i] == old(self.as_str().as_bytes()[i])))]
+    #[ensures(forall(|i: usize| i < string.len() ==>
+        self.as_str().as_bytes()[old(self.len()) + i] == string.as_bytes()[i]))]
+    pub fn push_str(&mut self, string: &str);
+}
+
+#[extern_spec]
+impl ::core::convert::From<&str> for ::std::string::String {
+    #[ensures(result.as_str() === s)]
+    fn from(s: &str) -> ::std::string::String;
+}
+
+// `a + b` appends the bytes of `b` to the ones of `a`, like `push_str`.
+#[extern_spec]
+impl ::core::ops::Add<&str> for ::std::string::String {
+    #[ensures(result.len() == self.len() + other.len())]
+    #[ensures(forall(|i: usize| i < self.len() ==>
+        result.as_str().as_bytes()[i] == self.as_str().as_bytes()[i]))]
+    #[ensures(forall(|i: usize| i < other.len() ==>
+        result.as_str().as_bytes()[self.len() + i] == other.as_bytes()[i]))]
+    fn add(self, other: &str) -> ::std::string::String;
+}
+
+#[extern_spec]
+impl ::core::borrow::Borrow<str> for ::std::string::String {
+    #[pure]
+    #[ensures(result === self.as_str())]
+    fn borrow(&self) -> &str;
+}
+
+#[extern_spec]
+impl ::core::ops::Deref for ::std::string::String {
+    #[pure]
+    #[ensures(result === self.as_str())]
+    fn deref(&self) -> &str;
+}
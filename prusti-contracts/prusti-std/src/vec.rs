@@ -0,0 +1,64 @@
+use prusti_contracts::*;
+
+#[extern_spec]
+impl<T> ::std::vec::Vec<T> {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::vec::Vec<T>;
+
+    #[ensures(result.len() == 0)]
+    pub fn with_capacity(capacity: usize) -> ::std::vec::Vec<T>;
+}
+
+// Indexing and slicing a vector in a specification, as in `v[i]` or
+// `v[i..j]`, is encoded as indexing and slicing the result of `as_slice`.
+// The elements of a vector are thus modelled by this slice.
+#[extern_spec]
+impl<T, A: ::std::alloc::Allocator> ::std::vec::Vec<T, A> {
+    #[pure]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[pure]
+    #[ensures(result.len() == self.len())]
+    pub fn as_slice(&self) -> &[T];
+
+    #[ensures(self.len() == old(self.len()) + 1)]
+    #[ensures(forall(|i: usize| i < old(self.len()) ==> self[i] === old(self[i])))]
+    #[ensures(self[old(self.len())] === value)]
+    pub fn push(&mut self, value: T);
+
+    #[ensures(forall(|i: usize| i < self.len() ==> self[i] === old(self[i])))]
+    #[ensures(match result {
+        Some(value) => old(self.len()) > 0
+            && self.len() == old(self.len()) - 1
+            && value === old(self[self.len() - 1]),
+        None => old(self.len()) == 0 && self.len() == 0,
+    })]
+    pub fn pop(&mut self) -> Option<T>;
+
+    #[ensures(self.len() == 0)]
+    pub fn clear(&mut self);
+
+    #[ensures(self.len() == if len < old(self.len()) { len } else { old(self.len()) })]
+    #[ensures(forall(|i: usize| i < self.len() ==> self[i] === old(self[i])))]
+    pub fn truncate(&mut self, len: usize);
+}
+
+#[extern_spec]
+impl<T, A: ::std::alloc::Allocator> ::core::borrow::Borrow<[T]> for ::std::vec::Vec<T, A> {
+    #[pure]
+    #[ensures(result === self.as_slice())]
+    fn borrow(&self) -> &[T];
+}
+
+// A deref coercion, as when a vector is passed where a slice is expected or
+// a slice method is called on a vector, goes through `deref`.
+#[extern_spec]
+impl<T, A: ::std::alloc::Allocator> ::core::ops::Deref for ::std::vec::Vec<T, A> {
+    #[pure]
+    #[ensures(result === self.as_slice())]
+    fn deref(&self) -> &[T];
+}
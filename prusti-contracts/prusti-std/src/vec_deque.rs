@@ -0,0 +1,45 @@
+use prusti_contracts::*;
+
+// Only the number of elements of a `VecDeque` is modelled; its elements are
+// not yet available in specifications.
+#[extern_spec]
+impl<T> ::std::collections::VecDeque<T> {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::collections::VecDeque<T>;
+
+    #[ensures(result.len() == 0)]
+    pub fn with_capacity(capacity: usize) -> ::std::collections::VecDeque<T>;
+}
+
+#[extern_spec]
+impl<T, A: ::std::alloc::Allocator> ::std::collections::VecDeque<T, A> {
+    #[pure]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[ensures(self.len() == old(self.len()) + 1)]
+    pub fn push_back(&mut self, value: T);
+
+    #[ensures(self.len() == old(self.len()) + 1)]
+    pub fn push_front(&mut self, value: T);
+
+    #[ensures(old(self.len()) == 0 ==> self.len() == 0 && matches!(result, None))]
+    #[ensures(old(self.len()) > 0 ==> self.len() == old(self.len()) - 1 && matches!(result, Some(_)))]
+    pub fn pop_back(&mut self) -> Option<T>;
+
+    #[ensures(old(self.len()) == 0 ==> self.len() == 0 && matches!(result, None))]
+    #[ensures(old(self.len()) > 0 ==> self.len() == old(self.len()) - 1 && matches!(result, Some(_)))]
+    pub fn pop_front(&mut self) -> Option<T>;
+
+    #[ensures(matches!(result, Some(_)) == (self.len() > 0))]
+    pub fn front(&self) -> Option<&T>;
+
+    #[ensures(matches!(result, Some(_)) == (self.len() > 0))]
+    pub fn back(&self) -> Option<&T>;
+
+    #[ensures(self.len() == 0)]
+    pub fn clear(&mut self);
+}
@@ -1,12 +1,19 @@
 use prusti_rustc_interface::{
-    errors::{DiagnosticBuilder, EmissionGuarantee, MultiSpan},
+    data_structures::fx::FxHashSet,
+    errors::{Applicability, DiagnosticBuilder, EmissionGuarantee, MultiSpan},
     middle::ty::TyCtxt,
+    span::Span,
 };
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 pub struct EnvDiagnostic<'tcx> {
     tcx: TyCtxt<'tcx>,
     warn_buffer: RefCell<Vec<prusti_rustc_interface::errors::Diagnostic>>,
+    /// The messages and spans of the emitted warnings. The same procedure can
+    /// be encoded several times, but its warnings are emitted only once.
+    emitted_warnings: RefCell<FxHashSet<(String, MultiSpan)>>,
+    /// Whether errors and warnings are currently dropped instead of emitted.
+    suppressed: Cell<bool>,
 }
 
 impl<'tcx> EnvDiagnostic<'tcx> {
@@ -14,14 +21,26 @@ impl<'tcx> EnvDiagnostic<'tcx> {
         EnvDiagnostic {
             tcx,
             warn_buffer: RefCell::new(Vec::new()),
+            emitted_warnings: RefCell::new(FxHashSet::default()),
+            suppressed: Cell::new(false),
         }
     }
 
+    /// Runs `f` without emitting any error or warning, e.g. to encode and
+    /// verify modified contracts whose errors are not meant for the user.
+    pub fn with_suppressed_diagnostics<R>(&self, f: impl FnOnce() -> R) -> R {
+        let was_suppressed = self.suppressed.replace(true);
+        let result = f();
+        self.suppressed.set(was_suppressed);
+        result
+    }
+
     fn configure_diagnostic<S: Into<MultiSpan> + Clone, T: EmissionGuarantee>(
         diagnostic: &mut DiagnosticBuilder<T>,
         sp: S,
         help: &Option<String>,
         notes: &[(String, Option<S>)],
+        suggestions: &[(String, Span, String, Applicability)],
     ) {
         diagnostic.set_span(sp);
         if let Some(help_msg) = help {
@@ -34,6 +53,9 @@ impl<'tcx> EnvDiagnostic<'tcx> {
                 diagnostic.note(note_msg);
             }
         }
+        for (suggestion_msg, suggestion_sp, replacement, applicability) in suggestions {
+            diagnostic.span_suggestion(*suggestion_sp, suggestion_msg, replacement, *applicability);
+        }
     }
 
     /// Emits an error message.
@@ -43,9 +65,13 @@ impl<'tcx> EnvDiagnostic<'tcx> {
         msg: &str,
         help: &Option<String>,
         notes: &[(String, Option<S>)],
+        suggestions: &[(String, Span, String, Applicability)],
     ) {
+        if self.suppressed.get() {
+            return;
+        }
         let mut diagnostic = self.tcx.sess.struct_err(msg);
-        Self::configure_diagnostic(&mut diagnostic, sp, help, notes);
+        Self::configure_diagnostic(&mut diagnostic, sp, help, notes, suggestions);
         for warn in self.warn_buffer.borrow_mut().iter_mut() {
             self.tcx.sess.diagnostic().emit_diagnostic(warn);
         }
@@ -59,9 +85,18 @@ impl<'tcx> EnvDiagnostic<'tcx> {
         msg: &str,
         help: &Option<String>,
         notes: &[(String, Option<S>)],
+        suggestions: &[(String, Span, String, Applicability)],
     ) {
+        if self.suppressed.get()
+            || !self
+                .emitted_warnings
+                .borrow_mut()
+                .insert((msg.to_string(), sp.clone().into()))
+        {
+            return;
+        }
         let mut diagnostic = self.tcx.sess.struct_warn(msg);
-        Self::configure_diagnostic(&mut diagnostic, sp, help, notes);
+        Self::configure_diagnostic(&mut diagnostic, sp, help, notes, suggestions);
         diagnostic.emit();
     }
 
@@ -72,9 +107,13 @@ impl<'tcx> EnvDiagnostic<'tcx> {
         msg: &str,
         help: &Option<String>,
         notes: &[(String, Option<S>)],
+        suggestions: &[(String, Span, String, Applicability)],
     ) {
+        if self.suppressed.get() {
+            return;
+        }
         let mut diagnostic = self.tcx.sess.struct_warn(msg);
-        Self::configure_diagnostic(&mut diagnostic, sp, help, notes);
+        Self::configure_diagnostic(&mut diagnostic, sp, help, notes, suggestions);
         diagnostic.buffer(&mut self.warn_buffer.borrow_mut());
     }
 
@@ -423,6 +423,11 @@ impl ProcedureLoops {
         !self.nonconditional_loop_blocks[&loop_head].contains(&bbi)
     }
 
+    /// Check whether `dominator` dominates `bbi`.
+    pub fn dominates(&self, dominator: BasicBlockIndex, bbi: BasicBlockIndex) -> bool {
+        self.dominators.dominates(dominator, bbi)
+    }
+
     pub fn get_enclosing_loop_heads(&self, bbi: BasicBlockIndex) -> &[BasicBlockIndex] {
         if let Some(heads) = self.enclosing_loop_heads.get(&bbi) {
             heads
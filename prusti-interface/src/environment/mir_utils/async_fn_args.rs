@@ -0,0 +1,47 @@
+use super::StatementAsAssign;
+use prusti_rustc_interface::{index::vec::Idx, middle::mir};
+
+pub trait AsyncFnArgsForMir<'tcx> {
+    /// For the body of an `async fn`, returns the assignments with which the
+    /// arguments of the function are moved out of the generator at the
+    /// beginning of the body, together with the locals to which they are
+    /// moved. The assignments are ordered like the arguments.
+    fn get_async_fn_arg_bindings(&self) -> Vec<(mir::Location, mir::Local)>;
+}
+
+impl<'tcx> AsyncFnArgsForMir<'tcx> for mir::Body<'tcx> {
+    fn get_async_fn_arg_bindings(&self) -> Vec<(mir::Location, mir::Local)> {
+        let generator = mir::Local::new(1);
+        let mut bindings = vec![];
+        for (statement_index, statement) in self.basic_blocks[mir::START_BLOCK]
+            .statements
+            .iter()
+            .enumerate()
+        {
+            let Some((lhs, rhs)) = statement.as_assign() else {
+                continue;
+            };
+            let Some(local) = lhs.as_local() else {
+                continue;
+            };
+            let mir::Rvalue::Use(mir::Operand::Move(place) | mir::Operand::Copy(place)) = rhs
+            else {
+                continue;
+            };
+            let is_upvar = place.local == generator
+                && matches!(
+                    place.projection.as_ref(),
+                    [mir::ProjectionElem::Field(..)]
+                        | [mir::ProjectionElem::Deref, mir::ProjectionElem::Field(..)]
+                );
+            if is_upvar {
+                let location = mir::Location {
+                    block: mir::START_BLOCK,
+                    statement_index,
+                };
+                bindings.push((location, local));
+            }
+        }
+        bindings
+    }
+}
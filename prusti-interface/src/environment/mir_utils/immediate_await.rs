@@ -0,0 +1,141 @@
+use super::StatementAsAssign;
+use prusti_rustc_interface::{
+    data_structures::fx::FxHashSet,
+    index::vec::Idx,
+    middle::{mir, ty::TyCtxt},
+    span::def_id::DefId,
+};
+
+/// A call whose result is a future that is awaited immediately, as in
+/// `f(x).await`.
+#[derive(Clone, Copy, Debug)]
+pub struct ImmediateAwait<'tcx> {
+    /// The place to which the output of the future is moved once the future
+    /// completes.
+    pub output: mir::Place<'tcx>,
+    /// The statement that moves the output out of `Poll::Ready`. The statements
+    /// before it in the same block only manage the storage of locals.
+    pub output_binding: mir::Location,
+}
+
+pub trait ImmediateAwaitsForMir<'tcx> {
+    /// If the call terminating `call_block` creates a future that is awaited
+    /// immediately, returns where the output of the future is stored.
+    ///
+    /// An `.await` is desugared to a call of `IntoFuture::into_future`
+    /// followed by a loop that polls the future and yields while it is
+    /// pending.
+    fn get_immediate_await(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        call_block: mir::BasicBlock,
+    ) -> Option<ImmediateAwait<'tcx>>;
+}
+
+fn called_function(func: &mir::Operand) -> Option<DefId> {
+    func.const_fn_def().map(|(def_id, _)| def_id)
+}
+
+fn is_storage_statement(statement: &mir::Statement) -> bool {
+    matches!(
+        statement.kind,
+        mir::StatementKind::StorageLive(_)
+            | mir::StatementKind::StorageDead(_)
+            | mir::StatementKind::Nop
+    )
+}
+
+impl<'tcx> ImmediateAwaitsForMir<'tcx> for mir::Body<'tcx> {
+    fn get_immediate_await(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        call_block: mir::BasicBlock,
+    ) -> Option<ImmediateAwait<'tcx>> {
+        let into_future_fn = tcx.lang_items().into_future_fn()?;
+        let future_poll_fn = tcx.lang_items().future_poll_fn()?;
+
+        let (future, target) = match self[call_block].terminator().kind {
+            mir::TerminatorKind::Call {
+                destination,
+                target: Some(target),
+                ..
+            } => (destination.as_local()?, target),
+            _ => return None,
+        };
+
+        // The future is converted by `into_future` right after the call.
+        if !self[target].statements.iter().all(is_storage_statement) {
+            return None;
+        }
+        let poll_loop_start = match &self[target].terminator().kind {
+            mir::TerminatorKind::Call {
+                func,
+                args,
+                target: Some(target),
+                ..
+            } if called_function(func) == Some(into_future_fn) => match args.as_slice() {
+                [mir::Operand::Move(place)] if place.as_local() == Some(future) => *target,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        // Find the call of `Future::poll` in the loop.
+        let mut visited = FxHashSet::default();
+        let mut block = poll_loop_start;
+        let poll_result = loop {
+            if !visited.insert(block) {
+                return None;
+            }
+            match &self[block].terminator().kind {
+                mir::TerminatorKind::Call {
+                    func, destination, ..
+                } if called_function(func) == Some(future_poll_fn) => {
+                    break destination.as_local()?;
+                }
+                mir::TerminatorKind::Goto { target }
+                | mir::TerminatorKind::Call {
+                    target: Some(target),
+                    ..
+                }
+                | mir::TerminatorKind::FalseUnwind {
+                    real_target: target,
+                    ..
+                } => block = *target,
+                _ => return None,
+            }
+        };
+
+        // Find the statement that moves the output out of `Poll::Ready`.
+        for (bb, bb_data) in self.basic_blocks.iter_enumerated() {
+            for (statement_index, statement) in bb_data.statements.iter().enumerate() {
+                let (lhs, place) = match statement.as_assign() {
+                    Some((lhs, mir::Rvalue::Use(mir::Operand::Move(place)))) => (lhs, *place),
+                    _ => continue,
+                };
+                let is_ready_output = place.local == poll_result
+                    && matches!(
+                        place.projection.as_ref(),
+                        [mir::ProjectionElem::Downcast(_, variant), mir::ProjectionElem::Field(field, _)]
+                            if variant.index() == 0 && field.index() == 0
+                    );
+                if is_ready_output {
+                    if !bb_data.statements[..statement_index]
+                        .iter()
+                        .all(is_storage_statement)
+                    {
+                        return None;
+                    }
+                    return Some(ImmediateAwait {
+                        output: lhs,
+                        output_binding: mir::Location {
+                            block: bb,
+                            statement_index,
+                        },
+                    });
+                }
+            }
+        }
+        None
+    }
+}
@@ -8,6 +8,8 @@
 
 mod all_places;
 mod args_for_mir;
+mod async_fn_args;
+mod immediate_await;
 mod mir_place;
 mod real_edges;
 mod slice_or_array_ref;
@@ -18,7 +20,7 @@ mod tuple_items_for_ty;
 mod ty_as_ty_ref;
 
 pub use self::{
-    all_places::*, args_for_mir::*, mir_place::*, real_edges::*, slice_or_array_ref::*,
-    split_aggregate_assignment::*, statement_as_assign::*, statement_at::*, tuple_items_for_ty::*,
-    ty_as_ty_ref::*,
+    all_places::*, args_for_mir::*, async_fn_args::*, immediate_await::*, mir_place::*,
+    real_edges::*, slice_or_array_ref::*, split_aggregate_assignment::*, statement_as_assign::*,
+    statement_at::*, tuple_items_for_ty::*, ty_as_ty_ref::*,
 };
@@ -42,6 +42,15 @@ impl RealEdges {
     pub fn predecessors(&self, bb: mir::BasicBlock) -> &[mir::BasicBlock] {
         &self.predecessors[bb]
     }
+
+    /// Replaces the successors of `bb` with `target`, as if the terminator of
+    /// `bb` jumped directly to `target`.
+    pub fn redirect(&mut self, bb: mir::BasicBlock, target: mir::BasicBlock) {
+        for old_target in std::mem::replace(&mut self.successors[bb], vec![target]) {
+            self.predecessors[old_target].retain(|&pred| pred != bb);
+        }
+        self.predecessors[target].push(bb);
+    }
 }
 
 fn real_targets(terminator: &mir::Terminator) -> Vec<mir::BasicBlock> {
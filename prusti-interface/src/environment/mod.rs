@@ -35,8 +35,9 @@ pub use self::{
     loops_utils::*,
     name::EnvName,
     procedure::{
-        get_loop_invariant, is_ghost_begin_marker, is_ghost_end_marker, is_loop_invariant_block,
-        is_loop_variant_block, is_marked_specification_block, BasicBlockIndex, Procedure,
+        get_loop_invariant, get_loop_unrolling_bound, is_ghost_begin_marker, is_ghost_end_marker,
+        is_loop_invariant_before_loop_block, is_loop_invariant_block, is_loop_variant_block,
+        is_marked_specification_block, BasicBlockIndex, Procedure,
     },
     query::EnvQuery,
 };
@@ -103,6 +104,23 @@ impl<'tcx> Environment<'tcx> {
         (procedures, types)
     }
 
+    /// The assertion timeout (in milliseconds) with which the procedure should
+    /// be verified instead of the configured `ASSERT_TIMEOUT`, if any. It is
+    /// given by a `#[timeout(ms)]` attribute of the procedure or else by the
+    /// most specific matching entry of `ASSERT_TIMEOUT_OVERRIDES`.
+    pub fn get_assert_timeout_override(&self, proc_def_id: ProcedureDefId) -> Option<u64> {
+        let attrs = self.query.get_attributes(proc_def_id);
+        if let Some(timeout) = crate::utils::read_prusti_attr("timeout", attrs) {
+            return Some(timeout.parse().unwrap());
+        }
+        let item_name = self.name.get_unique_item_name(proc_def_id);
+        prusti_common::config::assert_timeout_overrides()
+            .into_iter()
+            .filter(|(path, _)| item_name == *path || item_name.starts_with(&format!("{path}::")))
+            .max_by_key(|(path, _)| path.len())
+            .map(|(_, timeout)| timeout)
+    }
+
     /// Compare the current version of the `prusti` crate to the given other version
     pub fn compare_prusti_version(&self, other: &str) -> std::cmp::Ordering {
         version_compare::compare(self.prusti_version, other)
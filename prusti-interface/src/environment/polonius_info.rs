@@ -24,6 +24,11 @@ use crate::{
     },
     utils,
 };
+use analysis::{
+    domains::{compute_may_alias, MayAliasState, MaybeBorrowedState},
+    mir_utils::get_blocked_place,
+    PointwiseState,
+};
 use datafrog;
 use log::{debug, trace};
 use prusti_common::config;
@@ -614,9 +619,37 @@ fn get_borrowed_places<'a, 'tcx: 'a>(
     }
 }
 
+/// Whether `place_a`, borrowed at `location_a`, and `place_b`, borrowed at
+/// `location_b`, might overlap according to the pointees of the dereferenced
+/// pointers at the two locations. Without the result of the alias analysis,
+/// the places are assumed to overlap.
+fn borrowed_places_may_overlap<'tcx>(
+    may_alias: Option<&PointwiseState<'_, 'tcx, MayAliasState<'_, 'tcx>>>,
+    place_a: mir::Place<'tcx>,
+    location_a: mir::Location,
+    place_b: mir::Place<'tcx>,
+    location_b: mir::Location,
+) -> bool {
+    let states = may_alias.map(|may_alias| {
+        (
+            may_alias.lookup_before(location_a),
+            may_alias.lookup_before(location_b),
+        )
+    });
+    if let Some((Some(state_a), Some(state_b))) = states {
+        let pointees_a = state_a.resolve_place(place_a);
+        let pointees_b = state_b.resolve_place(place_b);
+        state_a.may_overlap(&pointees_a, &pointees_b)
+            || state_b.may_overlap(&pointees_a, &pointees_b)
+    } else {
+        true
+    }
+}
+
 #[tracing::instrument(level = "trace", skip_all, ret)]
-fn compute_loan_conflict_sets(
-    procedure: &Procedure,
+fn compute_loan_conflict_sets<'tcx>(
+    tcx: ty::TyCtxt<'tcx>,
+    procedure: &Procedure<'tcx>,
     loan_position: &FxHashMap<facts::Loan, mir::Location>,
     borrowck_in_facts: &facts::AllInputFacts,
     borrowck_out_facts: &facts::AllOutputFacts,
@@ -624,6 +657,10 @@ fn compute_loan_conflict_sets(
     let mut loan_conflict_sets = FxHashMap::default();
 
     let mir = procedure.get_mir();
+    // Syntactically overlapping places do not conflict if the pointers that
+    // they dereference point to disjoint memory when the loans are created.
+    // This way, fewer permissions need to be kept back when a loan expires.
+    let may_alias = compute_may_alias(tcx, procedure.get_id(), mir).ok();
 
     for &(_r, loan, _) in &borrowck_in_facts.loan_issued_at {
         loan_conflict_sets.insert(loan, FxHashSet::default());
@@ -642,8 +679,15 @@ fn compute_loan_conflict_sets(
                         continue;
                     }
                     for place in get_borrowed_places(mir, loan_position, *loan_alive)? {
-                        if utils::is_prefix(*borrowed_place, *place)
-                            || utils::is_prefix(*place, *borrowed_place)
+                        if (utils::is_prefix(*borrowed_place, *place)
+                            || utils::is_prefix(*place, *borrowed_place))
+                            && borrowed_places_may_overlap(
+                                may_alias.as_ref(),
+                                *borrowed_place,
+                                location,
+                                *place,
+                                loan_position[loan_alive],
+                            )
                         {
                             loan_conflict_sets
                                 .get_mut(&loan_created)
@@ -749,7 +793,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
         );
         // FIXME: Check whether the new info in Polonius could be used for computing initialization.
         let loan_conflict_sets =
-            compute_loan_conflict_sets(procedure, &loan_position, &all_facts, &output)?;
+            compute_loan_conflict_sets(tcx, procedure, &loan_position, &all_facts, &output)?;
 
         let info = Self {
             tcx,
@@ -922,6 +966,42 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
         (loans, zombie_loans)
     }
 
+    /// Computes the places that might be blocked by the loans that are active
+    /// (including the zombies) at the given location, as the `MaybeBorrowed`
+    /// analysis does for the whole body. Returns `None` if the place blocked by
+    /// some loan is not known.
+    pub fn get_maybe_borrowed_state(
+        &self,
+        location: mir::Location,
+    ) -> Option<MaybeBorrowedState<'tcx>> {
+        let mut state = MaybeBorrowedState::default();
+        let (loans, _) = self.get_all_active_loans(location);
+        for loan in loans {
+            let loan_places = self.get_loan_places(&loan).ok().flatten()?;
+            match loan_places.source {
+                mir::Rvalue::Ref(_, mir::BorrowKind::Shared, borrowed_place) => {
+                    state.insert_maybe_shared_borrowed(get_blocked_place(
+                        self.tcx,
+                        borrowed_place.into(),
+                    ));
+                }
+                mir::Rvalue::Ref(_, _, borrowed_place) => {
+                    state.insert_maybe_mut_borrowed(get_blocked_place(
+                        self.tcx,
+                        borrowed_place.into(),
+                    ));
+                }
+                // A loan created by a move of a reference blocks the moved place
+                mir::Rvalue::Use(mir::Operand::Move(moved_place))
+                | mir::Rvalue::Use(mir::Operand::Copy(moved_place)) => {
+                    state.insert_maybe_mut_borrowed(moved_place.into());
+                }
+                _ => return None,
+            }
+        }
+        Some(state)
+    }
+
     fn get_borrow_live_at(
         &self,
         zombie: bool,
@@ -7,7 +7,11 @@
 use super::{body::MirBody, loops, EnvName, EnvQuery};
 use crate::{
     data::ProcedureDefId,
-    environment::{debug_utils::to_text::ToText, mir_utils::RealEdges, Environment},
+    environment::{
+        debug_utils::to_text::ToText,
+        mir_utils::{ImmediateAwait, ImmediateAwaitsForMir, RealEdges},
+        Environment,
+    },
 };
 use log::{debug, trace};
 use prusti_rustc_interface::{
@@ -33,6 +37,8 @@ pub struct Procedure<'tcx> {
     loop_info: loops::ProcedureLoops,
     reachable_basic_blocks: FxHashSet<BasicBlock>,
     nonspec_basic_blocks: FxHashSet<BasicBlock>,
+    loop_invariant_before_loop_blocks: FxHashSet<BasicBlock>,
+    immediate_awaits: FxHashMap<BasicBlock, ImmediateAwait<'tcx>>,
 }
 
 impl<'tcx> Procedure<'tcx> {
@@ -43,10 +49,27 @@ impl<'tcx> Procedure<'tcx> {
         let mir = env
             .body
             .get_impure_fn_body_identity(proc_def_id.expect_local());
-        let real_edges = RealEdges::new(&mir);
+        let mut real_edges = RealEdges::new(&mir);
+        // A call whose future is awaited immediately is treated like a
+        // synchronous call that returns the output of the future, skipping the
+        // loop that polls the future.
+        let immediate_awaits: FxHashMap<_, _> = mir
+            .basic_blocks
+            .indices()
+            .filter_map(|bb| Some((bb, mir.get_immediate_await(env.tcx(), bb)?)))
+            .collect();
+        for (&bb, immediate_await) in &immediate_awaits {
+            real_edges.redirect(bb, immediate_await.output_binding.block);
+        }
         let reachable_basic_blocks = build_reachable_basic_blocks(&mir, &real_edges);
         let nonspec_basic_blocks = build_nonspec_basic_blocks(env.query, &mir, &real_edges);
         let loop_info = loops::ProcedureLoops::new(&mir, &real_edges);
+        let loop_invariant_before_loop_blocks = mir
+            .basic_blocks
+            .iter_enumerated()
+            .filter(|(_, bb_data)| is_loop_invariant_before_loop_block(env.query, bb_data))
+            .map(|(bb, _)| bb)
+            .collect();
 
         Self {
             tcx: env.tcx(),
@@ -57,6 +80,8 @@ impl<'tcx> Procedure<'tcx> {
             loop_info,
             reachable_basic_blocks,
             nonspec_basic_blocks,
+            loop_invariant_before_loop_blocks,
+            immediate_awaits,
         }
     }
 
@@ -189,6 +214,22 @@ impl<'tcx> Procedure<'tcx> {
         !self.nonspec_basic_blocks.contains(&bbi)
     }
 
+    /// If the call terminating the block creates a future that is awaited
+    /// immediately, returns where the output of the future is stored. The
+    /// block is then followed by the block that stores the output.
+    pub fn get_immediate_await(&self, bbi: BasicBlockIndex) -> Option<&ImmediateAwait<'tcx>> {
+        self.immediate_awaits.get(&bbi)
+    }
+
+    /// Check whether the statement moves the output of an immediately awaited
+    /// future out of `Poll::Ready`. The output is instead stored by the call
+    /// that creates the future.
+    pub fn is_immediate_await_output_binding(&self, location: mir::Location) -> bool {
+        self.immediate_awaits
+            .values()
+            .any(|immediate_await| immediate_await.output_binding == location)
+    }
+
     /// Check whether the block is reachable
     pub fn is_reachable_block(&self, bbi: BasicBlockIndex) -> bool {
         self.reachable_basic_blocks.contains(&bbi)
@@ -197,6 +238,67 @@ impl<'tcx> Procedure<'tcx> {
     pub fn successors(&self, bbi: BasicBlockIndex) -> &[BasicBlockIndex] {
         self.real_edges.successors(bbi)
     }
+
+    /// Check whether the block is an exit block of the loop that has a single
+    /// successor staying in the loop. This is the case for the condition of a
+    /// `while` loop, for the pattern of a `while let` loop and for a `match`
+    /// whose other arms `break` out of the loop (possibly with a value).
+    pub fn is_loop_guard_switch(&self, loop_head: BasicBlockIndex, bbi: BasicBlockIndex) -> bool {
+        let loop_body = &self.loop_info.loop_bodies[&loop_head];
+        self.loop_info.get_loop_exit_blocks(loop_head).contains(&bbi)
+            && self
+                .successors(bbi)
+                .iter()
+                .filter(|&succ| loop_body.contains(succ) && !self.is_spec_block(*succ))
+                .count()
+                == 1
+    }
+
+    /// Check whether the block contains a `loop_invariant!(...)`, which is
+    /// written before the loop instead of in its body
+    pub fn is_loop_invariant_before_loop_block(&self, bbi: BasicBlockIndex) -> bool {
+        self.loop_invariant_before_loop_blocks.contains(&bbi)
+    }
+
+    /// Get the head of the loop that immediately follows the block, i.e. that
+    /// is reached without branching and without leaving the enclosing loops
+    pub fn get_following_loop_head(&self, bbi: BasicBlockIndex) -> Option<BasicBlockIndex> {
+        let enclosing_loop_heads = self.loop_info.get_enclosing_loop_heads(bbi);
+        let mut visited: FxHashSet<BasicBlock> = FxHashSet::default();
+        let mut current = bbi;
+        while visited.insert(current) {
+            if self.loop_info.is_loop_head(current) {
+                return if enclosing_loop_heads.contains(&current) {
+                    None
+                } else {
+                    Some(current)
+                };
+            }
+            let successors = self.successors(current);
+            current = if self.is_spec_block(current) || successors.len() == 1 {
+                // Specification blocks rejoin the code that follows them.
+                *successors.first()?
+            } else {
+                return None;
+            };
+        }
+        None
+    }
+
+    /// Get the blocks containing the `loop_invariant!(...)` that are written
+    /// before the given loop
+    pub fn get_loop_invariant_before_loop_blocks(
+        &self,
+        loop_head: BasicBlockIndex,
+    ) -> Vec<BasicBlockIndex> {
+        self.get_reachable_cfg_blocks()
+            .into_iter()
+            .filter(|&bbi| {
+                self.is_loop_invariant_before_loop_block(bbi)
+                    && self.get_following_loop_head(bbi) == Some(loop_head)
+            })
+            .collect()
+    }
 }
 
 /// Returns the set of basic blocks that are not used as part of the typechecking of Prusti specifications
@@ -274,10 +376,42 @@ pub fn is_loop_invariant_block<'tcx>(env_query: EnvQuery, bb_data: &BasicBlockDa
     get_loop_invariant(env_query, bb_data).is_some()
 }
 
+pub fn is_loop_invariant_before_loop_block<'tcx>(
+    env_query: EnvQuery,
+    bb_data: &BasicBlockData<'tcx>,
+) -> bool {
+    is_spec_block_kind(env_query, bb_data, "loop_invariant_before_loop")
+}
+
 pub fn is_loop_variant_block<'tcx>(env_query: EnvQuery, bb: &BasicBlockData<'tcx>) -> bool {
     is_spec_block_kind(env_query, bb, "loop_body_variant_spec")
 }
 
+/// Returns the bound of the `no_invariant_needed!(bound = K)` marker if the
+/// block contains its marker.
+pub fn get_loop_unrolling_bound<'tcx>(
+    env_query: EnvQuery,
+    bb_data: &BasicBlockData<'tcx>,
+) -> Option<u32> {
+    for stmt in &bb_data.statements {
+        if let StatementKind::Assign(box (
+            _,
+            Rvalue::Aggregate(box AggregateKind::Closure(def_id, _), _),
+        )) = &stmt.kind
+        {
+            if is_spec_closure(env_query, *def_id) {
+                if let Some(bound) = crate::utils::read_prusti_attr(
+                    "loop_unrolling_bound",
+                    env_query.get_attributes(def_id),
+                ) {
+                    return bound.parse().ok();
+                }
+            }
+        }
+    }
+    None
+}
+
 pub fn is_ghost_begin_marker<'tcx>(env_query: EnvQuery, bb: &BasicBlockData<'tcx>) -> bool {
     is_spec_block_kind(env_query, bb, "ghost_begin")
 }
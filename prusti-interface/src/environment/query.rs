@@ -4,7 +4,7 @@ use crate::data::ProcedureDefId;
 use log::debug;
 use prusti_rustc_interface::{
     ast::ast::Attribute,
-    hir::hir_id::HirId,
+    hir::{def::DefKind, hir_id::HirId, AsyncGeneratorKind, GeneratorKind},
     middle::{
         hir::map::Map,
         ty::{
@@ -15,6 +15,7 @@ use prusti_rustc_interface::{
     span::{
         def_id::{DefId, LocalDefId},
         source_map::SourceMap,
+        symbol::{sym, Symbol},
         Span,
     },
     trait_selection::{
@@ -144,6 +145,16 @@ impl<'tcx> EnvQuery<'tcx> {
             .is_some()
     }
 
+    /// Returns true iff `def_id` is a method of an implementation generated by
+    /// `#[derive(...)]`.
+    pub fn is_automatically_derived_method(self, def_id: impl IntoParam<ProcedureDefId>) -> bool {
+        self.tcx
+            .impl_of_method(def_id.into_param())
+            .map_or(false, |impl_id| {
+                self.tcx.has_attr(impl_id, sym::automatically_derived)
+            })
+    }
+
     /// Returns true iff `def_id` is an unsafe function.
     pub fn is_unsafe_function(self, def_id: impl IntoParam<ProcedureDefId>) -> bool {
         self.tcx
@@ -185,6 +196,82 @@ impl<'tcx> EnvQuery<'tcx> {
         self.tcx.is_closure(def_id.into_param())
     }
 
+    /// Returns true iff `def_id` is an `async fn`.
+    pub fn is_async_fn(self, def_id: impl IntoParam<DefId>) -> bool {
+        let def_id = def_id.into_param();
+        matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            && self.tcx.asyncness(def_id).is_async()
+    }
+
+    /// Returns the `async fn` whose body is the generator `def_id`, if any.
+    pub fn get_async_fn_of_body(self, def_id: impl IntoParam<DefId>) -> Option<DefId> {
+        let def_id = def_id.into_param();
+        match self.tcx.generator_kind(def_id) {
+            Some(GeneratorKind::Async(AsyncGeneratorKind::Fn)) => Some(self.tcx.parent(def_id)),
+            _ => None,
+        }
+    }
+
+    /// Returns the function that carries the contract declared by a type
+    /// invariant for calls of the callable stored in the field `field_name`
+    /// of the struct `adt_def_id`, if any.
+    pub fn get_field_callable_contract(
+        self,
+        adt_def_id: impl IntoParam<DefId>,
+        field_name: &str,
+    ) -> Option<ProcedureDefId> {
+        self.tcx
+            .inherent_impls(adt_def_id.into_param())
+            .iter()
+            .flat_map(|&impl_def_id| self.tcx.associated_item_def_ids(impl_def_id))
+            .copied()
+            .find(|&item_def_id| {
+                crate::utils::read_prusti_attr("field_callable", self.get_attributes(item_def_id))
+                    .map_or(false, |name| name == field_name)
+            })
+    }
+
+    /// Returns the kind of the generator `def_id` if it is a generator closure
+    /// or an `async` block. The bodies of `async fn`s are not included.
+    pub fn get_generator_kind(self, def_id: impl IntoParam<DefId>) -> Option<GeneratorKind> {
+        match self.tcx.generator_kind(def_id.into_param()) {
+            Some(GeneratorKind::Async(AsyncGeneratorKind::Fn)) | None => None,
+            kind => kind,
+        }
+    }
+
+    /// Returns the function in whose body the closure or generator `def_id`
+    /// is defined. For other items, returns `def_id` itself.
+    pub fn get_enclosing_fn(self, def_id: impl IntoParam<DefId>) -> DefId {
+        self.tcx.typeck_root_def_id(def_id.into_param())
+    }
+
+    /// Returns true iff `ty` is `Vec<T, A>`.
+    pub fn is_vec(self, ty: ty::Ty<'tcx>) -> bool {
+        match ty.kind() {
+            ty::TyKind::Adt(adt_def, _) => {
+                self.tcx.get_diagnostic_item(sym::Vec) == Some(adt_def.did())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the inherent method of `Vec` with the given name, if any.
+    pub fn get_vec_method(self, name: &str) -> Option<DefId> {
+        let vec_def_id = self.tcx.get_diagnostic_item(sym::Vec)?;
+        let name = Symbol::intern(name);
+        self.tcx
+            .inherent_impls(vec_def_id)
+            .iter()
+            .find_map(|&impl_def_id| {
+                self.tcx
+                    .associated_items(impl_def_id)
+                    .filter_by_name_unhygienic(name)
+                    .find(|item| item.kind == ty::AssocKind::Fn)
+                    .map(|item| item.def_id)
+            })
+    }
+
     // /// Returns the `DefId` of the corresponding trait method, if any.
     // /// This should not be used to resolve calls (where substs are known): use
     // /// `find_trait_method_substs` instead!
@@ -284,7 +371,15 @@ impl<'tcx> EnvQuery<'tcx> {
             debug!("Fetching implementations of method '{:?}' defined in trait '{}' with substs '{:?}'", proc_def_id, self.tcx.def_path_str(trait_id), substs);
             let infcx = self.tcx.infer_ctxt().build();
             let mut sc = SelectionContext::new(&infcx);
-            let trait_ref = self.tcx.mk_trait_ref(trait_id, substs);
+            // The substs of the call also contain the method's own generics,
+            // which are not part of the trait reference. Selection fails in
+            // the presence of regions that are not inferred, e.g. the one of
+            // `impl Trait for &'a T` when the method is called through auto-ref.
+            let trait_substs = self
+                .tcx
+                .erase_regions(substs)
+                .truncate_to(self.tcx, self.tcx.generics_of(trait_id));
+            let trait_ref = self.tcx.mk_trait_ref(trait_id, trait_substs);
             let obligation = Obligation::new(
                 self.tcx,
                 ObligationCause::dummy(),
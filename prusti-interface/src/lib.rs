@@ -26,5 +26,7 @@ pub mod specs;
 pub mod utils;
 
 pub use prusti_error::*;
+pub use unsupported_feature::*;
 
 mod prusti_error;
+mod unsupported_feature;
@@ -7,7 +7,10 @@
 use crate::environment::EnvDiagnostic;
 use ::log::warn;
 use prusti_common::config;
-use prusti_rustc_interface::{errors::MultiSpan, span::Span};
+use prusti_rustc_interface::{
+    errors::{Applicability, MultiSpan},
+    span::Span,
+};
 
 /// The Prusti message that will be reported to the user.
 ///
@@ -32,6 +35,9 @@ pub struct PrustiError {
     span: Box<MultiSpan>,
     help: Option<String>,
     notes: Vec<(String, Option<MultiSpan>)>,
+    /// Fixes: a message, the span to replace, the replacement, and how
+    /// certain it is that the replacement is correct.
+    suggestions: Vec<(String, Span, String, Applicability)>,
 }
 /// Determines how a `PrustiError` is reported.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -66,6 +72,7 @@ impl PrustiError {
             span: Box::new(span),
             help: None,
             notes: vec![],
+            suggestions: vec![],
         }
     }
 
@@ -162,6 +169,15 @@ impl PrustiError {
         self.is_disabled
     }
 
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The notes of the error, such as the values of a counterexample.
+    pub fn notes(&self) -> impl Iterator<Item = &str> {
+        self.notes.iter().map(|(note, _)| note.as_str())
+    }
+
     #[must_use]
     pub fn set_help<S: ToString>(mut self, message: S) -> Self {
         self.help = Some(message.to_string());
@@ -179,6 +195,21 @@ impl PrustiError {
         self.notes.push((message.to_string(), opt_span));
     }
 
+    pub fn add_suggestion_mut<S: ToString, R: ToString>(
+        &mut self,
+        message: S,
+        span: Span,
+        replacement: R,
+        applicability: Applicability,
+    ) {
+        self.suggestions.push((
+            message.to_string(),
+            span,
+            replacement.to_string(),
+            applicability,
+        ));
+    }
+
     /// Report the encoding error using the compiler's interface.
     /// Warnings are not immediately emitted, but buffered and only shown
     /// if an error is emitted (i.e. verification failure)
@@ -190,18 +221,21 @@ impl PrustiError {
                 &self.message,
                 &self.help,
                 &self.notes,
+                &self.suggestions,
             ),
             PrustiErrorKind::Warning => env_diagnostic.span_warn_with_help_and_notes(
                 *self.span,
                 &self.message,
                 &self.help,
                 &self.notes,
+                &self.suggestions,
             ),
             PrustiErrorKind::WarningOnError => env_diagnostic.span_warn_on_err_with_help_and_notes(
                 *self.span,
                 &self.message,
                 &self.help,
                 &self.notes,
+                &self.suggestions,
             ),
         };
     }
@@ -0,0 +1,191 @@
+use super::common::*;
+use crate::{
+    environment::{EnvQuery, Environment},
+    utils::has_spec_only_attr,
+    PrustiError,
+};
+use prusti_common::config;
+use prusti_rustc_interface::{
+    errors::MultiSpan,
+    hir::{self as hir, def::Res, def_id::LocalDefId, intravisit},
+    middle::hir::map::Map,
+    span::{BytePos, Span},
+};
+
+/// The latest version of the specification language. Version 1 is the
+/// language before any of its idioms were deprecated.
+const LATEST_SPEC_LANGUAGE_VERSION: u32 = 2;
+
+/// Warns about idioms of the specification language which are deprecated in
+/// the version selected with the `spec_language_version` flag, and suggests
+/// their modern equivalent.
+///
+/// Version 2 deprecates:
+/// - calling `snapshot_equality(a, b)` instead of writing `a === b`,
+/// - naming the reference of a pledge, as in `#[after_expiry(result => ..)]`,
+///   which can only be `result`.
+pub struct DeprecatedSpecIdiomsChecker;
+
+impl<'tcx> SpecCheckerStrategy<'tcx> for DeprecatedSpecIdiomsChecker {
+    #[tracing::instrument(
+        name = "DeprecatedSpecIdiomsChecker::check",
+        level = "debug",
+        skip(self, env)
+    )]
+    fn check(&self, env: &Environment<'tcx>) -> Vec<PrustiError> {
+        let version = config::spec_language_version();
+        if version > LATEST_SPEC_LANGUAGE_VERSION {
+            return vec![PrustiError::incorrect(
+                format!(
+                    "unknown version {version} of the specification language selected with the \
+                    `spec_language_version` flag; the latest version is \
+                    {LATEST_SPEC_LANGUAGE_VERSION}"
+                ),
+                MultiSpan::new(),
+            )];
+        }
+        if version < 2 {
+            return Vec::new();
+        }
+        let mut visitor = DeprecatedSpecIdiomsVisitor {
+            env_query: env.query,
+            warnings: Vec::new(),
+        };
+        env.query.hir().walk_toplevel_module(&mut visitor);
+        visitor.warnings
+    }
+}
+
+struct DeprecatedSpecIdiomsVisitor<'tcx> {
+    env_query: EnvQuery<'tcx>,
+    warnings: Vec<PrustiError>,
+}
+
+impl<'tcx> DeprecatedSpecIdiomsVisitor<'tcx> {
+    fn snippet(&self, span: Span) -> Option<String> {
+        self.env_query
+            .tcx()
+            .sess
+            .source_map()
+            .span_to_snippet(span)
+            .ok()
+    }
+
+    /// Reports `snapshot_equality(a, b)` written by the user. The calls which
+    /// `a === b` is desugared to have the span of the whole comparison.
+    fn check_snapshot_equality_call(&mut self, ex: &'tcx hir::Expr<'tcx>) {
+        let hir::ExprKind::Call(callee, [lhs, rhs]) = &ex.kind else {
+            return;
+        };
+        let hir::ExprKind::Path(hir::QPath::Resolved(None, path)) = &callee.kind else {
+            return;
+        };
+        let Res::Def(_, def_id) = path.res else {
+            return;
+        };
+        if callee.span.from_expansion()
+            || self.env_query.tcx().def_path_str(def_id) != "prusti_contracts::snapshot_equality"
+        {
+            return;
+        }
+        let Some(callee_snippet) = self.snippet(callee.span) else {
+            return;
+        };
+        if !callee_snippet.ends_with("snapshot_equality") {
+            return;
+        }
+        let mut warning = PrustiError::warning(
+            "calling `snapshot_equality` is deprecated since version 2 of the specification \
+            language",
+            MultiSpan::from_span(ex.span),
+        );
+        if let (Some(lhs), Some(rhs)) = (self.snippet(lhs.span), self.snippet(rhs.span)) {
+            let operand = |snippet: &str| snippet.strip_prefix('&').unwrap_or(snippet).to_string();
+            warning.add_suggestion_mut(
+                "use the snapshot equality operator instead",
+                ex.span,
+                format!("{} === {}", operand(&lhs), operand(&rhs)),
+            );
+        }
+        self.warnings.push(warning);
+    }
+
+    /// Reports the reference `result =>` in front of the condition of a
+    /// pledge, whose specification item is named after the pledge and has the
+    /// span of the condition.
+    fn check_pledge_reference(&mut self, name: Span, local_id: LocalDefId) {
+        let attrs = self.env_query.get_local_attributes(local_id);
+        if !has_spec_only_attr(attrs) || name.from_expansion() {
+            return;
+        }
+        let Ok(preceding) = self
+            .env_query
+            .tcx()
+            .sess
+            .source_map()
+            .span_to_prev_source(name)
+        else {
+            return;
+        };
+        let Some(before_arrow) = preceding.trim_end().strip_suffix("=>") else {
+            return;
+        };
+        let Some(before_reference) = before_arrow.trim_end().strip_suffix("result") else {
+            return;
+        };
+        if before_reference.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+            return;
+        }
+        let reference_len = (preceding.len() - before_reference.len()) as u32;
+        let reference_span = name
+            .with_lo(name.lo() - BytePos(reference_len))
+            .with_hi(name.lo());
+        let mut warning = PrustiError::warning(
+            "naming the reference of a pledge is deprecated since version 2 of the \
+            specification language",
+            MultiSpan::from_span(reference_span),
+        );
+        warning.add_suggestion_mut(
+            "remove the reference, which is always `result`",
+            reference_span,
+            "",
+        );
+        self.warnings.push(warning);
+    }
+}
+
+impl<'tcx> intravisit::Visitor<'tcx> for DeprecatedSpecIdiomsVisitor<'tcx> {
+    type Map = Map<'tcx>;
+    type NestedFilter = prusti_rustc_interface::middle::hir::nested_filter::All;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.env_query.hir()
+    }
+
+    fn visit_fn(
+        &mut self,
+        fk: intravisit::FnKind<'tcx>,
+        fd: &'tcx hir::FnDecl<'tcx>,
+        b: hir::BodyId,
+        _s: Span,
+        local_id: LocalDefId,
+    ) {
+        let ident = match fk {
+            intravisit::FnKind::ItemFn(ident, ..) | intravisit::FnKind::Method(ident, ..) => {
+                Some(ident)
+            }
+            intravisit::FnKind::Closure => None,
+        };
+        if let Some(ident) = ident {
+            if ident.as_str().starts_with("prusti_pledge_item_") {
+                self.check_pledge_reference(ident.span, local_id);
+            }
+        }
+        intravisit::walk_fn(self, fk, fd, b, local_id);
+    }
+
+    fn visit_expr(&mut self, ex: &'tcx hir::Expr<'tcx>) {
+        self.check_snapshot_equality_call(ex);
+        intravisit::walk_expr(self, ex);
+    }
+}
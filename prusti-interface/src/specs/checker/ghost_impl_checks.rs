@@ -0,0 +1,161 @@
+use super::common::*;
+use crate::{
+    environment::{EnvQuery, Environment},
+    PrustiError,
+};
+use prusti_rustc_interface::{
+    errors::MultiSpan,
+    hir::{
+        self as hir,
+        def::{DefKind, Res},
+        def_id::DefId,
+    },
+    middle::ty::{subst::SubstsRef, TyCtxt},
+    span::Span,
+};
+
+/// Checks the usage of the methods of ghost implementations (induced by the
+/// `#[ghost_impl]` macro) in non-spec code. Such code would not compile without
+/// Prusti, because ghost implementations only exist during verification.
+pub struct IllegalGhostImplUsagesChecker;
+
+impl<'tcx> SpecCheckerStrategy<'tcx> for IllegalGhostImplUsagesChecker {
+    #[tracing::instrument(
+        name = "IllegalGhostImplUsagesChecker::check",
+        level = "debug",
+        skip(self, env)
+    )]
+    fn check(&self, env: &Environment<'tcx>) -> Vec<PrustiError> {
+        let mut visit = GhostImplUsageVisitor {
+            env_query: env.query,
+            ghost_impl_usages_in_non_spec_code: Vec::default(),
+        }
+        .wrap_as_visitor();
+
+        env.query.hir().walk_toplevel_module(&mut visit);
+
+        let illegal_ghost_impl_usages = visit.wrapped.ghost_impl_usages_in_non_spec_code;
+        illegal_ghost_impl_usages
+            .into_iter()
+            .map(|(usage_span, def_span)| {
+                PrustiError::incorrect(
+                    "using ghost implementations in non-specification code is not allowed"
+                        .to_string(),
+                    MultiSpan::from_span(usage_span),
+                )
+                .add_note(
+                    "this method is only implemented during verification",
+                    Some(def_span),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Checks for calls of methods of ghost implementations in non-specification
+/// code
+struct GhostImplUsageVisitor<'tcx> {
+    env_query: EnvQuery<'tcx>,
+    ghost_impl_usages_in_non_spec_code: Vec<(Span, Span)>,
+}
+
+impl<'tcx> NonSpecExprVisitor<'tcx> for GhostImplUsageVisitor<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.env_query.tcx()
+    }
+
+    fn visit_expr(&mut self, ex: &'tcx hir::Expr<'tcx>) {
+        let owner_def_id = ex.hir_id.owner.def_id;
+        if !self.env_query.has_body(owner_def_id)
+            || self
+                .env_query
+                .has_prusti_attribute(owner_def_id.to_def_id(), "ghost_impl")
+        {
+            // The methods of ghost implementations may call each other.
+            return;
+        }
+
+        let typeck_results = self.env_query.tcx().typeck(owner_def_id);
+        let called_def_id = match ex.kind {
+            hir::ExprKind::MethodCall(..) => typeck_results.type_dependent_def_id(ex.hir_id),
+            hir::ExprKind::Path(ref path) => match typeck_results.qpath_res(path, ex.hir_id) {
+                Res::Def(_, def_id) => Some(def_id),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(called_def_id) = called_def_id else {
+            return;
+        };
+        if !self.env_query.tcx().def_kind(called_def_id).is_fn_like() {
+            return;
+        }
+        let call_substs = typeck_results.node_substs(ex.hir_id);
+        let (resolved_def_id, _) = self.env_query.resolve_method_call(
+            owner_def_id.to_def_id(),
+            called_def_id,
+            call_substs,
+        );
+        let ghost_impl_method = if self
+            .env_query
+            .has_prusti_attribute(resolved_def_id, "ghost_impl")
+        {
+            Some(resolved_def_id)
+        } else {
+            // A generic function uses the implementations satisfying its
+            // trait bounds, e.g. `T: Model` when it is called with `T = u32`
+            self.find_ghost_impl_method_of_bounds(
+                owner_def_id.to_def_id(),
+                called_def_id,
+                call_substs,
+            )
+        };
+        if let Some(ghost_impl_method) = ghost_impl_method {
+            let def_span = self.env_query.get_def_span(ghost_impl_method);
+            self.ghost_impl_usages_in_non_spec_code
+                .push((ex.span, def_span));
+        }
+    }
+}
+
+impl<'tcx> GhostImplUsageVisitor<'tcx> {
+    /// Returns a method of a ghost implementation, if such an implementation
+    /// satisfies one of the trait bounds of `called_def_id` called with
+    /// `call_substs`.
+    fn find_ghost_impl_method_of_bounds(
+        &self,
+        caller_def_id: DefId,
+        called_def_id: DefId,
+        call_substs: SubstsRef<'tcx>,
+    ) -> Option<DefId> {
+        let tcx = self.env_query.tcx();
+        let predicates = tcx
+            .predicates_of(called_def_id)
+            .instantiate(tcx, call_substs);
+        predicates
+            .predicates
+            .into_iter()
+            .filter_map(|predicate| predicate.to_opt_poly_trait_pred())
+            .find_map(|trait_pred| {
+                let trait_ref = tcx.erase_late_bound_regions(trait_pred).trait_ref;
+                tcx.associated_item_def_ids(trait_ref.def_id)
+                    .iter()
+                    .copied()
+                    .filter(|&item_def_id| tcx.def_kind(item_def_id) == DefKind::AssocFn)
+                    .find_map(|trait_method_def_id| {
+                        let method_substs = self
+                            .env_query
+                            .identity_substs(trait_method_def_id)
+                            .rebase_onto(tcx, trait_ref.def_id, trait_ref.substs);
+                        let (resolved_def_id, _) = self.env_query.resolve_method_call(
+                            caller_def_id,
+                            trait_method_def_id,
+                            method_substs,
+                        );
+                        self.env_query
+                            .has_prusti_attribute(resolved_def_id, "ghost_impl")
+                            .then_some(resolved_def_id)
+                    })
+            })
+    }
+}
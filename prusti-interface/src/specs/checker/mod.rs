@@ -1,12 +1,16 @@
 //! Module for verifying user-provided specifications after macro expansion
 
 mod common;
+mod deprecation_checks;
+mod ghost_impl_checks;
 mod predicate_checks;
 mod type_model_checks;
 mod version_checks;
 
 use crate::environment::Environment;
 use common::*;
+use deprecation_checks::DeprecatedSpecIdiomsChecker;
+use ghost_impl_checks::IllegalGhostImplUsagesChecker;
 use predicate_checks::IllegalPredicateUsagesChecker;
 use type_model_checks::{IllegalModelUsagesChecker, ModelDefinedOnTypeWithoutFields};
 use version_checks::MismatchedVersionsChecker;
@@ -31,6 +35,8 @@ impl<'tcx> SpecChecker<'tcx> {
                 Box::new(IllegalPredicateUsagesChecker {}),
                 Box::new(IllegalModelUsagesChecker {}),
                 Box::new(ModelDefinedOnTypeWithoutFields {}),
+                Box::new(IllegalGhostImplUsagesChecker {}),
+                Box::new(DeprecatedSpecIdiomsChecker {}),
             ],
         }
     }
@@ -1,3 +1,4 @@
+use prusti_common::config;
 use prusti_rustc_interface::{
     serialize::{Decodable, Encodable},
     span::DUMMY_SP,
@@ -52,28 +53,45 @@ impl CrossCrateSpecs {
         // Otherwise the crate doesn't show up in `tcx.crates()`.  Is there some better way
         // to get dependency crates, which doesn't ignore unused ones? Maybe:
         // https://doc.rust-lang.org/stable/nightly-rustc/rustc_metadata/creader/struct.CrateMetadataRef.html#method.dependencies
+        // The `prusti_std` crate is loaded with `--extern force:prusti_std=...`
+        // (see `LOAD_STD_SPECS`), in which case it is not an `extern crate` item.
+        // Its specifications are only defaults for the ones of other crates, so
+        // it is imported last.
+        let mut crates = Vec::new();
         for crate_num in env.tcx().crates(()) {
-            if let Some(extern_crate) = env.tcx().extern_crate(crate_num.as_def_id()) {
-                if extern_crate.is_direct() {
-                    let crate_name = env.tcx().crate_name(*crate_num);
-                    let crate_source = env.tcx().used_crate_source(*crate_num);
-                    let mut source = crate_source.paths().next().unwrap().clone();
-                    source.set_extension("specs");
-                    if source.is_file() {
-                        if let Err(e) =
-                            Self::import_from_file(env, def_spec, &source, crate_name.as_str())
-                        {
-                            PrustiError::internal(
-                                format!(
-                                    "error importing specs from file \"{}\": {}",
-                                    source.to_string_lossy(),
-                                    e
-                                ),
-                                DUMMY_SP.into(),
-                            )
-                            .emit(&env.diagnostic);
-                        }
-                    }
+            let crate_name = env.tcx().crate_name(*crate_num);
+            let is_direct = env
+                .tcx()
+                .extern_crate(crate_num.as_def_id())
+                .map_or(false, |extern_crate| extern_crate.is_direct());
+            let is_std_specs = crate_name.as_str() == "prusti_std";
+            if is_direct || (is_std_specs && config::load_std_specs()) {
+                crates.push((*crate_num, is_std_specs));
+            }
+        }
+        crates.sort_by_key(|&(_, is_std_specs)| is_std_specs);
+        for (crate_num, is_std_specs) in crates {
+            let crate_name = env.tcx().crate_name(crate_num);
+            let crate_source = env.tcx().used_crate_source(crate_num);
+            let mut source = crate_source.paths().next().unwrap().clone();
+            source.set_extension("specs");
+            if source.is_file() {
+                if let Err(e) = Self::import_from_file(
+                    env,
+                    def_spec,
+                    &source,
+                    crate_name.as_str(),
+                    is_std_specs,
+                ) {
+                    PrustiError::internal(
+                        format!(
+                            "error importing specs from file \"{}\": {}",
+                            source.to_string_lossy(),
+                            e
+                        ),
+                        DUMMY_SP.into(),
+                    )
+                    .emit(&env.diagnostic);
                 }
             }
         }
@@ -102,6 +120,7 @@ impl CrossCrateSpecs {
         def_spec: &mut DefSpecificationMap,
         path: &path::PathBuf,
         crate_name: &str,
+        as_defaults: bool,
     ) -> io::Result<()> {
         use std::io::Read;
         let mut data = Vec::new();
@@ -112,7 +131,7 @@ impl CrossCrateSpecs {
         let proc_specs = FxHashMap::decode(&mut decoder);
         let type_specs = FxHashMap::decode(&mut decoder);
         let mirs_of_specs = CrossCrateBodies::decode(&mut decoder);
-        def_spec.import_external(proc_specs, type_specs, env);
+        def_spec.import_external(proc_specs, type_specs, env, as_defaults);
         env.body.import_external_bodies(mirs_of_specs);
         Ok(())
     }
@@ -10,7 +10,7 @@ use log::debug;
 use prusti_common::config;
 use prusti_rustc_interface::{
     ast::ast,
-    data_structures::fx::FxHashMap,
+    data_structures::fx::{FxHashMap, FxHashSet},
     errors::MultiSpan,
     hir::{
         self,
@@ -18,7 +18,7 @@ use prusti_rustc_interface::{
         intravisit, FnRetTy,
     },
     middle::{hir::map::Map, ty},
-    span::Span,
+    span::{symbol::sym, Span},
 };
 use std::{convert::TryInto, fmt::Debug};
 
@@ -27,6 +27,7 @@ pub mod cross_crate;
 pub mod decoder;
 pub mod encoder;
 pub mod external;
+pub mod trust_audit;
 pub mod typed;
 
 use typed::SpecIdRef;
@@ -117,6 +118,8 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
         let mut def_spec = typed::DefSpecificationMap::new();
         self.determine_procedure_specs(&mut def_spec);
         self.determine_extern_specs(&mut def_spec);
+        self.determine_derived_default_specs(&mut def_spec);
+        self.determine_async_fn_body_specs(&mut def_spec);
         self.determine_loop_specs(&mut def_spec);
         self.determine_type_specs(&mut def_spec);
         self.determine_prusti_assertions(&mut def_spec);
@@ -150,6 +153,11 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
                             self.env,
                         );
                     }
+                    SpecIdRef::CompletionPostcondition(spec_id) => {
+                        spec.add_completion_postcondition(
+                            *self.spec_functions.get(spec_id).unwrap(),
+                        );
+                    }
                     SpecIdRef::Purity(spec_id) => {
                         spec.add_purity(*self.spec_functions.get(spec_id).unwrap(), self.env);
                     }
@@ -175,6 +183,26 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
 
             spec.set_trusted(refs.trusted);
 
+            // The postconditions on completion of an `async fn` are verified
+            // against its body. Other functions only return a future, whose
+            // output is not verified.
+            let has_completion_posts = refs
+                .spec_id_refs
+                .iter()
+                .any(|spec_id_ref| matches!(spec_id_ref, SpecIdRef::CompletionPostcondition(_)));
+            if has_completion_posts
+                && !refs.trusted
+                && !self.env.query.is_async_fn(local_id.to_def_id())
+            {
+                let span = self.env.query.get_def_span(*local_id);
+                PrustiError::incorrect(
+                    "postconditions on completion can only be verified for async functions",
+                    MultiSpan::from(span),
+                )
+                .set_help("mark the function as `#[trusted]` to assume the postconditions")
+                .emit(&self.env.diagnostic);
+            }
+
             if let Some(kind) = kind_override {
                 spec.set_kind(kind);
             }
@@ -214,6 +242,108 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
         }
     }
 
+    /// Makes the implementations of `Default::default` generated by
+    /// `#[derive(Default)]` for structs pure if the defaults of all fields are
+    /// pure, so that callers know the fields of the default value. This
+    /// includes the fields whose types derive `Default` in the same way.
+    fn determine_derived_default_specs(&self, def_spec: &mut typed::DefSpecificationMap) {
+        let tcx = self.env.query.tcx();
+        let Some(default_trait) = tcx.get_diagnostic_item(sym::Default) else {
+            return;
+        };
+        // Maps structs to the local, non-generic, derived implementations of
+        // `Default` for them.
+        let derived_impls: FxHashMap<DefId, DefId> = tcx
+            .all_impls(default_trait)
+            .filter(|&impl_def_id| {
+                impl_def_id.is_local()
+                    && tcx.has_attr(impl_def_id, sym::automatically_derived)
+                    && tcx.generics_of(impl_def_id).count() == 0
+            })
+            .filter_map(|impl_def_id| {
+                match tcx.type_of(impl_def_id).subst_identity().kind() {
+                    ty::TyKind::Adt(adt_def, _) if adt_def.is_struct() => {
+                        Some((adt_def.did(), impl_def_id))
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+        for (&struct_def_id, &impl_def_id) in &derived_impls {
+            let mut visited = FxHashSet::default();
+            if !self.has_pure_derived_default(struct_def_id, &derived_impls, &mut visited) {
+                continue;
+            }
+            let Some(method) = tcx
+                .associated_items(impl_def_id)
+                .in_definition_order()
+                .find(|item| item.kind == ty::AssocKind::Fn)
+            else {
+                continue;
+            };
+            def_spec.proc_specs.entry(method.def_id).or_insert_with(|| {
+                let mut spec = SpecGraph::new(ProcedureSpecification::empty(method.def_id));
+                spec.set_kind(ProcedureSpecificationKind::Pure);
+                spec
+            });
+        }
+    }
+
+    /// The body of an `async fn` is a generator, which is verified against the
+    /// specification of the function as if the function were synchronous. The
+    /// postconditions on completion of the function are postconditions of the
+    /// body.
+    fn determine_async_fn_body_specs(&self, def_spec: &mut typed::DefSpecificationMap) {
+        let hir = self.env.query.hir();
+        let async_fn_bodies: Vec<(DefId, DefId)> = def_spec
+            .proc_specs
+            .keys()
+            .filter_map(|&def_id| {
+                let local_id = def_id.as_local()?;
+                if !self.env.query.is_async_fn(def_id) {
+                    return None;
+                }
+                let body = hir.body(hir.body_owned_by(local_id));
+                let hir::ExprKind::Closure(closure) = body.value.kind else {
+                    return None;
+                };
+                Some((def_id, closure.def_id.to_def_id()))
+            })
+            .collect();
+        for (fn_def_id, body_def_id) in async_fn_bodies {
+            let mut spec = def_spec.proc_specs[&fn_def_id].clone();
+            spec.move_completion_postconditions_to_postconditions();
+            def_spec.proc_specs.insert(body_def_id, spec);
+        }
+    }
+
+    fn has_pure_derived_default(
+        &self,
+        struct_def_id: DefId,
+        derived_impls: &FxHashMap<DefId, DefId>,
+        visited: &mut FxHashSet<DefId>,
+    ) -> bool {
+        if !derived_impls.contains_key(&struct_def_id) || !visited.insert(struct_def_id) {
+            return false;
+        }
+        let tcx = self.env.query.tcx();
+        let adt_def = tcx.adt_def(struct_def_id);
+        let is_pure = adt_def.all_fields().all(|field| {
+            match tcx.type_of(field.did).subst_identity().kind() {
+                // These have pure specifications of `Default::default` in `core_spec`.
+                ty::TyKind::Bool | ty::TyKind::Char | ty::TyKind::Int(_) | ty::TyKind::Uint(_) => {
+                    true
+                }
+                ty::TyKind::Adt(field_adt_def, substs) if substs.is_empty() => {
+                    self.has_pure_derived_default(field_adt_def.did(), derived_impls, visited)
+                }
+                _ => false,
+            }
+        });
+        visited.remove(&struct_def_id);
+        is_pure
+    }
+
     fn determine_loop_specs(&self, def_spec: &mut typed::DefSpecificationMap) {
         for local_id in self.loop_specs.iter() {
             def_spec.loop_specs.insert(
@@ -381,6 +511,13 @@ fn get_procedure_spec_ids(def_id: DefId, attrs: &[ast::Attribute]) -> Option<Pro
             .into_iter()
             .map(|raw_spec_id| SpecIdRef::Postcondition(parse_spec_id(raw_spec_id, def_id))),
     );
+    spec_id_refs.extend(
+        read_prusti_attrs("post_on_completion_spec_id_ref", attrs)
+            .into_iter()
+            .map(|raw_spec_id| {
+                SpecIdRef::CompletionPostcondition(parse_spec_id(raw_spec_id, def_id))
+            }),
+    );
     spec_id_refs.extend(
         read_prusti_attrs("pure_spec_id_ref", attrs)
             .into_iter()
@@ -401,19 +538,18 @@ fn get_procedure_spec_ids(def_id: DefId, attrs: &[ast::Attribute]) -> Option<Pro
                 rhs: parse_spec_id(raw_spec_id, def_id),
             }),
     );
-    match (
-        read_prusti_attr("assert_pledge_spec_id_ref_lhs", attrs),
-        read_prusti_attr("assert_pledge_spec_id_ref_rhs", attrs),
-    ) {
-        (Some(lhs_id), Some(rhs_id)) => {
-            spec_id_refs.push(SpecIdRef::Pledge {
+    let assert_pledge_lhs_ids = read_prusti_attrs("assert_pledge_spec_id_ref_lhs", attrs);
+    let assert_pledge_rhs_ids = read_prusti_attrs("assert_pledge_spec_id_ref_rhs", attrs);
+    assert_eq!(assert_pledge_lhs_ids.len(), assert_pledge_rhs_ids.len());
+    spec_id_refs.extend(
+        assert_pledge_lhs_ids
+            .into_iter()
+            .zip(assert_pledge_rhs_ids)
+            .map(|(lhs_id, rhs_id)| SpecIdRef::Pledge {
                 lhs: Some(parse_spec_id(lhs_id, def_id)),
                 rhs: parse_spec_id(rhs_id, def_id),
-            });
-        }
-        (None, None) => {}
-        _ => unreachable!(),
-    }
+            }),
+    );
     spec_id_refs.extend(
         read_prusti_attr("pred_spec_id_ref", attrs)
             .map(|raw_spec_id| SpecIdRef::Predicate(parse_spec_id(raw_spec_id, def_id))),
@@ -566,6 +702,31 @@ impl<'a, 'tcx> intravisit::Visitor<'tcx> for SpecCollector<'a, 'tcx> {
                 self.procedure_specs.insert(local_id, procedure_spec_ref);
             }
 
+            // Laws of traits have to be verified for every implementation,
+            // whether or not the implementation repeats the `#[law]` attribute
+            let implements_law = self
+                .env
+                .query
+                .tcx()
+                .opt_associated_item(def_id)
+                .and_then(|item| item.trait_item_def_id)
+                .map_or(false, |trait_item_id| {
+                    has_prusti_attr(self.env.query.get_attributes(trait_item_id), "law")
+                });
+            if (has_prusti_attr(attrs, "law") || implements_law)
+                && has_prusti_attr(attrs, "trusted")
+            {
+                PrustiError::incorrect(
+                    "implementations of laws cannot be trusted",
+                    MultiSpan::from_span(span),
+                )
+                .add_note(
+                    "every implementation of a trait has to verify the laws of the trait",
+                    None,
+                )
+                .emit(&self.env.diagnostic);
+            }
+
             // Collect model type flag
             if has_to_model_fn_attr(attrs) {
                 if let FnRetTy::Return(ty) = fn_decl.output {
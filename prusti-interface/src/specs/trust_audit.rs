@@ -0,0 +1,108 @@
+//! Report of the trusted items of a crate, which are the assumptions that its
+//! verification relies on.
+
+use crate::{
+    environment::Environment,
+    specs::typed::{DefSpecificationMap, SpecificationItem},
+    utils::{has_prusti_attr, read_prusti_attr},
+    PrustiError,
+};
+use prusti_rustc_interface::span::{Span, DUMMY_SP};
+use std::path::Path;
+
+/// Writes a JSON report of the trusted functions, types and external
+/// specifications of the local crate to the file `<crate>.trusted.json` in
+/// the directory `dir`. Every item is reported with the reason given with
+/// `#[trusted("reason")]`, if any, its location and its signature.
+pub fn write_trust_audit_report(env: &Environment<'_>, def_spec: &DefSpecificationMap, dir: &Path) {
+    let tcx = env.tcx();
+    let source_map = tcx.sess.source_map();
+    let mut items: Vec<(Span, serde_json::Value)> = vec![];
+    let mut report_item =
+        |kind: &str, item: String, reason: Option<String>, span: Span, signature: String| {
+            let location = source_map.lookup_char_pos(span.lo());
+            items.push((
+                span,
+                serde_json::json!({
+                    "item": item,
+                    "kind": kind,
+                    "reason": reason,
+                    "location": format!(
+                        "{}:{}:{}",
+                        location.file.name.prefer_local(),
+                        location.line,
+                        location.col.0 + 1
+                    ),
+                    "signature": signature,
+                }),
+            ));
+        };
+
+    for (&def_id, spec_graph) in &def_spec.proc_specs {
+        // The specification of an external function is written on a local
+        // stub, which is always trusted and carries the reason, if any.
+        let source = spec_graph.base_spec.source;
+        let Some(local_source) = source.as_local() else {
+            continue;
+        };
+        let attrs = env.query.get_local_attributes(local_source);
+        if !has_prusti_attr(attrs, "trusted") {
+            continue;
+        }
+        let kind = if def_id == source {
+            "function"
+        } else {
+            "extern_spec"
+        };
+        report_item(
+            kind,
+            env.name.get_absolute_item_name(def_id),
+            read_prusti_attr("trusted_reason", attrs),
+            env.query.get_def_span(source),
+            tcx.fn_sig(def_id).subst_identity().to_string(),
+        );
+    }
+
+    for (&def_id, type_spec) in &def_spec.type_specs {
+        if !matches!(type_spec.trusted, SpecificationItem::Inherent(true)) {
+            continue;
+        }
+        // The reason is recorded on the specification item marking the type
+        // as trusted, which is a method of the type.
+        let reason = tcx
+            .inherent_impls(def_id)
+            .iter()
+            .flat_map(|&impl_def_id| tcx.associated_item_def_ids(impl_def_id))
+            .map(|&item_def_id| env.query.get_attributes(item_def_id))
+            .find(|attrs| has_prusti_attr(attrs, "trusted_type"))
+            .and_then(|attrs| read_prusti_attr("trusted_reason", attrs));
+        report_item(
+            "type",
+            env.name.get_absolute_item_name(def_id),
+            reason,
+            env.query.get_def_span(def_id),
+            tcx.type_of(def_id).subst_identity().to_string(),
+        );
+    }
+
+    items.sort_by_key(|&(span, _)| span);
+    let items: Vec<_> = items.into_iter().map(|(_, item)| item).collect();
+
+    let path = dir.join(format!("{}.trusted.json", env.name.local_crate_filename()));
+    let write_report = || -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let file = std::fs::File::create(&path)?;
+        serde_json::to_writer_pretty(file, &serde_json::json!({ "trusted": items }))?;
+        Ok(())
+    };
+    if let Err(error) = write_report() {
+        PrustiError::internal(
+            format!(
+                "could not write the trust audit report to {}: {error}",
+                path.display()
+            ),
+            DUMMY_SP.into(),
+        )
+        .emit(&env.diagnostic);
+    }
+}
@@ -3,6 +3,7 @@ pub use common::{SpecIdRef, SpecType, SpecificationId};
 use prusti_rustc_interface::{
     hir::def_id::{DefId, LocalDefId},
     macros::{TyDecodable, TyEncodable},
+    middle::ty::TyCtxt,
 };
 use prusti_specs::specifications::common;
 use regex::Regex;
@@ -59,6 +60,47 @@ impl DefSpecificationMap {
         self.ghost_end.get(def_id)
     }
 
+    /// Whether the procedure `def_id` has a specification or contains
+    /// specifications in its body, such as loop invariants or `prusti_assert!`.
+    /// A trait method implementation without specifications of its own is
+    /// annotated if it inherits the specification of the trait method.
+    pub fn is_annotated(&self, tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+        let trait_item_def_id = tcx
+            .opt_associated_item(def_id)
+            .and_then(|item| item.trait_item_def_id);
+        self.proc_specs.contains_key(&def_id)
+            || trait_item_def_id.map_or(false, |trait_item| {
+                self.proc_specs.contains_key(&trait_item)
+            })
+            || self
+                .loop_specs
+                .keys()
+                .chain(self.prusti_assertions.keys())
+                .chain(self.prusti_assumptions.keys())
+                .chain(self.prusti_refutations.keys())
+                .any(|&spec_def_id| tcx.typeck_root_def_id(spec_def_id) == def_id)
+    }
+
+    /// Returns a copy of the specifications in which the given precondition,
+    /// postcondition or loop body invariant has been removed.
+    pub fn without_spec(&self, spec: DefId) -> Self {
+        let mut specs = self.clone();
+        let retain = |clauses: &mut Vec<DefId>| clauses.retain(|&clause| clause != spec);
+        for spec_graph in specs.proc_specs.values_mut() {
+            let all_specs = std::iter::once(&mut spec_graph.base_spec)
+                .chain(spec_graph.specs_with_constraints.values_mut());
+            for proc_spec in all_specs {
+                proc_spec.pres.for_each_mut(retain);
+                proc_spec.posts.for_each_mut(retain);
+                proc_spec.completion_posts.for_each_mut(retain);
+            }
+        }
+        if let Some(LoopSpecification::Invariant(_)) = specs.loop_specs.get(&spec) {
+            specs.loop_specs.remove(&spec);
+        }
+        specs
+    }
+
     pub(crate) fn defid_for_export(
         &self,
     ) -> (
@@ -82,6 +124,9 @@ impl DefSpecificationMap {
                 if let Some(posts) = spec.posts.extract_with_selective_replacement() {
                     specs.extend(posts);
                 }
+                if let Some(posts) = spec.completion_posts.extract_with_selective_replacement() {
+                    specs.extend(posts);
+                }
                 if let Some(Some(term)) = spec.terminates.extract_with_selective_replacement() {
                     specs.push(term.to_def_id());
                 }
@@ -115,7 +160,22 @@ impl DefSpecificationMap {
         proc_specs: FxHashMap<DefId, SpecGraph<ProcedureSpecification>>,
         type_specs: FxHashMap<DefId, TypeSpecification>,
         env: &Environment,
+        as_defaults: bool,
     ) {
+        if as_defaults {
+            // Default specifications are replaced by any other specification
+            for (k, v) in proc_specs {
+                self.proc_specs.entry(k).or_insert(v);
+            }
+            for (k, v) in type_specs {
+                self.type_specs.entry(k).or_insert(v);
+            }
+            return;
+        }
+        let is_override = |spec_id: DefId| {
+            env.query
+                .has_prusti_attribute(spec_id, "extern_spec_override")
+        };
         let duplicate_error = |item_id, spec_id_a: DefId, spec_id_b: DefId| {
             PrustiError::incorrect(
                 format!(
@@ -129,18 +189,37 @@ impl DefSpecificationMap {
                     env.query.get_def_span(spec_id_b),
                 ]),
             )
-            .emit(&env.diagnostic)
         };
         for (k, v) in proc_specs {
-            if let Some(other) = self.proc_specs.insert(k, v) {
-                let v = self.proc_specs.get(&k).unwrap();
-                duplicate_error(k, other.base_spec.source, v.base_spec.source);
+            let Some(other) = self.proc_specs.get(&k) else {
+                self.proc_specs.insert(k, v);
+                continue;
+            };
+            let (other_id, new_id) = (other.base_spec.source, v.base_spec.source);
+            if other_id == new_id {
+                // The same specification reached us through several dependencies
+                continue;
+            }
+            match (is_override(other_id), is_override(new_id)) {
+                (true, false) => {}
+                (false, true) => {
+                    self.proc_specs.insert(k, v);
+                }
+                _ => {
+                    self.proc_specs.insert(k, v);
+                    duplicate_error(k, other_id, new_id)
+                        .add_note(
+                            "use `#[extern_spec(override)]` to choose the specification that takes priority",
+                            None,
+                        )
+                        .emit(&env.diagnostic);
+                }
             }
         }
         for (k, v) in type_specs {
             if let Some(other) = self.type_specs.insert(k, v) {
                 let v = self.type_specs.get(&k).unwrap();
-                duplicate_error(k, other.source, v.source);
+                duplicate_error(k, other.source, v.source).emit(&env.diagnostic);
             }
         }
     }
@@ -210,6 +289,9 @@ pub struct ProcedureSpecification {
     pub kind: SpecificationItem<ProcedureSpecificationKind>,
     pub pres: SpecificationItem<Vec<DefId>>,
     pub posts: SpecificationItem<Vec<DefId>>,
+    /// Postconditions on the output of the returned future, which hold once
+    /// the future completes.
+    pub completion_posts: SpecificationItem<Vec<DefId>>,
     pub pledges: SpecificationItem<Vec<Pledge>>,
     pub trusted: SpecificationItem<bool>,
     pub terminates: SpecificationItem<Option<LocalDefId>>,
@@ -225,6 +307,7 @@ impl ProcedureSpecification {
             kind: SpecificationItem::Inherent(ProcedureSpecificationKind::Impure),
             pres: SpecificationItem::Empty,
             posts: SpecificationItem::Empty,
+            completion_posts: SpecificationItem::Empty,
             pledges: SpecificationItem::Empty,
             trusted: SpecificationItem::Inherent(false),
             terminates: SpecificationItem::Inherent(None),
@@ -361,7 +444,9 @@ impl<T> SpecGraph<T> {
 ///
 /// The consistency guarantees mentioned above satisfy (a) by construction but do not guarantee (b).
 ///
-/// **Important**: There is no automatic check that guarantees the validity of a [SpecGraph].
+/// **Important**: There is no automatic check that guarantees the validity of a [SpecGraph],
+/// except for constrained specs with type equality constraints (e.g. `T = u32`), for which
+/// the verifier checks (b).
 ///
 /// # Example: Pre- and postconditions
 /// ```ignore
@@ -453,6 +538,29 @@ impl SpecGraph<ProcedureSpecification> {
         }
     }
 
+    /// Attaches the postcondition on completion `post` to the base spec and
+    /// all constrained specs.
+    pub fn add_completion_postcondition(&mut self, post: LocalDefId) {
+        self.base_spec.completion_posts.push(post.to_def_id());
+        self.specs_with_constraints
+            .values_mut()
+            .for_each(|s| s.completion_posts.push(post.to_def_id()));
+    }
+
+    /// Turns the postconditions on completion of the base spec and all
+    /// constrained specs into ordinary postconditions.
+    pub fn move_completion_postconditions_to_postconditions(&mut self) {
+        let all_specs =
+            std::iter::once(&mut self.base_spec).chain(self.specs_with_constraints.values_mut());
+        for spec in all_specs {
+            let completion_posts =
+                std::mem::replace(&mut spec.completion_posts, SpecificationItem::Empty);
+            for post in completion_posts.extract_with_selective_replacement_iter() {
+                spec.posts.push(*post);
+            }
+        }
+    }
+
     pub fn add_purity<'tcx>(&mut self, purity: LocalDefId, env: &Environment<'tcx>) {
         match self.get_constraint(purity, env) {
             None => {
@@ -561,6 +669,18 @@ impl<T> SpecificationItem<T> {
         matches!(self, SpecificationItem::Empty)
     }
 
+    /// Applies `f` to all the values contained in this item
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(&mut T)) {
+        match self {
+            SpecificationItem::Empty => {}
+            SpecificationItem::Inherited(val) | SpecificationItem::Inherent(val) => f(val),
+            SpecificationItem::Refined(from, to) => {
+                f(from);
+                f(to);
+            }
+        }
+    }
+
     /// Returns the contained value of this item
     fn get(&self) -> Option<(Option<&T>, &T)> {
         // TODO(tymap): this API is not good: it must be possible to tell that
@@ -777,6 +897,9 @@ impl Refinable for ProcedureSpecification {
             source: self.source,
             pres: self.pres.refine(replace_empty(&EMPTYL, &other.pres)),
             posts: self.posts.refine(replace_empty(&EMPTYL, &other.posts)),
+            completion_posts: self
+                .completion_posts
+                .refine(replace_empty(&EMPTYL, &other.completion_posts)),
             pledges: self.pledges.refine(replace_empty(&EMPTYP, &other.pledges)),
             kind: self.kind.refine(&other.kind),
             trusted: self.trusted.refine(&other.trusted),
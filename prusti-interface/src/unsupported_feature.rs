@@ -0,0 +1,86 @@
+use crate::PrustiError;
+use prusti_rustc_interface::errors::MultiSpan;
+
+/// The help message of unsupported features that can be skipped by marking the
+/// enclosing function as `#[trusted]`.
+pub const TRUSTED_WORKAROUND_HELP: &str =
+    "mark the enclosing function as `#[trusted]` to skip the verification of its body";
+
+/// A Rust feature of the verified code that Prusti does not support.
+///
+/// This should be used instead of panicking when the encoding hits a feature
+/// that is not supported, so that the user is told which feature it is, where
+/// it is used, and whether it can be avoided.
+#[derive(Clone, Debug)]
+pub struct UnsupportedFeature {
+    /// The name of the feature, e.g. "inline assembly".
+    feature: String,
+    /// Whether the name of the feature is plural, e.g. "generators".
+    plural: bool,
+    span: MultiSpan,
+    /// Whether marking the enclosing function as `#[trusted]` avoids the error.
+    trusted_workaround: bool,
+}
+
+impl UnsupportedFeature {
+    /// An unsupported feature with a singular name, reported as "`feature` is
+    /// not supported".
+    pub fn new<S: ToString, T: Into<MultiSpan>>(feature: S, span: T) -> Self {
+        UnsupportedFeature {
+            feature: feature.to_string(),
+            plural: false,
+            span: span.into(),
+            trusted_workaround: false,
+        }
+    }
+
+    /// An unsupported feature with a plural name, reported as "`feature` are
+    /// not supported".
+    pub fn new_plural<S: ToString, T: Into<MultiSpan>>(feature: S, span: T) -> Self {
+        UnsupportedFeature {
+            plural: true,
+            ..Self::new(feature, span)
+        }
+    }
+
+    /// Records that the error can be avoided by marking the enclosing function
+    /// as `#[trusted]`, which is the case for features used in the body of a
+    /// function, but not for features used in its signature or contract.
+    #[must_use]
+    pub fn with_trusted_workaround(mut self) -> Self {
+        self.trusted_workaround = true;
+        self
+    }
+
+    pub fn feature(&self) -> &str {
+        &self.feature
+    }
+
+    pub fn span(&self) -> &MultiSpan {
+        &self.span
+    }
+
+    pub fn has_trusted_workaround(&self) -> bool {
+        self.trusted_workaround
+    }
+
+    pub fn message(&self) -> String {
+        let verb = if self.plural { "are" } else { "is" };
+        format!("{} {verb} not supported", self.feature)
+    }
+
+    /// The help message to show with the error, if any.
+    pub fn help(&self) -> Option<&'static str> {
+        self.trusted_workaround.then_some(TRUSTED_WORKAROUND_HELP)
+    }
+}
+
+impl From<UnsupportedFeature> for PrustiError {
+    fn from(unsupported: UnsupportedFeature) -> Self {
+        let error = PrustiError::unsupported(unsupported.message(), unsupported.span.clone());
+        match unsupported.help() {
+            Some(help) => error.set_help(help),
+            None => error,
+        }
+    }
+}
@@ -6,7 +6,14 @@
 #![feature(let_chains)]
 
 use prusti_utils::{config, launch};
-use std::{env, fs, io, path::PathBuf, process::Command};
+use serde_json::Value;
+use std::{
+    env,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 fn main() {
     if let Err(code) = process(env::args().skip(1)) {
@@ -30,7 +37,14 @@ where
     let args = args.skip_while(|arg| arg == "prusti");
     // Remove the "-- -Pflag" arguments since these won't apply to `cargo check`.
     // They have already been loaded (and the Category B flags are used below).
-    let args = args.take_while(|arg| arg != "--");
+    let args: Vec<_> = args.take_while(|arg| arg != "--").collect();
+    // The `--open-report` and `--trust-audit-report` arguments are for
+    // `cargo-prusti` only.
+    let open_report = args.iter().any(|arg| arg == "--open-report");
+    let trust_audit_report = args.iter().any(|arg| arg == "--trust-audit-report");
+    let args = args
+        .into_iter()
+        .filter(|arg| arg != "--open-report" && arg != "--trust-audit-report");
 
     // Category B flags (see dev-guide flags table):
     let cargo_path = config::cargo_path();
@@ -43,7 +57,37 @@ where
     };
     let cargo_target = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".to_string());
     let cargo_target: PathBuf = [cargo_target, "verify".to_string()].into_iter().collect();
-    let exit_status = Command::new(cargo_path)
+    let mut cargo = Command::new(cargo_path);
+    let report_dir = cargo_target.join("report");
+    let html_report = open_report || config::html_report();
+    if html_report {
+        // The compiled crates write the data of the report, unless the
+        // corresponding flags were set to other directories. The data of
+        // earlier runs is removed, so that the report only shows the crates
+        // compiled in this run.
+        let data_dir = report_dir.join("data");
+        if data_dir.is_dir() {
+            if let Err(error) = fs::remove_dir_all(&data_dir) {
+                eprintln!("Could not remove the data of the previous report: {error}");
+            }
+        }
+        cargo.env(
+            "PRUSTI_VERIFICATION_REPORT",
+            config::verification_report().unwrap_or_else(|| data_dir.clone()),
+        );
+        cargo.env(
+            "PRUSTI_TRUST_AUDIT_REPORT",
+            config::trust_audit_report().unwrap_or(data_dir),
+        );
+    }
+    let trust_audit_dir = if trust_audit_report && !html_report {
+        let dir = config::trust_audit_report().unwrap_or_else(|| cargo_target.join("trust-audit"));
+        cargo.env("PRUSTI_TRUST_AUDIT_REPORT", &dir);
+        Some(dir)
+    } else {
+        None
+    };
+    let exit_status = cargo
         .arg(&command)
         .args(features)
         .args(args)
@@ -66,6 +110,23 @@ where
         .status()
         .expect("could not run cargo");
 
+    // The report is also written if the verification failed, to show why.
+    if html_report {
+        match write_html_report(&report_dir) {
+            Ok(index) => {
+                eprintln!("The verification report is at {}", index.display());
+                if open_report {
+                    open_in_browser(&index);
+                }
+            }
+            Err(error) => eprintln!("Could not write the verification report: {error}"),
+        }
+    }
+
+    if let Some(dir) = trust_audit_dir {
+        eprintln!("The trust audit report is in {}", dir.display());
+    }
+
     if exit_status.success() {
         if command == "build" {
             copy_exported_specs(cargo_target).ok();
@@ -99,3 +160,177 @@ fn copy_exported_specs(cargo_target: PathBuf) -> io::Result<()> {
     }
     Ok(())
 }
+
+/// Write a static HTML report of the verification results and the trusted
+/// items of the crates whose data is in the subdirectory `data` of
+/// `report_dir`, and return the path of its index.
+fn write_html_report(report_dir: &Path) -> io::Result<PathBuf> {
+    let verification_dir = config::verification_report().unwrap_or_else(|| report_dir.join("data"));
+    let trust_dir = config::trust_audit_report().unwrap_or_else(|| report_dir.join("data"));
+    let verification = read_reports(&verification_dir, ".verification.json")?;
+    let trusted = read_reports(&trust_dir, ".trusted.json")?;
+
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Prusti verification report</title>\n<style>\n\
+         body { font-family: sans-serif; margin: 2em; }\n\
+         table { border-collapse: collapse; margin-bottom: 1em; }\n\
+         td, th { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }\n\
+         .verified { color: #1a7f37; } .failed { color: #cf222e; } .not-verified { color: #9a6700; }\n\
+         pre { margin: 0.3em 0; white-space: pre-wrap; }\n\
+         </style>\n</head>\n<body>\n<h1>Prusti verification report</h1>\n",
+    );
+    let mut crates: Vec<&String> = verification.keys().chain(trusted.keys()).collect();
+    crates.sort();
+    crates.dedup();
+    for crate_file in crates {
+        let name = verification
+            .get(crate_file)
+            .and_then(|report| report["crate"].as_str())
+            .unwrap_or(crate_file);
+        writeln!(html, "<h2>Crate <code>{}</code></h2>", escape(name)).unwrap();
+        match verification.get(crate_file) {
+            Some(report) => write_procedures(&mut html, report),
+            None => html.push_str("<p>The crate was not verified.</p>\n"),
+        }
+        if let Some(report) = trusted.get(crate_file) {
+            write_trusted_items(&mut html, report);
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+
+    let index = report_dir.join("index.html");
+    fs::create_dir_all(report_dir)?;
+    fs::write(&index, html)?;
+    Ok(index)
+}
+
+/// Read the JSON reports with the given suffix in `dir`, indexed by the part
+/// of their file name that identifies the crate.
+fn read_reports(dir: &Path, suffix: &str) -> io::Result<std::collections::BTreeMap<String, Value>> {
+    let mut reports = std::collections::BTreeMap::new();
+    if !dir.is_dir() {
+        return Ok(reports);
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if let Some(crate_file) = file_name.strip_suffix(suffix) {
+            let report = serde_json::from_str(&fs::read_to_string(&path)?)?;
+            reports.insert(crate_file.to_string(), report);
+        }
+    }
+    Ok(reports)
+}
+
+/// Write the table of the verified functions of a crate, in which the errors
+/// of every function can be expanded.
+fn write_procedures(html: &mut String, report: &Value) {
+    let procedures = report["procedures"].as_array().cloned().unwrap_or_default();
+    let count = |status: &str| {
+        procedures
+            .iter()
+            .filter(|procedure| procedure["status"] == status)
+            .count()
+    };
+    writeln!(
+        html,
+        "<p>{} verified, {} failed, {} not verified.</p>",
+        count("verified"),
+        count("failed"),
+        count("not verified")
+    )
+    .unwrap();
+    if procedures.is_empty() {
+        return;
+    }
+    html.push_str(
+        "<table>\n<tr><th>Function</th><th>Status</th><th>Time</th><th>Location</th></tr>\n",
+    );
+    for procedure in &procedures {
+        let status = procedure["status"].as_str().unwrap_or_default();
+        let errors = procedure["errors"].as_array().cloned().unwrap_or_default();
+        let mut function = format!("<code>{}</code>", escape_value(&procedure["function"]));
+        if !errors.is_empty() {
+            let mut details = String::new();
+            for error in &errors {
+                writeln!(details, "<pre>error: {}", escape_value(&error["message"])).unwrap();
+                for note in error["notes"].as_array().into_iter().flatten() {
+                    writeln!(details, "  note: {}", escape_value(note)).unwrap();
+                }
+                details.push_str("</pre>\n");
+            }
+            let plural = if errors.len() == 1 { "" } else { "s" };
+            function = format!(
+                "<details><summary>{function} ({} error{plural})</summary>\n{details}</details>",
+                errors.len()
+            );
+        }
+        writeln!(
+            html,
+            "<tr><td>{function}</td><td class=\"{}\">{}</td><td>{} ms</td><td>{}</td></tr>",
+            status.replace(' ', "-"),
+            escape(status),
+            procedure["duration_ms"].as_u64().unwrap_or_default(),
+            escape_value(&procedure["location"]),
+        )
+        .unwrap();
+    }
+    html.push_str("</table>\n");
+}
+
+/// Write the table of the trusted items of a crate.
+fn write_trusted_items(html: &mut String, report: &Value) {
+    let items = report["trusted"].as_array().cloned().unwrap_or_default();
+    writeln!(html, "<h3>Trusted items ({})</h3>", items.len()).unwrap();
+    if items.is_empty() {
+        return;
+    }
+    html.push_str(
+        "<table>\n<tr><th>Item</th><th>Kind</th><th>Reason</th><th>Signature</th><th>Location</th></tr>\n",
+    );
+    for item in &items {
+        writeln!(
+            html,
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td><code>{}</code></td><td>{}</td></tr>",
+            escape_value(&item["item"]),
+            escape_value(&item["kind"]),
+            escape_value(&item["reason"]),
+            escape_value(&item["signature"]),
+            escape_value(&item["location"]),
+        )
+        .unwrap();
+    }
+    html.push_str("</table>\n");
+}
+
+/// Escape a string value of a report for HTML. Other values are shown as
+/// empty.
+fn escape_value(value: &Value) -> String {
+    escape(value.as_str().unwrap_or_default())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Open a file in the default browser, reporting failures without aborting.
+fn open_in_browser(path: &Path) {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    if let Err(error) = command.arg(path).status() {
+        eprintln!("Could not open {} in a browser: {error}", path.display());
+    }
+}
@@ -9,6 +9,7 @@
 mod client;
 mod process_verification;
 mod server;
+mod session;
 mod verification_request;
 mod backend;
 
@@ -16,6 +17,7 @@ pub use backend::*;
 pub use client::*;
 pub use process_verification::*;
 pub use server::*;
+pub use session::*;
 pub use verification_request::*;
 
 // Futures returned by `Client` need to be executed in a compatible tokio runtime.
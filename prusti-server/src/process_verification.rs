@@ -4,7 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::{Backend, VerificationRequest, ViperBackendConfig};
+use crate::{record_session_entry, Backend, VerificationRequest, ViperBackendConfig};
 use log::info;
 use once_cell::sync::Lazy;
 use prusti_common::{
@@ -94,6 +94,9 @@ pub fn process_verification_request<'v, 't: 'v>(
                     let _ = build_or_dump_viper_program();
                 });
             }
+            if let Some(session_dir) = config::record_session() {
+                record_session_entry(&session_dir, &request, &result);
+            }
             normalization_info.denormalize_result(&mut result);
             return result;
         }
@@ -108,7 +111,7 @@ pub fn process_verification_request<'v, 't: 'v>(
             new_viper_verifier(
                 request.program.get_name(),
                 verification_context,
-                request.backend_config,
+                request.backend_config.clone(),
             ),
             verification_context,
         ),
@@ -127,6 +130,12 @@ pub fn process_verification_request<'v, 't: 'v>(
         cache.insert(hash, result.clone());
     }
 
+    // The request and the result are recorded in their normalized form, which
+    // is also the form in which they are replayed.
+    if let Some(session_dir) = config::record_session() {
+        record_session_entry(&session_dir, &request, &result);
+    }
+
     normalization_info.denormalize_result(&mut result);
     result
 }
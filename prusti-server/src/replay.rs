@@ -0,0 +1,75 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use clap::Parser;
+use once_cell::sync::Lazy;
+use prusti_server::{load_session, process_verification_request};
+use prusti_utils::config;
+use std::path::PathBuf;
+use viper::{PersistentCache, Viper};
+
+/// Replays a verification session recorded with `PRUSTI_RECORD_SESSION`, and
+/// reports the programs whose verification result differs from the recorded one.
+#[derive(Parser, Debug)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    /// The session archive directory, or a single recorded request.
+    #[clap(value_name = "PATH")]
+    path: PathBuf,
+
+    /// Verifies each recorded program this many times.
+    #[clap(long, value_name = "N", default_value_t = 1)]
+    repeat: usize,
+}
+
+fn main() {
+    env_logger::init_from_env(
+        env_logger::Env::new()
+            .filter_or("PRUSTI_LOG", config::log())
+            .write_style_or("PRUSTI_LOG_STYLE", config::log_style()),
+    );
+
+    let args = Args::parse();
+
+    let entries = load_session(&args.path).unwrap_or_else(|err| {
+        eprintln!(
+            "Could not load the session from \"{}\": {}",
+            args.path.display(),
+            err
+        );
+        std::process::exit(2);
+    });
+
+    let viper = Lazy::new(|| Viper::new_with_args(&config::viper_home(), config::extra_jvm_args()));
+    let viper_thread = Lazy::new(|| viper.attach_current_thread());
+
+    let mut mismatches = 0;
+    for (path, entry) in entries {
+        let program_name = entry.request.program.get_name_with_check_mode();
+        for run in 1..=args.repeat {
+            // A fresh cache, so that every run verifies the program again.
+            let mut cache = PersistentCache::load_cache(PathBuf::new());
+            let result =
+                process_verification_request(&viper_thread, entry.request.clone(), &mut cache);
+            if result == entry.result {
+                println!("{program_name} (run {run}): same result as recorded");
+            } else {
+                mismatches += 1;
+                println!(
+                    "{program_name} (run {run}): different result than recorded in \"{}\"",
+                    path.display()
+                );
+                println!("  recorded: {:?}", entry.result);
+                println!("  replayed: {result:?}");
+            }
+        }
+    }
+
+    if mismatches > 0 {
+        println!("{mismatches} replayed verification(s) differ from the recording");
+        std::process::exit(1);
+    }
+}
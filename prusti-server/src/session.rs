@@ -0,0 +1,82 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::VerificationRequest;
+use log::{info, warn};
+use prusti_common::report::log::to_legal_file_name;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use viper::VerificationResult;
+
+/// A verification request recorded together with its result. A session
+/// archive is a directory containing one such entry per JSON file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SessionEntry {
+    pub request: VerificationRequest,
+    pub result: VerificationResult,
+}
+
+/// Stores a verification request and its result in the session archive in
+/// `dir`. Failing to record the session does not fail the verification.
+pub fn record_session_entry(
+    dir: &Path,
+    request: &VerificationRequest,
+    result: &VerificationResult,
+) {
+    let file_name = format!(
+        "{}-{:016x}.json",
+        to_legal_file_name(&request.program.get_name_with_check_mode()),
+        request.get_hash()
+    );
+    let path = dir.join(file_name);
+    let entry = SessionEntry {
+        request: request.clone(),
+        result: result.clone(),
+    };
+    let write_entry = || -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let file = io::BufWriter::new(fs::File::create(&path)?);
+        serde_json::to_writer(file, &entry)?;
+        Ok(())
+    };
+    match write_entry() {
+        Ok(()) => info!("Recorded verification request to \"{}\"", path.display()),
+        Err(err) => warn!(
+            "Failed to record verification request to \"{}\": {}",
+            path.display(),
+            err
+        ),
+    }
+}
+
+/// Loads the entries of the session archive at `path`, which is either the
+/// archive directory or a single recorded entry. The entries of a directory
+/// are sorted by their file name.
+pub fn load_session(path: &Path) -> io::Result<Vec<(PathBuf, SessionEntry)>> {
+    let mut paths = if path.is_dir() {
+        let mut paths = vec![];
+        for dir_entry in fs::read_dir(path)? {
+            let entry_path = dir_entry?.path();
+            if entry_path.extension().map_or(false, |ext| ext == "json") {
+                paths.push(entry_path);
+            }
+        }
+        paths
+    } else {
+        vec![path.to_path_buf()]
+    };
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|entry_path| {
+            let file = io::BufReader::new(fs::File::open(&entry_path)?);
+            let entry = serde_json::from_reader(file)?;
+            Ok((entry_path, entry))
+        })
+        .collect()
+}
@@ -34,6 +34,12 @@ pub struct ViperBackendConfig {
 
 impl ViperBackendConfig {
     pub fn new(backend: VerificationBackend) -> Self {
+        Self::with_assert_timeout(backend, config::assert_timeout())
+    }
+
+    /// Like `new`, but with the given assertion timeout (in milliseconds)
+    /// instead of the configured `ASSERT_TIMEOUT`.
+    pub fn with_assert_timeout(backend: VerificationBackend, assert_timeout: u64) -> Self {
         let mut verifier_args = config::extra_verifier_args();
         match backend {
             VerificationBackend::Silicon => {
@@ -51,7 +57,7 @@ impl ViperBackendConfig {
 
                 verifier_args.extend(vec![
                     "--assertTimeout".to_string(),
-                    config::assert_timeout().to_string(),
+                    assert_timeout.to_string(),
                     "--proverConfigArgs".to_string(),
                     // model.partial changes the default case of functions in counterexamples
                     // to #unspecified
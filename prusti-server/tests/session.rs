@@ -0,0 +1,36 @@
+use prusti_common::vir::*;
+use prusti_server::{load_session, record_session_entry, VerificationRequest, ViperBackendConfig};
+use viper::VerificationResult;
+
+#[test]
+fn record_and_load_session() {
+    let session_dir = std::env::temp_dir().join(format!("prusti-session-{}", std::process::id()));
+
+    let program = Program {
+        name: "dummy".to_string(),
+        backend_types: vec![],
+        domains: vec![],
+        fields: vec![],
+        builtin_methods: vec![],
+        methods: vec![],
+        functions: vec![],
+        viper_predicates: vec![],
+    };
+    let request = VerificationRequest {
+        program: prusti_common::vir::program::Program::Legacy(program),
+        backend_config: ViperBackendConfig::new(
+            prusti_common::config::viper_backend().parse().unwrap(),
+        ),
+    };
+    let result = VerificationResult::ConsistencyErrors(vec!["error".to_string()]);
+    record_session_entry(&session_dir, &request, &result);
+
+    let entries = load_session(&session_dir).expect("Could not load the recorded session");
+    std::fs::remove_dir_all(&session_dir).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    let (_, entry) = &entries[0];
+    assert_eq!(entry.result, result);
+    assert_eq!(entry.request.backend_config, request.backend_config);
+    assert_eq!(entry.request.program.get_name(), request.program.get_name());
+}
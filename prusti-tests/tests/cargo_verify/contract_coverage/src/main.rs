@@ -0,0 +1,16 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[requires(x > 0)] // Not needed by any proof
+#[ensures(result > x)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+fn main() {
+    let mut i = 1;
+    while i < 10 {
+        body_invariant!(0 < i && i < 10);
+        i = increment(i);
+    }
+}
@@ -7,4 +7,7 @@ impl<T> Opt<T> {
     pub fn map<U, F>(self, f: F) -> Opt<U>
     where
         F: FnOnce(T) -> U;
+
+    #[ensures(self.is_some() ==> result.is_some())]
+    pub fn or(self, other: Opt<T>) -> Opt<T>;
 }
@@ -20,6 +20,13 @@ impl<T> Opt<T> {
             Opt::None => Opt::None,
         }
     }
+
+    pub fn or(self, other: Opt<T>) -> Opt<T> {
+        match self {
+            Opt::Some(x) => Opt::Some(x),
+            Opt::None => other,
+        }
+    }
 }
 
 #[trusted]
@@ -10,6 +10,7 @@ use prusti_contracts::*;
 fn main() {
     test_dependency_import();
     test_extern_specs();
+    test_override_extern_specs();
     test_builtin_extern_specs();
 }
 
@@ -36,6 +37,21 @@ fn test_extern_specs() {
     assert!(a_is_some == b.is_some());
 }
 
+// Replaces the specification of `Opt::or` imported from
+// `library_contracts_extern_specs` instead of reporting a duplicate
+#[extern_spec(override)]
+impl<T> Opt<T> {
+    #[ensures(result.is_some() == (self.is_some() || other.is_some()))]
+    pub fn or(self, other: Opt<T>) -> Opt<T>;
+}
+
+fn test_override_extern_specs() {
+    // Verifying that the local extern spec takes priority
+    let a = Opt::None::<i32>;
+    let b = Opt::None;
+    assert!(!a.or(b).is_some());
+}
+
 fn test_builtin_extern_specs() {
     // Verifying extern specs imported from `prusti_contracts`.
     let _ = divide(10).unwrap();
@@ -0,0 +1,7 @@
+//! Verified searching and sorting algorithms, which serve as examples of
+//! specifications for algorithms on slices.
+
+pub mod merge_sort;
+pub mod search;
+pub mod sort;
+pub mod two_pointers;
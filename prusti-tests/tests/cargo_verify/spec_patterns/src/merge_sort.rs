@@ -0,0 +1,106 @@
+use prusti_contracts::*;
+
+/// Exchanges the elements at the indices `i` and `j` of `a` and of the
+/// permutation `p`, and updates its inverse `q` accordingly.
+#[requires(i < a.len() && j < a.len())]
+#[requires(p.len() == a.len() && q.len() == a.len())]
+#[requires(forall(|k: usize| k < p.len() ==> p[k] < q.len()))]
+#[ensures(a.len() == old(a.len()) && p.len() == old(p.len()) && q.len() == old(q.len()))]
+#[ensures(swapped(a, old(&*a), i, j))]
+#[ensures(swapped(p, old(&*p), i, j))]
+#[ensures(q[old(p[i])] == j && q[old(p[j])] == i)]
+#[ensures(forall(|k: usize| (k < q.len() && k != old(p[i]) && k != old(p[j])) ==> q[k] == old(q[k])))]
+pub fn swap_tracked(a: &mut [i32], p: &mut [usize], q: &mut [usize], i: usize, j: usize) {
+    let tmp = a[i];
+    a[i] = a[j];
+    a[j] = tmp;
+    let tmp = p[i];
+    p[i] = p[j];
+    p[j] = tmp;
+    q[p[i]] = i;
+    q[p[j]] = j;
+}
+
+/// Sorts the slice `a` into `out` in ascending order. On return, `out` is `a`
+/// rearranged by the permutation `p`, whose inverse is `q`.
+#[requires(out.len() == a.len() && p.len() == a.len() && q.len() == a.len())]
+#[ensures(out.len() == a.len())]
+#[ensures(sorted_range!(out))]
+#[ensures(permuted_by(out, a, p, q))]
+pub fn merge_sort(a: &[i32], out: &mut [i32], p: &mut [usize], q: &mut [usize]) {
+    let n = a.len();
+    let mut k = 0;
+    while k < n {
+        body_invariant!(k < n && out.len() == n && p.len() == n && q.len() == n);
+        body_invariant!(forall(|i: usize| i < k ==> (out[i] == a[i] && p[i] == i && q[i] == i)));
+        out[k] = a[k];
+        p[k] = k;
+        q[k] = k;
+        k += 1;
+    }
+    sort_range(out, a, p, q, 0, n);
+}
+
+/// Sorts the elements of `a` in the index range `lo..hi`, keeping `a` a
+/// rearrangement of `orig` by `p`.
+#[requires(lo <= hi && hi <= a.len())]
+#[requires(permuted_by(a, orig, p, q))]
+#[ensures(a.len() == old(a.len()) && p.len() == old(p.len()) && q.len() == old(q.len()))]
+#[ensures(permuted_by(a, orig, p, q))]
+#[ensures(sorted_range!(a, lo, hi))]
+#[ensures(forall(|k: usize| (k < a.len() && (k < lo || hi <= k)) ==> a[k] == old(a[k])))]
+fn sort_range(a: &mut [i32], orig: &[i32], p: &mut [usize], q: &mut [usize], lo: usize, hi: usize) {
+    if hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        sort_range(a, orig, p, q, lo, mid);
+        sort_range(a, orig, p, q, mid, hi);
+        merge(a, orig, p, q, lo, mid, hi);
+    }
+}
+
+/// Merges the sorted index ranges `lo..mid` and `mid..hi` of `a` in place.
+/// Every element of the right range is moved to the left by exchanging it
+/// with its predecessor until it is in order, so that each step is a
+/// `swap_tracked` and `p` and `q` follow the elements.
+#[requires(lo <= mid && mid <= hi && hi <= a.len())]
+#[requires(permuted_by(a, orig, p, q))]
+#[requires(sorted_range!(a, lo, mid) && sorted_range!(a, mid, hi))]
+#[ensures(a.len() == old(a.len()) && p.len() == old(p.len()) && q.len() == old(q.len()))]
+#[ensures(permuted_by(a, orig, p, q))]
+#[ensures(sorted_range!(a, lo, hi))]
+#[ensures(forall(|k: usize| (k < a.len() && (k < lo || hi <= k)) ==> a[k] == old(a[k])))]
+#[allow(unused_variables)] // `orig` is only used in the specification
+fn merge(
+    a: &mut [i32],
+    orig: &[i32],
+    p: &mut [usize],
+    q: &mut [usize],
+    lo: usize,
+    mid: usize,
+    hi: usize,
+) {
+    let mut i = mid;
+    while i < hi {
+        body_invariant!(a.len() == old(a.len()) && p.len() == old(p.len()) && q.len() == old(q.len()));
+        body_invariant!(lo <= mid && mid <= i && i < hi && hi <= a.len());
+        body_invariant!(permuted_by(a, orig, p, q));
+        body_invariant!(sorted_range!(a, lo, i));
+        body_invariant!(forall(|k: usize| (k < a.len() && (k < lo || i <= k)) ==> a[k] == old(a[k])));
+        let mut j = i;
+        while lo < j {
+            body_invariant!(a.len() == old(a.len()) && p.len() == old(p.len()) && q.len() == old(q.len()));
+            body_invariant!(lo < j && j <= i && i < hi && hi <= a.len());
+            body_invariant!(permuted_by(a, orig, p, q));
+            body_invariant!(sorted_range!(a, lo, j));
+            body_invariant!(sorted_range!(a, j, i + 1));
+            body_invariant!(forall(|r: usize, s: usize| (lo <= r && r < j && j < s && s <= i) ==> a[r] <= a[s]));
+            body_invariant!(forall(|k: usize| (k < a.len() && (k < lo || i < k)) ==> a[k] == old(a[k])));
+            if a[j - 1] <= a[j] {
+                break;
+            }
+            swap_tracked(a, p, q, j - 1, j);
+            j -= 1;
+        }
+        i += 1;
+    }
+}
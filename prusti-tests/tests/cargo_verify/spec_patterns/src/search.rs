@@ -0,0 +1,65 @@
+use prusti_contracts::*;
+
+/// Returns the index of the first occurrence of `x` in `a`.
+#[ensures(result is Some(i) ==> i < a.len() && a[i] == x)]
+#[ensures(result is Some(i) ==> forall(|k: usize| k < i ==> a[k] != x))]
+#[ensures(result is None ==> forall(|k: usize| k < a.len() ==> a[k] != x))]
+pub fn linear_search(a: &[i32], x: i32) -> Option<usize> {
+    let mut i = 0;
+    while i < a.len() {
+        body_invariant!(i < a.len());
+        body_invariant!(forall(|k: usize| k < i ==> a[k] != x));
+        if a[i] == x {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Returns the index of an occurrence of `x` in the sorted slice `a`.
+#[requires(sorted_range!(a))]
+#[ensures(result is Some(i) ==> i < a.len() && a[i] == x)]
+#[ensures(result is None ==> forall(|k: usize| k < a.len() ==> a[k] != x))]
+pub fn binary_search(a: &[i32], x: i32) -> Option<usize> {
+    let mut lo = 0;
+    let mut hi = a.len();
+    while lo < hi {
+        body_invariant!(lo < hi && hi <= a.len());
+        body_invariant!(forall(|k: usize| k < lo ==> a[k] < x));
+        body_invariant!(forall(|k: usize| (hi <= k && k < a.len()) ==> x < a[k]));
+        let mid = lo + (hi - lo) / 2;
+        if a[mid] < x {
+            lo = mid + 1;
+        } else if x < a[mid] {
+            hi = mid;
+        } else {
+            return Some(mid);
+        }
+    }
+    None
+}
+
+/// Returns the number of elements of the sorted slice `a` that are smaller
+/// than `x`, i.e. the first index at which `x` can be inserted without
+/// breaking the order.
+#[requires(sorted_range!(a))]
+#[ensures(result <= a.len())]
+#[ensures(forall(|k: usize| k < result ==> a[k] < x))]
+#[ensures(forall(|k: usize| (result <= k && k < a.len()) ==> x <= a[k]))]
+pub fn lower_bound(a: &[i32], x: i32) -> usize {
+    let mut lo = 0;
+    let mut hi = a.len();
+    while lo < hi {
+        body_invariant!(lo < hi && hi <= a.len());
+        body_invariant!(forall(|k: usize| k < lo ==> a[k] < x));
+        body_invariant!(forall(|k: usize| (hi <= k && k < a.len()) ==> x <= a[k]));
+        let mid = lo + (hi - lo) / 2;
+        if a[mid] < x {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
@@ -0,0 +1,40 @@
+use prusti_contracts::*;
+
+/// Exchanges the elements at the indices `i` and `j`.
+#[requires(i < a.len() && j < a.len())]
+#[ensures(swapped(a, old(&*a), i, j))]
+pub fn swap(a: &mut [i32], i: usize, j: usize) {
+    let tmp = a[i];
+    a[i] = a[j];
+    a[j] = tmp;
+}
+
+/// Sorts the slice `a` in ascending order.
+#[ensures(a.len() == old(a.len()))]
+#[ensures(sorted_range!(a))]
+pub fn insertion_sort(a: &mut [i32]) {
+    let mut i = 1;
+    while i < a.len() {
+        body_invariant!(a.len() == old(a.len()));
+        body_invariant!(1 <= i && i < a.len());
+        body_invariant!(sorted_range!(a, 0, i));
+        // Move `a[i]` to the left until it is not smaller than its
+        // predecessor. The element at `j` is the one being moved, so the
+        // elements before and after it are sorted, and all elements before
+        // it are smaller than or equal to the elements after it.
+        let mut j = i;
+        while 0 < j {
+            body_invariant!(a.len() == old(a.len()));
+            body_invariant!(0 < j && j <= i && i < a.len());
+            body_invariant!(sorted_range!(a, 0, j));
+            body_invariant!(sorted_range!(a, j, i + 1));
+            body_invariant!(forall(|p: usize, q: usize| (p < j && j < q && q <= i) ==> a[p] <= a[q]));
+            if a[j - 1] <= a[j] {
+                break;
+            }
+            swap(a, j - 1, j);
+            j -= 1;
+        }
+        i += 1;
+    }
+}
@@ -0,0 +1,73 @@
+use prusti_contracts::*;
+
+/// Returns the indices of two elements of the sorted slice `a` whose sum is
+/// `target`.
+#[requires(sorted_range!(a))]
+#[requires(forall(|k: usize| k < a.len() ==> a[k] <= 1_000_000))]
+#[ensures(result is Some((i, j)) ==> i < j && j < a.len() && a[i] + a[j] == target)]
+#[ensures(result is None ==>
+    forall(|p: usize, q: usize| (p < q && q < a.len()) ==> a[p] + a[q] != target))]
+pub fn pair_with_sum(a: &[u32], target: u32) -> Option<(usize, usize)> {
+    if a.len() < 2 {
+        return None;
+    }
+    let mut i = 0;
+    let mut j = a.len() - 1;
+    while i < j {
+        body_invariant!(i < j && j < a.len());
+        // The pairs with an element left of `i` or right of `j` were ruled out.
+        body_invariant!(forall(|p: usize, q: usize| (p < i && p < q && q < a.len()) ==> a[p] + a[q] != target));
+        body_invariant!(forall(|p: usize, q: usize| (j < q && q < a.len() && p < q) ==> a[p] + a[q] != target));
+        let sum = a[i] + a[j];
+        if sum == target {
+            return Some((i, j));
+        } else if sum < target {
+            i += 1;
+        } else {
+            j -= 1;
+        }
+    }
+    None
+}
+
+/// Reverses the order of the elements of `a`.
+#[ensures(a.len() == old(a.len()))]
+#[ensures(forall(|k: usize| k < a.len() ==> a[k] == old(a[a.len() - 1 - k])))]
+pub fn reverse(a: &mut [i32]) {
+    if a.len() < 2 {
+        return;
+    }
+    let mut i = 0;
+    let mut j = a.len() - 1;
+    while i < j {
+        body_invariant!(a.len() == old(a.len()));
+        body_invariant!(i < j && j < a.len() && i + j == a.len() - 1);
+        body_invariant!(forall(|k: usize| (k < i || (j < k && k < a.len())) ==> a[k] == old(a[a.len() - 1 - k])));
+        body_invariant!(forall(|k: usize| (i <= k && k <= j) ==> a[k] == old(a[k])));
+        let tmp = a[i];
+        a[i] = a[j];
+        a[j] = tmp;
+        i += 1;
+        j -= 1;
+    }
+}
+
+/// Checks whether `a` reads the same forwards and backwards.
+#[ensures(result == forall(|k: usize| k < a.len() ==> a[k] == a[a.len() - 1 - k]))]
+pub fn is_palindrome(a: &[i32]) -> bool {
+    if a.len() < 2 {
+        return true;
+    }
+    let mut i = 0;
+    let mut j = a.len() - 1;
+    while i < j {
+        body_invariant!(i < j && j < a.len() && i + j == a.len() - 1);
+        body_invariant!(forall(|k: usize| (k < i || (j < k && k < a.len())) ==> a[k] == a[a.len() - 1 - k]));
+        if a[i] != a[j] {
+            return false;
+        }
+        i += 1;
+        j -= 1;
+    }
+    true
+}
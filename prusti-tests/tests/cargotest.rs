@@ -4,7 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use cargo_test_support::{cargo_test, project, symlink_supported};
+use cargo_test_support::{cargo_test, project, symlink_supported, Project};
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -78,6 +78,69 @@ error: could not compile `foo` (bin \"foo\") due to previous error; 1 warning em
         .run();
 }
 
+/// Read the file in `dir` whose name ends with `suffix`, which is unique.
+fn read_file_with_suffix(dir: &Path, suffix: &str) -> String {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|_| panic!("Failed to read directory {}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(suffix))
+        .collect();
+    assert_eq!(
+        paths.len(),
+        1,
+        "expected one file *{suffix} in {}",
+        dir.display()
+    );
+    let path = paths.pop().unwrap();
+    fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Failed to read the file {}", path.display()))
+}
+
+#[cargo_test]
+fn html_report() {
+    let p = project()
+        .file(
+            "src/main.rs",
+            "\
+fn verified() { assert!(true); }
+fn failing() { assert!(false); }
+fn main() {}
+",
+        )
+        // The data of an earlier run is removed.
+        .file("target/verify/report/data/old.verification.json", "{}")
+        .build();
+    p.process(cargo_prusti_path())
+        .env("PRUSTI_HTML_REPORT", "true")
+        .with_status(101)
+        .run();
+
+    let report_dir = p.root().join("target/verify/report");
+    let data_dir = report_dir.join("data");
+    assert!(!data_dir.join("old.verification.json").exists());
+    let data = read_file_with_suffix(&data_dir, ".verification.json");
+    assert!(data.contains("\"function\": \"verified\""), "{data}");
+    assert!(data.contains("\"function\": \"failing\""), "{data}");
+    assert!(data.contains("\"status\": \"failed\""), "{data}");
+    assert!(
+        data.contains("the asserted expression might not hold"),
+        "{data}"
+    );
+    assert!(data.contains("src/main.rs:2:1"), "{data}");
+
+    let html = fs::read_to_string(report_dir.join("index.html")).unwrap();
+    assert!(html.contains("<h2>Crate <code>foo</code></h2>"), "{html}");
+    assert!(
+        html.contains("<p>2 verified, 1 failed, 0 not verified.</p>"),
+        "{html}"
+    );
+    assert!(html.contains("<code>failing</code> (1 error)"), "{html}");
+    assert!(
+        html.contains("error: [Prusti: verification error] the asserted expression might not hold"),
+        "{html}"
+    );
+}
+
 /// Test `cargo-prusti` on one of the crates in `test/cargo_verify`.
 ///
 /// Special files and folders in the root of the test crate:
@@ -91,14 +154,42 @@ error: could not compile `foo` (bin \"foo\") due to previous error; 1 warning em
 /// For more details on the special syntax allowed in the `output.*` files, check the documentation
 /// of `cargo_test_support`: <https://doc.crates.io/contrib/tests/writing.html>.
 fn test_local_project<T: Into<PathBuf>>(project_name: T) {
-    let mut project_builder = project().no_manifest();
+    let project_path = local_project_path(project_name);
+    let project = build_local_project(&project_path);
+
+    // Set the expected exit status, stdout and stderr
+    let mut test_builder = project.process(cargo_prusti_path());
+    test_builder.arg("--quiet");
+    let opt_expected_stdout = fs::read_to_string(project_path.join("output.stdout")).ok();
+    let opt_expected_stderr = fs::read_to_string(project_path.join("output.stderr")).ok();
+    if let Some(ref expected_stdout) = opt_expected_stdout {
+        // In some cases, Prusti outputs more macro definitions than needed.
+        // See: https://github.com/viperproject/prusti-dev/pull/762
+        test_builder.with_stdout_contains(expected_stdout);
+    }
+    if let Some(ref expected_stderr) = opt_expected_stderr {
+        test_builder.with_status(101).with_stderr(expected_stderr);
+    }
+
+    // Run the test
+    test_builder.run();
+}
+
+/// Get the canonical path of one of the crates in `test/cargo_verify`.
+fn local_project_path<T: Into<PathBuf>>(project_name: T) -> PathBuf {
     let relative_project_path = Path::new("tests/cargo_verify").join(project_name.into());
-    let project_path = fs::canonicalize(&relative_project_path).unwrap_or_else(|_| {
+    fs::canonicalize(&relative_project_path).unwrap_or_else(|_| {
         panic!(
             "Failed to canonicalize the path {}",
             relative_project_path.display()
         )
-    });
+    })
+}
+
+/// Create a test project linking to the crate at `project_path` and to the
+/// Prusti crates, and fetch its dependencies.
+fn build_local_project(project_path: &Path) -> Project {
+    let mut project_builder = project().no_manifest();
 
     // Populate the test project with symlinks to the local project
     let project_path_content = fs::read_dir(&project_path)
@@ -147,23 +238,7 @@ fn test_local_project<T: Into<PathBuf>>(project_name: T) {
         .arg("build")
         .env("CARGO_TARGET_DIR", "target/verify")
         .run();
-
-    // Set the expected exit status, stdout and stderr
-    let mut test_builder = project.process(cargo_prusti_path());
-    test_builder.arg("--quiet");
-    let opt_expected_stdout = fs::read_to_string(project_path.join("output.stdout")).ok();
-    let opt_expected_stderr = fs::read_to_string(project_path.join("output.stderr")).ok();
-    if let Some(ref expected_stdout) = opt_expected_stdout {
-        // In some cases, Prusti outputs more macro definitions than needed.
-        // See: https://github.com/viperproject/prusti-dev/pull/762
-        test_builder.with_stdout_contains(expected_stdout);
-    }
-    if let Some(ref expected_stderr) = opt_expected_stderr {
-        test_builder.with_status(101).with_stderr(expected_stderr);
-    }
-
-    // Run the test
-    test_builder.run();
+    project
 }
 
 #[cargo_test]
@@ -175,6 +250,35 @@ fn test_symlinks() {
 // TODO: automatically create a test for each folder in `test/cargo_verify`.
 // Each of the following functions, listed in alphabetic order, test a crate in `cargo_verify/`.
 
+#[cargo_test]
+fn test_contract_coverage() {
+    let project = build_local_project(&local_project_path("contract_coverage"));
+    let report_dir = project.root().join("target/verify/report-data");
+    // The repeated verifications fail without the needed clauses, but their
+    // errors are not reported.
+    project
+        .process(cargo_prusti_path())
+        .arg("--quiet")
+        .env("PRUSTI_VERIFICATION_REPORT", &report_dir)
+        .env("PRUSTI_CONTRACT_COVERAGE", "true")
+        .run();
+    let report = read_file_with_suffix(&report_dir, ".verification.json");
+    // The fields of every clause are in alphabetic order, so `used` is the
+    // first one after the clause itself.
+    let is_used = |clause: &str| {
+        let start = report
+            .find(&format!(r#""clause": "{clause}""#))
+            .unwrap_or_else(|| panic!("missing clause {clause}: {report}"));
+        let used = start + report[start..].find(r#""used": "#).unwrap();
+        report[used..].starts_with(r#""used": true"#)
+    };
+    assert!(is_used("x < 100"), "{report}");
+    assert!(!is_used("x > 0"), "{report}");
+    assert!(is_used("result > x"), "{report}");
+    assert!(is_used("0 < i && i < 10"), "{report}");
+    assert!(report.contains(r#""kind": "loop_invariant""#), "{report}");
+}
+
 #[cargo_test]
 fn test_failing_crate() {
     test_local_project("failing_crate");
@@ -226,6 +330,44 @@ fn test_prusti_toml_fail() {
     }
 }
 
+#[cargo_test]
+fn test_spec_patterns() {
+    test_local_project("spec_patterns");
+}
+
+#[cargo_test]
+fn test_trust_audit() {
+    let project = build_local_project(&local_project_path("trust_audit"));
+    project
+        .process(cargo_prusti_path())
+        .arg("--quiet")
+        .arg("--trust-audit-report")
+        .run();
+    let report = read_file_with_suffix(
+        &project.root().join("target/verify/trust-audit"),
+        ".trusted.json",
+    );
+    // The fields of every item are checked separately, since their order is
+    // not specified.
+    let expected_fields = [
+        r#""item": "xor_swap""#,
+        r#""reason": "XOR operations are not supported""#,
+        r#""location": "src/main.rs:5:1""#,
+        r#""item": "positive""#,
+        r#""reason": null"#,
+        r#""location": "src/main.rs:13:1""#,
+        r#""signature": "fn() -> i32""#,
+        r#""item": "Buffer""#,
+        r#""kind": "type""#,
+        r#""reason": "the buffer is managed by the operating system""#,
+        r#""item": "external::answer""#,
+        r#""kind": "extern_spec""#,
+    ];
+    for expected_field in expected_fields {
+        assert!(report.contains(expected_field), "{report}");
+    }
+}
+
 // `#![no_std]` binaries on Windows are not a thing yet,
 // see <https://github.com/viperproject/prusti-dev/pull/762>.
 #[cfg_attr(windows, ignore)]
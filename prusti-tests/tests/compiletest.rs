@@ -12,7 +12,11 @@
 use compiletest_rs::{common::Mode, run_tests, Config};
 use log::{error, info};
 use prusti_server::spawn_server_thread;
-use std::{env, path::PathBuf};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 fn find_prusti_rustc_path() -> PathBuf {
     let target_directory = if cfg!(debug_assertions) {
@@ -106,11 +110,135 @@ fn run_prusti_tests(group_name: &str, filter: &Option<String>, rustc_flags: Opti
     let path: PathBuf = ["tests", group_name, "fail"].iter().collect();
     if path.exists() {
         config.mode = Mode::CompileFail;
-        config.src_base = path;
+        config.src_base = path.clone();
         run_tests(&config);
+        check_counterexample_snapshots(&path, filter, config.target_rustcflags.as_deref());
+    }
+}
+
+/// Collects the paths of all files with the given extension in `dir` and its
+/// subdirectories.
+fn collect_files(dir: &Path, extension: &str, files: &mut Vec<PathBuf>) {
+    let mut entries = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, extension, files);
+        } else if path.extension().map_or(false, |ext| ext == extension) {
+            files.push(path);
+        }
     }
 }
 
+/// Extracts the counterexample notes from the output of Prusti, normalizing
+/// the paths of the reported locations and the indentation that depends on
+/// the width of the line numbers. The continuation lines of a note are
+/// indented by the width of the line numbers, so their common indentation,
+/// which is the one of the first continuation line, is replaced by four spaces.
+fn extract_counterexamples(stderr: &str, test_dir: &Path) -> String {
+    let test_dir = test_dir.to_string_lossy();
+    let mut snapshot = String::new();
+    let mut in_counterexample = false;
+    let mut indentation: Option<String> = None;
+    for line in stderr.lines() {
+        let line = line.trim_end();
+        if line.starts_with("note: counterexample for") {
+            in_counterexample = true;
+            indentation = None;
+            snapshot.push_str(line);
+            snapshot.push('\n');
+            continue;
+        } else if line.starts_with("error") || line.starts_with("note:") || line.is_empty() {
+            in_counterexample = false;
+        }
+        if !in_counterexample {
+            continue;
+        }
+        if let Some(location) = line.trim_start().strip_prefix("--> ") {
+            snapshot.push_str("--> ");
+            snapshot.push_str(&location.replace(test_dir.as_ref(), "$DIR"));
+            snapshot.push('\n');
+            in_counterexample = false;
+        } else {
+            let indentation = indentation
+                .get_or_insert_with(|| line.chars().take_while(|c| c.is_whitespace()).collect());
+            snapshot.push_str("    ");
+            snapshot.push_str(
+                line.strip_prefix(indentation.as_str())
+                    .unwrap_or(line.trim_start()),
+            );
+            snapshot.push('\n');
+        }
+    }
+    snapshot
+}
+
+/// Checks the counterexamples reported for the fail tests in `fail_dir`
+/// against golden files. A test `foo.rs` is checked if there is a file
+/// `foo.counterexample` next to it, which contains the expected counterexample
+/// notes. When the `BLESS_COUNTEREXAMPLES` environment variable is set, the
+/// golden files are overwritten with the actual counterexamples instead.
+fn check_counterexample_snapshots(
+    fail_dir: &Path,
+    filter: &Option<String>,
+    rustc_flags: Option<&str>,
+) {
+    let bless = env::var_os("BLESS_COUNTEREXAMPLES").is_some();
+    let out_dir = env::temp_dir().join("prusti-counterexample-tests");
+    fs::create_dir_all(&out_dir).unwrap();
+    let mut golden_files = Vec::new();
+    collect_files(fail_dir, "counterexample", &mut golden_files);
+    let mut mismatches = Vec::new();
+    for golden_file in golden_files {
+        let test_file = golden_file.with_extension("rs");
+        if let Some(filter) = filter {
+            if !test_file.to_string_lossy().contains(filter.as_str()) {
+                continue;
+            }
+        }
+        let source = fs::read_to_string(&test_file)
+            .unwrap_or_else(|err| panic!("Failed to read {}: {err}", test_file.display()));
+        let compile_flags = source
+            .lines()
+            .filter_map(|line| line.strip_prefix("// compile-flags:"))
+            .flat_map(str::split_whitespace)
+            .collect::<Vec<_>>();
+        println!("counterexample snapshot {}", test_file.display());
+        let output = Command::new(find_prusti_rustc_path())
+            .arg(&test_file)
+            .args(rustc_flags.unwrap_or_default().split_whitespace())
+            .args(compile_flags)
+            .arg("--out-dir")
+            .arg(&out_dir)
+            .env("PRUSTI_COUNTEREXAMPLE", "true")
+            .output()
+            .expect("Failed to run prusti-rustc");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let actual = extract_counterexamples(&stderr, test_file.parent().unwrap());
+        if bless {
+            fs::write(&golden_file, &actual).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&golden_file).unwrap();
+        if expected != actual {
+            error!(
+                "Counterexamples of {} differ from {}.\n--- expected\n{expected}--- actual\n{actual}",
+                test_file.display(),
+                golden_file.display(),
+            );
+            mismatches.push(test_file);
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "Unexpected counterexamples in {mismatches:?}; rerun with BLESS_COUNTEREXAMPLES=1 \
+        to update the golden files."
+    );
+}
+
 fn run_no_verification(group_name: &str, filter: &Option<String>) {
     let _temporary_env_vars = (
         TemporaryEnvVar::set("PRUSTI_FULL_COMPILATION", "true"),
@@ -0,0 +1,8 @@
+use prusti_contracts::*;
+
+#[pure]
+async fn zero() -> u32 { //~ ERROR async functions cannot be pure
+    0
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use prusti_contracts::*;
+
+#[invariant(self.f |= |x: i32| [requires(x >= 0)])]
+struct FnPointer {
+    f: fn(i32) -> i32, //~ ERROR specification entailments are only supported for fields of type `Box<dyn Fn(..) -> ..>`
+}
+
+#[invariant(self.g |= |x: i32| [requires(x >= 0)])] //~ ERROR `g` is not a field of `Missing`
+struct Missing {
+    f: Box<dyn Fn(i32) -> i32>,
+}
+
+fn main() {}
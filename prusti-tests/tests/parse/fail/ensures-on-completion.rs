@@ -0,0 +1,8 @@
+use prusti_contracts::*;
+
+#[ensures_on_completion(result == 0)]
+fn zero() -> u32 { //~ ERROR `ensures_on_completion` can only be used on async functions and on functions returning a future with a known `Output` type
+    0
+}
+
+fn main() {}
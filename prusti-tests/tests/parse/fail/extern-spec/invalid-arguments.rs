@@ -0,0 +1,9 @@
+use prusti_contracts::*;
+
+#[extern_spec(core::mem, overrides)] //~ ERROR: expected `override`
+fn swap<T>(a: &mut T, b: &mut T);
+
+#[extern_spec(override, core::mem)] //~ ERROR: expected a module path and/or `override`
+fn swap<T>(a: &mut T, b: &mut T);
+
+fn main() {}
@@ -8,7 +8,7 @@ trait MyTrait {
 
 #[extern_spec]
 trait MyTrait {
-    type Item = i32; //~ ERROR: Associated types in external trait specs should not be declared
+    type Item = i32; //~ ERROR: Associated types in external trait specs should not have a default
 
     #[ensures(result == 42)]
     fn foo(&self) -> Option<Self::Item>;
@@ -0,0 +1,8 @@
+use prusti_contracts::*;
+
+#[ensures(result is ==> true)] //~ ERROR expected pattern after `is`
+fn f() -> Option<u32> {
+    None
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use prusti_contracts::*;
+
+trait Monoid: Copy {
+    #[pure]
+    fn op(self, other: Self) -> Self;
+
+    #[law]
+    fn returns_value(a: Self) -> bool; //~ ERROR a law must not return a value
+
+    #[law]
+    fn mutates(a: &mut Self); //~ ERROR a law must not take mutable references
+
+    #[law]
+    fn has_default(a: Self) {} //~ ERROR a law must not have a default implementation
+}
+
+#[law]
+fn not_a_method() {} //~ ERROR the `#[law]` attribute can only be used on methods of traits and their implementations
+
+fn main() {}
@@ -0,0 +1,19 @@
+use prusti_contracts::*;
+
+fn missing_name(n: u32) {
+    let mut i = 0;
+    while i < n {
+        no_invariant_needed!(3); //~ ERROR expected `no_invariant_needed!` to have the form `no_invariant_needed!(bound = K)`
+        i += 1;
+    }
+}
+
+fn wrong_name(n: u32) {
+    let mut i = 0;
+    while i < n {
+        no_invariant_needed!(limit = 3); //~ ERROR expected `no_invariant_needed!` to have the form `no_invariant_needed!(bound = K)`
+        i += 1;
+    }
+}
+
+fn main() {}
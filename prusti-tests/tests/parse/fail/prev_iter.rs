@@ -0,0 +1,12 @@
+use prusti_contracts::*;
+
+#[ensures(result >= prev_iter(x))] //~ ERROR `prev_iter(..)` can only be used in loop invariants
+fn postcondition(x: i32) -> i32 {
+    x
+}
+
+fn assertion(x: i32) {
+    prusti_assert!(x == prev_iter(x)); //~ ERROR `prev_iter(..)` can only be used in loop invariants
+}
+
+fn main() {}
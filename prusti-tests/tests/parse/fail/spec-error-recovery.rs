@@ -0,0 +1,38 @@
+use prusti_contracts::*;
+
+// Errors in specifications should not hide the items they are attached to,
+// so that the code using these items does not report further errors.
+
+#[requires(x >)] //~ ERROR expected expression
+fn f(x: u32) -> u32 {
+    x
+}
+
+#[invariant(self.value >)] //~ ERROR expected expression
+struct Counter {
+    value: u32,
+}
+
+trait Trait {
+    fn get(&self) -> u32;
+    fn set(&mut self, value: u32);
+}
+
+#[refine_trait_spec]
+impl Trait for Counter {
+    #[ensures(result ==)] //~ ERROR expected expression
+    fn get(&self) -> u32 {
+        self.value
+    }
+
+    #[ensures(self.get() == value)]
+    fn set(&mut self, value: u32) {
+        self.value = value;
+    }
+}
+
+fn main() {
+    let mut counter = Counter { value: f(1) };
+    counter.set(2);
+    assert!(counter.get() == 2);
+}
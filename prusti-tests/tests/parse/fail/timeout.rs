@@ -0,0 +1,9 @@
+use prusti_contracts::*;
+
+#[timeout(fast)] //~ ERROR expected the `#[timeout]` attribute to have the form `#[timeout(ms)]`
+fn test1() {}
+
+#[timeout] //~ ERROR expected the `#[timeout]` attribute to have the form `#[timeout(ms)]`
+fn test2() {}
+
+fn main() {}
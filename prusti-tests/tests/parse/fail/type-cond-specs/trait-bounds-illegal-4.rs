@@ -0,0 +1,11 @@
+use prusti_contracts::*;
+
+#[trusted]
+#[refine_spec(where Vec<T> = Vec<u32>, [ //~ ERROR: only generic parameters can be constrained to be equal to a type
+    ensures(true)
+])]
+fn foo<T>(_x: Vec<T>) {
+}
+
+fn main() {
+}
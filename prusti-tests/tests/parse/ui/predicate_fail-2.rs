@@ -27,10 +27,11 @@ predicate! {
     }
 }
 
-// parsing is different depending on the 'outermost' predicate, so a test the other way around
-// makes sense as well
+// each conflicting attribute is reported, while `#[trusted]` is allowed
 predicate! {
     #[trusted]
+    #[requires(true)]
+    #[ensures(result)]
     fn other_thing() -> bool {
         false
     }
@@ -0,0 +1,57 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+const fn inc(x: u32) -> u32 {
+    x + 1
+}
+
+struct Buffer<const N: usize> {
+    data: [u8; N],
+}
+
+impl<const N: usize> Buffer<N> {
+    #[ensures(result == N)]
+    const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[requires(i < N)]
+    #[ensures(result == self.data[i])]
+    fn get(&self, i: usize) -> u8 {
+        self.data[i]
+    }
+}
+
+trait Capacity {
+    fn capacity(&self) -> usize;
+}
+
+#[refine_trait_spec]
+impl<const N: usize> Capacity for Buffer<N> {
+    #[ensures(result == N)]
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+#[extern_spec]
+impl<T> std::option::Option<T> {
+    #[pure]
+    #[ensures(matches!(*self, Some(_)) == result)]
+    pub const fn is_some(&self) -> bool;
+
+    #[requires(self.is_some())]
+    pub const fn unwrap(self) -> T;
+}
+
+const FOUR: u32 = inc(3);
+
+fn main() {
+    assert!(inc(41) == 42);
+    let buffer = Buffer { data: [1, 2, 3] };
+    assert!(buffer.capacity() == 3);
+    assert!(buffer.get(1) == 2);
+    assert!(Capacity::capacity(&buffer) == 3);
+    let _ = Some(FOUR).unwrap();
+}
@@ -0,0 +1,14 @@
+use prusti_contracts::*;
+
+fn zero_divisor(a: u32) {
+    lemma_div_mod!(a, 0); //~ ERROR precondition might not hold
+}
+
+#[requires(b > 0)]
+#[ensures(result == a)] //~ ERROR postcondition might not hold
+fn wrong_cancellation(a: u32, b: u32) -> u32 {
+    lemma_mul_div!(a, b);
+    (a * b) / (b + 1)
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(forall(|r: usize| r < m.len() ==> m[r].len() == cols))]
+#[requires(i < m.len() && j <= cols)]
+fn get_off_by_one(m: &[&[i32]], cols: usize, i: usize, j: usize) -> i32 {
+    m[i][j] //~ ERROR the array or slice index may be out of bounds
+}
+
+#[requires(forall(|r: usize| r + 1 < m.len() ==> m[r].len() == cols))]
+#[requires(i < m.len() && j < cols)]
+fn get_last_row(m: &[&[i32]], cols: usize, i: usize, j: usize) -> i32 {
+    m[i][j] //~ ERROR the array or slice index may be out of bounds
+}
@@ -0,0 +1,16 @@
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(a.len() > 2)]
+#[ensures(a.contains(&7))] //~ ERROR postcondition might not hold
+fn contains_spec(a: &[i32]) {}
+
+#[requires(a.starts_with(b))]
+#[ensures(a.ends_with(b))] //~ ERROR postcondition might not hold
+fn starts_with_spec(a: &[u8], b: &[u8]) {}
+
+#[requires(a.len() == 3 && a[0] == 1 && a[1] == 2 && a[2] == 3)]
+fn call_queries(a: &[i32]) {
+    assert!(a.contains(&4)); //~ ERROR the asserted expression might not hold
+}
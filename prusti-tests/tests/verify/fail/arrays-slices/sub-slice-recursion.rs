@@ -0,0 +1,28 @@
+use prusti_contracts::*;
+
+#[ensures(s.len() == old(s.len()))]
+#[ensures(forall(|i: usize| i < s.len() ==> s[i] == 0))] //~ ERROR postcondition might not hold
+fn zero_tail(s: &mut [u32]) {
+    if s.len() > 1 {
+        zero_tail(&mut s[1..]);
+    }
+}
+
+#[requires(s.len() > 1)]
+#[ensures(s.len() == old(s.len()))]
+#[ensures(s[1] == 0)] //~ ERROR postcondition might not hold
+fn zero_first_through_tails(s: &mut [u32]) {
+    let len = s.len();
+    let mut k = 0;
+    while k < len {
+        body_invariant!(k < len && s.len() == len);
+        let tail = &mut s[k..];
+        // Only the first element of the first tail is written
+        if k == 0 {
+            tail[0] = 0;
+        }
+        k += 1;
+    }
+}
+
+fn main() {}
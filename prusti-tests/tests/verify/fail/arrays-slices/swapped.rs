@@ -0,0 +1,13 @@
+use prusti_contracts::*;
+
+#[requires(i < a.len() && j < a.len())]
+#[ensures(swapped(a, old(&*a), i, j))] //~ ERROR postcondition might not hold
+fn not_a_swap(a: &mut [i32], i: usize, j: usize) {
+    a[i] = a[j];
+}
+
+#[requires(a.len() == b.len())]
+#[ensures(permutation_of(a, b))] //~ ERROR postcondition might not hold
+fn arbitrary(a: &[i32], b: &[i32]) {}
+
+fn main() {}
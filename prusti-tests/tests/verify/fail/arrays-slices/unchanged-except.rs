@@ -0,0 +1,10 @@
+use prusti_contracts::*;
+
+#[requires(i + 1 < a.len())]
+#[ensures(unchanged_except!(a, i))] //~ ERROR postcondition might not hold
+fn set_two(a: &mut [i32], i: usize, value: i32) {
+    a[i] = value;
+    a[i + 1] = value;
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[requires(i < v.len())]
+#[ensures(result == v[i])]
+#[trusted]
+fn get(v: &Vec<u32>, i: usize) -> u32 {
+    v[i]
+}
+
+#[requires(v.len() >= 1)]
+#[ensures(result < 100)] //~ ERROR postcondition might not hold
+fn first(v: &Vec<u32>) -> u32 {
+    get(v, 0)
+}
+
+#[pure]
+fn get_unchecked(v: &Vec<u32>, i: usize) -> u32 {
+    v[i] //~ ERROR the array or slice index may be out of bounds
+}
+
+#[pure]
+#[requires(i <= j)]
+fn sum_range_unchecked(v: &Vec<u32>, i: usize, j: usize) -> usize {
+    v[i..j].len() //~ ERROR the range end value may be out of bounds when slicing
+}
+
+#[requires(v.len() > 3)]
+#[ensures(get_unchecked(v, 3) == v[3])]
+#[ensures(sum_range_unchecked(v, 1, 2) == 1)]
+fn uses_unchecked(v: &Vec<u32>) {}
+
+#[requires(v.len() > 3)]
+#[ensures(v[i] == v[i])] //~ ERROR postcondition might not hold
+fn index_out_of_bounds(v: &Vec<u32>, i: usize) {}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use prusti_contracts::*;
+
+#[ensures(result == x + 1)] //~ ERROR postcondition might not hold
+async fn increment(x: u32) -> u32 {
+    x + 2
+}
+
+async fn divide(a: u32, b: u32) -> u32 {
+    a / b //~ ERROR assertion might fail with "attempt to divide by zero"
+}
+
+#[requires(b != 0)]
+async fn divide_checked(a: u32, b: u32) -> u32 {
+    a / b
+}
+
+fn main() {}
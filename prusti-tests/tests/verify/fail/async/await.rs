@@ -0,0 +1,38 @@
+use core::future::Future;
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+async fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+async fn wrong_assertion(x: u32) {
+    if x < 10 {
+        let y = increment(x).await;
+        assert!(y == x); //~ ERROR the asserted expression might not hold
+    }
+}
+
+async fn missing_precondition(x: u32) -> u32 {
+    increment(x).await //~ ERROR precondition might not hold
+}
+
+#[ensures_on_completion(result > 0)] //~ ERROR postcondition might not hold
+async fn zero() -> i32 {
+    0
+}
+
+#[trusted]
+#[ensures_on_completion(result == 1)]
+fn one() -> impl Future<Output = i32> {
+    async { 1 }
+}
+
+async fn stored_future() {
+    let future = one();
+    let x = future.await; //~ ERROR only futures that are awaited immediately after being returned by a call are supported
+    assert!(x == 1);
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use core::future::Future;
+use prusti_contracts::*;
+
+// The output of the returned future is not verified.
+#[ensures_on_completion(result == 2)]
+fn two() -> impl Future<Output = i32> { //~ ERROR postconditions on completion can only be verified for async functions
+    async { 3 }
+}
+
+async fn client() {
+    let x = two().await;
+    assert!(x == 2);
+}
+
+fn main() {}
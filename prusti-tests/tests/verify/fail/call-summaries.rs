@@ -0,0 +1,35 @@
+use prusti_contracts::*;
+
+#[ensures(result >= x)]
+fn helper(x: u32) -> u32 {
+    x
+}
+
+// `helper` is not called on every path
+#[ensures(result == call_summary!(helper(x)))] //~ ERROR postcondition might not hold
+fn maybe_helper(x: u32, b: bool) -> u32 {
+    if b {
+        helper(x)
+    } else {
+        x
+    }
+}
+
+// The last call of `helper` has a different argument
+#[ensures(result == call_summary!(helper(x)))] //~ ERROR postcondition might not hold
+fn other_helper(x: u32) -> u32 {
+    let y = helper(x);
+    helper(0);
+    y
+}
+
+#[ensures(result == call_summary!(helper(x)))]
+fn wrapper(x: u32) -> u32 {
+    helper(x)
+}
+
+fn main() {
+    let a = wrapper(3);
+    assert!(a >= 3);
+    assert!(a == 3); //~ ERROR the asserted expression might not hold
+}
@@ -0,0 +1,30 @@
+use prusti_contracts::*;
+
+#[invariant(self.f |= |x: i32| [requires(x >= 0), ensures(result > x)])]
+struct Callbacks {
+    f: Box<dyn Fn(i32) -> i32>,
+}
+
+fn apply_negative(callbacks: &Callbacks) -> i32 {
+    (callbacks.f)(-1) //~ ERROR precondition might not hold
+}
+
+#[requires(x >= 0)]
+#[ensures(result > 10)] //~ ERROR postcondition might not hold
+fn apply(callbacks: &Callbacks, x: i32) -> i32 {
+    (callbacks.f)(x)
+}
+
+fn wrap(f: Box<dyn Fn(i32) -> i32>) -> Callbacks {
+    Callbacks { f } //~ ERROR the callable stored in the field `f` cannot be checked against the contract declared by the type invariant
+}
+
+fn replace(callbacks: &mut Callbacks, f: Box<dyn Fn(i32) -> i32>) {
+    callbacks.f = f; //~ ERROR the callable stored in the field `f` cannot be checked against the contract declared by the type invariant
+}
+
+fn swap(callbacks: &mut Callbacks, f: &mut Box<dyn Fn(i32) -> i32>) {
+    std::mem::swap(&mut callbacks.f, f); //~ ERROR the callable stored in the field `f` cannot be checked against the contract declared by the type invariant
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use prusti_contracts::*;
+
+trait Sensor {
+    #[ensures(result <= 100)]
+    fn read(&self) -> u32;
+}
+
+struct Fixed;
+
+#[refine_trait_spec]
+impl Sensor for Fixed {
+    // Disabled: `any()` never holds
+    #[cfg_attr(any(), ensures(result == 42))]
+    fn read(&self) -> u32 {
+        42
+    }
+}
+
+// Enabled: `all()` always holds
+#[cfg_attr(all(), requires(x > 0))]
+fn positive(x: u32) {}
+
+fn main() {
+    assert!(Fixed.read() == 42); //~ ERROR the asserted expression might not hold
+    positive(0); //~ ERROR precondition might not hold
+}
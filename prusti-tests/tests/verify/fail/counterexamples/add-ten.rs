@@ -0,0 +1,9 @@
+use prusti_contracts::*;
+
+#[requires(x == 11)]
+#[ensures(result > 30)] //~ ERROR postcondition might not hold
+fn add_ten(x: i32) -> i32 {
+    x + 10
+}
+
+fn main() {}
@@ -0,0 +1,95 @@
+use prusti_contracts::*;
+
+struct Connection {
+    open: bool,
+}
+
+impl Connection {
+    #[ensures(result.open)]
+    fn open() -> Self {
+        Connection { open: true }
+    }
+
+    #[ensures(!self.open)]
+    fn close(&mut self) {
+        self.open = false;
+    }
+}
+
+impl Drop for Connection {
+    #[requires(!self.open)]
+    fn drop(&mut self) {}
+}
+
+fn never_closed() {
+    let conn = Connection::open();
+} //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+
+fn closed_on_some_paths(b: bool) {
+    let mut conn = Connection::open();
+    if b {
+        conn.close();
+    }
+} //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+
+fn replaced_while_open() {
+    let mut conn = Connection::open();
+    conn = Connection::open(); //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+    conn.close();
+}
+
+struct Pair {
+    conn: Connection,
+    id: u32,
+}
+
+fn field_never_closed() {
+    let pair = Pair { conn: Connection::open(), id: 0 };
+} //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+
+fn tuple_field_never_closed() {
+    let pair = (0, Connection::open());
+} //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+
+fn consume(conn: Connection) {} //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+
+fn moved_on_some_paths(b: bool) {
+    let conn = Connection::open();
+    if b {
+        consume(conn);
+    }
+} //~ ERROR the preconditions of `Drop::drop` cannot be checked for a value of type `Connection` that might have been moved out
+
+fn in_option() {
+    let mut conn = Connection::open();
+    conn.close();
+    let opt = Some(conn);
+} //~ ERROR the preconditions of `Drop::drop` for `Connection` cannot be checked when it is dropped as part of a `std::option::Option<Connection>`
+
+fn dropped_explicitly_while_open() {
+    let conn = Connection::open();
+    std::mem::drop(conn); //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+}
+
+fn keep<T>(value: T) {}
+
+fn moved_into_generic_function() {
+    let mut conn = Connection::open();
+    conn.close();
+    keep(conn); //~ ERROR the preconditions of `Drop::drop` for `Connection` cannot be checked when a `Connection` is moved into the generic function `keep`
+}
+
+struct Session {
+    conn: Connection,
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {}
+}
+
+fn not_closed_by_outer_drop() {
+    let mut session = Session { conn: Connection::open() };
+    session.conn.close();
+} //~ ERROR the precondition of `Drop::drop` might not hold when the value is dropped.
+
+fn main() {}
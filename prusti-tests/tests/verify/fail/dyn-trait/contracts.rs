@@ -0,0 +1,41 @@
+use prusti_contracts::*;
+
+trait Counter {
+    #[pure]
+    fn value(&self) -> u32;
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn increment(&mut self);
+}
+
+struct Simple {
+    count: u32,
+}
+
+impl Counter for Simple {
+    #[pure]
+    fn value(&self) -> u32 {
+        self.count
+    }
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+// Nothing is known about the trait object apart from the trait's contracts.
+#[ensures(counter.value() == 1)] //~ ERROR postcondition might not hold
+fn increment_unknown(counter: &mut dyn Counter) {
+    if counter.value() < 100 {
+        counter.increment();
+    }
+}
+
+fn increment_unchecked(counter: &mut dyn Counter) {
+    counter.increment(); //~ ERROR precondition might not hold
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use prusti_contracts::*;
+
+trait Counter {
+    #[pure]
+    fn value(&self) -> u32;
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn increment(&mut self);
+}
+
+struct Simple {
+    count: u32,
+}
+
+impl Counter for Simple {
+    #[pure]
+    fn value(&self) -> u32 {
+        self.count
+    }
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+fn main() {
+    let mut simple = Simple { count: 0 };
+    let counter: &mut dyn Counter = &mut simple; //~ ERROR unsizing a &mut Simple into a &mut dyn Counter is not supported
+    counter.increment();
+    // The change made through the trait object has to be visible here.
+    assert!(simple.count == 1);
+}
@@ -12,4 +12,9 @@ fn client_1() {}
 //~^ ERROR use of impure function "get_u32" in pure code
 fn client_2() {}
 
+// Functions of other crates can only be made pure by external specifications.
+#[requires(std::cmp::max(a, b) > 0)]
+//~^ ERROR use of impure function
+fn client_3(a: i32, b: i32) {}
+
 fn main() {}
@@ -0,0 +1,35 @@
+use prusti_contracts::*;
+
+// The loans of `*p` are syntactically overlapping, but `p` points to disjoint
+// locals when they are created, so they do not conflict.
+fn disjoint_pointees(c: bool) {
+    let mut x = 0;
+    let mut y = 0;
+    let mut z = 0;
+    let mut p = &mut x;
+    if c {
+        p = &mut y;
+    }
+    let r1 = &mut *p;
+    p = &mut z;
+    let r2 = &mut *p;
+    *r1 = 1;
+    *r2 = 2;
+    assert!(z == 1); //~ ERROR the asserted expression might not hold
+}
+
+// `p` might point to `x` when both loans are created, so they conflict.
+fn overlapping_pointees(c: bool) {
+    let x = 1;
+    let y = 2;
+    let mut p = &x;
+    if c {
+        p = &y;
+    }
+    let r1 = &*p;
+    p = &x;
+    let _r2 = &*p;
+    assert!(*r1 == 1); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
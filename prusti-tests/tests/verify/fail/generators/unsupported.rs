@@ -0,0 +1,13 @@
+#![feature(generators, generator_trait)]
+
+use std::ops::Generator;
+
+fn numbers() -> impl Generator<Yield = u32, Return = ()> {
+    || { //~ ERROR generators are not supported
+    //~^ ERROR construction of generators is not supported
+        yield 1;
+        yield 2;
+    }
+}
+
+fn main() {}
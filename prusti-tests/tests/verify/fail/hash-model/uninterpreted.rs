@@ -0,0 +1,10 @@
+use prusti_contracts::{hash_model::*, *};
+
+#[ensures(hash(&x) == x as u64)] //~ ERROR postcondition might not hold
+fn identity(x: u32) {}
+
+#[requires(a != b)]
+#[ensures(hash(&a) != hash(&b))] //~ ERROR postcondition might not hold
+fn no_collisions(a: u64, b: u64) {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use prusti_contracts::*;
+
+fn only_when_verified(x: u32) {
+    if !is_verified_call!() {
+        unreachable!();
+    }
+    if is_verified_call!() {
+        prusti_assert!(x > 0); //~ ERROR the asserted expression might not hold
+    }
+}
+
+fn main() {}
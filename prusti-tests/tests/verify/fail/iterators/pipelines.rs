@@ -0,0 +1,33 @@
+use prusti_contracts::*;
+
+// Without a postcondition, nothing is known about the values yielded by `map`.
+fn map_values_unknown() {
+    let mut it = (0..5).map(|x| x / 2);
+    if let Some(value) = it.next() {
+        assert!(value == 0); //~ ERROR the asserted expression might not hold
+    }
+}
+
+// `filter` might yield fewer values than the underlying iterator.
+fn filter_length_unknown() {
+    let mut it = (0..5).filter(|x| *x < 10);
+    match it.next() {
+        Some(_) => {}
+        None => unreachable!(), //~ ERROR unreachable!(..) statement might be reachable
+    }
+}
+
+#[ensures(result <= x)]
+fn half(x: usize) -> usize {
+    x / 2
+}
+
+// Only the postcondition of the function is known about the values.
+fn map_values_beyond_postcondition() {
+    let mut it = (1..5).map(half);
+    if let Some(value) = it.next() {
+        assert!(value == 0); //~ ERROR the asserted expression might not hold
+    }
+}
+
+fn main() {}
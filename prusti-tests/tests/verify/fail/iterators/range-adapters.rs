@@ -0,0 +1,39 @@
+use prusti_contracts::*;
+
+fn countdown(n: usize) {
+    let mut it = (0..n).rev();
+    let mut expected = n;
+    let mut done = false;
+    while !done {
+        body_invariant!(it.range_step() == 1 && it.range_rev());
+        body_invariant!(it.range_len() == expected);
+        body_invariant!(expected > 0 ==> it.range_first() == expected - 1);
+        match it.next() {
+            Some(i) => {
+                assert!(i == expected); //~ ERROR the asserted expression might not hold
+                expected -= 1;
+            }
+            None => done = true,
+        }
+    }
+}
+
+fn window() {
+    let mut it = (0..100).skip(10).take(5);
+    let mut count = 0;
+    let mut done = false;
+    while !done {
+        body_invariant!(it.range_step() == 1 && !it.range_rev());
+        body_invariant!(count + it.range_len() == 5);
+        body_invariant!(it.range_len() > 0 ==> it.range_first() == 10 + count);
+        match it.next() {
+            Some(i) => {
+                assert!(i < 14); //~ ERROR the asserted expression might not hold
+                count += 1;
+            }
+            None => done = true,
+        }
+    }
+}
+
+fn main() {}
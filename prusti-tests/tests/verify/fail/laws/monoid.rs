@@ -0,0 +1,31 @@
+use prusti_contracts::*;
+
+trait Monoid: Copy {
+    #[pure]
+    fn op(self, other: Self) -> Self;
+
+    #[law]
+    #[ensures(a.op(b.op(c)) === a.op(b).op(c))] //~ ERROR postcondition
+    fn associativity(a: Self, b: Self, c: Self);
+}
+
+#[derive(Clone, Copy)]
+struct Difference(i32);
+
+impl Monoid for Difference {
+    #[pure]
+    fn op(self, other: Self) -> Self {
+        Difference(self.0 - other.0)
+    }
+
+    // Subtraction is not associative.
+    fn associativity(_a: Self, _b: Self, _c: Self) {}
+}
+
+// Without calling the law, generic code knows nothing about `op`.
+#[ensures(result === a.op(b.op(c)))] //~ ERROR postcondition
+fn regroup<T: Monoid>(a: T, b: T, c: T) -> T {
+    a.op(b).op(c)
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use prusti_contracts::*;
+
+trait Monoid: Copy {
+    #[pure]
+    fn op(self, other: Self) -> Self;
+
+    #[law]
+    #[ensures(a.op(b.op(c)) === a.op(b).op(c))]
+    fn associativity(a: Self, b: Self, c: Self);
+}
+
+#[derive(Clone, Copy)]
+struct Sum(i32);
+
+impl Monoid for Sum {
+    #[pure]
+    fn op(self, other: Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+
+    #[law]
+    #[trusted]
+    fn associativity(_a: Self, _b: Self, _c: Self) {} //~ ERROR implementations of laws cannot be trusted
+}
+
+#[derive(Clone, Copy)]
+struct Product(i32);
+
+impl Monoid for Product {
+    #[pure]
+    fn op(self, other: Self) -> Self {
+        Product(self.0 * other.0)
+    }
+
+    #[trusted]
+    fn associativity(_a: Self, _b: Self, _c: Self) {} //~ ERROR implementations of laws cannot be trusted
+}
+
+fn main() {}
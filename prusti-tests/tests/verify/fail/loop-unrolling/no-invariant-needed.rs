@@ -0,0 +1,14 @@
+use prusti_contracts::*;
+
+fn wrong_result() {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 3 {
+        no_invariant_needed!(bound = 3);
+        sum += i;
+        i += 1;
+    }
+    assert!(sum == 4); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
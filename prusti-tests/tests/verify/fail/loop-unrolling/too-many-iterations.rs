@@ -0,0 +1,29 @@
+// compile-flags: -Ploop_unrolling_bound=4
+
+use prusti_contracts::*;
+
+fn five_iterations() {
+    let mut i = 0;
+    while i < 5 { //~ ERROR the loop might iterate more often than the loop unrolling bound
+        i += 1;
+    }
+}
+
+fn unbounded(n: u32) {
+    let mut i = 0;
+    while i < n { //~ ERROR the loop might iterate more often than the loop unrolling bound
+        i += 1;
+    }
+}
+
+fn wrong_result() {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 3 {
+        sum += i;
+        i += 1;
+    }
+    assert!(sum == 4); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,55 @@
+use prusti_contracts::*;
+
+#[trusted]
+fn random(i: u32) -> bool {
+    unimplemented!()
+}
+
+fn invariant_broken_on_first_continue() {
+    let mut i = 0;
+    while i < 100 {
+        body_invariant!(i % 2 == 0); //~ ERROR loop invariant might not hold after a loop iteration
+        i += 1;
+        if random(i) {
+            continue;
+        }
+        i += 1;
+        if random(i + 1) {
+            continue;
+        }
+    }
+}
+
+fn invariant_broken_on_last_continue() {
+    let mut i = 0;
+    while i < 100 {
+        body_invariant!(i % 2 == 0); //~ ERROR loop invariant might not hold after a loop iteration
+        i += 2;
+        if random(i) {
+            continue;
+        }
+        if random(i + 1) {
+            i += 1;
+            continue;
+        }
+    }
+}
+
+fn invariant_broken_on_labeled_continue() {
+    let mut i = 0;
+    'outer: while i < 10 {
+        body_invariant!(i % 2 == 0); //~ ERROR loop invariant might not hold after a loop iteration
+        i += 2;
+        let mut j = 0;
+        while j < 10 {
+            body_invariant!(j < 10 && i % 2 == 0);
+            j += 1;
+            if random(j) {
+                i += 1;
+                continue 'outer;
+            }
+        }
+    }
+}
+
+fn main() {}
@@ -7,7 +7,7 @@ fn random(i: u32) -> bool {
 
 fn continue_before_invariant() {
     let mut i = 0;
-    'myloop: while { //~ ERROR the loop invariant cannot be in a conditional branch of the loop
+    'myloop: while { //~ ERROR the loop invariant is not reached by the loop iterations that end early
         if random(i + 2) {
             continue 'myloop;
         }
@@ -51,4 +51,14 @@ fn continue_outer_loop_before_invariant() {
     }
 }
 
+fn invariant_in_branch() {
+    let mut i = 0;
+    while random(i) { //~ ERROR the loop invariant cannot be in a conditional branch of the loop
+        if random(i + 1) {
+            body_invariant!(true);
+        }
+        i += 1;
+    }
+}
+
 fn main() {}
@@ -0,0 +1,25 @@
+use prusti_contracts::*;
+
+fn not_established() {
+    let mut i = 1;
+    loop_invariant!(i == 0); //~ ERROR loop invariant might not hold
+    while i < 10 {
+        i += 1;
+    }
+}
+
+fn not_preserved() {
+    let mut i = 0;
+    loop_invariant!(i < 10); //~ ERROR loop invariant might not hold
+    while i < 10 {
+        i += 1;
+    }
+}
+
+fn no_loop() {
+    let i = 0;
+    loop_invariant!(i == 0); //~ ERROR the loop invariant is not followed by a loop
+    assert!(i == 0);
+}
+
+fn main() {}
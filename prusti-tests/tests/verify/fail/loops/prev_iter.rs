@@ -0,0 +1,23 @@
+use prusti_contracts::*;
+
+fn decreasing_sum() {
+    let mut i = 0;
+    let mut sum = 100;
+    while i < 10 {
+        body_invariant!(i < 10);
+        body_invariant!(sum >= prev_iter(sum)); //~ ERROR loop invariant might not hold
+        sum -= i;
+        i += 1;
+    }
+}
+
+fn prev_iter_is_not_the_entry_state() {
+    let mut i = 0;
+    while i < 10 {
+        body_invariant!(i < 10);
+        body_invariant!(prev_iter(i) == 0); //~ ERROR loop invariant might not hold
+        i += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use prusti_contracts::*;
+
+macro_rules! with_spec {
+    ($name:ident, $post:expr) => {
+        #[ensures($post)]
+        fn $name(x: u32) -> u32 {
+            x
+        }
+    };
+}
+
+with_spec!(too_large, result > x); //~ ERROR postcondition might not hold
+
+fn main() {}
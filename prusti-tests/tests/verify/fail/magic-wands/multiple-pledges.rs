@@ -0,0 +1,27 @@
+use prusti_contracts::*;
+
+struct Pair {
+    a: i32,
+    b: i32,
+}
+
+#[after_expiry(p.a == before_expiry(result.a))]
+#[after_expiry(p.b == 0)] //~ ERROR pledge in the postcondition might not hold
+fn identity(p: &mut Pair) -> &mut Pair {
+    p
+}
+
+#[assert_on_expiry(result.a >= 0, p.a >= 0)]
+#[assert_on_expiry(result.b >= 0, p.b >= 0)]
+fn non_negative(p: &mut Pair) -> &mut Pair {
+    p
+}
+
+fn violate_second_obligation() {
+    let mut p = Pair { a: 1, b: 2 };
+    let r = non_negative(&mut p); //~ ERROR obligation might not hold on borrow expiry
+    r.a = 3;
+    r.b = -4;
+}
+
+fn main() {}
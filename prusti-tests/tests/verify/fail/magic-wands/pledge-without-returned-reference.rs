@@ -0,0 +1,6 @@
+use prusti_contracts::*;
+
+#[after_expiry(*x == 1)]
+fn set(x: &mut u32) { *x = 1; } //~ ERROR pledges are only supported for functions that return a reference
+
+fn main() {}
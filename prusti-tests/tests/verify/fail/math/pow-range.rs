@@ -0,0 +1,11 @@
+use prusti_contracts::{math::*, *};
+
+fn in_range() -> u128 {
+    pow(3, 64)
+}
+
+fn out_of_range() -> u128 {
+    pow(3, 100) //~ ERROR precondition might not hold
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use prusti_contracts::*;
+
+enum Shape {
+    Circle(u32),
+    Square(u32),
+}
+
+fn not_a_circle(shape: Shape) {
+    assert!(matches!(shape, Shape::Circle(_))); //~ ERROR `shape` might not match the pattern `Shape::Circle(_)`
+}
+
+fn guard_might_not_hold(x: Option<u32>) {
+    if let Some(_) = x {
+        assert!(matches!(x, Some(n) if n > 0)); //~ ERROR `x` might not match the pattern `Some(n) if n > 0`
+    }
+}
+
+fn nested_value(pair: (Option<u32>, u32)) {
+    assert!(matches!((pair.0, pair.1), (Some(_), 0))); //~ ERROR `(pair.0, pair.1)` might not match the pattern `(Some(_), 0)`
+}
+
+fn with_message(x: Option<u32>) {
+    assert!(matches!(x, Some(_)), "x must be set"); //~ ERROR `x` might not match the pattern `Some(_)`
+}
+
+fn main() {}
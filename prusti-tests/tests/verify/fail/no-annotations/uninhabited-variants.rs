@@ -0,0 +1,20 @@
+use std::convert::Infallible;
+
+fn unwrap_either(result: Result<u32, u32>) -> u32 {
+    match result {
+        Ok(value) => value,
+        Err(_) => unreachable!(), //~ ERROR unreachable!(..) statement might be reachable
+    }
+}
+
+fn unwrap_infallible(result: Result<u32, Infallible>) -> u32 {
+    match result {
+        Ok(value) => {
+            assert!(value > 0); //~ ERROR the asserted expression might not hold
+            value
+        }
+        Err(_) => unreachable!(),
+    }
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+use prusti_contracts::*;
+
+#[extern_spec]
+impl<T> std::option::Option<T> {
+    #[pure]
+    #[ensures(matches!(*self, Some(_)) == result)]
+    pub fn is_some(&self) -> bool;
+
+    #[requires(self.is_some())]
+    pub fn expect(self, msg: &str) -> T;
+}
+
+#[ensures(result.is_some() == (x > 0 || y > 0))]
+fn first_positive(x: i32, y: i32) -> Option<i32> {
+    if x > 0 {
+        Some(x)
+    } else if y > 0 {
+        Some(y)
+    } else {
+        None
+    }
+}
+
+fn test1(x: i32) -> i32 {
+    first_positive(x, 1).expect("some argument is positive")
+}
+
+fn test2(x: i32) -> i32 {
+    first_positive(x, x).expect("x is positive") //~ ERROR `expect` might panic with "x is positive"
+}
+
+fn test3(r: Result<u32, ()>) -> u32 {
+    r.expect("the value was parsed") //~ ERROR `expect` might panic with "the value was parsed"
+}
+
+#[requires(r.is_ok())]
+fn test4(r: Result<u32, ()>) -> u32 {
+    r.expect("the caller checked the value")
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use prusti_contracts::*;
+
+trait Account {
+    predicate! {
+        fn valid(&self) -> bool;
+    }
+
+    #[requires(self.valid())]
+    #[ensures(self.valid())]
+    fn deposit(&mut self, amount: u32);
+}
+
+struct Capped {
+    balance: u32,
+    cap: u32,
+}
+
+#[refine_trait_spec]
+impl Account for Capped {
+    predicate! {
+        fn valid(&self) -> bool {
+            self.balance <= self.cap
+        }
+    }
+
+    fn deposit(&mut self, amount: u32) { //~ ERROR postcondition might not hold
+        if amount <= u32::MAX - self.balance {
+            self.balance += amount;
+        }
+    }
+}
+
+fn main() {}
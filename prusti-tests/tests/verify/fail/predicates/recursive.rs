@@ -0,0 +1,14 @@
+use prusti_contracts::*;
+
+predicate! {
+    #[unfold_depth(2)]
+    fn even(n: u32) -> bool {
+        n == 0 || (n != 1 && even(n - 2))
+    }
+}
+
+#[requires(n < 100 && even(n))]
+#[ensures(even(n + 1))] //~ ERROR postcondition might not hold
+fn add_one(n: u32) {}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use prusti_contracts::*;
+
+predicate! {
+    #[trusted]
+    fn valid(x: u32) -> bool {
+        x % 2 == 0
+    }
+}
+
+// The body of a trusted predicate is hidden from the verifier.
+#[ensures(valid(result))] //~ ERROR postcondition might not hold
+fn two() -> u32 {
+    2
+}
+
+fn main() {}
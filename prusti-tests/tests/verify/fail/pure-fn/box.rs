@@ -0,0 +1,16 @@
+use prusti_contracts::*;
+
+#[pure]
+fn boxed(x: i32) -> Box<i32> {
+    Box::new(x)
+}
+
+#[ensures(result == Box::new(x + 1))] //~ ERROR postcondition might not hold
+fn make(x: i32) -> Box<i32> {
+    Box::new(x)
+}
+
+#[ensures(*boxed(x) == 0)] //~ ERROR postcondition might not hold
+fn test(x: i32) {}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use prusti_contracts::*;
+
+#[pure]
+fn count_below(v: &[u32], bound: u32) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < v.len() {
+        if v[i] < bound {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+#[pure]
+fn panics_in_loop(n: u32) -> u32 {
+    let mut i = 0;
+    while i < n {
+        assert!(i != 5); //~ ERROR the asserted expression might not hold
+        i += 1;
+    }
+    i
+}
+
+#[pure]
+fn sum_range(n: u32) -> u32 {
+    let mut sum = 0;
+    for i in 0..n { //~ ERROR loops in pure functions can only use variables of Copy types
+        sum += i;
+    }
+    sum
+}
+
+#[ensures(sum_range(0) == sum_range(0))]
+fn uses_sum_range() {}
+
+#[requires(v.len() == 0)]
+#[ensures(count_below(v, bound) == 1)] //~ ERROR postcondition might not hold
+fn empty(v: &[u32], bound: u32) {}
+
+fn main() {}
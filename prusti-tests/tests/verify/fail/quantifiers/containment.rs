@@ -0,0 +1,15 @@
+use prusti_contracts::*;
+
+#[requires(forall(|x: &i32| s.contains(x) ==> *x > 0))]
+#[requires(s.len() > 2)]
+fn second_non_negative(s: &[i32]) {
+    assert!(s[1] > 1); //~ ERROR the asserted expression might not hold
+}
+
+#[requires(s.len() > 0)]
+#[ensures(forall(|x: &i32| s.contains(x) ==> *x == 0))] //~ ERROR postcondition might not hold
+fn not_all_zero(s: &mut [i32]) {
+    s[0] = 0;
+}
+
+fn main() {}
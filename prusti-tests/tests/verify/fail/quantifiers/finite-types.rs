@@ -0,0 +1,15 @@
+use prusti_contracts::*;
+
+#[pure]
+fn select(b: bool, x: i32, y: i32) -> i32 {
+    if b {
+        x
+    } else {
+        y
+    }
+}
+
+#[ensures(forall(|b: bool| select(b, x, y) == x))] //~ ERROR postcondition might not hold
+fn select_first(x: i32, y: i32) {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use prusti_contracts::*;
+
+#[requires(*x < 100)]
+fn increment(x: &mut u32) {
+    *x += 1;
+    prusti_assert!(*x == old(*x)); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
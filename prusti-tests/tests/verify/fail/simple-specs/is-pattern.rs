@@ -0,0 +1,12 @@
+use prusti_contracts::*;
+
+#[ensures(result is Some(v) ==> v > x)] //~ ERROR postcondition might not hold
+fn next(x: u32) -> Option<u32> {
+    if x < 10 {
+        Some(x)
+    } else {
+        None
+    }
+}
+
+fn main() {}
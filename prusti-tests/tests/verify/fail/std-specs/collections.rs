@@ -0,0 +1,31 @@
+use prusti_contracts::*;
+use std::collections::{HashMap, VecDeque};
+
+#[ensures(result.len() == 2)] //~ ERROR postcondition might not hold
+fn vec_push() -> Vec<u32> {
+    let mut v = Vec::new();
+    v.push(1);
+    v
+}
+
+fn vec_pop(mut v: Vec<u32>) {
+    assert!(matches!(v.pop(), Some(_))); //~ ERROR `v.pop()` might not match the pattern `Some(_)`
+}
+
+fn deque(mut d: VecDeque<u32>) {
+    d.push_back(1);
+    d.pop_front();
+    assert!(d.is_empty()); //~ ERROR the asserted expression might not hold
+}
+
+fn map(mut m: HashMap<u32, u32>) {
+    m.insert(1, 10);
+    assert!(m.len() == 1); //~ ERROR the asserted expression might not hold
+}
+
+fn map_other_key(mut m: HashMap<String, u32>, key: String, name: &str) {
+    m.insert(key, 1);
+    assert!(m.contains_key(name)); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
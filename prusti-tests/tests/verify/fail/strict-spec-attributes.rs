@@ -0,0 +1,39 @@
+// compile-flags: -Pstrict_spec_attributes=true
+
+use prusti_contracts::*;
+
+trait Container {
+    type Item;
+
+    fn first(&self) -> Self::Item;
+}
+
+#[extern_spec]
+trait Container {
+    #[pure] //~ ERROR `#[pure]` has no effect on associated types in external specs and is ignored
+    type Item;
+
+    #[pure]
+    fn first(&self) -> Self::Item;
+}
+
+mod external {
+    pub fn answer() -> i32 {
+        42
+    }
+}
+
+#[extern_spec]
+#[trusted] //~ ERROR `#[trusted]` has no effect on modules in external specs and is ignored
+mod external {
+    #[ensures(result == 42)]
+    fn answer() -> i32;
+}
+
+fn main() {
+    let f = closure!(
+        #[pure] //~ ERROR `#[pure]` has no effect on closures and is ignored
+        |x: i32| -> i32 { x }
+    );
+    f(0);
+}
@@ -0,0 +1,17 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[ensures(result.len() == s.len())] //~ ERROR postcondition might not hold
+fn exclaim(s: &str) -> String {
+    let mut result = String::from(s);
+    result.push_str("!");
+    result
+}
+
+#[ensures(result.as_str().as_bytes()[0] == b'a')] //~ ERROR postcondition might not hold
+fn prefix(s: &str) -> String {
+    String::from("b") + s
+}
+
+fn main() {}
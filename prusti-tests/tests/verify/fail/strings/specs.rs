@@ -0,0 +1,14 @@
+use prusti_contracts::*;
+
+#[requires(s.len() >= 1)]
+#[ensures(result == b'a')] //~ ERROR postcondition might not hold
+fn first_byte(s: &str) -> u8 {
+    s.as_bytes()[0]
+}
+
+#[ensures(result == 4)] //~ ERROR postcondition might not hold
+fn literal_len() -> usize {
+    "hello".len()
+}
+
+fn main() {}
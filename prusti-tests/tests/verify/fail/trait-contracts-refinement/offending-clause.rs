@@ -0,0 +1,47 @@
+use prusti_contracts::*;
+
+trait Account {
+    #[requires(amount > 0)]
+    #[requires(amount <= 100)]
+    #[ensures(result >= amount)]
+    #[ensures(result <= 2 * amount)]
+    fn deposit(&mut self, amount: u32) -> u32;
+
+    #[requires(amount > 0)]
+    #[ensures(result <= amount)] //~ ERROR the method's postcondition may not be a valid strengthening of the postcondition of 'Account::withdraw'
+    fn withdraw(&mut self, amount: u32) -> u32;
+}
+
+struct Savings;
+
+#[refine_trait_spec]
+impl Account for Savings {
+    #[requires(amount > 0)]
+    #[requires(amount <= 50)] //~ ERROR the method's precondition may not be a valid weakening of the precondition of 'Account::deposit'
+    #[ensures(result == amount)]
+    fn deposit(&mut self, amount: u32) -> u32 {
+        amount
+    }
+
+    #[ensures(result <= amount + 1)]
+    fn withdraw(&mut self, amount: u32) -> u32 {
+        amount
+    }
+}
+
+struct Checking;
+
+#[refine_trait_spec]
+impl Account for Checking {
+    #[ensures(result == amount)]
+    fn deposit(&mut self, amount: u32) -> u32 {
+        amount
+    }
+
+    #[ensures(result == 0)]
+    fn withdraw(&mut self, amount: u32) -> u32 {
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use prusti_contracts::*;
+
+trait Model {
+    #[pure]
+    fn model(&self) -> u64;
+}
+
+#[ghost_impl]
+impl Model for u32 {
+    #[pure]
+    fn model(&self) -> u64 {
+        *self as u64
+    }
+}
+
+#[ensures(result == x.model())]
+fn in_specification(x: u32) -> u64 {
+    x as u64
+}
+
+fn in_code(x: u32) -> u64 {
+    x.model() //~ ERROR using ghost implementations in non-specification code is not allowed
+}
+
+fn generic_model<T: Model>(x: &T) -> u64 {
+    x.model()
+}
+
+fn through_trait_bound(x: u32) -> u64 {
+    generic_model(&x) //~ ERROR using ghost implementations in non-specification code is not allowed
+}
+
+fn main() {}
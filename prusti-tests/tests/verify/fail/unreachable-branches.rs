@@ -0,0 +1,43 @@
+// compile-flags: -Preport_unreachable_branches=true
+use prusti_contracts::*;
+
+#[requires(x > 10)]
+fn error_arm(x: u32) -> Result<u32, ()> {
+    if x > 5 {
+        Ok(x)
+    } else {
+        Err(()) //~ WARNING this branch is unreachable
+    }
+}
+
+#[ensures(result > 0)]
+fn positive() -> u32 {
+    42
+}
+
+fn unused_default() -> u32 {
+    match positive() {
+        0 => 1, //~ WARNING this branch is unreachable
+        n => n,
+    }
+}
+
+#[requires(x > 10 && x < 5)]
+fn contradictory(x: u32) -> u32 { //~ WARNING the precondition of this function cannot be satisfied
+    if x > 0 {
+        x
+    } else {
+        0
+    }
+}
+
+fn reachable(x: u32) -> u32 {
+    if x > 5 {
+        x
+    } else {
+        assert!(x > 5); //~ ERROR the asserted expression might not hold
+        x
+    }
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use prusti_contracts::*;
+
+#[trusted_unsafe_memory]
+fn wrong_value() {
+    let mut x = 1;
+    let p = &mut x as *mut i32;
+    unsafe {
+        *p = 2;
+        assert!(*p == 1); //~ ERROR the asserted expression might not hold
+    }
+}
+
+#[trusted_unsafe_memory]
+#[requires((*p).value < 100)]
+#[ensures((*p).value == old((*p).value) + 1)] //~ ERROR postcondition might not hold
+unsafe fn increment(p: *mut Cell) {
+    (*p).value += 2;
+}
+
+struct Cell {
+    value: u32,
+}
+
+fn not_opted_in() {
+    let x = 5;
+    let _p = &x as *const i32; //~ ERROR raw addresses of expressions or casting a reference to a raw pointer are not supported
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+fn apply(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x) //~ ERROR calls of function pointers are not supported
+}
+
+#[prusti_contracts::trusted]
+fn apply_trusted(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+// compile-flags: -Preport_vacuous_specs=true
+use prusti_contracts::*;
+
+#[ensures(result == 1)]
+fn assume_false() -> u32 {
+    prusti_assume!(false); //~ WARNING this assumption contradicts the reachable states
+    2
+}
+
+#[requires(x > 10)]
+fn contradicting_precondition(x: u32) -> u32 {
+    prusti_assume!(x < 5); //~ WARNING this assumption contradicts the reachable states
+    x
+}
+
+#[requires(x > 10 && x < 5)]
+fn unsatisfiable(x: u32) -> u32 { //~ WARNING the precondition of this function cannot be satisfied
+    prusti_assume!(false);
+    x
+}
+
+fn unreachable_assumption(x: u32) -> u32 {
+    if x > 5 && x < 2 {
+        prusti_assume!(false);
+    }
+    x
+}
+
+#[ensures(result > 5)]
+fn consistent_assumption(x: u32) -> u32 {
+    prusti_assume!(x > 5);
+    x
+}
+
+#[ensures(result > 5)] //~ ERROR postcondition might not hold
+fn still_checked(x: u32) -> u32 {
+    prusti_assume!(x > 2);
+    x
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+// compile-flags: -Pverify_only_annotated=true
+use prusti_contracts::*;
+
+fn not_annotated(v: &[u32]) -> u32 {
+    v[0]
+}
+
+#[ensures(result > 0)] //~ ERROR postcondition might not hold
+fn annotated(v: &[u32]) -> u32 {
+    if v.is_empty() {
+        0
+    } else {
+        1
+    }
+}
+
+#[requires(!v.is_empty())]
+fn checked_for_panics(v: &[u32]) -> u32 {
+    v[1] //~ ERROR the array or slice index may be out of bounds
+}
+
+fn with_assertion(x: u32) -> u32 {
+    let y = x / 2;
+    prusti_assert!(y < x); //~ ERROR the asserted expression might not hold
+    y
+}
+
+trait Positive {
+    #[ensures(result > 0)] //~ ERROR postcondition might not hold
+    fn positive(&self) -> u32;
+}
+
+struct Zero;
+
+impl Positive for Zero {
+    // Inherits the postcondition of the trait method.
+    fn positive(&self) -> u32 {
+        0
+    }
+}
+
+fn main() {
+    let v = [1, 2];
+    not_annotated(&v);
+}
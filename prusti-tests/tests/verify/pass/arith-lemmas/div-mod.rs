@@ -0,0 +1,26 @@
+use prusti_contracts::*;
+
+#[requires(b > 0)]
+#[ensures(result.0 * b + result.1 == a)]
+#[ensures(result.1 < b)]
+fn split(a: u32, b: u32) -> (u32, u32) {
+    lemma_div_mod!(a, b);
+    (a / b, a % b)
+}
+
+#[requires(0 < size && size <= 4096)]
+#[requires(count <= 1024)]
+#[ensures(result == count)]
+fn pages(count: usize, size: usize) -> usize {
+    lemma_mul_div!(count, size);
+    (count * size) / size
+}
+
+#[requires(b > 0 && k < 100 && a < 100)]
+#[ensures(result)]
+fn same_remainder(a: u64, k: u64, b: u64) -> bool {
+    lemma_div_mod_add_multiple!(a, k, b);
+    (a + k * b) % b == a % b
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use core::future::Future;
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+async fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(x < 50)]
+#[ensures(result == x + 2)]
+async fn increment_twice(x: u32) -> u32 {
+    let y = increment(x).await;
+    assert!(y == x + 1);
+    increment(y).await
+}
+
+#[trusted]
+#[ensures_on_completion(result >= a && result >= b)]
+fn max(a: i32, b: i32) -> impl Future<Output = i32> {
+    async move {
+        if a > b {
+            a
+        } else {
+            b
+        }
+    }
+}
+
+#[ensures_on_completion(result == x * 2)]
+async fn double(x: i32) -> i32 {
+    x * 2
+}
+
+async fn client() {
+    let m = max(3, 7).await;
+    assert!(m >= 7);
+    let d = double(m).await;
+    assert!(d >= 14);
+}
+
+fn main() {}
@@ -0,0 +1,74 @@
+use prusti_contracts::*;
+
+#[requires(x < 1000)]
+#[ensures(result > x && result <= x + 1)]
+fn helper(x: u32) -> u32 {
+    x + 1
+}
+
+#[ensures(result == (x % 2 == 0))]
+fn is_even(x: u32) -> bool {
+    x % 2 == 0
+}
+
+#[requires(x < 1000)]
+#[ensures(result == call_summary!(helper(x)) * 2)]
+fn double_helper(x: u32) -> u32 {
+    helper(x) * 2
+}
+
+// The summary refers to the last call of `helper`
+#[requires(x < 500)]
+#[ensures(result > x)]
+#[ensures({
+    let r = call_summary!(helper(result));
+    r > result
+})]
+fn helper_twice(x: u32) -> u32 {
+    let y = helper(x);
+    helper(y);
+    y
+}
+
+#[ensures(call_summary!(is_even(x)) ==> result == x / 2)]
+#[ensures(!call_summary!(is_even(x)) ==> result == 0)]
+fn half_if_even(x: u32) -> u32 {
+    if is_even(x) {
+        x / 2
+    } else {
+        0
+    }
+}
+
+// The summary is only required if `b` holds
+#[ensures(b ==> result == call_summary!(helper(x)))]
+fn helper_if(x: u32, b: bool) -> u32 {
+    if b {
+        helper(x)
+    } else {
+        0
+    }
+}
+
+#[ensures(result >= x)]
+fn tagged<T>(x: u32) -> u32 {
+    x
+}
+
+// Calls with different type arguments are summarized separately
+#[ensures(result == call_summary!(tagged::<u8>(x)) + call_summary!(tagged::<u16>(y)))]
+fn tagged_sum(x: u32, y: u32) -> u32 {
+    tagged::<u8>(x) + tagged::<u16>(y)
+}
+
+fn main() {
+    let a = double_helper(10);
+    assert!(a > 20);
+    let b = helper_twice(3);
+    assert!(b > 3);
+    let c = half_if_even(8);
+    assert!(c == 4);
+    helper_if(7, false);
+    let e = tagged_sum(1, 2);
+    assert!(e >= 3);
+}
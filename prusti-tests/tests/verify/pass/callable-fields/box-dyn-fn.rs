@@ -0,0 +1,34 @@
+use prusti_contracts::*;
+
+#[invariant(self.f |= |x: i32| [requires(x >= 0), ensures(result > x)])]
+struct Callbacks {
+    f: Box<dyn Fn(i32) -> i32>,
+}
+
+#[requires(x >= 0)]
+#[ensures(result > x)]
+fn apply(callbacks: &Callbacks, x: i32) -> i32 {
+    (callbacks.f)(x)
+}
+
+#[requires(x >= 0)]
+#[ensures(result > x)]
+fn apply_twice(callbacks: &Callbacks, x: i32) -> i32 {
+    let y = (callbacks.f)(x);
+    (callbacks.f)(y)
+}
+
+// The contract of the stored callable is not checked, so the struct can only be
+// created in trusted code.
+#[trusted]
+fn increment() -> Callbacks {
+    Callbacks {
+        f: Box::new(|x| x + 1),
+    }
+}
+
+fn main() {
+    let callbacks = increment();
+    apply(&callbacks, 1);
+    apply_twice(&callbacks, 1);
+}
@@ -0,0 +1,41 @@
+use prusti_contracts::*;
+
+enum Shape {
+    Circle(u32),
+    Rect { w: u32, h: u32 },
+}
+
+#[ensures(result is Ok(v) ==> v > 0)]
+#[ensures(result is Err(_) ==> x == 0)]
+fn checked(x: u32) -> Result<u32, ()> {
+    if x == 0 {
+        Err(())
+    } else {
+        Ok(x)
+    }
+}
+
+#[requires(opt is Some(v) && v < 10)]
+#[ensures(result < 11)]
+fn small(opt: Option<u32>) -> u32 {
+    match opt {
+        Some(v) => v + 1,
+        None => unreachable!(),
+    }
+}
+
+#[requires(shape is Shape::Rect { w, h } ==> w < 100 && h < 100)]
+#[requires(shape is Shape::Circle(r) ==> r < 10)]
+fn area_bound(shape: Shape) -> u32 {
+    match shape {
+        Shape::Circle(r) => 4 * r * r,
+        Shape::Rect { w, h } => w * h,
+    }
+}
+
+fn main() {
+    let r = checked(3);
+    prusti_assert!(r is Ok(v) ==> v > 0);
+    assert!(small(Some(3)) < 11);
+    area_bound(Shape::Circle(3));
+}
@@ -0,0 +1,57 @@
+use prusti_contracts::*;
+
+// Annotated functions produced by `macro_rules!` macros, whose specifications
+// are partly or entirely passed to the macro by the caller.
+macro_rules! bounded_inc {
+    ($name:ident, $bound:expr) => {
+        #[requires(x < $bound)]
+        #[ensures(result == x + 1)]
+        fn $name(x: u32) -> u32 {
+            x + 1
+        }
+    };
+}
+
+macro_rules! with_spec {
+    ($name:ident, $pre:expr, $post:expr) => {
+        #[requires($pre)]
+        #[ensures($post)]
+        fn $name(x: u32) -> u32 {
+            x
+        }
+    };
+}
+
+macro_rules! snapshot_identity {
+    ($name:ident, $ty:ty) => {
+        #[ensures(result === old(v))]
+        fn $name(v: $ty) -> $ty {
+            v
+        }
+    };
+}
+
+macro_rules! constant {
+    ($name:ident, $ty:ty, $value:expr) => {
+        #[ensures(result === $value)]
+        fn $name() -> $ty {
+            $value
+        }
+    };
+}
+
+bounded_inc!(inc_small, 10);
+with_spec!(id, x > 0, result > 0);
+with_spec!(id_eq, true, result === x);
+snapshot_identity!(id_pair, (u32, u32));
+constant!(origin, (u32, u32), (0, 0));
+
+fn main() {
+    let y = inc_small(3);
+    assert!(y == 4);
+    assert!(id(1) > 0);
+    assert!(id_eq(5) == 5);
+    let p = id_pair((1, 2));
+    assert!(p.0 == 1);
+    assert!(origin().1 == 0);
+}
@@ -0,0 +1,38 @@
+use prusti_contracts::*;
+
+// Specification macros defined by a library, expanding to Prusti syntax.
+mod spec_lib {
+    #[macro_export]
+    macro_rules! sorted {
+        ($s:expr) => {
+            ::prusti_contracts::prusti_expr!(
+                forall(|i: usize, j: usize| (i < j && j < $s.len()) ==> $s[i] <= $s[j])
+            )
+        };
+    }
+
+    #[macro_export]
+    macro_rules! in_range {
+        ($x:expr, $lo:expr, $hi:expr) => {
+            ::prusti_contracts::prusti_expr!($lo <= $x && $x < $hi)
+        };
+    }
+}
+
+#[requires(sorted!(s))]
+#[requires(s.len() > 3)]
+#[ensures(result <= s[3])]
+fn first_le_fourth(s: &[i32]) -> i32 {
+    s[0]
+}
+
+#[requires(in_range!(x, 0, 10))]
+#[ensures(in_range!(result, 1, 11))]
+fn inc(x: i32) -> i32 {
+    x + 1
+}
+
+fn main() {
+    let y = inc(3);
+    prusti_assert!(in_range!(y, 1, 11));
+}
@@ -0,0 +1,100 @@
+use prusti_contracts::*;
+
+struct Connection {
+    open: bool,
+}
+
+impl Connection {
+    #[ensures(result.open)]
+    fn open() -> Self {
+        Connection { open: true }
+    }
+
+    #[ensures(!self.open)]
+    fn close(&mut self) {
+        self.open = false;
+    }
+}
+
+impl Drop for Connection {
+    #[requires(!self.open)]
+    fn drop(&mut self) {
+        assert!(!self.open);
+    }
+}
+
+#[requires(!conn.open)]
+fn consume(conn: Connection) {}
+
+fn close_before_scope_end() {
+    let mut conn = Connection::open();
+    conn.close();
+}
+
+fn close_on_all_paths(b: bool) {
+    let mut conn = Connection::open();
+    if b {
+        conn.close();
+    } else {
+        conn.open = false;
+    }
+}
+
+// A value that was moved out is not dropped by the caller.
+fn moved_out() {
+    let mut conn = Connection::open();
+    conn.close();
+    consume(conn);
+}
+
+fn replaced() {
+    let mut conn = Connection::open();
+    conn.close();
+    conn = Connection::open();
+    conn.close();
+}
+
+struct Pair {
+    conn: Connection,
+    id: u32,
+}
+
+fn field_closed() {
+    let mut pair = Pair { conn: Connection::open(), id: 0 };
+    pair.conn.close();
+}
+
+fn tuple_field_closed() {
+    let mut pair = (0, Connection::open());
+    pair.1.close();
+}
+
+fn field_moved_out() {
+    let mut pair = Pair { conn: Connection::open(), id: 0 };
+    pair.conn.close();
+    consume(pair.conn);
+}
+
+fn dropped_explicitly() {
+    let mut conn = Connection::open();
+    conn.close();
+    std::mem::drop(conn);
+}
+
+struct Session {
+    conn: Connection,
+}
+
+impl Drop for Session {
+    #[ensures(!self.conn.open)]
+    fn drop(&mut self) {
+        self.conn.close();
+    }
+}
+
+// The field is dropped after `Session::drop`, which closes it.
+fn closed_by_outer_drop() {
+    let session = Session { conn: Connection::open() };
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+use prusti_contracts::*;
+
+trait Counter {
+    #[pure]
+    fn value(&self) -> u32;
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn increment(&mut self);
+}
+
+struct Simple {
+    count: u32,
+}
+
+impl Counter for Simple {
+    #[pure]
+    fn value(&self) -> u32 {
+        self.count
+    }
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+#[requires(counter.value() < 50)]
+#[ensures(counter.value() == old(counter.value()) + 2)]
+fn increment_twice(counter: &mut dyn Counter) {
+    counter.increment();
+    counter.increment();
+}
+
+#[requires(counter.value() == 3)]
+#[ensures(result == 3)]
+fn read(counter: &dyn Counter) -> u32 {
+    counter.value()
+}
+
+fn describe(counter: &dyn Counter) -> u32 {
+    counter.value()
+}
+
+fn main() {
+    let simple = Simple { count: 0 };
+    describe(&simple);
+}
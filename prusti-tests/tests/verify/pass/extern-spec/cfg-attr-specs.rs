@@ -0,0 +1,41 @@
+use prusti_contracts::*;
+
+// Specifications nested in `cfg_attr` are processed like the other
+// specifications of the item. `all()` always holds, while `any()` never holds.
+
+trait Sensor {
+    #[ensures(result <= 100)]
+    fn read(&self) -> u32;
+}
+
+struct Fixed;
+
+impl Sensor for Fixed {
+    fn read(&self) -> u32 {
+        42
+    }
+}
+
+#[extern_spec]
+impl Sensor for Fixed {
+    #[cfg_attr(all(), ensures(result == 42))]
+    #[cfg_attr(any(), ensures(result == 0))]
+    fn read(&self) -> u32;
+}
+
+struct Counter {
+    count: u32,
+}
+
+#[refine_trait_spec]
+impl Sensor for Counter {
+    #[cfg_attr(all(), ensures(result <= self.count), inline)]
+    #[cfg_attr(any(), ensures(result == 0))]
+    fn read(&self) -> u32 {
+        if self.count <= 100 { self.count } else { 100 }
+    }
+}
+
+fn main() {
+    assert!(Fixed.read() == 42);
+}
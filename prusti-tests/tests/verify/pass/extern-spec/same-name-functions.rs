@@ -0,0 +1,59 @@
+use prusti_contracts::*;
+
+// Functions of different modules with the same name, whose specifications are
+// given in a single module.
+
+#[extern_spec(first)]
+#[ensures(result == 1)]
+fn value() -> i32;
+
+#[extern_spec(second)]
+#[ensures(result == 2)]
+fn value() -> i32;
+
+#[extern_spec]
+mod first {
+    mod nested {
+        #[ensures(result == 3)]
+        fn value() -> i32;
+    }
+}
+
+#[extern_spec]
+mod second {
+    mod nested {
+        #[ensures(result == 4)]
+        fn value() -> i32;
+    }
+}
+
+mod first {
+    pub fn value() -> i32 {
+        1
+    }
+
+    pub mod nested {
+        pub fn value() -> i32 {
+            3
+        }
+    }
+}
+
+mod second {
+    pub fn value() -> i32 {
+        2
+    }
+
+    pub mod nested {
+        pub fn value() -> i32 {
+            4
+        }
+    }
+}
+
+fn main() {
+    assert!(first::value() == 1);
+    assert!(second::value() == 2);
+    assert!(first::nested::value() == 3);
+    assert!(second::nested::value() == 4);
+}
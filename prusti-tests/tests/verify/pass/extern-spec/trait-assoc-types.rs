@@ -0,0 +1,48 @@
+use prusti_contracts::*;
+
+trait Container {
+    type Item;
+
+    fn first(&self) -> Self::Item;
+
+    fn replace_first(&mut self, item: Self::Item) -> Self::Item;
+}
+
+#[extern_spec]
+trait Container {
+    type Item: Copy;
+
+    #[pure]
+    fn first(&self) -> Self::Item;
+
+    #[ensures(self.first() === item)]
+    #[ensures(result === old(self.first()))]
+    fn replace_first(&mut self, item: Self::Item) -> Self::Item;
+}
+
+struct Single {
+    value: i32,
+}
+
+impl Container for Single {
+    type Item = i32;
+
+    #[pure]
+    fn first(&self) -> i32 {
+        self.value
+    }
+
+    #[trusted]
+    fn replace_first(&mut self, item: i32) -> i32 {
+        let old = self.value;
+        self.value = item;
+        old
+    }
+}
+
+fn main() {
+    let mut s = Single { value: 1 };
+    let old = s.replace_first(5);
+    assert!(old == 1);
+    assert!(s.first() == 5);
+}
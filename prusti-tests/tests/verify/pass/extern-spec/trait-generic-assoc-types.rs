@@ -0,0 +1,54 @@
+use prusti_contracts::*;
+
+trait Windows {
+    type Window<'a>
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize;
+
+    fn window<'a>(&'a self, start: usize) -> Self::Window<'a>;
+}
+
+#[extern_spec]
+trait Windows {
+    type Window<'a>: Copy
+    where
+        Self: 'a;
+
+    #[pure]
+    fn len(&self) -> usize;
+
+    #[requires(start < self.len())]
+    fn window<'a>(&'a self, start: usize) -> Self::Window<'a>;
+}
+
+struct Pair {
+    first: u32,
+    second: u32,
+}
+
+impl Windows for Pair {
+    type Window<'a> = &'a u32;
+
+    #[pure]
+    fn len(&self) -> usize {
+        2
+    }
+
+    fn window<'a>(&'a self, start: usize) -> &'a u32 {
+        if start == 0 {
+            &self.first
+        } else {
+            &self.second
+        }
+    }
+}
+
+fn main() {
+    let pair = Pair {
+        first: 1,
+        second: 2,
+    };
+    let _window = pair.window(1);
+}
@@ -0,0 +1,42 @@
+use prusti_contracts::*;
+
+trait Describe {
+    fn describe(self) -> u32;
+}
+
+struct Item {
+    id: u32,
+}
+
+impl Describe for &Item {
+    fn describe(self) -> u32 {
+        self.id
+    }
+}
+
+impl Describe for &mut Item {
+    fn describe(self) -> u32 {
+        self.id = 0;
+        1
+    }
+}
+
+#[extern_spec]
+impl Describe for &Item {
+    #[ensures(result == self.id)]
+    fn describe(self) -> u32;
+}
+
+#[extern_spec]
+impl Describe for &mut Item {
+    #[ensures(self.id == 0)]
+    #[ensures(result == 1)]
+    fn describe(self) -> u32;
+}
+
+fn main() {
+    let mut item = Item { id: 7 };
+    assert!((&item).describe() == 7);
+    assert!((&mut item).describe() == 1);
+    assert!(item.id == 0);
+}
@@ -0,0 +1,53 @@
+use prusti_contracts::*;
+
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+struct Pair {
+    first: Point,
+    second: Point,
+}
+
+// `counter` is not blocked by the returned reference, so its permission is
+// not folded inside the package of the magic wand.
+#[requires(counter.x < 100)]
+#[ensures(counter.x == old(counter.x) + 1 && counter.y == old(counter.y))]
+#[ensures(*result == old(pair.first.x))]
+#[after_expiry(pair.first.x == before_expiry(*result) && pair.second.y == old(pair.second.y))]
+fn first_x<'a>(pair: &'a mut Pair, counter: &mut Point) -> &'a mut u32 {
+    counter.x += 1;
+    &mut pair.first.x
+}
+
+// Both arguments might be blocked by the returned reference.
+#[ensures(choose ==> *result == old(a.x))]
+#[ensures(!choose ==> *result == old(b.y))]
+fn choose_field<'a>(a: &'a mut Point, b: &'a mut Point, choose: bool) -> &'a mut u32 {
+    if choose {
+        &mut a.x
+    } else {
+        &mut b.y
+    }
+}
+
+fn client() {
+    let mut pair = Pair {
+        first: Point { x: 1, y: 2 },
+        second: Point { x: 3, y: 4 },
+    };
+    let mut counter = Point { x: 0, y: 0 };
+    let x = first_x(&mut pair, &mut counter);
+    assert!(*x == 1);
+    *x = 5;
+    assert!(pair.first.x == 5);
+    assert!(pair.second.y == 4);
+    assert!(counter.x == 1);
+    let mut a = Point { x: 7, y: 8 };
+    let mut b = Point { x: 9, y: 10 };
+    let r = choose_field(&mut a, &mut b, false);
+    assert!(*r == 10);
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+#![feature(generators, generator_trait)]
+
+use prusti_contracts::*;
+use std::{
+    ops::{Generator, GeneratorState},
+    pin::Pin,
+};
+
+#[trusted]
+fn numbers() -> impl Generator<Yield = u32, Return = ()> {
+    || {
+        yield 1;
+        yield 2;
+    }
+}
+
+#[trusted]
+#[ensures(result == 3)]
+fn sum() -> u32 {
+    let mut generator = numbers();
+    let mut sum = 0;
+    while let GeneratorState::Yielded(x) = Pin::new(&mut generator).resume(()) {
+        sum += x;
+    }
+    sum
+}
+
+fn main() {
+    assert!(sum() == 3);
+}
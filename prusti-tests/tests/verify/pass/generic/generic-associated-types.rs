@@ -0,0 +1,53 @@
+use prusti_contracts::*;
+
+// A lending iterator, whose items may borrow from the iterator itself.
+trait Lending {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    #[pure]
+    fn remaining(&self) -> usize;
+
+    #[requires(self.remaining() > 0)]
+    #[ensures(self.remaining() == old(self.remaining()) - 1)]
+    fn advance<'a>(&'a mut self) -> Self::Item<'a>;
+}
+
+struct Countdown {
+    left: usize,
+}
+
+#[refine_trait_spec]
+impl Lending for Countdown {
+    type Item<'a> = usize;
+
+    #[pure]
+    fn remaining(&self) -> usize {
+        self.left
+    }
+
+    #[requires(self.remaining() > 0)]
+    #[ensures(self.remaining() == old(self.remaining()) - 1)]
+    #[ensures(result == self.left)]
+    fn advance<'a>(&'a mut self) -> usize {
+        self.left -= 1;
+        self.left
+    }
+}
+
+// The items of `L` are projections that cannot be normalized.
+#[requires(iter.remaining() >= 2)]
+#[ensures(iter.remaining() == old(iter.remaining()) - 2)]
+fn skip_two<L: Lending>(iter: &mut L) {
+    iter.advance();
+    iter.advance();
+}
+
+fn main() {
+    let mut countdown = Countdown { left: 3 };
+    skip_two(&mut countdown);
+    let last = countdown.advance();
+    assert!(last == 0);
+    assert!(countdown.remaining() == 0);
+}
@@ -0,0 +1,20 @@
+// compile-flags: -Phash_model=identity
+
+use prusti_contracts::{hash_model::*, *};
+
+#[ensures(hash(&x) == x as u64)]
+fn identity(x: u32) {}
+
+#[ensures(bucket(&key, 8) == key % 8)]
+fn bucket_of_key(key: usize) {}
+
+#[requires(a != b)]
+#[ensures(hash(&a) != hash(&b))]
+fn no_collisions(a: u64, b: u64) {}
+
+// Only unsigned integers are hashed with the identity.
+#[requires(a == b)]
+#[ensures(hash(&a) == hash(&b))]
+fn signed_deterministic(a: i32, b: i32) {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use prusti_contracts::{hash_model::*, *};
+
+#[requires(a == b)]
+#[ensures(hash(&a) == hash(&b))]
+fn deterministic(a: u32, b: u32) {}
+
+#[requires(hash(&a) != hash(&b))]
+#[ensures(a != b)]
+fn distinct_hashes(a: u64, b: u64) {}
+
+#[ensures(bucket(&key, 1) == 0)]
+fn single_bucket(key: u32) {}
+
+#[requires(bucket(&key, 16) == 3)]
+#[ensures(result < 16)]
+fn bucket_index(key: u32) -> usize {
+    bucket(&key, 16)
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use prusti_contracts::*;
+
+const VERIFIED: bool = is_verified_call!();
+
+#[requires(x < 100)]
+#[ensures(result > x)]
+fn next(x: u32) -> u32 {
+    // A proof hint that would only slow down the compiled program.
+    if is_verified_call!() {
+        prusti_assert!(x + 1 <= 100);
+    }
+    x + 1
+}
+
+fn main() {
+    assert!(VERIFIED);
+    assert!(is_verified_call!());
+    next(1);
+}
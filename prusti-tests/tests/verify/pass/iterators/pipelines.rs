@@ -0,0 +1,78 @@
+use prusti_contracts::*;
+
+fn enumerate_take() {
+    let mut it = (10..20).enumerate().take(3);
+    let mut count = 0;
+    let mut done = false;
+    while !done {
+        body_invariant!(count <= 3);
+        body_invariant!(it.remaining().len() == Int::new_usize(3 - count));
+        body_invariant!(forall(|i: usize| Int::new_usize(i) < it.remaining().len() ==>
+            it.remaining()[i].0 == count + i && it.remaining()[i].1 == 10 + count + i));
+        match it.next() {
+            Some((index, value)) => {
+                assert!(index == count);
+                assert!(value == 10 + index);
+                count += 1;
+            }
+            None => done = true,
+        }
+    }
+    assert!(count == 3);
+}
+
+fn map_preserves_length(n: usize) {
+    let mut it = (0..n).map(|x| x % 2 == 0);
+    let mut count = 0;
+    let mut done = false;
+    while !done {
+        body_invariant!(count <= n);
+        body_invariant!(it.remaining().len() == Int::new_usize(n - count));
+        match it.next() {
+            Some(_) => count += 1,
+            None => done = true,
+        }
+    }
+    assert!(count == n);
+}
+
+fn filter_yields_at_most(n: usize) {
+    let mut it = (0..n).filter(|x| x % 3 == 0);
+    let mut count = 0;
+    let mut done = false;
+    while !done {
+        body_invariant!(count <= n);
+        body_invariant!(it.remaining().len() + Int::new_usize(count) <= Int::new_usize(n));
+        match it.next() {
+            Some(_) => count += 1,
+            None => done = true,
+        }
+    }
+    assert!(count <= n);
+}
+
+#[ensures(result <= x)]
+fn half(x: usize) -> usize {
+    x / 2
+}
+
+fn map_satisfies_postcondition() {
+    let mut it = (0..5).map(half);
+    if let Some(value) = it.next() {
+        assert!(value == 0);
+    }
+}
+
+#[ensures(result ==> *x % 2 == 0)]
+fn is_even(x: &usize) -> bool {
+    *x % 2 == 0
+}
+
+fn filter_satisfies_postcondition(n: usize) {
+    let mut it = (0..n).filter(is_even);
+    if let Some(value) = it.next() {
+        assert!(value % 2 == 0);
+    }
+}
+
+fn main() {}
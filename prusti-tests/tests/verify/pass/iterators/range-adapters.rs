@@ -0,0 +1,58 @@
+use prusti_contracts::*;
+
+#[requires(n <= 100)]
+fn evens(n: usize) {
+    let mut it = (0..n).step_by(2);
+    let mut done = false;
+    while !done {
+        body_invariant!(it.range_step() == 2 && !it.range_rev());
+        body_invariant!(it.range_len() > 0 ==>
+            it.range_first() % 2 == 0 && it.range_first() + 2 * (it.range_len() - 1) < n);
+        match it.next() {
+            Some(i) => assert!(i % 2 == 0 && i < n),
+            None => done = true,
+        }
+    }
+}
+
+fn countdown(n: usize) {
+    let mut it = (0..n).rev();
+    let mut expected = n;
+    let mut done = false;
+    while !done {
+        body_invariant!(it.range_step() == 1 && it.range_rev());
+        body_invariant!(it.range_len() == expected);
+        body_invariant!(expected > 0 ==> it.range_first() == expected - 1);
+        match it.next() {
+            Some(i) => {
+                assert!(i == expected - 1);
+                expected -= 1;
+            }
+            None => {
+                assert!(expected == 0);
+                done = true;
+            }
+        }
+    }
+}
+
+fn window() {
+    let mut it = (0..100).skip(10).take(5);
+    let mut count = 0;
+    let mut done = false;
+    while !done {
+        body_invariant!(it.range_step() == 1 && !it.range_rev());
+        body_invariant!(count + it.range_len() == 5);
+        body_invariant!(it.range_len() > 0 ==> it.range_first() == 10 + count);
+        match it.next() {
+            Some(i) => {
+                assert!(10 <= i && i < 15);
+                count += 1;
+            }
+            None => done = true,
+        }
+    }
+    assert!(count == 5);
+}
+
+fn main() {}
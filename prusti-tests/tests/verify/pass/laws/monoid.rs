@@ -0,0 +1,79 @@
+use prusti_contracts::*;
+
+trait Monoid: Copy {
+    #[pure]
+    fn unit() -> Self;
+
+    #[pure]
+    fn op(self, other: Self) -> Self;
+
+    #[law]
+    #[ensures(a.op(b.op(c)) === a.op(b).op(c))]
+    fn associativity(a: Self, b: Self, c: Self);
+
+    #[law]
+    #[ensures(Self::unit().op(a) === a && a.op(Self::unit()) === a)]
+    fn identity(a: Self);
+}
+
+#[derive(Clone, Copy)]
+struct Sum(i32);
+
+impl Monoid for Sum {
+    #[pure]
+    fn unit() -> Self {
+        Sum(0)
+    }
+
+    #[pure]
+    fn op(self, other: Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+
+    #[law]
+    fn associativity(_a: Self, _b: Self, _c: Self) {}
+
+    #[law]
+    fn identity(_a: Self) {}
+}
+
+#[derive(Clone, Copy)]
+struct Any(bool);
+
+impl Monoid for Any {
+    #[pure]
+    fn unit() -> Self {
+        Any(false)
+    }
+
+    #[pure]
+    fn op(self, other: Self) -> Self {
+        Any(self.0 || other.0)
+    }
+
+    fn associativity(_a: Self, _b: Self, _c: Self) {}
+
+    fn identity(_a: Self) {}
+}
+
+// Generic code can rely on the laws by calling them.
+#[ensures(result === a.op(b.op(c.op(d))))]
+fn fold_left<T: Monoid>(a: T, b: T, c: T, d: T) -> T {
+    T::associativity(a, b, c);
+    T::associativity(a.op(b), c, d);
+    T::associativity(a, b, c.op(d));
+    a.op(b).op(c).op(d)
+}
+
+#[ensures(result === x)]
+fn drop_units<T: Monoid>(x: T) -> T {
+    T::identity(x);
+    T::unit().op(x)
+}
+
+fn main() {
+    let s = fold_left(Sum(1), Sum(2), Sum(3), Sum(4));
+    assert!(s.0 == 10);
+    let a = drop_units(Any(true));
+    assert!(a.0);
+}
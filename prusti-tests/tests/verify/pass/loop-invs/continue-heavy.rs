@@ -0,0 +1,93 @@
+use prusti_contracts::*;
+
+#[trusted]
+fn random(i: u32) -> bool {
+    unimplemented!()
+}
+
+fn many_continues() {
+    let mut i = 0;
+    let mut even = 0;
+    while i < 100 {
+        body_invariant!(i < 100 && even <= i);
+        i += 1;
+        if i % 2 == 1 {
+            continue;
+        }
+        if random(i) {
+            continue;
+        }
+        even += 1;
+        if random(i + 1) {
+            continue;
+        }
+        if even > 50 {
+            break;
+        }
+    }
+    assert!(even <= i);
+}
+
+fn continue_in_match(limit: u32) {
+    let mut i = 0;
+    let mut count = 0;
+    while i < limit {
+        body_invariant!(count <= i && i < limit);
+        i += 1;
+        match i % 3 {
+            0 => continue,
+            1 => {
+                count += 1;
+                continue;
+            }
+            _ => {}
+        }
+        count += 1;
+    }
+    assert!(count <= i);
+}
+
+fn labeled_continues() {
+    let mut i = 0;
+    let mut total = 0;
+    'outer: while i < 10 {
+        body_invariant!(i < 10 && total <= 10 * i);
+        i += 1;
+        let mut j = 0;
+        'inner: while j < 10 {
+            body_invariant!(j < 10 && total <= 10 * (i - 1) + j);
+            j += 1;
+            if random(j) {
+                continue 'inner;
+            }
+            if random(j + 1) {
+                continue 'outer;
+            }
+            total += 1;
+        }
+    }
+    assert!(total <= 100);
+}
+
+fn labeled_continue_from_nested_loop() {
+    let mut i = 0;
+    'outer: loop {
+        body_invariant!(i <= 10);
+        if i == 10 {
+            break;
+        }
+        i += 1;
+        loop {
+            body_invariant!(i <= 10);
+            if random(i) {
+                continue 'outer;
+            }
+            if random(i + 1) {
+                break;
+            }
+        }
+    }
+    assert!(i == 10);
+}
+
+fn main() {}
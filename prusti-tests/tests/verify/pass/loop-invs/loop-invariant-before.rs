@@ -0,0 +1,61 @@
+use prusti_contracts::*;
+
+#[ensures(*i == old(*i) + 1)]
+#[ensures(result == (old(*i) < 10))]
+fn step(i: &mut u32) -> bool {
+    let below = *i < 10;
+    *i += 1;
+    below
+}
+
+fn effectful_condition() {
+    let mut i = 0;
+    loop_invariant!(i <= 10);
+    while step(&mut i) {}
+    assert!(i == 11);
+}
+
+struct Counter {
+    value: u32,
+    limit: u32,
+}
+
+impl Counter {
+    #[ensures(self.limit == old(self.limit))]
+    #[ensures(old(self.value < self.limit) ==> self.value == old(self.value) + 1 && result === Some(old(self.value)))]
+    #[ensures(!old(self.value < self.limit) ==> self.value == old(self.value) && result === None)]
+    fn next(&mut self) -> Option<u32> {
+        if self.value < self.limit {
+            self.value += 1;
+            Some(self.value - 1)
+        } else {
+            None
+        }
+    }
+}
+
+fn while_let(limit: u32) {
+    let mut counter = Counter { value: 0, limit };
+    loop_invariant!(counter.value <= counter.limit);
+    loop_invariant!(counter.limit == limit);
+    while let Some(value) = counter.next() {
+        assert!(value < limit);
+    }
+    assert!(counter.value == limit);
+}
+
+fn nested() {
+    let mut i = 0;
+    while i < 10 {
+        body_invariant!(i < 10);
+        let mut j = 0;
+        loop_invariant!(j <= i);
+        while j < i {
+            j += 1;
+        }
+        assert!(j == i);
+        i += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+#[requires(n >= 0)]
+fn old_is_function_entry(mut n: i32) -> i32 {
+    let mut sum = 0;
+    while n > 0 {
+        body_invariant!(n >= 0 && n <= old(n));
+        body_invariant!(sum >= old(n) - n);
+        sum += n;
+        n -= 1;
+    }
+    sum
+}
+
+fn monotonic_sum() {
+    let mut i = 0;
+    let mut sum = 0;
+    while i < 10 {
+        body_invariant!(0 <= i && i < 10);
+        body_invariant!(sum >= prev_iter(sum));
+        body_invariant!(sum <= i * 10);
+        sum += i;
+        i += 1;
+    }
+}
+
+fn counter_increases() {
+    let mut i = 0;
+    while i < 10 {
+        body_invariant!(i < 10);
+        // On loop entry, `prev_iter(i)` is the current value of `i`.
+        body_invariant!(i == 0 || i == prev_iter(i) + 1);
+        i += 1;
+    }
+}
+
+fn main() {}
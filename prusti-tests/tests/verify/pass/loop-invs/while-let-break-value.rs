@@ -0,0 +1,93 @@
+use prusti_contracts::*;
+
+struct Counter {
+    value: u32,
+    limit: u32,
+}
+
+impl Counter {
+    #[ensures(self.limit == old(self.limit))]
+    #[ensures(old(self.value < self.limit) ==> self.value == old(self.value) + 1 && result === Some(old(self.value)))]
+    #[ensures(!old(self.value < self.limit) ==> self.value == old(self.value) && result === None)]
+    fn next(&mut self) -> Option<u32> {
+        if self.value < self.limit {
+            self.value += 1;
+            Some(self.value - 1)
+        } else {
+            None
+        }
+    }
+}
+
+#[requires(limit <= 100)]
+fn while_let(limit: u32) {
+    let mut counter = Counter { value: 0, limit };
+    let mut last = 0;
+    while let Some(value) = counter.next() {
+        body_invariant!(counter.limit == limit);
+        body_invariant!(value < counter.value && counter.value <= limit);
+        last = value;
+    }
+    assert!(last < 100);
+}
+
+enum Step {
+    Stop,
+    Skip,
+    Add(u32),
+}
+
+#[ensures(i >= 10 ==> result === Step::Stop)]
+fn step(i: u32) -> Step {
+    if i >= 10 {
+        Step::Stop
+    } else if i % 2 == 0 {
+        Step::Skip
+    } else {
+        Step::Add(i)
+    }
+}
+
+fn while_let_multiple_variants() {
+    let mut i = 0;
+    let mut sum = 0;
+    while let Step::Skip | Step::Add(_) = step(i) {
+        body_invariant!(i < 10 && sum <= 10 * i);
+        sum += 10;
+        i += 1;
+    }
+    assert!(sum <= 100);
+}
+
+fn break_value() {
+    let mut sum = 0;
+    let result = loop {
+        body_invariant!(sum <= 100);
+        if sum >= 90 {
+            break sum;
+        }
+        sum += 10;
+    };
+    assert!(result <= 100);
+}
+
+fn break_values_in_match(limit: u32) -> u32 {
+    let mut i = 0;
+    let found = loop {
+        body_invariant!(i <= 10);
+        match step(i) {
+            Step::Stop => break i,
+            Step::Skip => {}
+            Step::Add(n) => {
+                if n == limit {
+                    break i;
+                }
+            }
+        }
+        i += 1;
+    };
+    assert!(found <= 10);
+    found
+}
+
+fn main() {}
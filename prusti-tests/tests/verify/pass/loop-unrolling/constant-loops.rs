@@ -0,0 +1,54 @@
+// compile-flags: -Ploop_unrolling_bound=4
+
+use prusti_contracts::*;
+
+fn init_array() -> [usize; 4] {
+    let mut a = [0; 4];
+    let mut i = 0;
+    while i < 4 {
+        a[i] = i * i;
+        i += 1;
+    }
+    assert!(a[0] == 0 && a[1] == 1 && a[2] == 4 && a[3] == 9);
+    a
+}
+
+fn loop_with_break() -> u32 {
+    let mut i = 0;
+    loop {
+        if i == 3 {
+            break;
+        }
+        i += 1;
+    }
+    assert!(i == 3);
+    i
+}
+
+#[requires(n <= 2)]
+fn nested_loops(n: usize) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < n {
+        let mut j = 0;
+        while j < 2 {
+            count += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    assert!(count == 2 * n);
+    count
+}
+
+// Loops with specifications are not unrolled.
+fn loop_with_invariant(n: u32) {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(i < n);
+        i += 1;
+    }
+    assert!(i >= n);
+}
+
+fn main() {}
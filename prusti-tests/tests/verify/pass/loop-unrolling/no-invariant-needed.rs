@@ -0,0 +1,48 @@
+use prusti_contracts::*;
+
+// Every marked loop is reported with a warning that its verification is
+// bounded.
+
+fn sum_of_three() -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 3 {
+        no_invariant_needed!(bound = 3);
+        sum += i;
+        i += 1;
+    }
+    assert!(sum == 3);
+    sum
+}
+
+#[requires(n <= 2)]
+fn at_most_two(n: u32) -> u32 {
+    let mut i = 0;
+    while i < n {
+        no_invariant_needed!(bound = 2);
+        i += 1;
+    }
+    assert!(i == n);
+    i
+}
+
+// The executions in which the loop iterates more often than the bound are not
+// verified, so the assertion after the loop is never checked.
+fn too_small_bound() {
+    let mut i = 0;
+    while i < 3 {
+        no_invariant_needed!(bound = 2);
+        i += 1;
+    }
+    assert!(i == 2);
+}
+
+fn with_invariant(n: u32) {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(i < n);
+        i += 1;
+    }
+}
+
+fn main() {}
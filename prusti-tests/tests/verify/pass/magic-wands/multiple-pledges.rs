@@ -0,0 +1,36 @@
+use prusti_contracts::*;
+
+struct Pair {
+    a: i32,
+    b: i32,
+}
+
+#[after_expiry(p.a == before_expiry(result.a))]
+#[after_expiry(p.b == before_expiry(result.b))]
+fn identity(p: &mut Pair) -> &mut Pair {
+    p
+}
+
+#[assert_on_expiry(result.a >= 0, p.a >= 0)]
+#[assert_on_expiry(result.b >= 0, p.b >= 0)]
+fn non_negative(p: &mut Pair) -> &mut Pair {
+    p
+}
+
+fn use_identity() {
+    let mut p = Pair { a: 1, b: 2 };
+    let r = identity(&mut p);
+    r.a = 3;
+    r.b = 4;
+    assert!(p.a == 3 && p.b == 4);
+}
+
+fn use_non_negative() {
+    let mut p = Pair { a: 1, b: 2 };
+    let r = non_negative(&mut p);
+    r.a = 3;
+    r.b = 4;
+    assert!(p.a >= 0 && p.b >= 0);
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use prusti_contracts::{math::*, *};
+
+#[ensures(result == pow(2, 3))]
+fn eight() -> u128 {
+    8
+}
+
+#[requires(n >= 1)]
+#[ensures(pow(2, log2(n)) <= n)]
+fn largest_power_of_two_below(n: u128) {
+    log2_bounds(n);
+}
+
+// Number of steps of a merge sort of `n` elements.
+#[pure]
+fn merge_sort_cost(n: u128) -> u128 {
+    if n <= 1 {
+        0
+    } else {
+        2 * merge_sort_cost(n / 2) + n
+    }
+}
+
+#[requires(n >= 1 && n <= 1_000_000)]
+#[requires(merge_sort_cost(n / 2) <= (n / 2) * log2(n / 2))]
+#[ensures(merge_sort_cost(n) <= n * log2(n))]
+fn merge_sort_bound_step(n: u128) {
+    if n >= 2 {
+        n_log2_n_split(n);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use prusti_contracts::*;
+
+enum Shape {
+    Circle(u32),
+    Square(u32),
+}
+
+#[requires(matches!(shape, Shape::Circle(r) if r > 0))]
+fn circle(shape: Shape) {
+    assert!(matches!(shape, Shape::Circle(_)));
+    assert!(matches!(shape, Shape::Circle(r) if r >= 1));
+    assert!(!matches!(shape, Shape::Square(_)));
+}
+
+fn after_match(x: Option<u32>) {
+    match x {
+        Some(n) if n > 5 => assert!(matches!(x, Some(m) if m > 5)),
+        Some(_) => assert!(matches!(x, Some(_))),
+        None => assert!(matches!(x, None)),
+    }
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+#![feature(never_type)]
+
+use std::convert::Infallible;
+
+fn unwrap_infallible(result: Result<u32, Infallible>) -> u32 {
+    match result {
+        Ok(value) => value,
+        // No value of type `Infallible` exists, so this arm is unreachable.
+        Err(_) => unreachable!(),
+    }
+}
+
+fn unwrap_never(result: Result<u32, !>) -> u32 {
+    match result {
+        Ok(value) => value,
+        Err(_) => panic!(),
+    }
+}
+
+fn get_none(option: Option<!>) {
+    match option {
+        Some(_) => unreachable!(),
+        None => {}
+    }
+}
+
+fn exit_early(x: u32) -> u32 {
+    if x == 0 {
+        std::process::exit(1);
+    }
+    // Calls of functions returning `!` do not return.
+    assert!(x > 0);
+    x
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+use prusti_contracts::*;
+
+trait Account {
+    predicate! {
+        fn valid(&self) -> bool;
+    }
+
+    #[requires(self.valid())]
+    #[ensures(self.valid())]
+    fn deposit(&mut self, amount: u32);
+}
+
+struct Capped {
+    balance: u32,
+    cap: u32,
+}
+
+#[refine_trait_spec]
+impl Account for Capped {
+    predicate! {
+        fn valid(&self) -> bool {
+            self.balance <= self.cap
+        }
+    }
+
+    fn deposit(&mut self, amount: u32) {
+        if amount <= self.cap - self.balance {
+            self.balance += amount;
+        }
+    }
+}
+
+struct Unbounded {
+    balance: u64,
+}
+
+#[refine_trait_spec]
+impl Account for Unbounded {
+    predicate! {
+        fn valid(&self) -> bool {
+            self.balance < 1_000_000
+        }
+    }
+
+    fn deposit(&mut self, amount: u32) {
+        if (amount as u64) < 1_000_000 - self.balance {
+            self.balance += amount as u64;
+        }
+    }
+}
+
+#[requires(account.valid())]
+#[ensures(account.valid())]
+fn deposit_twice<T: Account>(account: &mut T) {
+    account.deposit(10);
+    account.deposit(5);
+}
+
+fn main() {
+    let mut capped = Capped { balance: 0, cap: 100 };
+    deposit_twice(&mut capped);
+    let mut unbounded = Unbounded { balance: 0 };
+    deposit_twice(&mut unbounded);
+}
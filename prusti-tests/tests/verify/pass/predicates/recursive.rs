@@ -0,0 +1,27 @@
+use prusti_contracts::*;
+
+predicate! {
+    fn even(n: u32) -> bool {
+        n == 0 || (n != 1 && even(n - 2))
+    }
+}
+
+predicate! {
+    #[unfold_depth(3)]
+    fn even_deep(n: u32) -> bool {
+        n == 0 || (n != 1 && even_deep(n - 2))
+    }
+}
+
+#[requires(n < 100 && even(n))]
+#[ensures(even(n + 2))]
+fn add_two(n: u32) {}
+
+#[requires(n < 100 && even_deep(n))]
+#[ensures(even_deep(n + 6))]
+fn add_six(n: u32) {}
+
+fn main() {
+    prusti_assert!(even(2));
+    prusti_assert!(even_deep(6));
+}
@@ -0,0 +1,22 @@
+use prusti_contracts::*;
+
+predicate! {
+    #[trusted]
+    fn valid(x: u32) -> bool {
+        x % 2 == 0
+    }
+}
+
+#[requires(valid(x))]
+#[ensures(valid(result))]
+fn keep(x: u32) -> u32 {
+    x
+}
+
+#[requires(valid(x))]
+fn consume(x: u32) {
+    let y = keep(x);
+    prusti_assert!(valid(y));
+}
+
+fn main() {}
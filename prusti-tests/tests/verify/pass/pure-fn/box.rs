@@ -0,0 +1,48 @@
+#![feature(box_patterns)]
+
+use prusti_contracts::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+enum Tree {
+    Leaf(i32),
+    Node(Box<Tree>, Box<Tree>),
+}
+
+#[pure]
+fn boxed(x: i32) -> Box<i32> {
+    Box::new(x)
+}
+
+#[pure]
+fn leftmost_child(t: &Tree) -> Option<i32> {
+    match t {
+        Tree::Node(box Tree::Leaf(v), _) => Some(*v),
+        _ => None,
+    }
+}
+
+#[ensures(*result == x)]
+#[ensures(result == Box::new(x))]
+#[ensures(result == boxed(x))]
+fn make(x: i32) -> Box<i32> {
+    Box::new(x)
+}
+
+#[ensures(result.x == p.x && result.y == p.y)]
+#[ensures(**result == p)]
+fn nest(p: Point) -> Box<Box<Point>> {
+    Box::new(Box::new(p))
+}
+
+#[requires(matches!(**b, Tree::Leaf(_)))]
+#[ensures(leftmost_child(&result) == Some(0))]
+fn grow(b: Box<Tree>) -> Tree {
+    Tree::Node(Box::new(Tree::Leaf(0)), b)
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+use prusti_contracts::*;
+
+#[pure]
+fn abs_diff(a: i32, b: i32) -> i32 {
+    let mut diff = a - b;
+    if diff < 0 {
+        diff = -diff;
+    }
+    diff
+}
+
+#[pure]
+fn count_below(v: &[u32], bound: u32) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < v.len() {
+        if v[i] < bound {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+#[pure]
+fn first_zero(v: &[u32]) -> usize {
+    let mut i = 0;
+    loop {
+        if i >= v.len() || v[i] == 0 {
+            break;
+        }
+        i += 1;
+    }
+    i
+}
+
+#[ensures(abs_diff(a, b) == abs_diff(b, a))]
+fn symmetric(a: i32, b: i32) {}
+
+#[requires(v.len() == 0)]
+#[ensures(count_below(v, bound) == 0)]
+#[ensures(first_zero(v) == 0)]
+fn empty(v: &[u32], bound: u32) {}
+
+#[requires(v.len() > 0 && v[0] == 0)]
+#[ensures(first_zero(v) == 0)]
+fn starts_with_zero(v: &[u32]) {}
+
+fn main() {}
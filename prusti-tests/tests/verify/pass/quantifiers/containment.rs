@@ -0,0 +1,20 @@
+use prusti_contracts::*;
+
+#[requires(forall(|x: &i32| s.contains(x) ==> *x > 0))]
+#[requires(s.len() > 2)]
+fn second_positive(s: &[i32]) -> i32 {
+    let y = s[1];
+    assert!(y > 0);
+    y
+}
+
+#[requires(forall(|x: &i32| s.contains(x) ==> 0 <= *x && *x < 10))]
+#[ensures(forall(|x: &i32| s.contains(x) ==> *x < 10))]
+fn bounded(s: &[i32]) {}
+
+#[requires(forall(|x: &u32| a.contains(x) ==> *x == 3))]
+fn all_three(a: &[u32; 4]) {
+    assert!(a[0] + a[3] == 6);
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use prusti_contracts::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[pure]
+fn is_red(c: Color) -> bool {
+    matches!(c, Color::Red)
+}
+
+#[pure]
+fn select(b: bool, x: i32, y: i32) -> i32 {
+    if b {
+        x
+    } else {
+        y
+    }
+}
+
+// Quantifiers over `bool` and field-less enums are split into finitely many
+// cases, so no triggers are needed.
+#[ensures(forall(|b: bool| select(b, x, x) == x))]
+#[ensures(exists(|b: bool| select(b, x, y) == y))]
+fn select_same(x: i32, y: i32) {}
+
+#[ensures(exists(|c: Color| is_red(c)))]
+#[ensures(forall(|c: Color, d: Color| is_red(c) && is_red(d) ==> c === d))]
+fn red_exists() {}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use prusti_contracts::*;
+
+struct Account {
+    balance: u32,
+}
+
+#[requires(n <= 100 && x <= 100)]
+fn add(mut x: u32, n: u32) -> u32 {
+    x += n;
+    prusti_assert!(x == old(x) + n);
+    x
+}
+
+#[requires(*x < 100)]
+fn increment(x: &mut u32) {
+    *x += 1;
+    prusti_assert!(*x == old(*x) + 1);
+    *x += 1;
+    prusti_assert!(*x == old(*x) + 2);
+}
+
+// `old(account)` does not move `account`, which is still used afterwards.
+#[requires(account.balance < 100)]
+fn deposit(mut account: Account) -> Account {
+    account.balance += 1;
+    prusti_assert!(account.balance == old(account).balance + 1);
+    prusti_assert!(account.balance == old(account.balance) + 1);
+    account
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+const fn inc(x: u32) -> u32 {
+    x + 1
+}
+
+struct Counter {
+    value: u32,
+}
+
+impl Counter {
+    #[ensures(result.value == 0)]
+    const fn new() -> Self {
+        Counter { value: 0 }
+    }
+
+    #[pure]
+    const fn get(&self) -> u32 {
+        self.value
+    }
+}
+
+#[extern_spec]
+impl<T> std::option::Option<T> {
+    #[pure]
+    #[ensures(matches!(*self, Some(_)) == result)]
+    pub const fn is_some(&self) -> bool;
+
+    #[requires(self.is_some())]
+    pub const fn unwrap(self) -> T;
+}
+
+fn main() {
+    assert!(inc(41) == 42);
+    let counter = Counter::new();
+    assert!(counter.get() == 0);
+    let _ = Some(counter).unwrap();
+}
@@ -0,0 +1,34 @@
+use prusti_contracts::*;
+
+struct Wide {
+    a: u32,
+    b: u32,
+    c: u32,
+    d: u32,
+    inner: Inner,
+}
+
+struct Inner {
+    x: u32,
+    y: u32,
+}
+
+impl Wide {
+    #[requires(self.a < 100)]
+    #[ensures(self.a == old(self).a + 1)]
+    #[ensures(self.inner.x == old(*self).inner.x)]
+    #[ensures(self.b == old(self.b))]
+    fn bump(&mut self) {
+        self.a += 1;
+    }
+}
+
+#[requires(w.inner.y < 100)]
+#[ensures(result.inner.y == old(w).inner.y + 1)]
+fn bump_inner(mut w: Wide) -> Wide {
+    w.inner.y += 1;
+    prusti_assert!(w.inner.y == old(w).inner.y + 1);
+    w
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use prusti_contracts::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Counter {
+    value: u32,
+    limit: u32,
+    name: Point,
+}
+
+impl Point {
+    #[ensures(*self === Point { x: 5, ..old(*self) })]
+    fn set_x(&mut self) {
+        self.x = 5;
+    }
+}
+
+impl Counter {
+    #[requires(self.value < self.limit)]
+    #[ensures(*self === Counter { value: old(self.value) + 1, ..old(*self) })]
+    fn incr(&mut self) {
+        self.value += 1;
+    }
+}
+
+fn main() {
+    let mut p = Point { x: 1, y: 2 };
+    p.set_x();
+    assert!(p.x == 5 && p.y == 2);
+
+    let mut c = Counter { value: 0, limit: 10, name: p };
+    c.incr();
+    assert!(c.value == 1 && c.limit == 10);
+}
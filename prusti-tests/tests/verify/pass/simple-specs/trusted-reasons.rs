@@ -0,0 +1,45 @@
+use prusti_contracts::*;
+
+#[trusted("XOR operations are not supported")]
+#[ensures(*a == old(*b) && *b == old(*a))]
+fn xor_swap(a: &mut i32, b: &mut i32) {
+    *a ^= *b;
+    *b ^= *a;
+    *a ^= *b;
+}
+
+#[trusted("the buffer is managed by the operating system")]
+struct Buffer {
+    address: usize,
+}
+
+impl Buffer {
+    #[trusted("reads memory that is not modelled")]
+    #[ensures(result >= 0)]
+    fn first(&self) -> i32 {
+        0
+    }
+}
+
+#[extern_spec(external)]
+#[trusted("the module is not verified")]
+#[ensures(result == 42)]
+fn answer() -> i32;
+
+mod external {
+    pub fn answer() -> i32 {
+        42
+    }
+}
+
+fn main() {
+    let mut a = 1;
+    let mut b = 2;
+    xor_swap(&mut a, &mut b);
+    assert!(a == 2 && b == 1);
+
+    let buffer = Buffer { address: 0 };
+    assert!(buffer.first() >= 0);
+
+    assert!(external::answer() == 42);
+}
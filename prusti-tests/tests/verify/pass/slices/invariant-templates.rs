@@ -0,0 +1,53 @@
+use prusti_contracts::*;
+
+#[requires(a.len() > 0)]
+#[ensures(is_max_of!(result, a, 0, a.len()))]
+fn max(a: &[i32]) -> i32 {
+    let mut m = a[0];
+    let mut i = 1;
+    while i < a.len() {
+        body_invariant!(i < a.len());
+        body_invariant!(is_max_of!(m, a, 0, i));
+        if a[i] > m {
+            m = a[i];
+        }
+        i += 1;
+    }
+    m
+}
+
+#[requires(a.len() > 0)]
+#[ensures(is_min_of!(result, a, 0, a.len()))]
+fn min(a: &[i32]) -> i32 {
+    let mut m = a[0];
+    let mut i = 1;
+    while i < a.len() {
+        body_invariant!(i < a.len());
+        body_invariant!(is_min_of!(m, a, 0, i));
+        if a[i] < m {
+            m = a[i];
+        }
+        i += 1;
+    }
+    m
+}
+
+#[requires(sorted_range!(a, 2, a.len()) && a.len() > 4)]
+#[ensures(result <= a[4])]
+fn third(a: &[i32]) -> i32 {
+    a[2]
+}
+
+#[requires(sorted_range!(a) && a.len() > 0)]
+#[ensures(result <= a[a.len() - 1])]
+fn first(a: &[i32]) -> i32 {
+    a[0]
+}
+
+#[requires(swapped(a, b, i, j))]
+#[ensures(multiset_eq!(a, b))]
+fn swap_preserves_elements(a: &[i32], b: &[i32], i: usize, j: usize) {
+    slices::swapped_permutation(a, b, i, j);
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use prusti_contracts::*;
+
+#[requires(forall(|r: usize| r < m.len() ==> m[r].len() == cols))]
+#[requires(i < m.len() && j < cols)]
+fn get(m: &[&[i32]], cols: usize, i: usize, j: usize) -> i32 {
+    m[i][j]
+}
+
+#[requires(forall(|r: usize| r < m.len() ==> m[r].len() == cols))]
+fn count_zeros(m: &[&[i32]], cols: usize) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < m.len() {
+        body_invariant!(i < m.len() && count <= i * cols);
+        body_invariant!(forall(|r: usize| r < m.len() ==> m[r].len() == cols));
+        let mut j = 0;
+        while j < cols {
+            body_invariant!(i < m.len() && j < cols && count <= i * cols + j);
+            body_invariant!(forall(|r: usize| r < m.len() ==> m[r].len() == cols));
+            if m[i][j] == 0 {
+                count += 1;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+use prusti_contracts::*;
+
+#[pure]
+#[requires(i < m.len() && j < m[i].len())]
+fn get(m: &[&[i32]], i: usize, j: usize) -> i32 {
+    m[i][j]
+}
+
+#[requires(m.len() == 2 && forall(|i: usize| i < m.len() ==> m[i].len() == 2))]
+#[requires(forall(|i: usize, j: usize| (i < 2 && j < 2) ==> m[i][j] >= 0, triggers = [(m[i][j],)]))]
+#[ensures(result >= 0)]
+fn diagonal_sum(m: &[&[i32]]) -> i32 {
+    let a = m[0][0];
+    let b = m[1][1];
+    assert!(get(m, 0, 0) == a);
+    if a < 1000 && b < 1000 {
+        a + b
+    } else {
+        0
+    }
+}
+
+#[requires(forall(|i: usize, j: usize| (i < 3 && j < 3) ==> m[i][j] == 1, triggers = [(m[i][j],)]))]
+fn count_ones(m: &[[u32; 3]; 3]) -> u32 {
+    let mut count = 0;
+    let mut i = 0;
+    while i < 3 {
+        body_invariant!(i < 3 && count == 3 * i as u32);
+        body_invariant!(forall(|k: usize, l: usize| (k < 3 && l < 3) ==> m[k][l] == 1, triggers = [(m[k][l],)]));
+        let mut j = 0;
+        while j < 3 {
+            body_invariant!(i < 3 && j < 3 && count == 3 * i as u32 + j as u32);
+            body_invariant!(forall(|k: usize, l: usize| (k < 3 && l < 3) ==> m[k][l] == 1, triggers = [(m[k][l],)]));
+            count += m[i][j];
+            j += 1;
+        }
+        i += 1;
+    }
+    assert!(count == 9);
+    count
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(a.len() > 2 && a[1] == 7)]
+#[ensures(a.contains(&7))]
+fn contains_spec(a: &[i32]) {}
+
+#[requires(a.contains(&x))]
+#[ensures(exists(|i: usize| i < a.len() && a[i] == x))]
+fn contains_exists(a: &[i32], x: i32) {}
+
+#[requires(a.starts_with(b) && b.len() > 0)]
+#[ensures(a[0] == b[0])]
+fn starts_with_spec(a: &[u8], b: &[u8]) {}
+
+#[requires(a.ends_with(b) && b.len() == 1)]
+#[ensures(a[a.len() - 1] == b[0])]
+fn ends_with_spec(a: &[u8], b: &[u8]) {}
+
+#[requires(a.contains(&x) && a.ends_with(b))]
+fn call_queries(a: &[i32], b: &[i32], x: i32) {
+    assert!(a.contains(&x));
+    assert!(a.ends_with(b));
+}
@@ -0,0 +1,76 @@
+use prusti_contracts::*;
+
+#[pure]
+#[ensures(result <= s.len())]
+fn count_zeros(s: &[u32]) -> usize {
+    if s.len() == 0 {
+        0
+    } else if s[0] == 0 {
+        1 + count_zeros(&s[1..])
+    } else {
+        count_zeros(&s[1..])
+    }
+}
+
+#[ensures(result ==> forall(|i: usize| i < s.len() ==> s[i] < 10))]
+fn all_small(s: &[u32]) -> bool {
+    if s.len() == 0 {
+        true
+    } else if s[0] >= 10 {
+        false
+    } else {
+        all_small(&s[1..])
+    }
+}
+
+#[ensures(result ==> forall(|i: usize| i < s.len() ==> s[i] == 0))]
+fn all_zero_halves(s: &[u32]) -> bool {
+    if s.len() <= 1 {
+        s.len() == 0 || s[0] == 0
+    } else {
+        let mid = s.len() / 2;
+        all_zero_halves(&s[..mid]) && all_zero_halves(&s[mid..])
+    }
+}
+
+#[ensures(s.len() == old(s.len()))]
+#[ensures(forall(|i: usize| i < s.len() ==> s[i] == 0))]
+fn zero(s: &mut [u32]) {
+    if s.len() > 0 {
+        s[0] = 0;
+        zero(&mut s[1..]);
+    }
+}
+
+#[requires(s.len() > 0)]
+#[ensures(s.len() == old(s.len()))]
+#[ensures(s[0] == 0)]
+fn zero_first_through_tails(s: &mut [u32]) {
+    let len = s.len();
+    let mut k = 0;
+    while k < len {
+        body_invariant!(k < len && s.len() == len);
+        body_invariant!(k > 0 ==> s[0] == 0);
+        // A new sub-slice is created and expires in every iteration
+        let tail = &mut s[k..];
+        tail[0] = 0;
+        k += 1;
+    }
+}
+
+#[ensures(result ==> forall(|i: usize| i < s.len() ==> s[i] == 0))]
+fn all_zero_in_chunks(s: &[u32]) -> bool {
+    let mut k = 0;
+    while k < s.len() {
+        body_invariant!(k < s.len());
+        body_invariant!(forall(|i: usize| i < k ==> s[i] == 0));
+        let end = if s.len() - k > 2 { k + 2 } else { s.len() };
+        if !all_zero_halves(&s[k..end]) {
+            return false;
+        }
+        k = end;
+    }
+    true
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use prusti_contracts::*;
+
+#[requires(i < a.len() && j < a.len())]
+#[ensures(swapped(a, old(&*a), i, j))]
+fn swap(a: &mut [i32], i: usize, j: usize) {
+    let tmp = a[i];
+    a[i] = a[j];
+    a[j] = tmp;
+}
+
+#[requires(swapped(a, b, 0, 1))]
+#[ensures(a[0] == b[1] && a[1] == b[0])]
+#[ensures(permutation_of(a, b))]
+fn swapped_first_two(a: &[i32], b: &[i32]) {
+    slices::swapped_permutation(a, b, 0, 1);
+}
+
+#[requires(swapped(a, b, i, j) && swapped(b, c, k, l))]
+#[ensures(permutation_of(c, a))]
+fn two_swaps(a: &[i32], b: &[i32], c: &[i32], i: usize, j: usize, k: usize, l: usize) {
+    slices::swapped_permutation(a, b, i, j);
+    slices::swapped_permutation(b, c, k, l);
+    slices::permutation_trans(a, b, c);
+    slices::permutation_symm(a, c);
+}
+
+#[requires(a.len() == 2 && b.len() == 2 && a[0] == b[1] && a[1] == b[0])]
+#[requires(p.len() == 2 && p[0] == 1 && p[1] == 0)]
+#[requires(q.len() == 2 && q[0] == 1 && q[1] == 0)]
+#[ensures(permuted_by(a, b, p, q))]
+#[ensures(permutation_of(a, b))]
+fn reversed_pair(a: &[i32], b: &[i32], p: &[usize], q: &[usize]) {
+    slices::permuted_by_permutation(a, b, p, q);
+}
+
+fn main() {}
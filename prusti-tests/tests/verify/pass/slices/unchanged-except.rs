@@ -0,0 +1,19 @@
+use prusti_contracts::*;
+
+#[requires(i < a.len())]
+#[ensures(a[i] == value)]
+#[ensures(unchanged_except!(a, i))]
+fn set(a: &mut [i32], i: usize, value: i32) {
+    a[i] = value;
+}
+
+#[requires(a.len() == 3)]
+fn client(a: &mut [i32]) {
+    let first = a[0];
+    let last = a[2];
+    set(a, 1, 42);
+    assert!(a.len() == 3);
+    assert!(a[0] == first && a[1] == 42 && a[2] == last);
+}
+
+fn main() {}
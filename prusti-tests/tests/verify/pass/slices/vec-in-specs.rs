@@ -0,0 +1,31 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[requires(i < v.len())]
+#[ensures(result == v[i])]
+#[trusted]
+fn get(v: &Vec<u32>, i: usize) -> u32 {
+    v[i]
+}
+
+#[requires(v.len() >= 2)]
+#[requires(forall(|k: usize| k < v.len() ==> v[k] < 100))]
+#[ensures(result < 200)]
+fn sum_first_two(v: &Vec<u32>) -> u32 {
+    get(v, 0) + get(v, 1)
+}
+
+#[requires(i <= j && j <= v.len())]
+#[ensures(result == v[i..j].len())]
+fn range_len(v: &Vec<u32>, i: usize, j: usize) -> usize {
+    j - i
+}
+
+#[requires(!v.is_empty())]
+#[ensures(result == v[1..].len() + 1)]
+fn tail_len(v: &Vec<u32>) -> usize {
+    v.len()
+}
+
+fn main() {}
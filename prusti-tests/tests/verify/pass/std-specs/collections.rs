@@ -0,0 +1,60 @@
+// The specifications of `prusti_std` are loaded without `extern crate prusti_std;`.
+use prusti_contracts::*;
+use std::collections::{HashMap, VecDeque};
+
+#[ensures(result.len() == 2)]
+#[ensures(result[0] == 1 && result[1] == 2)]
+fn vec_push() -> Vec<u32> {
+    let mut v = Vec::new();
+    v.push(1);
+    v.push(2);
+    v
+}
+
+#[requires(v.len() == 2 && v[0] == 1 && v[1] == 2)]
+fn vec_pop(mut v: Vec<u32>) {
+    let last = v.pop();
+    match last {
+        Some(x) => assert!(x == 2),
+        None => unreachable!(),
+    }
+    assert!(v.len() == 1);
+    assert!(v[0] == 1);
+    v.clear();
+    assert!(v.is_empty());
+    assert!(matches!(v.pop(), None));
+}
+
+fn vec_truncate(mut v: Vec<u32>) {
+    v.truncate(3);
+    assert!(v.len() <= 3);
+}
+
+fn deque() {
+    let mut d = VecDeque::new();
+    d.push_back(1);
+    d.push_front(0);
+    assert!(d.len() == 2);
+    let front = d.pop_front();
+    assert!(matches!(front, Some(_)));
+    assert!(matches!(d.back(), Some(_)));
+    d.pop_back();
+    assert!(d.is_empty());
+    assert!(matches!(d.pop_back(), None));
+}
+
+fn map() {
+    let mut m = HashMap::new();
+    assert!(!m.contains_key(&1));
+    let previous = m.insert(1, 10);
+    assert!(matches!(previous, None));
+    assert!(m.contains_key(&1));
+    assert!(m.len() == 1);
+    assert!(matches!(m.get(&1), Some(_)));
+    let removed = m.remove(&1);
+    assert!(matches!(removed, Some(_)));
+    assert!(!m.contains_key(&1));
+    assert!(m.is_empty());
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use prusti_contracts::*;
+use std::collections::HashMap;
+
+#[requires(key.as_str() === name)]
+fn lookup_by_str(mut m: HashMap<String, u32>, key: String, name: &str) {
+    m.insert(key, 1);
+    assert!(m.contains_key(name));
+    assert!(matches!(m.get(name), Some(_)));
+    let _value = m[name];
+    m.remove(name);
+    assert!(!m.contains_key(name));
+}
+
+#[requires(key.as_slice() === bytes)]
+fn lookup_by_slice(mut m: HashMap<Vec<u8>, u32>, key: Vec<u8>, bytes: &[u8]) {
+    m.insert(key, 1);
+    assert!(m.contains_key(bytes));
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[ensures(result.len() == s.len() + 1)]
+#[ensures(result.as_str().as_bytes()[s.len()] == b'!')]
+fn exclaim(s: &str) -> String {
+    String::from(s) + "!"
+}
+
+#[ensures(result.len() == a.len() + b.len())]
+#[ensures(forall(|i: usize| i < b.len() ==> result.as_str().as_bytes()[a.len() + i] == b.as_bytes()[i]))]
+fn append(a: &str, b: &str) -> String {
+    let mut result = String::new();
+    result.push_str(a);
+    result.push_str(b);
+    result
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[requires(s.len() >= 2 && s.as_bytes()[0] == b'0' && s.as_bytes()[1] == b'x')]
+#[ensures(result == s[2..].len())]
+fn digits(s: &str) -> usize {
+    s.len() - 2
+}
+
+#[pure]
+fn is_hello(s: &str) -> bool {
+    s == "hello"
+}
+
+#[requires(is_hello(s))]
+#[ensures(result == 5)]
+fn hello_len(s: &str) -> usize {
+    s.len()
+}
+
+#[ensures(result == 5)]
+fn literal_len() -> usize {
+    "hello".len()
+}
+
+#[requires(!s.is_empty())]
+#[ensures(result == s.len())]
+fn string_len(s: &String) -> usize {
+    s.len()
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use prusti_contracts::*;
+
+#[timeout(60_000)]
+#[requires(0 <= a && a <= 1000)]
+#[ensures(result == a * a * a)]
+fn cube(a: i64) -> i64 {
+    a * a * a
+}
+
+#[requires(a < i64::MAX)]
+#[ensures(result == a + 1)]
+#[timeout(1000)]
+fn next(a: i64) -> i64 {
+    a + 1
+}
+
+fn main() {}
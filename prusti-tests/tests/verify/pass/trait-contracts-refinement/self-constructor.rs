@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+trait New {
+    fn make(x: i32, y: i32) -> Self;
+}
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[refine_trait_spec]
+impl New for Point {
+    #[ensures(result === Self { x, y })]
+    fn make(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Meters(i32);
+
+#[refine_trait_spec]
+impl New for Meters {
+    #[ensures(result === Self(x + y))]
+    fn make(x: i32, y: i32) -> Self {
+        Meters(x + y)
+    }
+}
+
+fn main() {
+    let p = Point::make(1, 2);
+    assert!(p.x == 1 && p.y == 2);
+    let m = Meters::make(1, 2);
+    assert!(m.0 == 3);
+}
@@ -0,0 +1,54 @@
+use prusti_contracts::*;
+use std::ops::Deref;
+
+#[derive(Clone, Copy)]
+struct Inner {
+    value: i32,
+}
+
+impl Inner {
+    #[pure]
+    fn get(&self) -> i32 {
+        self.value
+    }
+
+    #[ensures(result == self.value + 1)]
+    fn next(&self) -> i32 {
+        self.value + 1
+    }
+}
+
+struct Wrapper {
+    inner: Inner,
+}
+
+#[refine_trait_spec]
+impl Deref for Wrapper {
+    type Target = Inner;
+
+    #[pure]
+    fn deref(&self) -> &Inner {
+        &self.inner
+    }
+}
+
+// `w.get()` goes through a deref coercion to `Inner::get`.
+#[pure]
+fn get_through_deref(w: &Wrapper) -> i32 {
+    w.get()
+}
+
+#[requires(w.get() == 3)]
+#[ensures(result == 4)]
+fn next_through_deref(w: &Wrapper) -> i32 {
+    w.next()
+}
+
+fn main() {
+    let w = Wrapper {
+        inner: Inner { value: 3 },
+    };
+    assert!(get_through_deref(&w) == 3);
+    prusti_assert!(w.get() == 3);
+    assert!(next_through_deref(&w) == 4);
+}
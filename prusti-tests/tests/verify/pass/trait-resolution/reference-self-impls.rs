@@ -0,0 +1,57 @@
+use prusti_contracts::*;
+
+trait Measure {
+    fn measure(self) -> i32;
+}
+
+trait Bump {
+    fn bump(self, amount: i32) -> i32;
+}
+
+struct Counter {
+    value: i32,
+}
+
+#[refine_trait_spec]
+impl Measure for &Counter {
+    #[ensures(result == self.value)]
+    fn measure(self) -> i32 {
+        self.value
+    }
+}
+
+#[refine_trait_spec]
+impl Bump for &mut Counter {
+    #[requires(amount >= 0 && self.value <= 1000 && amount <= 1000)]
+    #[ensures(self.value == old(self.value) + amount)]
+    #[ensures(result == self.value)]
+    fn bump(self, amount: i32) -> i32 {
+        self.value += amount;
+        self.value
+    }
+}
+
+trait Size {
+    fn size(&self) -> usize;
+}
+
+#[refine_trait_spec]
+impl<T> Size for &[T] {
+    #[ensures(result == self.len())]
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+// The impls are selected through auto-ref of the receiver.
+fn main() {
+    let mut counter = Counter { value: 3 };
+    assert!(counter.measure() == 3);
+    assert!((&counter).measure() == 3);
+    assert!(counter.bump(2) == 5);
+    assert!(counter.value == 5);
+
+    let values = [1, 2, 3];
+    let slice: &[i32] = &values;
+    assert!(slice.size() == 3);
+}
@@ -0,0 +1,27 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[pure]
+fn slice_len(s: &[u32]) -> usize {
+    s.len()
+}
+
+#[pure]
+fn str_len(s: &str) -> usize {
+    s.len()
+}
+
+// `&v` is coerced to a slice through `<Vec<u32> as Deref>::deref`.
+#[requires(slice_len(&v) > 0)]
+#[ensures(result == v.len())]
+fn vec_len(v: &Vec<u32>) -> usize {
+    slice_len(v)
+}
+
+#[ensures(result == s.len())]
+fn string_len(s: &String) -> usize {
+    str_len(s)
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use prusti_contracts::*;
+use std::hash::{Hash, Hasher};
+
+/// Interprets the foreign trait `Hash` as a pure function of the hashed value.
+trait HashModel: Hash {
+    #[pure]
+    fn hash_model(&self) -> u64;
+}
+
+#[ghost_impl]
+impl HashModel for u32 {
+    #[pure]
+    #[trusted]
+    fn hash_model(&self) -> u64 {
+        unimplemented!()
+    }
+}
+
+#[trusted]
+#[refine_spec(where T: HashModel, [ensures(result == value.hash_model())])]
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[requires(a == b)]
+#[ensures(result)]
+fn same_hash(a: u32, b: u32) -> bool {
+    hash(&a) == hash(&b)
+}
+
+fn main() {}
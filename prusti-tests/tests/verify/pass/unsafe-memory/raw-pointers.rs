@@ -0,0 +1,50 @@
+use prusti_contracts::*;
+
+#[trusted_unsafe_memory]
+#[ensures(result == 5)]
+fn read_through_pointer() -> i32 {
+    let x = 5;
+    let p = &x as *const i32;
+    unsafe { *p }
+}
+
+#[trusted_unsafe_memory]
+#[ensures(result == 3)]
+fn write_through_pointer() -> i32 {
+    let mut x = 1;
+    let p = &mut x as *mut i32;
+    unsafe {
+        *p += 2;
+        *p
+    }
+}
+
+struct Cell {
+    value: u32,
+}
+
+#[trusted_unsafe_memory]
+#[requires((*p).value < 100)]
+#[ensures((*p).value == old((*p).value) + 1)]
+unsafe fn increment(p: *mut Cell) {
+    (*p).value += 1;
+}
+
+#[trusted_unsafe_memory]
+fn use_increment() {
+    let mut cell = Cell { value: 41 };
+    let p = &mut cell as *mut Cell;
+    unsafe {
+        increment(p);
+    }
+}
+
+#[trusted_unsafe_memory]
+fn copy_pointer() {
+    let x = 7;
+    let p = &x as *const i32;
+    let q = p;
+    assert!(unsafe { *q } == 7);
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// compile-flags: -Pcounterexample=true
+
+use prusti_contracts::*;
+
+// The temporary of the macro does not occur in the source code of `test`, so
+// it is shown at the macro call.
+macro_rules! double {
+    ($x:expr) => {{
+        let tmp = $x;
+        tmp * 2
+    }};
+}
+
+#[ensures(result != 86)]
+fn test(x: i32) -> i32 {
+    let y = x + 1;
+    double!(y)
+}
+
+fn main() {}
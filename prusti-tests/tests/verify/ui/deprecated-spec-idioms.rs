@@ -0,0 +1,28 @@
+// compile-flags: -Pspec_language_version=2
+use prusti_contracts::*;
+
+struct T {
+    f: u32,
+}
+
+#[ensures(snapshot_equality(&result, &x))]
+fn id(x: u32) -> u32 {
+    x
+}
+
+#[ensures(result === x)]
+fn id_modern(x: u32) -> u32 {
+    x
+}
+
+#[after_expiry(result => before_expiry(*result) == x.f)]
+fn reborrow(x: &mut T) -> &mut u32 {
+    &mut x.f
+}
+
+#[after_expiry(before_expiry(*result) == x.f)]
+fn reborrow_modern(x: &mut T) -> &mut u32 {
+    &mut x.f
+}
+
+fn main() {}
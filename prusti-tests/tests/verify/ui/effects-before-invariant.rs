@@ -0,0 +1,42 @@
+use prusti_contracts::*;
+
+#[requires(n < 100)]
+fn modified_before_invariant(n: u32) {
+    let mut i = 0;
+    while i < n {
+        i += 1;
+        body_invariant!(i <= n);
+    }
+}
+
+#[requires(n < 100)]
+fn loop_local_effects_before_invariant(n: u32) {
+    let mut i = 0;
+    while i < n {
+        let j = 2 * i;
+        body_invariant!(j == 2 * i && i < n);
+        i += 1;
+    }
+}
+
+#[ensures(match result {
+    Some(next) => next == i + 1,
+    None => true,
+})]
+fn successor(i: u32) -> Option<u32> {
+    if i < 10 {
+        Some(i + 1)
+    } else {
+        None
+    }
+}
+
+fn pattern_variables_before_invariant() {
+    let mut count = 0;
+    while let Some(next) = successor(count) {
+        body_invariant!(next == count + 1);
+        count = next;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use prusti_contracts::*;
+
+fn sum_of_three() -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < 3 { no_invariant_needed!(bound = 3); sum += i; i += 1; }
+    assert!(sum == 3);
+    sum
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use prusti_contracts::*;
+
+fn split_invariants() {
+    let mut i = 0;
+    let mut j = 0;
+    while i < 10 {
+        body_invariant!(i < 10 && j <= i);
+        j = i;
+        body_invariant!(j <= i);
+        i += 1;
+    }
+}
+
+fn adjacent_invariants() {
+    let mut i = 0;
+    while i < 10 {
+        body_invariant!(i < 10);
+        body_invariant!(i <= 10);
+        i += 1;
+    }
+}
+
+fn main() {}
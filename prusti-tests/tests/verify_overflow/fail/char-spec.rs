@@ -0,0 +1,24 @@
+use prusti_contracts::*;
+
+#[pure]
+fn is_digit(c: char) -> bool {
+    ('0'..='9').contains(&c)
+}
+
+#[ensures(result == is_digit(c))] //~ ERROR postcondition might not hold
+fn is_digit_or_letter(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+// Casting a character to a byte truncates all but the ASCII characters.
+fn to_byte(c: char) -> u8 {
+    c as u8 //~ ERROR value might not fit into the target type.
+}
+
+#[requires(c.is_ascii_uppercase())]
+#[ensures(result == c)] //~ ERROR postcondition might not hold
+fn lower(c: char) -> char {
+    c.to_ascii_lowercase()
+}
+
+fn main() {}
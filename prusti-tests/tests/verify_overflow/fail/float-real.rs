@@ -0,0 +1,15 @@
+// compile-flags: -Pfloat_encoding=real
+
+use prusti_contracts::*;
+
+#[requires(0.0 <= a && a <= 1e10 && 0.0 <= b && b <= 1e10)]
+#[ensures(result > a)] //~ ERROR postcondition might not hold
+fn add(a: f64, b: f64) -> f64 {
+    a + b
+}
+
+fn div(a: f64, b: f64) -> f64 {
+    a / b //~ ERROR division of floating-point numbers is not supported
+}
+
+fn main() {}
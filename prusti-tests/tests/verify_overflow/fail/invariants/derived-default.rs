@@ -0,0 +1,19 @@
+// compile-flags: -Penable_type_invariants=true
+use prusti_contracts::*;
+
+#[derive(Default)] //~ ERROR type invariants
+#[invariant(self.value >= 1)]
+struct NonZero {
+    value: u32,
+}
+
+#[derive(Default)]
+struct Pair {
+    first: u32,
+    second: u32,
+}
+
+fn main() {
+    let pair = Pair::default();
+    assert!(pair.first == 1); //~ ERROR the asserted expression might not hold
+}
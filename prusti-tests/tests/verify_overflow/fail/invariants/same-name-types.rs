@@ -0,0 +1,51 @@
+// compile-flags: -Penable_type_invariants=true
+use prusti_contracts::*;
+
+// The invariant of a type does not apply to the types with the same name in
+// other modules, or to the other types generated by the same macro.
+
+mod small {
+    use prusti_contracts::*;
+
+    #[invariant(self.value <= 10)]
+    pub struct Counter {
+        pub value: u8,
+    }
+}
+
+mod large {
+    use prusti_contracts::*;
+
+    #[invariant(self.value <= 20)]
+    pub struct Counter {
+        pub value: u8,
+    }
+}
+
+macro_rules! bounded {
+    ($module:ident, $bound:expr) => {
+        mod $module {
+            use prusti_contracts::*;
+
+            #[invariant(self.value <= $bound)]
+            pub struct Bounded {
+                pub value: u8,
+            }
+        }
+    };
+}
+
+bounded!(five, 5);
+bounded!(seven, 7);
+
+#[ensures(result <= 10)] //~ ERROR postcondition might not hold
+fn large_value(counter: &large::Counter) -> u8 {
+    counter.value
+}
+
+#[ensures(result <= 5)] //~ ERROR postcondition might not hold
+fn seven_value(bounded: &seven::Bounded) -> u8 {
+    bounded.value
+}
+
+fn main() {}
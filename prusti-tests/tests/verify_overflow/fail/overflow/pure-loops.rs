@@ -0,0 +1,40 @@
+use prusti_contracts::*;
+
+#[pure]
+fn count_below(v: &[u32], bound: u32) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < v.len() {
+        if v[i] < bound {
+            count += 1; //~ ERROR overflow
+        }
+        i += 1;
+    }
+    count
+}
+
+#[pure]
+fn count_below_wrong_invariant(v: &[u32], bound: u32) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    loop_invariant!(count < i || i == 0); //~ ERROR loop invariant might not hold after a loop iteration
+    while i < v.len() {
+        if v[i] < bound {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+#[pure]
+fn count_from(v: &[u32], start: usize) -> usize {
+    let mut i = start;
+    loop_invariant!(i <= v.len()); //~ ERROR loop invariant might not hold in the first loop iteration
+    while i < v.len() {
+        i += 1;
+    }
+    i
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+use prusti_contracts::*;
+
+trait Small {
+    #[pure]
+    fn is_small(&self) -> bool;
+}
+
+impl Small for u32 {
+    #[pure]
+    fn is_small(&self) -> bool {
+        *self < 100
+    }
+}
+
+impl Small for u64 {
+    #[pure]
+    fn is_small(&self) -> bool {
+        *self < 100
+    }
+}
+
+#[trusted]
+#[requires(x.is_small())]
+#[refine_spec(where T = u32, [
+    requires(x < 100),
+    ensures(result == x + 1),
+])]
+fn succ<T: Small>(x: T) -> T {
+    unimplemented!()
+}
+
+fn other_type() {
+    // the refinement only applies to `u32`
+    let y = succ(41u64);
+    assert!(y == 42); //~ ERROR: the asserted expression might not hold
+}
+
+fn violated_precondition() {
+    let _y = succ(100u32); //~ ERROR: precondition might not hold
+}
+
+// Generic code only establishes the generic precondition, which does not
+// imply the refined one
+#[trusted]
+#[refine_spec(where T = u32, [
+    requires(x < 100), //~ ERROR: the precondition of the type-conditional spec refinement might not be implied by the generic precondition
+])]
+fn strengthened_precondition<T>(x: T) {
+}
+
+fn main() {}
@@ -0,0 +1,61 @@
+use prusti_contracts::*;
+
+trait Even {}
+
+trait Produce {
+    #[ensures(result > 0)]
+    #[trusted]
+    fn produce(&self) -> i32;
+}
+
+struct Producer<T> {
+    t: T,
+}
+
+struct Plain;
+
+struct Marker;
+
+impl Even for Marker {}
+
+#[refine_trait_spec]
+impl<T> Produce for Producer<T> {
+    #[ensures(result >= 10)]
+    #[refine_spec(where T: Even, [
+        ensures(result % 2 == 0)
+    ])]
+    #[trusted]
+    fn produce(&self) -> i32 {
+        42
+    }
+}
+
+trait Convert {
+    #[ensures(result >= 0)]
+    #[trusted]
+    fn convert<U>(&self, u: U) -> i32;
+}
+
+#[refine_trait_spec]
+impl<T> Convert for Producer<T> {
+    #[refine_spec(where U: Even, [
+        ensures(result == 2)
+    ])]
+    #[trusted]
+    fn convert<U>(&self, u: U) -> i32 {
+        2
+    }
+}
+
+fn main() {
+    // Plain does not implement Even
+    let p = Producer { t: Plain };
+    assert!(p.produce() >= 10);
+    assert!(p.produce() % 2 == 0); //~ ERROR: the asserted expression might not hold
+
+    // The refinement is conditioned on the parameter of the method, and u8
+    // does not implement Even
+    let q = Producer { t: Marker };
+    assert!(q.convert(0u8) >= 0);
+    assert!(q.convert(0u8) == 2); //~ ERROR: the asserted expression might not hold
+}
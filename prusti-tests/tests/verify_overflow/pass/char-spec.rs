@@ -0,0 +1,58 @@
+use prusti_contracts::*;
+
+#[pure]
+fn is_hex_digit(c: char) -> bool {
+    ('0'..='9').contains(&c) || ('a'..='f').contains(&c)
+}
+
+#[requires(is_hex_digit(c))]
+#[ensures(result < 16)]
+fn hex_value(c: char) -> u32 {
+    if c <= '9' {
+        c as u32 - '0' as u32
+    } else {
+        c as u32 - 'a' as u32 + 10
+    }
+}
+
+#[ensures(result == matches!(c, 'a'..='z' | 'A'..='Z'))]
+fn is_letter(c: char) -> bool {
+    c.is_ascii_alphabetic()
+}
+
+#[ensures(result == ('0'..'8').contains(&c))]
+fn is_octal_digit(c: char) -> bool {
+    '0' <= c && c < '8'
+}
+
+#[requires(c.is_ascii_lowercase())]
+#[ensures(result.is_ascii_uppercase())]
+#[ensures(result.to_ascii_lowercase() == c)]
+fn to_upper(c: char) -> char {
+    c.to_ascii_uppercase()
+}
+
+#[ensures(c.is_ascii_digit() ==> result == Some(c as u32 - '0' as u32))]
+#[ensures(!c.is_ascii_digit() ==> result == None)]
+fn decimal_digit(c: char) -> Option<u32> {
+    c.to_digit(10)
+}
+
+#[requires(c.is_ascii())]
+#[ensures(result == c as u8)]
+fn ascii_byte(c: char) -> u8 {
+    c as u8
+}
+
+#[ensures(1 <= result && result <= 4)]
+#[ensures(c.is_ascii() ==> result == 1)]
+fn utf8_width(c: char) -> usize {
+    c.len_utf8()
+}
+
+fn from_code_points() {
+    assert!(matches!(char::from_u32(0x41), Some('A')));
+    assert!(matches!(char::from_u32(0xD800), None));
+}
+
+fn main() {}
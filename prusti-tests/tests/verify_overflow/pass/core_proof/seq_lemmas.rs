@@ -0,0 +1,35 @@
+// compile-flags: -Punsafe_core_proof=true
+
+#![allow(unused)]
+
+use prusti_contracts::{seq_lemmas::*, *};
+
+#[ensures(a.concat(b).concat(c) === a.concat(b.concat(c)))]
+fn append_three(a: Seq<u32>, b: Seq<u32>, c: Seq<u32>) {
+    concat_assoc(a, b, c);
+}
+
+#[requires(Int::new(0) <= n && n <= a.len())]
+#[ensures(a.take(n).len() + a.drop(n).len() == a.len())]
+#[ensures(a.take(n).concat(a.drop(n)) === a)]
+fn split(a: Seq<u32>, n: Int) {
+    take_drop_len(a, n);
+    take_concat_drop(a, n);
+}
+
+#[requires(a.len() == Int::new(2))]
+#[requires(b.len() == Int::new(1))]
+#[ensures(a.concat(b)[Int::new(2)] === b[Int::new(0)])]
+fn last_of_append(a: Seq<u32>, b: Seq<u32>) {
+    concat_len(a, b);
+    lookup_concat(a, b, Int::new(2));
+}
+
+#[requires(Int::new(2) <= a.len())]
+#[ensures(a.take(Int::new(2))[Int::new(1)] === a.drop(Int::new(1))[Int::new(0)])]
+fn second_element(a: Seq<u32>) {
+    lookup_take(a, Int::new(2), Int::new(1));
+    lookup_drop(a, Int::new(1), Int::new(0));
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+// compile-flags: -Punsafe_core_proof=true
+
+// The lemmas of `prusti_contracts::seq_lemmas` are trusted. Each function
+// below restates one of them without `#[trusted]`, so that its statement is
+// verified against the sequence axioms of the backend.
+
+#![allow(unused)]
+
+use prusti_contracts::*;
+
+#[ensures(a.concat(b).concat(c) === a.concat(b.concat(c)))]
+fn concat_assoc<T: Copy>(a: Seq<T>, b: Seq<T>, c: Seq<T>) {}
+
+#[ensures(a.concat(Seq::empty()) === a)]
+#[ensures(Seq::empty().concat(a) === a)]
+fn concat_empty<T: Copy>(a: Seq<T>) {}
+
+#[ensures(a.concat(b).len() == a.len() + b.len())]
+fn concat_len<T: Copy>(a: Seq<T>, b: Seq<T>) {}
+
+#[requires(Int::new(0) <= i && i < a.len() + b.len())]
+#[ensures(i < a.len() ==> a.concat(b)[i] === a[i])]
+#[ensures(i >= a.len() ==> a.concat(b)[i] === b[i - a.len()])]
+fn lookup_concat<T: Copy>(a: Seq<T>, b: Seq<T>, i: Int) {}
+
+#[requires(Int::new(0) <= n && n <= a.len())]
+#[ensures(a.take(n).len() == n)]
+#[ensures(a.drop(n).len() == a.len() - n)]
+fn take_drop_len<T: Copy>(a: Seq<T>, n: Int) {}
+
+#[requires(Int::new(0) <= n && n <= a.len())]
+#[ensures(a.take(n).concat(a.drop(n)) === a)]
+fn take_concat_drop<T: Copy>(a: Seq<T>, n: Int) {}
+
+#[requires(Int::new(0) <= i && i < n && n <= a.len())]
+#[ensures(a.take(n)[i] === a[i])]
+fn lookup_take<T: Copy>(a: Seq<T>, n: Int, i: Int) {}
+
+#[requires(Int::new(0) <= n && Int::new(0) <= i && n + i < a.len())]
+#[ensures(a.drop(n)[i] === a[n + i])]
+fn lookup_drop<T: Copy>(a: Seq<T>, n: Int, i: Int) {}
+
+#[ensures(a.concat(b).take(a.len()) === a)]
+#[ensures(a.concat(b).drop(a.len()) === b)]
+fn split_concat<T: Copy>(a: Seq<T>, b: Seq<T>) {}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use prusti_contracts::*;
+use std::collections::HashMap;
+
+#[extern_spec]
+impl<K, V, S: std::hash::BuildHasher> std::collections::HashMap<K, V, S> {
+    #[pure]
+    pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: core::borrow::Borrow<Q> + std::cmp::Eq + std::hash::Hash,
+        Q: core::hash::Hash + Eq;
+
+    #[pure]
+    fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q> + std::cmp::Eq + std::hash::Hash,
+        Q: core::hash::Hash + Eq;
+
+    #[ensures(self.contains_key(&key))]
+    #[ensures(unchanged_except!(self, key, K))]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: std::cmp::Eq + std::hash::Hash;
+}
+
+#[requires(m.contains_key(&1))]
+#[ensures(m.contains_key(&1) && m.contains_key(&2))]
+fn insert_second(m: &mut HashMap<u32, u32>) {
+    m.insert(2, 5);
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+// compile-flags: -Pfloat_encoding=real
+
+use prusti_contracts::*;
+
+#[requires(0.0 <= a && a <= 1e10 && 0.0 <= b && b <= 1e10)]
+#[ensures(result >= a && result >= b)]
+fn add_non_negative(a: f64, b: f64) -> f64 {
+    a + b
+}
+
+#[ensures(result == 0.75)]
+fn sum_of_fractions() -> f64 {
+    0.5 + 0.25
+}
+
+#[requires(1.0 < x && x < 1e10)]
+#[ensures(result > x)]
+fn square(x: f32) -> f32 {
+    x * x
+}
+
+#[ensures(result >= a && result >= b)]
+#[ensures(result == a || result == b)]
+fn max(a: f64, b: f64) -> f64 {
+    if a >= b { a } else { b }
+}
+
+fn main() {}
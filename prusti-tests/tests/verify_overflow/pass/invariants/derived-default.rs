@@ -0,0 +1,31 @@
+// compile-flags: -Penable_type_invariants=true
+use prusti_contracts::*;
+
+#[derive(Default)]
+#[invariant(self.value <= 100)]
+struct Percentage {
+    value: u8,
+}
+
+#[derive(Default)]
+struct Counter {
+    count: usize,
+    enabled: bool,
+}
+
+#[derive(Default)]
+#[invariant(!self.counter.enabled ==> self.counter.count == 0)]
+struct Progress {
+    done: Percentage,
+    counter: Counter,
+}
+
+fn main() {
+    let p = Percentage::default();
+    assert!(p.value == 0);
+
+    let progress = Progress::default();
+    assert!(progress.done.value == 0);
+    assert!(progress.counter.count == 0);
+    assert!(!progress.counter.enabled);
+}
@@ -0,0 +1,61 @@
+// compile-flags: -Penable_type_invariants=true
+use prusti_contracts::*;
+
+// Types with the same name in different modules, and types generated by the
+// same macro, each have their own invariant.
+
+mod small {
+    use prusti_contracts::*;
+
+    #[invariant(self.value <= 10)]
+    pub struct Counter {
+        pub value: u8,
+    }
+}
+
+mod large {
+    use prusti_contracts::*;
+
+    #[invariant(self.value <= 20)]
+    pub struct Counter {
+        pub value: u8,
+    }
+}
+
+macro_rules! bounded {
+    ($module:ident, $bound:expr) => {
+        mod $module {
+            use prusti_contracts::*;
+
+            #[invariant(self.value <= $bound)]
+            pub struct Bounded {
+                pub value: u8,
+            }
+        }
+    };
+}
+
+bounded!(five, 5);
+bounded!(seven, 7);
+
+#[ensures(result <= 10)]
+fn small_value(counter: &small::Counter) -> u8 {
+    counter.value
+}
+
+#[ensures(result <= 20)]
+fn large_value(counter: &large::Counter) -> u8 {
+    counter.value
+}
+
+#[ensures(result <= 5)]
+fn five_value(bounded: &five::Bounded) -> u8 {
+    bounded.value
+}
+
+#[ensures(result <= 7)]
+fn seven_value(bounded: &seven::Bounded) -> u8 {
+    bounded.value
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+#[pure]
+fn count_below(v: &[u32], bound: u32) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    loop_invariant!(count <= i && i <= v.len());
+    while i < v.len() {
+        if v[i] < bound {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+#[pure]
+#[requires(n <= 1000)]
+fn triangle(n: u32) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    loop_invariant!(i <= n && n <= 1000 && sum <= i * 1000);
+    while i < n {
+        i += 1;
+        sum += i;
+    }
+    sum
+}
+
+#[requires(v.len() == 0)]
+#[ensures(count_below(v, bound) == 0)]
+fn empty(v: &[u32], bound: u32) {}
+
+#[ensures(triangle(0) == 0)]
+fn triangle_zero() {}
+
+fn main() {}
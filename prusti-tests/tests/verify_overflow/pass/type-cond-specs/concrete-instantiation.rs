@@ -0,0 +1,48 @@
+use prusti_contracts::*;
+
+struct Wrapper<T> {
+    value: T,
+}
+
+impl<T> Wrapper<T> {
+    #[trusted]
+    #[ensures(result == 1)]
+    #[refine_spec(where T = u32, [
+        ensures(_x > 5 ==> result < _x),
+    ])]
+    fn scale(&self, _x: T) -> u32 {
+        1
+    }
+}
+
+trait Small {
+    #[pure]
+    fn is_small(&self) -> bool;
+}
+
+impl Small for u32 {
+    #[pure]
+    fn is_small(&self) -> bool {
+        *self < 100
+    }
+}
+
+#[trusted]
+#[requires(x.is_small())]
+#[refine_spec(where T = u32, U = bool, [
+    requires(x < 100),
+    ensures(result == x + 1),
+])]
+fn succ<T: Small, U>(x: T, _flag: U) -> T {
+    unimplemented!()
+}
+
+fn main() {
+    let w = Wrapper { value: 3u32 };
+    let r = w.scale(10u32);
+    assert!(r == 1);
+    assert!(r < 10);
+
+    let y = succ(41u32, true);
+    assert!(y == 42);
+}
@@ -0,0 +1,76 @@
+use prusti_contracts::*;
+
+trait Even {}
+
+trait Produce {
+    #[ensures(result > 0)]
+    #[trusted]
+    fn produce(&self) -> i32;
+}
+
+struct Producer<T> {
+    t: T,
+}
+
+struct Marker;
+
+impl Even for Marker {}
+
+#[refine_trait_spec]
+impl<T> Produce for Producer<T> {
+    #[ensures(result >= 10)]
+    #[refine_spec(where T: Even, [
+        ensures(result % 2 == 0)
+    ])]
+    #[trusted]
+    fn produce(&self) -> i32 {
+        42
+    }
+}
+
+trait Convert {
+    #[ensures(result >= 0)]
+    #[trusted]
+    fn convert<U>(&self, u: U) -> i32;
+}
+
+#[refine_trait_spec]
+impl<T> Convert for Producer<T> {
+    #[refine_spec(where U: Even, [
+        ensures(result == 2)
+    ])]
+    #[trusted]
+    fn convert<U>(&self, u: U) -> i32 {
+        2
+    }
+}
+
+trait Container<T> {
+    #[refine_spec(where T: Copy, [
+        ensures(result >= 0)
+    ])]
+    #[trusted]
+    fn size(&self) -> i32;
+}
+
+struct Bag;
+
+impl<T> Container<T> for Bag {
+    fn size(&self) -> i32 {
+        0
+    }
+}
+
+fn main() {
+    let p = Producer { t: Marker };
+    let r = p.produce();
+    assert!(r >= 10 && r % 2 == 0);
+
+    // The refinement is conditioned on the parameter of the method, not the
+    // one of the impl
+    let q = Producer { t: 0u8 };
+    assert!(q.convert(Marker) == 2);
+
+    let b = Bag;
+    assert!(Container::<i32>::size(&b) >= 0);
+}
@@ -29,6 +29,27 @@ pub struct Optimizations {
     pub clean_cfg: bool,
 }
 
+/// The model of the hash function `prusti_contracts::hash_model::hash`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashModel {
+    /// The hash is an unknown, but deterministic, function of the value.
+    Uninterpreted,
+    /// The hash of an unsigned integer (of at most 64 bits) is the integer
+    /// itself; the hash of any other value is uninterpreted.
+    Identity,
+}
+
+/// The encoding of the floating-point types `f32` and `f64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatEncoding {
+    /// Floating-point numbers are encoded as IEEE 754 floating-point numbers
+    /// of the SMT solver, which is precise but slow.
+    Ieee,
+    /// Floating-point numbers are approximated by real numbers, ignoring
+    /// rounding, infinities and NaN; this is unsound, but much faster.
+    Real,
+}
+
 impl Optimizations {
     fn all_disabled() -> Self {
         Optimizations {
@@ -82,7 +103,11 @@ lazy_static::lazy_static! {
         settings.set_default("check_panics", true).unwrap();
         settings.set_default("encode_unsigned_num_constraint", true).unwrap();
         settings.set_default("encode_bitvectors", false).unwrap();
+        settings.set_default("hash_model", "uninterpreted").unwrap();
+        settings.set_default("float_encoding", "ieee").unwrap();
         settings.set_default("simplify_encoding", true).unwrap();
+        settings.set_default("predicate_unfold_depth", 1).unwrap();
+        settings.set_default::<Option<u32>>("loop_unrolling_bound", None).unwrap();
         settings.set_default("log", "").unwrap();
         settings.set_default("log_style", "auto").unwrap();
         settings.set_default("log_dir", "log").unwrap();
@@ -102,6 +127,7 @@ lazy_static::lazy_static! {
         settings.set_default::<Vec<String>>("extra_verifier_args", vec![]).unwrap();
         settings.set_default("quiet", false).unwrap();
         settings.set_default("assert_timeout", 10_000).unwrap();
+        settings.set_default::<Vec<String>>("assert_timeout_overrides", vec![]).unwrap();
         settings.set_default("smt_qi_eager_threshold", 1000).unwrap();
         settings.set_default("use_more_complete_exhale", true).unwrap();
         settings.set_default("skip_unsupported_features", false).unwrap();
@@ -110,6 +136,10 @@ lazy_static::lazy_static! {
         settings.set_default("no_verify", false).unwrap();
         settings.set_default("no_verify_deps", false).unwrap();
         settings.set_default("opt_in_verification", false).unwrap();
+        settings.set_default("verify_only_annotated", false).unwrap();
+        settings.set_default("strict_spec_attributes", false).unwrap();
+        settings.set_default("spec_language_version", 1).unwrap();
+        settings.set_default("load_std_specs", true).unwrap();
         settings.set_default("full_compilation", false).unwrap();
         settings.set_default("json_communication", false).unwrap();
         settings.set_default("optimizations", "all").unwrap();
@@ -141,9 +171,16 @@ lazy_static::lazy_static! {
         settings.set_default::<Option<String>>("dump_fold_unfold_state_of_blocks", None).unwrap();
         settings.set_default("print_hash", false).unwrap();
         settings.set_default("enable_cache", true).unwrap();
+        settings.set_default::<Option<String>>("record_session", None).unwrap();
+        settings.set_default("contract_coverage", false).unwrap();
+        settings.set_default::<Option<String>>("trust_audit_report", None).unwrap();
+        settings.set_default::<Option<String>>("verification_report", None).unwrap();
+        settings.set_default("report_unreachable_branches", false).unwrap();
+        settings.set_default("report_vacuous_specs", false).unwrap();
 
         settings.set_default("cargo_path", "cargo").unwrap();
         settings.set_default("cargo_command", "check").unwrap();
+        settings.set_default("html_report", false).unwrap();
 
         // Flags for testing.
         settings.set_default::<Option<i64>>("verification_deadline", None).unwrap();
@@ -206,6 +243,7 @@ lazy_static::lazy_static! {
             Environment::with_prefix("PRUSTI")
                 .ignore_empty(true)
                 .try_parsing(true)
+                .with_list_parse_key("assert_timeout_overrides")
                 .with_list_parse_key("delete_basic_blocks")
                 .with_list_parse_key("extra_jvm_args")
                 .with_list_parse_key("extra_verifier_args")
@@ -372,6 +410,20 @@ pub fn simplify_encoding() -> bool {
     read_setting("simplify_encoding")
 }
 
+/// The default number of times the body of a recursive predicate is unfolded
+/// into its own definition. Can be overridden per predicate with
+/// `#[unfold_depth(n)]`.
+pub fn predicate_unfold_depth() -> u32 {
+    read_setting("predicate_unfold_depth")
+}
+
+/// When set, loops without specifications are unrolled this many times in the
+/// encoding instead of requiring a loop invariant. Verification then fails if
+/// such a loop might iterate more often.
+pub fn loop_unrolling_bound() -> Option<u32> {
+    read_setting("loop_unrolling_bound")
+}
+
 /// When enabled, debug files will be created.
 pub fn dump_debug_info() -> bool {
     read_setting("dump_debug_info")
@@ -453,6 +505,48 @@ pub fn cache_path() -> PathBuf {
     PathBuf::from(read_setting::<String>("cache_path"))
 }
 
+/// If this is set to a path, every verification request is recorded together
+/// with its result into a session archive in the given directory. The archive
+/// can be replayed without the original crate using `prusti-replay`.
+pub fn record_session() -> Option<PathBuf> {
+    read_setting::<Option<String>>("record_session").map(PathBuf::from)
+}
+
+/// When enabled, the verification report tells for every contract clause of
+/// the verified functions whether it is needed by at least one proof. The
+/// verification is repeated once for every clause.
+pub fn contract_coverage() -> bool {
+    read_setting("contract_coverage")
+}
+
+/// If this is set to the path of a directory, a JSON report of the trusted
+/// items of every compiled crate is written to it, with the reasons given
+/// with `#[trusted("reason")]`, the locations and the signatures.
+pub fn trust_audit_report() -> Option<PathBuf> {
+    read_setting::<Option<String>>("trust_audit_report").map(PathBuf::from)
+}
+
+/// If this is set to the path of a directory, a JSON report with the status,
+/// the verification time and the errors of every verified function of every
+/// compiled crate is written to it.
+pub fn verification_report() -> Option<PathBuf> {
+    read_setting::<Option<String>>("verification_report").map(PathBuf::from)
+}
+
+/// When enabled, Prusti reports the branches that the contracts and
+/// invariants make unreachable, and the functions whose precondition cannot be
+/// satisfied.
+pub fn report_unreachable_branches() -> bool {
+    read_setting("report_unreachable_branches")
+}
+
+/// When enabled, Prusti reports the functions whose precondition cannot be
+/// satisfied and the reachable `prusti_assume!` statements that make the code
+/// after them unreachable.
+pub fn report_vacuous_specs() -> bool {
+    read_setting("report_vacuous_specs")
+}
+
 /// When enabled, binary operations and numeric casts will be checked for
 /// overflows.
 pub fn check_overflows() -> bool {
@@ -472,6 +566,35 @@ pub fn encode_bitvectors() -> bool {
     read_setting("encode_bitvectors")
 }
 
+/// The model of the hash function `prusti_contracts::hash_model::hash`, which
+/// is used by the specifications of hash-based collections. Either
+/// `uninterpreted` or `identity`.
+pub fn hash_model() -> HashModel {
+    let hash_model_string = read_setting::<String>("hash_model");
+    match hash_model_string.to_lowercase().trim() {
+        "uninterpreted" => HashModel::Uninterpreted,
+        "identity" => HashModel::Identity,
+        other => {
+            warn!("Ignoring unknown hash model '{}'", other);
+            HashModel::Uninterpreted
+        }
+    }
+}
+
+/// The encoding of floating-point numbers. Either `ieee` (precise) or `real`
+/// (an approximation by real numbers, which does not support division).
+pub fn float_encoding() -> FloatEncoding {
+    let float_encoding_string = read_setting::<String>("float_encoding");
+    match float_encoding_string.to_lowercase().trim() {
+        "ieee" => FloatEncoding::Ieee,
+        "real" => FloatEncoding::Real,
+        other => {
+            warn!("Ignoring unknown float encoding '{}'", other);
+            FloatEncoding::Ieee
+        }
+    }
+}
+
 /// Additional arguments to pass to the JVM when launching a verifier backend.
 pub fn extra_jvm_args() -> Vec<String> {
     read_setting("extra_jvm_args")
@@ -495,6 +618,25 @@ pub fn assert_timeout() -> u64 {
     read_setting("assert_timeout")
 }
 
+/// Overrides of `ASSERT_TIMEOUT` for the items of some modules, given as a
+/// list of entries of the form `"path=ms"`. The override applies to the items
+/// whose definition path starts with `path`, e.g. `"my_crate::solver=60000"`.
+/// If several entries match an item, the one with the longest path is used.
+/// A `#[timeout(ms)]` attribute on an item takes precedence over this list.
+pub fn assert_timeout_overrides() -> Vec<(String, u64)> {
+    let mut overrides = vec![];
+    for entry in read_setting::<Vec<String>>("assert_timeout_overrides") {
+        let parsed = entry
+            .rsplit_once('=')
+            .and_then(|(path, ms)| Some((path.trim().to_string(), ms.trim().parse().ok()?)));
+        match parsed {
+            Some(entry) => overrides.push(entry),
+            None => warn!("Ignoring invalid assert timeout override '{}'", entry),
+        }
+    }
+    overrides
+}
+
 /// Set `qi.eager_threshold` value to the given one.
 pub fn smt_qi_eager_threshold() -> u64 {
     read_setting("smt_qi_eager_threshold")
@@ -995,6 +1137,33 @@ pub fn opt_in_verification() -> bool {
     read_setting("opt_in_verification")
 }
 
+/// When enabled, only the functions that have a specification or whose body
+/// contains specifications are verified. The other functions are not encoded
+/// at all.
+pub fn verify_only_annotated() -> bool {
+    read_setting("verify_only_annotated")
+}
+
+/// When enabled, Prusti attributes in positions where they have no effect
+/// (e.g. `#[pure]` on a closure) are reported as errors instead of being
+/// silently ignored.
+pub fn strict_spec_attributes() -> bool {
+    read_setting("strict_spec_attributes")
+}
+
+/// The version of the specification language the verified code is written in.
+/// Prusti warns about idioms that are deprecated in this version.
+pub fn spec_language_version() -> u32 {
+    read_setting("spec_language_version")
+}
+
+/// When enabled, the specifications shipped with Prusti for the standard
+/// library (the `prusti_std` crate) are loaded even if the verified crate
+/// does not contain `extern crate prusti_std;`.
+pub fn load_std_specs() -> bool {
+    read_setting("load_std_specs")
+}
+
 /// When enabled, compilation will continue and a binary will be generated
 /// after Prusti terminates.
 pub fn full_compilation() -> bool {
@@ -1018,6 +1187,13 @@ pub fn cargo_command() -> String {
     read_setting("cargo_command")
 }
 
+/// When enabled, `cargo prusti` writes a static HTML report of the
+/// verification results and the trusted items to
+/// `target/verify/report/index.html`.
+pub fn html_report() -> bool {
+    read_setting("html_report")
+}
+
 /// When enabled, type invariants can be declared on types using the
 /// `#[invariant(...)]` attribute.
 pub fn enable_type_invariants() -> bool {
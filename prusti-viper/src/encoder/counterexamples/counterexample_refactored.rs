@@ -9,20 +9,45 @@ pub struct CounterexampleEntry {
     name: Option<String>,
     /// history of all Variables with Span
     history: Vec<(Entry, MultiSpan)>,
+    /// Names and values of the arguments, if this is a pure function call.
+    arguments: Vec<(String, Entry)>,
 }
 
 impl CounterexampleEntry {
     pub fn new(name: Option<String>, history: Vec<(Entry, MultiSpan)>) -> Self {
-        CounterexampleEntry { name, history }
+        CounterexampleEntry {
+            name,
+            history,
+            arguments: Vec::new(),
+        }
+    }
+    pub fn with_arguments(
+        name: String,
+        arguments: Vec<(String, Entry)>,
+        value: (Entry, MultiSpan),
+    ) -> Self {
+        CounterexampleEntry {
+            name: Some(name),
+            history: vec![value],
+            arguments,
+        }
     }
     fn history_to_string(&self) -> Vec<String> {
         let mut messages = Vec::new();
         for (value, _) in &self.history {
-            messages.push(format!(
+            let mut message = format!(
                 "counterexample for \"{}\"\n value:   {}",
                 self.name.as_ref().unwrap_or(&"result".to_string()),
                 indented_debug(value)
-            ));
+            );
+            for (name, argument) in &self.arguments {
+                message.push_str(&format!(
+                    "\n argument \"{}\": {}",
+                    name,
+                    indented_debug(argument)
+                ));
+            }
+            messages.push(message);
         }
         messages
     }
@@ -1,4 +1,7 @@
-use super::{super::high::types::HighTypeEncoderInterface, counterexample::*};
+use super::{
+    super::high::types::HighTypeEncoderInterface, counterexample::*,
+    surface_names::surface_variables,
+};
 use crate::encoder::{
     counterexamples::*,
     places::{Local, LocalVariableManager},
@@ -187,18 +190,10 @@ impl<'ce, 'tcx> CounterexampleTranslator<'ce, 'tcx> {
         encoder: &Encoder<'_, 'tcx>,
     ) -> Vec<(String, Span, String, Ty<'tcx>, String, bool)> {
         let mut entries_to_process = vec![];
-        for vdi in &self.var_debug_info {
-            let rust_name = vdi.name.to_ident_string();
-            let span = vdi.source_info.span;
-            let local: mir::Local = if let mir::VarDebugInfoContents::Place(place) = vdi.value {
-                if let Some(local) = place.as_local() {
-                    local
-                } else {
-                    continue;
-                }
-            } else {
-                continue;
-            };
+        for surface_variable in surface_variables(&self.var_debug_info) {
+            let rust_name = surface_variable.name;
+            let span = surface_variable.span;
+            let local = surface_variable.local;
             let index = local.index();
             let var_local = Local::from(local);
             let typ = self.local_variable_manager.get_type(var_local);
@@ -1,6 +1,7 @@
-use super::{counterexample_refactored::*, VarMapping, VarMappingInterface};
+use super::{
+    counterexample_refactored::*, surface_names::surface_variables, VarMapping, VarMappingInterface,
+};
 use crate::encoder::{
-    counterexamples::mapping::PureFunction,
     errors::PositionManager,
     mir::{pure::PureFunctionEncoderInterface, specifications::SpecificationsInterface},
     places::{Local, LocalVariableManager},
@@ -131,18 +132,9 @@ impl<'ce, 'tcx, 'v> CounterexampleTranslator<'ce, 'tcx, 'v> {
         //variables
         let mut entries = vec![];
 
-        for vdi in &self.var_debug_info {
-            let rust_name = vdi.name.to_ident_string();
-            let local: mir::Local = if let mir::VarDebugInfoContents::Place(place) = vdi.value {
-                if let Some(local) = place.as_local() {
-                    local
-                } else {
-                    continue;
-                }
-            } else {
-                continue;
-            };
-            let var_local = Local::from(local);
+        for surface_variable in surface_variables(&self.var_debug_info) {
+            let rust_name = surface_variable.name;
+            let var_local = Local::from(surface_variable.local);
             let typ = self.local_variable_manager.get_type(var_local);
             let vir_name = self.local_variable_manager.get_name(var_local);
             let trace = self.get_trace_of_var(position_manager, &vir_name, label_markers);
@@ -166,60 +158,98 @@ impl<'ce, 'tcx, 'v> CounterexampleTranslator<'ce, 'tcx, 'v> {
                 relevant_pure_functions.extend(val.iter());
             }
         }
+        let mut reported_pure_functions = vec![];
         for pure_fn in relevant_pure_functions {
-            let ce_pure_fn = self.process_pure_function(pure_fn, position_manager);
-            let mut pure_fn_name = format!(
-                "{}()",
-                pure_fn
-                    .name
-                    .trim_start_matches("caller_for$m_")
-                    .trim_end_matches('$')
-            );
-            pure_fn_name = pure_fn_name.split('$').last().unwrap().to_string(); //remove prefix of functions in implementations
-            entries.push(CounterexampleEntry::new(
-                Some(pure_fn_name),
-                vec![ce_pure_fn],
+            let sil_arguments: Vec<_> = pure_fn
+                .args
+                .iter()
+                .map(|arg| self.silicon_counterexample.model.entries.get(arg).cloned())
+                .collect();
+            let return_value = self
+                .silicon_counterexample
+                .functions
+                .entries
+                .get(&pure_fn.name)
+                .and_then(|function| function.get_function_value(&sil_arguments).as_ref());
+            let span = self.get_span(position_manager, &pure_fn.position);
+            entries.push(self.process_pure_function(
+                &pure_fn.name,
+                &sil_arguments,
+                return_value,
+                span,
             ));
+            reported_pure_functions.push(pure_fn.name.as_str());
+        }
+
+        //evaluations of pure functions which are not called by the executed
+        //code, e.g. in the failing specification
+        let mut evaluated_pure_functions = self
+            .silicon_counterexample
+            .functions
+            .entries
+            .iter()
+            .filter(|(name, _)| {
+                name.starts_with("caller_for$") && !reported_pure_functions.contains(&name.as_str())
+            })
+            .collect::<Vec<_>>();
+        evaluated_pure_functions.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        for (name, function) in evaluated_pure_functions {
+            let Some(fn_proc_id) = self.get_pure_function_proc_id(name) else {
+                continue;
+            };
+            let span = MultiSpan::from_span(self.encoder.env().query.get_def_span(fn_proc_id));
+            for (sil_arguments, return_value) in &function.options {
+                entries.push(self.process_pure_function(
+                    name,
+                    sil_arguments,
+                    return_value.as_ref(),
+                    span.clone(),
+                ));
+            }
         }
 
         entries
     }
 
+    fn get_pure_function_proc_id(&self, name: &str) -> Option<ProcedureDefId> {
+        self.encoder
+            .get_proc_def_id(name.trim_start_matches("caller_for$").to_string())
+    }
+
+    /// Translates a call of a pure function with the given arguments and
+    /// return value.
     fn process_pure_function(
         &self,
-        pure_fn: &PureFunction,
-        position_manager: &PositionManager,
-    ) -> (Entry, MultiSpan) {
-        let typ = self
-            .encoder
-            .get_proc_def_id(pure_fn.name.trim_start_matches("caller_for$").to_string())
-            .map(|fn_proc_id| {
-                self.tcx
-                    .fn_sig(fn_proc_id)
-                    .subst_identity()
-                    .skip_binder()
-                    .output()
-            });
-        let sil_arguments = pure_fn
-            .args
-            .iter()
-            .map(|arg| self.silicon_counterexample.model.entries.get(arg).cloned())
-            .collect();
-
-        let return_value = if let Some(function) = self
-            .silicon_counterexample
-            .functions
-            .entries
-            .get(&pure_fn.name)
-        {
-            function.get_function_value(&sil_arguments)
-        } else {
-            &None
-        };
-        (
-            self.translate_snapshot_entry(return_value.as_ref(), typ, true),
-            self.get_span(position_manager, &pure_fn.position),
-        )
+        name: &str,
+        sil_arguments: &[Option<ModelEntry>],
+        return_value: Option<&ModelEntry>,
+        span: MultiSpan,
+    ) -> CounterexampleEntry {
+        let fn_proc_id = self.get_pure_function_proc_id(name);
+        let fn_sig =
+            fn_proc_id.map(|fn_proc_id| self.tcx.fn_sig(fn_proc_id).subst_identity().skip_binder());
+        let mut arguments = vec![];
+        if let (Some(fn_proc_id), Some(fn_sig)) = (fn_proc_id, fn_sig) {
+            let arg_names = self.tcx.fn_arg_names(fn_proc_id);
+            if arg_names.len() == sil_arguments.len() {
+                for ((arg_name, arg_typ), sil_argument) in
+                    iter::zip(iter::zip(arg_names, fn_sig.inputs()), sil_arguments)
+                {
+                    let entry =
+                        self.translate_snapshot_entry(sil_argument.as_ref(), Some(*arg_typ), true);
+                    arguments.push((arg_name.to_string(), entry));
+                }
+            }
+        }
+        let mut pure_fn_name = format!(
+            "{}()",
+            name.trim_start_matches("caller_for$m_")
+                .trim_end_matches('$')
+        );
+        pure_fn_name = pure_fn_name.split('$').last().unwrap().to_string(); //remove prefix of functions in implementations
+        let value =
+            self.translate_snapshot_entry(return_value, fn_sig.map(|sig| sig.output()), true);
+        CounterexampleEntry::with_arguments(pure_fn_name, arguments, (value, span))
     }
 
     fn process_entry(
@@ -1,6 +1,7 @@
 mod discriminants_interface;
 mod interface;
 mod mapping;
+mod surface_names;
 
 pub(crate) use self::{
     discriminants_interface::{DiscriminantsState, DiscriminantsStateInterface},
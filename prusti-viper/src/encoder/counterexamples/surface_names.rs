@@ -0,0 +1,43 @@
+use prusti_rustc_interface::{
+    middle::mir::{self, VarDebugInfoContents},
+    span::Span,
+};
+
+/// The prefix of the names of the variables introduced by the rewriting of the
+/// specification macros, such as `_prusti_closure` for `closure!(..)`.
+const PRUSTI_VARIABLE_PREFIX: &str = "_prusti_";
+
+/// A local variable of a MIR body, together with the name and the span under
+/// which it occurs in the source code.
+pub(crate) struct SurfaceVariable {
+    pub(crate) local: mir::Local,
+    pub(crate) name: String,
+    pub(crate) span: Span,
+}
+
+/// Returns the local variables of the body that are shown in counterexamples,
+/// with their names and spans mapped back to the source code.
+///
+/// Variables which are introduced by the rewriting of the specification
+/// macros are shown without the `_prusti_` prefix of their names. Variables
+/// which are introduced by macros or by the desugaring of the compiler (e.g.
+/// the iterator of a `for` loop) are shown at the macro call or the desugared
+/// construct in the source code.
+pub(crate) fn surface_variables(var_debug_info: &[mir::VarDebugInfo]) -> Vec<SurfaceVariable> {
+    var_debug_info
+        .iter()
+        .filter_map(|vdi| {
+            let VarDebugInfoContents::Place(place) = vdi.value else {
+                return None;
+            };
+            let local = place.as_local()?;
+            let name = vdi.name.to_ident_string();
+            let name = match name.strip_prefix(PRUSTI_VARIABLE_PREFIX) {
+                Some(surface_name) => surface_name.to_string(),
+                None => name,
+            };
+            let span = vdi.source_info.span.source_callsite();
+            Some(SurfaceVariable { local, name, span })
+        })
+        .collect()
+}
@@ -4,7 +4,7 @@ use super::{
     Encoder,
 };
 use crate::encoder::high::types::HighTypeEncoderInterface;
-use prusti_common::vir_local;
+use prusti_common::{config, vir_local};
 use prusti_rustc_interface::span::Span;
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -667,7 +667,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> Collector<'p, 'v, 'tcx> {
             .map(|(k, v)| (k.to_string(), v.to_string()))
             .collect(),
         };
-        let mut backend_types = vec![f32, f64];
+        // With the real-number encoding, floats do not need a backend type.
+        let mut backend_types = if config::float_encoding() == config::FloatEncoding::Ieee {
+            vec![f32, f64]
+        } else {
+            vec![]
+        };
         for size in &[8, 16, 32, 64, 128] {
             let name = format!("BitVectorDomain{size}");
             let typ =
@@ -19,9 +19,10 @@ use prusti_common::report::log;
 use prusti_interface::data::ProcedureDefId;
 use prusti_interface::environment::Environment;
 use prusti_interface::specs::typed;
-use prusti_interface::PrustiError;
+use prusti_interface::{PrustiError, UnsupportedFeature};
 use vir_crate::polymorphic::{self as vir};
 use vir_crate::common::identifier::WithIdentifier;
+use prusti_rustc_interface::hir;
 use prusti_rustc_interface::hir::def_id::DefId;
 use prusti_rustc_interface::middle::mir;
 use prusti_rustc_interface::middle::ty;
@@ -31,6 +32,7 @@ use rustc_hash::{FxHashSet, FxHashMap};
 use std::io::Write;
 use std::rc::Rc;
 use crate::encoder::stub_procedure_encoder::StubProcedureEncoder;
+use crate::encoder::refinement_check_encoder::RefinementCheckEncoder;
 use std::ops::AddAssign;
 use prusti_interface::specs::typed::ProcedureSpecificationKind;
 use crate::encoder::name_interner::NameInterner;
@@ -104,6 +106,10 @@ pub struct Encoder<'v, 'tcx: 'v> {
     /// this requires special care when encoding array/slice accesses which may come with
     /// bound checks included in the MIR.
     pub(super) is_encoding_trigger: Cell<bool>,
+    /// The functions whose calls are summarized with `call_summary!(..)`, by
+    /// the name of the function encoding the summaries.
+    pub(super) call_summary_callees:
+        RefCell<FxHashMap<String, (ProcedureDefId, ty::subst::SubstsRef<'tcx>)>>,
 }
 
 pub enum EncodingTask<'tcx> {
@@ -179,6 +185,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             encoding_errors_counter: RefCell::new(0),
             name_interner: RefCell::new(NameInterner::new()),
             is_encoding_trigger: Cell::new(false),
+            call_summary_callees: RefCell::new(FxHashMap::default()),
             specifications_state: SpecificationsState::new(def_spec),
             mir_procedure_mapping: Default::default(),
             discriminants_state: Default::default(),
@@ -280,6 +287,10 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         identifier
     }
 
+    pub(super) fn contains_function(&self, identifier: &vir::FunctionIdentifier) -> bool {
+        self.functions.borrow().contains_key(identifier)
+    }
+
     pub(super) fn get_function(&self, identifier: &vir::FunctionIdentifier) -> SpannedEncodingResult<Rc<vir::Function>> {
         self.ensure_pure_function_encoded(identifier)?;
         if self.functions.borrow().contains_key(identifier) {
@@ -336,6 +347,22 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         opt_scalar_value.ok_or_else(|| EncodingError::unsupported(format!("unsupported constant value: {value:?}")))
     }
 
+    /// Extract the bytes of a string literal.
+    pub fn const_eval_str_bytes(
+        &self,
+        value: mir::ConstantKind<'tcx>,
+    ) -> EncodingResult<Vec<u8>> {
+        match value {
+            mir::ConstantKind::Val(mir::interpret::ConstValue::Slice { data, start, end }, _) => {
+                Ok(data
+                    .inner()
+                    .inspect_with_uninit_and_ptr_outside_interpreter(start..end)
+                    .to_vec())
+            }
+            _ => error_unsupported!("unsupported string constant: {:?}", value),
+        }
+    }
+
     /// Encodes a value in a field if the base expression is a reference or
     /// a primitive types.
     /// For composed data structures, the base expression is returned.
@@ -345,7 +372,8 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             | ty::TyKind::Closure(_, _)
             | ty::TyKind::Array(..)
             | ty::TyKind::Tuple(_)
-            | ty::TyKind::Param(_) => {
+            | ty::TyKind::Param(_)
+            | ty::TyKind::Dynamic(..) => {
                 Ok(base) // don't use a field for tuples and ADTs
             }
             _ => {
@@ -577,6 +605,29 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         Ok(())
     }
 
+    /// Encodes the check that the type-conditional spec refinement of the
+    /// trusted procedure for concrete types refines its generic contract, if
+    /// there is such a refinement.
+    fn encode_refinement_check(&mut self, proc_name: String, def_id: ProcedureDefId) {
+        match RefinementCheckEncoder::new(self, def_id).encode() {
+            Ok(Some(method)) => {
+                self.procedures.borrow_mut().insert(def_id, method);
+                match self.finalize_viper_program(proc_name, def_id) {
+                    Ok(program) => self.programs.push(program),
+                    Err(error) => {
+                        self.register_encoding_error(error);
+                        debug!("Error finalizing program: {:?}", def_id);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(error) => {
+                self.register_encoding_error(error);
+                debug!("Error encoding refinement check: {:?}", def_id);
+            }
+        }
+    }
+
     /// Checks whether the given type implements structural equality
     /// by either being a primitive type or by deriving the Eq trait.
     pub fn has_structural_eq_impl(&self, ty: ty::Ty<'tcx>) -> bool {
@@ -626,6 +677,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             ty::TyKind::Uint(ty::UintTy::Usize) => scalar_value.to_target_usize(&self.env.tcx()).unwrap().into(),
             ty::TyKind::Float(ty::FloatTy::F32) => {
                 let bits = scalar_value.to_u32().unwrap();
+                Self::check_float_constant_encodable(f32::from_bits(bits).is_finite())?;
                 vir::Expr::Const(vir::ConstExpr {
                     value: vir::Const::Float(vir::FloatConst::F32(bits)),
                     position: vir::Position::default(),
@@ -633,6 +685,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             },
             ty::TyKind::Float(ty::FloatTy::F64) => {
                 let bits = scalar_value.to_u64().unwrap();
+                Self::check_float_constant_encodable(f64::from_bits(bits).is_finite())?;
                 vir::Expr::Const(vir::ConstExpr {
                     value: vir::Const::Float(vir::FloatConst::F64(bits)),
                     position: vir::Position::default(),
@@ -651,6 +704,17 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         Ok(expr)
     }
 
+    /// Infinite and NaN constants cannot be approximated by real numbers.
+    fn check_float_constant_encodable(is_finite: bool) -> EncodingResult<()> {
+        if !is_finite && config::float_encoding() == config::FloatEncoding::Real {
+            error_unsupported!(
+                "infinite and NaN floating-point constants are not supported with \
+                `float_encoding=real`; use `float_encoding=ieee` instead"
+            );
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip(self), ret)]
     pub fn encode_int_cast(&self, value: u128, ty: ty::Ty<'tcx>) -> vir::Expr {
         let expr = match ty.kind() {
@@ -713,6 +777,28 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
                     info!("Encoding: {} ({})", proc_name, proc_def_path);
                     assert!(substs.is_empty());
 
+                    if let Some(generator_kind) = self.env.query.get_generator_kind(proc_def_id) {
+                        let enclosing_fn = self.env.query.get_enclosing_fn(proc_def_id);
+                        if self.is_trusted(enclosing_fn, None) {
+                            debug!(
+                                "Generator in a trusted procedure will not be encoded or verified: {:?}",
+                                proc_def_id
+                            );
+                        } else {
+                            let kind_name = match generator_kind {
+                                hir::GeneratorKind::Gen => "generators",
+                                hir::GeneratorKind::Async(_) => "async blocks and closures",
+                            };
+                            let error = UnsupportedFeature::new_plural(
+                                kind_name,
+                                self.env.query.get_def_span(proc_def_id),
+                            )
+                            .with_trusted_workaround();
+                            self.register_encoding_error(error.into());
+                        }
+                        continue;
+                    }
+
                     if config::unsafe_core_proof() {
                         if self.env.query.is_unsafe_function(proc_def_id) {
                             if let Err(error) = self.encode_lifetimes_core_proof(proc_def_id, CheckMode::Both) {
@@ -765,6 +851,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
                                 "Trusted procedure will not be encoded or verified: {:?}",
                                 proc_def_id
                             );
+                            self.encode_refinement_check(proc_name, proc_def_id);
                         },
                         ProcedureSpecificationKind::Predicate(_) => {
                             debug!(
@@ -772,6 +859,12 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
                                 proc_def_id
                             );
                         },
+                        _ if self.env.query.is_async_fn(proc_def_id) => {
+                            debug!(
+                                "The body of an async function is verified as a generator: {:?}",
+                                proc_def_id
+                            );
+                        },
                         ProcedureSpecificationKind::Pure |
                         ProcedureSpecificationKind::Impure => {
                             if let Err(error) = self.encode_procedure(proc_def_id) {
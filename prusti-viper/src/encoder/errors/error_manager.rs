@@ -62,6 +62,16 @@ pub enum ErrorCtxt {
     Panic(PanicCause),
     /// A Viper `exhale expr` that encodes the call of a Rust procedure with precondition `expr`
     ExhaleMethodPrecondition,
+    /// A Viper `assert false` that encodes the failure of
+    /// `assert!(matches!(value, pattern))`
+    /// Arguments: the asserted value and pattern
+    AssertMatches(String, String),
+    /// A Viper `exhale expr` that encodes the call of `Option::expect` or
+    /// `Result::expect` with precondition `expr` and the given message
+    ExhaleExpectPrecondition(String),
+    /// A Viper `assert expr` that encodes the implicit drop of a value whose
+    /// `Drop::drop` implementation has precondition `expr`
+    AssertDropPrecondition,
     /// An error when assuming method's functional specification.
     UnexpectedAssumeMethodPrecondition,
     /// An error when assuming method's functional specification.
@@ -88,6 +98,9 @@ pub enum ErrorCtxt {
     LoopVariantOnEntry,
     LoopVariantAfterIteration,
     LoopVariantNonDecreased,
+    /// A Viper `assert false` that is reached if an unrolled loop iterates
+    /// more often than the loop unrolling bound
+    UnrolledLoopBound,
     /// If a loop needs to terminate and no loop variant is provided
     UnexpectedReachableLoop,
     /// If a call needs to terminate and it does not necessarily terminate
@@ -146,11 +159,19 @@ pub enum ErrorCtxt {
     /// A Viper pure function call with `false` precondition that encodes a Rust panic in a pure function
     PanicInPureFunction(PanicCause),
     /// A Viper `assert e1 ==> e2` that encodes a weakening of the precondition
-    /// of a method implementation of a trait
-    AssertMethodPreconditionWeakening,
+    /// of a method implementation of a trait. Contains the name of the trait method.
+    AssertMethodPreconditionWeakening(String),
     /// A Viper `assert e1 ==> e2` that encodes a strengthening of the precondition
-    /// of a method implementation of a trait.
-    AssertMethodPostconditionStrengthening,
+    /// of a method implementation of a trait. Contains the name of the trait method.
+    AssertMethodPostconditionStrengthening(String),
+    /// A Viper `assert e` that checks that the generic precondition of a
+    /// function implies the precondition `e` of its type-conditional spec
+    /// refinement for concrete types.
+    AssertTypeCondSpecPreconditionWeakening,
+    /// A Viper `assert e` that checks that the postcondition of a
+    /// type-conditional spec refinement for concrete types implies the generic
+    /// postcondition `e`.
+    AssertTypeCondSpecPostconditionStrengthening,
     /// A cast like `usize as u32`.
     TypeCast,
     /// A Viper `assert false` that encodes an unsupported feature.
@@ -188,6 +209,19 @@ pub enum ErrorCtxt {
     /// The state that fold-unfold algorithm deduced as unreachable, is actually
     /// reachable.
     UnreachableFoldingState,
+    /// A Viper `refute false` at the start of a branch that encodes the check
+    /// whether the contracts make the branch unreachable
+    UnreachableBranch,
+    /// A Viper `refute false` after inhaling the precondition that encodes the
+    /// check whether the precondition is satisfiable
+    UnreachableBody,
+    /// A Viper `refute false` before inhaling a `prusti_assume!` that encodes
+    /// the check whether the assumption is reachable
+    UnreachableAssumption,
+    /// A Viper `refute false` after inhaling a `prusti_assume!` that encodes
+    /// the check whether the assumption contradicts the reachable states. The
+    /// value is the position id of the corresponding `UnreachableAssumption`.
+    VacuousAssumption(u64),
 }
 
 /// The error manager
@@ -264,6 +298,42 @@ impl<'tcx> ErrorManager<'tcx> {
             .and_then(|id| self.position_manager.def_id.get(&id).copied())
     }
 
+    fn get_error_ctxt(&self, ver_error: &VerificationError) -> Option<&ErrorCtxt> {
+        ver_error.offending_pos_id.as_ref()
+            .and_then(|id| id.parse::<u64>().ok())
+            .and_then(|id| self.error_contexts.get(&id))
+    }
+
+    /// Whether `ver_error` reports that a branch is unreachable.
+    pub fn is_unreachable_branch(&self, ver_error: &VerificationError) -> bool {
+        matches!(self.get_error_ctxt(ver_error), Some(ErrorCtxt::UnreachableBranch))
+    }
+
+    /// Whether `ver_error` reports that a precondition cannot be satisfied.
+    pub fn is_unreachable_body(&self, ver_error: &VerificationError) -> bool {
+        matches!(self.get_error_ctxt(ver_error), Some(ErrorCtxt::UnreachableBody))
+    }
+
+    /// If `ver_error` reports that a `prusti_assume!` is unreachable, returns
+    /// the position id of the failing check.
+    pub fn get_unreachable_assumption(&self, ver_error: &VerificationError) -> Option<u64> {
+        let pos_id = ver_error.offending_pos_id.as_ref()?.parse::<u64>().ok()?;
+        match self.error_contexts.get(&pos_id) {
+            Some(ErrorCtxt::UnreachableAssumption) => Some(pos_id),
+            _ => None,
+        }
+    }
+
+    /// If `ver_error` reports that the code after a `prusti_assume!` is
+    /// unreachable, returns the position id of the check whether the
+    /// assumption itself is reachable.
+    pub fn get_vacuous_assumption(&self, ver_error: &VerificationError) -> Option<u64> {
+        match self.get_error_ctxt(ver_error) {
+            Some(&ErrorCtxt::VacuousAssumption(reachability_check)) => Some(reachability_check),
+            _ => None,
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub fn translate_verification_error(&self, ver_error: &VerificationError) -> PrustiError {
         let opt_pos_id: Option<u64> = match ver_error.offending_pos_id {
@@ -375,6 +445,13 @@ impl<'tcx> ErrorManager<'tcx> {
                     .set_failing_assertion(opt_cause_span)
             }
 
+            ("assert.failed:assertion.false", ErrorCtxt::AssertMatches(ref value, ref pattern)) => {
+                PrustiError::verification(
+                    format!("`{value}` might not match the pattern `{pattern}`"),
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+            }
+
             ("assert.failed:assertion.false", ErrorCtxt::Panic(PanicCause::Unreachable)) => {
                 PrustiError::verification("unreachable!(..) statement might be reachable", error_span)
                     .set_failing_assertion(opt_cause_span)
@@ -408,7 +485,29 @@ impl<'tcx> ErrorManager<'tcx> {
                     .set_failing_assertion(opt_cause_span)
             }
 
-            ("fold.failed:assertion.false", ErrorCtxt::ExhaleMethodPrecondition) => {
+            ("assert.failed:assertion.false", ErrorCtxt::ExhaleExpectPrecondition(ref message)) => {
+                PrustiError::verification(
+                    format!("precondition might not hold; `expect` might panic with \"{message}\""),
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+            }
+
+            ("assert.failed:assertion.false", ErrorCtxt::AssertDropPrecondition) => {
+                PrustiError::verification(
+                    "the precondition of `Drop::drop` might not hold when the value is dropped.",
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+            }
+
+            ("fold.failed:assertion.false", ErrorCtxt::AssertDropPrecondition) => {
+                PrustiError::verification(
+                    "implicit type invariant expected by `Drop::drop` might not hold when the value is dropped.",
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+            }
+
+            ("fold.failed:assertion.false", ErrorCtxt::ExhaleMethodPrecondition) |
+            ("fold.failed:assertion.false", ErrorCtxt::ExhaleExpectPrecondition(_)) => {
                 PrustiError::verification(
                     "implicit type invariant expected by the function call might not hold.",
                     error_span
@@ -451,6 +550,27 @@ impl<'tcx> ErrorManager<'tcx> {
                 ).push_primary_span(opt_cause_span)
             }
 
+            ("application.precondition:assertion.false", ErrorCtxt::AssertLoopInvariantOnEntry) => {
+                PrustiError::verification("loop invariant might not hold in the first loop iteration.", error_span)
+                    .push_primary_span(opt_cause_span)
+            }
+
+            ("application.precondition:assertion.false", ErrorCtxt::AssertLoopInvariantAfterIteration) => {
+                PrustiError::verification(
+                    "loop invariant might not hold after a loop iteration that preserves the loop condition.",
+                    error_span
+                ).push_primary_span(opt_cause_span)
+            }
+
+            ("assert.failed:assertion.false", ErrorCtxt::UnrolledLoopBound) => {
+                PrustiError::verification(
+                    "the loop might iterate more often than the loop unrolling bound.",
+                    error_span
+                ).set_help(
+                    "increase `LOOP_UNROLLING_BOUND`, or specify the loop with a `body_invariant!(...)`"
+                )
+            }
+
             ("assert.failed:assertion.false", ErrorCtxt::DropCall) => {
                 PrustiError::verification(
                     "the drop handler was called.",
@@ -597,14 +717,24 @@ impl<'tcx> ErrorManager<'tcx> {
                 .add_note("Prusti does not support yet reinterpreting memory of Rust unions' fields and allow reading only the field that was previously initialized.", None)
             }
 
-            ("assert.failed:assertion.false", ErrorCtxt::AssertMethodPreconditionWeakening) => {
-                PrustiError::verification("the method's precondition may not be a valid weakening of the trait's precondition.".to_string(), error_span)
-                    .set_help("The trait's precondition should imply the implemented method's precondition.")
+            ("assert.failed:assertion.false", ErrorCtxt::AssertMethodPreconditionWeakening(ref trait_method)) => {
+                PrustiError::verification(format!("the method's precondition may not be a valid weakening of the precondition of '{trait_method}'."), error_span)
+                    .set_help("The trait's precondition should imply this precondition of the implemented method.")
+            }
+
+            ("assert.failed:assertion.false", ErrorCtxt::AssertMethodPostconditionStrengthening(ref trait_method)) => {
+                PrustiError::verification(format!("the method's postcondition may not be a valid strengthening of the postcondition of '{trait_method}'."), error_span)
+                    .set_help("The implemented method's postcondition should imply this postcondition of the trait.")
             }
 
-            ("assert.failed:assertion.false", ErrorCtxt::AssertMethodPostconditionStrengthening) => {
-                PrustiError::verification("the method's postcondition may not be a valid strengthening of the trait's postcondition.".to_string(), error_span)
-                    .set_help("The implemented method's postcondition should imply the trait's postcondition.")
+            ("assert.failed:assertion.false", ErrorCtxt::AssertTypeCondSpecPreconditionWeakening) => {
+                PrustiError::verification("the precondition of the type-conditional spec refinement might not be implied by the generic precondition", error_span)
+                    .set_help("Calls from generic code only establish the generic precondition, which should imply this precondition.")
+            }
+
+            ("assert.failed:assertion.false", ErrorCtxt::AssertTypeCondSpecPostconditionStrengthening) => {
+                PrustiError::verification("the generic postcondition might not be implied by the type-conditional spec refinement", error_span)
+                    .set_help("Calls from generic code rely on the generic postcondition, which should be implied by the postcondition of the refinement.")
             }
 
             ("assert.failed:assertion.false", ErrorCtxt::BoundsCheckAssert) |
@@ -705,6 +835,27 @@ impl<'tcx> ErrorManager<'tcx> {
                 )
             }
 
+            ("refute.failed:refutation.true", ErrorCtxt::UnreachableBranch) => {
+                PrustiError::warning(
+                    "this branch is unreachable given the contracts of the function and of the called functions",
+                    error_span,
+                ).set_help("if the branch is meant to be reachable, the precondition might be too strong")
+            }
+
+            ("refute.failed:refutation.true", ErrorCtxt::UnreachableBody) => {
+                PrustiError::warning(
+                    "the precondition of this function cannot be satisfied, so none of its code is reachable",
+                    error_span,
+                ).set_help("the postconditions and the assertions of the function hold vacuously")
+            }
+
+            ("refute.failed:refutation.true", ErrorCtxt::VacuousAssumption(_)) => {
+                PrustiError::warning(
+                    "this assumption contradicts the reachable states, so none of the code after it is reachable",
+                    error_span,
+                ).set_help("the assertions after the assumption hold vacuously and are not checked")
+            }
+
             (full_err_id, ErrorCtxt::Unexpected) => {
                 PrustiError::internal(
                     format!(
@@ -4,9 +4,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use prusti_rustc_interface::errors::MultiSpan;
+use prusti_rustc_interface::{errors::{Applicability, MultiSpan}, span::Span};
 use log::{debug, error};
-use prusti_interface::PrustiError;
+use prusti_interface::{PrustiError, UnsupportedFeature};
 
 use crate::encoder::errors::EncodingErrorKind;
 use backtrace::Backtrace;
@@ -18,6 +18,7 @@ pub struct SpannedEncodingError {
     span: Box<MultiSpan>,
     help: Option<String>,
     notes: Vec<(String, Option<MultiSpan>)>,
+    suggestions: Vec<(String, Span, String, Applicability)>,
 }
 
 pub type SpannedEncodingResult<T> = Result<T, SpannedEncodingError>;
@@ -41,6 +42,22 @@ impl From<SpannedEncodingError> for PrustiError {
         for (message, span) in other.notes {
             error.add_note_mut(message, span);
         }
+        for (message, span, replacement, applicability) in other.suggestions {
+            error.add_suggestion_mut(message, span, replacement, applicability);
+        }
+        error
+    }
+}
+
+impl From<UnsupportedFeature> for SpannedEncodingError {
+    fn from(unsupported: UnsupportedFeature) -> Self {
+        let mut error = SpannedEncodingError::unsupported(
+            unsupported.message(),
+            unsupported.span().clone(),
+        );
+        if let Some(help) = unsupported.help() {
+            error.set_help(help);
+        }
         error
     }
 }
@@ -52,6 +69,7 @@ impl SpannedEncodingError {
             span: Box::new(span.into()),
             help: None,
             notes: Vec::new(),
+            suggestions: Vec::new(),
         }
     }
 
@@ -107,4 +125,16 @@ impl SpannedEncodingError {
     pub fn set_help<S: ToString>(&mut self, message: S) {
         self.help = Some(message.to_string());
     }
+
+    /// Adds a fix replacing the code at `span`.
+    pub fn add_suggestion<S: ToString, R: ToString>(
+        &mut self,
+        message: S,
+        span: Span,
+        replacement: R,
+        applicability: Applicability,
+    ) {
+        self.suggestions
+            .push((message.to_string(), span, replacement.to_string(), applicability));
+    }
 }
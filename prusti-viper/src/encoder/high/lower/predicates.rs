@@ -228,8 +228,18 @@ impl IntoPredicates for vir_high::type_decl::Pointer {
         ty: &vir_high::Type,
         encoder: &impl HighTypeEncoderInterfacePrivate,
     ) -> Predicates {
-        // Pointers are unsupported.
-        let predicate = Predicate::new_abstract(ty.lower(encoder));
+        if !encoder.uses_trusted_unsafe_memory() {
+            // Pointers are unsupported.
+            let predicate = Predicate::new_abstract(ty.lower(encoder));
+            return Ok(vec![predicate]);
+        }
+        // Like a reference, a raw pointer holds the permission to its target.
+        // Raw pointers can only be created and dereferenced in procedures
+        // annotated with `#[trusted_unsafe_memory]`. The predicates are shared
+        // by all procedures, so this encoding is only used in crates that
+        // contain such procedures.
+        let field = create_value_field(ty.clone())?.lower(encoder);
+        let predicate = Predicate::new_struct(ty.lower(encoder), vec![field]);
         Ok(vec![predicate])
     }
 }
@@ -34,7 +34,8 @@ pub(crate) fn create_value_field(ty: vir::Type) -> EncodingResult<vir::FieldDecl
         | vir::Type::TypeVar(_)
         | vir::Type::Projection(_) => vir::FieldDecl::new("val_ref", 0usize, ty),
 
-        vir::Type::Reference(vir::ty::Reference { target_type, .. }) => {
+        vir::Type::Reference(vir::ty::Reference { target_type, .. })
+        | vir::Type::Pointer(vir::ty::Pointer { target_type, .. }) => {
             vir::FieldDecl::new("val_ref", 0usize, (*target_type).clone())
         }
 
@@ -43,7 +44,6 @@ pub(crate) fn create_value_field(ty: vir::Type) -> EncodingResult<vir::FieldDecl
         }
 
         vir::Type::Union(_)
-        | vir::Type::Pointer(_)
         | vir::Type::Never
         | vir::Type::Str
         | vir::Type::Unsupported(_) => {
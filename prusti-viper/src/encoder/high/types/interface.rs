@@ -49,6 +49,10 @@ pub(in super::super) trait HighTypeEncoderInterfacePrivate {
     ) -> vir_poly::Type;
     fn decode_type_mid_into_high(&self, ty: vir_mid::Type)
         -> SpannedEncodingResult<vir_high::Type>;
+    /// Whether a function of the crate is annotated with
+    /// `#[trusted_unsafe_memory]`, so that raw pointers have to hold the
+    /// permission to their target.
+    fn uses_trusted_unsafe_memory(&self) -> bool;
 }
 
 impl<'v, 'tcx: 'v> HighTypeEncoderInterfacePrivate for super::super::super::Encoder<'v, 'tcx> {
@@ -122,6 +126,13 @@ impl<'v, 'tcx: 'v> HighTypeEncoderInterfacePrivate for super::super::super::Enco
         let typed_ty = vir_mid::operations::MiddleToTypedType::middle_to_typed_type(ty, self)?;
         self.type_from_typed_to_high(&typed_ty)
     }
+    fn uses_trusted_unsafe_memory(&self) -> bool {
+        let query = self.env().query;
+        query
+            .hir()
+            .body_owners()
+            .any(|def_id| query.has_prusti_attribute(def_id.to_def_id(), "trusted_unsafe_memory"))
+    }
 }
 
 pub(crate) trait HighTypeEncoderInterface<'tcx> {
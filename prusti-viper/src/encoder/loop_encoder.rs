@@ -10,11 +10,13 @@ use prusti_interface::environment::mir_analyses::initialization::{
 use prusti_interface::environment::mir_sets::PlaceSet;
 use prusti_interface::environment::{BasicBlockIndex, LoopAnalysisError, PermissionForest, ProcedureLoops, Procedure};
 use prusti_interface::utils;
+use rustc_hash::FxHashSet;
 use prusti_rustc_interface::middle::{mir, ty};
 use log::debug;
 
 pub enum LoopEncoderError {
     LoopInvariantInBranch(BasicBlockIndex),
+    LoopInvariantAfterContinue(BasicBlockIndex),
 }
 
 pub struct LoopEncoder<'p, 'tcx: 'p> {
@@ -130,6 +132,38 @@ impl<'p, 'tcx: 'p> LoopEncoder<'p, 'tcx> {
             .any(|def_init_place| utils::is_prefix(place, *def_init_place))
     }
 
+    /// Is the conditional block `bbi` of the loop skipped only by iterations
+    /// that end early, e.g. with a `continue`? This is the case if every back
+    /// edge that can be reached from `bbi` in the same iteration starts from a
+    /// block dominated by `bbi`; otherwise `bbi` is in a branch that joins the
+    /// rest of the loop body again.
+    fn is_only_skipped_by_continue(
+        &self,
+        loop_head: BasicBlockIndex,
+        bbi: BasicBlockIndex,
+    ) -> bool {
+        let loop_info = self.loops();
+        let loop_body = &loop_info.loop_bodies[&loop_head];
+        let mut reachable: FxHashSet<BasicBlockIndex> = FxHashSet::default();
+        reachable.insert(bbi);
+        let mut to_visit = vec![bbi];
+        while let Some(current) = to_visit.pop() {
+            for &successor in self.procedure.successors(current) {
+                if successor != loop_head
+                    && loop_body.contains(&successor)
+                    && reachable.insert(successor)
+                {
+                    to_visit.push(successor);
+                }
+            }
+        }
+        loop_info
+            .back_edges
+            .iter()
+            .filter(|&&(source, head)| head == loop_head && reachable.contains(&source))
+            .all(|&(source, _)| loop_info.dominates(bbi, source))
+    }
+
     /// Return the block at whose end the loop invariant holds
     #[tracing::instrument(level = "debug", skip(self))]
     pub fn get_loop_invariant_block(
@@ -154,6 +188,7 @@ impl<'p, 'tcx: 'p> LoopEncoder<'p, 'tcx> {
                     && self.mir()[bb].terminator().successors().any(|succ_bb| {
                         self.procedure.is_reachable_block(succ_bb)
                             && self.procedure.is_spec_block(succ_bb)
+                            && !self.procedure.is_loop_invariant_before_loop_block(succ_bb)
                     })
             });
 
@@ -164,17 +199,25 @@ impl<'p, 'tcx: 'p> LoopEncoder<'p, 'tcx> {
                     "{:?} is conditional branch in loop {:?}",
                     before_invariant_block, loop_head
                 );
-                Err(LoopEncoderError::LoopInvariantInBranch(loop_head))
+                if self.is_only_skipped_by_continue(loop_head, before_invariant_block) {
+                    Err(LoopEncoderError::LoopInvariantAfterContinue(loop_head))
+                } else {
+                    Err(LoopEncoderError::LoopInvariantInBranch(loop_head))
+                }
             } else {
                 Ok(before_invariant_block)
             }
+        } else if !self.procedure.get_loop_invariant_before_loop_blocks(loop_head).is_empty() {
+            // The invariant is written before the loop, so it holds whenever the
+            // loop condition is about to be evaluated
+            Ok(loop_head)
         } else {
-            // HEURISTIC: place the invariant after the first boolean exit block in a
+            // HEURISTIC: place the invariant after the first guard exit block in a
             // non-conditional branch. If there is none, place the invariant at the loop head.
             let loop_exit_blocks = loop_info.get_loop_exit_blocks(loop_head);
             let before_invariant_block = loop_exit_blocks.iter().copied()
                 .find(|&bb| {
-                    self.procedure.successors(bb).len() == 2
+                    self.procedure.is_loop_guard_switch(loop_head, bb)
                     && !loop_info.is_conditional_branch(loop_head, bb)
                 })
                 .unwrap_or(loop_head);
@@ -581,6 +581,25 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
                 }
             }
             BuiltinFunc::ConcatSeq => seq(ContainerOpKind::SeqConcat),
+            BuiltinFunc::TakeSeq | BuiltinFunc::DropSeq => {
+                assert_eq!(args.len(), 2);
+                let kind = if app.function == BuiltinFunc::TakeSeq {
+                    ContainerOpKind::SeqTake
+                } else {
+                    ContainerOpKind::SeqDrop
+                };
+                let count = lowerer.obtain_constant_value(
+                    app.arguments[1].get_type(),
+                    args[1].clone(),
+                    app.position,
+                )?;
+                Ok(vir_low::Expression::container_op(
+                    kind,
+                    vir_low::Type::seq(ty_args[0].clone()),
+                    vec![args[0].clone(), count],
+                    app.position,
+                ))
+            }
             BuiltinFunc::SeqLen => {
                 let value = seq(ContainerOpKind::SeqLen)?;
                 lowerer.construct_constant_snapshot(app.get_type(), value, app.position)
@@ -9,7 +9,7 @@ use crate::{
     error_unsupported,
 };
 use log::trace;
-use prusti_interface::specs::typed;
+use prusti_interface::{environment::mir_utils::AsyncFnArgsForMir, specs::typed};
 use prusti_rustc_interface::{
     hir::{def_id::DefId, Mutability},
     middle::{
@@ -55,6 +55,19 @@ pub(crate) trait ContractsEncoderInterface<'tcx> {
         target: places::Local,
         call_substs: SubstsRef<'tcx>,
     ) -> EncodingResult<ProcedureContract<'tcx>>;
+
+    /// Get a contract for a call whose returned future is awaited immediately.
+    /// The `target` then receives the output of the future, of type
+    /// `output_ty`, for which the postconditions on completion hold.
+    fn get_procedure_contract_for_awaited_call(
+        &self,
+        caller_def_id: DefId,
+        called_def_id: DefId,
+        args: &[places::Local],
+        target: places::Local,
+        output_ty: ty::Ty<'tcx>,
+        call_substs: SubstsRef<'tcx>,
+    ) -> EncodingResult<ProcedureContract<'tcx>>;
 }
 
 #[derive(Default)]
@@ -76,7 +89,7 @@ impl<'v, 'tcx: 'v> ContractsEncoderInterface<'tcx> for super::super::super::Enco
                 let specification = self
                     .get_procedure_specs(proc_def_id, substs)
                     .unwrap_or_else(|| typed::ProcedureSpecification::empty(proc_def_id));
-                get_procedure_contract(self, specification, proc_def_id, substs)
+                get_procedure_contract(self, specification, proc_def_id, substs, None)
             })
             .clone()
     }
@@ -94,7 +107,8 @@ impl<'v, 'tcx: 'v> ContractsEncoderInterface<'tcx> for super::super::super::Enco
         let specification = self
             .get_procedure_specs_for_call(called_def_id, caller_def_id, call_substs)
             .unwrap_or_else(|| typed::ProcedureSpecification::empty(called_def_id));
-        let contract = get_procedure_contract(self, specification, called_def_id, call_substs)?;
+        let contract =
+            get_procedure_contract(self, specification, called_def_id, call_substs, None)?;
         Ok(contract)
     }
 
@@ -126,7 +140,35 @@ impl<'v, 'tcx: 'v> ContractsEncoderInterface<'tcx> for super::super::super::Enco
         let specification = self
             .get_procedure_specs_for_call(called_def_id, caller_def_id, call_substs)
             .unwrap_or_else(|| typed::ProcedureSpecification::empty(called_def_id));
-        let contract = get_procedure_contract(self, specification, called_def_id, call_substs)?;
+        let contract =
+            get_procedure_contract(self, specification, called_def_id, call_substs, None)?;
+        Ok(contract.to_call_site_contract(args, target))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn get_procedure_contract_for_awaited_call(
+        &self,
+        caller_def_id: DefId,
+        called_def_id: DefId,
+        args: &[places::Local],
+        target: places::Local,
+        output_ty: ty::Ty<'tcx>,
+        call_substs: SubstsRef<'tcx>,
+    ) -> EncodingResult<ProcedureContract<'tcx>> {
+        let (called_def_id, call_substs) =
+            self.env()
+                .query
+                .resolve_method_call(caller_def_id, called_def_id, call_substs);
+        let specification = self
+            .get_procedure_specs_for_call(called_def_id, caller_def_id, call_substs)
+            .unwrap_or_else(|| typed::ProcedureSpecification::empty(called_def_id));
+        let contract = get_procedure_contract(
+            self,
+            specification,
+            called_def_id,
+            call_substs,
+            Some(output_ty),
+        )?;
         Ok(contract.to_call_site_contract(args, target))
     }
 }
@@ -134,15 +176,53 @@ impl<'v, 'tcx: 'v> ContractsEncoderInterface<'tcx> for super::super::super::Enco
 #[tracing::instrument(level = "debug", skip(encoder, specification))]
 fn get_procedure_contract<'p, 'v: 'p, 'tcx: 'v>(
     encoder: &'p Encoder<'v, 'tcx>,
-    specification: typed::ProcedureSpecification,
+    mut specification: typed::ProcedureSpecification,
     proc_def_id: DefId,
     substs: SubstsRef<'tcx>,
+    awaited_output_ty: Option<ty::Ty<'tcx>>,
 ) -> EncodingResult<ProcedureContractMirDef<'tcx>> {
     let env = encoder.env();
     let args_ty: Vec<(mir::Local, ty::Ty<'tcx>)>;
     let return_ty;
 
-    if !env.query.is_closure(proc_def_id) {
+    if awaited_output_ty.is_some() {
+        // The result of the call is the output of the future, about which the
+        // postconditions on completion hold.
+        let completion_posts = std::mem::replace(
+            &mut specification.completion_posts,
+            typed::SpecificationItem::Empty,
+        );
+        for &post in completion_posts.extract_with_selective_replacement_iter() {
+            specification.posts.push(post);
+        }
+    } else if env.query.is_async_fn(proc_def_id) {
+        // A call of an `async fn` only creates its future, so nothing is known
+        // about the result before the future is awaited.
+        specification.posts = typed::SpecificationItem::Empty;
+        specification.pledges = typed::SpecificationItem::Empty;
+    }
+
+    if let Some(async_fn_def_id) = env.query.get_async_fn_of_body(proc_def_id) {
+        let async_fn_substs = env.query.identity_substs(async_fn_def_id);
+        if !async_fn_substs.is_empty() {
+            error_unsupported!("generic async functions are not supported");
+        }
+        let mir = env
+            .body
+            .get_impure_fn_body(proc_def_id.expect_local(), substs);
+        // The contract of the `async fn` refers to the locals to which its
+        // arguments are moved at the beginning of the body of the generator.
+        let arg_bindings = mir.get_async_fn_arg_bindings();
+        let fn_sig = env.query.get_fn_sig(async_fn_def_id, async_fn_substs);
+        if arg_bindings.len() != fn_sig.skip_binder().inputs().len() {
+            error_unsupported!("the arguments of this async function are not supported");
+        }
+        args_ty = arg_bindings
+            .into_iter()
+            .map(|(_, local)| (local, mir.local_decls[local].ty))
+            .collect();
+        return_ty = mir.local_decls[mir::RETURN_PLACE].ty;
+    } else if !env.query.is_closure(proc_def_id) {
         // FIXME: "skip_binder" is most likely wrong
         // FIXME: Replace with FakeMirEncoder.
         let fn_sig: FnSig = env.query.get_fn_sig(proc_def_id, substs).skip_binder();
@@ -172,6 +252,7 @@ fn get_procedure_contract<'p, 'v: 'p, 'tcx: 'v>(
             .collect();
         return_ty = mir.local_decls[mir::Local::from_usize(0)].ty;
     }
+    let return_ty = awaited_output_ty.unwrap_or(return_ty);
 
     let mut fake_mir_args = Vec::new();
     let mut fake_mir_args_ty = Vec::new();
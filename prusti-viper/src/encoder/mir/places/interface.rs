@@ -449,8 +449,6 @@ impl<'v, 'tcx: 'v> PlacesEncoderInterface<'tcx> for super::super::super::Encoder
         let encoded_val = match (src_ty.kind(), dst_ty.kind()) {
             // Numeric casts that cannot fail
             (ty::TyKind::Char, ty::TyKind::Char)
-            | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U8))
-            | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U16))
             | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U32))
             | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U64))
             | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U128))
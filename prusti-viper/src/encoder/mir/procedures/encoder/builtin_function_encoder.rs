@@ -233,6 +233,12 @@ impl<'p, 'v, 'tcx> BuiltinFuncAppEncoder<'p, 'v, 'tcx> for super::ProcedureEncod
             "prusti_contracts::Seq::<T>::concat" => {
                 make_builtin_call(self, block_builder, vir_high::BuiltinFunc::ConcatSeq)?
             }
+            "prusti_contracts::Seq::<T>::take" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::TakeSeq)?
+            }
+            "prusti_contracts::Seq::<T>::drop" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::DropSeq)?
+            }
             "prusti_contracts::Seq::<T>::lookup" => {
                 make_builtin_call(self, block_builder, vir_high::BuiltinFunc::LookupSeq)?
             }
@@ -103,8 +103,14 @@ impl<'a, 'p, 'v, 'tcx> Visitor<'tcx> for GhostChecker<'a, 'p, 'v, 'tcx> {
                     func: mir::Operand::Constant(box mir::Constant { literal, .. }),
                     ..
                 } => {
-                    if let ty::TyKind::FnDef(def_id, _call_substs) = literal.ty().kind() {
-                        if !self.p.encoder.is_pure(*def_id, None) {
+                    if let ty::TyKind::FnDef(def_id, call_substs) = literal.ty().kind() {
+                        let (called_def_id, call_substs) = self
+                            .p
+                            .encoder
+                            .env()
+                            .query
+                            .resolve_method_call(self.p.def_id, *def_id, call_substs);
+                        if !self.p.encoder.is_pure(called_def_id, Some(call_substs)) {
                             self.violations.push(SpannedEncodingError::incorrect(
                                 "Only pure function calls are allowed in ghost blocks.",
                                 term.source_info.span,
@@ -82,6 +82,7 @@ impl SpecificationBlocks {
         let predecessors = body.basic_blocks.predecessors();
         let mut loop_invariant_blocks = BTreeMap::<_, LoopInvariantBlocks>::new();
         let mut loop_spec_blocks_flat = BTreeSet::new();
+        let mut loop_specs_before_loop = BTreeMap::<_, Vec<_>>::new();
         if collect_loop_invariants {
             // We use reverse_postorder here because we need to make sure that we
             // preserve the order of invariants in which they were specified by the
@@ -93,6 +94,19 @@ impl SpecificationBlocks {
                     && (is_loop_invariant_block(env_query, data)
                         || is_loop_variant_block(env_query, data))
                 {
+                    if procedure.is_loop_invariant_before_loop_block(bb) {
+                        // The invariants written before the loop are collected
+                        // separately, because they do not determine the
+                        // location of the invariant.
+                        if let Some(loop_head) = procedure.get_following_loop_head(bb) {
+                            loop_specs_before_loop
+                                .entry(loop_head)
+                                .or_default()
+                                .push(bb);
+                            loop_spec_blocks_flat.insert(bb);
+                        }
+                        continue;
+                    }
                     let loop_head = loop_info.get_loop_head(bb).unwrap();
                     let loop_blocks = loop_invariant_blocks.entry(loop_head).or_insert_with(|| {
                         assert_eq!(
@@ -109,6 +123,16 @@ impl SpecificationBlocks {
                     loop_spec_blocks_flat.insert(bb);
                 }
             }
+            for (loop_head, blocks) in loop_specs_before_loop {
+                let loop_blocks =
+                    loop_invariant_blocks
+                        .entry(loop_head)
+                        .or_insert_with(|| LoopInvariantBlocks {
+                            location: loop_head,
+                            specification_blocks: Vec::new(),
+                        });
+                loop_blocks.specification_blocks.splice(0..0, blocks);
+            }
         }
 
         // Collect entry points.
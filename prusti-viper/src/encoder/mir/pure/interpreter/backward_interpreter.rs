@@ -11,7 +11,8 @@ use prusti_rustc_interface::middle::mir;
 use rustc_hash::FxHashMap;
 use std::{fmt::Debug, iter::FromIterator, marker::Sized};
 
-/// Backward interpreter for a loop-less MIR
+/// Backward interpreter for a MIR. Loops are supported only if the interpreter
+/// provides the states at the back edges of the CFG.
 pub trait BackwardMirInterpreter<'tcx> {
     type Error: Sized;
     type State: Sized;
@@ -28,10 +29,29 @@ pub trait BackwardMirInterpreter<'tcx> {
         stmt: &mir::Statement<'tcx>,
         state: &mut Self::State,
     ) -> Result<(), Self::Error>;
+    /// Returns the state at the beginning of the loop head `loop_head`, as
+    /// seen from the back edges of the loop. `None` means that loops are not
+    /// supported by the interpreter.
+    fn apply_loop_back_edge(
+        &self,
+        _loop_head: mir::BasicBlock,
+    ) -> Result<Option<Self::State>, Self::Error> {
+        Ok(None)
+    }
+    /// Given the state at the beginning of the loop head `loop_head`, returns
+    /// the state to use on the edges that enter the loop.
+    fn apply_loop_head(
+        &self,
+        _loop_head: mir::BasicBlock,
+        state: Self::State,
+    ) -> Result<Self::State, Self::Error> {
+        Ok(state)
+    }
 }
 
-/// Interpret a loop-less MIR starting from the end and return the **initial** state.
-/// The result is None if the CFG contains a loop.
+/// Interpret a MIR starting from the end and return the **initial** state.
+/// The result is None if the CFG contains a loop that the interpreter does not
+/// support (see `BackwardMirInterpreter::apply_loop_back_edge`).
 #[tracing::instrument(level = "debug", skip_all)]
 pub fn run_backward_interpretation<'tcx, S, E, I>(
     mir: &mir::Body<'tcx>,
@@ -45,11 +65,33 @@ where
     let basic_blocks = &mir.basic_blocks;
     let mut heads: FxHashMap<mir::BasicBlock, S> = FxHashMap::default();
 
-    // Find the final basic blocks
+    // Find the back edges, whose target dominates their source, and the state
+    // of their loop heads
+    let dominators = basic_blocks.dominators();
+    let is_back_edge =
+        |source: mir::BasicBlock, target: mir::BasicBlock| dominators.dominates(target, source);
+    let mut back_edge_states: FxHashMap<mir::BasicBlock, S> = FxHashMap::default();
+    for (bb, bb_data) in basic_blocks.iter_enumerated() {
+        let Some(ref term) = bb_data.terminator else {
+            continue;
+        };
+        for succ_bb in term.successors() {
+            if is_back_edge(bb, succ_bb) && !back_edge_states.contains_key(&succ_bb) {
+                let Some(state) = interpreter.apply_loop_back_edge(succ_bb)? else {
+                    trace!("Unsupported loop with head {:?}", succ_bb);
+                    return Ok(None);
+                };
+                back_edge_states.insert(succ_bb, state);
+            }
+        }
+    }
+
+    // Find the final basic blocks, and the ones that only jump back to a loop
+    // head
     let mut pending_blocks: Vec<mir::BasicBlock> = basic_blocks
         .iter_enumerated()
-        .filter(|(_, bb_data)| match bb_data.terminator {
-            Some(ref term) => term.successors().next().is_none(),
+        .filter(|&(bb, bb_data)| match bb_data.terminator {
+            Some(ref term) => term.successors().all(|succ_bb| is_back_edge(bb, succ_bb)),
             _ => false,
         })
         .map(|(bb, _)| bb)
@@ -62,7 +104,13 @@ where
 
         // Apply the terminator
         let terminator = bb_data.terminator();
-        let states = FxHashMap::from_iter(terminator.successors().map(|bb| (bb, &heads[&bb])));
+        let states = FxHashMap::from_iter(terminator.successors().map(|bb| {
+            if is_back_edge(curr_bb, bb) {
+                (bb, &back_edge_states[&bb])
+            } else {
+                (bb, &heads[&bb])
+            }
+        }));
         trace!("States before: {:?}", states);
         trace!("Apply terminator {:?}", terminator);
         let mut curr_state = interpreter.apply_terminator(curr_bb, terminator, states)?;
@@ -76,15 +124,24 @@ where
             trace!("State after: {:?}", curr_state);
         }
 
+        if back_edge_states.contains_key(&curr_bb) {
+            trace!("Apply loop head {:?}", curr_bb);
+            curr_state = interpreter.apply_loop_head(curr_bb, curr_state)?;
+            trace!("State after: {:?}", curr_state);
+        }
+
         // Store the state at the beginning of block `curr_bb`
         heads.insert(curr_bb, curr_state);
 
-        // Put the preceding basic blocks
+        // Put the preceding basic blocks, except for the sources of back edges
         for &pred_bb in mir.basic_blocks.predecessors()[curr_bb].iter() {
+            if is_back_edge(pred_bb, curr_bb) {
+                continue;
+            }
             if let Some(ref term) = basic_blocks[pred_bb].terminator {
                 if term
                     .successors()
-                    .all(|succ_bb| heads.contains_key(&succ_bb))
+                    .all(|succ_bb| is_back_edge(pred_bb, succ_bb) || heads.contains_key(&succ_bb))
                 {
                     pending_blocks.push(pred_bb);
                 }
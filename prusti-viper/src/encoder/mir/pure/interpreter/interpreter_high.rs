@@ -18,8 +18,8 @@ use crate::encoder::{
         generics::MirGenericsEncoderInterface,
         places::PlacesEncoderInterface,
         pure::{
-            interpreter::BackwardMirInterpreter, PureEncodingContext, PureFunctionEncoderInterface,
-            SpecificationEncoderInterface,
+            interpreter::{impure_function_call_error, BackwardMirInterpreter},
+            PureEncodingContext, PureFunctionEncoderInterface, SpecificationEncoderInterface,
         },
         specifications::SpecificationsInterface,
         types::MirTypeEncoderInterface,
@@ -56,6 +56,9 @@ pub(in super::super) struct ExpressionBackwardInterpreter<'p, 'v: 'p, 'tcx: 'v>
     pure_encoding_context: PureEncodingContext,
     /// DefId of the caller. Used for error reporting.
     caller_def_id: DefId,
+    /// DefId of the pure function being encoded. Used for resolving trait
+    /// method calls.
+    def_id: DefId,
     substs: SubstsRef<'tcx>,
 }
 
@@ -79,6 +82,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
             mir_encoder: MirEncoder::new(encoder, mir, def_id),
             pure_encoding_context,
             caller_def_id,
+            def_id,
             substs,
         }
     }
@@ -459,23 +463,34 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                     substs,
                 )? {
                     state
-                } else if self.encoder.is_pure(def_id, Some(substs)) {
+                } else {
+                    // The called method might be a trait method (e.g. a
+                    // `Deref::deref` inserted by a deref coercion). We try to
+                    // resolve it to the concrete implementation, so that the
+                    // contract of the implementation is used.
+                    let (called_def_id, call_substs) = self
+                        .encoder
+                        .env()
+                        .query
+                        .resolve_method_call(self.def_id, def_id, substs);
+                    if !self.encoder.is_pure(called_def_id, Some(call_substs)) {
+                        return Err(impure_function_call_error(
+                            self.encoder.env(),
+                            self.def_id,
+                            called_def_id,
+                            func_proc_name,
+                            span,
+                        ));
+                    }
                     self.encode_call_generic(
                         *target_block,
                         states,
                         encoded_lhs,
-                        def_id,
+                        called_def_id,
                         encoded_args,
                         span,
-                        substs,
+                        call_substs,
                     )?
-                } else {
-                    return Err(SpannedEncodingError::incorrect(
-                        format!(
-                            "use of impure function {func_proc_name:?} in pure code is not allowed"
-                        ),
-                        span,
-                    ));
                 }
             } else {
                 // FIXME: Refactor the common code with the procedure encoder.
@@ -579,6 +594,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 "len" => (SeqLen, Type::Int(vir_high::ty::Int::Unbounded)),
                 "lookup" => (LookupSeq, elem_type),
                 "concat" => (ConcatSeq, seq_type),
+                "take" => (TakeSeq, seq_type),
+                "drop" => (DropSeq, seq_type),
                 _ => unreachable!("no further Seq functions"),
             });
         } else if let Some(proc_name) = proc_name.strip_prefix("prusti_contracts::Int::") {
@@ -659,6 +676,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 );
                 subst_with(encoded_rhs)
             }
+            "prusti_contracts::snap" => {
+                let argument = encoded_args[0].clone();
+                let encoded_rhs = match argument {
+                    vir_high::Expression::AddrOf(vir_high::AddrOf { base, .. }) => *base,
+                    argument => {
+                        let vir_high::Type::Reference(reference) = argument.get_type() else {
+                            unreachable!("the argument of snap is not a reference: {}", argument);
+                        };
+                        let target_type = (*reference.target_type).clone();
+                        vir_high::Expression::deref_no_pos(argument, target_type)
+                    }
+                };
+                subst_with(encoded_rhs)
+            }
             "prusti_contracts::snapshot_equality" => {
                 let position = encoded_args[0].position();
                 let encoded_rhs = vir_high::Expression::builtin_func_app(
@@ -670,6 +701,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 );
                 subst_with(encoded_rhs)
             }
+            "prusti_contracts::prev_iter" => Err(SpannedEncodingError::unsupported(
+                "prev_iter(..) is not supported by the core proof encoding",
+                span,
+            )),
             "prusti_contracts::before_expiry" => {
                 // self.encode_call_before_expiry()?
                 unimplemented!();
@@ -10,9 +10,14 @@ use crate::{
         },
         mir::{
             pure::{
-                interpreter::{state_poly::ExprBackwardInterpreterState, BackwardMirInterpreter},
+                interpreter::{
+                    impure_function_call_error, state_poly::ExprBackwardInterpreterState,
+                    BackwardMirInterpreter,
+                },
                 pure_functions::PureFunctionEncoderInterface,
-                specifications::SpecificationEncoderInterface,
+                specifications::{
+                    encode_call_summary_function_name, SpecificationEncoderInterface,
+                },
                 PureEncodingContext,
             },
             sequences::MirSequencesEncoderInterface,
@@ -20,23 +25,35 @@ use crate::{
             types::MirTypeEncoderInterface,
         },
         mir_encoder::{
-            MirEncoder, PlaceEncoder, PlaceEncoding, PRECONDITION_LABEL, WAND_LHS_LABEL,
+            MirEncoder, PlaceEncoder, PlaceEncoding, PREV_ITER_LABEL, PRECONDITION_LABEL, WAND_LHS_LABEL,
         },
-        snapshot::interface::SnapshotEncoderInterface,
+        snapshot::{encoder::strip_refs_and_boxes, interface::SnapshotEncoderInterface},
         Encoder,
     },
     error_unsupported,
 };
 use log::{debug, trace};
-use prusti_common::vir_local;
-use prusti_interface::environment::mir_utils::SliceOrArrayRef;
+use prusti_common::{config, vir::optimizations::functions::Simplifier, vir_local};
+use prusti_interface::environment::{
+    get_loop_invariant, is_loop_invariant_before_loop_block, is_loop_invariant_block,
+    mir_utils::SliceOrArrayRef,
+};
 use prusti_rustc_interface::{
+    dataflow::{impls::MaybeLiveLocals, Analysis},
+    errors::MultiSpan,
     hir::def_id::DefId,
-    middle::{mir, span_bug, ty},
+    middle::{
+        mir, span_bug,
+        ty::{self, subst::SubstsRef},
+    },
+    span::Span,
 };
 use rustc_hash::FxHashMap;
-use std::{convert::TryInto, mem};
-use vir_crate::polymorphic::{self as vir};
+use std::{cell::RefCell, collections::BTreeSet, convert::TryInto, mem, rc::Rc};
+use vir_crate::{
+    common::identifier::WithIdentifier,
+    polymorphic::{self as vir},
+};
 
 pub(crate) struct PureFunctionBackwardInterpreter<'p, 'v: 'p, 'tcx: 'v> {
     encoder: &'p Encoder<'v, 'tcx>,
@@ -49,6 +66,26 @@ pub(crate) struct PureFunctionBackwardInterpreter<'p, 'v: 'p, 'tcx: 'v> {
     /// DefId of the caller. Used for error reporting.
     caller_def_id: DefId,
     def_id: DefId, // TODO(tymap): is this actually caller_def_id?
+    /// Type substitutions applied to the MIR.
+    substs: SubstsRef<'tcx>,
+    /// The signatures of the functions that encode the loops, by loop head.
+    loop_functions: RefCell<FxHashMap<mir::BasicBlock, Rc<LoopFunction<'tcx>>>>,
+}
+
+/// The Viper function that encodes a loop of a pure function. It takes the
+/// locals that are live at the loop head and returns the result of the pure
+/// function, so that each iteration is a tail-recursive call.
+struct LoopFunction<'tcx> {
+    name: String,
+    type_arguments: Vec<vir::Type>,
+    /// The live locals, with their types and encodings as formal arguments.
+    arguments: Vec<(mir::Local, ty::Ty<'tcx>, vir::LocalVar)>,
+    return_type: vir::Type,
+    span: Span,
+    /// The `loop_invariant!` specifications written before the loop, over the
+    /// locals. They become preconditions of the function.
+    invariants: Vec<vir::Expr>,
+    invariant_spans: Vec<Span>,
 }
 
 /// This encoding works backward, so there is the risk of generating expressions whose length
@@ -61,6 +98,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionBackwardInterpreter<'p, 'v, 'tcx> {
         def_id: DefId,
         pure_encoding_context: PureEncodingContext,
         caller_def_id: DefId,
+        substs: SubstsRef<'tcx>,
     ) -> Self {
         PureFunctionBackwardInterpreter {
             encoder,
@@ -69,6 +107,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionBackwardInterpreter<'p, 'v, 'tcx> {
             pure_encoding_context,
             caller_def_id,
             def_id,
+            substs,
+            loop_functions: RefCell::new(FxHashMap::default()),
         }
     }
 
@@ -107,6 +147,487 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionBackwardInterpreter<'p, 'v, 'tcx> {
         &self.mir_encoder
     }
 
+    /// Encodes the bounds `(start, end)` of the range `encoded_idx` of type
+    /// `idx_ty`, which is used to slice `base_ty`. `base_len` encodes the
+    /// length of the sliced sequence, which is the end of open ranges.
+    fn encode_range_bounds(
+        &self,
+        base_ty: ty::Ty<'tcx>,
+        idx_ty: ty::Ty<'tcx>,
+        encoded_idx: &vir::Expr,
+        base_len: impl FnOnce() -> SpannedEncodingResult<vir::Expr>,
+        span: Span,
+    ) -> SpannedEncodingResult<(vir::Expr, vir::Expr)> {
+        let idx_ty_did = match idx_ty.ty_adt_def() {
+            Some(def) => def.did(),
+            None => return Err(SpannedEncodingError::unsupported(
+                format!("Using {idx_ty} as index/range type for {base_ty} is not currently supported in pure functions"),
+                span,
+            ))
+        };
+        let idx_ident = self.encoder.env().name.get_absolute_item_name(idx_ty_did);
+
+        // TODO: what do we actually do here? this seems a littly hacky.
+        // there's fields like _5.f$start.val_int on `encoded_idx`, it just feels hacky to
+        // manually re-do them here when we probably just encoded the type and the
+        // construction of the fields..
+        // Also, duplication with procedure_encoder.rs
+        let usize_ty = self
+            .encoder
+            .env()
+            .tcx()
+            .mk_ty_from_kind(ty::TyKind::Uint(ty::UintTy::Usize));
+        let start = match &*idx_ident {
+            "std::ops::Range" | "core::ops::Range" |
+            "std::ops::RangeFrom" | "core::ops::RangeFrom" =>
+                self.encoder.encode_struct_field_value(encoded_idx.clone(), "start", usize_ty).with_span(span)?,
+            // See procedure_encoder.rs
+            "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
+                SpannedEncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string(), span)
+            ),
+            "std::ops::RangeTo" | "core::ops::RangeTo" |
+            "std::ops::RangeFull" | "core::ops::RangeFull" |
+            "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => vir::Expr::from(0u32),
+            _ => unreachable!("{}", idx_ident)
+        };
+        let end = match &*idx_ident {
+            "std::ops::Range" | "core::ops::Range" |
+            "std::ops::RangeTo" | "core::ops::RangeTo" =>
+                self.encoder.encode_struct_field_value(encoded_idx.clone(), "end", usize_ty).with_span(span)?,
+            "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
+                SpannedEncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string(), span)
+            ),
+            "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => {
+                let end_expr = self.encoder.encode_struct_field_value(encoded_idx.clone(), "end", usize_ty).with_span(span)?;
+                vir::Expr::add(end_expr, vir::Expr::from(1u32))
+            }
+            "std::ops::RangeFrom" | "core::ops::RangeFrom" |
+            "std::ops::RangeFull" | "core::ops::RangeFull" => base_len()?,
+            _ => unreachable!("{}", idx_ident)
+        };
+        Ok((start, end))
+    }
+
+    /// Encodes `range.contains(&item)` for a range of integers or characters.
+    /// Both `range` and `item` are the places behind the references passed to
+    /// `contains`.
+    fn encode_range_contains(
+        &self,
+        inclusive: bool,
+        idx_ty: ty::Ty<'tcx>,
+        range: vir::Expr,
+        item: vir::Expr,
+    ) -> EncodingResult<vir::Expr> {
+        let item = self.encoder.encode_value_expr(item, idx_ty)?;
+        let start = self
+            .encoder
+            .encode_struct_field_value(range.clone(), "start", idx_ty)?;
+        let end = self
+            .encoder
+            .encode_struct_field_value(range.clone(), "end", idx_ty)?;
+        let below_end = if inclusive {
+            // An exhausted `RangeInclusive` no longer contains its end.
+            let bool_ty = self.encoder.env().tcx().types.bool;
+            let exhausted = self
+                .encoder
+                .encode_struct_field_value(range, "exhausted", bool_ty)?;
+            vir::Expr::ite(
+                exhausted,
+                vir::Expr::lt_cmp(item.clone(), end.clone()),
+                vir::Expr::le_cmp(item.clone(), end),
+            )
+        } else {
+            vir::Expr::lt_cmp(item.clone(), end)
+        };
+        Ok(vir::Expr::and(vir::Expr::le_cmp(start, item), below_end))
+    }
+
+    /// Checks whether `==` on values of the given type can be encoded as
+    /// equality of their snapshots. Unlike in impure code, string slices are
+    /// supported here because string literals have snapshots.
+    fn has_snapshot_eq(&self, ty: ty::Ty<'tcx>) -> bool {
+        // Boxes are compared by their contents, which is what their snapshots
+        // consist of.
+        let ty = strip_refs_and_boxes(ty);
+        self.encoder.has_structural_eq_impl(ty) || ty.is_str()
+    }
+
+    /// Encodes the check of `guard` before the state `target_state`, such as
+    /// the assertion of a panic condition.
+    fn encode_assertion(
+        &self,
+        guard: vir::Expr,
+        target_state: &ExprBackwardInterpreterState,
+        pos: vir::Position,
+        span: Span,
+    ) -> SpannedEncodingResult<ExprBackwardInterpreterState> {
+        Ok(match self.pure_encoding_context {
+            PureEncodingContext::Trigger => {
+                // We are encoding a trigger, so all panic branches must be stripped.
+                target_state.clone()
+            }
+            PureEncodingContext::Assertion
+                if matches!(self.mir.return_ty().kind(), ty::TyKind::Bool) =>
+            {
+                // We are encoding an assertion, so all failures should be equivalent to false.
+                // Predicates are also encoded as assertions, but non-Boolean predicates should
+                // use the other arm.
+                ExprBackwardInterpreterState::new(target_state.expr().map(|target_expr| {
+                    vir::Expr::ite(guard.clone(), target_expr.clone(), false.into())
+                }))
+            }
+            PureEncodingContext::Assertion | PureEncodingContext::Code => {
+                // We are encoding a pure function, so all failures should be unreachable.
+                let failure_encoding = unreachable_expr(pos).with_span(span)?;
+                ExprBackwardInterpreterState::new(target_state.expr().map(|target_expr| {
+                    vir::Expr::ite(guard.clone(), target_expr.clone(), failure_encoding)
+                }))
+            }
+        })
+    }
+
+    /// Encodes the slice of the elements of the vector `vec`, which is the
+    /// result of the pure `Vec::as_slice`. Returns the type of the slice and
+    /// its encoding.
+    fn encode_vec_as_slice(
+        &self,
+        vec_ty: ty::Ty<'tcx>,
+        vec: &mir::Operand<'tcx>,
+        encoded_vec: vir::Expr,
+        span: Span,
+    ) -> SpannedEncodingResult<(ty::Ty<'tcx>, vir::Expr)> {
+        let tcx = self.encoder.env().tcx();
+        let vec_substs = match vec_ty.peel_refs().kind() {
+            ty::TyKind::Adt(_, substs) => substs,
+            _ => unreachable!("{:?} is not a vector", vec_ty),
+        };
+        let as_slice = self
+            .encoder
+            .env()
+            .query
+            .get_vec_method("as_slice")
+            .filter(|&as_slice| self.encoder.is_pure(as_slice, Some(vec_substs)));
+        let as_slice = if let Some(as_slice) = as_slice {
+            as_slice
+        } else {
+            let mut error = SpannedEncodingError::unsupported(
+                "indexing a vector in a specification requires `Vec::as_slice` to be pure",
+                span,
+            );
+            error.set_help(
+                "the specifications of `Vec` shipped with Prusti in `prusti_std` are not \
+                loaded; enable `LOAD_STD_SPECS` or add `extern crate prusti_std;`",
+            );
+            return Err(error);
+        };
+        let (function_name, return_type) = self
+            .encoder
+            .encode_pure_function_use(as_slice, self.caller_def_id, vec_substs)
+            .with_span(span)?;
+        let formal_args = vec![vir::LocalVar::new(
+            "x0",
+            self.mir_encoder.encode_operand_expr_type(vec).with_span(span)?,
+        )];
+        let type_arguments = self
+            .encoder
+            .encode_generic_arguments(as_slice, vec_substs)
+            .with_span(span)?;
+        let pos = self.encoder.error_manager().register_error(
+            span,
+            ErrorCtxt::PureFunctionCall,
+            self.caller_def_id,
+        );
+        let slice = vir::Expr::func_app(
+            function_name,
+            type_arguments,
+            vec![encoded_vec],
+            formal_args,
+            return_type,
+            pos,
+        );
+        let elem_ty = vec_substs.type_at(0);
+        let slice_ty = tcx.mk_imm_ref(tcx.lifetimes.re_erased, tcx.mk_slice(elem_ty));
+        Ok((slice_ty, slice))
+    }
+
+    /// Returns the `loop_invariant!(...)` blocks that are followed by the loop
+    /// with head `loop_head` without branching.
+    fn get_loop_invariant_blocks(&self, loop_head: mir::BasicBlock) -> Vec<mir::BasicBlock> {
+        let basic_blocks = &self.mir.basic_blocks;
+        let dominators = basic_blocks.dominators();
+        let is_loop_head = |bb: mir::BasicBlock| {
+            basic_blocks.predecessors()[bb]
+                .iter()
+                .any(|&pred_bb| dominators.dominates(bb, pred_bb))
+        };
+        let query = self.encoder.env().query;
+        basic_blocks
+            .iter_enumerated()
+            .filter(|(_, bb_data)| is_loop_invariant_before_loop_block(query, bb_data))
+            .filter(|&(spec_bb, _)| {
+                let mut current = spec_bb;
+                loop {
+                    if current == loop_head {
+                        return true;
+                    }
+                    if current != spec_bb && is_loop_head(current) {
+                        return false;
+                    }
+                    let mut successors = basic_blocks[current].terminator().successors();
+                    // The specification block rejoins the code that follows it.
+                    match (successors.next(), successors.next()) {
+                        (Some(next), None) => current = next,
+                        (Some(next), Some(_)) if current == spec_bb => current = next,
+                        _ => return false,
+                    }
+                }
+            })
+            .map(|(bb, _)| bb)
+            .collect()
+    }
+
+    /// Returns the signature of the function that encodes the loop with head
+    /// `loop_head`, encoding it the first time.
+    fn get_loop_function(
+        &self,
+        loop_head: mir::BasicBlock,
+    ) -> SpannedEncodingResult<Rc<LoopFunction<'tcx>>> {
+        if let Some(loop_function) = self.loop_functions.borrow().get(&loop_head) {
+            return Ok(loop_function.clone());
+        }
+        let loop_function = Rc::new(self.encode_loop_function_signature(loop_head)?);
+        self.loop_functions
+            .borrow_mut()
+            .insert(loop_head, loop_function.clone());
+        Ok(loop_function)
+    }
+
+    /// Encodes the signature of the function that encodes the loop with head
+    /// `loop_head`.
+    fn encode_loop_function_signature(
+        &self,
+        loop_head: mir::BasicBlock,
+    ) -> SpannedEncodingResult<LoopFunction<'tcx>> {
+        let tcx = self.encoder.env().tcx();
+        let span = self
+            .mir_encoder
+            .get_span_of_location(self.mir.terminator_loc(loop_head));
+        let query = self.encoder.env().query;
+        if let Some(body_invariant_bb) = self.mir.basic_blocks.indices().find(|&bb| {
+            let bb_data = &self.mir.basic_blocks[bb];
+            is_loop_invariant_block(query, bb_data)
+                && !is_loop_invariant_before_loop_block(query, bb_data)
+        }) {
+            let mut error = SpannedEncodingError::unsupported(
+                "`body_invariant!` is not supported in loops of pure functions",
+                self.mir_encoder.get_span_of_basic_block(body_invariant_bb),
+            );
+            error.set_help("write the invariant with `loop_invariant!` right before the loop");
+            return Err(error);
+        }
+        let mut invariants = vec![];
+        let mut invariant_spans = vec![];
+        let mut live_locals = MaybeLiveLocals
+            .into_engine(tcx, self.mir)
+            .iterate_to_fixpoint()
+            .into_results_cursor(self.mir);
+        live_locals.seek_to_block_start(loop_head);
+        let mut argument_locals: BTreeSet<mir::Local> = live_locals.get().iter().collect();
+        for invariant_bb in self.get_loop_invariant_blocks(loop_head) {
+            let (cl_def_id, cl_substs) =
+                get_loop_invariant(query, &self.mir.basic_blocks[invariant_bb]).unwrap();
+            invariants.push(self.encoder.encode_invariant(
+                self.mir,
+                invariant_bb,
+                self.def_id,
+                cl_substs,
+            )?);
+            invariant_spans.push(tcx.def_span(cl_def_id));
+            // The locals captured by the invariant are needed even if the loop
+            // does not use them.
+            live_locals.seek_to_block_start(invariant_bb);
+            argument_locals.extend(live_locals.get().iter());
+        }
+        let mut arguments = vec![];
+        for local in argument_locals {
+            let local_ty = self.mir_encoder.get_local_ty(local);
+            let local_span = self.mir_encoder.get_local_span(local);
+            if !self
+                .encoder
+                .env()
+                .query
+                .type_is_copy(ty::Binder::dummy(local_ty), self.caller_def_id)
+            {
+                let mut error = SpannedEncodingError::unsupported(
+                    format!(
+                        "loops in pure functions can only use variables of Copy types, \
+                        but this variable has type `{local_ty}`"
+                    ),
+                    local_span,
+                );
+                error.add_note("the loop starts here", Some(span.into()));
+                return Err(error);
+            }
+            let encoded_type = self
+                .encoder
+                .encode_snapshot_type(local_ty)
+                .with_span(local_span)?;
+            let formal_arg = vir::LocalVar::new(format!("{local:?}"), encoded_type);
+            arguments.push((local, local_ty, formal_arg));
+        }
+        Ok(LoopFunction {
+            name: format!(
+                "{}$loop{}",
+                self.encoder.encode_item_name(self.def_id),
+                loop_head.index()
+            ),
+            type_arguments: self
+                .encoder
+                .encode_generic_arguments(self.def_id, self.substs)
+                .with_span(span)?,
+            arguments,
+            return_type: self
+                .encoder
+                .encode_snapshot_type(self.mir.return_ty())
+                .with_span(span)?,
+            span,
+            invariants,
+            invariant_spans,
+        })
+    }
+
+    /// Encodes the place that holds the value of the local `local`.
+    fn encode_local_value(
+        &self,
+        local: mir::Local,
+        local_ty: ty::Ty<'tcx>,
+    ) -> SpannedEncodingResult<vir::Expr> {
+        self.encoder
+            .encode_value_expr(
+                vir::Expr::local(self.mir_encoder.encode_local(local)?),
+                local_ty,
+            )
+            .with_span(self.mir_encoder.get_local_span(local))
+    }
+
+    /// Encodes a call of the function of a loop, passing the current values of
+    /// the live locals. The call on a back edge has to establish the loop
+    /// invariants after an iteration.
+    fn encode_loop_function_call(
+        &self,
+        loop_function: &LoopFunction<'tcx>,
+        is_back_edge: bool,
+    ) -> SpannedEncodingResult<vir::Expr> {
+        let mut arguments = vec![];
+        let mut formal_arguments = vec![];
+        for (local, local_ty, formal_arg) in &loop_function.arguments {
+            arguments.push(self.encode_local_value(*local, *local_ty)?);
+            formal_arguments.push(formal_arg.clone());
+        }
+        let pos = if loop_function.invariants.is_empty() {
+            self.encoder.error_manager().register_error(
+                loop_function.span,
+                ErrorCtxt::PureFunctionCall,
+                self.caller_def_id,
+            )
+        } else {
+            let error_ctxt = if is_back_edge {
+                ErrorCtxt::AssertLoopInvariantAfterIteration
+            } else {
+                ErrorCtxt::AssertLoopInvariantOnEntry
+            };
+            self.encoder.error_manager().register_error(
+                MultiSpan::from_spans(loop_function.invariant_spans.clone()),
+                error_ctxt,
+                self.caller_def_id,
+            )
+        };
+        Ok(vir::Expr::func_app(
+            loop_function.name.clone(),
+            loop_function.type_arguments.clone(),
+            arguments,
+            formal_arguments,
+            loop_function.return_type.clone(),
+            pos,
+        ))
+    }
+
+    /// Encodes the function of a loop, given the encoding of the rest of the
+    /// pure function from the loop head onwards, in which the back edges are
+    /// recursive calls.
+    fn encode_loop_function(
+        &self,
+        loop_function: &LoopFunction<'tcx>,
+        body: Option<vir::Expr>,
+    ) -> SpannedEncodingResult<()> {
+        // Like the arguments of a pure function, the live locals are replaced
+        // by their snapshots.
+        let replace_locals = |mut expr: vir::Expr| -> SpannedEncodingResult<vir::Expr> {
+            for (local, local_ty, formal_arg) in &loop_function.arguments {
+                let target_place = self.encode_local_value(*local, *local_ty)?;
+                let mut new_place: vir::Expr = formal_arg.clone().into();
+                if let ty::TyKind::Ref(_, _, _) = local_ty.kind() {
+                    new_place = vir::Expr::snap_app(new_place);
+                }
+                expr = expr.replace_place(&target_place, &new_place);
+            }
+            Ok(expr)
+        };
+        let body = body.map(&replace_locals).transpose()?;
+        let mut pres = loop_function
+            .invariants
+            .iter()
+            .cloned()
+            .map(&replace_locals)
+            .collect::<SpannedEncodingResult<Vec<_>>>()?;
+        // Add the value range of the arguments to the precondition
+        for (_, local_ty, formal_arg) in &loop_function.arguments {
+            if config::check_overflows() {
+                pres.extend(
+                    self.encoder
+                        .encode_type_bounds(&vir::Expr::local(formal_arg.clone()), *local_ty),
+                );
+            } else if config::encode_unsigned_num_constraint() {
+                if let ty::TyKind::Uint(_) = local_ty.kind() {
+                    pres.push(vir::Expr::le_cmp(0u32.into(), formal_arg.into()));
+                }
+            }
+        }
+        let mut function = vir::Function {
+            name: loop_function.name.clone(),
+            type_arguments: loop_function.type_arguments.clone(),
+            formal_args: loop_function
+                .arguments
+                .iter()
+                .map(|(_, _, formal_arg)| formal_arg.clone())
+                .collect(),
+            return_type: loop_function.return_type.clone(),
+            pres,
+            posts: vec![],
+            body,
+        };
+        // The same pure function can be encoded in several contexts.
+        let identifier: vir::FunctionIdentifier = function.get_identifier().into();
+        if self.encoder.contains_function(&identifier) {
+            return Ok(());
+        }
+        if config::simplify_encoding() {
+            function = Simplifier::simplify(function);
+        }
+        self.encoder
+            .mirror_encoder
+            .borrow_mut()
+            .encode_mirror_of_helper(&mut function);
+        function = self
+            .encoder
+            .patch_snapshots_function(function)
+            .with_span(loop_function.span)?;
+        self.encoder
+            .log_vir_program_before_viper(function.to_string());
+        self.encoder.insert_function(function);
+        Ok(())
+    }
+
     fn encode_place(
         &self,
         place: mir::Place<'tcx>,
@@ -299,7 +820,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
             TerminatorKind::FalseUnwind {
                 ref real_target, ..
             } => {
-                assert_eq!(states.len(), 1);
+                assert!(states.len() == 1 || states.len() == 2); // the second is the unwind edge of a loop
                 states[real_target].clone()
             }
 
@@ -413,7 +934,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                         call_substs
                     );
                     let def_id = *def_id;
-                    let tcx = self.encoder.env().tcx();
                     let env_name = self.encoder.env().name;
                     let full_func_proc_name: &str = &env_name.get_absolute_item_name(def_id);
                     let func_proc_name = &self.encoder.env().name.get_item_name(def_id);
@@ -442,6 +962,17 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
+                            "prusti_contracts::prev_iter" => {
+                                assert_eq!(args.len(), 1);
+                                let encoded_rhs = self.mir_encoder.encode_old_expr(
+                                    vir::Expr::snap_app(encoded_args[0].clone()),
+                                    PREV_ITER_LABEL,
+                                );
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_rhs);
+                                state
+                            }
+
                             "prusti_contracts::before_expiry" => {
                                 trace!("Encoding before_expiry expression {:?}", args[0]);
                                 assert_eq!(args.len(), 1);
@@ -454,10 +985,25 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
+                            // With the identity hash model, unsigned integers are
+                            // their own hash; otherwise the hash is uninterpreted.
+                            "prusti_contracts::hash_model::hash"
+                                if config::hash_model() == config::HashModel::Identity
+                                    && matches!(
+                                        self.mir_encoder.get_operand_ty(&args[0]).peel_refs().kind(),
+                                        ty::TyKind::Uint(uint_ty) if *uint_ty != ty::UintTy::U128
+                                    ) =>
+                            {
+                                assert_eq!(args.len(), 1);
+                                let encoded_rhs = vir::Expr::snap_app(encoded_args[0].clone());
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_rhs);
+                                state
+                            }
+
                             "std::cmp::PartialEq::eq" | "core::cmp::PartialEq::eq"
-                                if self.encoder.has_structural_eq_impl(
-                                    self.mir_encoder.get_operand_ty(&args[0]),
-                                ) =>
+                                if self
+                                    .has_snapshot_eq(self.mir_encoder.get_operand_ty(&args[0])) =>
                             {
                                 assert_eq!(args.len(), 2);
                                 let encoded_rhs = vir::Expr::eq_cmp(
@@ -470,9 +1016,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                             }
 
                             "std::cmp::PartialEq::ne" | "core::cmp::PartialEq::ne"
-                                if self.encoder.has_structural_eq_impl(
-                                    self.mir_encoder.get_operand_ty(&args[0]),
-                                ) =>
+                                if self
+                                    .has_snapshot_eq(self.mir_encoder.get_operand_ty(&args[0])) =>
                             {
                                 assert_eq!(args.len(), 2);
                                 let encoded_rhs = vir::Expr::ne_cmp(
@@ -484,7 +1029,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
-                            "core::slice::<impl [T]>::len" => {
+                            "core::slice::<impl [T]>::len" | "core::str::<impl str>::len" => {
                                 assert_eq!(args.len(), 1);
                                 let slice_ty = self.mir_encoder.get_operand_ty(&args[0]);
                                 let len = self
@@ -497,68 +1042,173 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
-                            "std::ops::Index::index" | "core::ops::Index::index" => {
+                            "core::slice::<impl [T]>::is_empty"
+                            | "core::str::<impl str>::is_empty" => {
+                                assert_eq!(args.len(), 1);
+                                let slice_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let len = self
+                                    .encoder
+                                    .encode_snapshot_slice_len(slice_ty, encoded_args[0].clone())
+                                    .with_span(span)?;
+                                let encoded_rhs = vir::Expr::eq_cmp(len, vir::Expr::from(0usize));
+
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_rhs);
+                                state
+                            }
+
+                            "core::slice::<impl [T]>::contains"
+                                if self.has_snapshot_eq(call_substs.type_at(0)) =>
+                            {
                                 assert_eq!(args.len(), 2);
-                                trace!("slice::index(args={:?}, encoded_args={:?}, ty={:?}, encoded_lhs={:?})", args, encoded_args, ty, encoded_lhs);
+                                let slice_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let encoded_rhs = self
+                                    .encoder
+                                    .encode_snapshot_slice_contains(
+                                        slice_ty,
+                                        encoded_args[0].clone(),
+                                        encoded_args[1].clone(),
+                                    )
+                                    .with_span(span)?;
 
-                                let base_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_rhs);
+                                state
+                            }
+
+                            "core::slice::<impl [T]>::starts_with"
+                            | "core::slice::<impl [T]>::ends_with"
+                                if self.has_snapshot_eq(call_substs.type_at(0)) =>
+                            {
+                                assert_eq!(args.len(), 2);
+                                let slice_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let at_end = full_func_proc_name.ends_with("ends_with");
+                                let encoded_rhs = self
+                                    .encoder
+                                    .encode_snapshot_slice_affix(
+                                        slice_ty,
+                                        encoded_args[0].clone(),
+                                        encoded_args[1].clone(),
+                                        at_end,
+                                    )
+                                    .with_span(span)?;
+
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_rhs);
+                                state
+                            }
 
+                            // A string slice is encoded as the slice of its bytes.
+                            "core::str::<impl str>::as_bytes" => {
+                                assert_eq!(args.len(), 1);
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_args[0].clone());
+                                state
+                            }
+
+                            "std::ops::Index::index" | "core::ops::Index::index"
+                                if self.encoder.env().query.is_vec(
+                                    self.mir_encoder.get_operand_ty(&args[0]).peel_refs(),
+                                ) =>
+                            {
+                                assert_eq!(args.len(), 2);
+                                // A vector is indexed through its slice.
+                                let vec_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let (slice_ty, slice) = self.encode_vec_as_slice(
+                                    vec_ty,
+                                    &args[0],
+                                    encoded_args[0].clone(),
+                                    span,
+                                )?;
                                 let idx_ty = self.mir_encoder.get_operand_ty(&args[1]);
-                                let idx_ty_did = match idx_ty.ty_adt_def() {
-                                    Some(def) => def.did(),
-                                    None => return Err(SpannedEncodingError::unsupported(
-                                        format!("Using {idx_ty} as index/range type for {base_ty} is not currently supported in pure functions"),
+                                let len = self
+                                    .encoder
+                                    .encode_snapshot_slice_len(slice_ty, slice.clone())
+                                    .with_span(span)?;
+                                // `Index::index` of `Vec` panics if the index
+                                // is out of bounds.
+                                let (encoded_rhs, checks) = if idx_ty.is_integral() {
+                                    let idx = self
+                                        .encoder
+                                        .patch_snapshots(vir::Expr::snap_app(encoded_args[1].clone()))
+                                        .with_span(span)?;
+                                    let in_bounds = vir::Expr::lt_cmp(idx.clone(), len);
+                                    let encoded_rhs = self
+                                        .encoder
+                                        .encode_snapshot_slice_idx(slice_ty, slice, idx)
+                                        .with_span(span)?;
+                                    (encoded_rhs, vec![(in_bounds, ErrorCtxt::BoundsCheckAssert)])
+                                } else {
+                                    let (start, end) = self.encode_range_bounds(
+                                        vec_ty,
+                                        idx_ty,
+                                        &encoded_args[1],
+                                        || Ok(len.clone()),
                                         span,
-                                    ))
-                                };
-                                let idx_ident = env_name.get_absolute_item_name(idx_ty_did);
-                                let encoded_idx = &encoded_args[1];
-
-                                // TODO: what do we actually do here? this seems a littly hacky.
-                                // there's fields like _5.f$start.val_int on `encoded_idx`, it just feels hacky to
-                                // manually re-do them here when we probably just encoded the type and the
-                                // construction of the fields..
-                                // Also, duplication with procedure_encoder.rs
-                                let usize_ty =
-                                    tcx.mk_ty_from_kind(ty::TyKind::Uint(ty::UintTy::Usize));
-                                let start = match &*idx_ident {
-                                    "std::ops::Range" | "core::ops::Range" |
-                                    "std::ops::RangeFrom" | "core::ops::RangeFrom" =>
-                                        self.encoder.encode_struct_field_value(encoded_idx.clone(), "start", usize_ty).with_span(span)?,
-                                    // See procedure_encoder.rs
-                                    "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
-                                        SpannedEncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string(), span)
-                                    ),
-                                    "std::ops::RangeTo" | "core::ops::RangeTo" |
-                                    "std::ops::RangeFull" | "core::ops::RangeFull" |
-                                    "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => vir::Expr::from(0u32),
-                                    _ => unreachable!("{}", idx_ident)
+                                    )?;
+                                    let checks = vec![
+                                        (
+                                            vir::Expr::le_cmp(end.clone(), len.clone()),
+                                            ErrorCtxt::SliceRangeBoundsCheckAssert(
+                                                "the range end value may be out of bounds when \
+                                                slicing"
+                                                    .to_string(),
+                                            ),
+                                        ),
+                                        (
+                                            vir::Expr::le_cmp(start.clone(), end.clone()),
+                                            ErrorCtxt::SliceRangeBoundsCheckAssert(
+                                                "the range end may be smaller than the start when \
+                                                slicing"
+                                                    .to_string(),
+                                            ),
+                                        ),
+                                    ];
+                                    let encoded_rhs = self
+                                        .encoder
+                                        .encode_snapshot_slicing(slice_ty, slice, ty, start, end)
+                                        .with_span(span)?;
+                                    (encoded_rhs, checks)
                                 };
-                                let end = match &*idx_ident {
-                                    "std::ops::Range" | "core::ops::Range" |
-                                    "std::ops::RangeTo" | "core::ops::RangeTo" =>
-                                        self.encoder.encode_struct_field_value(encoded_idx.clone(), "end", usize_ty).with_span(span)?,
-                                    "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
-                                        SpannedEncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string(), span)
-                                    ),
-                                    "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => {
-                                        let end_expr = self.encoder.encode_struct_field_value(encoded_idx.clone(), "end", usize_ty).with_span(span)?;
-                                        vir::Expr::add(end_expr, vir::Expr::from(1u32))
-                                    }
-                                    "std::ops::RangeFrom" | "core::ops::RangeFrom" |
-                                    "std::ops::RangeFull" | "core::ops::RangeFull" => {
+
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_rhs);
+                                // The first check is the outermost one.
+                                for (guard, error_ctxt) in checks.into_iter().rev() {
+                                    let pos = self.encoder.error_manager().register_error(
+                                        span,
+                                        error_ctxt,
+                                        self.caller_def_id,
+                                    );
+                                    state = self.encode_assertion(guard, &state, pos, span)?;
+                                }
+                                state
+                            }
+
+                            "std::ops::Index::index" | "core::ops::Index::index" => {
+                                assert_eq!(args.len(), 2);
+                                trace!("slice::index(args={:?}, encoded_args={:?}, ty={:?}, encoded_lhs={:?})", args, encoded_args, ty, encoded_lhs);
+
+                                let base_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let idx_ty = self.mir_encoder.get_operand_ty(&args[1]);
+                                let (start, end) = self.encode_range_bounds(
+                                    base_ty,
+                                    idx_ty,
+                                    &encoded_args[1],
+                                    || {
                                         if base_ty.peel_refs().is_array() {
                                             let array_len = self.encoder.encode_sequence_types(base_ty.peel_refs()).with_span(span)?.sequence_len.unwrap();
-                                            vir::Expr::from(array_len)
-                                        } else if base_ty.is_slice() {
+                                            Ok(vir::Expr::from(array_len))
+                                        } else if base_ty.is_slice() || base_ty.peel_refs().is_str()
+                                        {
                                             let base = self.mir_encoder.encode_operand_place(&args[0]).with_span(span)?.unwrap();
                                             let base_expr = self.encoder.encode_value_expr(base, base_ty).with_span(span)?;
                                             let slice_types_base = self.encoder.encode_sequence_types(base_ty.peel_refs()).with_span(span)?;
-                                            slice_types_base.len(self.encoder, base_expr)
+                                            Ok(slice_types_base.len(self.encoder, base_expr))
                                         } else { todo!("Get last idx for {}", base_ty) }
-                                    }
-                                    _ => unreachable!("{}", idx_ident)
-                                };
+                                    },
+                                    span,
+                                )?;
 
                                 let slice_expr = self
                                     .encoder
@@ -576,6 +1226,80 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
+                            // Snapshots do not distinguish a box from its contents, so
+                            // `Box::new(x)` is encoded as `x`, both when the box is
+                            // dereferenced and when it is used as a whole.
+                            "std::boxed::Box::<T>::new" | "alloc::boxed::Box::<T>::new" => {
+                                assert_eq!(args.len(), 1);
+                                let deref_field = self
+                                    .encoder
+                                    .encode_dereference_field(call_substs.type_at(0))
+                                    .with_span(span)?;
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(
+                                    &encoded_lhs.clone().field(deref_field),
+                                    encoded_args[0].clone(),
+                                );
+                                state.substitute_value(&encoded_lhs, encoded_args[0].clone());
+                                state
+                            }
+
+                            // `a..=b` is desugared to a call, which is encoded like the
+                            // construction of the struct in `a..b`.
+                            "std::ops::RangeInclusive::<Idx>::new"
+                            | "core::ops::RangeInclusive::<Idx>::new" => {
+                                assert_eq!(args.len(), 2);
+                                let idx_ty = call_substs.type_at(0);
+                                let bool_ty = self.encoder.env().tcx().types.bool;
+                                let fields = [
+                                    ("start", idx_ty, encoded_args[0].clone()),
+                                    ("end", idx_ty, encoded_args[1].clone()),
+                                    ("exhausted", bool_ty, false.into()),
+                                ];
+                                let mut state = states[&target_block].clone();
+                                let mut field_exprs = vec![];
+                                for (field_name, field_ty, encoded_rhs) in fields {
+                                    let field_value = self
+                                        .encoder
+                                        .encode_struct_field_value(
+                                            encoded_lhs.clone(),
+                                            field_name,
+                                            field_ty,
+                                        )
+                                        .with_span(span)?;
+                                    state.substitute_value(&field_value, encoded_rhs.clone());
+                                    field_exprs.push(encoded_rhs);
+                                }
+                                let snapshot = self
+                                    .encoder
+                                    .encode_snapshot(ty, Some(0), field_exprs)
+                                    .with_span(span)?;
+                                state.substitute_value(&encoded_lhs, snapshot);
+                                state
+                            }
+
+                            "std::ops::Range::<Idx>::contains"
+                            | "core::ops::Range::<Idx>::contains"
+                            | "std::ops::RangeInclusive::<Idx>::contains"
+                            | "core::ops::RangeInclusive::<Idx>::contains"
+                                if (call_substs.type_at(0).is_integral()
+                                    || call_substs.type_at(0).is_char())
+                                    && call_substs.type_at(0) == call_substs.type_at(1) =>
+                            {
+                                assert_eq!(args.len(), 2);
+                                let encoded_rhs = self
+                                    .encode_range_contains(
+                                        full_func_proc_name.contains("RangeInclusive"),
+                                        call_substs.type_at(0),
+                                        encoded_args[0].clone(),
+                                        encoded_args[1].clone(),
+                                    )
+                                    .with_span(span)?;
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_rhs);
+                                state
+                            }
+
                             // Prusti-specific syntax
                             // TODO: check we are in a spec function
                             "prusti_contracts::exists"
@@ -583,7 +1307,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                             | "prusti_contracts::specification_entailment"
                             | "prusti_contracts::call_description"
                             | "prusti_contracts::snap"
-                            | "prusti_contracts::snapshot_equality" => {
+                            | "prusti_contracts::snapshot_equality"
+                            | "prusti_contracts::call_summary"
+                            | "prusti_contracts::closure_ensures" => {
                                 let expr = self.encoder.encode_prusti_operation(
                                     full_func_proc_name,
                                     span,
@@ -615,11 +1341,40 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                             call_substs,
                                         )
                                         .with_span(span)?
-                                } else {
-                                    return Err(SpannedEncodingError::incorrect(
-                                        format!(
-                                            "use of impure function {func_proc_name:?} in pure code is not allowed"
+                                } else if self
+                                    .encoder
+                                    .env()
+                                    .query
+                                    .has_prusti_attribute(self.def_id, "call_summary")
+                                {
+                                    // The call of `call_summary!(..)`, which is not executed
+                                    let is_primitive = |ty: ty::Ty<'tcx>| {
+                                        ty.is_bool() || ty.is_integral() || ty.is_char()
+                                    };
+                                    if !is_primitive(ty)
+                                        || args.iter().any(|arg| {
+                                            !is_primitive(self.mir_encoder.get_operand_ty(arg))
+                                        })
+                                    {
+                                        error_unsupported!(span =>
+                                            "`call_summary!(..)` is only supported for calls whose \
+                                            arguments and result are booleans, integers or characters"
+                                        );
+                                    }
+                                    (
+                                        encode_call_summary_function_name(
+                                            self.encoder,
+                                            called_def_id,
+                                            call_substs,
                                         ),
+                                        self.encoder.encode_snapshot_type(ty).with_span(span)?,
+                                    )
+                                } else {
+                                    return Err(impure_function_call_error(
+                                        self.encoder.env(),
+                                        self.def_id,
+                                        called_def_id,
+                                        func_proc_name,
                                         span,
                                     ));
                                 };
@@ -747,42 +1502,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                     self.caller_def_id,
                 );
 
-                match self.pure_encoding_context {
-                    PureEncodingContext::Trigger => {
-                        // We are encoding a trigger, so all panic branches must be stripped.
-                        states[target].clone()
-                    }
-                    PureEncodingContext::Assertion
-                        if matches!(self.mir.return_ty().kind(), ty::TyKind::Bool) =>
-                    {
-                        // We are encoding an assertion, so all failures should be equivalent to false.
-                        // Predicates are also encoded as assertions, but non-Boolean predicates should
-                        // use the other arm.
-                        ExprBackwardInterpreterState::new(states[target].expr().map(
-                            |target_expr| {
-                                vir::Expr::ite(
-                                    viper_guard.clone(),
-                                    target_expr.clone(),
-                                    false.into(),
-                                )
-                            },
-                        ))
-                    }
-                    PureEncodingContext::Assertion | PureEncodingContext::Code => {
-                        // We are encoding a pure function, so all failures should be unreachable.
-                        let failure_encoding =
-                            unreachable_expr(pos).with_span(term.source_info.span)?;
-                        ExprBackwardInterpreterState::new(states[target].expr().map(
-                            |target_expr| {
-                                vir::Expr::ite(
-                                    viper_guard.clone(),
-                                    target_expr.clone(),
-                                    failure_encoding,
-                                )
-                            },
-                        ))
-                    }
-                }
+                self.encode_assertion(viper_guard, states[target], pos, term.source_info.span)?
             }
 
             TerminatorKind::Yield { .. }
@@ -1301,4 +2021,29 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
 
         Ok(())
     }
+
+    /// Loops are functionalized: the rest of the pure function from the loop
+    /// head onwards is encoded as a function of the locals that are live at
+    /// the loop head, and the back edges become recursive calls.
+    fn apply_loop_back_edge(
+        &self,
+        loop_head: mir::BasicBlock,
+    ) -> Result<Option<Self::State>, Self::Error> {
+        let loop_function = self.get_loop_function(loop_head)?;
+        Ok(Some(ExprBackwardInterpreterState::new_defined(
+            self.encode_loop_function_call(&loop_function, true)?,
+        )))
+    }
+
+    fn apply_loop_head(
+        &self,
+        loop_head: mir::BasicBlock,
+        state: Self::State,
+    ) -> Result<Self::State, Self::Error> {
+        let loop_function = self.get_loop_function(loop_head)?;
+        self.encode_loop_function(&loop_function, state.into_expr())?;
+        Ok(ExprBackwardInterpreterState::new_defined(
+            self.encode_loop_function_call(&loop_function, false)?,
+        ))
+    }
 }
@@ -13,3 +13,93 @@ pub(super) mod interpreter_high;
 pub(super) mod interpreter_poly;
 
 pub use backward_interpreter::*;
+
+use crate::encoder::errors::SpannedEncodingError;
+use prusti_interface::environment::Environment;
+use prusti_rustc_interface::{
+    errors::Applicability,
+    hir::def_id::DefId,
+    span::{BytePos, Span},
+};
+
+/// The error reported for a call of the impure function `called_def_id` in
+/// pure code, such as a specification. The pure code is the body of
+/// `caller_def_id`. If the function is local, the error suggests marking it
+/// as pure; otherwise, it points to external specifications.
+fn impure_function_call_error(
+    env: &Environment,
+    caller_def_id: DefId,
+    called_def_id: DefId,
+    func_proc_name: &str,
+    span: Span,
+) -> SpannedEncodingError {
+    let mut error = SpannedEncodingError::incorrect(
+        format!("use of impure function {func_proc_name:?} in pure code is not allowed"),
+        span,
+    );
+    if let Some(clause) = spec_clause_description(env, caller_def_id) {
+        error.add_note(format!("the function is called in {clause}"), None);
+    }
+    let def_span = env.query.get_def_span(called_def_id);
+    if called_def_id.is_local() && !def_span.from_expansion() {
+        // Insert the attribute on a line of its own, indented like the function.
+        let indentation = env
+            .tcx()
+            .sess
+            .source_map()
+            .indentation_before(def_span)
+            .unwrap_or_default();
+        let line_start = def_span
+            .with_lo(def_span.lo() - BytePos(indentation.len() as u32))
+            .shrink_to_lo();
+        // Whether the function is deterministic and has no side effects is not
+        // checked, so the fix might be incorrect.
+        error.add_suggestion(
+            "if the function is deterministic and has no side effects, mark it as pure",
+            line_start,
+            format!("{indentation}#[pure]\n"),
+            Applicability::MaybeIncorrect,
+        );
+    } else {
+        error.set_help(format!(
+            "to use {func_proc_name:?} in specifications, declare it as `#[pure]` in an \
+            external specification (see `#[extern_spec]`)"
+        ));
+    }
+    error
+}
+
+/// Describes the specification clause whose body is `def_id`, such as "a
+/// precondition", or returns `None` if `def_id` is not a specification clause.
+fn spec_clause_description(env: &Environment, def_id: DefId) -> Option<&'static str> {
+    let attribute_clauses = [
+        ("loop_body_invariant_spec", "a loop invariant"),
+        ("loop_body_variant_spec", "a loop variant"),
+        ("type_invariant_spec", "a type invariant"),
+        ("prusti_assertion", "a `prusti_assert!`"),
+        ("prusti_assumption", "a `prusti_assume!`"),
+        ("prusti_refutation", "a `prusti_refute!`"),
+    ];
+    if let Some((_, clause)) = attribute_clauses
+        .into_iter()
+        .find(|(attribute, _)| env.query.has_prusti_attribute(def_id, attribute))
+    {
+        return Some(clause);
+    }
+    // The kind of the other clauses is part of the name of their items.
+    let name = env.tcx().opt_item_name(def_id)?;
+    let name_prefix_clauses = [
+        ("prusti_pre_item_", "a precondition"),
+        ("prusti_post_item_", "a postcondition"),
+        (
+            "prusti_post_on_completion_item_",
+            "a postcondition on completion",
+        ),
+        ("prusti_pledge_item_", "a pledge"),
+        ("prusti_term_item_", "a termination measure"),
+    ];
+    name_prefix_clauses
+        .into_iter()
+        .find(|(prefix, _)| name.as_str().starts_with(prefix))
+        .map(|(_, clause)| clause)
+}
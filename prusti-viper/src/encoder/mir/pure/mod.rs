@@ -4,5 +4,9 @@ mod specifications;
 
 pub(crate) use self::{
     pure_functions::{PureEncodingContext, PureFunctionEncoderInterface, PureFunctionEncoderState},
-    specifications::SpecificationEncoderInterface,
+    specifications::{
+        call_summary_function_name, collect_call_summaries, get_call_summary_callee,
+        replace_call_summaries, replace_call_summaries_with_conditions,
+        SpecificationEncoderInterface,
+    },
 };
@@ -23,6 +23,7 @@ use crate::encoder::{
 };
 use log::debug;
 use prusti_common::{config, vir::optimizations::functions::Simplifier, vir_local};
+use rustc_hash::FxHashSet;
 
 use prusti_rustc_interface::{
     hir,
@@ -35,7 +36,7 @@ use vir_crate::{
     common::identifier::WithIdentifier,
     high as vir_high,
     high::operations::identifier::compute_function_identifier,
-    polymorphic::{self as vir, ExprIterator},
+    polymorphic::{self as vir, ExprFolder, ExprIterator, ExprWalker},
 };
 
 pub(super) struct PureFunctionEncoder<'p, 'v: 'p, 'tcx: 'v> {
@@ -74,6 +75,7 @@ pub(super) fn encode_body<'p, 'v: 'p, 'tcx: 'v>(
         proc_def_id,
         pure_encoding_context,
         parent_def_id,
+        substs,
     )
 }
 
@@ -99,6 +101,7 @@ pub(super) fn encode_promoted<'p, 'v: 'p, 'tcx: 'v>(
         proc_def_id.did,
         PureEncodingContext::Code,
         parent_def_id,
+        substs,
     )
 }
 
@@ -114,6 +117,7 @@ fn encode_mir<'p, 'v: 'p, 'tcx: 'v>(
     proc_def_id: DefId,
     pure_encoding_context: PureEncodingContext,
     parent_def_id: DefId,
+    substs: SubstsRef<'tcx>,
 ) -> SpannedEncodingResult<vir::Expr> {
     let interpreter = PureFunctionBackwardInterpreter::new(
         encoder,
@@ -121,6 +125,7 @@ fn encode_mir<'p, 'v: 'p, 'tcx: 'v>(
         proc_def_id,
         pure_encoding_context,
         parent_def_id,
+        substs,
     );
 
     let function_name = encoder.env().name.get_absolute_item_name(proc_def_id);
@@ -188,6 +193,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionEncoder<'p, 'v, 'tcx> {
             self.proc_def_id,
             self.pure_encoding_context,
             self.parent_def_id,
+            self.substs,
         );
 
         self.local_spans = Some(
@@ -282,7 +288,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionEncoder<'p, 'v, 'tcx> {
                 *predicate_body,
             ));
 
-        self.encode_function_given_body(Some(predicate_body_encoded))
+        let mut function = self.encode_function_given_body(Some(predicate_body_encoded))?;
+        let unfold_depth = self.encoder.get_predicate_unfold_depth(self.proc_def_id);
+        if unfold_depth > 1 {
+            unfold_recursive_calls(&mut function, unfold_depth);
+        }
+        Ok(function)
     }
 
     // Private
@@ -657,6 +668,113 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionEncoder<'p, 'v, 'tcx> {
     }
 }
 
+/// Unfolds the body of a recursive function into its own definition, so that
+/// the definition covers `depth` levels of recursion. Recursive calls whose
+/// unfolding could capture variables bound in the body are left untouched.
+fn unfold_recursive_calls(function: &mut vir::Function, depth: u32) {
+    let Some(body) = function.body.clone() else {
+        return;
+    };
+    let mut collector = BoundVariablesCollector::default();
+    collector.walk(&body);
+    let bound_variables = collector.bound_variables;
+    if function
+        .formal_args
+        .iter()
+        .any(|arg| bound_variables.contains(arg))
+    {
+        debug!(
+            "Not unfolding {}, because a formal argument is shadowed in its body",
+            function.name
+        );
+        return;
+    }
+    let mut unfolder = RecursiveCallUnfolder {
+        function: &*function,
+        body: &body,
+        bound_variables: &bound_variables,
+    };
+    let mut unfolded_body = body.clone();
+    for _ in 1..depth {
+        unfolded_body = unfolder.fold(unfolded_body);
+    }
+    function.body = Some(unfolded_body);
+}
+
+#[derive(Default)]
+struct BoundVariablesCollector {
+    bound_variables: FxHashSet<vir::LocalVar>,
+}
+
+impl ExprWalker for BoundVariablesCollector {
+    fn walk(&mut self, expr: &vir::Expr) {
+        match expr {
+            vir::Expr::ForAll(vir::ForAll { variables, .. })
+            | vir::Expr::Exists(vir::Exists { variables, .. }) => {
+                self.bound_variables.extend(variables.iter().cloned());
+            }
+            vir::Expr::LetExpr(vir::LetExpr { variable, .. }) => {
+                self.bound_variables.insert(variable.clone());
+            }
+            _ => {}
+        }
+        vir::default_walk_expr(self, expr);
+    }
+}
+
+#[derive(Default)]
+struct LocalVariablesCollector {
+    variables: FxHashSet<vir::LocalVar>,
+}
+
+impl ExprWalker for LocalVariablesCollector {
+    fn walk_local_var(&mut self, var: &vir::LocalVar) {
+        self.variables.insert(var.clone());
+    }
+}
+
+struct RecursiveCallUnfolder<'a> {
+    function: &'a vir::Function,
+    body: &'a vir::Expr,
+    bound_variables: &'a FxHashSet<vir::LocalVar>,
+}
+
+impl<'a> RecursiveCallUnfolder<'a> {
+    fn is_unfoldable(&self, expr: &vir::FuncApp) -> bool {
+        if expr.function_name != self.function.name
+            || expr.type_arguments != self.function.type_arguments
+        {
+            return false;
+        }
+        let mut collector = LocalVariablesCollector::default();
+        for arg in &expr.arguments {
+            collector.walk(arg);
+        }
+        collector.variables.is_disjoint(self.bound_variables)
+    }
+}
+
+impl<'a> ExprFolder for RecursiveCallUnfolder<'a> {
+    fn fold(&mut self, expr: vir::Expr) -> vir::Expr {
+        match expr {
+            vir::Expr::FuncApp(func_app) if self.is_unfoldable(&func_app) => {
+                let replacements: Vec<_> = self
+                    .function
+                    .formal_args
+                    .iter()
+                    .cloned()
+                    .map(vir::Expr::local)
+                    .zip(func_app.arguments)
+                    .collect();
+                // The unfolded body is not folded again, so that each
+                // iteration unfolds every recursive call exactly once.
+                self.body.clone().replace_multiple_places(&replacements)
+            }
+            _ => vir::default_fold_expr(self, expr),
+        }
+    }
+}
+
 pub(super) struct FunctionCallInfo {
     pub name: String,
     pub type_arguments: Vec<vir::Type>,
@@ -0,0 +1,259 @@
+// © 2022, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Encoding of `call_summary!(f(args))`, which refers in a postcondition to
+//! the result of the last call `f(args)` made by the specified function.
+//!
+//! In assertions, the summarized call is encoded as an application of the
+//! function `call_summary$<f>` to the values of the arguments, where `<f>`
+//! also contains the type arguments of the call. The procedure encoder
+//! replaces these applications: at the end of the specified function by the
+//! result of the last call of `f`, whose arguments have to be equal to the
+//! given ones, and at the calls of the specified function by existentially
+//! quantified values, for which the postcondition of `f` holds.
+
+use crate::encoder::Encoder;
+use prusti_common::utils::identifiers::encode_identifier;
+use prusti_interface::data::ProcedureDefId;
+use prusti_rustc_interface::middle::ty::subst::SubstsRef;
+use vir_crate::polymorphic::{self as vir, ExprFolder, ExprIterator, ExprWalker};
+
+const CALL_SUMMARY_FUNCTION_PREFIX: &str = "call_summary$";
+
+/// Returns the name of the function that encodes summaries of calls of
+/// `def_id` with the type arguments `substs`.
+pub(crate) fn call_summary_function_name<'tcx>(
+    encoder: &Encoder<'_, 'tcx>,
+    def_id: ProcedureDefId,
+    substs: SubstsRef<'tcx>,
+) -> String {
+    let item_name = encoder.encode_item_name(def_id);
+    let substs = encoder.env().tcx().erase_regions(substs);
+    if substs.is_empty() {
+        format!("{CALL_SUMMARY_FUNCTION_PREFIX}{item_name}")
+    } else {
+        let substs = substs
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{CALL_SUMMARY_FUNCTION_PREFIX}{item_name}${}",
+            encode_identifier(substs)
+        )
+    }
+}
+
+/// Like `call_summary_function_name`, but also remembers the callee so that
+/// its contract can be retrieved from the name.
+pub(crate) fn encode_call_summary_function_name<'tcx>(
+    encoder: &Encoder<'_, 'tcx>,
+    def_id: ProcedureDefId,
+    substs: SubstsRef<'tcx>,
+) -> String {
+    let name = call_summary_function_name(encoder, def_id, substs);
+    encoder
+        .call_summary_callees
+        .borrow_mut()
+        .insert(name.clone(), (def_id, substs));
+    name
+}
+
+/// Returns the callee of the call summaries encoded with the function `name`.
+pub(crate) fn get_call_summary_callee<'tcx>(
+    encoder: &Encoder<'_, 'tcx>,
+    name: &str,
+) -> (ProcedureDefId, SubstsRef<'tcx>) {
+    encoder.call_summary_callees.borrow()[name]
+}
+
+/// Returns the call summaries in `expr`, in the order in which they appear.
+pub(crate) fn collect_call_summaries(expr: &vir::Expr) -> Vec<vir::FuncApp> {
+    struct CallSummaryCollector {
+        call_summaries: Vec<vir::FuncApp>,
+    }
+    impl ExprWalker for CallSummaryCollector {
+        fn walk_func_app(&mut self, func_app: &vir::FuncApp) {
+            if func_app
+                .function_name
+                .starts_with(CALL_SUMMARY_FUNCTION_PREFIX)
+            {
+                self.call_summaries.push(func_app.clone());
+            }
+            for arg in &func_app.arguments {
+                self.walk(arg);
+            }
+        }
+    }
+    let mut collector = CallSummaryCollector {
+        call_summaries: vec![],
+    };
+    collector.walk(expr);
+    collector.call_summaries
+}
+
+/// Replaces each call summary in `expr` by the expression returned by
+/// `replacement` for it.
+pub(crate) fn replace_call_summaries(
+    expr: vir::Expr,
+    replacement: impl FnMut(&vir::FuncApp) -> vir::Expr,
+) -> vir::Expr {
+    struct CallSummaryReplacer<F> {
+        replacement: F,
+    }
+    impl<F: FnMut(&vir::FuncApp) -> vir::Expr> ExprFolder for CallSummaryReplacer<F> {
+        fn fold_func_app(&mut self, func_app: vir::FuncApp) -> vir::Expr {
+            if func_app
+                .function_name
+                .starts_with(CALL_SUMMARY_FUNCTION_PREFIX)
+            {
+                (self.replacement)(&func_app)
+            } else {
+                vir::Expr::FuncApp(vir::FuncApp {
+                    arguments: func_app
+                        .arguments
+                        .into_iter()
+                        .map(|arg| self.fold(arg))
+                        .collect(),
+                    ..func_app
+                })
+            }
+        }
+    }
+    CallSummaryReplacer { replacement }.fold(expr)
+}
+
+/// Like `replace_call_summaries`, but `replacement` also returns conditions
+/// that have to hold for its result to be the value of the summary. The
+/// conditions are conjoined to the resulting expression, guarded by the
+/// conditions under which the summary is evaluated, so that they are only
+/// required if it is.
+pub(crate) fn replace_call_summaries_with_conditions(
+    expr: vir::Expr,
+    replacement: impl FnMut(&vir::FuncApp) -> (vir::Expr, Vec<vir::Expr>),
+) -> vir::Expr {
+    struct CallSummaryReplacer<F> {
+        replacement: F,
+        /// The conditions under which the folded expression is evaluated.
+        path_conditions: Vec<vir::Expr>,
+        /// The variables bound by the quantifiers around the folded expression.
+        bound_vars: Vec<vir::LocalVar>,
+        conditions: Vec<vir::Expr>,
+    }
+    impl<F: FnMut(&vir::FuncApp) -> (vir::Expr, Vec<vir::Expr>)> CallSummaryReplacer<F> {
+        /// Folds `expr`, which is only evaluated if `path_condition` holds.
+        fn fold_under(
+            &mut self,
+            path_condition: vir::Expr,
+            expr: Box<vir::Expr>,
+        ) -> Box<vir::Expr> {
+            self.path_conditions.push(path_condition);
+            let expr = self.fold_boxed(expr);
+            self.path_conditions.pop();
+            expr
+        }
+    }
+    impl<F: FnMut(&vir::FuncApp) -> (vir::Expr, Vec<vir::Expr>)> ExprFolder for CallSummaryReplacer<F> {
+        fn fold_bin_op(&mut self, bin_op: vir::BinOp) -> vir::Expr {
+            let vir::BinOp {
+                op_kind,
+                left,
+                right,
+                position,
+            } = bin_op;
+            let left = self.fold_boxed(left);
+            let right = match op_kind {
+                vir::BinaryOpKind::And | vir::BinaryOpKind::Implies => {
+                    self.fold_under((*left).clone(), right)
+                }
+                vir::BinaryOpKind::Or => self.fold_under(vir::Expr::not((*left).clone()), right),
+                _ => self.fold_boxed(right),
+            };
+            vir::Expr::BinOp(vir::BinOp {
+                op_kind,
+                left,
+                right,
+                position,
+            })
+        }
+
+        fn fold_cond(&mut self, cond: vir::Cond) -> vir::Expr {
+            let vir::Cond {
+                guard,
+                then_expr,
+                else_expr,
+                position,
+            } = cond;
+            let guard = self.fold_boxed(guard);
+            let then_expr = self.fold_under((*guard).clone(), then_expr);
+            let else_expr = self.fold_under(vir::Expr::not((*guard).clone()), else_expr);
+            vir::Expr::Cond(vir::Cond {
+                guard,
+                then_expr,
+                else_expr,
+                position,
+            })
+        }
+
+        fn fold_forall(&mut self, forall: vir::ForAll) -> vir::Expr {
+            let bound_vars_len = self.bound_vars.len();
+            self.bound_vars.extend(forall.variables.iter().cloned());
+            let body = self.fold_boxed(forall.body);
+            self.bound_vars.truncate(bound_vars_len);
+            vir::Expr::ForAll(vir::ForAll { body, ..forall })
+        }
+
+        fn fold_exists(&mut self, exists: vir::Exists) -> vir::Expr {
+            let bound_vars_len = self.bound_vars.len();
+            self.bound_vars.extend(exists.variables.iter().cloned());
+            let body = self.fold_boxed(exists.body);
+            self.bound_vars.truncate(bound_vars_len);
+            vir::Expr::Exists(vir::Exists { body, ..exists })
+        }
+
+        fn fold_func_app(&mut self, func_app: vir::FuncApp) -> vir::Expr {
+            let func_app = vir::FuncApp {
+                arguments: func_app
+                    .arguments
+                    .into_iter()
+                    .map(|arg| self.fold(arg))
+                    .collect(),
+                ..func_app
+            };
+            if !func_app
+                .function_name
+                .starts_with(CALL_SUMMARY_FUNCTION_PREFIX)
+            {
+                return vir::Expr::FuncApp(func_app);
+            }
+            let (replacement, conditions) = (self.replacement)(&func_app);
+            if !conditions.is_empty() {
+                let mut condition = conditions.into_iter().conjoin();
+                if !self.path_conditions.is_empty() {
+                    let path_condition = self.path_conditions.iter().cloned().conjoin();
+                    condition = vir::Expr::implies(path_condition, condition);
+                }
+                if !self.bound_vars.is_empty() {
+                    condition = vir::Expr::forall(self.bound_vars.clone(), vec![], condition);
+                }
+                self.conditions.push(condition);
+            }
+            replacement
+        }
+    }
+    let mut replacer = CallSummaryReplacer {
+        replacement,
+        path_conditions: vec![],
+        bound_vars: vec![],
+        conditions: vec![],
+    };
+    let expr = replacer.fold(expr);
+    if replacer.conditions.is_empty() {
+        expr
+    } else {
+        vir::Expr::and(replacer.conditions.into_iter().conjoin(), expr)
+    }
+}
@@ -9,14 +9,21 @@ use crate::{
         errors::{EncodingError, EncodingResult, SpannedEncodingResult, WithSpan},
         high::types::HighTypeEncoderInterface,
         mir::{
-            pure::{specifications::utils::extract_closure_from_ty, PureFunctionEncoderInterface},
+            contracts::ContractsEncoderInterface,
+            pure::{
+                specifications::{
+                    call_summaries::collect_call_summaries, utils::extract_closure_from_ty,
+                    SpecificationEncoderInterface,
+                },
+                PureFunctionEncoderInterface,
+            },
             types::MirTypeEncoderInterface,
         },
         mir_encoder::{MirEncoder, PlaceEncoder},
         snapshot::interface::SnapshotEncoderInterface,
         Encoder,
     },
-    error_incorrect,
+    error_incorrect, error_unsupported,
 };
 use prusti_common::config;
 use prusti_rustc_interface::{
@@ -159,7 +166,9 @@ pub(super) fn encode_quantifier<'tcx>(
 
     let mut encoded_qvars = vec![];
     let mut bounds = vec![];
+    let mut qvar_values = vec![];
     for (arg_idx, arg_ty) in args.into_iter().enumerate() {
+        qvar_values.push(encode_finite_type_values(encoder, arg_ty).with_span(body_span)?);
         let qvar_ty = encoder.encode_snapshot_type(arg_ty).with_span(body_span)?;
         let qvar_name = format!(
             "_{}_quant_{}",
@@ -240,6 +249,42 @@ pub(super) fn encode_quantifier<'tcx>(
         parent_def_id,
         body_substs,
     )?;
+    // the arguments of a summarized call must not depend on the bound variables
+    if !collect_call_summaries(&encoded_body).is_empty() {
+        error_unsupported!(body_span => "`call_summary!(..)` cannot be used in quantifiers");
+    }
+
+    // quantifiers over types with few values are expanded into a finite
+    // conjunction (or disjunction) of the body instantiated with each value
+    if let Some(qvar_values) = qvar_values.into_iter().collect::<Option<Vec<_>>>() {
+        let case_count = qvar_values.iter().map(Vec::len).product::<usize>();
+        if case_count <= MAX_QUANTIFIER_CASE_SPLITS {
+            let mut cases = vec![vec![]];
+            for (qvar, values) in encoded_qvars.iter().zip(qvar_values.iter()) {
+                cases = cases
+                    .into_iter()
+                    .flat_map(|case: Vec<_>| {
+                        values.iter().map(move |value| {
+                            let mut case = case.clone();
+                            case.push((
+                                vir_crate::polymorphic::Expr::local(qvar.clone()),
+                                value.clone(),
+                            ));
+                            case
+                        })
+                    })
+                    .collect();
+            }
+            let mut instantiations = cases
+                .iter()
+                .map(|case| encoded_body.clone().replace_multiple_places(case));
+            return Ok(if is_exists {
+                instantiations.disjoin()
+            } else {
+                instantiations.conjoin()
+            });
+        }
+    }
 
     // replace qvars with a nicer name based on quantifier depth to ensure that
     // quantifiers remain stable for caching
@@ -289,6 +334,89 @@ pub(super) fn encode_quantifier<'tcx>(
     }
 }
 
+pub(super) fn encode_call_summary<'tcx>(
+    encoder: &Encoder<'_, 'tcx>,
+    encoded_args: Vec<vir_crate::polymorphic::Expr>,
+    parent_def_id: DefId,
+    substs: ty::subst::SubstsRef<'tcx>,
+) -> SpannedEncodingResult<vir_crate::polymorphic::Expr> {
+    // `call_summary!(f(args))` is encoded as `call_summary(|| f(args))`; the
+    // call in the body of the closure is encoded as a call summary by the
+    // interpreter, since the closure is marked with `#[prusti::call_summary]`
+    let (call_def_id, call_substs, _, _, _) =
+        extract_closure_from_ty(encoder.env().query, substs.type_at(1));
+    inline_closure(
+        encoder,
+        call_def_id,
+        encoded_args[0].clone(),
+        vec![],
+        parent_def_id,
+        call_substs,
+    )
+}
+
+/// Encodes `closure_ensures(&f, arg, result)` as the postcondition of `f`, if
+/// `f` is a function or a closure, applied to `arg` and `result`.
+pub(super) fn encode_closure_ensures<'tcx>(
+    encoder: &Encoder<'_, 'tcx>,
+    span: Span,
+    encoded_args: Vec<vir_crate::polymorphic::Expr>,
+    parent_def_id: DefId,
+    substs: ty::subst::SubstsRef<'tcx>,
+) -> SpannedEncodingResult<vir_crate::polymorphic::Expr> {
+    let (callee_def_id, callee_substs) = match substs.type_at(0).kind() {
+        ty::TyKind::FnDef(def_id, substs) | ty::TyKind::Closure(def_id, substs) => {
+            (*def_id, *substs)
+        }
+        _ => return Ok(true.into()),
+    };
+    let contract = encoder
+        .get_procedure_contract_for_def(callee_def_id, callee_substs)
+        .with_span(span)?;
+    let target_args = [encoded_args[1].clone()];
+    let mut posts = vec![];
+    for (assertion, assertion_substs) in
+        contract.functional_postcondition(encoder.env(), callee_substs)
+    {
+        posts.push(encoder.encode_assertion(
+            &assertion,
+            None,
+            &target_args,
+            Some(&encoded_args[2]),
+            true,
+            parent_def_id,
+            assertion_substs,
+        )?);
+    }
+    Ok(posts.into_iter().conjoin())
+}
+
+/// The maximum number of cases into which a quantifier over finite types is
+/// split. Quantifiers with more cases are encoded as SMT quantifiers.
+const MAX_QUANTIFIER_CASE_SPLITS: usize = 16;
+
+/// Returns the snapshots of all values of `ty`, if `ty` is `bool` or an enum
+/// whose variants have no fields.
+fn encode_finite_type_values<'tcx>(
+    encoder: &Encoder<'_, 'tcx>,
+    ty: ty::Ty<'tcx>,
+) -> EncodingResult<Option<Vec<vir_crate::polymorphic::Expr>>> {
+    match ty.kind() {
+        ty::TyKind::Bool => Ok(Some(vec![true.into(), false.into()])),
+        ty::TyKind::Adt(adt_def, _)
+            if adt_def.is_enum()
+                && adt_def.variants().len() <= MAX_QUANTIFIER_CASE_SPLITS
+                && adt_def.all_fields().next().is_none() =>
+        {
+            (0..adt_def.variants().len())
+                .map(|variant_idx| encoder.encode_snapshot(ty, Some(variant_idx), vec![]))
+                .collect::<Result<_, _>>()
+                .map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
 fn find_quantifier_depth(expr: &vir_crate::polymorphic::Expr) -> usize {
     use vir_crate::polymorphic::ExprWalker;
     struct DepthChecker {
@@ -19,7 +19,10 @@ use crate::encoder::{
                 encoder_high::{
                     encode_quantifier_high, inline_closure_high, inline_spec_item_high,
                 },
-                encoder_poly::{encode_quantifier, inline_closure, inline_spec_item},
+                encoder_poly::{
+                    encode_call_summary, encode_closure_ensures, encode_quantifier, inline_closure,
+                    inline_spec_item,
+                },
             },
             PureEncodingContext,
         },
@@ -250,6 +253,12 @@ impl<'v, 'tcx: 'v> SpecificationEncoderInterface<'tcx> for crate::encoder::Encod
                 parent_def_id,
                 substs,
             ),
+            "prusti_contracts::call_summary" => {
+                encode_call_summary(self, encoded_args, parent_def_id, substs)
+            }
+            "prusti_contracts::closure_ensures" => {
+                encode_closure_ensures(self, span, encoded_args, parent_def_id, substs)
+            }
             "prusti_contracts::snap" => Ok(vir_poly::Expr::snap_app(encoded_args[0].clone())),
             "prusti_contracts::snapshot_equality" => Ok(vir_poly::Expr::eq_cmp(
                 vir_poly::Expr::snap_app(encoded_args[0].clone()),
@@ -355,6 +364,7 @@ impl<'v, 'tcx: 'v> SpecificationEncoderInterface<'tcx> for crate::encoder::Encod
             parent_def_id,
             PureEncodingContext::Code,
             parent_def_id,
+            substs,
         );
         let invariant = run_backward_interpretation_point_to_point(
             mir,
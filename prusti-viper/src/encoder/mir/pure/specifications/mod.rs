@@ -6,9 +6,14 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+mod call_summaries;
 mod encoder_high;
 mod encoder_poly;
 mod interface;
 mod utils;
 
+pub(crate) use call_summaries::{
+    call_summary_function_name, collect_call_summaries, encode_call_summary_function_name,
+    get_call_summary_callee, replace_call_summaries, replace_call_summaries_with_conditions,
+};
 pub(crate) use interface::SpecificationEncoderInterface;
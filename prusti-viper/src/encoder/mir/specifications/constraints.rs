@@ -77,6 +77,27 @@ impl<'spec, 'env: 'spec, 'tcx: 'env> ConstraintResolver<'spec, 'env, 'tcx>
             },
         };
 
+        // The constraints are checked against the static types. For a trait
+        // object, they do not tell which specification applies to the
+        // type behind it, so using either of them would be unsound.
+        if let Some(caller_def_id) = context.caller_proc_def_id {
+            if context.substs.types().any(|ty| ty.is_trait()) {
+                let span = env.query.get_def_span(context.proc_def_id);
+                return Err(PrustiError::unsupported(
+                    "Calls through trait objects to functions with type-conditional spec refinements are not supported",
+                    MultiSpan::from_span(span),
+                )
+                .add_note(
+                    "The refinement that applies depends on the type behind the trait object, which is unknown",
+                    None,
+                )
+                .add_note(
+                    "This error is triggered because of a call to this function",
+                    Some(env.query.get_def_span(caller_def_id)),
+                ));
+            }
+        }
+
         let mut applicable_specs =
             self.specs_with_constraints
                 .iter()
@@ -143,9 +164,9 @@ mod trait_bounds {
         context: &ConstraintSolvingContext<'tcx>,
         proc_spec: &'spec ProcedureSpecification,
     ) -> bool {
-        let param_env_constraint = extract_param_env(env, proc_spec);
+        let (param_env_constraint, spec_id) = extract_param_env(env, proc_spec);
         let param_env_constraint =
-            perform_param_env_substitutions(env, context, param_env_constraint);
+            perform_param_env_substitutions(env, context, spec_id, param_env_constraint);
 
         // There is no caller when encoding a function.
         // We still resolve obligations to account for constrained specs on a trait
@@ -174,21 +195,29 @@ mod trait_bounds {
             })
     }
 
-    /// Substitutes the param environment
+    /// Substitutes the param environment of the spec function `spec_id`
     #[tracing::instrument(level = "debug", skip(env), ret)]
     fn perform_param_env_substitutions<'env, 'tcx: 'env>(
         env: &'env Environment<'tcx>,
         context: &ConstraintSolvingContext<'tcx>,
+        spec_id: DefId,
         param_env: ty::ParamEnv<'tcx>,
     ) -> ty::ParamEnv<'tcx> {
+        // Only spec functions declared in the trait are generic over the
+        // trait's parameters. Those of a method in a `#[refine_trait_spec]`
+        // impl are generic over the parameters of the impl.
         let maybe_trait_method = env
             .query
-            .find_trait_method_substs(context.proc_def_id, context.substs);
-        let param_env = if let Some((_, trait_substs)) = maybe_trait_method {
+            .find_trait_method_substs(context.proc_def_id, context.substs)
+            .filter(|_| env.query.get_trait_of_item(spec_id).is_some());
+        let (param_env, call_substs) = if let Some((_, trait_substs)) = maybe_trait_method {
             trace!("Applying trait substs {:?}", trait_substs);
-            ty::EarlyBinder(param_env).subst(env.tcx(), trait_substs)
+            (
+                ty::EarlyBinder(param_env).subst(env.tcx(), trait_substs),
+                context.substs,
+            )
         } else {
-            param_env
+            (param_env, translate_call_substs(env, context, spec_id))
         };
 
         trace!(
@@ -196,19 +225,51 @@ mod trait_bounds {
             param_env
         );
 
-        if context.substs.is_empty() {
+        if call_substs.is_empty() {
             param_env
         } else {
-            trace!("Applying call substs {:?}", context.substs);
-            ty::EarlyBinder(param_env).subst(env.tcx(), context.substs)
+            trace!("Applying call substs {:?}", call_substs);
+            ty::EarlyBinder(param_env).subst(env.tcx(), call_substs)
+        }
+    }
+
+    /// Translates the substitutions of the call to the generics of the spec
+    /// function `spec_id`. The spec functions generated for methods of a
+    /// `#[refine_trait_spec]` impl are free functions which merge the
+    /// generics of the impl and of the method, without the lifetimes, so that
+    /// their parameters are matched with those of the procedure by name.
+    fn translate_call_substs<'tcx>(
+        env: &Environment<'tcx>,
+        context: &ConstraintSolvingContext<'tcx>,
+        spec_id: DefId,
+    ) -> SubstsRef<'tcx> {
+        if context.substs.is_empty() {
+            return context.substs;
         }
+        let tcx = env.tcx();
+        let proc_generics = tcx.generics_of(context.proc_def_id);
+        let proc_params: FxHashMap<_, _> = (0..proc_generics.count())
+            .map(|index| {
+                let param = proc_generics.param_at(index, tcx);
+                (param.name, context.substs[index])
+            })
+            .collect();
+        ty::List::for_item(tcx, spec_id, |param, _| {
+            proc_params
+                .get(&param.name)
+                .copied()
+                .unwrap_or_else(|| tcx.mk_param_from_def(param))
+        })
     }
 
+    /// Extracts the param environment of the constrained specification,
+    /// together with one of its spec functions.
     fn extract_param_env<'a, 'tcx>(
         env: &'a Environment<'tcx>,
         spec: &ProcedureSpecification,
-    ) -> ty::ParamEnv<'tcx> {
-        let mut param_envs: FxHashMap<ty::ParamEnv<'tcx>, Vec<Span>> = FxHashMap::default();
+    ) -> (ty::ParamEnv<'tcx>, DefId) {
+        let mut param_envs: FxHashMap<ty::ParamEnv<'tcx>, Vec<(DefId, Span)>> =
+            FxHashMap::default();
 
         let pres: Vec<DefId> = spec
             .pres
@@ -230,7 +291,10 @@ mod trait_bounds {
             let spec_span = env.query.get_def_span(spec_id);
             let attrs = env.query.get_attributes(*spec_id);
             if has_trait_bounds_type_cond_spec(attrs) {
-                param_envs.entry(param_env).or_default().push(spec_span);
+                param_envs
+                    .entry(param_env)
+                    .or_default()
+                    .push((*spec_id, spec_span));
             }
         }
 
@@ -240,7 +304,11 @@ mod trait_bounds {
             "Could not extract trait bound obligations from contract"
         );
         if param_envs.len() > 1 {
-            let spans = param_envs.values().flatten().cloned().collect();
+            let spans = param_envs
+                .values()
+                .flatten()
+                .map(|&(_, span)| span)
+                .collect();
             PrustiError::unsupported(
                 "Multiple type-conditional spec refinements with different bounds defined",
                 MultiSpan::from_spans(spans),
@@ -249,6 +317,7 @@ mod trait_bounds {
             .emit(&env.diagnostic);
         }
 
-        param_envs.into_keys().next().unwrap()
+        let (param_env, specs) = param_envs.into_iter().next().unwrap();
+        (param_env, specs[0].0)
     }
 }
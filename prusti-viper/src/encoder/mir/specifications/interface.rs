@@ -1,10 +1,11 @@
 use crate::encoder::mir::specifications::specs::Specifications;
+use prusti_common::config;
 use prusti_interface::{
     specs::{
         typed,
         typed::{DefSpecificationMap, ProcedureSpecification, ProcedureSpecificationKind},
     },
-    utils::has_spec_only_attr,
+    utils::{has_spec_only_attr, read_prusti_attr},
 };
 use prusti_rustc_interface::{hir::def_id::DefId, middle::ty::subst::SubstsRef, span::Span};
 use std::{cell::RefCell, hash::Hash};
@@ -82,6 +83,10 @@ pub(crate) trait SpecificationsInterface<'tcx> {
 
     fn get_predicate_body(&self, def_id: DefId, substs: SubstsRef<'tcx>) -> Option<DefId>;
 
+    /// Get the number of times the body of the predicate `def_id` is unfolded
+    /// into its own definition, as given by `#[unfold_depth(n)]`.
+    fn get_predicate_unfold_depth(&self, def_id: DefId) -> u32;
+
     fn terminates(&self, def_id: DefId, substs: Option<SubstsRef<'tcx>>) -> bool;
 
     /// Get the loop invariant attached to a function with a
@@ -121,6 +126,13 @@ pub(crate) trait SpecificationsInterface<'tcx> {
         call_substs: SubstsRef<'tcx>,
     ) -> Option<typed::ProcedureSpecification>;
 
+    /// Get the specifications attached to a function together with its
+    /// type-conditional spec refinements, without resolving which one applies.
+    fn get_procedure_spec_graph(
+        &self,
+        def_id: DefId,
+    ) -> Option<typed::SpecGraph<typed::ProcedureSpecification>>;
+
     /// Is the closure specified with the `def_id` spec only?
     fn is_spec_closure(&self, def_id: DefId) -> bool;
 
@@ -188,6 +200,17 @@ impl<'v, 'tcx: 'v> SpecificationsInterface<'tcx> for super::super::super::Encode
             .cloned()
     }
 
+    #[tracing::instrument(level = "trace", skip(self), ret)]
+    fn get_predicate_unfold_depth(&self, def_id: DefId) -> u32 {
+        read_prusti_attr("unfold_depth", self.env().query.get_attributes(def_id))
+            .map(|depth| {
+                depth
+                    .parse()
+                    .unwrap_or_else(|_| panic!("cannot parse the unfold_depth of {def_id:?}"))
+            })
+            .unwrap_or_else(config::predicate_unfold_depth)
+    }
+
     #[tracing::instrument(level = "trace", skip(self), ret)]
     fn terminates(&self, def_id: DefId, substs: Option<SubstsRef<'tcx>>) -> bool {
         let substs = substs.unwrap_or_else(|| self.env().query.identity_substs(def_id));
@@ -288,6 +311,17 @@ impl<'v, 'tcx: 'v> SpecificationsInterface<'tcx> for super::super::super::Encode
         Some(spec.clone())
     }
 
+    fn get_procedure_spec_graph(
+        &self,
+        def_id: DefId,
+    ) -> Option<typed::SpecGraph<typed::ProcedureSpecification>> {
+        self.specifications_state
+            .specs
+            .borrow()
+            .get_proc_spec_graph(&def_id)
+            .cloned()
+    }
+
     fn is_spec_closure(&self, def_id: DefId) -> bool {
         has_spec_only_attr(self.env().query.get_attributes(def_id))
     }
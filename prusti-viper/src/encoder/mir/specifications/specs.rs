@@ -11,7 +11,8 @@ use prusti_interface::{
     specs::typed::{
         DefSpecificationMap, GhostBegin, GhostEnd, LoopSpecification, ProcedureSpecification,
         ProcedureSpecificationKind, ProcedureSpecificationKindError, PrustiAssertion,
-        PrustiAssumption, PrustiRefutation, Refinable, SpecificationItem, TypeSpecification,
+        PrustiAssumption, PrustiRefutation, Refinable, SpecGraph, SpecificationItem,
+        TypeSpecification,
     },
     PrustiError,
 };
@@ -175,6 +176,15 @@ impl<'tcx> Specifications<'tcx> {
         })
     }
 
+    /// Returns the specifications of a procedure together with its
+    /// type-conditional spec refinements, without resolving which one applies.
+    pub(super) fn get_proc_spec_graph(
+        &self,
+        def_id: &DefId,
+    ) -> Option<&SpecGraph<ProcedureSpecification>> {
+        self.user_typed_specs.get_proc_spec(def_id)
+    }
+
     fn is_refined(&self, query: &SpecQuery<'tcx>) -> bool {
         self.refined_specs.contains_key(query)
     }
@@ -190,7 +190,12 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
 
             ty::TyKind::Never => vir::Type::Never,
 
-            ty::TyKind::Str => vir::Type::Str,
+            // A string slice is encoded as the slice of its UTF-8 bytes.
+            ty::TyKind::Str => vir::Type::slice(
+                self.encoder
+                    .encode_type_high(self.encoder.env().tcx().types.u8)?,
+                lifetimes,
+            ),
 
             ty::TyKind::Array(elem_ty, size) => {
                 let (array_len, tail): (_, &[vir::Expression]) =
@@ -243,7 +248,11 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 )
             }
 
-            ty::TyKind::Dynamic(..) => vir::Type::unsupported("dynamic".to_string()),
+            // A trait object is opaque, like a type parameter: all that is
+            // known about it are the contracts of its trait's methods.
+            ty::TyKind::Dynamic(predicates, ..) => vir::Type::TypeVar(
+                vir::ty::TypeVar::generic_type(encode_trait_object_name(self.encoder, predicates)),
+            ),
 
             ty::TyKind::FnPtr(..) => vir::Type::unsupported("fnptr".to_string()),
 
@@ -473,6 +482,14 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
             ty::TyKind::Param(param_ty) => {
                 vir::TypeDecl::type_var(param_ty.name.as_str().to_string())
             }
+            ty::TyKind::Dynamic(predicates, ..) => {
+                vir::TypeDecl::type_var(encode_trait_object_name(self.encoder, predicates))
+            }
+            // A projection that cannot be normalized, e.g. `<T as Trait>::Item<'a>`
+            // of a generic associated type, is opaque like a type parameter.
+            ty::TyKind::Alias(ty::AliasKind::Projection, ty::AliasTy { def_id, .. }) => {
+                vir::TypeDecl::type_var(self.encoder.encode_item_name(*def_id))
+            }
             ty::TyKind::Closure(def_id, internal_substs) => {
                 let cl_substs = internal_substs.as_closure();
                 let arguments = cl_substs
@@ -692,6 +709,17 @@ fn encode_struct_name<'v, 'tcx: 'v>(encoder: &Encoder<'v, 'tcx>, did: DefId) ->
     format!("struct${}", encoder.encode_item_name(did))
 }
 
+fn encode_trait_object_name<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    predicates: &ty::List<ty::PolyExistentialPredicate<'tcx>>,
+) -> String {
+    match predicates.principal_def_id() {
+        Some(did) => format!("dyn${}", encoder.encode_item_name(did)),
+        // Only auto traits, such as `dyn Send`.
+        None => "dyn$auto".to_string(),
+    }
+}
+
 fn encode_trusted_name<'v, 'tcx: 'v>(encoder: &Encoder<'v, 'tcx>, did: DefId) -> String {
     format!("trusted${}", encoder.encode_item_name(did))
 }
@@ -806,7 +834,7 @@ pub(super) fn encode_adt_def<'v, 'tcx>(
             // vir::TypeDecl::Struct(encode_variant(encoder, name, substs, variant)?)
             unimplemented!("FIXME: How this should be implemented?")
         } else {
-            let discriminant_bounds = compute_discriminant_ranges(adt_def, tcx);
+            let discriminant_bounds = compute_discriminant_ranges(adt_def, substs, tcx);
             let discriminant_values = compute_discriminant_values(adt_def, tcx)
                 .into_iter()
                 .collect();
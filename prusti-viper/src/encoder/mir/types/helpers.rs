@@ -23,12 +23,37 @@ pub(crate) fn compute_discriminant_values<'tcx>(
 }
 
 /// Encode a disjunction that lists all possible discrimintant values.
+///
+/// Variants with a field of an uninhabited type, such as `!`, cannot be
+/// constructed, so their discriminants are left out; this makes the code
+/// handling them unreachable. If no variant can be constructed, all
+/// discriminants are kept, because a `false` here is unsound (see issues #38
+/// and #158).
 pub(super) fn compute_discriminant_ranges<'tcx>(
     adt_def: ty::AdtDef<'tcx>,
+    substs: ty::subst::SubstsRef<'tcx>,
     tcx: ty::TyCtxt<'tcx>,
 ) -> Vec<vir_high::DiscriminantRange> {
     let discr_values = compute_discriminant_values(adt_def, tcx);
-    range_extract(discr_values)
+    let param_env = ty::ParamEnv::reveal_all();
+    let inhabited_discr_values: Vec<i128> = adt_def
+        .variants()
+        .iter()
+        .zip(discr_values.iter())
+        .filter(|(variant, _)| {
+            !variant.fields.iter().any(|field| {
+                field
+                    .ty(tcx, substs)
+                    .is_privately_uninhabited(tcx, param_env)
+            })
+        })
+        .map(|(_, &discr)| discr)
+        .collect();
+    if inhabited_discr_values.is_empty() {
+        range_extract(discr_values)
+    } else {
+        range_extract(inhabited_discr_values)
+    }
 }
 
 /// Encode a disjunction that lists all possible discrimintant values.
@@ -37,6 +37,7 @@ pub use place_encoding::{PlaceEncoding, ExprOrArrayBase};
 
 pub static PRECONDITION_LABEL: &str = "pre";
 pub static WAND_LHS_LABEL: &str = "lhs";
+pub static PREV_ITER_LABEL: &str = "prev_iter";
 
 pub trait PlaceEncoder<'v, 'tcx: 'v> {
 
@@ -184,14 +185,23 @@ pub trait PlaceEncoder<'v, 'tcx: 'v> {
             }
 
             mir::ProjectionElem::Deref => {
-                match encoded_base.try_into_expr() {
+                match encoded_base.clone().try_into_expr() {
                     Ok(e) => {
                         let (e, ty, v) = self.encode_deref(e, base_ty)?;
                         (PlaceEncoding::Expr(e), ty, v)
                     }
-                    Err(_) => error_unsupported!(
-                        "mixed dereferencing and array indexing projections are not supported"
-                    ),
+                    // Dereferencing an element of an array or a slice, e.g.
+                    // `*m[i]` in `m[i][j]` for `m: &[&[T]]`
+                    Err(_) => match base_ty.kind() {
+                        ty::TyKind::RawPtr(ty::TypeAndMut { ty, .. })
+                        | ty::TyKind::Ref(_, ty, _) => {
+                            let ref_field = self.encoder().encode_dereference_field(*ty)?;
+                            (encoded_base.field(ref_field), *ty, None)
+                        }
+                        _ => error_unsupported!(
+                            "mixed dereferencing and array indexing projections are not supported"
+                        ),
+                    },
                 }
             }
 
@@ -502,6 +512,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
             mir::BinOp::Le => vir::Expr::le_cmp(left, right),
             mir::BinOp::Add => vir::Expr::add(left, right),
             mir::BinOp::Sub => vir::Expr::sub(left, right),
+            mir::BinOp::Div | mir::BinOp::Rem
+                if ty.is_floating_point()
+                    && config::float_encoding() == config::FloatEncoding::Real =>
+            {
+                error_unsupported!(
+                    "division of floating-point numbers is not supported with \
+                    `float_encoding=real`; use `float_encoding=ieee` instead"
+                );
+            }
             mir::BinOp::Rem => vir::Expr::rem(left, right),
             mir::BinOp::Div => vir::Expr::div(left, right),
             mir::BinOp::Mul => vir::Expr::mul(left, right),
@@ -674,8 +693,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         let encoded_val = match (src_ty.kind(), dst_ty.kind()) {
             // Numeric casts that cannot fail
             | (ty::TyKind::Char, ty::TyKind::Char)
-            | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U8))
-            | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U16))
             | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U32))
             | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U64))
             | (ty::TyKind::Char, ty::TyKind::Uint(ty::UintTy::U128))
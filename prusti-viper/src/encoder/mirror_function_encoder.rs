@@ -35,7 +35,7 @@ impl MirrorEncoder {
 
 
     pub fn get_domain(&self) -> Option<&vir::Domain> {
-        if self.encoded.is_empty() {
+        if self.domain.functions.is_empty() {
             None
         } else {
             Some(&self.domain)
@@ -54,13 +54,21 @@ impl MirrorEncoder {
         }
         self.encoded.insert(def_id);
 
-        self.encode_mirror_simple(def_id, function);
+        self.encode_mirror_simple(function);
         self.encode_mirror_axiomatized(def_id, function);
     }
 
+    /// Encodes the mirror of a helper function that does not correspond to a
+    /// Rust function, such as the encoding of a loop in a pure function.
+    pub fn encode_mirror_of_helper(
+        &mut self,
+        function: &mut vir::Function,
+    ) {
+        self.encode_mirror_simple(function);
+    }
+
     fn encode_mirror_simple(
         &mut self,
-        _def_id: DefId,
         function: &mut vir::Function,
     ) {
         // create mirror function
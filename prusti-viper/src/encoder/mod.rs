@@ -18,6 +18,7 @@ mod mir_successor;
 mod name_interner;
 mod places;
 mod procedure_encoder;
+mod refinement_check_encoder;
 mod stub_function_encoder;
 mod stub_procedure_encoder;
 mod utils;
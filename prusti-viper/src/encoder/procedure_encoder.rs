@@ -16,7 +16,7 @@ use crate::encoder::high::types::HighTypeEncoderInterface;
 use crate::encoder::initialisation::InitInfo;
 use crate::encoder::loop_encoder::{LoopEncoder, LoopEncoderError};
 use crate::encoder::mir_encoder::{MirEncoder, FakeMirEncoder, PlaceEncoder, PlaceEncoding, ExprOrArrayBase};
-use crate::encoder::mir_encoder::PRECONDITION_LABEL;
+use crate::encoder::mir_encoder::{PRECONDITION_LABEL, PREV_ITER_LABEL};
 use crate::encoder::mir_successor::MirSuccessor;
 use crate::encoder::places::{Local, LocalVariableManager, Place};
 use crate::encoder::Encoder;
@@ -45,9 +45,10 @@ use prusti_interface::{
             LoanPlaces, PoloniusInfo, PoloniusInfoError, ReborrowingDAG, ReborrowingDAGNode,
             ReborrowingKind, ReborrowingZombity,
         },
+        get_loop_unrolling_bound, is_loop_invariant_block, is_loop_variant_block,
         BasicBlockIndex, LoopAnalysisError, PermissionKind, Procedure,
     },
-    PrustiError,
+    PrustiError, UnsupportedFeature,
 };
 use std::collections::{BTreeMap};
 use std::fmt::Debug;
@@ -57,6 +58,14 @@ use prusti_rustc_interface::middle::mir;
 use prusti_rustc_interface::middle::mir::{TerminatorKind};
 use prusti_rustc_interface::middle::ty::{self, subst::SubstsRef};
 use prusti_rustc_interface::target::abi::Integer;
+use prusti_rustc_interface::abi::FieldIdx;
+use prusti_rustc_interface::dataflow::{
+    impls::MaybeInitializedPlaces,
+    move_paths::{LookupResult, MoveData, MovePathIndex},
+    on_all_children_bits, Analysis, MoveDataParamEnv,
+};
+use prusti_rustc_interface::index::bit_set::ChunkedBitSet;
+use once_cell::unsync::OnceCell;
 use rustc_hash::{FxHashMap, FxHashSet};
 use prusti_rustc_interface::span::Span;
 use prusti_rustc_interface::errors::MultiSpan;
@@ -76,13 +85,16 @@ use crate::encoder::mir::{
     },
     pure::PureFunctionEncoderInterface,
     types::MirTypeEncoderInterface,
-    pure::SpecificationEncoderInterface,
+    pure::{
+        SpecificationEncoderInterface, call_summary_function_name, collect_call_summaries,
+        get_call_summary_callee, replace_call_summaries, replace_call_summaries_with_conditions,
+    },
     specifications::SpecificationsInterface,
     type_invariants::TypeInvariantEncoderInterface,
 };
 use super::high::generics::HighGenericsEncoderInterface;
 use super::counterexamples::DiscriminantsStateInterface;
-use prusti_interface::environment::mir_utils::SliceOrArrayRef;
+use prusti_interface::environment::mir_utils::{AsyncFnArgsForMir, SliceOrArrayRef};
 
 pub struct ProcedureEncoder<'p, 'v: 'p, 'tcx: 'v> {
     encoder: &'p Encoder<'v, 'tcx>,
@@ -114,8 +126,12 @@ pub struct ProcedureEncoder<'p, 'v: 'p, 'tcx: 'v> {
     array_magic_wand_at: FxHashMap<mir::Location, (vir::Expr, vir::Expr, vir::Expr)>,
     /// Labels for array equalities in loops
     array_loop_old_label: FxHashMap<BasicBlockIndex, String>,
-    /// Slices created at certain locations
-    slice_created_at: FxHashMap<mir::Location, vir::Expr>,
+    /// The labels of the states at the beginning of a loop iteration, in which
+    /// `prev_iter(..)` expressions are evaluated at the end of the iteration.
+    loop_iteration_label: FxHashMap<BasicBlockIndex, String>,
+    /// Slices created at certain locations, with the statements that give the
+    /// permission back to the sliced sequence when the slice expires.
+    slice_created_at: FxHashMap<mir::Location, (vir::Expr, Vec<vir::Stmt>)>,
     // /// Contracts of functions called at given locations with map for replacing fake expressions.
     procedure_contracts:
         FxHashMap<mir::Location, (ProcedureContract<'tcx>, FxHashMap<vir::Expr, vir::Expr>)>,
@@ -133,8 +149,21 @@ pub struct ProcedureEncoder<'p, 'v: 'p, 'tcx: 'v> {
     /// Type substitutions inside this procedure. Most likely identity for the
     /// given proc_def_id.
     substs: SubstsRef<'tcx>,
+    /// Whether the procedure is annotated with `#[trusted_unsafe_memory]`, which
+    /// allows creating, copying and dereferencing raw pointers.
+    unsafe_memory: bool,
+    /// The local variables that record the result and the arguments of the
+    /// last call of each function summarized with `call_summary!(..)` in the
+    /// postcondition, by the name of the function encoding the summaries.
+    call_summaries: FxHashMap<String, (vir::LocalVar, Vec<vir::LocalVar>)>,
+    /// The move paths of the procedure and the ones that might be initialized
+    /// before each terminator, computed when a drop is first encoded.
+    maybe_initialized_places: OnceCell<MaybeInitializedPlacesAtTerminators<'tcx>>,
 }
 
+type MaybeInitializedPlacesAtTerminators<'tcx> =
+    (MoveData<'tcx>, FxHashMap<mir::Location, ChunkedBitSet<MovePathIndex>>);
+
 impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
     #[tracing::instrument(name = "ProcedureEncoder::new", level = "debug", skip_all, fields(proc_def_id = ?procedure.get_id()))]
     pub fn new(
@@ -184,6 +213,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             magic_wand_at_location: FxHashMap::default(),
             array_magic_wand_at: FxHashMap::default(),
             array_loop_old_label: FxHashMap::default(),
+            loop_iteration_label: FxHashMap::default(),
             slice_created_at: FxHashMap::default(),
             procedure_contracts: FxHashMap::default(),
             pure_var_for_preserving_value_map: FxHashMap::default(),
@@ -192,6 +222,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             old_ghost_vars: FxHashMap::default(),
             cached_loop_invariant_block: FxHashMap::default(),
             substs,
+            unsafe_memory: encoder
+                .env()
+                .query
+                .has_prusti_attribute(proc_def_id, "trusted_unsafe_memory"),
+            call_summaries: FxHashMap::default(),
+            maybe_initialized_places: OnceCell::new(),
         })
     }
 
@@ -239,9 +275,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 mir::Rvalue::Aggregate(box mir::AggregateKind::Closure(cl_def_id, cl_substs), _),
             )) = stmt.kind
             {
-                if self.encoder.get_prusti_assumption(cl_def_id).is_none() {
-                    return Ok(false);
-                }
+                let assumption = match self.encoder.get_prusti_assumption(cl_def_id) {
+                    Some(spec) => spec,
+                    None => return Ok(false),
+                };
                 let assume_expr = self.encoder.encode_invariant(self.mir, bb, self.proc_def_id, cl_substs)?;
 
                 let assume_stmt = vir::Stmt::Inhale(
@@ -250,7 +287,29 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     }
                 );
 
-                encoded_statements.push(assume_stmt);
+                if config::report_vacuous_specs() {
+                    // The assumption is vacuous if the code after it is
+                    // unreachable although the assumption itself is reachable.
+                    let span = self
+                        .encoder
+                        .get_definition_span(assumption.assumption.to_def_id());
+                    let reachability_check = self.register_error(span, ErrorCtxt::UnreachableAssumption);
+                    let vacuity_check = self.register_error(
+                        span,
+                        ErrorCtxt::VacuousAssumption(reachability_check.id()),
+                    );
+                    encoded_statements.push(vir::Stmt::Refute(vir::Refute {
+                        expr: false.into(),
+                        position: reachability_check,
+                    }));
+                    encoded_statements.push(assume_stmt);
+                    encoded_statements.push(vir::Stmt::Refute(vir::Refute {
+                        expr: false.into(),
+                        position: vacuity_check,
+                    }));
+                } else {
+                    encoded_statements.push(assume_stmt);
+                }
 
                 return Ok(true);
             }
@@ -420,17 +479,51 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
 
         // Preprocess loops
+        for bbi in self.procedure.get_reachable_cfg_blocks() {
+            if self.procedure.is_loop_invariant_before_loop_block(bbi)
+                && self.procedure.get_following_loop_head(bbi).is_none()
+            {
+                let mut error = SpannedEncodingError::incorrect(
+                    "the loop invariant is not followed by a loop",
+                    MultiSpan::from_spans(self.get_loop_invariant_spans(bbi)),
+                );
+                error.set_help(
+                    "place the `loop_invariant!(...)` immediately before the loop, or use \
+                    `body_invariant!(...)` at the beginning of the loop body",
+                );
+                return Err(error);
+            }
+        }
         for bbi in self.procedure.get_reachable_nonspec_cfg_blocks() {
             if self.loop_encoder.loops().is_loop_head(bbi) {
                 match self.loop_encoder.get_loop_invariant_block(bbi) {
                     Err(LoopEncoderError::LoopInvariantInBranch(loop_head)) => {
-                        return Err(SpannedEncodingError::incorrect(
+                        let mut error = SpannedEncodingError::incorrect(
                             "the loop invariant cannot be in a conditional branch of the loop",
                             self.get_loop_span(loop_head),
-                        ));
+                        );
+                        error.set_help(
+                            "move the `body_invariant!(...)` out of the branch; an invariant \
+                            that should only hold under some condition can be written as an \
+                            implication, e.g. `body_invariant!(condition ==> property)`",
+                        );
+                        return Err(error);
+                    }
+                    Err(LoopEncoderError::LoopInvariantAfterContinue(loop_head)) => {
+                        let mut error = SpannedEncodingError::incorrect(
+                            "the loop invariant is not reached by the loop iterations that end \
+                            early, e.g. with a `continue`",
+                            self.get_loop_span(loop_head),
+                        );
+                        error.set_help(
+                            "move the `body_invariant!(...)` before the `continue` statements \
+                            of the loop body, so that it holds in every iteration",
+                        );
+                        return Err(error);
                     }
                     Ok(loop_inv_bbi) => {
                         self.cached_loop_invariant_block.insert(bbi, loop_inv_bbi);
+                        self.warn_about_split_loop_invariants(bbi);
                     }
                 }
             }
@@ -482,6 +575,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
 
         self.encode_specification_blocks()?;
+        self.declare_call_summary_vars();
 
         // Encode all blocks
         let (opt_body_head, unresolved_edges) = self.encode_blocks_group(
@@ -511,6 +605,17 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         // Encode preconditions
         self.encode_preconditions(start_cfg_block, precondition_weakening)?;
+        if config::report_unreachable_branches() || config::report_vacuous_specs() {
+            let span = self.encoder.env().query.get_def_span(self.proc_def_id);
+            let position = self.register_error(span, ErrorCtxt::UnreachableBody);
+            self.cfg_method.add_stmt(
+                start_cfg_block,
+                vir::Stmt::Refute(vir::Refute {
+                    expr: false.into(),
+                    position,
+                }),
+            );
+        }
 
         // Encode postcondition
         self.encode_postconditions(return_cfg_block, postcondition_strengthening)?;
@@ -826,6 +931,50 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         loop_head: BasicBlockIndex,
         return_block: CfgBlockIndex,
     ) -> SpannedEncodingResult<(CfgBlockIndex, Vec<(CfgBlockIndex, BasicBlockIndex)>)> {
+        let loop_spec_blocks = self.get_loop_spec_blocks(loop_head);
+        let annotated_bound = loop_spec_blocks.iter().find_map(|&bbi| {
+            get_loop_unrolling_bound(self.encoder.env().query, &self.mir.basic_blocks[bbi])
+        });
+        if let Some(bound) = annotated_bound {
+            let loop_span = self.get_loop_span(loop_head);
+            let env_query = self.encoder.env().query;
+            let has_other_specs = loop_spec_blocks.iter().any(|&bbi| {
+                let bb_data = &self.mir.basic_blocks[bbi];
+                is_loop_invariant_block(env_query, bb_data) || is_loop_variant_block(env_query, bb_data)
+            });
+            if has_other_specs {
+                return Err(SpannedEncodingError::incorrect(
+                    "a loop marked with `no_invariant_needed!` cannot have other loop specifications",
+                    loop_span,
+                ));
+            }
+            PrustiError::warning(
+                format!("this loop is verified only for executions in which it iterates at most {bound} times"),
+                MultiSpan::from_span(loop_span),
+            )
+            .set_help("the guarantee of the verification is bounded for this loop only")
+            .emit(&self.encoder.env().diagnostic);
+            // The executions in which the loop iterates more often are not
+            // verified, as the warning says.
+            return self.encode_unrolled_loop(
+                label_prefix,
+                loop_head,
+                return_block,
+                bound,
+                None,
+            );
+        }
+        if let Some(bound) = config::loop_unrolling_bound() {
+            if loop_spec_blocks.is_empty() {
+                return self.encode_unrolled_loop(
+                    label_prefix,
+                    loop_head,
+                    return_block,
+                    bound,
+                    Some(ErrorCtxt::UnrolledLoopBound),
+                );
+            }
+        }
         let loop_info = self.loop_encoder.loops();
         debug_assert!(loop_info.is_loop_head(loop_head));
         let loop_label_prefix = format!("{}loop{}", label_prefix, loop_head.index());
@@ -849,15 +998,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             .position(|bb| bb == before_invariant_block)
             .unwrap();
         let after_inv_block_pos = 1 + before_inv_block_pos;
-        // Find boolean switch exit blocks before the invariant
-        let boolean_exit_blocks_before_inv: Vec<_> = loop_body[0..after_inv_block_pos]
+        // Find the exit blocks before the invariant that decide whether to stay in the loop
+        let guard_exit_blocks_before_inv: Vec<_> = loop_body[0..after_inv_block_pos]
             .iter().copied()
             .filter(|bb| loop_exit_blocks_set.contains(bb))
-            .filter(|&bb| self.procedure.successors(bb).len() == 2)
+            .filter(|&bb| self.procedure.is_loop_guard_switch(loop_head, bb))
             .collect();
-        // HEURISTIC: pick the last boolean exit block before the invariant.
+        // HEURISTIC: pick the last guard exit block before the invariant.
         // An infinite loop will have no exit blocks, so we have to use an Option here
-        let opt_loop_guard_switch = boolean_exit_blocks_before_inv.last().cloned();
+        let opt_loop_guard_switch = guard_exit_blocks_before_inv.last().cloned();
         let after_guard_block_pos = opt_loop_guard_switch
             .and_then(|loop_guard_switch| {
                 loop_body
@@ -900,6 +1049,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let loop_guard_evaluation = &loop_body[0..after_guard_block_pos];
         let loop_body_before_inv = &loop_body[after_guard_block_pos..after_inv_block_pos];
         let loop_body_after_inv = &loop_body[after_inv_block_pos..];
+        self.warn_about_effects_before_loop_invariant(loop_head, &loop_body, loop_body_before_inv);
 
         // The main path in the encoding is: start -> G -> B1 -> invariant -> B2 -> G -> B1 -> end
         // We are going to build the encoding left to right.
@@ -1193,6 +1343,16 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         self.cfg_method
             .set_successor(end_body_block, vir::Successor::Return);
 
+        // Every back edge, including those of `continue` statements and of labeled
+        // `continue` statements in nested loops, must lead to the check of the invariant.
+        // An enclosing group would otherwise link it to the start of the loop.
+        if still_unresolved_edges.iter().any(|&(_, target)| target == loop_head) {
+            return Err(SpannedEncodingError::internal(
+                "a back edge of the loop is not linked to the check of the loop invariant",
+                loop_head_span,
+            ));
+        }
+
         // Final step: havoc Viper local variables assigned in the encoding of the loop body
         let vars = collect_assigned_vars(&self.cfg_method, end_body_block, inv_pre_block);
         for var in vars {
@@ -1225,6 +1385,116 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok((start_block, still_unresolved_edges))
     }
 
+    /// Encode a loop without loop invariant by unrolling it `bound` times,
+    /// such that no loop invariant is needed:
+    ///
+    /// ```text
+    /// start -> L_0 -> L_1 -> ... -> L_bound -> end
+    /// ```
+    ///
+    /// Each `L_i` is a copy of the loop body whose back edges lead to the
+    /// next copy. The back edges of the last copy lead to the "end" block,
+    /// which checks that the loop does not iterate more than `bound` times
+    /// and reports a violation with `bound_ctxt`. Without `bound_ctxt`, the
+    /// "end" block instead assumes that the loop does not iterate more often.
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn encode_unrolled_loop(
+        &mut self,
+        label_prefix: &str,
+        loop_head: BasicBlockIndex,
+        return_block: CfgBlockIndex,
+        bound: u32,
+        bound_ctxt: Option<ErrorCtxt>,
+    ) -> SpannedEncodingResult<(CfgBlockIndex, Vec<(CfgBlockIndex, BasicBlockIndex)>)> {
+        let loop_info = self.loop_encoder.loops();
+        debug_assert!(loop_info.is_loop_head(loop_head));
+        let loop_label_prefix = format!("{}loop{}", label_prefix, loop_head.index());
+        let loop_depth = loop_info.get_loop_head_depth(loop_head);
+        let loop_body: Vec<BasicBlockIndex> = loop_info
+            .get_loop_body(loop_head)
+            .iter()
+            .copied()
+            .filter(
+                |&bb| self.procedure.is_reachable_block(bb) && !self.procedure.is_spec_block(bb)
+            )
+            .collect();
+
+        let start_block = self.cfg_method.add_block(
+            &format!("{loop_label_prefix}_start"),
+            vec![vir::Stmt::comment(format!(
+                "========== {loop_label_prefix}_start =========="
+            ))],
+        );
+        let mut still_unresolved_edges = vec![];
+        let mut back_edges = vec![(start_block, loop_head)];
+        for iteration in 0..=bound {
+            let iteration_label_prefix = format!("{loop_label_prefix}_unroll{iteration}_");
+            // Like `encode_blocks_group`, except that the edges to the loop
+            // head are left to the next copy of the body.
+            let mut bb_map: FxHashMap<_, _> = FxHashMap::default();
+            let mut unresolved_edges = vec![];
+            for &curr_bb in &loop_body {
+                let loop_info = self.loop_encoder.loops();
+                let curr_loop_depth = loop_info.get_loop_depth(curr_bb);
+                let (curr_block, curr_edges) = if curr_loop_depth == loop_depth {
+                    self.encode_block(&iteration_label_prefix, curr_bb, return_block)?
+                } else if curr_loop_depth == loop_depth + 1 && loop_info.is_loop_head(curr_bb) {
+                    self.encode_loop(&iteration_label_prefix, curr_bb, return_block)?
+                } else {
+                    continue;
+                };
+                bb_map.insert(curr_bb, curr_block);
+                unresolved_edges.extend(curr_edges);
+            }
+            let iteration_head = bb_map[&loop_head];
+            for (curr_block, _) in back_edges.drain(..) {
+                self.cfg_method
+                    .set_successor(curr_block, vir::Successor::Goto(iteration_head));
+            }
+            for (curr_block, target) in self.encode_unresolved_edges(unresolved_edges, |bb| {
+                if bb == loop_head {
+                    None
+                } else {
+                    bb_map.get(&bb).cloned()
+                }
+            })? {
+                if target == loop_head {
+                    back_edges.push((curr_block, target));
+                } else {
+                    still_unresolved_edges.push((curr_block, target));
+                }
+            }
+        }
+
+        // Build the "end" CFG block, which is reached if the loop iterates
+        // more often than it was unrolled.
+        let end_block = self.cfg_method.add_block(
+            &format!("{loop_label_prefix}_end_unroll"),
+            vec![vir::Stmt::comment(format!(
+                "========== {loop_label_prefix}_end_unroll =========="
+            ))],
+        );
+        let end_stmt = if let Some(bound_ctxt) = bound_ctxt {
+            let pos = self.register_error(self.get_loop_span(loop_head), bound_ctxt);
+            vir::Stmt::Assert( vir::Assert {
+                expr: false.into(),
+                position: pos,
+            })
+        } else {
+            vir::Stmt::Inhale( vir::Inhale {
+                expr: false.into(),
+            })
+        };
+        self.cfg_method.add_stmt(end_block, end_stmt);
+        self.cfg_method.set_successor(end_block, vir::Successor::Return);
+        for (curr_block, _) in back_edges {
+            self.cfg_method
+                .set_successor(curr_block, vir::Successor::Goto(end_block));
+        }
+
+        Ok((start_block, still_unresolved_edges))
+    }
+
     /// Encode a block.
     ///
     /// Returns:
@@ -1258,6 +1528,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
 
         self.encode_execution_flag(bbi, curr_block)?;
+        if config::report_unreachable_branches() && self.is_checked_branch(bbi) {
+            self.encode_unreachable_branch_check(bbi, curr_block);
+        }
         let opt_successor = self.encode_block_statements(bbi, curr_block)?;
         let mir_successor: MirSuccessor = if let Some(successor) = opt_successor {
             // In case of unsupported statements, we do not encode the terminator
@@ -1305,6 +1578,45 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok((curr_block, unresolved_edges))
     }
 
+    /// Whether `bbi` starts a branch that should be checked for being
+    /// unreachable. Blocks in loops are encoded more than once, so a branch in a
+    /// loop could be unreachable in one of its copies while being reachable in
+    /// another; they are not checked.
+    fn is_checked_branch(&self, bbi: BasicBlockIndex) -> bool {
+        let bb_data = &self.mir[bbi];
+        if matches!(bb_data.terminator().kind, TerminatorKind::Unreachable)
+            || self.loop_encoder.get_loop_depth(bbi) > 0
+            || self.branch_span(bbi).from_expansion()
+        {
+            return false;
+        }
+        self.mir.basic_blocks.predecessors()[bbi].iter().any(|&pred| {
+            match &self.mir[pred].terminator().kind {
+                TerminatorKind::SwitchInt { targets, .. } => {
+                    targets.all_targets().iter().any(|&target| target != bbi)
+                }
+                _ => false,
+            }
+        })
+    }
+
+    fn branch_span(&self, bbi: BasicBlockIndex) -> Span {
+        self.mir.source_info(mir::Location { block: bbi, statement_index: 0 }).span
+    }
+
+    /// Check that the contracts do not make the branch starting at `bbi`
+    /// unreachable.
+    fn encode_unreachable_branch_check(&mut self, bbi: BasicBlockIndex, cfg_block: CfgBlockIndex) {
+        let position = self.register_error(self.branch_span(bbi), ErrorCtxt::UnreachableBranch);
+        self.cfg_method.add_stmt(
+            cfg_block,
+            vir::Stmt::Refute(vir::Refute {
+                expr: false.into(),
+                position,
+            }),
+        );
+    }
+
     /// Store a flag that becomes true the first time the block is executed
     fn encode_execution_flag(
         &mut self,
@@ -1437,6 +1749,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok((self.set_stmts_default_pos(stmts, span), successor))
     }
 
+    /// Checks whether the statement moves an argument of an `async fn` out of
+    /// the generator at the beginning of the body. The contract of the
+    /// function refers to the locals to which the arguments are moved.
+    fn is_async_fn_arg_binding(&self, location: mir::Location) -> bool {
+        location.block == mir::START_BLOCK
+            && self.encoder.env().query.get_async_fn_of_body(self.proc_def_id).is_some()
+            && self
+                .mir
+                .get_async_fn_arg_bindings()
+                .iter()
+                .any(|&(binding_location, _)| binding_location == location)
+    }
+
     /// Note: it's better to call `encode_statement_at` instead of this method.
     #[tracing::instrument(level = "debug", skip(self), fields(statement = ?stmt.kind, span = ?stmt.source_info.span))]
     fn encode_statement(
@@ -1447,6 +1772,18 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mut stmts = vec![vir::Stmt::comment(format!("[mir] {stmt:?}"))];
         let span = self.mir_encoder.get_span_of_location(location);
 
+        if self.is_async_fn_arg_binding(location) {
+            // The local already holds the argument, as required by the
+            // precondition of the `async fn`.
+            return Ok(stmts);
+        }
+
+        if self.procedure.is_immediate_await_output_binding(location) {
+            // The output of the awaited future has been stored by the call
+            // that created the future.
+            return Ok(stmts);
+        }
+
         let encoding_stmts = match stmt.kind {
             mir::StatementKind::StorageLive(..)
             | mir::StatementKind::StorageDead(..)
@@ -1457,6 +1794,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             | mir::StatementKind::Nop => vec![],
 
             mir::StatementKind::Assign(box (lhs, ref rhs)) => {
+                self.check_field_callables_unchanged(lhs, rhs, span)?;
                 // Array access on the LHS should always be mutable (idx is always calculated
                 // before, and just a separate local variable here)
                 let (lhs_place_encoding, ty, _) = self.mir_encoder.encode_place(lhs).with_span(span)?;
@@ -1618,6 +1956,16 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         cast_ty,
                         location,
                     )?
+                } else if rhs_ty.is_ref()
+                    && cast_ty.is_ref()
+                    // A mutable trait object would not be linked to the value
+                    // it was created from, so changes made through it would be
+                    // lost.
+                    && !cast_ty.is_mutable_ptr()
+                    && cast_ty.peel_refs().is_trait()
+                {
+                    trace!("trait object: operand={:?}, ty={:?}", operand, cast_ty);
+                    self.encode_assign_trait_object(encoded_lhs, cast_ty, location)?
                 } else {
                     return Err(SpannedEncodingError::unsupported(
                         format!("unsizing a {rhs_ty} into a {cast_ty} is not supported"),
@@ -1625,6 +1973,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     ));
                 }
             }
+            mir::Rvalue::Cast(
+                mir::CastKind::Pointer(ty::adjustment::PointerCast::MutToConstPointer),
+                ref operand,
+                _,
+            ) if self.unsafe_memory => {
+                // The pointer keeps the permission to its target.
+                self.encode_assign_operand(&encoded_lhs, operand, location)?
+            }
             mir::Rvalue::Cast(mir::CastKind::Pointer(_), _, _) |
             mir::Rvalue::Cast(mir::CastKind::DynStar, _, _) => {
                 return Err(SpannedEncodingError::unsupported(
@@ -1636,6 +1992,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     format!("casts {cast_kind:?} are not supported"), span
                 ));
             }
+            mir::Rvalue::AddressOf(_, place) if self.unsafe_memory => {
+                self.encode_assign_address_of(place, location, encoded_lhs, ty)?
+            }
             mir::Rvalue::AddressOf(_, _) => {
                 return Err(SpannedEncodingError::unsupported(
                     "raw addresses of expressions or casting a reference to a raw pointer are not supported", span
@@ -1976,7 +2335,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             .with_span(span)?;
         trace!("loan_places: {:?}", loan_places);
 
-        Ok(if let Some(regained) = self.slice_created_at.get(&loan_location) {
+        Ok(if let Some((regained, rejoin_stmts)) = self.slice_created_at.get(&loan_location) {
             let guard = self.construct_location_guard(loan_location);
             trace!("guard: {:?}", guard);
             trace!("regained: {:?}", regained);
@@ -1986,9 +2345,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     node.loan.index().into(),
                     convert_loans_to_borrows(&node.reborrowing_loans),
                     convert_loans_to_borrows(&node.reborrowed_loans),
-                    vec![
-                        vir::Stmt::comment("hi"),
-                    ],
+                    rejoin_stmts.clone(),
                     vec![regained.clone()],
                     Vec::new(),
                     Vec::new(),
@@ -2445,7 +2802,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 (stmts, MirSuccessor::Kill)
             }
 
-            TerminatorKind::Drop { target, .. } => (stmts, MirSuccessor::Goto(target)),
+            TerminatorKind::Drop { place, target, .. } => {
+                stmts.extend(self.encode_drop_obligations(place, location)?);
+                (stmts, MirSuccessor::Goto(target))
+            }
 
             TerminatorKind::FalseEdge { real_target, .. } => {
                 (stmts, MirSuccessor::Goto(real_target))
@@ -2472,9 +2832,26 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     let called_def_id = *called_def_id;
                     debug!("Encode function call {:?} with substs {:?}", called_def_id, call_substs);
 
+                    // If the returned future is awaited immediately, the call
+                    // continues where the output of the future is stored.
+                    let (destination, target) = match self.procedure.get_immediate_await(location.block) {
+                        Some(immediate_await) => (
+                            immediate_await.output,
+                            Some(immediate_await.output_binding.block),
+                        ),
+                        None => (destination, target),
+                    };
+
                     let full_func_proc_name: &str =
                         &self.encoder.env().name.get_absolute_item_name(called_def_id);
 
+                    stmts.extend(self.encode_drop_obligations_of_moved_args(
+                        called_def_id,
+                        full_func_proc_name,
+                        args,
+                        location,
+                    )?);
+
                     match full_func_proc_name {
                         "std::rt::begin_panic"
                         | "core::panicking::panic"
@@ -2489,10 +2866,22 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             let panic_cause = self.mir_encoder.encode_panic_cause(
                                 term.source_info.span
                             );
-                            let pos = self.register_error(
+                            let pos = match self.get_assert_matches_operands(
+                                panic_cause,
+                                term.source_info.span,
+                            ) {
+                                // Report the failed pattern check at the
+                                // `assert!` in the user's code, not within
+                                // the expansion of `matches!`
+                                Some((value, pattern)) => self.register_error(
+                                    term.source_info.span.source_callsite(),
+                                    ErrorCtxt::AssertMatches(value, pattern),
+                                ),
+                                None => self.register_error(
                                     term.source_info.span,
                                     ErrorCtxt::Panic(panic_cause),
-                                );
+                                ),
+                            };
 
                             if self.check_panics {
                                 stmts.push(vir::Stmt::comment(format!(
@@ -2607,15 +2996,29 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                                 }
 
                                 _ => {
-                                    return Err(SpannedEncodingError::unsupported(
-                                        format!("only calls to closures are supported. The term is a {:?}, not a closure.", cl_type.kind()),
+                                    let Some((contract_def_id, contract_substs)) =
+                                        self.get_field_callable_contract(&args[0], location) else {
+                                        return Err(SpannedEncodingError::unsupported(
+                                            format!("only calls to closures and to callables stored in fields with a contract are supported. The term is a {:?}, not a closure.", cl_type.kind()),
+                                            term.source_info.span,
+                                        ));
+                                    };
+                                    debug!("Encoding call to callable field with contract {:?}", contract_def_id);
+                                    stmts.extend(self.encode_impure_function_call(
+                                        location,
                                         term.source_info.span,
-                                    ));
+                                        args,
+                                        destination,
+                                        target,
+                                        contract_def_id,
+                                        contract_substs,
+                                    )?);
                                 }
                             }
                         }
 
-                        "core::slice::<impl [T]>::len" => {
+                        "core::slice::<impl [T]>::len" |
+                        "core::str::<impl str>::len" => {
                             stmts.extend(
                                 self.encode_slice_len_call(
                                     destination,
@@ -2626,6 +3029,23 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             );
                         }
 
+                        "core::slice::<impl [T]>::contains" |
+                        "core::slice::<impl [T]>::starts_with" |
+                        "core::slice::<impl [T]>::ends_with"
+                            if self.encoder.has_structural_eq_impl(call_substs.type_at(0))
+                        => {
+                            stmts.extend(
+                                self.encode_slice_query_call(
+                                    full_func_proc_name,
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                    target,
+                                )?
+                            );
+                        }
+
                         "std::iter::Iterator::next" |
                         "core::iter::Iterator::next" => {
                             return Err(SpannedEncodingError::unsupported(
@@ -2716,14 +3136,16 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         (stmts, MirSuccessor::Kill)
                     }
                 } else {
-                    // Other kind of calls?
-                    unimplemented!();
+                    return Err(UnsupportedFeature::new_plural("calls of function pointers", span)
+                        .with_trusted_workaround()
+                        .into());
                 }
             }
 
             TerminatorKind::Call { .. } => {
-                // Other kind of calls?
-                unimplemented!();
+                return Err(UnsupportedFeature::new_plural("calls of function pointers", span)
+                    .with_trusted_workaround()
+                    .into());
             }
 
             TerminatorKind::Assert {
@@ -2782,10 +3204,29 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 (stmts, MirSuccessor::Goto(target))
             }
 
-            TerminatorKind::Resume
-            | TerminatorKind::Yield { .. }
-            | TerminatorKind::GeneratorDrop
-            | TerminatorKind::InlineAsm { .. } => unimplemented!("{:?}", term.kind),
+            TerminatorKind::Yield { .. } => {
+                let message = if self.encoder.env().query.get_async_fn_of_body(self.proc_def_id).is_some() {
+                    "only futures that are awaited immediately after being returned by a call \
+                    are supported"
+                } else {
+                    "generators are not supported"
+                };
+                return Err(SpannedEncodingError::unsupported(message, span));
+            }
+
+            TerminatorKind::GeneratorDrop => {
+                return Err(UnsupportedFeature::new_plural("generators", span)
+                    .with_trusted_workaround()
+                    .into());
+            }
+
+            TerminatorKind::InlineAsm { .. } => {
+                return Err(UnsupportedFeature::new("inline assembly", span)
+                    .with_trusted_workaround()
+                    .into());
+            }
+
+            TerminatorKind::Resume => unimplemented!("{:?}", term.kind),
         };
         Ok(result)
     }
@@ -2842,6 +3283,54 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Encodes a call of `contains`, `starts_with` or `ends_with` on a slice
+    /// whose elements can be compared by their snapshots, like a call of a
+    /// pure function with the same result.
+    fn encode_slice_query_call(
+        &mut self,
+        func_proc_name: &str,
+        location: mir::Location,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        destination: mir::Place<'tcx>,
+        target: Option<BasicBlockIndex>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        assert_eq!(args.len(), 2, "unexpected args to {func_proc_name}: {args:?}");
+        let slice_ty = self.mir_encoder.get_operand_ty(&args[0]);
+        let slice = self.mir_encoder.encode_operand_expr(&args[0])
+            .with_span(call_site_span)?;
+        let arg = self.mir_encoder.encode_operand_expr(&args[1])
+            .with_span(call_site_span)?;
+        let expr = match func_proc_name {
+            "core::slice::<impl [T]>::contains" => {
+                self.encoder.encode_snapshot_slice_contains(slice_ty, slice, arg)
+            }
+            "core::slice::<impl [T]>::starts_with" => {
+                self.encoder.encode_snapshot_slice_affix(slice_ty, slice, arg, false)
+            }
+            "core::slice::<impl [T]>::ends_with" => {
+                self.encoder.encode_snapshot_slice_affix(slice_ty, slice, arg, true)
+            }
+            _ => unreachable!("{func_proc_name} is not a slice query"),
+        }.with_span(call_site_span)?;
+
+        let (target_value, mut stmts) = self.encode_pure_function_call_lhs_value(destination, target, location)
+            .with_span(call_site_span)?;
+        let inhaled_expr = vir::Expr::eq_cmp(target_value, expr);
+
+        let (call_stmts, label) = self.encode_pure_function_call_site(
+            location,
+            destination,
+            target,
+            inhaled_expr,
+        )?;
+        stmts.extend(call_stmts);
+
+        self.encode_transfer_args_permissions(location, args,  &mut stmts, &label, false)?;
+
+        Ok(stmts)
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     fn encode_sequence_index_call(
         &mut self,
@@ -2911,9 +3400,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let idx_ident = self.encoder.env().name.get_absolute_item_name(idx_ty.ty_adt_def().unwrap().did());
         trace!("ident: {}", idx_ident);
 
-        self.slice_created_at.insert(location, encoded_lhs);
-
-        let original_len = enc_sequence_types.len(self.encoder, base_seq_expr);
+        let original_len = enc_sequence_types.len(self.encoder, base_seq_expr.clone());
 
         // TODO: there's fields like _5.f$start.val_int on `encoded_idx`, it just feels hacky to
         // manually re-do and hardcode them here when we probably just encoded the type
@@ -3006,9 +3493,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let lhs_lookup_i = {
             slice_types_lhs.encode_lookup_pure_call(
                 self.encoder,
-                lhs_slice_expr,
+                lhs_slice_expr.clone(),
                 i_var.clone(),
-                elem_snap_ty,
+                elem_snap_ty.clone(),
             )
         };
 
@@ -3034,13 +3521,94 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         stmts.push(vir_stmt!{
             inhale [
                 vir::Expr::forall(
-                    vec![i, j],
+                    vec![i, j.clone()],
                     vec![vir::Trigger::new(vec![lhs_lookup_i, rhs_lookup_j])],
                     vir_expr!{ ([indices] ==> [lookup_eq]) }
                 )
             ]
         });
 
+        // The range and the length of the sliced sequence are bound to fresh
+        // variables, because the places they are read from can be overwritten
+        // before the slice expires, e.g. in the next iteration of a loop.
+        let mut bind_fresh_var = |stmts: &mut Vec<vir::Stmt>, value: vir::Expr| {
+            let var = self.cfg_method.add_fresh_local_var(vir::Type::Int);
+            stmts.push(vir::Stmt::Assign( vir::Assign {
+                target: var.clone().into(),
+                source: value,
+                kind: vir::AssignKind::Copy,
+            }));
+            vir::Expr::local(var)
+        };
+        let start = bind_fresh_var(&mut stmts, start);
+        let end = bind_fresh_var(&mut stmts, end);
+        let base_len = enc_sequence_types.len(self.encoder, base_seq_expr.clone());
+        let original_base_len = bind_fresh_var(&mut stmts, base_len.clone());
+
+        // When the slice expires, the sliced sequence gets its permission back.
+        // The elements of a mutable slice may have been changed through it, so
+        // they are copied back into the range of the sliced sequence, while the
+        // elements outside of the range keep their values.
+        let mut rejoin_stmts = self.encode_transfer_permissions(
+            base_seq_expr.clone().old(&label),
+            base_seq_expr.clone(),
+            location,
+            false,
+        );
+        if perm_amount == vir::PermAmount::Write {
+            let base_perm = vir::Expr::pred_permission(base_seq_expr.clone(), perm_amount).unwrap();
+            rejoin_stmts.push(vir::Stmt::Exhale( vir::Exhale {
+                expr: base_perm.clone(),
+                position: self.register_error(error_span, ErrorCtxt::Unexpected),
+            }));
+            rejoin_stmts.push(vir_stmt!{ inhale [base_perm] });
+
+            rejoin_stmts.push(vir_stmt!{ inhale [vir_expr!{ [base_len] == [original_base_len] }] });
+
+            let base_lookup_j = enc_sequence_types.encode_lookup_pure_call(
+                self.encoder,
+                base_seq_expr,
+                j_var.clone(),
+                elem_snap_ty.clone(),
+            );
+            let slice_lookup = slice_types_lhs.encode_lookup_pure_call(
+                self.encoder,
+                lhs_slice_expr,
+                vir_expr!{ [j_var] - [start] },
+                elem_snap_ty,
+            );
+            let in_range = vir_expr!{
+                [vir_expr!{ [start] <= [j_var] }] && [vir_expr!{ [j_var] < [end] }]
+            };
+            let in_bounds = vir_expr!{
+                [vir_expr!{ [vir::Expr::from(0usize)] <= [j_var] }] && [vir_expr!{ [j_var] < [original_base_len] }]
+            };
+            let updated = vir_expr!{ [base_lookup_j] == [slice_lookup] };
+            let unchanged = vir_expr!{
+                [base_lookup_j] == [vir::Expr::labelled_old(&label, base_lookup_j.clone())]
+            };
+            let outside_range = vir_expr!{ [in_bounds] && [in_range.clone().negate()] };
+            rejoin_stmts.push(vir_stmt!{
+                inhale [
+                    vir::Expr::forall(
+                        vec![j.clone()],
+                        vec![vir::Trigger::new(vec![base_lookup_j.clone()])],
+                        vir_expr!{ ([in_range] ==> [updated]) }
+                    )
+                ]
+            });
+            rejoin_stmts.push(vir_stmt!{
+                inhale [
+                    vir::Expr::forall(
+                        vec![j],
+                        vec![vir::Trigger::new(vec![base_lookup_j])],
+                        vir_expr!{ ([outside_range] ==> [unchanged]) }
+                    )
+                ]
+            });
+        }
+        self.slice_created_at.insert(location, (encoded_lhs, rejoin_stmts));
+
         self.encode_transfer_args_permissions(location, args,  &mut stmts, &label, false)?;
         // Store a label for permissions got back from the call
         debug!(
@@ -3157,26 +3725,165 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
     }
 
-    #[allow(clippy::too_many_arguments)]
-    #[tracing::instrument(level = "debug", skip(self))]
-    fn encode_impure_function_call(
-        &mut self,
-        location: mir::Location,
-        call_site_span: prusti_rustc_interface::span::Span,
+    /// If the call is a call of `Option::expect` or `Result::expect` with a
+    /// string literal, returns the message of the literal.
+    fn get_expect_message(
+        &self,
+        full_func_proc_name: &str,
         mir_args: &[mir::Operand<'tcx>],
-        destination: mir::Place<'tcx>,
-        target: Option<BasicBlockIndex>,
-        called_def_id: ProcedureDefId,
-        mut substs: ty::subst::SubstsRef<'tcx>,
-    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
-        let full_func_proc_name = &self
-            .encoder
-            .env()
-            .name
-            .get_absolute_item_name(called_def_id);
-        debug!("Encoding non-pure function call '{}' with args {:?} and substs {:?}", full_func_proc_name, mir_args, substs);
+    ) -> Option<String> {
+        if !matches!(
+            full_func_proc_name,
+            "core::option::Option::<T>::expect" | "core::result::Result::<T, E>::expect"
+        ) {
+            return None;
+        }
+        get_str_constant(mir_args.get(1)?)
+    }
 
-        // Spans for fake exprs that cannot be encoded in viper
+    /// If the panic is the failure of `assert!(matches!(value, pattern))`,
+    /// returns the source text of the value and of the pattern. They are taken
+    /// from the source code of the `assert!` call that expands to the panic
+    /// at `span`.
+    fn get_assert_matches_operands(
+        &self,
+        panic_cause: PanicCause,
+        span: Span,
+    ) -> Option<(String, String)> {
+        // An `assert!` with a custom message panics through `panic!`
+        if !matches!(panic_cause, PanicCause::Assert | PanicCause::Panic) {
+            return None;
+        }
+        let source_map = self.encoder.env().tcx().sess.source_map();
+        let snippet = source_map.span_to_snippet(span.source_callsite()).ok()?;
+        // The condition is the first argument of `assert!`; the others are
+        // the custom panic message.
+        let assert_arguments = split_macro_arguments(get_macro_arguments(&snippet, "assert")?);
+        let condition = assert_arguments.first()?;
+        let matches_arguments = split_macro_arguments(get_macro_arguments(condition, "matches")?);
+        let [value, pattern] = matches_arguments.as_slice() else {
+            return None;
+        };
+        Some((value.to_string(), pattern.to_string()))
+    }
+
+    /// If the callable passed to `Fn::call` is borrowed from a field of a
+    /// struct whose type invariant declares the contract of the field, returns
+    /// the function carrying that contract and the type arguments of the struct.
+    fn get_field_callable_contract(
+        &self,
+        callable: &mir::Operand<'tcx>,
+        location: mir::Location,
+    ) -> Option<(ProcedureDefId, ty::subst::SubstsRef<'tcx>)> {
+        let callable_local = callable.place()?.as_local()?;
+        let statements = &self.mir[location.block].statements[..location.statement_index];
+        let borrowed_place = statements.iter().rev().find_map(|stmt| match stmt.kind {
+            mir::StatementKind::Assign(box (lhs, mir::Rvalue::Ref(_, _, place)))
+                if lhs.as_local() == Some(callable_local) => Some(place),
+            _ => None,
+        })?;
+        let (base, mir::ProjectionElem::Field(field, _)) = borrowed_place.iter_projections().last()? else {
+            return None;
+        };
+        self.get_field_callable_contract_of_field(base, field)
+            .map(|(contract_def_id, substs, _)| (contract_def_id, substs))
+    }
+
+    /// Returns the function carrying the contract declared by a type invariant
+    /// for the callable stored in the field `field` of `base`, the type
+    /// arguments of the struct and the name of the field, if there is such a
+    /// contract.
+    fn get_field_callable_contract_of_field(
+        &self,
+        base: mir::PlaceRef<'tcx>,
+        field: FieldIdx,
+    ) -> Option<(ProcedureDefId, ty::subst::SubstsRef<'tcx>, String)> {
+        let tcx = self.encoder.env().tcx();
+        let ty::TyKind::Adt(adt_def, substs) = base.ty(self.mir, tcx).ty.kind() else {
+            return None;
+        };
+        if !adt_def.is_struct() {
+            return None;
+        }
+        let field_name = adt_def.non_enum_variant().fields[field].ident(tcx).to_string();
+        let contract_def_id = self.encoder.env().query
+            .get_field_callable_contract(adt_def.did(), &field_name)?;
+        Some((contract_def_id, substs, field_name))
+    }
+
+    /// The callables stored in fields are not checked against the contracts
+    /// declared for them by type invariants. To keep the calls through such
+    /// fields sound, the fields can only be initialized, assigned or borrowed
+    /// mutably in trusted functions.
+    fn check_field_callables_unchanged(
+        &self,
+        lhs: mir::Place<'tcx>,
+        rhs: &mir::Rvalue<'tcx>,
+        span: Span,
+    ) -> SpannedEncodingResult<()> {
+        let contracted_field = |place: mir::Place<'tcx>| {
+            place.iter_projections().find_map(|(base, elem)| match elem {
+                mir::ProjectionElem::Field(field, _) => self
+                    .get_field_callable_contract_of_field(base, field)
+                    .map(|(_, _, field_name)| field_name),
+                _ => None,
+            })
+        };
+        let changed_field = match *rhs {
+            mir::Rvalue::Aggregate(box mir::AggregateKind::Adt(adt_did, ..), _) => {
+                let tcx = self.encoder.env().tcx();
+                let adt_def = tcx.adt_def(adt_did);
+                if adt_def.is_struct() {
+                    adt_def.non_enum_variant().fields.iter().find_map(|field| {
+                        let field_name = field.name.to_string();
+                        self.encoder.env().query
+                            .get_field_callable_contract(adt_did, &field_name)
+                            .map(|_| field_name)
+                    })
+                } else {
+                    None
+                }
+            }
+            mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, place) => contracted_field(place),
+            _ => None,
+        }.or_else(|| contracted_field(lhs));
+        if let Some(field_name) = changed_field {
+            let mut error = SpannedEncodingError::incorrect(
+                format!(
+                    "the callable stored in the field `{field_name}` cannot be checked against \
+                    the contract declared by the type invariant"
+                ),
+                span,
+            );
+            error.set_help(
+                "initialize and change the field only in `#[trusted]` functions, which are \
+                responsible for storing callables that satisfy the contract",
+            );
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn encode_impure_function_call(
+        &mut self,
+        location: mir::Location,
+        call_site_span: prusti_rustc_interface::span::Span,
+        mir_args: &[mir::Operand<'tcx>],
+        destination: mir::Place<'tcx>,
+        target: Option<BasicBlockIndex>,
+        called_def_id: ProcedureDefId,
+        mut substs: ty::subst::SubstsRef<'tcx>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let full_func_proc_name = &self
+            .encoder
+            .env()
+            .name
+            .get_absolute_item_name(called_def_id);
+        debug!("Encoding non-pure function call '{}' with args {:?} and substs {:?}", full_func_proc_name, mir_args, substs);
+
+        // Spans for fake exprs that cannot be encoded in viper
         let mut fake_expr_spans: FxHashMap<Local, Span> = FxHashMap::default();
 
         // First we construct the "operands" vector. This construction differs
@@ -3191,9 +3898,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             .map(|arg| self.mir_encoder.encode_operand_place(arg))
             .collect::<Result<Vec<Option<vir::Expr>>, _>>()
             .with_span(call_site_span)?;
-        if self.encoder.env().query.is_closure(called_def_id) {
-            // Closure calls are wrapped around std::ops::Fn::call(), which receives
-            // two arguments: The closure instance, and the tupled-up arguments
+        let is_closure = self.encoder.env().query.is_closure(called_def_id);
+        if is_closure || self.encoder.env().query.has_prusti_attribute(called_def_id, "field_callable") {
+            // Closure calls, like calls of callables stored in fields, are wrapped
+            // around std::ops::Fn::call(), which receives two arguments: The
+            // closure instance, and the tupled-up arguments
             assert_eq!(mir_args.len(), 2);
 
             let cl_ty = self.mir_encoder.get_operand_ty(&mir_args[0]);
@@ -3229,9 +3938,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 unimplemented!();
             }
 
-            // TODO: weird fix for closure call substitutions, we need to
-            // prepend the identity substs of the containing method ...
-            substs = self.encoder.env().tcx().mk_substs_from_iter(self.substs.iter().chain(substs));
+            if is_closure {
+                // TODO: weird fix for closure call substitutions, we need to
+                // prepend the identity substs of the containing method ...
+                substs = self.encoder.env().tcx().mk_substs_from_iter(self.substs.iter().chain(substs));
+            }
         } else {
             for (arg, encoded_operand) in mir_args.iter().zip(encoded_operands.iter_mut()) {
                 let arg_ty = self.mir_encoder.get_operand_ty(arg);
@@ -3266,6 +3977,25 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mut stmts = vec![];
         let mut stmts_after: Vec<vir::Stmt> = vec![];
 
+        // Record the arguments of a call summarized in the postcondition
+        let call_summary_vars = if self.call_summaries.is_empty() {
+            None
+        } else {
+            let name = call_summary_function_name(self.encoder, called_def_id, substs);
+            self.call_summaries.get(&name).cloned()
+        };
+        if let Some((_, arg_vars)) = &call_summary_vars {
+            for (mir_arg, arg_var) in mir_args.iter().zip(arg_vars) {
+                let arg_value = self.mir_encoder.encode_operand_expr(mir_arg)
+                    .with_span(call_site_span)?;
+                stmts.push(vir::Stmt::Assign( vir::Assign {
+                    target: vir::Expr::local(arg_var.clone()),
+                    source: arg_value,
+                    kind: vir::AssignKind::Copy,
+                }));
+            }
+        }
+
         for (mir_arg, arg, arg_ty, encoded_operand) in operands {
             arguments.push(arg);
 
@@ -3293,8 +4023,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     constant_args.push(arg_place.clone());
                     let val_field = self.encoder.encode_value_field(arg_ty).with_span(call_site_span)?;
 
-                    // TODO: String constants are not being encoded currently,
-                    //       instead just inhale the permission to the value.
+                    // String constants are not encoded as expressions; instead,
+                    // inhale the permission to the value and its contents.
                     if !is_str(arg_ty) {
                         let arg_val_expr = self.mir_encoder.encode_operand_expr(mir_arg)
                             .with_span(call_site_span)?;
@@ -3308,11 +4038,21 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         stmts.push(vir::Stmt::Inhale (
                             vir::Inhale {
                                 expr: vir::Expr::acc_permission(
-                                    arg_place.clone().field(val_field),
+                                    arg_place.clone().field(val_field.clone()),
                                     vir::PermAmount::Read
                                 )
                             }
                         ));
+                        if let mir::Operand::Constant(constant) = mir_arg
+                            && constant.ty().builtin_deref(true).map_or(false, |tm| tm.ty.is_str())
+                        {
+                            stmts.extend(
+                                self.encode_str_literal_contents(
+                                    arg_place.clone().field(val_field),
+                                    constant,
+                                ).with_span(call_site_span)?
+                            );
+                        }
                     }
                     let in_loop = self.loop_encoder.get_loop_depth(location.block) > 0;
                     if in_loop {
@@ -3395,7 +4135,17 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             expr
         };
 
-        let procedure_contract = {
+        let procedure_contract = if self.procedure.get_immediate_await(location.block).is_some() {
+            let output_ty = destination.ty(self.mir, self.encoder.env().tcx()).ty;
+            self.encoder.get_procedure_contract_for_awaited_call(
+                self.proc_def_id,
+                called_def_id,
+                &arguments,
+                target_local,
+                output_ty,
+                substs,
+            ).with_span(call_site_span)?
+        } else {
             self.encoder.get_procedure_contract_for_call(
                 self.proc_def_id,
                 called_def_id,
@@ -3422,7 +4172,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             pre_invs_spec,
             pre_func_spec,
         ) = self.encode_precondition_expr(&procedure_contract, substs, fake_expr_spans)?;
-        let pos = self.register_error(call_site_span, ErrorCtxt::ExhaleMethodPrecondition);
+        let precondition_ctxt = match self.get_expect_message(full_func_proc_name, mir_args) {
+            Some(message) => ErrorCtxt::ExhaleExpectPrecondition(message),
+            None => ErrorCtxt::ExhaleMethodPrecondition,
+        };
+        let pos = self.register_error(call_site_span, precondition_ctxt);
         stmts.push(vir::Stmt::Assert( vir::Assert {
             expr: replace_fake_exprs(pre_func_spec),
             position: pos,
@@ -3537,6 +4291,18 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         // Emit the label and magic wands
         stmts.push(vir::Stmt::label(post_label.clone()));
 
+        // Record the result of a call summarized in the postcondition
+        if let (Some((result_var, _)), Some(target_place)) = (&call_summary_vars, &encoded_target) {
+            let target_ty = destination.ty(self.mir, self.encoder.env().tcx()).ty;
+            let value_field = self.encoder.encode_value_field(target_ty)
+                .with_span(call_site_span)?;
+            stmts.push(vir::Stmt::Assign( vir::Assign {
+                target: vir::Expr::local(result_var.clone()),
+                source: target_place.clone().field(value_field),
+                kind: vir::AssignKind::Copy,
+            }));
+        }
+
         stmts.extend(stmts_after);
 
         self.procedure_contracts
@@ -3968,12 +4734,22 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mut weakening: Option<PreconditionWeakening> = None;
         let mut strengthening: Option<PostconditionStrengthening> = None;
 
-        if let SpecificationItem::Refined(from, to) = &procedure_spec.pres {
+        // `Drop::drop` cannot be called explicitly, so its precondition does
+        // not need to be weaker than the (empty) one of the trait. Instead, it
+        // is checked wherever a value of the type is dropped.
+        let tcx = self.encoder.env().tcx();
+        let is_drop_impl = self.encoder.env().query
+            .find_trait_method_substs(self.proc_def_id, self.substs)
+            .map_or(false, |(trait_method_def_id, _)| {
+                tcx.trait_of_item(trait_method_def_id) == tcx.lang_items().drop_trait()
+            });
+
+        if let SpecificationItem::Refined(from, to) = &procedure_spec.pres && !is_drop_impl {
             // See comment in `ProcedureContractGeneric::functional_precondition`.
-            let trait_substs = self.encoder.env().query.find_trait_method_substs(
+            let (trait_method_def_id, trait_substs) = self.encoder.env().query.find_trait_method_substs(
                 self.proc_def_id,
                 self.substs,
-            ).unwrap().1;
+            ).unwrap();
 
             let from_pre = from.iter()
                 .map(|spec| self.encoder.encode_assertion(
@@ -3988,8 +4764,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter()
                 .conjoin();
-            let to_pre = to.iter()
-                .map(|spec| self.encoder.encode_assertion(
+
+            // Every precondition of the implementation has to be implied by the
+            // trait's precondition; checking them one by one allows us to
+            // report the clause that is not.
+            let mut clause_checks = vec![];
+            for spec in to.iter() {
+                let to_pre = self.encoder.encode_assertion(
                     spec,
                     None,
                     &encoded_args,
@@ -3997,30 +4778,27 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     false,
                     self.proc_def_id,
                     self.substs,
-                ))
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .conjoin();
-
-            // The spans are used for error reporting
-            let spec_functions_span = MultiSpan::from_spans(from.iter().chain(to.iter())
-                .map(|spec_def_id| self.encoder.env().query.get_def_span(spec_def_id)).collect()
-            );
+                )?;
+                clause_checks.push((
+                    MultiSpan::from_span(self.encoder.env().query.get_def_span(spec)),
+                    vir_expr! {[from_pre.clone()] ==> [to_pre]},
+                ));
+            }
 
             weakening = Some(RefinementCheckExpr {
-                spec_functions_span,
-                refinement_check_expr: vir_expr! {[from_pre] ==> [to_pre]},
+                trait_method_name: self.encoder.env().name.get_item_name(trait_method_def_id),
+                clause_checks,
             });
         }
 
         if let SpecificationItem::Refined(from, to) = &procedure_spec.posts {
             // See comment in `ProcedureContractGeneric::functional_precondition`.
-            let trait_substs = self.encoder.env().query.find_trait_method_substs(
+            let (trait_method_def_id, trait_substs) = self.encoder.env().query.find_trait_method_substs(
                 self.proc_def_id,
                 self.substs,
-            ).unwrap().1;
+            ).unwrap();
 
-            let from_post = from
+            let to_post = to
                 .iter()
                 .map(|spec| self.encoder.encode_assertion(
                     spec,
@@ -4029,49 +4807,62 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     Some(&encoded_return),
                     false,
                     self.proc_def_id,
-                    trait_substs,
+                    self.substs,
                 ))
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter()
                 .conjoin();
-            let to_post = to
-                .iter()
-                .map(|spec| self.encoder.encode_assertion(
+
+            // Every postcondition of the trait has to be implied by the
+            // postcondition of the implementation; checking them one by one
+            // allows us to report the clause that is not.
+            let mut clause_checks = vec![];
+            for spec in from.iter() {
+                let from_post = self.encoder.encode_assertion(
                     spec,
                     Some(pre_label),
                     &encoded_args,
                     Some(&encoded_return),
                     false,
                     self.proc_def_id,
-                    self.substs,
-                ))
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .conjoin();
-
-            // The spans are used for error reporting
-            let spec_functions_span = MultiSpan::from_spans(from.iter().chain(to.iter())
-                .map(|spec_def_id| self.encoder.env().query.get_def_span(spec_def_id)).collect()
-            );
-
-            let strengthening_expr = self.wrap_arguments_into_old(
-                vir_expr! {
-                    [to_post] ==> [from_post]
-                },
-                pre_label,
-                self.procedure_contract(),
-                &encoded_args,
-            )?;
+                    trait_substs,
+                )?;
+                let strengthening_expr = self.wrap_arguments_into_old(
+                    vir_expr! {
+                        [to_post.clone()] ==> [from_post]
+                    },
+                    pre_label,
+                    self.procedure_contract(),
+                    &encoded_args,
+                )?;
+                clause_checks.push((
+                    MultiSpan::from_span(self.encoder.env().query.get_def_span(spec)),
+                    strengthening_expr,
+                ));
+            }
+            if clause_checks.iter().any(|(_, check)| !collect_call_summaries(check).is_empty()) {
+                return Err(UnsupportedFeature::new(
+                    "refining the specification of a trait method with `call_summary!(..)`",
+                    self.mir.span,
+                )
+                .with_trusted_workaround()
+                .into());
+            }
 
             strengthening = Some(PostconditionStrengthening {
-                spec_functions_span,
-                refinement_check_expr: strengthening_expr,
+                trait_method_name: self.encoder.env().name.get_item_name(trait_method_def_id),
+                clause_checks,
             });
         }
 
         if let SpecificationItem::Refined(from, to) = &procedure_spec.pledges {
             if !from.is_empty() || to.iter().any(|p| p.lhs.is_some()) {
-                unimplemented!("Refining specifications with pledges is not supported");
+                return Err(UnsupportedFeature::new(
+                    "refining the specification of a trait method with pledges",
+                    self.mir.span,
+                )
+                .with_trusted_workaround()
+                .into());
             }
             // If the trait has no pledges and the implementer only makes additional guarantees
             // then the refinement is safe
@@ -4115,14 +4906,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         // Weakening assertion must be put before inhaling the precondition, otherwise the weakening
         // soundness check becomes trivially satisfied.
         if let Some(weakening_spec) = weakening_spec {
-            let pos = self.register_error(weakening_spec.spec_functions_span, ErrorCtxt::AssertMethodPreconditionWeakening);
-            self.cfg_method.add_stmt(
-                start_cfg_block,
-                vir::Stmt::Assert( vir::Assert {
-                    expr: weakening_spec.refinement_check_expr,
-                    position: pos
-                }),
-            );
+            for (span, check) in weakening_spec.clause_checks {
+                let pos = self.register_error(
+                    span,
+                    ErrorCtxt::AssertMethodPreconditionWeakening(weakening_spec.trait_method_name.clone()),
+                );
+                self.cfg_method.add_stmt(
+                    start_cfg_block,
+                    vir::Stmt::Assert( vir::Assert {
+                        expr: check,
+                        position: pos
+                    }),
+                );
+            }
         }
         self.cfg_method.add_stmt(
             start_cfg_block,
@@ -4164,18 +4960,23 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         // Encode magic wands
         let borrow_infos = &contract.borrow_infos;
+        // The only magic wand is the one of the returned reference, so
+        // pledges cannot describe the expiry of other references.
+        if borrow_infos.is_empty() && contract.pledges().next().is_some() {
+            return Err(EncodingError::unsupported(
+                "pledges are only supported for functions that return a reference",
+            ));
+        }
+        if borrow_infos.len() > 1 {
+            return Err(EncodingError::unsupported(
+                "functions that return several references are not supported",
+            ));
+        }
         if !borrow_infos.is_empty() {
-            assert_eq!(
-                borrow_infos.len(),
-                1,
-                "We can have at most one magic wand in the postcondition."
-            );
             let borrow_info = &borrow_infos[0];
+            // All the pledges of the function describe the expiry of the
+            // returned reference and are conjoined in the same magic wand.
             let pledges: Vec<&Pledge> = contract.pledges().collect();
-            assert!(
-                pledges.len() <= 1,
-                "There can be at most one pledge in the function postcondition."
-            );
             debug!("borrow_info {:?}", borrow_info);
             let encode_place_perm = |place, mutability, label| -> _ {
                 let perm_amount = match mutability {
@@ -4207,7 +5008,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 .iter()
                 .map(|(place, mutability)| encode_place_perm(*place, *mutability, pre_label))
                 .collect::<SpannedEncodingResult<_>>()?;
-            if let Some(typed::Pledge { reference, lhs: body_lhs, rhs: body_rhs}) = pledges.first() {
+            for typed::Pledge { reference, lhs: body_lhs, rhs: body_rhs} in pledges {
                 debug!(
                     "pledge reference={:?} lhs={:?} rhs={:?}",
                     reference, body_lhs, body_rhs
@@ -4256,7 +5057,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 );
                 let (encoded_deref, ..) = self
                     .mir_encoder
-                    .encode_deref(encoded_return, ty)
+                    .encode_deref(encoded_return.clone(), ty)
                     .with_span(return_span)?;
 
                 let original_expr = encoded_deref;
@@ -4483,8 +5284,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let postcondition_span = MultiSpan::from_spans(func_spec_spans);
         let func_spec_pos = self.mir_encoder.register_span(postcondition_span.clone());
 
-        // Encode invariant for return value
-        if !self.encoder.is_pure(contract.def_id, Some(substs)) {
+        // Encode invariant for return value. Derived implementations of
+        // `Default::default` can be pure, but still have to establish it.
+        if !self.encoder.is_pure(contract.def_id, Some(substs))
+            || self.encoder.env().query.is_automatically_derived_method(contract.def_id)
+        {
             invs_spec.push(
                 self.encoder.encode_invariant_func_app(
                     self.locals.get_type(contract.returned_value),
@@ -4495,6 +5299,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         let full_func_spec = func_spec.into_iter().conjoin()
             .set_default_pos(func_spec_pos);
+        let full_func_spec = if function_end {
+            self.replace_call_summaries_with_recorded_calls(full_func_spec)
+        } else {
+            self.replace_call_summaries_with_unknown_results(full_func_spec, span)?
+        };
 
         Ok((
             type_spec.into_iter().conjoin(),
@@ -4506,6 +5315,145 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         ))
     }
 
+    /// Declares the local variables that record the last calls of the
+    /// functions summarized with `call_summary!(..)` in the postcondition.
+    fn declare_call_summary_vars(&mut self) {
+        // This clone is only due to borrow checker restrictions
+        let contract = self.procedure_contract().clone();
+        let encoded_args: Vec<vir::Expr> = contract
+            .args
+            .iter()
+            .map(|local| self.encode_prusti_local(*local).into())
+            .collect();
+        let encoded_return: vir::Expr = self.encode_prusti_local(contract.returned_value).into();
+        for (typed_assertion, assertion_substs) in
+            contract.functional_postcondition(self.encoder.env(), self.substs)
+        {
+            // Errors are reported when the postcondition is encoded, after
+            // the errors in the body of the procedure.
+            let Ok(assertion) = self.encoder.encode_assertion(
+                &typed_assertion,
+                None,
+                &encoded_args,
+                Some(&encoded_return),
+                false,
+                self.proc_def_id,
+                assertion_substs,
+            ) else {
+                continue;
+            };
+            for call_summary in collect_call_summaries(&assertion) {
+                if self.call_summaries.contains_key(&call_summary.function_name) {
+                    continue;
+                }
+                let result_var = self.cfg_method.add_fresh_local_var(call_summary.return_type);
+                let arg_vars = call_summary
+                    .formal_arguments
+                    .into_iter()
+                    .map(|arg| self.cfg_method.add_fresh_local_var(arg.typ))
+                    .collect();
+                self.call_summaries
+                    .insert(call_summary.function_name, (result_var, arg_vars));
+            }
+        }
+    }
+
+    /// Replaces the calls summarized in the postcondition of the procedure by
+    /// the recorded result of the last call of the summarized function. The
+    /// arguments of that call have to be the ones of the summary wherever the
+    /// summary is evaluated.
+    fn replace_call_summaries_with_recorded_calls(&self, expr: vir::Expr) -> vir::Expr {
+        replace_call_summaries_with_conditions(expr, |call_summary| {
+            let (result_var, arg_vars) = &self.call_summaries[&call_summary.function_name];
+            let arg_equalities = call_summary.arguments.iter()
+                .zip(arg_vars)
+                .map(|(arg, arg_var)| vir::Expr::eq_cmp(
+                    arg.clone(),
+                    vir::Expr::local(arg_var.clone()),
+                ))
+                .collect();
+            (vir::Expr::local(result_var.clone()), arg_equalities)
+        })
+    }
+
+    /// Replaces the calls summarized in the postcondition of a called function
+    /// by existentially quantified results, for which the postconditions of
+    /// the summarized functions hold. Summaries in the postconditions of the
+    /// summarized functions are replaced by unconstrained results.
+    fn replace_call_summaries_with_unknown_results(
+        &self,
+        expr: vir::Expr,
+        span: Span,
+    ) -> SpannedEncodingResult<vir::Expr> {
+        let call_summaries = collect_call_summaries(&expr);
+        if call_summaries.is_empty() {
+            return Ok(expr);
+        }
+        let mut result_vars: FxHashMap<String, vir::LocalVar> = FxHashMap::default();
+        let mut summarized_posts = vec![];
+        for call_summary in call_summaries {
+            if result_vars.contains_key(&call_summary.function_name) {
+                continue;
+            }
+            let result_var = vir::LocalVar::new(
+                format!("_call_summary_{}", result_vars.len()),
+                call_summary.return_type.clone(),
+            );
+            let mut arg_vars = vec![];
+            let mut arg_replacements = vec![];
+            for (index, (arg, formal_arg)) in call_summary.arguments.iter()
+                .zip(call_summary.formal_arguments.iter())
+                .enumerate()
+            {
+                let arg_var: vir::Expr = vir::LocalVar::new(
+                    format!("_call_summary_arg_{index}"),
+                    formal_arg.typ.clone(),
+                ).into();
+                arg_vars.push(arg_var.clone());
+                arg_replacements.push((arg_var, arg.clone()));
+            }
+            let (callee_def_id, callee_substs) =
+                get_call_summary_callee(self.encoder, &call_summary.function_name);
+            let callee_contract = self.encoder
+                .get_procedure_contract_for_def(callee_def_id, callee_substs)
+                .with_span(span)?;
+            for (typed_assertion, assertion_substs) in
+                callee_contract.functional_postcondition(self.encoder.env(), callee_substs)
+            {
+                let callee_post = self.encoder.encode_assertion(
+                    &typed_assertion,
+                    None,
+                    &arg_vars,
+                    Some(&result_var.clone().into()),
+                    true,
+                    self.proc_def_id,
+                    assertion_substs,
+                )?;
+                // The arguments and the result are values, which do not depend
+                // on the state in which they are evaluated.
+                let callee_post = callee_post.fold_expr(|expr| match expr {
+                    vir::Expr::LabelledOld(vir::LabelledOld { base, .. }) => *base,
+                    expr => expr,
+                });
+                summarized_posts.push(callee_post.replace_multiple_places(&arg_replacements));
+            }
+            result_vars.insert(call_summary.function_name, result_var);
+        }
+        let body = vir::Expr::and(summarized_posts.into_iter().conjoin(), expr);
+        let body = replace_call_summaries(body, |call_summary| {
+            let fresh_index = result_vars.len();
+            result_vars
+                .entry(call_summary.function_name.clone())
+                .or_insert_with(|| vir::LocalVar::new(
+                    format!("_call_summary_{fresh_index}"),
+                    call_summary.return_type.clone(),
+                ))
+                .clone()
+                .into()
+        });
+        Ok(vir::Expr::exists(result_vars.into_values().collect(), vec![], body))
+    }
+
     /// Modelling move as simple assignment on Viper level has a consequence
     /// that the assigned place changes. Therefore, if some value is
     /// moved into a borrow, the borrow starts pointing to a different
@@ -4639,12 +5587,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             let post_label = post_label.to_string();
             stmts.push(vir::Stmt::label(post_label.clone()));
 
-            // Make the deref of reference arguments to be folded (see issue #47)
+            // Make the deref of reference arguments to be folded (see issue #47).
+            // The magic wand gives back only the permissions blocked by the
+            // returned reference, so the arguments that no loan might block
+            // at the end of the method do not need to be folded in the package.
             package_stmts.push(vir::Stmt::comment("Fold predicates for &mut args"));
+            let maybe_borrowed = self.polonius_info().get_maybe_borrowed_state(location);
             for arg_index in self.mir.args_iter() {
                 let arg_ty = self.mir.local_decls[arg_index].ty;
                 let arg_span = self.mir_encoder.get_local_span(arg_index);
-                if is_reference(arg_ty) {
+                let arg_deref = self.encoder.env().tcx().mk_place_deref(arg_index.into());
+                let is_definitely_unborrowed = maybe_borrowed
+                    .as_ref()
+                    .map_or(false, |state| state.is_definitely_unborrowed(arg_deref.into()));
+                if is_reference(arg_ty) && !is_definitely_unborrowed {
                     let encoded_arg = self.mir_encoder.encode_local(arg_index)?;
                     let (deref_place, ..) =
                         self.mir_encoder
@@ -4864,16 +5820,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             vir::Stmt::comment("Assert possible strengthening"),
         );
         if let Some(strengthening_spec) = strengthening_spec {
-            let patched_strengthening_spec =
-                self.replace_old_places_with_ghost_vars(None, strengthening_spec.refinement_check_expr);
-            let pos = self.register_error(strengthening_spec.spec_functions_span, ErrorCtxt::AssertMethodPostconditionStrengthening);
-            self.cfg_method.add_stmt(
-                return_cfg_block,
-                vir::Stmt::Assert( vir::Assert {
-                    expr: patched_strengthening_spec,
-                    position: pos,
-                }),
-            );
+            for (span, check) in strengthening_spec.clause_checks {
+                let patched_check = self.replace_old_places_with_ghost_vars(None, check);
+                let pos = self.register_error(
+                    span,
+                    ErrorCtxt::AssertMethodPostconditionStrengthening(strengthening_spec.trait_method_name.clone()),
+                );
+                self.cfg_method.add_stmt(
+                    return_cfg_block,
+                    vir::Stmt::Assert( vir::Assert {
+                        expr: patched_check,
+                        position: pos,
+                    }),
+                );
+            }
         }
 
         // Assert functional specification of postcondition
@@ -5205,7 +6165,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                                                     equalities.push(eq);
                                                 }
                                             }
-                                            ExprOrArrayBase::SliceBase(_) => unimplemented!("slices in loops not yet implemented"),
+                                            ExprOrArrayBase::SliceBase(_) => {
+                                                return Err(EncodingError::unsupported(
+                                                    "accessing slices in loops is not supported",
+                                                ));
+                                            }
                                         }
                                     }
                                 }
@@ -5272,9 +6236,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
     fn get_loop_spec_blocks(&self, loop_head: BasicBlockIndex) -> Vec<BasicBlockIndex> {
         let mut res = vec![];
         for bbi in self.procedure.get_reachable_cfg_blocks() {
-            if Some(loop_head) == self.loop_encoder.get_loop_head(bbi)
-                && self.procedure.is_spec_block(bbi)
-            {
+            // A `loop_invariant!(...)` belongs to the loop following it, not to
+            // the loop containing it.
+            let spec_loop_head = if self.procedure.is_loop_invariant_before_loop_block(bbi) {
+                self.procedure.get_following_loop_head(bbi)
+            } else {
+                self.loop_encoder.get_loop_head(bbi)
+            };
+            if Some(loop_head) == spec_loop_head && self.procedure.is_spec_block(bbi) {
                 res.push(bbi)
             } else {
                 debug!(
@@ -5288,26 +6257,204 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         res
     }
 
-    /// Encode the functional specification of a loop
-    #[tracing::instrument(level = "trace", skip(self), ret)]
-    fn encode_loop_invariant_specs(
-        &self,
-        loop_head: BasicBlockIndex,
-        _loop_inv_block: BasicBlockIndex,
-    ) -> SpannedEncodingResult<(Vec<vir::Expr>, MultiSpan)> {
-        let spec_blocks = self.get_loop_spec_blocks(loop_head);
-        trace!(
-            "loop head {:?} has spec blocks {:?}",
-            loop_head,
-            spec_blocks
-        );
+    /// Returns the spans of the `body_invariant!(...)` specifications in a
+    /// specification block.
+    fn get_loop_invariant_spans(&self, bbi: BasicBlockIndex) -> Vec<Span> {
+        let mut spans = vec![];
+        for stmt in &self.mir.basic_blocks[bbi].statements {
+            if let mir::StatementKind::Assign(box (
+                _,
+                mir::Rvalue::Aggregate(box mir::AggregateKind::Closure(cl_def_id, _), _),
+            )) = stmt.kind {
+                if let Some(prusti_interface::specs::typed::LoopSpecification::Invariant(inv)) =
+                    self.encoder.get_loop_specs(cl_def_id)
+                {
+                    spans.push(self.encoder.env().tcx().def_span(inv));
+                }
+            }
+        }
+        spans
+    }
 
-        // `body_invariant!(..)` is desugared to a closure with special attributes,
-        // which we can detect and use to retrieve the specification.
-        let mut encoded_specs = vec![];
-        let mut encoded_spec_spans = vec![];
-        for bbi in spec_blocks {
-            for stmt in &self.mir.basic_blocks[bbi].statements {
+    /// All the invariants of a loop are checked together at the position of
+    /// the first `body_invariant!(...)`. Warn about invariants that are
+    /// separated from it by code of the loop body, because they do not describe
+    /// the state at their own position.
+    fn warn_about_split_loop_invariants(&self, loop_head: BasicBlockIndex) {
+        let mut invariant_spans: Vec<Span> = self
+            .get_loop_spec_blocks(loop_head)
+            .into_iter()
+            .filter(|&bbi| !self.procedure.is_loop_invariant_before_loop_block(bbi))
+            .flat_map(|bbi| self.get_loop_invariant_spans(bbi))
+            .collect();
+        invariant_spans.sort_by_key(|span| span.lo());
+        let Some(&first_invariant_span) = invariant_spans.first() else {
+            return;
+        };
+        let loop_info = self.loop_encoder.loops();
+        let code_spans: Vec<Span> = loop_info
+            .get_loop_body(loop_head)
+            .iter()
+            .filter(|&&bbi| !self.procedure.is_spec_block(bbi))
+            .flat_map(|&bbi| {
+                let data = &self.mir.basic_blocks[bbi];
+                data.statements
+                    .iter()
+                    .map(|stmt| stmt.source_info.span)
+                    .chain(data.terminator.iter().map(|term| term.source_info.span))
+            })
+            .filter(|span| !span.from_expansion())
+            .collect();
+        let split_invariant_spans: Vec<Span> = invariant_spans[1..]
+            .iter()
+            .copied()
+            .filter(|invariant_span| {
+                code_spans.iter().any(|code_span| {
+                    first_invariant_span.hi() <= code_span.lo()
+                        && code_span.hi() <= invariant_span.lo()
+                })
+            })
+            .collect();
+        if !split_invariant_spans.is_empty() {
+            PrustiError::warning(
+                "these loop invariants are checked at the position of the first \
+                `body_invariant!(...)` of the loop, not at their own position",
+                MultiSpan::from_spans(split_invariant_spans),
+            )
+            .set_help("place all the `body_invariant!(...)` of a loop next to each other")
+            .emit(&self.encoder.env().diagnostic);
+        }
+    }
+
+    /// Warn about statements that are executed in every iteration before the
+    /// `body_invariant!(...)` and that modify variables mentioned by it. Such
+    /// an invariant does not describe the state at the loop head, which makes
+    /// its verification failures hard to understand.
+    fn warn_about_effects_before_loop_invariant(
+        &self,
+        loop_head: BasicBlockIndex,
+        loop_body: &[BasicBlockIndex],
+        loop_body_before_inv: &[BasicBlockIndex],
+    ) {
+        struct MentionedLocals(FxHashSet<mir::Local>);
+        impl<'tcx> mir::visit::Visitor<'tcx> for MentionedLocals {
+            fn visit_local(
+                &mut self,
+                local: mir::Local,
+                _context: mir::visit::PlaceContext,
+                _location: mir::Location,
+            ) {
+                self.0.insert(local);
+            }
+        }
+
+        let invariant_blocks: Vec<BasicBlockIndex> = self
+            .get_loop_spec_blocks(loop_head)
+            .into_iter()
+            .filter(|&bbi| !self.procedure.is_loop_invariant_before_loop_block(bbi))
+            .filter(|&bbi| !self.get_loop_invariant_spans(bbi).is_empty())
+            .collect();
+        let Some(invariant_span) = invariant_blocks
+            .iter()
+            .flat_map(|&bbi| self.get_loop_invariant_spans(bbi))
+            .min_by_key(|span| span.lo())
+        else {
+            return;
+        };
+        let mut mentioned = MentionedLocals(FxHashSet::default());
+        for &bbi in &invariant_blocks {
+            mir::visit::Visitor::visit_basic_block_data(
+                &mut mentioned,
+                bbi,
+                &self.mir.basic_blocks[bbi],
+            );
+        }
+        // Only variables that keep their value across iterations are relevant;
+        // e.g. the pattern variables of a `for` or `while let` loop are bound
+        // in every iteration before the invariant.
+        let declared_in_loop: FxHashSet<mir::Local> = loop_body
+            .iter()
+            .flat_map(|&bbi| &self.mir.basic_blocks[bbi].statements)
+            .filter_map(|stmt| match stmt.kind {
+                mir::StatementKind::StorageLive(local) => Some(local),
+                _ => None,
+            })
+            .collect();
+        let user_variables: FxHashSet<mir::Local> = self
+            .mir
+            .var_debug_info
+            .iter()
+            .filter_map(|vdi| match vdi.value {
+                mir::VarDebugInfoContents::Place(place) => place.as_local(),
+                _ => None,
+            })
+            .collect();
+        let is_relevant = |local: mir::Local| {
+            mentioned.0.contains(&local)
+                && user_variables.contains(&local)
+                && !declared_in_loop.contains(&local)
+        };
+
+        let mut effect_spans = vec![];
+        for &bbi in loop_body_before_inv {
+            let data = &self.mir.basic_blocks[bbi];
+            for stmt in &data.statements {
+                if let mir::StatementKind::Assign(box (place, _)) = stmt.kind {
+                    if is_relevant(place.local) {
+                        effect_spans.push(stmt.source_info.span);
+                    }
+                }
+            }
+            if let Some(mir::Terminator {
+                kind: TerminatorKind::Call { destination, .. },
+                source_info,
+            }) = &data.terminator
+            {
+                if is_relevant(destination.local) {
+                    effect_spans.push(source_info.span);
+                }
+            }
+        }
+        effect_spans.retain(|span| !span.from_expansion());
+        if !effect_spans.is_empty() {
+            PrustiError::warning(
+                "the loop invariant is checked after code of the loop body that modifies \
+                variables mentioned by the invariant",
+                MultiSpan::from_spans(effect_spans),
+            )
+            .add_note(
+                "the invariant describes the state after these modifications, not the state \
+                at the beginning of each iteration",
+                Some(invariant_span),
+            )
+            .set_help(
+                "move the `body_invariant!(...)` to the beginning of the loop body, before \
+                the modifications",
+            )
+            .emit(&self.encoder.env().diagnostic);
+        }
+    }
+
+    /// Encode the functional specification of a loop
+    #[tracing::instrument(level = "trace", skip(self), ret)]
+    fn encode_loop_invariant_specs(
+        &self,
+        loop_head: BasicBlockIndex,
+        _loop_inv_block: BasicBlockIndex,
+    ) -> SpannedEncodingResult<(Vec<vir::Expr>, MultiSpan)> {
+        let spec_blocks = self.get_loop_spec_blocks(loop_head);
+        trace!(
+            "loop head {:?} has spec blocks {:?}",
+            loop_head,
+            spec_blocks
+        );
+
+        // `body_invariant!(..)` is desugared to a closure with special attributes,
+        // which we can detect and use to retrieve the specification.
+        let mut encoded_specs = vec![];
+        let mut encoded_spec_spans = vec![];
+        for bbi in spec_blocks {
+            for stmt in &self.mir.basic_blocks[bbi].statements {
                 if let mir::StatementKind::Assign(box (
                     _,
                     mir::Rvalue::Aggregate(box mir::AggregateKind::Closure(cl_def_id, cl_substs), _),
@@ -5344,6 +6491,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
         let (func_spec, func_spec_span) =
             self.encode_loop_invariant_specs(loop_head, loop_inv_block)?;
+        let prev_iter_state = if after_loop_iteration {
+            PrevIterState::Label(self.loop_iteration_label(loop_head))
+        } else {
+            PrevIterState::Current
+        };
+        let func_spec = func_spec
+            .into_iter()
+            .map(|spec| self.encode_prev_iter_exprs(spec, &prev_iter_state))
+            .collect::<Vec<_>>();
         let (permissions, equalities, invs_spec) =
             self.encode_loop_invariant_permissions(loop_head, loop_inv_block, true)
                 .with_span(func_spec_span.clone())?;
@@ -5454,15 +6610,79 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let (func_spec, func_spec_span) =
             self.encode_loop_invariant_specs(loop_head, loop_inv_block)?;
 
+        // The same `prev_iter(..)` expression has to get the same value in
+        // all the invariants, so they are replaced after conjoining them.
+        let func_spec_expr = self.encode_prev_iter_exprs(
+            func_spec.into_iter().conjoin(),
+            &PrevIterState::Unknown,
+        );
+
         let mut stmts = vec![vir::Stmt::comment(format!(
             "Inhale the loop fnspec invariant of block {loop_head:?}"
         ))];
         stmts.push(vir::Stmt::Inhale( vir::Inhale {
-            expr: func_spec.into_iter().conjoin(),
+            expr: func_spec_expr,
         }));
+        if !after_loop {
+            // The state at which `prev_iter(..)` is evaluated when the
+            // invariant is checked at the end of this iteration.
+            stmts.push(vir::Stmt::label(self.loop_iteration_label(loop_head)));
+        }
         Ok((stmts, func_spec_span))
     }
 
+    /// The label of the state in which the invariant of the loop `loop_head`
+    /// holds at the beginning of an iteration.
+    fn loop_iteration_label(&mut self, loop_head: BasicBlockIndex) -> String {
+        self.loop_iteration_label
+            .entry(loop_head)
+            .or_insert_with(|| self.cfg_method.get_fresh_label_name())
+            .clone()
+    }
+
+    /// Replace the `prev_iter(..)` expressions of a loop invariant, encoded as
+    /// old expressions with the `PREV_ITER_LABEL` label, according to `state`.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn encode_prev_iter_exprs(
+        &mut self,
+        expr: vir::Expr,
+        state: &PrevIterState,
+    ) -> vir::Expr {
+        struct PrevIterReplacer<'a> {
+            state: &'a PrevIterState,
+            unknown_values: FxHashMap<vir::Expr, vir::Expr>,
+            cfg_method: &'a mut vir::CfgMethod,
+        }
+        impl<'a> vir::ExprFolder for PrevIterReplacer<'a> {
+            fn fold_labelled_old(&mut self, vir::LabelledOld {label, base, position}: vir::LabelledOld) -> vir::Expr {
+                let base = self.fold_boxed(base);
+                if label != PREV_ITER_LABEL {
+                    return vir::Expr::LabelledOld( vir::LabelledOld { label, base, position });
+                }
+                match self.state {
+                    PrevIterState::Current => *base,
+                    PrevIterState::Label(iteration_label) => vir::Expr::LabelledOld( vir::LabelledOld {
+                        label: iteration_label.clone(),
+                        base,
+                        position,
+                    }),
+                    PrevIterState::Unknown => {
+                        let cfg_method = &mut self.cfg_method;
+                        self.unknown_values.entry(*base).or_insert_with_key(|base| {
+                            cfg_method.add_fresh_local_var(base.get_type().clone()).into()
+                        }).clone().set_pos(position)
+                    }
+                }
+            }
+        }
+        let mut replacer = PrevIterReplacer {
+            state,
+            unknown_values: FxHashMap::default(),
+            cfg_method: &mut self.cfg_method,
+        };
+        vir::ExprFolder::fold(&mut replacer, expr)
+    }
+
     fn encode_prusti_local(&self, local: Local) -> vir::LocalVar {
         let var_name = self.locals.get_name(local);
         let typ = self
@@ -5691,6 +6911,28 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Inhales the (read-only) permission to the string slice that a string
+    /// literal points to, together with the bytes it consists of.
+    fn encode_str_literal_contents(
+        &self,
+        encoded_str: vir::Expr,
+        constant: &mir::Constant<'tcx>,
+    ) -> EncodingResult<Vec<vir::Stmt>> {
+        let sequence_types = self.encoder.encode_sequence_types(constant.ty().peel_refs())?;
+        let str_acc = vir::Expr::predicate_access_predicate(
+            sequence_types.sequence_pred_type,
+            encoded_str.clone(),
+            vir::PermAmount::Read,
+        );
+        let str_snap = self.encoder.patch_snapshots(vir::Expr::snap_app(encoded_str))?;
+        let literal_snap = self.encoder.encode_snapshot_constant(constant)?;
+        let contents_eq = vir_expr!{ [ str_snap ] == [ literal_snap ] };
+        Ok(vec![
+            vir_stmt!{ inhale [ str_acc ] },
+            vir_stmt!{ inhale [ contents_eq ] },
+        ])
+    }
+
     /// Return type:
     /// - `Vec<vir::Stmt>`: the statements that encode the assignment of `operand` to `lhs`
     #[tracing::instrument(level = "debug", skip(self), ret())]
@@ -5749,6 +6991,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 stmts
             }
 
+            mir::Operand::Copy(place)
+                if self.unsafe_memory && self.mir_encoder.get_operand_ty(operand).is_unsafe_ptr() =>
+            {
+                // The permission to the target of the pointer is transferred
+                // to the copy, so the copied pointer cannot be dereferenced
+                // anymore.
+                self.encode_assign_operand(lhs, &mir::Operand::Move(*place), location)?
+            }
+
             mir::Operand::Copy(place) => {
                 let (src, mut stmts, ty, _) = self.encode_place(*place, ArrayAccessKind::Shared, location)?;
                 let encode_stmts = match ty.kind() {
@@ -5802,8 +7053,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             vir::AssignKind::Copy,
                             true
                         )?;
-                        // TODO Encoding of string literals is not yet supported,
-                        // so do not encode an assignment if the RHS is a string
+                        // String literals are not encoded as expressions, so
+                        // inhale the contents of the RHS instead of assigning it
                         if !is_str(ty) {
                             // Initialize the constant
                             let const_val = self.encoder
@@ -5815,6 +7066,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                                 source: const_val,
                                 kind: vir::AssignKind::Copy,
                             }));
+                        } else if ty.builtin_deref(true).map_or(false, |tm| tm.ty.is_str()) {
+                            stmts.extend(
+                                self.encode_str_literal_contents(lhs.clone().field(field), expr)
+                                    .with_span(span)?
+                            );
                         }
                         stmts
                     }
@@ -6158,6 +7414,43 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Assignment where the RHS is a raw pointer to a place, in a procedure
+    /// annotated with `#[trusted_unsafe_memory]`. The permission to the place
+    /// is transferred to the pointer, which thus points to a valid value until
+    /// the permission is transferred elsewhere.
+    fn encode_assign_address_of(
+        &mut self,
+        place: mir::Place<'tcx>,
+        location: mir::Location,
+        encoded_lhs: vir::Expr,
+        ty: ty::Ty<'tcx>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        let (encoded_value, mut stmts, _, _) =
+            self.encode_place(place, ArrayAccessKind::Mutable(None, location), location)?;
+        let field = self.encoder.encode_value_field(ty).with_span(span)?;
+        stmts.extend(
+            self.prepare_assign_target(
+                encoded_lhs.clone(),
+                field.clone(),
+                location,
+                vir::AssignKind::Move,
+                false
+            )?
+        );
+        stmts.push(vir::Stmt::Assign( vir::Assign {
+            target: encoded_lhs.field(field),
+            source: encoded_value,
+            kind: vir::AssignKind::Move,
+        }));
+        // Store a label for this state
+        let label = self.cfg_method.get_fresh_label_name();
+        debug!("Current loc {:?} has label {}", location, label);
+        self.label_after_location.insert(location, label.clone());
+        stmts.push(vir::Stmt::label(label));
+        Ok(stmts)
+    }
+
     /// Assignment where the RHS is a cast operation
     /// [encoded_lhs] = [operand] as [dst_ty]
     #[tracing::instrument(level = "trace", skip(self))]
@@ -6275,6 +7568,380 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Encodes the implicit drop of a value at a `Drop` terminator, which runs
+    /// the drop glue of its type. If the type of the value has a `Drop`
+    /// implementation in the current crate, the preconditions of its `drop`
+    /// method are checked for the dropped value and its postconditions are
+    /// assumed afterwards, before the fields are dropped. The fields of
+    /// structs and tuples without such an implementation are dropped one by
+    /// one. Values that have been moved out are not checked, because then no
+    /// destructor runs; the other cases in which the drop glue has
+    /// preconditions are not supported.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn encode_drop_obligations(
+        &mut self,
+        place: mir::Place<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        let tcx = self.encoder.env().tcx();
+        let ty = place.ty(self.mir, tcx).ty;
+        let field_types: Vec<_> = match ty.kind() {
+            ty::TyKind::Adt(adt_def, substs) if adt_def.destructor(tcx).is_none()
+                && adt_def.is_struct() && !adt_def.is_box() =>
+            {
+                adt_def.all_fields().map(|field| field.ty(tcx, substs)).collect()
+            }
+            ty::TyKind::Tuple(types) => types.iter().collect(),
+            _ => vec![],
+        };
+        if !field_types.is_empty() {
+            let mut stmts = vec![];
+            for (i, field_ty) in field_types.into_iter().enumerate() {
+                let field_place = tcx.mk_place_field(place, FieldIdx::from_usize(i), field_ty);
+                stmts.extend(self.encode_drop_obligations(field_place, location)?);
+            }
+            return Ok(stmts);
+        }
+        let (adt_def, destructor_def_id, substs) = match ty.kind() {
+            ty::TyKind::Adt(adt_def, substs) => match adt_def.destructor(tcx) {
+                Some(destructor) if destructor.did.is_local() => (adt_def, destructor.did, *substs),
+                _ => return self.check_drop_glue_supported(place, ty, location).map(|_| vec![]),
+            },
+            _ => return self.check_drop_glue_supported(place, ty, location).map(|_| vec![]),
+        };
+
+        // The fields of structs are dropped one by one after `drop`, in the
+        // state described by its postconditions; the other fields are not
+        // supported.
+        let field_types: Vec<_> = adt_def.all_fields().map(|field| field.ty(tcx, substs)).collect();
+        let fields_need_checks = if adt_def.is_struct() {
+            field_types.iter().any(|&field_ty| {
+                self.find_drop_with_preconditions(field_ty, &mut FxHashSet::default()).is_some()
+            })
+        } else {
+            for &field_ty in &field_types {
+                self.check_drop_glue_supported(place, field_ty, location)?;
+            }
+            false
+        };
+
+        let (encoded_place, mut stmts, ..) =
+            self.encode_place(place, ArrayAccessKind::Shared, location)?;
+        if !self.init_info.is_vir_place_accessible(&encoded_place, location) {
+            if self.find_drop_with_preconditions(ty, &mut FxHashSet::default()).is_some()
+                && self.is_place_maybe_initialized(place, location)
+            {
+                let mut error = SpannedEncodingError::unsupported(
+                    format!(
+                        "the preconditions of `Drop::drop` cannot be checked for a value of type \
+                        `{ty}` that might have been moved out"
+                    ),
+                    span,
+                );
+                error.set_help(
+                    "move the value out on all paths, for example by passing it to \
+                    `std::mem::drop` on the paths on which it is not moved"
+                );
+                return Err(error);
+            }
+            return Ok(vec![]);
+        }
+
+        // `drop` receives the value as `&mut self`; we pretend that it is
+        // called with a reference to the dropped place.
+        let self_ty = tcx.mk_mut_ref(tcx.lifetimes.re_erased, ty);
+        let self_local = self.locals.get_fresh(self_ty);
+        let target_local = self.locals.get_fresh(tcx.mk_unit());
+        let contract = self.encoder.get_procedure_contract_for_call(
+            self.proc_def_id,
+            destructor_def_id,
+            &[self_local],
+            target_local,
+            substs,
+        ).with_span(span)?;
+        let (_, _, invs_spec, func_spec) =
+            self.encode_precondition_expr(&contract, substs, FxHashMap::default())?;
+
+        let val_ref_field = self.encoder.encode_value_field(self_ty).with_span(span)?;
+        let self_place = vir::Expr::local(self.encode_prusti_local(self_local)).field(val_ref_field);
+        let pos = self.register_error(span, ErrorCtxt::AssertDropPrecondition);
+        for expr in [func_spec, invs_spec] {
+            stmts.push(vir::Stmt::Assert( vir::Assert {
+                expr: expr.replace_place(&self_place, &encoded_place),
+                position: pos,
+            }));
+        }
+
+        if !fields_need_checks && !self.drop_has_postconditions(destructor_def_id, substs) {
+            return Ok(stmts);
+        }
+        // `drop` might change the value, about which only its postconditions
+        // are known afterwards.
+        let pre_label = self.cfg_method.get_fresh_label_name();
+        stmts.push(vir::Stmt::label(pre_label.clone()));
+        stmts.extend(self.encode_havoc_and_initialization(&encoded_place).with_span(span)?);
+        let encoded_self = vir::Expr::local(self.encode_prusti_local(self_local));
+        let encoded_target = vir::Expr::local(self.encode_prusti_local(target_local));
+        for (typed_assertion, assertion_substs) in
+            contract.functional_postcondition(self.encoder.env(), substs)
+        {
+            let assertion = self.encoder.encode_assertion(
+                &typed_assertion,
+                Some(&pre_label),
+                &[encoded_self.clone()],
+                Some(&encoded_target),
+                false,
+                self.proc_def_id,
+                assertion_substs,
+            )?;
+            stmts.push(vir::Stmt::Inhale( vir::Inhale {
+                expr: assertion.replace_place(&self_place, &encoded_place),
+            }));
+        }
+        if adt_def.is_struct() {
+            for (i, field_ty) in field_types.into_iter().enumerate() {
+                let field_place = tcx.mk_place_field(place, FieldIdx::from_usize(i), field_ty);
+                stmts.extend(self.encode_drop_obligations(field_place, location)?);
+            }
+        }
+        Ok(stmts)
+    }
+
+    /// Encodes the drops of the arguments of a call that are moved into the
+    /// callee and whose drop glue calls a `drop` method with preconditions.
+    /// The arguments of `std::mem::drop` are dropped at the call. A
+    /// non-generic callee is verified to satisfy the preconditions when it
+    /// drops the argument; the drops in generic callees are not supported.
+    fn encode_drop_obligations_of_moved_args(
+        &mut self,
+        called_def_id: ProcedureDefId,
+        full_func_proc_name: &str,
+        args: &[mir::Operand<'tcx>],
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let tcx = self.encoder.env().tcx();
+        let mut stmts = vec![];
+        for (index, arg) in args.iter().enumerate() {
+            let mir::Operand::Move(place) = arg else {
+                continue;
+            };
+            let ty = place.ty(self.mir, tcx).ty;
+            let adt_ty = self.find_drop_with_preconditions(ty, &mut FxHashSet::default());
+            let Some(adt_ty) = adt_ty else {
+                continue;
+            };
+            if matches!(full_func_proc_name, "core::mem::drop" | "std::mem::drop") {
+                stmts.extend(self.encode_drop_obligations(*place, location)?);
+                continue;
+            }
+            let declared_ty = tcx.fn_sig(called_def_id).subst_identity().skip_binder().inputs()[index];
+            let is_generic = declared_ty.walk().any(|generic_arg| matches!(
+                generic_arg.unpack(),
+                ty::subst::GenericArgKind::Type(arg_ty) if matches!(arg_ty.kind(), ty::TyKind::Param(_))
+            ));
+            if is_generic {
+                let span = self.mir_encoder.get_span_of_location(location);
+                let mut error = SpannedEncodingError::unsupported(
+                    format!(
+                        "the preconditions of `Drop::drop` for `{adt_ty}` cannot be checked when \
+                        a `{ty}` is moved into the generic function `{full_func_proc_name}`"
+                    ),
+                    span,
+                );
+                error.set_help(
+                    "pass the value to `std::mem::drop` or let it go out of scope instead"
+                );
+                return Err(error);
+            }
+        }
+        Ok(stmts)
+    }
+
+    /// Reports an error if the value of type `ty` that is dropped together
+    /// with `place` might be initialized and its drop glue calls a `drop`
+    /// method with preconditions.
+    fn check_drop_glue_supported(
+        &self,
+        place: mir::Place<'tcx>,
+        ty: ty::Ty<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<()> {
+        let adt_ty = self.find_drop_with_preconditions(ty, &mut FxHashSet::default());
+        if let Some(adt_ty) = adt_ty {
+            if !self.is_place_maybe_initialized(place, location) {
+                return Ok(());
+            }
+            let span = self.mir_encoder.get_span_of_location(location);
+            let mut error = SpannedEncodingError::unsupported(
+                format!(
+                    "the preconditions of `Drop::drop` for `{adt_ty}` cannot be checked when it \
+                    is dropped as part of a `{ty}`"
+                ),
+                span,
+            );
+            error.set_help(format!(
+                "pass the `{adt_ty}` to `std::mem::drop` before the `{ty}` goes out of scope"
+            ));
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Returns a type with a `drop` method with preconditions that is dropped
+    /// by the drop glue of `ty`. The containers of other crates are assumed to
+    /// drop the values of their type arguments.
+    fn find_drop_with_preconditions(
+        &self,
+        ty: ty::Ty<'tcx>,
+        visited: &mut FxHashSet<ty::Ty<'tcx>>,
+    ) -> Option<ty::Ty<'tcx>> {
+        if !visited.insert(ty) {
+            return None;
+        }
+        let tcx = self.encoder.env().tcx();
+        match ty.kind() {
+            ty::TyKind::Adt(adt_def, substs) => {
+                if let Some(destructor) = adt_def.destructor(tcx) {
+                    if destructor.did.is_local()
+                        && self.drop_has_preconditions(destructor.did, substs)
+                    {
+                        return Some(ty);
+                    }
+                }
+                if adt_def.is_phantom_data() {
+                    None
+                } else if adt_def.did().is_local() {
+                    adt_def.all_fields().find_map(|field| {
+                        self.find_drop_with_preconditions(field.ty(tcx, substs), visited)
+                    })
+                } else {
+                    substs.types().find_map(|ty| self.find_drop_with_preconditions(ty, visited))
+                }
+            }
+            ty::TyKind::Tuple(types) => types
+                .iter()
+                .find_map(|ty| self.find_drop_with_preconditions(ty, visited)),
+            ty::TyKind::Array(ty, _) | ty::TyKind::Slice(ty) => {
+                self.find_drop_with_preconditions(*ty, visited)
+            }
+            ty::TyKind::Closure(_, substs) => substs
+                .as_closure()
+                .upvar_tys()
+                .find_map(|ty| self.find_drop_with_preconditions(ty, visited)),
+            _ => None,
+        }
+    }
+
+    fn drop_has_preconditions(
+        &self,
+        destructor_def_id: ProcedureDefId,
+        substs: SubstsRef<'tcx>,
+    ) -> bool {
+        self.encoder
+            .get_procedure_specs(destructor_def_id, substs)
+            .and_then(|spec| spec.pres.extract_with_selective_replacement().cloned())
+            .map_or(false, |pres| !pres.is_empty())
+    }
+
+    fn drop_has_postconditions(
+        &self,
+        destructor_def_id: ProcedureDefId,
+        substs: SubstsRef<'tcx>,
+    ) -> bool {
+        self.encoder
+            .get_procedure_specs(destructor_def_id, substs)
+            .and_then(|spec| spec.posts.extract_with_selective_replacement().cloned())
+            .map_or(false, |posts| !posts.is_empty())
+    }
+
+    /// Whether some part of `place` might be initialized before the
+    /// terminator at `location`.
+    fn is_place_maybe_initialized(
+        &self,
+        place: mir::Place<'tcx>,
+        location: mir::Location,
+    ) -> bool {
+        let tcx = self.encoder.env().tcx();
+        let (move_data, maybe_inits) = self
+            .maybe_initialized_places
+            .get_or_init(|| self.compute_maybe_initialized_places());
+        let Some(maybe_init) = maybe_inits.get(&location) else {
+            return true;
+        };
+        let path = match move_data.rev_lookup.find(place.as_ref()) {
+            LookupResult::Exact(path) | LookupResult::Parent(Some(path)) => path,
+            LookupResult::Parent(None) => return true,
+        };
+        let mut maybe_initialized = false;
+        on_all_children_bits(tcx, self.mir, move_data, path, |child| {
+            maybe_initialized |= maybe_init.contains(child);
+        });
+        maybe_initialized
+    }
+
+    /// Runs the maybe-initialized analysis of the compiler once for the
+    /// procedure and records its result before every terminator.
+    fn compute_maybe_initialized_places(&self) -> MaybeInitializedPlacesAtTerminators<'tcx> {
+        let tcx = self.encoder.env().tcx();
+        let param_env = tcx.param_env_reveal_all_normalized(self.proc_def_id);
+        let (_, move_data) = match MoveData::gather_moves(self.mir, tcx, param_env) {
+            Ok(move_data) => move_data,
+            Err((move_data, _)) => move_data,
+        };
+        let env = MoveDataParamEnv { move_data, param_env };
+        let mut flow_inits = MaybeInitializedPlaces::new(tcx, self.mir, &env)
+            .into_engine(tcx, self.mir)
+            .iterate_to_fixpoint()
+            .into_results_cursor(self.mir);
+        let mut maybe_inits = FxHashMap::default();
+        for block in self.mir.basic_blocks.indices() {
+            let location = self.mir.terminator_loc(block);
+            flow_inits.seek_before_primary_effect(location);
+            maybe_inits.insert(location, flow_inits.get().clone());
+        }
+        drop(flow_inits);
+        (env.move_data, maybe_inits)
+    }
+
+    /// Encodes the coercion of a shared reference into a reference to a trait
+    /// object. The trait object is opaque: calls of its methods only rely on
+    /// the contracts declared on the trait, which every implementation of the
+    /// trait has to satisfy. Since the object is only read, the value it was
+    /// created from does not need to be updated when the reference expires.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn encode_assign_trait_object(
+        &mut self,
+        encoded_lhs: vir::Expr,
+        ty: ty::Ty<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        let mut stmts = Vec::new();
+
+        let label = self.cfg_method.get_fresh_label_name();
+        stmts.push(vir::Stmt::label(label.clone()));
+
+        debug_assert!(ty.is_ref() && !ty.is_mutable_ptr());
+
+        stmts.extend(self.encode_havoc(&encoded_lhs).with_span(span)?);
+        let val_ref_field = self.encoder.encode_value_field(ty).with_span(span)?;
+        let object_expr = encoded_lhs.field(val_ref_field);
+        stmts.push(vir_stmt!{ inhale [vir::Expr::acc_permission(
+            object_expr.clone(),
+            vir::PermAmount::Write,
+        )]});
+        let object_perm = self.mir_encoder
+            .encode_place_predicate_permission(object_expr, vir::PermAmount::Read)
+            .unwrap();
+        stmts.push(vir_stmt!{ inhale [object_perm] });
+
+        // Store a label for permissions got back from the call
+        self.label_after_location.insert(location, label);
+
+        Ok(stmts)
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     fn encode_assign_sequence_len(
         &mut self,
@@ -6304,7 +7971,24 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     position: vir::Position::default(),
                 }));
 
-                let rhs = slice_types.len(self.encoder, encoded_place);
+                let rhs = slice_types.len(self.encoder, encoded_place.clone());
+
+                if place_ty.is_slice() {
+                    // Relate the length to the snapshot of the slice. Otherwise,
+                    // facts about `s.len()` stated in the specification (e.g. a
+                    // quantified precondition about the rows of a slice of
+                    // slices) cannot be used to discharge the bounds checks.
+                    let snap_place = self.encoder.patch_snapshots(
+                        vir::Expr::snap_app(encoded_place)
+                    ).with_span(span)?;
+                    let snap_len = self.encoder.encode_snapshot_slice_len(
+                        place_ty,
+                        snap_place,
+                    ).with_span(span)?;
+                    stmts.push(vir::Stmt::Inhale( vir::Inhale {
+                        expr: vir_expr!{ [ rhs.clone() ] == [ snap_len ] }
+                    }));
+                }
 
                 stmts.extend(
                     self.encode_copy_value_assign(
@@ -6968,6 +8652,19 @@ enum ArrayAccessKind {
     Mutable(Option<Borrow>, mir::Location),
 }
 
+/// The state in which the `prev_iter(..)` expressions of a loop invariant are
+/// evaluated
+#[derive(Debug)]
+enum PrevIterState {
+    /// On loop entry, the previous iteration is the current state
+    Current,
+    /// After an iteration, the state at the given label
+    Label(String),
+    /// When assuming the invariant for an arbitrary iteration, the previous
+    /// state is unconstrained
+    Unknown,
+}
+
 fn convert_loans_to_borrows(loans: &[facts::Loan]) -> Vec<Borrow> {
     loans.iter().map(|l| l.index().into()).collect()
 }
@@ -6990,9 +8687,86 @@ fn is_str(ty: ty::Ty<'_>) -> bool {
     }
 }
 
+// Returns the value of a string constant operand, such as a panic message.
+fn get_str_constant(operand: &mir::Operand<'_>) -> Option<String> {
+    let mir::Operand::Constant(constant) = operand else {
+        return None;
+    };
+    let mir::ConstantKind::Val(
+        mir::interpret::ConstValue::Slice { data, start, end },
+        _,
+    ) = constant.literal else {
+        return None;
+    };
+    let bytes = data.inner().inspect_with_uninit_and_ptr_outside_interpreter(start..end);
+    std::str::from_utf8(bytes).ok().map(|message| message.to_string())
+}
+
+/// Returns the source text between the delimiters of the call `name!(...)` of
+/// the macro `name`.
+fn get_macro_arguments<'a>(call: &'a str, name: &str) -> Option<&'a str> {
+    let arguments = call.trim().strip_prefix(name)?.trim_start().strip_prefix('!')?.trim_start();
+    let arguments = arguments.strip_prefix(&['(', '[', '{'][..])?;
+    arguments.strip_suffix(&[')', ']', '}'][..])
+}
+
+/// Splits the source text of the arguments of a macro call at the commas that
+/// are not nested in delimiters or literals, ignoring a trailing comma.
+fn split_macro_arguments(arguments: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    let mut chars = arguments.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '"' => {
+                // Skip the string literal
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '\'' => {
+                // Skip a character literal, but not a lifetime
+                let rest = &arguments[index + 1..];
+                let closing_quote = if rest.starts_with('\\') {
+                    rest.get(2..).and_then(|escaped| escaped.find('\'')).map(|end| end + 2)
+                } else {
+                    rest.chars()
+                        .next()
+                        .map(char::len_utf8)
+                        .filter(|&len| rest[len..].starts_with('\''))
+                };
+                if let Some(closing_quote) = closing_quote {
+                    while chars.next_if(|&(next, _)| next <= index + 1 + closing_quote).is_some() {}
+                }
+            }
+            ',' if depth == 0 => {
+                parts.push(arguments[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = arguments[start..].trim();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
 type PreconditionWeakening = RefinementCheckExpr;
 type PostconditionStrengthening = RefinementCheckExpr;
 struct RefinementCheckExpr {
-    spec_functions_span: MultiSpan,
-    refinement_check_expr: vir::Expr,
+    /// The name of the refined trait method, used for error reporting
+    trait_method_name: String,
+    /// One check per specification clause, together with the span of the clause
+    clause_checks: Vec<(MultiSpan, vir::Expr)>,
 }
@@ -0,0 +1,271 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::encoder::{
+    errors::{ErrorCtxt, SpannedEncodingError, SpannedEncodingResult, WithSpan},
+    high::types::HighTypeEncoderInterface,
+    mir::{pure::SpecificationEncoderInterface, specifications::SpecificationsInterface},
+    snapshot::interface::SnapshotEncoderInterface,
+    Encoder,
+};
+use prusti_common::config;
+use prusti_interface::{
+    specs::typed::ProcedureSpecification, utils::has_trait_bounds_type_cond_spec,
+};
+use prusti_rustc_interface::{
+    hir::{def_id::DefId, Mutability},
+    middle::ty,
+    span::{symbol::Symbol, Span},
+};
+use rustc_hash::FxHashMap;
+use vir_crate::polymorphic::{self as vir, ExprIterator, Successor};
+
+/// Encodes a method that checks that the type-conditional spec refinement of
+/// the trusted procedure `proc_def_id` for concrete types, such as
+/// `#[refine_spec(where T = u32, [...])]`, refines its generic contract.
+///
+/// A call from generic code uses the generic contract, even if the type
+/// parameters are later instantiated with the concrete types. Hence, the
+/// generic precondition has to imply the refined precondition, and the refined
+/// postcondition has to imply the generic postcondition, for all arguments of
+/// the concrete types.
+pub(super) struct RefinementCheckEncoder<'p, 'v: 'p, 'tcx: 'v> {
+    encoder: &'p Encoder<'v, 'tcx>,
+    proc_def_id: DefId,
+    span: Span,
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> RefinementCheckEncoder<'p, 'v, 'tcx> {
+    pub fn new(encoder: &'p Encoder<'v, 'tcx>, proc_def_id: DefId) -> Self {
+        let span = encoder.env().query.get_def_span(proc_def_id);
+        RefinementCheckEncoder {
+            encoder,
+            proc_def_id,
+            span,
+        }
+    }
+
+    /// Returns `None` if the procedure has no refinement for concrete types.
+    #[tracing::instrument(level = "debug", skip(self), fields(proc_def_id = ?self.proc_def_id))]
+    pub fn encode(&self) -> SpannedEncodingResult<Option<vir::CfgMethod>> {
+        let Some(spec_graph) = self.encoder.get_procedure_spec_graph(self.proc_def_id) else {
+            return Ok(None);
+        };
+        // Only a single refinement is supported, see `SpecGraph`
+        let Some(refined_spec) = spec_graph.specs_with_constraints.values().next() else {
+            return Ok(None);
+        };
+        let base_spec = &spec_graph.base_spec;
+        let Some(substs) = self.encode_concrete_substs(refined_spec) else {
+            return Ok(None);
+        };
+
+        let env = self.encoder.env();
+        let sig = env
+            .query
+            .get_fn_sig_resolved(self.proc_def_id, substs, self.proc_def_id)
+            .skip_binder();
+        let mut locals = vec![];
+        let mut arg_bounds = vec![];
+        for (index, &arg_ty) in sig.inputs().iter().enumerate() {
+            // The check compares the snapshots of the arguments, which do not
+            // tell what a mutable reference points to after the call.
+            if let ty::TyKind::Ref(_, _, Mutability::Mut) = arg_ty.kind() {
+                return Err(SpannedEncodingError::unsupported(
+                    "type-conditional spec refinements with type equality constraints of \
+                    functions with mutable reference arguments are not supported",
+                    self.span,
+                ));
+            }
+            let local = vir::LocalVar::new(
+                format!("_{}", index + 1),
+                self.encoder
+                    .encode_snapshot_type(arg_ty)
+                    .with_span(self.span)?,
+            );
+            arg_bounds.extend(self.encode_value_range(&local, arg_ty));
+            locals.push(local);
+        }
+        let return_ty = sig.output();
+        let return_local = vir::LocalVar::new(
+            "_0",
+            self.encoder
+                .encode_snapshot_type(return_ty)
+                .with_span(self.span)?,
+        );
+        let return_bounds = self.encode_value_range(&return_local, return_ty);
+        let encoded_args: Vec<vir::Expr> = locals.iter().cloned().map(vir::Expr::local).collect();
+        let encoded_return = vir::Expr::local(return_local.clone());
+
+        let pres = |spec: &ProcedureSpecification| {
+            spec.pres
+                .expect_empty_or_inherent()
+                .cloned()
+                .unwrap_or_default()
+        };
+        let posts = |spec: &ProcedureSpecification| {
+            spec.posts
+                .expect_empty_or_inherent()
+                .cloned()
+                .unwrap_or_default()
+        };
+        let encode_assertion = |spec_id: &DefId, target_return: Option<&vir::Expr>| {
+            self.encoder.encode_assertion(
+                spec_id,
+                None,
+                &encoded_args,
+                target_return,
+                true,
+                self.proc_def_id,
+                substs,
+            )
+        };
+
+        let mut stmts = vec![
+            vir::Stmt::comment("========== type-conditional spec refinement check =========="),
+            vir::Stmt::comment(format!(
+                "Def path: {:?}",
+                env.name.get_item_def_path(self.proc_def_id)
+            )),
+            vir::Stmt::Inhale(vir::Inhale {
+                expr: arg_bounds.into_iter().conjoin(),
+            }),
+        ];
+
+        // The generic precondition has to imply the refined one
+        let base_pres = pres(base_spec)
+            .iter()
+            .map(|spec_id| encode_assertion(spec_id, None))
+            .collect::<Result<Vec<_>, _>>()?;
+        stmts.push(vir::Stmt::Inhale(vir::Inhale {
+            expr: base_pres.into_iter().conjoin(),
+        }));
+        for spec_id in pres(refined_spec).iter() {
+            stmts.push(self.encode_clause_check(
+                *spec_id,
+                encode_assertion(spec_id, None)?,
+                ErrorCtxt::AssertTypeCondSpecPreconditionWeakening,
+            ));
+        }
+
+        // The refined postcondition has to imply the generic one
+        let refined_posts = posts(refined_spec)
+            .iter()
+            .map(|spec_id| encode_assertion(spec_id, Some(&encoded_return)))
+            .collect::<Result<Vec<_>, _>>()?;
+        stmts.push(vir::Stmt::Inhale(vir::Inhale {
+            expr: return_bounds.into_iter().chain(refined_posts).conjoin(),
+        }));
+        for spec_id in posts(base_spec).iter() {
+            stmts.push(self.encode_clause_check(
+                *spec_id,
+                encode_assertion(spec_id, Some(&encoded_return))?,
+                ErrorCtxt::AssertTypeCondSpecPostconditionStrengthening,
+            ));
+        }
+
+        let mut cfg_method = vir::CfgMethod::new(
+            // method name
+            format!(
+                "{}$type_cond_spec_refinement",
+                self.encoder.encode_item_name(self.proc_def_id)
+            ),
+            // formal returns
+            vec![],
+            // local vars
+            vec![],
+            // reserved labels
+            vec![],
+        );
+        for local in locals.iter().chain(std::iter::once(&return_local)) {
+            cfg_method.add_local_var(&local.name, local.typ.clone());
+        }
+        let check_cfg_block = cfg_method.add_block("check", stmts);
+        cfg_method.set_successor(check_cfg_block, Successor::Return);
+
+        let cfg_method = self
+            .encoder
+            .patch_snapshots_method(cfg_method)
+            .with_span(self.span)?;
+        Ok(Some(cfg_method))
+    }
+
+    /// Substitutes the generic parameters that the refinement constrains to be
+    /// equal to concrete types. Returns `None` if there is no such constraint.
+    fn encode_concrete_substs(
+        &self,
+        refined_spec: &ProcedureSpecification,
+    ) -> Option<ty::subst::SubstsRef<'tcx>> {
+        let env = self.encoder.env();
+        let tcx = env.tcx();
+        // All spec functions of the refinement have the same constraints
+        let spec_id = refined_spec
+            .pres
+            .expect_empty_or_inherent()
+            .into_iter()
+            .chain(refined_spec.posts.expect_empty_or_inherent())
+            .flatten()
+            .copied()
+            .find(|spec_id| has_trait_bounds_type_cond_spec(env.query.get_attributes(*spec_id)))?;
+        // An equality constraint `T = U` is encoded as the bound
+        // `T: prusti_contracts::SameType<U>`
+        let equalities: FxHashMap<Symbol, ty::Ty<'tcx>> = tcx
+            .param_env(spec_id)
+            .caller_bounds()
+            .iter()
+            .filter_map(|predicate| predicate.to_opt_poly_trait_pred())
+            .map(|trait_pred| tcx.erase_late_bound_regions(trait_pred).trait_ref)
+            .filter(|trait_ref| {
+                env.name.get_absolute_item_name(trait_ref.def_id) == "prusti_contracts::SameType"
+            })
+            .filter_map(|trait_ref| match trait_ref.self_ty().kind() {
+                ty::TyKind::Param(param) => Some((param.name, trait_ref.substs.type_at(1))),
+                _ => None,
+            })
+            .collect();
+        if equalities.is_empty() {
+            return None;
+        }
+        Some(ty::List::for_item(tcx, self.proc_def_id, |param, _| {
+            equalities
+                .get(&param.name)
+                .map(|&ty| ty.into())
+                .unwrap_or_else(|| tcx.mk_param_from_def(param))
+        }))
+    }
+
+    /// Encodes the range of values of the type `ty`, as for the arguments of
+    /// pure functions.
+    fn encode_value_range(&self, local: &vir::LocalVar, ty: ty::Ty<'tcx>) -> Vec<vir::Expr> {
+        if config::check_overflows() {
+            self.encoder
+                .encode_type_bounds(&vir::Expr::local(local.clone()), ty)
+        } else if config::encode_unsigned_num_constraint()
+            && matches!(ty.kind(), ty::TyKind::Uint(_))
+        {
+            vec![vir::Expr::le_cmp(0u32.into(), local.clone().into())]
+        } else {
+            vec![]
+        }
+    }
+
+    fn encode_clause_check(
+        &self,
+        spec_id: DefId,
+        clause: vir::Expr,
+        error_ctxt: ErrorCtxt,
+    ) -> vir::Stmt {
+        let span = self.encoder.env().query.get_def_span(spec_id);
+        let position =
+            self.encoder
+                .error_manager()
+                .register_error(span, error_ctxt, self.proc_def_id);
+        vir::Stmt::Assert(vir::Assert {
+            expr: clause,
+            position,
+        })
+    }
+}
@@ -60,6 +60,9 @@ pub(super) struct SnapshotEncoder {
 
     /// Interning table for domains.
     domains: FxHashMap<String, vir::Domain>,
+
+    /// Number of variables created to quantify over the indices of slices.
+    slice_idx_vars: usize,
 }
 
 /// Snapshot encoding flattens references and boxes. This function removes any
@@ -552,6 +555,84 @@ impl SnapshotEncoder {
         }
     }
 
+    /// Encodes `slice.contains(elem)`, i.e. that some element of the slice
+    /// is equal to `elem`.
+    pub fn encode_slice_contains<'p, 'v: 'p, 'tcx: 'v>(
+        &mut self,
+        encoder: &'p Encoder<'v, 'tcx>,
+        slice_ty: ty::Ty<'tcx>,
+        slice: Expr,
+        elem: Expr,
+    ) -> EncodingResult<Expr> {
+        let (read, len) = self.encode_slice_read_and_len(encoder, slice_ty)?;
+        let i = self.fresh_slice_idx_var();
+        let i_expr: Expr = i.clone().into();
+        let slice_i = read.apply(vec![slice.clone(), i_expr.clone()]);
+        let slice_len = len.apply(vec![slice]);
+        let elem = Expr::snap_app(elem);
+        // exists i: Int :: { read(slice, i) } 0 <= i && i < len(slice) && read(slice, i) == elem
+        Ok(Expr::exists(
+            vec![i],
+            vec![vir::Trigger::new(vec![slice_i.clone()])],
+            vir_expr! { (([Expr::from(0)] <= [i_expr]) && ([i_expr] < [slice_len])) && ([slice_i] == [elem]) },
+        ))
+    }
+
+    /// Encodes `slice.starts_with(affix)`, or `slice.ends_with(affix)` if
+    /// `at_end` is set, i.e. that the elements of `affix` are equal to the
+    /// first (or last) elements of the slice.
+    pub fn encode_slice_affix<'p, 'v: 'p, 'tcx: 'v>(
+        &mut self,
+        encoder: &'p Encoder<'v, 'tcx>,
+        slice_ty: ty::Ty<'tcx>,
+        slice: Expr,
+        affix: Expr,
+        at_end: bool,
+    ) -> EncodingResult<Expr> {
+        let (read, len) = self.encode_slice_read_and_len(encoder, slice_ty)?;
+        let i = self.fresh_slice_idx_var();
+        let i_expr: Expr = i.clone().into();
+        let slice_len = len.apply(vec![slice.clone()]);
+        let affix_len = len.apply(vec![affix.clone()]);
+        let slice_idx = if at_end {
+            vir_expr! { ([slice_len] - [affix_len]) + [i_expr] }
+        } else {
+            i_expr.clone()
+        };
+        let slice_i = read.apply(vec![slice, slice_idx]);
+        let affix_i = read.apply(vec![affix, i_expr.clone()]);
+        // len(affix) <= len(slice) &&
+        // forall i: Int :: { read(affix, i) } 0 <= i && i < len(affix) ==> read(slice, idx) == read(affix, i)
+        let elems_eq = Expr::forall(
+            vec![i],
+            vec![vir::Trigger::new(vec![affix_i.clone()])],
+            vir_expr! { (([Expr::from(0)] <= [i_expr]) && ([i_expr] < [affix_len])) ==> ([slice_i] == [affix_i]) },
+        );
+        Ok(vir_expr! { ([affix_len] <= [slice_len]) && [elems_eq] })
+    }
+
+    fn encode_slice_read_and_len<'p, 'v: 'p, 'tcx: 'v>(
+        &mut self,
+        encoder: &'p Encoder<'v, 'tcx>,
+        slice_ty: ty::Ty<'tcx>,
+    ) -> EncodingResult<(vir::DomainFunc, vir::DomainFunc)> {
+        if let Snapshot::Slice { read, len, .. } = self.encode_snapshot(encoder, slice_ty)? {
+            Ok((read, len))
+        } else {
+            error_internal!(
+                "called encode_slice_read_and_len on non-slice-type {:?}",
+                slice_ty
+            );
+        }
+    }
+
+    /// Returns a fresh variable to quantify over the indices of a slice, so
+    /// that nested slice queries do not shadow each other's variables.
+    fn fresh_slice_idx_var(&mut self) -> vir::LocalVar {
+        self.slice_idx_vars += 1;
+        vir::LocalVar::new(format!("__slice_idx_{}", self.slice_idx_vars), Type::Int)
+    }
+
     /// Encodes the snapshot of the given type and returns a VIR type
     /// representing that snapshot.
     pub fn encode_type<'p, 'v: 'p, 'tcx: 'v>(
@@ -576,6 +657,13 @@ impl SnapshotEncoder {
         ty: ty::Ty<'tcx>,
     ) -> EncodingResult<Snapshot> {
         let ty = strip_refs_and_boxes(ty);
+        // A string slice has the same encoding as the slice of its bytes.
+        let ty = if ty.is_str() {
+            let tcx = encoder.env().tcx();
+            tcx.mk_slice(tcx.types.u8)
+        } else {
+            ty
+        };
         let predicate_type = encoder.encode_type(ty)?;
 
         // was the snapshot for the type already encoded?
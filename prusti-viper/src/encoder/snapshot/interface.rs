@@ -71,6 +71,19 @@ pub(crate) trait SnapshotEncoderInterface<'tcx> {
         lo: vir_poly::Expr,
         hi: vir_poly::Expr,
     ) -> EncodingResult<vir_poly::Expr>;
+    fn encode_snapshot_slice_contains(
+        &self,
+        ty: ty::Ty<'tcx>,
+        slice: vir_poly::Expr,
+        elem: vir_poly::Expr,
+    ) -> EncodingResult<vir_poly::Expr>;
+    fn encode_snapshot_slice_affix(
+        &self,
+        ty: ty::Ty<'tcx>,
+        slice: vir_poly::Expr,
+        affix: vir_poly::Expr,
+        at_end: bool,
+    ) -> EncodingResult<vir_poly::Expr>;
     fn supports_snapshot_equality(&self, ty: ty::Ty<'tcx>) -> EncodingResult<bool>;
     fn is_quantifiable(&self, ty: ty::Ty<'tcx>) -> EncodingResult<bool>;
 }
@@ -150,6 +163,17 @@ impl<'v, 'tcx: 'v> SnapshotEncoderInterface<'tcx> for super::super::Encoder<'v,
     ) -> EncodingResult<vir_poly::Expr> {
         let args = match expr.ty().kind() {
             ty::TyKind::Tuple(substs) if substs.is_empty() => vec![],
+            // A string literal is encoded as the sequence of its bytes.
+            ty::TyKind::Ref(_, inner_ty, _) if inner_ty.is_str() => {
+                let bytes = self.const_eval_str_bytes(expr.literal)?;
+                vec![vir_poly::Expr::Seq(vir_poly::Seq {
+                    typ: vir_poly::Type::Seq(vir_poly::SeqType {
+                        typ: Box::new(vir_poly::Type::Int),
+                    }),
+                    elements: bytes.into_iter().map(vir_poly::Expr::from).collect(),
+                    position: vir_poly::Position::default(),
+                })]
+            }
             _ => {
                 vec![self.encode_const_expr(expr.ty(), expr.literal)?]
             }
@@ -231,6 +255,31 @@ impl<'v, 'tcx: 'v> SnapshotEncoderInterface<'tcx> for super::super::Encoder<'v,
             .encode_slicing(self, base_ty, base, slice_ty, lo, hi)
     }
 
+    fn encode_snapshot_slice_contains(
+        &self,
+        ty: ty::Ty<'tcx>,
+        slice: vir_poly::Expr,
+        elem: vir_poly::Expr,
+    ) -> EncodingResult<vir_poly::Expr> {
+        self.snapshot_encoder_state
+            .encoder
+            .borrow_mut()
+            .encode_slice_contains(self, ty, slice, elem)
+    }
+
+    fn encode_snapshot_slice_affix(
+        &self,
+        ty: ty::Ty<'tcx>,
+        slice: vir_poly::Expr,
+        affix: vir_poly::Expr,
+        at_end: bool,
+    ) -> EncodingResult<vir_poly::Expr> {
+        self.snapshot_encoder_state
+            .encoder
+            .borrow_mut()
+            .encode_slice_affix(self, ty, slice, affix, at_end)
+    }
+
     fn supports_snapshot_equality(&self, ty: ty::Ty<'tcx>) -> EncodingResult<bool> {
         self.snapshot_encoder_state
             .encoder
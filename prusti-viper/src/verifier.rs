@@ -17,7 +17,7 @@ use prusti_common::{
     Stopwatch,
 };
 use prusti_interface::{
-    data::{VerificationResult, VerificationTask},
+    data::{ProcedureDefId, VerificationResult, VerificationTask},
     environment::Environment,
     specs::typed,
     PrustiError,
@@ -27,6 +27,11 @@ use prusti_server::{
     process_verification_request, spawn_server_thread, tokio::runtime::Builder, PrustiClient,
     VerificationRequest, ViperBackendConfig,
 };
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
 use viper::{self, PersistentCache, Viper};
 use vir_crate::common::check_mode::CheckMode;
 
@@ -38,13 +43,17 @@ where
 {
     env: &'v Environment<'tcx>,
     encoder: Encoder<'v, 'tcx>,
+    /// The specifications, kept to re-verify the crate with modified
+    /// contracts when computing the contract coverage.
+    def_spec: typed::DefSpecificationMap,
 }
 
 impl<'v, 'tcx> Verifier<'v, 'tcx> {
     pub fn new(env: &'v Environment<'tcx>, def_spec: typed::DefSpecificationMap) -> Self {
         Verifier {
             env,
-            encoder: Encoder::new(env, def_spec),
+            encoder: Encoder::new(env, def_spec.clone()),
+            def_spec,
         }
     }
 
@@ -64,44 +73,21 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
             info!(" - {} ({})", proc_name, proc_def_path);
             info!("   Source: {:?}", proc_span);
         }
-        for &proc_id in task.procedures.iter().rev() {
-            // FIXME: Use the loop above.
-            self.encoder.queue_procedure_encoding(proc_id);
-        }
-        for &type_id in task.types.iter().rev() {
-            // FIXME: Use the loop above.
-            self.encoder.queue_type_encoding(type_id);
-        }
-        self.encoder.process_encoding_queue();
-
+        let programs = encode_task(&mut self.encoder, task, &mut stopwatch);
         let encoding_errors_count = self.encoder.count_encoding_errors();
-
-        let polymorphic_programs = self.encoder.get_viper_programs();
-
-        let mut programs: Vec<Program> = if config::simplify_encoding() {
-            stopwatch.start_next("optimizing Viper program");
-            let source_file_name = self.encoder.env().name.source_file_name();
-            polymorphic_programs
-                .into_iter()
-                .map(|program| Program::Legacy(optimize_program(program, &source_file_name).into()))
-                .collect()
-        } else {
-            polymorphic_programs
-                .into_iter()
-                .map(|program| Program::Legacy(program.into()))
-                .collect()
-        };
-        programs.extend(self.encoder.get_core_proof_programs());
+        let assert_timeouts = assert_timeout_overrides(self.env, task);
 
         stopwatch.start_next("verifying Viper program");
-        let verification_results = verify_programs(self.env, programs);
+        let verification_results = verify_programs(self.env, programs, &assert_timeouts);
         stopwatch.finish();
 
         // Group verification results
         let mut verification_errors: Vec<_> = vec![];
         let mut consistency_errors: Vec<_> = vec![];
         let mut java_exceptions: Vec<_> = vec![];
-        for (method_name, result) in verification_results.into_iter() {
+        let mut outcomes: FxHashMap<String, ProgramOutcome> = FxHashMap::default();
+        for (method_name, result, duration) in verification_results.into_iter() {
+            outcomes.entry(method_name.clone()).or_default().duration += duration;
             match result {
                 viper::VerificationResult::Success => {}
                 viper::VerificationResult::ConsistencyErrors(errors) => {
@@ -125,25 +111,67 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
         let mut result = VerificationResult::Success;
 
         for (method, error) in consistency_errors.into_iter() {
-            PrustiError::internal(
+            let prusti_error = PrustiError::internal(
                 format!("consistency error in {method}: {error}"),
                 DUMMY_SP.into(),
-            )
-            .emit(&self.env.diagnostic);
+            );
+            outcomes.entry(method).or_default().record(&prusti_error);
+            prusti_error.emit(&self.env.diagnostic);
             result = VerificationResult::Failure;
         }
 
         for (method, exception) in java_exceptions.into_iter() {
             error!("Java exception: {}", exception.get_stack_trace());
-            PrustiError::internal(format!("in {method}: {exception}"), DUMMY_SP.into())
-                .emit(&self.env.diagnostic);
+            let prusti_error =
+                PrustiError::internal(format!("in {method}: {exception}"), DUMMY_SP.into());
+            outcomes.entry(method).or_default().record(&prusti_error);
+            prusti_error.emit(&self.env.diagnostic);
             result = VerificationResult::Failure;
         }
 
         // Report verification errors
         let mut prusti_errors: Vec<_> = vec![];
+        let mut unreachability_warnings: Vec<_> = vec![];
+        let unreachable_bodies: FxHashSet<_> = verification_errors
+            .iter()
+            .filter(|(_, error)| error_manager.is_unreachable_body(error))
+            .map(|(method, _)| method.clone())
+            .collect();
+        let unreachable_assumptions: FxHashSet<_> = verification_errors
+            .iter()
+            .filter_map(|(_, error)| error_manager.get_unreachable_assumption(error))
+            .collect();
         for (method, verification_error) in verification_errors.into_iter() {
             debug!("Verification error in {}: {:?}", method, verification_error);
+            if error_manager
+                .get_unreachable_assumption(&verification_error)
+                .is_some()
+            {
+                // Only checks whether the code after an assumption is
+                // unreachable because of the assumption itself.
+                continue;
+            }
+            if let Some(reachability_check) =
+                error_manager.get_vacuous_assumption(&verification_error)
+            {
+                if !unreachable_assumptions.contains(&reachability_check)
+                    && !unreachable_bodies.contains(&method)
+                {
+                    unreachability_warnings
+                        .push(error_manager.translate_verification_error(&verification_error));
+                }
+                continue;
+            }
+            let is_unreachable_body = error_manager.is_unreachable_body(&verification_error);
+            if is_unreachable_body || error_manager.is_unreachable_branch(&verification_error) {
+                // All branches of a method with an unsatisfiable precondition
+                // are unreachable; only the precondition is reported.
+                if is_unreachable_body || !unreachable_bodies.contains(&method) {
+                    unreachability_warnings
+                        .push(error_manager.translate_verification_error(&verification_error));
+                }
+                continue;
+            }
             let mut prusti_error = error_manager.translate_verification_error(&verification_error);
 
             // annotate with counterexample, if requested
@@ -187,6 +215,9 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
                 }
             }
 
+            if !prusti_error.is_disabled() {
+                outcomes.entry(method).or_default().record(&prusti_error);
+            }
             prusti_errors.push(prusti_error);
         }
         prusti_errors.sort();
@@ -201,20 +232,272 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
             result = VerificationResult::Failure;
         }
 
+        // Unreachable branches and vacuous assumptions are reported as warnings
+        // and do not make the verification fail.
+        unreachability_warnings.sort();
+        for warning in unreachability_warnings {
+            warning.emit(&self.env.diagnostic);
+        }
+
         if encoding_errors_count != 0 {
             result = VerificationResult::Failure;
         }
 
+        if let Some(dir) = config::verification_report() {
+            let contract_coverage =
+                if config::contract_coverage() && result == VerificationResult::Success {
+                    Some(self.compute_contract_coverage(task))
+                } else {
+                    None
+                };
+            self.report_verification_outcomes(task, &outcomes, contract_coverage.as_ref(), &dir);
+        }
+
         result
     }
+
+    /// Writes a JSON report with the status, the verification time and the
+    /// errors of every verified procedure, and its contract clauses if the
+    /// contract coverage has been computed, to the file
+    /// `<crate>.verification.json` in the directory `dir`.
+    fn report_verification_outcomes(
+        &self,
+        task: &VerificationTask<'tcx>,
+        outcomes: &FxHashMap<String, ProgramOutcome>,
+        contract_coverage: Option<&FxHashMap<ProcedureDefId, Vec<serde_json::Value>>>,
+        dir: &Path,
+    ) {
+        let source_map = self.env.tcx().sess.source_map();
+        let mut procedures = vec![];
+        for &proc_id in &task.procedures {
+            // Legacy programs are named by the unique name of the procedure,
+            // core proof programs by its absolute name.
+            let absolute_name = self.env.name.get_absolute_item_name(proc_id);
+            let proc_outcomes: Vec<_> = [
+                self.env.name.get_unique_item_name(proc_id),
+                absolute_name.clone(),
+            ]
+            .iter()
+            .filter_map(|name| outcomes.get(name))
+            .collect();
+            // A procedure without a program could not be encoded, which has
+            // been reported as an error of the crate.
+            let status = if proc_outcomes.is_empty() {
+                "not verified"
+            } else if proc_outcomes
+                .iter()
+                .all(|outcome| outcome.errors.is_empty())
+            {
+                "verified"
+            } else {
+                "failed"
+            };
+            let duration: Duration = proc_outcomes.iter().map(|outcome| outcome.duration).sum();
+            let errors: Vec<_> = proc_outcomes
+                .iter()
+                .flat_map(|outcome| outcome.errors.iter().cloned())
+                .collect();
+            let span = self.env.query.get_def_span(proc_id);
+            let location = source_map.lookup_char_pos(span.lo());
+            let mut procedure = serde_json::json!({
+                "function": absolute_name,
+                "location": format!(
+                    "{}:{}:{}",
+                    location.file.name.prefer_local(),
+                    location.line,
+                    location.col.0 + 1
+                ),
+                "status": status,
+                "duration_ms": duration.as_millis() as u64,
+                "errors": errors,
+            });
+            if let Some(contract_coverage) = contract_coverage {
+                procedure["clauses"] = contract_coverage
+                    .get(&proc_id)
+                    .cloned()
+                    .unwrap_or_default()
+                    .into();
+            }
+            procedures.push(procedure);
+        }
+
+        let path = dir.join(format!(
+            "{}.verification.json",
+            self.env.name.local_crate_filename()
+        ));
+        let write_report = || -> std::io::Result<()> {
+            std::fs::create_dir_all(dir)?;
+            let file = std::fs::File::create(&path)?;
+            serde_json::to_writer_pretty(
+                file,
+                &serde_json::json!({
+                    "crate": self.env.name.local_crate_name(),
+                    "procedures": procedures,
+                }),
+            )?;
+            Ok(())
+        };
+        if let Err(error) = write_report() {
+            PrustiError::internal(
+                format!(
+                    "could not write the verification report to {}: {error}",
+                    path.display()
+                ),
+                DUMMY_SP.into(),
+            )
+            .emit(&self.env.diagnostic);
+        }
+    }
+
+    /// Finds the contract clauses of the verified procedures that are not
+    /// needed by any proof, by verifying the task again once for every clause
+    /// with only that clause removed. Returns the clauses of every procedure.
+    /// The errors of these verifications are not meant for the user and are
+    /// not reported.
+    fn compute_contract_coverage(
+        &self,
+        task: &VerificationTask<'tcx>,
+    ) -> FxHashMap<ProcedureDefId, Vec<serde_json::Value>> {
+        let mut stopwatch = Stopwatch::start("prusti-viper", "contract coverage");
+        let tcx = self.env.tcx();
+        let source_map = tcx.sess.source_map();
+        let mut clauses = vec![];
+        for &proc_id in &task.procedures {
+            let Some(spec_graph) = self.def_spec.get_proc_spec(&proc_id) else {
+                continue;
+            };
+            let proc_spec = &spec_graph.base_spec;
+            for (kind, item) in [
+                ("precondition", &proc_spec.pres),
+                ("postcondition", &proc_spec.posts),
+            ] {
+                if let Some(specs) = item.extract_with_selective_replacement() {
+                    clauses.extend(specs.iter().map(|&spec| (proc_id, kind, spec)));
+                }
+            }
+        }
+        for (&spec, loop_spec) in &self.def_spec.loop_specs {
+            let proc_id = tcx.typeck_root_def_id(spec);
+            if matches!(loop_spec, typed::LoopSpecification::Invariant(_))
+                && task.procedures.contains(&proc_id)
+            {
+                clauses.push((proc_id, "loop_invariant", spec));
+            }
+        }
+        clauses.sort_by_key(|&(_, _, spec)| self.env.query.get_def_span(spec));
+        clauses.dedup_by_key(|&mut (_, _, spec)| spec);
+
+        let assert_timeouts = assert_timeout_overrides(self.env, task);
+        let mut coverage: FxHashMap<ProcedureDefId, Vec<serde_json::Value>> = FxHashMap::default();
+        for (proc_id, kind, spec) in clauses {
+            stopwatch.start_next(format!("verifying without {spec:?}"));
+            let used = self.env.diagnostic.with_suppressed_diagnostics(|| {
+                let mut encoder = Encoder::new(self.env, self.def_spec.without_spec(spec));
+                let programs = encode_task(&mut encoder, task, &mut stopwatch);
+                // If removing the clause leads to encoding errors, we cannot
+                // tell whether it is needed and conservatively consider it used.
+                encoder.count_encoding_errors() != 0
+                    || verify_programs(self.env, programs, &assert_timeouts)
+                        .into_iter()
+                        .any(|(_, result, _)| !matches!(result, viper::VerificationResult::Success))
+            });
+            let span = self.env.query.get_def_span(spec);
+            let location = source_map.lookup_char_pos(span.lo());
+            coverage
+                .entry(proc_id)
+                .or_default()
+                .push(serde_json::json!({
+                    "kind": kind,
+                    "clause": source_map.span_to_snippet(span).unwrap_or_default(),
+                    "location": format!(
+                        "{}:{}:{}",
+                        location.file.name.prefer_local(),
+                        location.line,
+                        location.col.0 + 1
+                    ),
+                    "used": used,
+                }));
+        }
+        stopwatch.finish();
+        coverage
+    }
+}
+
+/// The time spent verifying a Viper program and the errors reported for it,
+/// which are collected for the verification report.
+#[derive(Default)]
+struct ProgramOutcome {
+    duration: Duration,
+    errors: Vec<serde_json::Value>,
+}
+
+impl ProgramOutcome {
+    fn record(&mut self, error: &PrustiError) {
+        self.errors.push(serde_json::json!({
+            "message": error.message(),
+            "notes": error.notes().collect::<Vec<_>>(),
+        }));
+    }
+}
+
+/// Encode the procedures and types of the task, and return the resulting
+/// Viper programs.
+fn encode_task<'v, 'tcx: 'v>(
+    encoder: &mut Encoder<'v, 'tcx>,
+    task: &VerificationTask<'tcx>,
+    stopwatch: &mut Stopwatch,
+) -> Vec<Program> {
+    for &proc_id in task.procedures.iter().rev() {
+        encoder.queue_procedure_encoding(proc_id);
+    }
+    for &type_id in task.types.iter().rev() {
+        encoder.queue_type_encoding(type_id);
+    }
+    encoder.process_encoding_queue();
+
+    let polymorphic_programs = encoder.get_viper_programs();
+
+    let mut programs: Vec<Program> = if config::simplify_encoding() {
+        stopwatch.start_next("optimizing Viper program");
+        let source_file_name = encoder.env().name.source_file_name();
+        polymorphic_programs
+            .into_iter()
+            .map(|program| Program::Legacy(optimize_program(program, &source_file_name).into()))
+            .collect()
+    } else {
+        polymorphic_programs
+            .into_iter()
+            .map(|program| Program::Legacy(program.into()))
+            .collect()
+    };
+    programs.extend(encoder.get_core_proof_programs());
+    programs
+}
+
+/// Collect the assertion timeouts of the procedures of the task that override
+/// the configured one, indexed by the names of the Viper programs that encode
+/// the procedures.
+fn assert_timeout_overrides(env: &Environment, task: &VerificationTask) -> FxHashMap<String, u64> {
+    let mut timeouts = FxHashMap::default();
+    for &proc_id in &task.procedures {
+        if let Some(timeout) = env.get_assert_timeout_override(proc_id) {
+            // Legacy programs are named by the unique name of the procedure,
+            // core proof programs by its absolute name.
+            timeouts.insert(env.name.get_unique_item_name(proc_id), timeout);
+            timeouts.insert(env.name.get_absolute_item_name(proc_id), timeout);
+        }
+    }
+    timeouts
 }
 
-/// Verify a list of programs.
-/// Returns a list of (program_name, verification_result) tuples.
+/// Verify a list of programs, using the given assertion timeouts for the
+/// programs with the corresponding names.
+/// Returns a list of (program_name, verification_result, duration) tuples.
 fn verify_programs(
     env: &Environment,
     programs: Vec<Program>,
-) -> Vec<(String, viper::VerificationResult)> {
+    assert_timeouts: &FxHashMap<String, u64>,
+) -> Vec<(String, viper::VerificationResult, Duration)> {
     let source_path = env.name.source_path();
     let rust_program_name = source_path
         .file_name()
@@ -234,9 +517,15 @@ fn verify_programs(
         }
         .parse()
         .unwrap();
+        let backend_config = match assert_timeouts.get(&program_name) {
+            Some(&assert_timeout) => {
+                ViperBackendConfig::with_assert_timeout(backend, assert_timeout)
+            }
+            None => ViperBackendConfig::new(backend),
+        };
         let request = VerificationRequest {
             program,
-            backend_config: ViperBackendConfig::new(backend),
+            backend_config,
         };
         (program_name, request)
     });
@@ -260,11 +549,12 @@ fn verify_programs(
             .expect("failed to construct Tokio runtime");
         verification_requests
             .map(|(program_name, request)| {
+                let start = Instant::now();
                 let remote_result = runtime.block_on(client.verify(request));
                 let result = remote_result.unwrap_or_else(|error| {
                     panic!("Verification request of program {program_name} failed: {error:?}")
                 });
-                (program_name, result)
+                (program_name, result, start.elapsed())
             })
             .collect()
     } else {
@@ -277,8 +567,9 @@ fn verify_programs(
         let mut cache = PersistentCache::load_cache(config::cache_path());
         verification_requests
             .map(|(program_name, request)| {
+                let start = Instant::now();
                 let result = process_verification_request(&viper_thread, request, &mut cache);
-                (program_name, result)
+                (program_name, result, start.elapsed())
             })
             .collect()
     }
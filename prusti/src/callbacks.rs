@@ -136,6 +136,9 @@ impl prusti_rustc_interface::driver::Callbacks for PrustiCompilerCalls {
                     println!("{value}");
                 }
             }
+            if let Some(dir) = config::trust_audit_report() {
+                specs::trust_audit::write_trust_audit_report(&env, &def_spec, &dir);
+            }
             CrossCrateSpecs::import_export_cross_crate(&mut env, &mut def_spec);
             if !config::no_verify() {
                 verify(env, def_spec);
@@ -134,6 +134,23 @@ fn init_loggers() -> Option<FlushGuard> {
     guard
 }
 
+/// Adds the `force` modifier to the `--extern` flag of the given crate, if the
+/// crate is passed to rustc, so that the crate is loaded even if the verified
+/// crate does not use it.
+fn force_load_extern(rustc_args: &mut Vec<String>, crate_name: &str) {
+    let prefix = format!("{crate_name}=");
+    let Some(index) = rustc_args
+        .windows(2)
+        .position(|p| p[0] == "--extern" && p[1].starts_with(&prefix))
+    else {
+        return;
+    };
+    rustc_args[index + 1] = format!("force:{}", rustc_args[index + 1]);
+    if !rustc_args.iter().any(|arg| arg == "-Zunstable-options") {
+        rustc_args.push("-Zunstable-options".to_owned());
+    }
+}
+
 fn main() {
     let stopwatch = Stopwatch::start("prusti", "main");
 
@@ -214,6 +231,22 @@ fn main() {
         rustc_args.push("-Zcrate-attr=feature(register_tool)".to_owned());
         rustc_args.push("-Zcrate-attr=register_tool(prusti)".to_owned());
 
+        if !config::no_verify() {
+            // Makes `is_verified_call!` evaluate to `true` in this crate.
+            rustc_args.push(format!("--cfg={}", prusti_specs::VERIFIED_CFG));
+        }
+
+        if config::strict_spec_attributes() {
+            // Turns ignored Prusti attributes into compilation errors.
+            rustc_args.push(format!("--cfg={}", prusti_specs::STRICT_ATTRIBUTES_CFG));
+        }
+
+        if config::load_std_specs() {
+            // Makes the specifications of `prusti_std` available without
+            // `extern crate prusti_std;` in the verified crate.
+            force_load_extern(&mut rustc_args, "prusti_std");
+        }
+
         if config::check_overflows() {
             // Some crates might have a `overflow-checks = false` in their `Cargo.toml` to
             // disable integer overflow checks, but we want to override that.
@@ -17,7 +17,15 @@ pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
         debug!("Prepare verification task...");
         // TODO: can we replace `get_annotated_procedures` with information
         // that is already in `def_spec`?
-        let (annotated_procedures, types) = env.get_annotated_procedures_and_types();
+        let (mut annotated_procedures, types) = env.get_annotated_procedures_and_types();
+        if config::verify_only_annotated() {
+            let all_procedures = annotated_procedures.len();
+            annotated_procedures.retain(|&procedure| def_spec.is_annotated(env.tcx(), procedure));
+            user::message(format!(
+                "Skipping {} items without specifications",
+                all_procedures - annotated_procedures.len()
+            ));
+        }
         let verification_task = VerificationTask {
             procedures: annotated_procedures,
             types,
@@ -81,5 +89,15 @@ pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
                 );
             }
         };
+
+        for &procedure in &verification_task.procedures {
+            if let Some(timeout) = env.get_assert_timeout_override(procedure) {
+                user::message(format!(
+                    "The assertion timeout of {} was overridden to {} ms",
+                    env.name.get_unique_item_name(procedure),
+                    timeout
+                ));
+            }
+        }
     }
 }
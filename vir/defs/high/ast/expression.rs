@@ -245,6 +245,8 @@ pub enum BuiltinFunc {
     SingleSeq,
     LookupSeq,
     ConcatSeq,
+    TakeSeq,
+    DropSeq,
     SeqLen,
     NewInt,
     Index,
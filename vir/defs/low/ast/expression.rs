@@ -177,6 +177,8 @@ pub enum ContainerOpKind {
     SeqConstructor,
     SeqIndex,
     SeqConcat,
+    SeqTake,
+    SeqDrop,
     SeqLen,
     MapEmpty,
     MapUpdate,
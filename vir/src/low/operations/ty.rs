@@ -181,6 +181,8 @@ impl Typed for ContainerOp {
             ContainerOpKind::SeqEmpty
             | ContainerOpKind::SeqConstructor
             | ContainerOpKind::SeqConcat
+            | ContainerOpKind::SeqTake
+            | ContainerOpKind::SeqDrop
             | ContainerOpKind::MapEmpty
             | ContainerOpKind::MapUpdate
             | ContainerOpKind::SetEmpty